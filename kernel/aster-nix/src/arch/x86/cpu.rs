@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use ostd::cpu::UserContext;
+use alloc::{format, string::String, vec::Vec};
+use core::arch::x86_64::{CpuidResult, __cpuid, __cpuid_count};
+
+use ostd::{arch::tsc_freq, cpu::UserContext};
 
 use crate::cpu::LinuxAbi;
 
@@ -36,3 +39,258 @@ impl LinuxAbi for UserContext {
         self.fsbase()
     }
 }
+
+/// The information of a CPU, which is reported to user space by `/proc/cpuinfo`.
+pub struct CpuInformation {
+    vendor_id: String,
+    cpu_family: u32,
+    model: u32,
+    stepping: u32,
+    model_name: String,
+    cpu_khz: u64,
+    flags: Vec<&'static str>,
+}
+
+impl CpuInformation {
+    /// Collects the information of the current CPU with `CPUID`.
+    pub fn new() -> Self {
+        // SAFETY: `CPUID` is available on all x86-64 CPUs.
+        let cpuid = |leaf| unsafe { __cpuid(leaf) };
+        // SAFETY: `CPUID` is available on all x86-64 CPUs.
+        let cpuid_count = |leaf, sub_leaf| unsafe { __cpuid_count(leaf, sub_leaf) };
+
+        let leaf_0 = cpuid(0);
+        let max_leaf = leaf_0.eax;
+        let vendor_id = registers_to_string(&[leaf_0.ebx, leaf_0.edx, leaf_0.ecx]);
+
+        let leaf_1 = cpuid(1);
+        let (cpu_family, model, stepping) = decode_signature(leaf_1.eax);
+
+        let max_extended_leaf = cpuid(0x8000_0000).eax;
+        let model_name = if max_extended_leaf >= 0x8000_0004 {
+            let words: Vec<u32> = (0x8000_0002..=0x8000_0004)
+                .flat_map(|leaf| {
+                    let CpuidResult { eax, ebx, ecx, edx } = cpuid(leaf);
+                    [eax, ebx, ecx, edx]
+                })
+                .collect();
+            String::from(registers_to_string(&words).trim())
+        } else {
+            String::from("unknown")
+        };
+
+        let mut flags = Vec::new();
+        collect_flags(&mut flags, leaf_1.edx, LEAF_1_EDX_FLAGS);
+        collect_flags(&mut flags, leaf_1.ecx, LEAF_1_ECX_FLAGS);
+        if max_extended_leaf >= 0x8000_0001 {
+            let extended_leaf_1 = cpuid(0x8000_0001);
+            collect_flags(&mut flags, extended_leaf_1.edx, EXTENDED_LEAF_1_EDX_FLAGS);
+            collect_flags(&mut flags, extended_leaf_1.ecx, EXTENDED_LEAF_1_ECX_FLAGS);
+        }
+        if max_leaf >= 7 {
+            let leaf_7 = cpuid_count(7, 0);
+            collect_flags(&mut flags, leaf_7.ebx, LEAF_7_EBX_FLAGS);
+            collect_flags(&mut flags, leaf_7.ecx, LEAF_7_ECX_FLAGS);
+        }
+
+        Self {
+            vendor_id,
+            cpu_family,
+            model,
+            stepping,
+            model_name,
+            cpu_khz: tsc_freq() / 1000,
+            flags,
+        }
+    }
+
+    /// Formats the information of the CPU with the processor ID in the format of `/proc/cpuinfo`.
+    pub fn format(&self, processor: u32) -> String {
+        format!(
+            "processor\t: {}\n\
+             vendor_id\t: {}\n\
+             cpu family\t: {}\n\
+             model\t\t: {}\n\
+             model name\t: {}\n\
+             stepping\t: {}\n\
+             cpu MHz\t\t: {}.{:03}\n\
+             flags\t\t: {}\n\n",
+            processor,
+            self.vendor_id,
+            self.cpu_family,
+            self.model,
+            self.model_name,
+            self.stepping,
+            self.cpu_khz / 1000,
+            self.cpu_khz % 1000,
+            self.flags.join(" "),
+        )
+    }
+}
+
+impl Default for CpuInformation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes the family, the model and the stepping from the processor signature.
+fn decode_signature(signature: u32) -> (u32, u32, u32) {
+    let stepping = signature & 0xf;
+    let base_model = (signature >> 4) & 0xf;
+    let base_family = (signature >> 8) & 0xf;
+    let extended_model = (signature >> 16) & 0xf;
+    let extended_family = (signature >> 20) & 0xff;
+
+    let family = if base_family == 0xf {
+        base_family + extended_family
+    } else {
+        base_family
+    };
+    let model = if base_family == 0x6 || base_family == 0xf {
+        (extended_model << 4) + base_model
+    } else {
+        base_model
+    };
+    (family, model, stepping)
+}
+
+/// Converts the registers, which hold ASCII characters in little endian, to a string.
+fn registers_to_string(registers: &[u32]) -> String {
+    registers
+        .iter()
+        .flat_map(|register| register.to_le_bytes())
+        .take_while(|byte| *byte != 0)
+        .map(char::from)
+        .collect()
+}
+
+fn collect_flags(flags: &mut Vec<&'static str>, register: u32, names: &[(u32, &'static str)]) {
+    for &(bit, name) in names {
+        if register & (1 << bit) != 0 {
+            flags.push(name);
+        }
+    }
+}
+
+// The names of the feature flags follow `arch/x86/include/asm/cpufeatures.h` in Linux.
+
+const LEAF_1_EDX_FLAGS: &[(u32, &str)] = &[
+    (0, "fpu"),
+    (1, "vme"),
+    (2, "de"),
+    (3, "pse"),
+    (4, "tsc"),
+    (5, "msr"),
+    (6, "pae"),
+    (7, "mce"),
+    (8, "cx8"),
+    (9, "apic"),
+    (11, "sep"),
+    (12, "mtrr"),
+    (13, "pge"),
+    (14, "mca"),
+    (15, "cmov"),
+    (16, "pat"),
+    (17, "pse36"),
+    (19, "clflush"),
+    (21, "dts"),
+    (22, "acpi"),
+    (23, "mmx"),
+    (24, "fxsr"),
+    (25, "sse"),
+    (26, "sse2"),
+    (27, "ss"),
+    (28, "ht"),
+    (29, "tm"),
+    (31, "pbe"),
+];
+
+const EXTENDED_LEAF_1_EDX_FLAGS: &[(u32, &str)] = &[
+    (11, "syscall"),
+    (20, "nx"),
+    (22, "mmxext"),
+    (26, "pdpe1gb"),
+    (27, "rdtscp"),
+    (29, "lm"),
+];
+
+const LEAF_1_ECX_FLAGS: &[(u32, &str)] = &[
+    (0, "pni"),
+    (1, "pclmulqdq"),
+    (2, "dtes64"),
+    (3, "monitor"),
+    (4, "ds_cpl"),
+    (5, "vmx"),
+    (6, "smx"),
+    (7, "est"),
+    (8, "tm2"),
+    (9, "ssse3"),
+    (10, "cid"),
+    (11, "sdbg"),
+    (12, "fma"),
+    (13, "cx16"),
+    (14, "xtpr"),
+    (15, "pdcm"),
+    (17, "pcid"),
+    (18, "dca"),
+    (19, "sse4_1"),
+    (20, "sse4_2"),
+    (21, "x2apic"),
+    (22, "movbe"),
+    (23, "popcnt"),
+    (24, "tsc_deadline_timer"),
+    (25, "aes"),
+    (26, "xsave"),
+    (28, "avx"),
+    (29, "f16c"),
+    (30, "rdrand"),
+    (31, "hypervisor"),
+];
+
+const EXTENDED_LEAF_1_ECX_FLAGS: &[(u32, &str)] = &[
+    (0, "lahf_lm"),
+    (1, "cmp_legacy"),
+    (2, "svm"),
+    (5, "abm"),
+    (6, "sse4a"),
+    (8, "3dnowprefetch"),
+];
+
+const LEAF_7_EBX_FLAGS: &[(u32, &str)] = &[
+    (0, "fsgsbase"),
+    (3, "bmi1"),
+    (4, "hle"),
+    (5, "avx2"),
+    (7, "smep"),
+    (8, "bmi2"),
+    (9, "erms"),
+    (10, "invpcid"),
+    (11, "rtm"),
+    (16, "avx512f"),
+    (17, "avx512dq"),
+    (18, "rdseed"),
+    (19, "adx"),
+    (20, "smap"),
+    (21, "avx512ifma"),
+    (23, "clflushopt"),
+    (24, "clwb"),
+    (28, "avx512cd"),
+    (29, "sha_ni"),
+    (30, "avx512bw"),
+    (31, "avx512vl"),
+];
+
+const LEAF_7_ECX_FLAGS: &[(u32, &str)] = &[
+    (1, "avx512vbmi"),
+    (2, "umip"),
+    (3, "pku"),
+    (4, "ospke"),
+    (8, "gfni"),
+    (9, "vaes"),
+    (10, "vpclmulqdq"),
+    (11, "avx512_vnni"),
+    (12, "avx512_bitalg"),
+    (14, "avx512_vpopcntdq"),
+    (22, "rdpid"),
+];
@@ -1,4 +1,10 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use core::ffi::CStr;
+
 pub mod cpu;
 pub mod signal;
+
+/// The string that identifies the platform, which is reported to user space by
+/// `AT_PLATFORM` in the auxiliary vector.
+pub const ELF_PLATFORM: &CStr = c"x86_64";
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#![allow(unused_variables)]
+
+use super::*;
+use crate::{events::IoEvents, fs::inode_handle::FileIo, prelude::*, process::signal::Poller};
+
+/// The device that is always full, which is useful to test the handling of `ENOSPC`.
+pub struct Full;
+
+impl Device for Full {
+    fn type_(&self) -> DeviceType {
+        DeviceType::CharDevice
+    }
+
+    fn id(&self) -> DeviceId {
+        // Same value with Linux
+        DeviceId::new(1, 7)
+    }
+}
+
+impl FileIo for Full {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        buf.fill(0);
+        Ok(buf.len())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        return_errno_with_message!(Errno::ENOSPC, "the device is full");
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        let events = IoEvents::IN | IoEvents::OUT;
+        events & mask
+    }
+}
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 
+mod full;
 mod null;
 mod pty;
 mod random;
@@ -29,6 +30,8 @@ pub fn init() -> Result<()> {
     add_node(null, "null")?;
     let zero = Arc::new(zero::Zero);
     add_node(zero, "zero")?;
+    let full = Arc::new(full::Full);
+    add_node(full, "full")?;
     tty::init();
     let console = get_n_tty().clone();
     add_node(console, "console")?;
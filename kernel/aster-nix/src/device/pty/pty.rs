@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use alloc::format;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 use ringbuf::{ring_buffer::RbBase, HeapRb, Rb};
 
@@ -37,6 +38,8 @@ pub struct PtyMaster {
     job_control: Arc<JobControl>,
     /// The state of input buffer
     pollee: Pollee,
+    /// Whether the slave is locked, which cannot be opened until unlocked
+    is_locked: AtomicBool,
     weak_self: Weak<Self>,
 }
 
@@ -50,6 +53,7 @@ impl PtyMaster {
             input: SpinLock::new(HeapRb::new(BUFFER_CAPACITY)),
             job_control,
             pollee: Pollee::new(IoEvents::OUT),
+            is_locked: AtomicBool::new(true),
             weak_self: weak_ref.clone(),
         })
     }
@@ -158,8 +162,27 @@ impl FileIo for PtyMaster {
                 self.output.set_termios(termios);
                 Ok(0)
             }
+            IoctlCmd::TCSETSW => {
+                let termios = read_val_from_user(arg)?;
+                self.output.set_termios(termios);
+                // TODO: drain output buffer
+                Ok(0)
+            }
+            IoctlCmd::TCSETSF => {
+                let termios = read_val_from_user(arg)?;
+                self.output.set_termios(termios);
+                self.output.drain_input();
+                // TODO: drain output buffer
+                Ok(0)
+            }
             IoctlCmd::TIOCSPTLCK => {
-                // TODO: lock/unlock pty
+                let lock: i32 = read_val_from_user(arg)?;
+                self.is_locked.store(lock != 0, Ordering::Relaxed);
+                Ok(0)
+            }
+            IoctlCmd::TIOCGPTLCK => {
+                let lock = self.is_locked.load(Ordering::Relaxed) as i32;
+                write_val_to_user(arg, &lock)?;
                 Ok(0)
             }
             IoctlCmd::TIOCGPTN => {
@@ -191,7 +214,7 @@ impl FileIo for PtyMaster {
                 let fd = {
                     let mut file_table = current.file_table().lock();
                     // TODO: deal with the O_CLOEXEC flag
-                    file_table.insert(slave, FdFlags::empty())
+                    file_table.insert(slave, FdFlags::empty())?
                 };
                 Ok(fd)
             }
@@ -286,7 +309,9 @@ impl Drop for PtyMaster {
 
 pub struct PtySlave {
     master: Weak<PtyMaster>,
-    job_control: JobControl,
+    /// The job control shared with the master, so the signals sent by the line
+    /// discipline reach the foreground process group of the slave.
+    job_control: Arc<JobControl>,
     weak_self: Weak<Self>,
 }
 
@@ -294,7 +319,7 @@ impl PtySlave {
     pub fn new(master: &Arc<PtyMaster>) -> Arc<Self> {
         Arc::new_cyclic(|weak_ref| PtySlave {
             master: Arc::downgrade(master),
-            job_control: JobControl::new(),
+            job_control: master.job_control.clone(),
             weak_self: weak_ref.clone(),
         })
     }
@@ -316,6 +341,13 @@ impl Device for PtySlave {
     fn id(&self) -> crate::fs::device::DeviceId {
         DeviceId::new(88, self.index())
     }
+
+    fn open(&self) -> Result<Option<Arc<dyn FileIo>>> {
+        if self.master().is_locked.load(Ordering::Relaxed) {
+            return_errno_with_message!(Errno::EIO, "the pty slave is locked");
+        }
+        Ok(None)
+    }
 }
 
 impl Terminal for PtySlave {
@@ -356,6 +388,8 @@ impl FileIo for PtySlave {
         match cmd {
             IoctlCmd::TCGETS
             | IoctlCmd::TCSETS
+            | IoctlCmd::TCSETSW
+            | IoctlCmd::TCSETSF
             | IoctlCmd::TIOCGPTN
             | IoctlCmd::TIOCGWINSZ
             | IoctlCmd::TIOCSWINSZ => self.master().ioctl(cmd, arg),
@@ -12,7 +12,7 @@ use crate::{
     events::IoEvents,
     prelude::*,
     process::signal::{
-        constants::{SIGINT, SIGQUIT},
+        constants::{SIGINT, SIGQUIT, SIGWINCH},
         signals::kernel::KernelSignal,
         Pollee, Poller,
     },
@@ -37,7 +37,8 @@ pub struct LineDiscipline {
     winsize: SpinLock<WinSize>,
     /// Pollee
     pollee: Pollee,
-    /// Used to send signal for foreground processes, when some char comes.
+    /// Used to send signal for foreground processes, when some char comes
+    /// or the window size changes.
     send_signal: LdiscSignalSender,
     /// work item
     work_item: Arc<WorkItem>,
@@ -229,12 +230,16 @@ impl LineDiscipline {
         match ch {
             b'\n' => echo_callback("\n"),
             b'\r' => echo_callback("\r\n"),
-            ch if ch == *termios.get_special_char(CC_C_CHAR::VERASE) => {
+            // The end-of-file character is not echoed in canonical mode
+            ch if termios.is_canonical_mode() && is_eof(ch, termios) => {}
+            ch if termios.is_canonical_mode()
+                && ch == *termios.get_special_char(CC_C_CHAR::VERASE) =>
+            {
                 // write a space to overwrite current character
                 let backspace: &str = core::str::from_utf8(&[b'\x08', b' ', b'\x08']).unwrap();
                 echo_callback(backspace);
             }
-            ch if is_printable_char(ch) => print!("{}", char::from(ch)),
+            ch if is_printable_char(ch) => echo_callback(core::str::from_utf8(&[ch]).unwrap()),
             ch if is_ctrl_char(ch) && termios.contains_echo_ctl() => {
                 let ctrl_char = format!("^{}", get_printable_char(ch));
                 echo_callback(&ctrl_char);
@@ -261,12 +266,22 @@ impl LineDiscipline {
 
     /// read all bytes buffered to dst, return the actual read length.
     fn try_read(&self, dst: &mut [u8]) -> Result<usize> {
-        let (vmin, vtime) = {
+        let (is_canonical_mode, vmin, vtime) = {
             let termios = self.termios.lock_irq_disabled();
             let vmin = *termios.get_special_char(CC_C_CHAR::VMIN);
             let vtime = *termios.get_special_char(CC_C_CHAR::VTIME);
-            (vmin, vtime)
+            (termios.is_canonical_mode(), vmin, vtime)
         };
+        if is_canonical_mode {
+            // `VMIN` and `VTIME` are ignored in canonical mode,
+            // where a read blocks until a whole line is available.
+            if self.is_empty() {
+                return_errno!(Errno::EAGAIN);
+            }
+            let read_len = self.poll_read(dst);
+            self.update_readable_state();
+            return Ok(read_len);
+        }
         let read_len = {
             let len = self.read_buffer.lock_irq_disabled().len();
             let max_read_len = len.min(dst.len());
@@ -377,8 +392,14 @@ impl LineDiscipline {
         *self.winsize.lock()
     }
 
+    /// Sets the window size.
+    ///
+    /// If the window size changes, `SIGWINCH` is sent to the foreground process group.
     pub fn set_window_size(&self, winsize: WinSize) {
-        *self.winsize.lock() = winsize;
+        let old_winsize = core::mem::replace(&mut *self.winsize.lock(), winsize);
+        if old_winsize != winsize {
+            (self.send_signal)(KernelSignal::new(SIGWINCH));
+        }
     }
 }
 
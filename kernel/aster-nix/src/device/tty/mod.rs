@@ -161,6 +161,10 @@ impl FileIo for Tty {
                 self.set_current_session()?;
                 Ok(0)
             }
+            IoctlCmd::TIOCNOTTY => {
+                self.release_current_session()?;
+                Ok(0)
+            }
             _ => todo!(),
         }
     }
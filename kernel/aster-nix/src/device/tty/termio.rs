@@ -293,7 +293,7 @@ const fn control_character(c: char) -> u8 {
     c as u8 - b'A' + 1u8
 }
 
-#[derive(Debug, Clone, Copy, Default, Pod)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Pod)]
 #[repr(C)]
 pub struct WinSize {
     ws_row: u16,
@@ -11,7 +11,10 @@ use crate::prelude::*;
 pub struct Subject<E: Events, F: EventsFilter<E> = ()> {
     // A table that maintains all interesting observers.
     observers: Mutex<BTreeMap<KeyableWeak<dyn Observer<E>>, F>>,
-    // To reduce lock contentions, we maintain a counter for the size of the table
+    // A queue of the observers that are notified exclusively. The observer that
+    // has been notified most recently is moved to the back of the queue.
+    exclusive_observers: Mutex<VecDeque<(KeyableWeak<dyn Observer<E>>, F)>>,
+    // To reduce lock contentions, we maintain a counter for the size of the tables
     num_observers: AtomicUsize,
 }
 
@@ -19,6 +22,7 @@ impl<E: Events, F: EventsFilter<E>> Subject<E, F> {
     pub const fn new() -> Self {
         Self {
             observers: Mutex::new(BTreeMap::new()),
+            exclusive_observers: Mutex::new(VecDeque::new()),
             num_observers: AtomicUsize::new(0),
         }
     }
@@ -40,6 +44,32 @@ impl<E: Events, F: EventsFilter<E>> Subject<E, F> {
         }
     }
 
+    /// Register an exclusive observer.
+    ///
+    /// Unlike the observers registered with [`register_observer`], exclusive
+    /// observers are not notified all at once. They are only notified by
+    /// [`notify_exclusive_observer`], which notifies one of the exclusive
+    /// observers that are interested in the events in a round-robin manner.
+    ///
+    /// If the given observer has already been registered as an exclusive
+    /// observer, then its registered events filter will be updated.
+    ///
+    /// [`register_observer`]: Self::register_observer
+    /// [`notify_exclusive_observer`]: Self::notify_exclusive_observer
+    pub fn register_exclusive_observer(&self, observer: Weak<dyn Observer<E>>, filter: F) {
+        let observer: KeyableWeak<dyn Observer<E>> = observer.into();
+        let mut exclusive_observers = self.exclusive_observers.lock();
+        if let Some((_, old_filter)) = exclusive_observers
+            .iter_mut()
+            .find(|(registered, _)| *registered == observer)
+        {
+            *old_filter = filter;
+            return;
+        }
+        exclusive_observers.push_back((observer, filter));
+        self.num_observers.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Unregister an observer.
     ///
     /// If such an observer is found, then the registered observer will be
@@ -50,17 +80,27 @@ impl<E: Events, F: EventsFilter<E>> Subject<E, F> {
         observer: &Weak<dyn Observer<E>>,
     ) -> Option<Weak<dyn Observer<E>>> {
         let observer: KeyableWeak<dyn Observer<E>> = observer.clone().into();
-        let mut observers = self.observers.lock();
-        let observer = observers
+        let removed = self
+            .observers
+            .lock()
             .remove_entry(&observer)
-            .map(|(observer, _)| observer.into());
-        if observer.is_some() {
+            .map(|(observer, _)| observer)
+            .or_else(|| {
+                let mut exclusive_observers = self.exclusive_observers.lock();
+                let index = exclusive_observers
+                    .iter()
+                    .position(|(registered, _)| *registered == observer)?;
+                exclusive_observers
+                    .remove(index)
+                    .map(|(observer, _)| observer)
+            });
+        if removed.is_some() {
             self.num_observers.fetch_sub(1, Ordering::Relaxed);
         }
-        observer
+        removed.map(|observer| observer.into())
     }
 
-    /// Notify events to all registered observers.
+    /// Notify events to all registered observers except the exclusive ones.
     ///
     /// It will remove the observers which have been freed.
     pub fn notify_observers(&self, events: &E) {
@@ -84,6 +124,39 @@ impl<E: Events, F: EventsFilter<E>> Subject<E, F> {
             }
         });
     }
+
+    /// Notify events to one of the exclusive observers.
+    ///
+    /// It will remove the exclusive observers which have been freed.
+    pub fn notify_exclusive_observer(&self, events: &E) {
+        // Fast path.
+        if self.num_observers.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        let mut exclusive_observers = self.exclusive_observers.lock();
+        let mut index = 0;
+        while index < exclusive_observers.len() {
+            let (weak_observer, filter) = &exclusive_observers[index];
+            let is_interested = filter.filter(events);
+            let Some(observer) = weak_observer.upgrade() else {
+                exclusive_observers.remove(index);
+                self.num_observers.fetch_sub(1, Ordering::Relaxed);
+                continue;
+            };
+            if !is_interested {
+                index += 1;
+                continue;
+            }
+
+            // Move the notified observer to the back of the queue so that the
+            // exclusive observers take turns to get notified.
+            let entry = exclusive_observers.remove(index).unwrap();
+            exclusive_observers.push_back(entry);
+            observer.on_events(events);
+            return;
+        }
+    }
 }
 
 impl<E: Events> Default for Subject<E> {
@@ -2,12 +2,14 @@
 
 use super::inode_handle::FileIo;
 use crate::{
+    events::IoEvents,
     fs::{
-        fs_resolver::{FsPath, FsResolver},
-        path::Dentry,
-        utils::{InodeMode, InodeType},
+        path::{Dentry, MountNode},
+        ramfs::RamFS,
+        utils::{FileSystem, InodeMode, InodeType},
     },
     prelude::*,
+    process::signal::Poller,
 };
 
 /// The abstract of device
@@ -33,7 +35,7 @@ impl Debug for dyn Device {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 /// Device type
 pub enum DeviceType {
     CharDevice,
@@ -81,15 +83,89 @@ impl From<DeviceId> for u64 {
     }
 }
 
-/// Add a device node to FS for the device.
+impl From<u64> for DeviceId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+/// The devices that have been registered along with their names, indexed by the device ID.
+static DEVICES: Mutex<BTreeMap<u64, (String, Arc<dyn Device>)>> = Mutex::new(BTreeMap::new());
+
+/// Get the registered device with the device ID.
+///
+/// If no device is registered with the ID, a device that cannot be opened is returned,
+/// so that a device node can always be created (e.g., by `mknod`) for the ID.
+pub fn get_device(type_: DeviceType, id: DeviceId) -> Arc<dyn Device> {
+    if let Some((_, device)) = DEVICES.lock().get(&u64::from(id)) {
+        return device.clone();
+    }
+    Arc::new(UnregisteredDevice { type_, id })
+}
+
+/// Get all the registered devices along with their names.
+///
+/// The name of a device is the path of its node in devtmpfs.
+pub fn registered_devices() -> Vec<(String, Arc<dyn Device>)> {
+    DEVICES.lock().values().cloned().collect()
+}
+
+/// A device ID without any registered device.
+struct UnregisteredDevice {
+    type_: DeviceType,
+    id: DeviceId,
+}
+
+impl Device for UnregisteredDevice {
+    fn type_(&self) -> DeviceType {
+        self.type_
+    }
+
+    fn id(&self) -> DeviceId {
+        self.id
+    }
+
+    fn open(&self) -> Result<Option<Arc<dyn FileIo>>> {
+        return_errno_with_message!(Errno::ENXIO, "the device is not registered");
+    }
+}
+
+impl FileIo for UnregisteredDevice {
+    fn read(&self, _buf: &mut [u8]) -> Result<usize> {
+        return_errno_with_message!(Errno::ENXIO, "the device is not registered");
+    }
+
+    fn write(&self, _buf: &[u8]) -> Result<usize> {
+        return_errno_with_message!(Errno::ENXIO, "the device is not registered");
+    }
+
+    fn poll(&self, _mask: IoEvents, _poller: Option<&Poller>) -> IoEvents {
+        IoEvents::ERR
+    }
+}
+
+lazy_static! {
+    /// The devtmpfs, in which a node is created for each registered device.
+    ///
+    /// All the mounts of devtmpfs share this instance, so a node added after a mount
+    /// is also visible through the mount.
+    static ref DEVTMPFS: Arc<RamFS> = RamFS::new_devtmpfs();
+    /// The root of devtmpfs, which is not reachable from the mount tree of any process.
+    static ref DEVTMPFS_ROOT: Arc<Dentry> =
+        Dentry::new_fs_root(MountNode::new_root(DEVTMPFS.clone()));
+}
+
+/// Get the devtmpfs to mount.
+pub fn devtmpfs() -> Arc<dyn FileSystem> {
+    DEVTMPFS.clone()
+}
+
+/// Add a device node to devtmpfs for the device.
 ///
 /// If the parent path is not existing, `mkdir -p` the parent path.
 /// This function is used in registering device.
 pub fn add_node(device: Arc<dyn Device>, path: &str) -> Result<Arc<Dentry>> {
-    let mut dentry = {
-        let fs_resolver = FsResolver::new();
-        fs_resolver.lookup(&FsPath::try_from("/dev").unwrap())?
-    };
+    let mut dentry = DEVTMPFS_ROOT.clone();
     let mut relative_path = {
         let relative_path = path.trim_start_matches('/');
         if relative_path.is_empty() {
@@ -97,6 +173,7 @@ pub fn add_node(device: Arc<dyn Device>, path: &str) -> Result<Arc<Dentry>> {
         }
         relative_path
     };
+    let device_path = relative_path.to_string();
 
     while !relative_path.is_empty() {
         let (next_name, path_remain) = if let Some((prefix, suffix)) = relative_path.split_once('/')
@@ -134,26 +211,27 @@ pub fn add_node(device: Arc<dyn Device>, path: &str) -> Result<Arc<Dentry>> {
         relative_path = path_remain;
     }
 
+    DEVICES
+        .lock()
+        .insert(u64::from(device.id()), (device_path, device));
     Ok(dentry)
 }
 
-/// Delete the device node from FS for the device.
+/// Delete the device node from devtmpfs for the device.
 ///
 /// This function is used in unregistering device.
 pub fn delete_node(path: &str) -> Result<()> {
-    let abs_path = {
-        let device_path = path.trim_start_matches('/');
-        if device_path.is_empty() {
-            return_errno_with_message!(Errno::EINVAL, "invalid device path");
-        }
-        String::from("/dev") + "/" + device_path
-    };
+    let device_path = path.trim_start_matches('/');
+    if device_path.is_empty() {
+        return_errno_with_message!(Errno::EINVAL, "invalid device path");
+    }
 
-    let (parent_dentry, name) = {
-        let fs_resolver = FsResolver::new();
-        fs_resolver.lookup_dir_and_base_name(&FsPath::try_from(abs_path.as_str()).unwrap())?
-    };
+    let (dir_path, name) = device_path.rsplit_once('/').unwrap_or(("", device_path));
+    let mut parent_dentry = DEVTMPFS_ROOT.clone();
+    for dir_name in dir_path.split('/').filter(|name| !name.is_empty()) {
+        parent_dentry = parent_dentry.lookup(dir_name)?;
+    }
 
-    parent_dentry.unlink(&name)?;
+    parent_dentry.unlink(name)?;
     Ok(())
 }
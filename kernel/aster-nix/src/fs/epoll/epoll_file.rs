@@ -11,7 +11,11 @@ use core::{
 use super::*;
 use crate::{
     events::Observer,
-    fs::{file_handle::FileLike, file_table::FdEvents, utils::IoctlCmd},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        file_table::FdEvents,
+        utils::IoctlCmd,
+    },
     process::signal::{Pollee, Poller},
 };
 
@@ -36,6 +40,7 @@ pub struct EpollFile {
     pollee: Pollee,
     // Any EpollFile is wrapped with Arc when created.
     weak_self: Weak<Self>,
+    _file_count: FileCount,
 }
 
 impl EpollFile {
@@ -46,6 +51,7 @@ impl EpollFile {
             ready: Mutex::new(VecDeque::new()),
             pollee: Pollee::new(IoEvents::empty()),
             weak_self: me.clone(),
+            _file_count: FileCount::new(),
         })
     }
 
@@ -71,6 +77,9 @@ impl EpollFile {
         let file = file_table_entry.file();
         let weak_file = Arc::downgrade(file);
         let mask = ep_event.events;
+        if ep_flags.contains(EpollFlags::EXCLUSIVE) {
+            check_exclusive(file.as_ref(), &ep_event, ep_flags)?;
+        }
         let entry = EpollEntry::new(fd, weak_file, ep_event, ep_flags, self.weak_self.clone());
 
         // Add the new entry to the interest list and start monitering its events
@@ -78,7 +87,11 @@ impl EpollFile {
         if interest.contains_key(&fd) {
             return_errno_with_message!(Errno::EEXIST, "the fd has been added");
         }
-        file.register_observer(entry.self_weak() as _, IoEvents::all())?;
+        if ep_flags.contains(EpollFlags::EXCLUSIVE) {
+            file.register_exclusive_observer(entry.self_weak() as _, IoEvents::all())?;
+        } else {
+            file.register_observer(entry.self_weak() as _, IoEvents::all())?;
+        }
         interest.insert(fd, entry.clone());
         // Register self to the file table entry
         file_table_entry.register_observer(self.weak_self.clone() as _);
@@ -135,6 +148,14 @@ impl EpollFile {
         if entry.is_deleted() {
             return_errno_with_message!(Errno::ENOENT, "fd is not in the interest list");
         }
+        if new_ep_flags.contains(EpollFlags::EXCLUSIVE)
+            || entry.flags().contains(EpollFlags::EXCLUSIVE)
+        {
+            return_errno_with_message!(
+                Errno::EINVAL,
+                "EPOLLEXCLUSIVE cannot be used to modify an entry"
+            );
+        }
         let new_mask = new_ep_event.events;
         entry.update(new_ep_event, new_ep_flags);
         let entry = entry.clone();
@@ -285,12 +306,30 @@ impl EpollFile {
     }
 
     fn warn_unsupported_flags(&self, flags: &EpollFlags) {
-        if flags.intersects(EpollFlags::EXCLUSIVE | EpollFlags::WAKE_UP) {
+        if flags.intersects(EpollFlags::WAKE_UP) {
             warn!("{:?} contains unsupported flags", flags);
         }
     }
 }
 
+/// Checks whether a file can be added to the interest list with `EPOLLEXCLUSIVE`.
+fn check_exclusive(file: &dyn FileLike, ep_event: &EpollEvent, ep_flags: EpollFlags) -> Result<()> {
+    let exclusive_ok_events = IoEvents::IN | IoEvents::OUT | IoEvents::ERR | IoEvents::HUP;
+    if ep_flags.contains(EpollFlags::ONE_SHOT) || !exclusive_ok_events.contains(ep_event.events) {
+        return_errno_with_message!(
+            Errno::EINVAL,
+            "EPOLLEXCLUSIVE is used with unsupported events or flags"
+        );
+    }
+    if file.downcast_ref::<EpollFile>().is_some() {
+        return_errno_with_message!(
+            Errno::EINVAL,
+            "EPOLLEXCLUSIVE cannot be used with an epoll file"
+        );
+    }
+    Ok(())
+}
+
 impl Observer<FdEvents> for EpollFile {
     fn on_events(&self, events: &FdEvents) {
         // Delete the file from the interest list if it is closed.
@@ -26,7 +26,7 @@ impl FileSystem for Ext2 {
     }
 
     fn flags(&self) -> FsFlags {
-        FsFlags::empty()
+        FsFlags::NEGATIVE_DENTRY
     }
 }
 
@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! File system event notification with fanotify.
+//!
+//! A fanotify group is created by `fanotify_init` and is represented by a file.
+//! Marks are added to the group by `fanotify_mark`, each of which watches either
+//! an inode or a whole mount for a set of events. Reading the group file returns
+//! the pending events as `fanotify_event_metadata` records, each carrying a newly
+//! opened file descriptor of the object that the event happened on.
+//!
+//! Only the notification events `FAN_OPEN` and `FAN_MODIFY` are supported for now.
+//! The permission events are not supported.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::{
+    file_handle::{FileCount, FileLike},
+    file_table::FdFlags,
+    inode_handle::InodeHandle,
+    path::{Dentry, MountNode},
+    utils::{AccessMode, Inode, InodeMode, InodeType, Metadata, StatusFlags},
+};
+use crate::{
+    events::{IoEvents, Observer},
+    prelude::*,
+    process::{
+        signal::{Pollee, Poller},
+        Gid, Pid, Uid,
+    },
+    time::clocks::RealTimeClock,
+};
+
+bitflags! {
+    /// The events that can be reported by fanotify.
+    pub struct FanEvents: u64 {
+        const MODIFY         = 0x0000_0002;
+        const OPEN           = 0x0000_0020;
+        const Q_OVERFLOW     = 0x0000_4000;
+        const EVENT_ON_CHILD = 0x0800_0000;
+        const ONDIR          = 0x4000_0000;
+    }
+}
+
+impl FanEvents {
+    /// The events that can be reported to the user.
+    const REPORTABLE: Self = Self::from_bits_truncate(Self::MODIFY.bits | Self::OPEN.bits);
+}
+
+/// The object watched by a fanotify mark.
+pub enum FanMarkTarget {
+    Inode(Arc<dyn Inode>),
+    Mount(Arc<MountNode>),
+}
+
+impl FanMarkTarget {
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Inode(this), Self::Inode(other)) => is_same_inode(this, other),
+            (Self::Mount(this), Self::Mount(other)) => Arc::ptr_eq(this, other),
+            _ => false,
+        }
+    }
+}
+
+fn is_same_inode(this: &Arc<dyn Inode>, other: &Arc<dyn Inode>) -> bool {
+    Arc::as_ptr(this) as *const () == Arc::as_ptr(other) as *const ()
+}
+
+struct FanMark {
+    target: FanMarkTarget,
+    mask: FanEvents,
+}
+
+struct FanEvent {
+    mask: FanEvents,
+    /// The object of the event, which is `None` for an overflow event.
+    dentry: Option<Arc<Dentry>>,
+    pid: Pid,
+}
+
+/// The default maximum number of the pending events in a group.
+pub const DEFAULT_MAX_EVENTS: usize = 16384;
+
+/// The version of `fanotify_event_metadata`.
+const FANOTIFY_METADATA_VERSION: u8 = 3;
+
+/// The file descriptor reported for the events without any object.
+const FAN_NOFD: i32 = -1;
+
+/// A fanotify group.
+pub struct FanotifyGroup {
+    marks: Mutex<Vec<FanMark>>,
+    events: Mutex<VecDeque<FanEvent>>,
+    max_events: Option<usize>,
+    pollee: Pollee,
+    is_nonblocking: AtomicBool,
+    /// The flags used to open the file descriptors of the events.
+    event_access_mode: AccessMode,
+    event_status_flags: StatusFlags,
+    event_fd_flags: FdFlags,
+    _file_count: FileCount,
+}
+
+impl FanotifyGroup {
+    /// Creates a new fanotify group and registers it to receive events.
+    ///
+    /// If `max_events` is `None`, the event queue is unlimited.
+    pub fn new(
+        is_nonblocking: bool,
+        max_events: Option<usize>,
+        event_access_mode: AccessMode,
+        event_status_flags: StatusFlags,
+        event_fd_flags: FdFlags,
+    ) -> Arc<Self> {
+        let group = Arc::new(Self {
+            marks: Mutex::new(Vec::new()),
+            events: Mutex::new(VecDeque::new()),
+            max_events,
+            pollee: Pollee::new(IoEvents::empty()),
+            is_nonblocking: AtomicBool::new(is_nonblocking),
+            event_access_mode,
+            event_status_flags,
+            event_fd_flags,
+            _file_count: FileCount::new(),
+        });
+        GROUPS.lock().push(Arc::downgrade(&group));
+        group
+    }
+
+    /// Adds the events in `mask` to the mark of `target`.
+    ///
+    /// A new mark is created if there is no mark of `target` yet.
+    pub fn add_mark(&self, target: FanMarkTarget, mask: FanEvents) {
+        let mut marks = self.marks.lock();
+        if let Some(mark) = marks.iter_mut().find(|mark| mark.target.is_same(&target)) {
+            mark.mask |= mask;
+            return;
+        }
+        marks.push(FanMark { target, mask });
+    }
+
+    /// Removes the events in `mask` from the mark of `target`.
+    ///
+    /// The mark is destroyed once it does not contain any events.
+    pub fn remove_mark(&self, target: &FanMarkTarget, mask: FanEvents) -> Result<()> {
+        let mut marks = self.marks.lock();
+        let Some(index) = marks.iter().position(|mark| mark.target.is_same(target)) else {
+            return_errno_with_message!(Errno::ENOENT, "the mark does not exist");
+        };
+        marks[index].mask -= mask;
+        if !marks[index].mask.intersects(FanEvents::REPORTABLE) {
+            marks.remove(index);
+        }
+        Ok(())
+    }
+
+    /// Removes all the inode marks or all the mount marks.
+    pub fn flush_marks(&self, is_mount: bool) {
+        self.marks
+            .lock()
+            .retain(|mark| matches!(mark.target, FanMarkTarget::Mount(_)) != is_mount);
+    }
+
+    fn is_interested(&self, dentry: &Dentry, event: FanEvents) -> bool {
+        let is_dir = dentry.type_() == InodeType::Dir;
+        let parent_inode = dentry.parent_inode();
+
+        self.marks.lock().iter().any(|mark| {
+            if !mark.mask.contains(event) {
+                return false;
+            }
+            if is_dir && !mark.mask.contains(FanEvents::ONDIR) {
+                return false;
+            }
+            match &mark.target {
+                FanMarkTarget::Mount(mount_node) => Arc::ptr_eq(mount_node, dentry.mount_node()),
+                FanMarkTarget::Inode(inode) => {
+                    is_same_inode(inode, dentry.inode())
+                        || (mark.mask.contains(FanEvents::EVENT_ON_CHILD)
+                            && parent_inode
+                                .as_ref()
+                                .is_some_and(|parent| is_same_inode(inode, parent)))
+                }
+            }
+        })
+    }
+
+    fn push_event(&self, dentry: &Arc<Dentry>, event: FanEvents, pid: Pid) {
+        let mut events = self.events.lock();
+
+        // Merge the event with the last one if they are on the same object.
+        if let Some(last) = events.back_mut() {
+            let is_same_object = last
+                .dentry
+                .as_ref()
+                .is_some_and(|last_dentry| last_dentry.is_same(dentry));
+            if is_same_object && last.pid == pid {
+                last.mask |= event;
+                return;
+            }
+        }
+
+        if self
+            .max_events
+            .is_some_and(|max_events| events.len() >= max_events)
+        {
+            let is_overflowed = events
+                .back()
+                .is_some_and(|last| last.mask.contains(FanEvents::Q_OVERFLOW));
+            if !is_overflowed {
+                events.push_back(FanEvent {
+                    mask: FanEvents::Q_OVERFLOW,
+                    dentry: None,
+                    pid: 0,
+                });
+            }
+        } else {
+            events.push_back(FanEvent {
+                mask: event,
+                dentry: Some(dentry.clone()),
+                pid,
+            });
+        }
+
+        self.pollee.add_events(IoEvents::IN);
+    }
+
+    /// Opens the object of an event and returns the file descriptor.
+    fn open_event_fd(&self, event: &FanEvent) -> Result<i32> {
+        let Some(dentry) = event.dentry.as_ref() else {
+            return Ok(FAN_NOFD);
+        };
+
+        // Opening the file directly does not generate another `FAN_OPEN` event.
+        let inode_handle = InodeHandle::new(
+            dentry.clone(),
+            self.event_access_mode,
+            self.event_status_flags,
+        )?;
+        let current = current!();
+        current
+            .file_table()
+            .lock()
+            .insert(Arc::new(inode_handle), self.event_fd_flags)
+    }
+
+    fn is_nonblocking(&self) -> bool {
+        self.is_nonblocking.load(Ordering::Relaxed)
+    }
+
+    fn try_read(&self, buf: &mut [u8]) -> Result<usize> {
+        let metadata_len = core::mem::size_of::<fanotify_event_metadata>();
+
+        let mut events = self.events.lock();
+        if events.is_empty() {
+            return_errno_with_message!(Errno::EAGAIN, "there are no events");
+        }
+        if buf.len() < metadata_len {
+            return_errno_with_message!(Errno::EINVAL, "the buffer is too small");
+        }
+
+        let mut read_len = 0;
+        while let Some(event) = events.front() {
+            if buf.len() - read_len < metadata_len {
+                break;
+            }
+
+            let fd = match self.open_event_fd(event) {
+                Ok(fd) => fd,
+                // Report the error only if no event has been read.
+                Err(err) if read_len == 0 => {
+                    events.pop_front();
+                    return Err(err);
+                }
+                Err(_) => break,
+            };
+            let metadata = fanotify_event_metadata {
+                event_len: metadata_len as u32,
+                vers: FANOTIFY_METADATA_VERSION,
+                reserved: 0,
+                metadata_len: metadata_len as u16,
+                mask: event.mask.bits(),
+                fd,
+                pid: event.pid as i32,
+            };
+            buf[read_len..read_len + metadata_len].copy_from_slice(metadata.as_bytes());
+            read_len += metadata_len;
+            events.pop_front();
+        }
+
+        if events.is_empty() {
+            self.pollee.del_events(IoEvents::IN);
+        }
+
+        Ok(read_len)
+    }
+}
+
+impl FileLike for FanotifyGroup {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        if self.is_nonblocking() {
+            return self.try_read(buf);
+        }
+
+        let poller = Poller::new();
+        loop {
+            match self.try_read(buf) {
+                Err(err) if err.error() == Errno::EAGAIN => {}
+                result => return result,
+            }
+            if self.poll(IoEvents::IN, Some(&poller)).is_empty() {
+                poller.wait()?;
+            }
+        }
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        self.pollee.poll(mask, poller)
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        if self.is_nonblocking() {
+            StatusFlags::O_NONBLOCK
+        } else {
+            StatusFlags::empty()
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        self.is_nonblocking.store(
+            new_flags.contains(StatusFlags::O_NONBLOCK),
+            Ordering::Relaxed,
+        );
+        Ok(())
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_observer(observer, mask);
+        Ok(())
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        self.pollee.unregister_observer(observer)
+    }
+
+    fn metadata(&self) -> Metadata {
+        let now = RealTimeClock::get().read_time();
+        Metadata {
+            dev: 0,
+            ino: 0,
+            size: 0,
+            blk_size: 0,
+            blocks: 0,
+            atime: now,
+            mtime: now,
+            ctime: now,
+            type_: InodeType::NamedPipe,
+            mode: InodeMode::from_bits_truncate(0o600),
+            nlinks: 1,
+            uid: Uid::new_root(),
+            gid: Gid::new_root(),
+            rdev: 0,
+        }
+    }
+}
+
+/// All the fanotify groups.
+static GROUPS: Mutex<Vec<Weak<FanotifyGroup>>> = Mutex::new(Vec::new());
+
+/// Notifies the fanotify groups that `event` happens on `dentry`.
+pub fn notify(dentry: &Arc<Dentry>, event: FanEvents) {
+    let groups: Vec<Arc<FanotifyGroup>> = {
+        let mut groups = GROUPS.lock();
+        if groups.is_empty() {
+            return;
+        }
+        groups.retain(|group| group.strong_count() > 0);
+        groups.iter().filter_map(Weak::upgrade).collect()
+    };
+
+    let pid = current!().pid();
+    for group in groups {
+        if group.is_interested(dentry, event) {
+            group.push_event(dentry, event, pid);
+        }
+    }
+}
+
+/// The event record read from a fanotify group.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod)]
+struct fanotify_event_metadata {
+    event_len: u32,
+    vers: u8,
+    reserved: u8,
+    metadata_len: u16,
+    mask: u64,
+    fd: i32,
+    pid: i32,
+}
@@ -4,6 +4,8 @@
 
 //! Opend File Handle
 
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use crate::{
     events::{IoEvents, Observer},
     fs::{
@@ -119,6 +121,19 @@ pub trait FileLike: Send + Sync + Any {
         return_errno_with_message!(Errno::EINVAL, "register_observer is not supported")
     }
 
+    /// Registers an observer that is notified exclusively.
+    ///
+    /// Among all the exclusive observers of the file, only one of them gets
+    /// notified when new events happen. By default, the observer is registered
+    /// as a normal one, which is still correct but may cause more wakeups.
+    fn register_exclusive_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.register_observer(observer, mask)
+    }
+
     #[must_use]
     fn unregister_observer(
         &self,
@@ -141,3 +156,37 @@ impl dyn FileLike {
         (self as &dyn Any).downcast_ref::<T>()
     }
 }
+
+/// The number of files in the system.
+static NR_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of files in the system.
+pub fn nr_files() -> usize {
+    NR_FILES.load(Ordering::Relaxed)
+}
+
+/// A token that counts a file in [`nr_files`] until it is dropped.
+///
+/// Each file created as a [`FileLike`] holds one token, so a file is counted once no matter how
+/// many file descriptors refer to it.
+#[derive(Debug)]
+pub struct FileCount(());
+
+impl FileCount {
+    pub fn new() -> Self {
+        NR_FILES.fetch_add(1, Ordering::Relaxed);
+        Self(())
+    }
+}
+
+impl Default for FileCount {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for FileCount {
+    fn drop(&mut self) {
+        NR_FILES.fetch_sub(1, Ordering::Relaxed);
+    }
+}
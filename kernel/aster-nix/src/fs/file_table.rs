@@ -2,7 +2,7 @@
 
 #![allow(unused_variables)]
 
-use core::sync::atomic::{AtomicU8, Ordering};
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
 
 use aster_util::slot_vec::SlotVec;
 
@@ -19,6 +19,38 @@ use crate::{
 
 pub type FileDesc = i32;
 
+/// The default maximum number of file descriptors that a process can open.
+const DEFAULT_NR_OPEN: usize = 1024 * 1024;
+/// The minimum value that the maximum number of file descriptors can be set to.
+const NR_OPEN_MIN: usize = 64;
+/// The maximum value that the maximum number of file descriptors can be set to.
+///
+/// The value is the largest multiple of 64 that fits in a `FileDesc`.
+const NR_OPEN_MAX: usize = (FileDesc::MAX as usize) & !(NR_OPEN_MIN - 1);
+
+/// The maximum number of file descriptors that a process can open.
+///
+/// This is a kernel-wide hard cap, like `/proc/sys/fs/nr_open` in Linux.
+/// No file descriptor can be allocated at or above it, and `RLIMIT_NOFILE`
+/// cannot be raised above it, even by privileged processes.
+static NR_OPEN: AtomicUsize = AtomicUsize::new(DEFAULT_NR_OPEN);
+
+/// Returns the maximum number of file descriptors that a process can open.
+pub fn nr_open() -> usize {
+    NR_OPEN.load(Ordering::Relaxed)
+}
+
+/// Sets the maximum number of file descriptors that a process can open.
+///
+/// The file descriptors that are already open are not affected.
+pub fn set_nr_open(nr_open: usize) -> Result<()> {
+    if !(NR_OPEN_MIN..=NR_OPEN_MAX).contains(&nr_open) {
+        return_errno_with_message!(Errno::EINVAL, "nr_open is out of range");
+    }
+    NR_OPEN.store(nr_open, Ordering::Relaxed);
+    Ok(())
+}
+
 pub struct FileTable {
     table: SlotVec<FileTableEntry>,
     subject: Subject<FdEvents>,
@@ -83,14 +115,25 @@ impl FileTable {
         };
 
         let min_free_fd = get_min_free_fd();
+        if min_free_fd >= nr_open() {
+            return_errno_with_message!(Errno::EMFILE, "too many open files");
+        }
         let entry = FileTableEntry::new(file, flags);
         self.table.put_at(min_free_fd, entry);
         Ok(min_free_fd as FileDesc)
     }
 
-    pub fn insert(&mut self, item: Arc<dyn FileLike>, flags: FdFlags) -> FileDesc {
+    /// Inserts a file at the lowest available file descriptor.
+    ///
+    /// Returns `EMFILE` if the file descriptor would reach the limit of `nr_open()`.
+    pub fn insert(&mut self, item: Arc<dyn FileLike>, flags: FdFlags) -> Result<FileDesc> {
         let entry = FileTableEntry::new(item, flags);
-        self.table.put(entry) as FileDesc
+        let fd = self.table.put(entry);
+        if fd >= nr_open() {
+            self.table.remove(fd);
+            return_errno_with_message!(Errno::EMFILE, "too many open files");
+        }
+        Ok(fd as FileDesc)
     }
 
     pub fn insert_at(
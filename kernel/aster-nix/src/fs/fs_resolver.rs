@@ -3,11 +3,15 @@
 use alloc::str;
 
 use super::{
+    fanotify::{self, FanEvents},
     file_table::FileDesc,
     inode_handle::InodeHandle,
     path::Dentry,
     rootfs::root_mount,
-    utils::{AccessMode, CreationFlags, InodeMode, InodeType, StatusFlags, PATH_MAX, SYMLINKS_MAX},
+    utils::{
+        AccessMode, CreationFlags, InodeMode, InodeType, Permission, StatusFlags, PATH_MAX,
+        SYMLINKS_MAX,
+    },
 };
 use crate::prelude::*;
 
@@ -15,6 +19,7 @@ use crate::prelude::*;
 pub struct FsResolver {
     root: Arc<Dentry>,
     cwd: Arc<Dentry>,
+    lookup_cache: LookupCache,
 }
 
 impl Clone for FsResolver {
@@ -22,6 +27,7 @@ impl Clone for FsResolver {
         Self {
             root: self.root.clone(),
             cwd: self.cwd.clone(),
+            lookup_cache: LookupCache::new(),
         }
     }
 }
@@ -37,6 +43,7 @@ impl FsResolver {
         Self {
             root: Dentry::new_fs_root(root_mount().clone()),
             cwd: Dentry::new_fs_root(root_mount().clone()),
+            lookup_cache: LookupCache::new(),
         }
     }
 
@@ -58,12 +65,18 @@ impl FsResolver {
     /// Set the root directory
     pub fn set_root(&mut self, dentry: Arc<Dentry>) {
         self.root = dentry;
+        // The cached lookups may depend on the old root.
+        self.lookup_cache = LookupCache::new();
     }
 
     /// Open or create a file inode handler.
     pub fn open(&self, path: &FsPath, flags: u32, mode: u16) -> Result<InodeHandle> {
         let creation_flags = CreationFlags::from_bits_truncate(flags);
         let status_flags = StatusFlags::from_bits_truncate(flags);
+        if status_flags.contains(StatusFlags::O_PATH) {
+            return self.open_path(path, creation_flags);
+        }
+
         let access_mode = AccessMode::from_u32(flags)?;
         let inode_mode = InodeMode::from_bits_truncate(mode);
 
@@ -73,17 +86,18 @@ impl FsResolver {
         let dentry = match self.lookup_inner(path, follow_tail_link) {
             Ok(dentry) => {
                 let inode = dentry.inode();
-                if inode.type_() == InodeType::SymLink
-                    && creation_flags.contains(CreationFlags::O_NOFOLLOW)
-                    && !status_flags.contains(StatusFlags::O_PATH)
-                {
-                    return_errno_with_message!(Errno::ELOOP, "file is a symlink");
-                }
                 if creation_flags.contains(CreationFlags::O_CREAT)
                     && creation_flags.contains(CreationFlags::O_EXCL)
                 {
                     return_errno_with_message!(Errno::EEXIST, "file exists");
                 }
+                // With `O_NOFOLLOW`, the trailing symlink is not followed. So the open
+                // must fail to prevent the symlink from being redirected elsewhere.
+                if inode.type_() == InodeType::SymLink
+                    && creation_flags.contains(CreationFlags::O_NOFOLLOW)
+                {
+                    return_errno_with_message!(Errno::ELOOP, "file is a symlink");
+                }
                 if creation_flags.contains(CreationFlags::O_DIRECTORY)
                     && inode.type_() != InodeType::Dir
                 {
@@ -106,9 +120,9 @@ impl FsResolver {
                 if file_name.ends_with('/') {
                     return_errno_with_message!(Errno::EISDIR, "path refers to a directory");
                 }
-                if !dir_dentry.mode()?.is_writable() {
-                    return_errno_with_message!(Errno::EACCES, "file cannot be created");
-                }
+                dir_dentry
+                    .inode()
+                    .check_permission(Permission::MAY_WRITE | Permission::MAY_EXEC)?;
                 dir_dentry.new_fs_child(&file_name, InodeType::File, inode_mode)?
             }
             Err(e) => return Err(e),
@@ -116,13 +130,32 @@ impl FsResolver {
 
         if creation_flags.contains(CreationFlags::O_TRUNC) {
             dentry.resize(0)?;
+            fanotify::notify(&dentry, FanEvents::MODIFY);
         }
 
-        let inode_handle = InodeHandle::new(dentry, access_mode, status_flags)?;
+        let inode_handle = InodeHandle::new(dentry.clone(), access_mode, status_flags)?;
+        fanotify::notify(&dentry, FanEvents::OPEN);
 
         Ok(inode_handle)
     }
 
+    /// Open a file only as a location in the file system (`O_PATH`).
+    ///
+    /// The file itself is not opened, so the returned handle cannot be used for I/O.
+    /// Other than `O_DIRECTORY` and `O_NOFOLLOW`, the creation flags are ignored.
+    fn open_path(&self, path: &FsPath, creation_flags: CreationFlags) -> Result<InodeHandle> {
+        let follow_tail_link = !creation_flags.contains(CreationFlags::O_NOFOLLOW);
+        let dentry = self.lookup_inner(path, follow_tail_link)?;
+        if creation_flags.contains(CreationFlags::O_DIRECTORY) && dentry.type_() != InodeType::Dir {
+            return_errno_with_message!(
+                Errno::ENOTDIR,
+                "O_DIRECTORY is specified but file is not a directory"
+            );
+        }
+
+        Ok(InodeHandle::new_path(dentry))
+    }
+
     /// Lookup dentry according to FsPath, always follow symlinks
     pub fn lookup(&self, path: &FsPath) -> Result<Arc<Dentry>> {
         self.lookup_inner(path, true)
@@ -134,17 +167,21 @@ impl FsResolver {
     }
 
     fn lookup_inner(&self, path: &FsPath, follow_tail_link: bool) -> Result<Arc<Dentry>> {
+        let mut follows = 0;
         let dentry = match path.inner {
-            FsPathInner::Absolute(path) => {
-                self.lookup_from_parent(&self.root, path.trim_start_matches('/'), follow_tail_link)?
-            }
+            FsPathInner::Absolute(path) => self.lookup_from_parent(
+                &self.root,
+                path.trim_start_matches('/'),
+                follow_tail_link,
+                &mut follows,
+            )?,
             FsPathInner::CwdRelative(path) => {
-                self.lookup_from_parent(&self.cwd, path, follow_tail_link)?
+                self.lookup_from_parent(&self.cwd, path, follow_tail_link, &mut follows)?
             }
             FsPathInner::Cwd => self.cwd.clone(),
             FsPathInner::FdRelative(fd, path) => {
                 let parent = self.lookup_from_fd(fd)?;
-                self.lookup_from_parent(&parent, path, follow_tail_link)?
+                self.lookup_from_parent(&parent, path, follow_tail_link, &mut follows)?
             }
             FsPathInner::Fd(fd) => self.lookup_from_fd(fd)?,
         };
@@ -158,16 +195,20 @@ impl FsResolver {
     /// If `path` ends with `/`, then the returned inode must be a directory inode.
     ///
     /// While looking up the dentry, symbolic links will be followed for
-    /// at most `SYMLINKS_MAX` times.
+    /// at most `SYMLINKS_MAX` times. The number of symlinks that have been followed
+    /// is counted by `follows`, which is shared by all the lookups of a single path
+    /// resolution, so that a symlink loop always fails with `ELOOP`.
     ///
     /// If `follow_tail_link` is true and the trailing component is a symlink,
-    /// it will be followed.
+    /// it will be followed. A trailing component suffixed by `/` is always followed,
+    /// since it must be resolved to a directory.
     /// Symlinks in earlier components of the path will always be followed.
     fn lookup_from_parent(
         &self,
         parent: &Arc<Dentry>,
         relative_path: &str,
         follow_tail_link: bool,
+        follows: &mut usize,
     ) -> Result<Arc<Dentry>> {
         debug_assert!(!relative_path.starts_with('/'));
 
@@ -175,11 +216,26 @@ impl FsResolver {
             return_errno_with_message!(Errno::ENAMETOOLONG, "path is too long");
         }
 
+        if let Some(entry) = self
+            .lookup_cache
+            .get(parent, relative_path, follow_tail_link)
+        {
+            if let Some(dentry) = entry.revalidate(follows)? {
+                return Ok(dentry);
+            }
+            self.lookup_cache.remove(&entry);
+        }
+
+        // The directories searched by the lookup, which are recorded in the lookup cache
+        let mut searched_dirs = Vec::new();
+        let mut is_cacheable = parent.inode().is_dentry_cacheable();
+        let start_follows = *follows;
+
         // To handle symlinks
         let mut link_path = String::new();
-        let mut follows = 0;
 
         // Initialize the first dentry and the relative path
+        let full_path = relative_path;
         let (mut dentry, mut relative_path) = (parent.clone(), relative_path);
 
         while !relative_path.is_empty() {
@@ -191,14 +247,26 @@ impl FsResolver {
                     (relative_path, "", false)
                 };
 
-            // Iterate next dentry
-            let next_dentry = dentry.lookup(next_name)?;
+            // Iterate next dentry. The root directory is its own parent, so that
+            // the lookup can never escape from the root by `..`.
+            let next_dentry = if next_name == ".." && dentry.is_same(&self.root) {
+                dentry.clone()
+            } else {
+                // The generation is read before the lookup, so that a concurrent update
+                // of the directory always invalidates the result.
+                searched_dirs.push((dentry.clone(), dentry.generation()));
+                dentry.lookup(next_name)?
+            };
+            is_cacheable &= next_dentry.inode().is_dentry_cacheable();
             let next_type = next_dentry.type_();
             let next_is_tail = path_remain.is_empty();
 
             // If next inode is a symlink, follow symlinks at most `SYMLINKS_MAX` times.
-            if next_type == InodeType::SymLink && (follow_tail_link || !next_is_tail) {
-                if follows >= SYMLINKS_MAX {
+            if next_type == InodeType::SymLink
+                && !next_dentry.inode().is_magic_link()
+                && (follow_tail_link || !next_is_tail || must_be_dir)
+            {
+                if *follows >= SYMLINKS_MAX {
                     return_errno_with_message!(Errno::ELOOP, "too many symlinks");
                 }
                 let link_path_remain = {
@@ -222,7 +290,7 @@ impl FsResolver {
                 link_path.clear();
                 link_path.push_str(link_path_remain.trim_start_matches('/'));
                 relative_path = &link_path;
-                follows += 1;
+                *follows += 1;
             } else {
                 // If path ends with `/`, the inode must be a directory
                 if must_be_dir && next_type != InodeType::Dir {
@@ -233,6 +301,17 @@ impl FsResolver {
             }
         }
 
+        if is_cacheable && !searched_dirs.is_empty() {
+            self.lookup_cache.insert(LookupEntry {
+                start: parent.clone(),
+                path: String::from(full_path),
+                follow_tail_link,
+                searched_dirs,
+                nr_follows: *follows - start_follows,
+                result: dentry.clone(),
+            });
+        }
+
         Ok(dentry)
     }
 
@@ -259,18 +338,24 @@ impl FsResolver {
         path: &FsPath,
         follow_tail_link: bool,
     ) -> Result<(Arc<Dentry>, String)> {
+        let mut follows = 0;
         let (mut dir_dentry, mut base_name) = match path.inner {
             FsPathInner::Absolute(path) => {
                 let (dir, file_name) = split_path(path);
                 (
-                    self.lookup_from_parent(&self.root, dir.trim_start_matches('/'), true)?,
+                    self.lookup_from_parent(
+                        &self.root,
+                        dir.trim_start_matches('/'),
+                        true,
+                        &mut follows,
+                    )?,
                     String::from(file_name),
                 )
             }
             FsPathInner::CwdRelative(path) => {
                 let (dir, file_name) = split_path(path);
                 (
-                    self.lookup_from_parent(&self.cwd, dir, true)?,
+                    self.lookup_from_parent(&self.cwd, dir, true, &mut follows)?,
                     String::from(file_name),
                 )
             }
@@ -278,7 +363,7 @@ impl FsResolver {
                 let (dir, file_name) = split_path(path);
                 let parent = self.lookup_from_fd(fd)?;
                 (
-                    self.lookup_from_parent(&parent, dir, true)?,
+                    self.lookup_from_parent(&parent, dir, true, &mut follows)?,
                     String::from(file_name),
                 )
             }
@@ -291,7 +376,14 @@ impl FsResolver {
         // Dereference the tail symlinks if needed
         loop {
             match dir_dentry.lookup(base_name.trim_end_matches('/')) {
-                Ok(dentry) if dentry.type_() == InodeType::SymLink => {
+                Ok(dentry)
+                    if dentry.type_() == InodeType::SymLink && !dentry.inode().is_magic_link() =>
+                {
+                    if follows >= SYMLINKS_MAX {
+                        return_errno_with_message!(Errno::ELOOP, "too many symlinks");
+                    }
+                    follows += 1;
+
                     let link = {
                         let mut link = dentry.inode().read_link()?;
                         if link.is_empty() {
@@ -304,11 +396,16 @@ impl FsResolver {
                     };
                     let (dir, file_name) = split_path(&link);
                     if dir.starts_with('/') {
-                        dir_dentry =
-                            self.lookup_from_parent(&self.root, dir.trim_start_matches('/'), true)?;
+                        dir_dentry = self.lookup_from_parent(
+                            &self.root,
+                            dir.trim_start_matches('/'),
+                            true,
+                            &mut follows,
+                        )?;
                         base_name = String::from(file_name);
                     } else {
-                        dir_dentry = self.lookup_from_parent(&dir_dentry, dir, true)?;
+                        dir_dentry =
+                            self.lookup_from_parent(&dir_dentry, dir, true, &mut follows)?;
                         base_name = String::from(file_name);
                     }
                 }
@@ -411,3 +508,100 @@ pub fn split_path(path: &str) -> (&str, &str) {
 
     (dir_path, file_name)
 }
+
+/// The maximum number of entries in the lookup cache of a resolver.
+const LOOKUP_CACHE_CAPACITY: usize = 64;
+
+/// A small cache of the recent path lookups of a resolver.
+///
+/// An entry remembers the dentry that a path has been resolved to, together with
+/// the directories searched on the way and their generations. The entry stays valid
+/// as long as none of these directories changes its generation, so a hit skips the
+/// walk of the dentry tree. The names themselves are only cached by the dentries.
+///
+/// Since the dentries are mount-aware, a cached lookup never crosses the boundary of
+/// the mount tree that it was resolved in.
+struct LookupCache {
+    entries: Mutex<VecDeque<Arc<LookupEntry>>>,
+}
+
+struct LookupEntry {
+    start: Arc<Dentry>,
+    path: String,
+    follow_tail_link: bool,
+    /// The searched directories and their generations before they were searched.
+    searched_dirs: Vec<(Arc<Dentry>, u64)>,
+    /// The number of symlinks followed by the lookup.
+    nr_follows: usize,
+    result: Arc<Dentry>,
+}
+
+impl LookupCache {
+    fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get the entry of the lookup of `path` from `start`.
+    fn get(&self, start: &Dentry, path: &str, follow_tail_link: bool) -> Option<Arc<LookupEntry>> {
+        self.entries
+            .lock()
+            .iter()
+            .find(|entry| entry.matches(start, path, follow_tail_link))
+            .cloned()
+    }
+
+    /// Insert an entry, which replaces the entry of the same lookup, if any.
+    fn insert(&self, entry: LookupEntry) {
+        let mut entries = self.entries.lock();
+        entries.retain(|old| !old.matches(&entry.start, &entry.path, entry.follow_tail_link));
+        if entries.len() >= LOOKUP_CACHE_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(Arc::new(entry));
+    }
+
+    fn remove(&self, entry: &Arc<LookupEntry>) {
+        self.entries.lock().retain(|old| !Arc::ptr_eq(old, entry));
+    }
+}
+
+impl LookupEntry {
+    fn matches(&self, start: &Dentry, path: &str, follow_tail_link: bool) -> bool {
+        self.follow_tail_link == follow_tail_link && self.path == path && self.start.is_same(start)
+    }
+
+    /// Revalidate the entry and return the cached result.
+    ///
+    /// Returns `None` if the entry is stale or the lookup must be redone to report
+    /// an error.
+    fn revalidate(&self, follows: &mut usize) -> Result<Option<Arc<Dentry>>> {
+        if self
+            .searched_dirs
+            .iter()
+            .any(|(dir, generation)| dir.generation() != *generation)
+        {
+            return Ok(None);
+        }
+        if *follows + self.nr_follows > SYMLINKS_MAX {
+            return Ok(None);
+        }
+
+        // The permissions are not cached since they can be changed at any time.
+        for (dir, _) in self.searched_dirs.iter() {
+            dir.inode().check_permission(Permission::MAY_EXEC)?;
+        }
+
+        *follows += self.nr_follows;
+        Ok(Some(self.result.clone()))
+    }
+}
+
+impl Debug for LookupCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("LookupCache")
+            .field("len", &self.entries.lock().len())
+            .finish()
+    }
+}
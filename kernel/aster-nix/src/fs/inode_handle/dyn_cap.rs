@@ -4,7 +4,13 @@ use aster_rights::TRights;
 use inherit_methods_macro::inherit_methods;
 
 use super::*;
-use crate::prelude::*;
+use crate::{
+    fs::{
+        fanotify::{self, FanEvents},
+        path::PerMountFlags,
+    },
+    prelude::*,
+};
 
 impl InodeHandle<Rights> {
     pub fn new(
@@ -13,17 +19,22 @@ impl InodeHandle<Rights> {
         status_flags: StatusFlags,
     ) -> Result<Self> {
         let inode = dentry.inode();
-        if access_mode.is_readable() && !inode.mode()?.is_readable() {
-            return_errno_with_message!(Errno::EACCES, "File is not readable");
+        if access_mode.is_readable() {
+            inode.check_permission(Permission::MAY_READ)?;
         }
-        if access_mode.is_writable() && !inode.mode()?.is_writable() {
-            return_errno_with_message!(Errno::EACCES, "File is not writable");
+        if access_mode.is_writable() {
+            inode.check_permission(Permission::MAY_WRITE)?;
         }
         if access_mode.is_writable() && inode.type_() == InodeType::Dir {
             return_errno_with_message!(Errno::EISDIR, "Directory cannot open to write");
         }
 
-        let file_io = if let Some(device) = inode.as_device() {
+        let file_io = if let Some(file_io) = inode.open(access_mode, status_flags) {
+            Some(file_io?)
+        } else if let Some(device) = inode.as_device() {
+            if dentry.mount_node().flags().contains(PerMountFlags::NODEV) {
+                return_errno_with_message!(Errno::EACCES, "the device is on a nodev mount");
+            }
             device.open()?
         } else {
             None
@@ -35,10 +46,27 @@ impl InodeHandle<Rights> {
             offset: Mutex::new(0),
             access_mode,
             status_flags: AtomicU32::new(status_flags.bits()),
+            _file_count: FileCount::new(),
         });
         Ok(Self(inner, Rights::from(access_mode)))
     }
 
+    /// Create a handle that only refers to the location of the file (`O_PATH`).
+    ///
+    /// The handle has no rights, so any I/O on it fails with `EBADF`. Since the file is
+    /// not really opened, no permission on the file is required.
+    pub fn new_path(dentry: Arc<Dentry>) -> Self {
+        let inner = Arc::new(InodeHandle_ {
+            dentry,
+            file_io: None,
+            offset: Mutex::new(0),
+            access_mode: AccessMode::O_RDONLY,
+            status_flags: AtomicU32::new(StatusFlags::O_PATH.bits()),
+            _file_count: FileCount::new(),
+        });
+        Self(inner, Rights::empty())
+    }
+
     pub fn to_static<R1: TRights>(self) -> Result<InodeHandle<R1>> {
         let rights = Rights::from_bits(R1::BITS).ok_or(Error::new(Errno::EBADF))?;
         if !self.1.contains(rights) {
@@ -72,17 +100,37 @@ impl Clone for InodeHandle<Rights> {
 #[inherit_methods(from = "self.0")]
 impl FileLike for InodeHandle<Rights> {
     fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents;
-    fn ioctl(&self, cmd: IoctlCmd, arg: usize) -> Result<i32>;
     fn status_flags(&self) -> StatusFlags;
     fn access_mode(&self) -> AccessMode;
     fn metadata(&self) -> Metadata;
     fn mode(&self) -> Result<InodeMode>;
-    fn set_mode(&self, mode: InodeMode) -> Result<()>;
     fn owner(&self) -> Result<Uid>;
-    fn set_owner(&self, uid: Uid) -> Result<()>;
     fn group(&self) -> Result<Gid>;
-    fn set_group(&self, gid: Gid) -> Result<()>;
-    fn seek(&self, seek_from: SeekFrom) -> Result<usize>;
+
+    fn set_mode(&self, mode: InodeMode) -> Result<()> {
+        self.0.check_not_path_only()?;
+        self.0.set_mode(mode)
+    }
+
+    fn set_owner(&self, uid: Uid) -> Result<()> {
+        self.0.check_not_path_only()?;
+        self.0.set_owner(uid)
+    }
+
+    fn set_group(&self, gid: Gid) -> Result<()> {
+        self.0.check_not_path_only()?;
+        self.0.set_group(gid)
+    }
+
+    fn ioctl(&self, cmd: IoctlCmd, arg: usize) -> Result<i32> {
+        self.0.check_not_path_only()?;
+        self.0.ioctl(cmd, arg)
+    }
+
+    fn seek(&self, seek_from: SeekFrom) -> Result<usize> {
+        self.0.check_not_path_only()?;
+        self.0.seek(seek_from)
+    }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
         if !self.1.contains(Rights::READ) {
@@ -95,7 +143,11 @@ impl FileLike for InodeHandle<Rights> {
         if !self.1.contains(Rights::WRITE) {
             return_errno_with_message!(Errno::EBADF, "File is not writable");
         }
-        self.0.write(buf)
+        let len = self.0.write(buf)?;
+        if len > 0 {
+            fanotify::notify(self.dentry(), FanEvents::MODIFY);
+        }
+        Ok(len)
     }
 
     fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<usize> {
@@ -109,14 +161,21 @@ impl FileLike for InodeHandle<Rights> {
         if !self.1.contains(Rights::WRITE) {
             return_errno_with_message!(Errno::EBADF, "file is not writable");
         }
-        self.0.write_at(offset, buf)
+        let len = self.0.write_at(offset, buf)?;
+        if len > 0 {
+            fanotify::notify(self.dentry(), FanEvents::MODIFY);
+        }
+        Ok(len)
     }
 
     fn resize(&self, new_size: usize) -> Result<()> {
+        self.0.check_not_path_only()?;
         if !self.1.contains(Rights::WRITE) {
             return_errno_with_message!(Errno::EINVAL, "File is not writable");
         }
-        self.0.resize(new_size)
+        self.0.resize(new_size)?;
+        fanotify::notify(self.dentry(), FanEvents::MODIFY);
+        Ok(())
     }
 
     fn set_status_flags(&self, new_status_flags: StatusFlags) -> Result<()> {
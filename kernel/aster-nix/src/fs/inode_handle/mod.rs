@@ -16,15 +16,15 @@ use crate::{
     events::IoEvents,
     fs::{
         device::Device,
-        file_handle::FileLike,
+        file_handle::{FileCount, FileLike},
         path::Dentry,
         utils::{
-            AccessMode, DirentVisitor, InodeMode, InodeType, IoctlCmd, Metadata, SeekFrom,
-            StatusFlags,
+            AccessMode, DirentVisitor, InodeMode, InodeType, IoctlCmd, Metadata, Permission,
+            SeekFrom, StatusFlags,
         },
     },
     prelude::*,
-    process::{signal::Poller, Gid, Uid},
+    process::{credentials::UserNamespace, signal::Poller, Gid, Uid},
 };
 
 #[derive(Debug)]
@@ -39,6 +39,7 @@ struct InodeHandle_ {
     offset: Mutex<usize>,
     access_mode: AccessMode,
     status_flags: AtomicU32,
+    _file_count: FileCount,
 }
 
 impl InodeHandle_ {
@@ -164,6 +165,19 @@ impl InodeHandle_ {
         StatusFlags::from_bits(bits).unwrap()
     }
 
+    /// Returns whether the file is opened with `O_PATH`.
+    pub fn is_path_only(&self) -> bool {
+        self.status_flags().contains(StatusFlags::O_PATH)
+    }
+
+    /// Fails with `EBADF` if the file is opened with `O_PATH`.
+    pub fn check_not_path_only(&self) -> Result<()> {
+        if self.is_path_only() {
+            return_errno_with_message!(Errno::EBADF, "file is opened as a path");
+        }
+        Ok(())
+    }
+
     pub fn set_status_flags(&self, new_status_flags: StatusFlags) {
         self.status_flags
             .store(new_status_flags.bits(), Ordering::Relaxed);
@@ -191,6 +205,24 @@ impl InodeHandle_ {
 
         self.dentry.inode().ioctl(cmd, arg)
     }
+
+    fn pipe_capacity(&self) -> Result<usize> {
+        let Some(ref file_io) = self.file_io else {
+            return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+        };
+        file_io.pipe_capacity()
+    }
+
+    fn set_pipe_capacity(&self, size: usize) -> Result<usize> {
+        let Some(ref file_io) = self.file_io else {
+            return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+        };
+        file_io.set_pipe_capacity(size)
+    }
+
+    fn user_ns(&self) -> Option<Arc<UserNamespace>> {
+        self.file_io.as_ref()?.user_ns()
+    }
 }
 
 #[inherit_methods(from = "self.dentry")]
@@ -221,6 +253,28 @@ impl<R> InodeHandle<R> {
     pub fn dentry(&self) -> &Arc<Dentry> {
         &self.0.dentry
     }
+
+    /// Returns whether the file is opened with `O_PATH`.
+    pub fn is_path_only(&self) -> bool {
+        self.0.is_path_only()
+    }
+
+    /// Returns the capacity in bytes of the pipe if the file is a named pipe.
+    pub fn pipe_capacity(&self) -> Result<usize> {
+        self.0.pipe_capacity()
+    }
+
+    /// Sets the capacity of the pipe to at least `size` bytes if the file is a named pipe.
+    ///
+    /// On success, this method returns the new capacity in bytes.
+    pub fn set_pipe_capacity(&self, size: usize) -> Result<usize> {
+        self.0.set_pipe_capacity(size)
+    }
+
+    /// Returns the user namespace if the file is opened from `/proc/[pid]/ns/user`.
+    pub fn user_ns(&self) -> Option<Arc<UserNamespace>> {
+        self.0.user_ns()
+    }
 }
 
 pub trait FileIo: Send + Sync + 'static {
@@ -233,4 +287,21 @@ pub trait FileIo: Send + Sync + 'static {
     fn ioctl(&self, cmd: IoctlCmd, arg: usize) -> Result<i32> {
         return_errno_with_message!(Errno::EINVAL, "ioctl is not supported");
     }
+
+    /// Returns the capacity in bytes of the pipe behind the file.
+    fn pipe_capacity(&self) -> Result<usize> {
+        return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+    }
+
+    /// Sets the capacity of the pipe behind the file to at least `size` bytes.
+    ///
+    /// On success, this method returns the new capacity in bytes.
+    fn set_pipe_capacity(&self, size: usize) -> Result<usize> {
+        return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+    }
+
+    /// Returns the user namespace that the file refers to.
+    fn user_ns(&self) -> Option<Arc<UserNamespace>> {
+        None
+    }
 }
@@ -4,15 +4,18 @@ pub mod devpts;
 pub mod epoll;
 pub mod exfat;
 pub mod ext2;
+pub mod fanotify;
 pub mod file_handle;
 pub mod file_table;
 pub mod fs_resolver;
 pub mod inode_handle;
+pub mod named_pipe;
 pub mod path;
 pub mod pipe;
 pub mod procfs;
 pub mod ramfs;
 pub mod rootfs;
+pub mod sysfs;
 pub mod utils;
 
 use aster_block::BlockDevice;
@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Named pipes (FIFOs).
+//!
+//! A named pipe is a pipe that has a name in the file system. The pipe behind a named pipe
+//! is created when the named pipe is opened for the first time, and it is shared by all the
+//! files opening the named pipe until all of them are closed.
+
+use super::{
+    file_handle::FileLike,
+    inode_handle::FileIo,
+    pipe::{self, PipeReader, PipeWriter},
+    utils::{AccessMode, StatusFlags},
+};
+use crate::{events::IoEvents, prelude::*, process::signal::Poller};
+
+pub struct NamedPipe {
+    pipe: Mutex<Option<(PipeEnd<PipeReader>, PipeEnd<PipeWriter>)>>,
+}
+
+/// One end of the pipe behind a named pipe.
+enum PipeEnd<T> {
+    /// The end has not been opened yet.
+    ///
+    /// It is kept alive by the named pipe, so that the peer end does not see it closed.
+    Unopened(Arc<T>),
+    /// The end has been opened. It is closed once all the files opening it are closed.
+    Opened(Weak<T>),
+}
+
+impl<T> PipeEnd<T> {
+    fn is_closed(&self) -> bool {
+        match self {
+            PipeEnd::Unopened(_) => false,
+            PipeEnd::Opened(end) => end.strong_count() == 0,
+        }
+    }
+
+    fn is_opened(&self) -> bool {
+        match self {
+            PipeEnd::Unopened(_) => false,
+            PipeEnd::Opened(end) => end.strong_count() > 0,
+        }
+    }
+
+    fn open(&mut self) -> Option<Arc<T>> {
+        let end = match self {
+            PipeEnd::Unopened(end) => end.clone(),
+            PipeEnd::Opened(end) => end.upgrade()?,
+        };
+        *self = PipeEnd::Opened(Arc::downgrade(&end));
+        Some(end)
+    }
+}
+
+impl NamedPipe {
+    pub fn new() -> Self {
+        Self {
+            pipe: Mutex::new(None),
+        }
+    }
+
+    /// Opens the named pipe with the access mode and the status flags.
+    ///
+    /// Like Linux, opening a named pipe for writing only in the non-blocking mode fails
+    /// with `ENXIO` if the named pipe has not been opened for reading.
+    ///
+    /// FIXME: Opening a named pipe in the blocking mode should wait for the peer end to be
+    /// opened. Currently, it returns immediately.
+    pub fn open(
+        &self,
+        access_mode: AccessMode,
+        status_flags: StatusFlags,
+    ) -> Result<Arc<dyn FileIo>> {
+        let mut pipe = self.pipe.lock();
+
+        if !access_mode.is_readable()
+            && status_flags.contains(StatusFlags::O_NONBLOCK)
+            && !pipe.as_ref().is_some_and(|(reader, _)| reader.is_opened())
+        {
+            return_errno_with_message!(Errno::ENXIO, "the named pipe has no readers");
+        }
+
+        // A new pipe is needed if the end to open has been closed, since a closed end
+        // cannot be opened again.
+        let is_closed = pipe.as_ref().map_or(true, |(reader, writer)| {
+            (access_mode.is_readable() && reader.is_closed())
+                || (access_mode.is_writable() && writer.is_closed())
+        });
+        if is_closed {
+            let (reader, writer) = pipe::new_pair()?;
+            *pipe = Some((
+                PipeEnd::Unopened(Arc::new(reader)),
+                PipeEnd::Unopened(Arc::new(writer)),
+            ));
+        }
+
+        let (reader_end, writer_end) = pipe.as_mut().unwrap();
+        let reader = if access_mode.is_readable() {
+            reader_end.open()
+        } else {
+            None
+        };
+        let writer = if access_mode.is_writable() {
+            writer_end.open()
+        } else {
+            None
+        };
+
+        // The status flags of a pipe end are shared by all the files opening it.
+        let nonblocking_flags = status_flags & StatusFlags::O_NONBLOCK;
+        if let Some(reader) = reader.as_ref() {
+            reader.set_status_flags(nonblocking_flags)?;
+        }
+        if let Some(writer) = writer.as_ref() {
+            writer.set_status_flags(nonblocking_flags)?;
+        }
+
+        Ok(Arc::new(NamedPipeHandle { reader, writer }))
+    }
+}
+
+impl Default for NamedPipe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An opened named pipe.
+struct NamedPipeHandle {
+    reader: Option<Arc<PipeReader>>,
+    writer: Option<Arc<PipeWriter>>,
+}
+
+impl FileIo for NamedPipeHandle {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let Some(reader) = self.reader.as_ref() else {
+            return_errno_with_message!(Errno::EBADF, "the named pipe is not opened for reading");
+        };
+        reader.read(buf)
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let Some(writer) = self.writer.as_ref() else {
+            return_errno_with_message!(Errno::EBADF, "the named pipe is not opened for writing");
+        };
+        writer.write(buf)
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        let mut events = IoEvents::empty();
+        if let Some(reader) = self.reader.as_ref() {
+            events |= reader.poll(mask, poller);
+        }
+        if let Some(writer) = self.writer.as_ref() {
+            events |= writer.poll(mask, poller);
+        }
+        events
+    }
+
+    fn pipe_capacity(&self) -> Result<usize> {
+        match (self.reader.as_ref(), self.writer.as_ref()) {
+            (Some(reader), _) => Ok(reader.capacity()),
+            (None, Some(writer)) => Ok(writer.capacity()),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    fn set_pipe_capacity(&self, size: usize) -> Result<usize> {
+        match (self.reader.as_ref(), self.writer.as_ref()) {
+            (Some(reader), _) => reader.set_capacity(size),
+            (None, Some(writer)) => writer.set_capacity(size),
+            (None, None) => unreachable!(),
+        }
+    }
+}
@@ -4,7 +4,7 @@
 #![allow(unused_variables)]
 
 use core::{
-    sync::atomic::{AtomicU32, Ordering},
+    sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
     time::Duration,
 };
 
@@ -13,8 +13,8 @@ use inherit_methods_macro::inherit_methods;
 use crate::{
     fs::{
         device::Device,
-        path::mount::MountNode,
-        utils::{FileSystem, Inode, InodeMode, InodeType, Metadata, NAME_MAX},
+        path::mount::{MountNode, PerMountFlags},
+        utils::{FileSystem, FsFlags, Inode, InodeMode, InodeType, Metadata, Permission, NAME_MAX},
     },
     prelude::*,
     process::{Gid, Uid},
@@ -24,6 +24,12 @@ lazy_static! {
     static ref DCACHE: Mutex<BTreeMap<DentryKey, Arc<Dentry_>>> = Mutex::new(BTreeMap::new());
 }
 
+/// The maximum number of negative dentries in all the directories.
+const MAX_NEGATIVE_DENTRIES: usize = 4096;
+
+/// The number of negative dentries in all the directories.
+static NR_NEGATIVE_DENTRIES: AtomicUsize = AtomicUsize::new(0);
+
 /// The Dentry_ cache to accelerate path lookup
 pub struct Dentry_ {
     inode: Arc<dyn Inode>,
@@ -31,6 +37,11 @@ pub struct Dentry_ {
     this: Weak<Dentry_>,
     children: Mutex<Children>,
     flags: AtomicU32,
+    /// The generation of the names in this directory.
+    ///
+    /// It is increased whenever a name is removed from or replaced in this directory,
+    /// or a mount is attached to or detached from a name in this directory.
+    generation: AtomicU64,
 }
 
 impl Dentry_ {
@@ -49,6 +60,7 @@ impl Dentry_ {
         Arc::new_cyclic(|weak_self| Self {
             inode,
             flags: AtomicU32::new(DentryFlags::empty().bits()),
+            generation: AtomicU64::new(0),
             name_and_parent: match options {
                 DentryOptions::Leaf(name_and_parent) => RwLock::new(Some(name_and_parent)),
                 _ => RwLock::new(None),
@@ -104,6 +116,16 @@ impl Dentry_ {
         DentryFlags::from_bits(flags).unwrap()
     }
 
+    /// Get the generation of the names in this directory.
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Increase the generation of the names in this directory.
+    fn bump_generation(&self) {
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
     /// Check if this dentry is a descendant (child, grandchild, or
     /// great-grandchild, etc.) of another dentry.
     pub fn is_descendant_of(&self, ancestor: &Arc<Self>) -> bool {
@@ -159,15 +181,28 @@ impl Dentry_ {
     }
 
     /// Lookup a Dentry_ from DCACHE.
-    pub fn lookup_via_cache(&self, name: &str) -> Option<Arc<Dentry_>> {
+    ///
+    /// Returns `ENOENT` if the name is known not to exist.
+    pub fn lookup_via_cache(&self, name: &str) -> Result<Option<Arc<Dentry_>>> {
         let mut children = self.children.lock();
-        children.find_dentry(name)
+        if children.is_negative(name) {
+            return_errno_with_message!(Errno::ENOENT, "the name is a negative dentry");
+        }
+        Ok(children.find_dentry(name))
     }
 
     /// Lookup a Dentry_ from filesystem.
     pub fn lookuop_via_fs(&self, name: &str) -> Result<Arc<Dentry_>> {
         let mut children = self.children.lock();
-        let inode = self.inode.lookup(name)?;
+        let inode = match self.inode.lookup(name) {
+            Ok(inode) => inode,
+            Err(err) => {
+                if err.error() == Errno::ENOENT && self.is_negative_dentry_cacheable() {
+                    children.insert_negative(name);
+                }
+                return Err(err);
+            }
+        };
         let inner = Self::new(
             inode,
             DentryOptions::Leaf((String::from(name), self.this())),
@@ -176,6 +211,11 @@ impl Dentry_ {
         Ok(inner)
     }
 
+    /// Whether the names that do not exist in this directory can be cached.
+    fn is_negative_dentry_cacheable(&self) -> bool {
+        self.inode.is_dentry_cacheable() && self.fs().flags().contains(FsFlags::NEGATIVE_DENTRY)
+    }
+
     fn insert_dentry(&self, child_dentry: &Arc<Dentry_>) {
         let mut children = self.children.lock();
         children.insert_dentry(child_dentry);
@@ -231,6 +271,7 @@ impl Dentry_ {
         let _ = children.find_dentry_with_checking_mountpoint(name)?;
         self.inode.unlink(name)?;
         children.delete_dentry(name);
+        self.bump_generation();
         Ok(())
     }
 
@@ -243,6 +284,7 @@ impl Dentry_ {
         let _ = children.find_dentry_with_checking_mountpoint(name)?;
         self.inode.rmdir(name)?;
         children.delete_dentry(name);
+        self.bump_generation();
         Ok(())
     }
 
@@ -274,6 +316,7 @@ impl Dentry_ {
                     children.delete_dentry(new_name);
                 }
             }
+            self.bump_generation();
         } else {
             // Self and new_dir are different Dentry_
             let (mut self_children, mut new_dir_children) =
@@ -286,11 +329,15 @@ impl Dentry_ {
                     self_children.delete_dentry(old_name);
                     dentry.set_name_and_parent(new_name, new_dir.this());
                     new_dir_children.insert_dentry(dentry);
+                    // The parent of the moved directory, i.e., its `..`, is changed.
+                    dentry.bump_generation();
                 }
                 None => {
                     new_dir_children.delete_dentry(new_name);
                 }
             }
+            self.bump_generation();
+            new_dir.bump_generation();
         }
         Ok(())
     }
@@ -365,16 +412,22 @@ enum DentryOptions {
 
 struct Children {
     inner: BTreeMap<String, Weak<Dentry_>>,
+    /// The names that are known not to exist, i.e., the negative dentries.
+    negatives: BTreeSet<String>,
 }
 
 impl Children {
     pub fn new() -> Self {
         Self {
             inner: BTreeMap::new(),
+            negatives: BTreeSet::new(),
         }
     }
 
     pub fn insert_dentry(&mut self, dentry: &Arc<Dentry_>) {
+        // The name exists now, even if the dentry is not cacheable.
+        self.remove_negative(&dentry.name());
+
         // Do not cache it in DCACHE and children if is not cacheable.
         // When we look up it from the parent, it will always be newly created.
         if !dentry.inode().is_dentry_cacheable() {
@@ -386,6 +439,8 @@ impl Children {
     }
 
     pub fn delete_dentry(&mut self, name: &str) -> Option<Arc<Dentry_>> {
+        // The name may be replaced by the one that is not cached (e.g., by `rename`).
+        self.remove_negative(name);
         self.inner
             .remove(name)
             .and_then(|d| d.upgrade())
@@ -403,6 +458,26 @@ impl Children {
         }
     }
 
+    /// Remember that `name` does not exist.
+    ///
+    /// The name is not remembered if there are too many negative dentries.
+    pub fn insert_negative(&mut self, name: &str) {
+        let nr_negatives = NR_NEGATIVE_DENTRIES.fetch_add(1, Ordering::Relaxed);
+        if nr_negatives >= MAX_NEGATIVE_DENTRIES || !self.negatives.insert(String::from(name)) {
+            NR_NEGATIVE_DENTRIES.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn remove_negative(&mut self, name: &str) {
+        if self.negatives.remove(name) {
+            NR_NEGATIVE_DENTRIES.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn is_negative(&self, name: &str) -> bool {
+        self.negatives.contains(name)
+    }
+
     pub fn find_dentry_with_checking_mountpoint(
         &mut self,
         name: &str,
@@ -417,6 +492,12 @@ impl Children {
     }
 }
 
+impl Drop for Children {
+    fn drop(&mut self) {
+        NR_NEGATIVE_DENTRIES.fetch_sub(self.negatives.len(), Ordering::Relaxed);
+    }
+}
+
 fn write_lock_children_on_two_dentries<'a>(
     this: &'a Dentry_,
     other: &'a Dentry_,
@@ -455,7 +536,7 @@ impl Dentry {
     }
 
     /// Internal constructor.
-    fn new(mount_node: Arc<MountNode>, inner: Arc<Dentry_>) -> Arc<Self> {
+    pub(super) fn new(mount_node: Arc<MountNode>, inner: Arc<Dentry_>) -> Arc<Self> {
         Arc::new_cyclic(|weak_self| Self {
             mount_node,
             inner,
@@ -468,9 +549,7 @@ impl Dentry {
         if self.inner.inode().type_() != InodeType::Dir {
             return_errno!(Errno::ENOTDIR);
         }
-        if !self.inner.inode().mode()?.is_executable() {
-            return_errno!(Errno::EACCES);
-        }
+        self.inner.inode().check_permission(Permission::MAY_EXEC)?;
         if name.len() > NAME_MAX {
             return_errno!(Errno::ENAMETOOLONG);
         }
@@ -479,7 +558,7 @@ impl Dentry {
             "." => self.this(),
             ".." => self.effective_parent().unwrap_or_else(|| self.this()),
             name => {
-                let children_inner = self.inner.lookup_via_cache(name);
+                let children_inner = self.inner.lookup_via_cache(name)?;
                 match children_inner {
                     Some(inner) => Self::new(self.mount_node().clone(), inner.clone()),
                     None => {
@@ -515,6 +594,31 @@ impl Dentry {
         path
     }
 
+    /// Get the path of this Dentry relative to `root`.
+    ///
+    /// Like `abs_path`, it will resolve the mountpoint automatically. But the path stops
+    /// at `root` rather than the root of the mount tree.
+    ///
+    /// Returns `None` if `root` is not an ancestor of this Dentry.
+    pub fn path_from(&self, root: &Self) -> Option<String> {
+        let mut names = Vec::new();
+        let mut dentry = self.this();
+        while !dentry.is_same(root) {
+            let parent = dentry.effective_parent()?;
+            names.push(dentry.effective_name());
+            dentry = parent;
+        }
+
+        if names.is_empty() {
+            return Some(String::from("/"));
+        }
+        let path = names
+            .iter()
+            .rev()
+            .fold(String::new(), |path, name| path + "/" + name);
+        Some(path)
+    }
+
     /// Get the effective name of Dentry.
     ///
     /// If it is the root of mount, it will go up to the mountpoint to get the name
@@ -575,6 +679,25 @@ impl Dentry {
         }
     }
 
+    /// Get the generation of the names in this directory.
+    ///
+    /// The lookups of the names in this directory that are made at the same generation
+    /// give the same results.
+    pub fn generation(&self) -> u64 {
+        self.inner.generation()
+    }
+
+    /// Invalidate the lookups of this Dentry after a mount is attached to or
+    /// detached from it.
+    ///
+    /// If this Dentry is the root of a mount, its name lives in the directory of
+    /// the mountpoint below, so the generation of that directory is increased.
+    pub(super) fn invalidate_mountpoint_lookups(&self) {
+        if let Some(parent) = self.effective_parent() {
+            parent.inner.bump_generation();
+        }
+    }
+
     /// Make this Dentry's inner to be a mountpoint,
     /// and set the mountpoint of the child mount to this Dentry's inner.
     pub(super) fn set_mountpoint(&self, child_mount: Arc<MountNode>) {
@@ -590,14 +713,26 @@ impl Dentry {
     ///
     /// Return the mounted child mount.
     pub fn mount(&self, fs: Arc<dyn FileSystem>) -> Result<Arc<MountNode>> {
+        self.mount_with_flags(fs, PerMountFlags::empty())
+    }
+
+    /// Mount the fs on this Dentry with the per-mount flags.
+    ///
+    /// See `mount` for the details.
+    pub fn mount_with_flags(
+        &self,
+        fs: Arc<dyn FileSystem>,
+        flags: PerMountFlags,
+    ) -> Result<Arc<MountNode>> {
         if self.inner.inode().type_() != InodeType::Dir {
             return_errno!(Errno::ENOTDIR);
         }
         if self.effective_parent().is_none() {
             return_errno_with_message!(Errno::EINVAL, "can not mount on root");
         }
-        let child_mount = self.mount_node().mount(fs, &self.this())?;
+        let child_mount = self.mount_node().mount(fs, flags, &self.this())?;
         self.set_mountpoint(child_mount.clone());
+        self.invalidate_mountpoint_lookups();
         Ok(child_mount)
     }
 
@@ -667,6 +802,55 @@ impl Dentry {
         Ok(())
     }
 
+    /// Make the mount of this Dentry a root mount and attach the mount of `old_root`
+    /// to `put_old`.
+    ///
+    /// This Dentry must be the root of a mount other than the one of `old_root`,
+    /// and `put_old` must be at or underneath this Dentry.
+    pub fn pivot_root(&self, old_root: &Arc<Self>, put_old: &Arc<Self>) -> Result<()> {
+        if self.type_() != InodeType::Dir || put_old.type_() != InodeType::Dir {
+            return_errno!(Errno::ENOTDIR);
+        }
+        if Arc::ptr_eq(&self.mount_node, &old_root.mount_node)
+            || Arc::ptr_eq(&put_old.mount_node, &old_root.mount_node)
+        {
+            return_errno_with_message!(Errno::EBUSY, "new_root or put_old is on the current root");
+        }
+        if !old_root.inner.is_root_of_mount() {
+            return_errno_with_message!(Errno::EINVAL, "the current root is not a mount point");
+        }
+        if !self.inner.is_root_of_mount() || self.mount_node.parent().is_none() {
+            return_errno_with_message!(Errno::EINVAL, "new_root is not a mount point");
+        }
+        if !self.is_descendant_of(old_root) {
+            return_errno_with_message!(Errno::EINVAL, "new_root is not under the current root");
+        }
+        if !put_old.is_same(self) && !put_old.is_descendant_of(self) {
+            return_errno_with_message!(Errno::EINVAL, "put_old is not under new_root");
+        }
+
+        self.mount_node.detach_as_root();
+        old_root.mount_node.graft_mount_node_tree(put_old)?;
+        Ok(())
+    }
+
+    /// Check if this Dentry is a descendant of `ancestor` in the mount tree.
+    pub fn is_descendant_of(&self, ancestor: &Self) -> bool {
+        let mut parent = self.effective_parent();
+        while let Some(p) = parent {
+            if p.is_same(ancestor) {
+                return true;
+            }
+            parent = p.effective_parent();
+        }
+        false
+    }
+
+    /// Returns whether `self` and `other` represent the same location in the mount tree.
+    pub fn is_same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.mount_node, &other.mount_node) && Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
     /// Get the arc reference to self.
     fn this(&self) -> Arc<Self> {
         self.this.upgrade().unwrap()
@@ -676,6 +860,13 @@ impl Dentry {
     pub fn mount_node(&self) -> &Arc<MountNode> {
         &self.mount_node
     }
+
+    /// Get the inode of the parent directory in the same file system.
+    ///
+    /// Returns None if it is the root of the file system.
+    pub fn parent_inode(&self) -> Option<Arc<dyn Inode>> {
+        self.inner.parent().map(|parent| parent.inode().clone())
+    }
 }
 
 #[inherit_methods(from = "self.inner")]
@@ -17,6 +17,8 @@ pub struct MountNode {
     mountpoint_dentry: RwLock<Option<Arc<Dentry_>>>,
     /// The associated FS.
     fs: Arc<dyn FileSystem>,
+    /// The flags that restrict the accesses through this mount.
+    flags: PerMountFlags,
     /// The parent mount node.
     parent: RwLock<Option<Weak<MountNode>>>,
     /// Child mount nodes which are mounted on one dentry of self.
@@ -34,7 +36,7 @@ impl MountNode {
     /// It is allowed to create a mount node even if the fs has been provided to another
     /// mount node. It is the fs's responsibility to ensure the data consistency.
     pub fn new_root(fs: Arc<dyn FileSystem>) -> Arc<Self> {
-        Self::new(fs, None)
+        Self::new(fs, PerMountFlags::empty(), None)
     }
 
     /// The internal constructor.
@@ -45,13 +47,18 @@ impl MountNode {
     /// avoiding fixed mountpoint limitations. This allows the root mount node to
     /// exist without a mountpoint, ensuring uniformity and security, while all other
     /// mount nodes must be explicitly assigned a mountpoint to maintain structural integrity.
-    fn new(fs: Arc<dyn FileSystem>, parent_mount: Option<Weak<MountNode>>) -> Arc<Self> {
+    fn new(
+        fs: Arc<dyn FileSystem>,
+        flags: PerMountFlags,
+        parent_mount: Option<Weak<MountNode>>,
+    ) -> Arc<Self> {
         Arc::new_cyclic(|weak_self| Self {
             root_dentry: Dentry_::new_root(fs.root_inode()),
             mountpoint_dentry: RwLock::new(None),
             parent: RwLock::new(parent_mount),
             children: Mutex::new(BTreeMap::new()),
             fs,
+            flags,
             this: weak_self.clone(),
         })
     }
@@ -67,7 +74,12 @@ impl MountNode {
     /// mountpoint. It is the fs's responsibility to ensure the data consistency.
     ///
     /// Return the mounted child mount.
-    pub fn mount(&self, fs: Arc<dyn FileSystem>, mountpoint: &Arc<Dentry>) -> Result<Arc<Self>> {
+    pub fn mount(
+        &self,
+        fs: Arc<dyn FileSystem>,
+        flags: PerMountFlags,
+        mountpoint: &Arc<Dentry>,
+    ) -> Result<Arc<Self>> {
         if !Arc::ptr_eq(mountpoint.mount_node(), &self.this()) {
             return_errno_with_message!(Errno::EINVAL, "mountpoint not belongs to this");
         }
@@ -76,7 +88,7 @@ impl MountNode {
         }
 
         let key = mountpoint.key();
-        let child_mount = Self::new(fs, Some(Arc::downgrade(mountpoint.mount_node())));
+        let child_mount = Self::new(fs, flags, Some(Arc::downgrade(mountpoint.mount_node())));
         self.children.lock().insert(key, child_mount.clone());
         Ok(child_mount)
     }
@@ -94,6 +106,7 @@ impl MountNode {
             .lock()
             .remove(&mountpoint.key())
             .ok_or_else(|| Error::with_message(Errno::ENOENT, "can not find child mount"))?;
+        mountpoint.invalidate_mountpoint_lookups();
         Ok(child_mount)
     }
 
@@ -108,6 +121,7 @@ impl MountNode {
             parent: RwLock::new(None),
             children: Mutex::new(BTreeMap::new()),
             fs: self.fs.clone(),
+            flags: self.flags,
             this: weak_self.clone(),
         })
     }
@@ -161,11 +175,21 @@ impl MountNode {
     fn detach_mount_node(&self) {
         if let Some(parent) = self.parent() {
             let parent = parent.upgrade().unwrap();
-            parent
-                .children
-                .lock()
-                .remove(&self.mountpoint_dentry().unwrap().key());
+            let mountpoint_dentry = self.mountpoint_dentry().unwrap();
+            parent.children.lock().remove(&mountpoint_dentry.key());
+            Dentry::new(parent, mountpoint_dentry).invalidate_mountpoint_lookups();
+        }
+    }
+
+    /// Detach the mount node from the parent mount node and make it a root mount node.
+    ///
+    /// The original mountpoint will no longer be a mountpoint.
+    pub(super) fn detach_as_root(&self) {
+        self.detach_mount_node();
+        if let Some(mountpoint_dentry) = self.mountpoint_dentry.write().take() {
+            mountpoint_dentry.clear_mountpoint();
         }
+        *self.parent.write() = None;
     }
 
     /// Attach the mount node to the mountpoint.
@@ -178,6 +202,7 @@ impl MountNode {
             .insert(key, self.this());
         self.set_parent(mountpoint.mount_node());
         mountpoint.set_mountpoint(self.this());
+        mountpoint.invalidate_mountpoint_lookups();
     }
 
     /// Graft the mount node tree to the mountpoint.
@@ -229,6 +254,11 @@ impl MountNode {
         Ok(())
     }
 
+    /// Get the flags of this mount node.
+    pub fn flags(&self) -> PerMountFlags {
+        self.flags
+    }
+
     /// Try to get the parent mount node.
     pub fn parent(&self) -> Option<Weak<Self>> {
         self.parent.read().as_ref().cloned()
@@ -263,3 +293,15 @@ impl Debug for MountNode {
             .finish()
     }
 }
+
+bitflags! {
+    /// The flags that apply to a single mount rather than to the whole FS.
+    pub struct PerMountFlags: u32 {
+        /// Ignore the `set_uid` and `set_gid` bits of the files.
+        const NOSUID = 1 << 1;
+        /// Disallow opening the device files.
+        const NODEV  = 1 << 2;
+        /// Disallow executing the files.
+        const NOEXEC = 1 << 3;
+    }
+}
@@ -1,25 +1,204 @@
 // SPDX-License-Identifier: MPL-2.0
 
-#![allow(dead_code)]
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use super::{
-    file_handle::FileLike,
-    utils::{AccessMode, Consumer, InodeMode, InodeType, Metadata, Producer, StatusFlags},
+    file_handle::{FileCount, FileLike},
+    utils::{AccessMode, Channel, Consumer, InodeMode, InodeType, Metadata, Producer, StatusFlags},
 };
 use crate::{
     events::{IoEvents, Observer},
     prelude::*,
-    process::{signal::Poller, Gid, Uid},
+    process::{
+        credentials,
+        credentials::capabilities::CapSet,
+        signal::{constants::SIGPIPE, signals::kernel::KernelSignal, Poller},
+        Gid, Uid,
+    },
     time::clocks::RealTimeCoarseClock,
 };
 
+/// The default capacity of a pipe in bytes.
+const DEFAULT_PIPE_SIZE: usize = 16 * PAGE_SIZE;
+
+/// The capacity of a pipe in bytes if the user has too many pages in pipes.
+const MIN_PIPE_SIZE: usize = 2 * PAGE_SIZE;
+
+/// The number of pages in pipes that a user can have before new pipes are limited
+/// to `MIN_PIPE_SIZE` and pipes can no longer be enlarged.
+const PIPE_USER_PAGES_SOFT: usize = 16384;
+
+/// The maximum size of an atomic write, which is also the maximum size of a packet in
+/// the packet mode.
+pub const PIPE_BUF: usize = 4096;
+
+/// The maximum capacity in bytes that a pipe can have.
+const PIPE_SIZE_LIMIT: usize = 1 << 31;
+
+/// The maximum capacity in bytes that an unprivileged user can set for a pipe.
+static PIPE_MAX_SIZE: AtomicUsize = AtomicUsize::new(1024 * 1024);
+
+/// The number of pages in pipes of each user.
+static USER_PIPE_PAGES: Mutex<BTreeMap<Uid, usize>> = Mutex::new(BTreeMap::new());
+
+/// Returns the maximum capacity in bytes that an unprivileged user can set for a pipe.
+pub fn pipe_max_size() -> usize {
+    PIPE_MAX_SIZE.load(Ordering::Relaxed)
+}
+
+/// Sets the maximum capacity in bytes that an unprivileged user can set for a pipe.
+///
+/// Like the capacity of a pipe, the size is rounded up to a power of two that is at
+/// least a page.
+pub fn set_pipe_max_size(size: usize) -> Result<()> {
+    PIPE_MAX_SIZE.store(round_pipe_size(size)?, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Rounds `size` up to a power of two that is at least a page.
+fn round_pipe_size(size: usize) -> Result<usize> {
+    if size > PIPE_SIZE_LIMIT {
+        return_errno_with_message!(Errno::EINVAL, "the pipe size is too large");
+    }
+    Ok(size.max(PAGE_SIZE).next_power_of_two())
+}
+
+/// Creates a pair of connected pipe ends.
+pub fn new_pair() -> Result<(PipeReader, PipeWriter)> {
+    new_pair_with_flags(StatusFlags::empty())
+}
+
+/// Creates a pair of connected pipe ends with the status flags applied to both ends.
+///
+/// If `O_DIRECT` is specified, the pipe works in the packet mode, where each write
+/// (or each `PIPE_BUF` bytes of a larger write) becomes a packet, and each read
+/// returns at most one packet.
+pub fn new_pair_with_flags(flags: StatusFlags) -> Result<(PipeReader, PipeWriter)> {
+    let pages = Arc::new(PipePages::new(credentials().ruid()));
+    let size = pages.nr_pages() * PAGE_SIZE;
+
+    let channel_flags = flags - StatusFlags::O_DIRECT;
+    let (consumer, producer) = if flags.contains(StatusFlags::O_DIRECT) {
+        // Like Linux, each page of the pipe holds one packet.
+        let (producer, consumer) =
+            Channel::with_capacity_and_flags(size / PAGE_SIZE, channel_flags)?.split();
+        (
+            PipeConsumer::Packet(consumer),
+            PipeProducer::Packet(producer),
+        )
+    } else {
+        let (producer, consumer) = Channel::with_capacity_and_flags(size, channel_flags)?.split();
+        (
+            PipeConsumer::Stream(consumer),
+            PipeProducer::Stream(producer),
+        )
+    };
+
+    Ok((
+        PipeReader::new(consumer, pages.clone()),
+        PipeWriter::new(producer, pages),
+    ))
+}
+
+/// The pages of a pipe, which are charged to the user who creates the pipe.
+struct PipePages {
+    user: Uid,
+    nr_pages: Mutex<usize>,
+}
+
+impl PipePages {
+    fn new(user: Uid) -> Self {
+        let mut user_pipe_pages = USER_PIPE_PAGES.lock();
+        let used_pages = user_pipe_pages.entry(user).or_insert(0);
+
+        let size = if *used_pages >= PIPE_USER_PAGES_SOFT {
+            MIN_PIPE_SIZE
+        } else {
+            DEFAULT_PIPE_SIZE.min(pipe_max_size())
+        };
+        let nr_pages = size / PAGE_SIZE;
+        *used_pages += nr_pages;
+
+        Self {
+            user,
+            nr_pages: Mutex::new(nr_pages),
+        }
+    }
+
+    fn nr_pages(&self) -> usize {
+        *self.nr_pages.lock()
+    }
+
+    /// Resizes the pipe to hold at least `size` bytes with `set_capacity`.
+    ///
+    /// The size is rounded up to a power of two that is at least a page. Without
+    /// `CAP_SYS_RESOURCE`, the size cannot exceed `pipe_max_size()`, and the pipe cannot
+    /// be enlarged if the user has too many pages in pipes.
+    ///
+    /// On success, this method returns the new capacity in bytes.
+    fn resize(&self, size: usize, set_capacity: impl FnOnce(usize) -> Result<()>) -> Result<usize> {
+        let new_size = round_pipe_size(size)?;
+        let is_privileged = credentials().capable(CapSet::SYS_RESOURCE);
+        if new_size > pipe_max_size() && !is_privileged {
+            return_errno_with_message!(Errno::EPERM, "the pipe size exceeds the maximum");
+        }
+        let new_nr_pages = new_size / PAGE_SIZE;
+
+        let mut nr_pages = self.nr_pages.lock();
+        let mut user_pipe_pages = USER_PIPE_PAGES.lock();
+        let used_pages = user_pipe_pages.get_mut(&self.user).unwrap();
+        if new_nr_pages > *nr_pages
+            && *used_pages - *nr_pages + new_nr_pages > PIPE_USER_PAGES_SOFT
+            && !is_privileged
+        {
+            return_errno_with_message!(Errno::EPERM, "the user has too many pages in pipes");
+        }
+
+        set_capacity(new_size)?;
+        *used_pages = *used_pages - *nr_pages + new_nr_pages;
+        *nr_pages = new_nr_pages;
+
+        Ok(new_size)
+    }
+}
+
+impl Drop for PipePages {
+    fn drop(&mut self) {
+        let mut user_pipe_pages = USER_PIPE_PAGES.lock();
+        let used_pages = user_pipe_pages.get_mut(&self.user).unwrap();
+        *used_pages -= *self.nr_pages.lock();
+        if *used_pages == 0 {
+            user_pipe_pages.remove(&self.user);
+        }
+    }
+}
+
 pub struct PipeReader {
-    consumer: Consumer<u8>,
+    consumer: PipeConsumer,
+    pages: Arc<PipePages>,
+    _file_count: FileCount,
 }
 
 impl PipeReader {
-    pub fn new(consumer: Consumer<u8>) -> Self {
-        Self { consumer }
+    fn new(consumer: PipeConsumer, pages: Arc<PipePages>) -> Self {
+        Self {
+            consumer,
+            pages,
+            _file_count: FileCount::new(),
+        }
+    }
+
+    /// Returns the capacity of the pipe in bytes.
+    pub fn capacity(&self) -> usize {
+        self.consumer.capacity()
+    }
+
+    /// Sets the capacity of the pipe to at least `size` bytes.
+    ///
+    /// On success, this method returns the new capacity in bytes.
+    pub fn set_capacity(&self, size: usize) -> Result<usize> {
+        self.pages
+            .resize(size, |new_size| self.consumer.set_capacity(new_size))
     }
 }
 
@@ -81,18 +260,54 @@ impl FileLike for PipeReader {
 }
 
 pub struct PipeWriter {
-    producer: Producer<u8>,
+    producer: PipeProducer,
+    pages: Arc<PipePages>,
+    _file_count: FileCount,
 }
 
 impl PipeWriter {
-    pub fn new(producer: Producer<u8>) -> Self {
-        Self { producer }
+    fn new(producer: PipeProducer, pages: Arc<PipePages>) -> Self {
+        Self {
+            producer,
+            pages,
+            _file_count: FileCount::new(),
+        }
+    }
+
+    /// Returns the capacity of the pipe in bytes.
+    pub fn capacity(&self) -> usize {
+        self.producer.capacity()
+    }
+
+    /// Sets the capacity of the pipe to at least `size` bytes.
+    ///
+    /// On success, this method returns the new capacity in bytes.
+    pub fn set_capacity(&self, size: usize) -> Result<usize> {
+        self.pages
+            .resize(size, |new_size| self.producer.set_capacity(new_size))
     }
+
+    /// Writes the bytes in `buf` to the pipe without blocking, regardless of the status
+    /// flags.
+    pub fn try_write(&self, buf: &[u8]) -> Result<usize> {
+        check_broken_pipe(self.producer.write(buf, true))
+    }
+}
+
+/// Sends `SIGPIPE` to the current thread if the write fails because the read end is closed.
+fn check_broken_pipe(res: Result<usize>) -> Result<usize> {
+    if let Err(err) = &res
+        && err.error() == Errno::EPIPE
+        && let Some(posix_thread) = current_thread!().as_posix_thread()
+    {
+        posix_thread.enqueue_signal(Box::new(KernelSignal::new(SIGPIPE)));
+    }
+    res
 }
 
 impl FileLike for PipeWriter {
     fn write(&self, buf: &[u8]) -> Result<usize> {
-        self.producer.write(buf)
+        check_broken_pipe(self.producer.write(buf, false))
     }
 
     fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
@@ -147,13 +362,203 @@ impl FileLike for PipeWriter {
     }
 }
 
-fn should_io_return(res: &Result<usize>, is_nonblocking: bool) -> bool {
-    if is_nonblocking {
-        return true;
+/// The read end of a pipe.
+enum PipeConsumer {
+    /// Data is read as a byte stream.
+    Stream(Consumer<u8>),
+    /// Data is read packet by packet.
+    Packet(Consumer<Vec<u8>>),
+}
+
+impl PipeConsumer {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let consumer = match self {
+            PipeConsumer::Stream(consumer) => return consumer.read(buf),
+            PipeConsumer::Packet(consumer) => consumer,
+        };
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let packet = match consumer.pop() {
+            Ok(packet) => packet,
+            // All the data has been read and the write end has been closed.
+            Err(err) if err.error() == Errno::EPIPE && consumer.is_peer_shutdown() => {
+                return Ok(0);
+            }
+            Err(err) => return Err(err),
+        };
+        // Like Linux, the excess bytes are discarded if the buffer is smaller than the packet.
+        let len = packet.len().min(buf.len());
+        buf[..len].copy_from_slice(&packet[..len]);
+        Ok(len)
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.capacity(),
+            PipeConsumer::Packet(consumer) => consumer.capacity() * PAGE_SIZE,
+        }
+    }
+
+    fn set_capacity(&self, new_size: usize) -> Result<()> {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.set_capacity(new_size),
+            PipeConsumer::Packet(consumer) => consumer.set_capacity(new_size / PAGE_SIZE),
+        }
     }
-    match res {
-        Ok(_) => true,
-        Err(e) if e.error() == Errno::EAGAIN => false,
-        Err(_) => true,
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.poll(mask, poller),
+            PipeConsumer::Packet(consumer) => consumer.poll(mask, poller),
+        }
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.status_flags(),
+            PipeConsumer::Packet(consumer) => consumer.status_flags(),
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        // The packet mode is decided when the pipe is created.
+        let new_flags = new_flags - StatusFlags::O_DIRECT;
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.set_status_flags(new_flags),
+            PipeConsumer::Packet(consumer) => consumer.set_status_flags(new_flags),
+        }
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.register_observer(observer, mask),
+            PipeConsumer::Packet(consumer) => consumer.register_observer(observer, mask),
+        }
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        match self {
+            PipeConsumer::Stream(consumer) => consumer.unregister_observer(observer),
+            PipeConsumer::Packet(consumer) => consumer.unregister_observer(observer),
+        }
+    }
+}
+
+/// The write end of a pipe.
+enum PipeProducer {
+    /// Data is written as a byte stream.
+    Stream(Producer<u8>),
+    /// Data is written packet by packet.
+    Packet(Producer<Vec<u8>>),
+}
+
+impl PipeProducer {
+    /// Writes the bytes in `buf` to the pipe.
+    ///
+    /// If `is_nonblocking` is true, this method never blocks, regardless of the status
+    /// flags.
+    fn write(&self, buf: &[u8], is_nonblocking: bool) -> Result<usize> {
+        let producer = match self {
+            // Like Linux, writes of at most `PIPE_BUF` bytes are atomic.
+            PipeProducer::Stream(producer) if buf.len() <= PIPE_BUF => {
+                return if is_nonblocking {
+                    producer.try_write_atomic(buf)
+                } else {
+                    producer.write_atomic(buf)
+                };
+            }
+            PipeProducer::Stream(producer) => {
+                return if is_nonblocking {
+                    producer.try_write(buf)
+                } else {
+                    producer.write(buf)
+                };
+            }
+            PipeProducer::Packet(producer) => producer,
+        };
+
+        let mut written_len = 0;
+        for packet in buf.chunks(PIPE_BUF) {
+            let res = if is_nonblocking {
+                producer.try_push(packet.to_vec())
+            } else {
+                producer.push(packet.to_vec())
+            };
+            if let Err((err, _)) = res {
+                if written_len > 0 {
+                    break;
+                }
+                return Err(err);
+            }
+            written_len += packet.len();
+        }
+        Ok(written_len)
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            PipeProducer::Stream(producer) => producer.capacity(),
+            PipeProducer::Packet(producer) => producer.capacity() * PAGE_SIZE,
+        }
+    }
+
+    fn set_capacity(&self, new_size: usize) -> Result<()> {
+        match self {
+            PipeProducer::Stream(producer) => producer.set_capacity(new_size),
+            PipeProducer::Packet(producer) => producer.set_capacity(new_size / PAGE_SIZE),
+        }
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        match self {
+            PipeProducer::Stream(producer) => producer.poll(mask, poller),
+            PipeProducer::Packet(producer) => producer.poll(mask, poller),
+        }
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        match self {
+            PipeProducer::Stream(producer) => producer.status_flags(),
+            PipeProducer::Packet(producer) => producer.status_flags(),
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        // The packet mode is decided when the pipe is created.
+        let new_flags = new_flags - StatusFlags::O_DIRECT;
+        match self {
+            PipeProducer::Stream(producer) => producer.set_status_flags(new_flags),
+            PipeProducer::Packet(producer) => producer.set_status_flags(new_flags),
+        }
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        match self {
+            PipeProducer::Stream(producer) => producer.register_observer(observer, mask),
+            PipeProducer::Packet(producer) => producer.register_observer(observer, mask),
+        }
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        match self {
+            PipeProducer::Stream(producer) => producer.unregister_observer(observer),
+            PipeProducer::Packet(producer) => producer.unregister_observer(observer),
+        }
     }
 }
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use ostd::cpu::num_cpus;
+
+use crate::{
+    arch::cpu::CpuInformation,
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/cpuinfo`.
+pub struct CpuInfoFileOps;
+
+impl CpuInfoFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for CpuInfoFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        // FIXME: The information is collected on the current CPU, which assumes
+        // that all the CPUs are identical.
+        let cpu_information = CpuInformation::new();
+        let output: String = (0..num_cpus())
+            .map(|processor| cpu_information.format(processor))
+            .collect();
+        Ok(output.into_bytes())
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use ostd::mm::stat::{mem_free, mem_total};
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/meminfo`.
+pub struct MemInfoFileOps;
+
+impl MemInfoFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for MemInfoFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let total = mem_total() / 1024;
+        let free = mem_free() / 1024;
+
+        // TODO: Report the page cache and the reclaimable memory. Before that,
+        // all the free memory is considered to be available.
+        let fields = [
+            ("MemTotal", total),
+            ("MemFree", free),
+            ("MemAvailable", free),
+            ("Buffers", 0),
+            ("Cached", 0),
+            ("SwapTotal", 0),
+            ("SwapFree", 0),
+        ];
+
+        let mut output = String::new();
+        for (key, value_kb) in fields {
+            // The same format as Linux, e.g., "MemTotal:       16314456 kB".
+            output.push_str(&format!("{:<16}{:>8} kB\n", format!("{}:", key), value_kb));
+        }
+        Ok(output.into_bytes())
+    }
+}
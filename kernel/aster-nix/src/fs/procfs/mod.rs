@@ -3,9 +3,16 @@
 use core::sync::atomic::{AtomicU64, Ordering};
 
 use self::{
+    cpuinfo::CpuInfoFileOps,
+    meminfo::MemInfoFileOps,
     pid::PidDirOps,
     self_::SelfSymOps,
+    stat::ProcStatFileOps,
+    sys::SysDirOps,
     template::{DirOps, ProcDir, ProcDirBuilder, ProcSymBuilder, SymOps},
+    thread_self::ThreadSelfSymOps,
+    uptime::UptimeFileOps,
+    version::VersionFileOps,
 };
 use crate::{
     events::Observer,
@@ -14,9 +21,16 @@ use crate::{
     process::{process_table, process_table::PidEvent, Pid},
 };
 
+mod cpuinfo;
+mod meminfo;
 mod pid;
 mod self_;
-mod template;
+mod stat;
+mod sys;
+pub(super) mod template;
+mod thread_self;
+mod uptime;
+mod version;
 
 /// Magic number.
 const PROC_MAGIC: u64 = 0x9fa0;
@@ -40,7 +54,7 @@ impl ProcFS {
         })
     }
 
-    pub(in crate::fs::procfs) fn alloc_id(&self) -> u64 {
+    pub(in crate::fs) fn alloc_id(&self) -> u64 {
         self.inode_allocator.fetch_add(1, Ordering::SeqCst)
     }
 }
@@ -91,6 +105,20 @@ impl DirOps for RootDirOps {
     fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
         let child = if name == "self" {
             SelfSymOps::new_inode(this_ptr.clone())
+        } else if name == "thread-self" {
+            ThreadSelfSymOps::new_inode(this_ptr.clone())
+        } else if name == "sys" {
+            SysDirOps::new_inode(this_ptr.clone())
+        } else if name == "meminfo" {
+            MemInfoFileOps::new_inode(this_ptr.clone())
+        } else if name == "cpuinfo" {
+            CpuInfoFileOps::new_inode(this_ptr.clone())
+        } else if name == "stat" {
+            ProcStatFileOps::new_inode(this_ptr.clone())
+        } else if name == "uptime" {
+            UptimeFileOps::new_inode(this_ptr.clone())
+        } else if name == "version" {
+            VersionFileOps::new_inode(this_ptr.clone())
         } else if let Ok(pid) = name.parse::<Pid>() {
             let process_ref =
                 process_table::get_process(pid).ok_or_else(|| Error::new(Errno::ENOENT))?;
@@ -108,6 +136,20 @@ impl DirOps for RootDirOps {
         };
         let mut cached_children = this.cached_children().write();
         cached_children.put_entry_if_not_found("self", || SelfSymOps::new_inode(this_ptr.clone()));
+        cached_children.put_entry_if_not_found("thread-self", || {
+            ThreadSelfSymOps::new_inode(this_ptr.clone())
+        });
+        cached_children.put_entry_if_not_found("sys", || SysDirOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("meminfo", || MemInfoFileOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("cpuinfo", || CpuInfoFileOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("stat", || ProcStatFileOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("uptime", || UptimeFileOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("version", || VersionFileOps::new_inode(this_ptr.clone()));
 
         for process in process_table::process_table().iter() {
             let pid = process.pid().to_string();
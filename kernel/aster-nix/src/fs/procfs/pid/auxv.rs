@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/auxv`.
+pub struct AuxvFileOps(Arc<Process>);
+
+impl AuxvFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for AuxvFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let auxv_output = if self.0.is_zombie() {
+            // Returns 0 bytes for zombie process.
+            Vec::new()
+        } else {
+            let Ok(auxvec) = self.0.vm().init_stack_reader().auxvec() else {
+                return Ok(Vec::new());
+            };
+            auxvec
+                .into_iter()
+                .flat_map(|(key, value)| key.to_ne_bytes().into_iter().chain(value.to_ne_bytes()))
+                .collect()
+        };
+        Ok(auxv_output)
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet, posix_thread::PosixThreadExt},
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/environ`.
+pub struct EnvironFileOps(Arc<Process>);
+
+impl EnvironFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+
+    /// Checks whether the current process may read the environment of the process.
+    ///
+    /// The environment often contains secrets, so it can only be read by the owner
+    /// of the process or a process with `CAP_SYS_PTRACE`. The check is done here
+    /// since the inodes in procfs are always owned by root.
+    fn check_permission(&self) -> Result<()> {
+        let Some(main_thread) = self.0.main_thread() else {
+            return_errno_with_message!(Errno::ESRCH, "the process has no main thread");
+        };
+        let target_credentials = main_thread.as_posix_thread().unwrap().credentials();
+
+        let credentials = credentials();
+        let uid = credentials.fsuid();
+        if (uid == target_credentials.ruid()
+            && uid == target_credentials.euid()
+            && uid == target_credentials.suid())
+            || credentials.capable(CapSet::SYS_PTRACE)
+        {
+            return Ok(());
+        }
+
+        return_errno_with_message!(Errno::EACCES, "the environment cannot be read");
+    }
+}
+
+impl FileOps for EnvironFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        self.check_permission()?;
+
+        let environ_output = if self.0.is_zombie() {
+            // Returns 0 characters for zombie process.
+            Vec::new()
+        } else {
+            let Ok(envp_cstrs) = self.0.vm().init_stack_reader().envp() else {
+                return Ok(Vec::new());
+            };
+            envp_cstrs
+                .into_iter()
+                .flat_map(|c_str| c_str.into_bytes_with_nul().into_iter())
+                .collect()
+        };
+        Ok(environ_output)
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::{fmt::Write, ops::Range};
+
+use crate::{
+    fs::{
+        device::DeviceId,
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    vm::{perms::VmPerms, vmar::VmMappingInfo},
+    Process,
+};
+
+/// The column where the pathname starts, as in Linux.
+const PATHNAME_COLUMN: usize = 73;
+
+/// Represents the inode at `/proc/[pid]/maps`.
+pub struct MapsFileOps(Arc<Process>);
+
+impl MapsFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+
+    /// Returns the pathname of a mapping.
+    ///
+    /// File-backed mappings are named by the path of the file, while the stack
+    /// and the heap are labeled specially. Other anonymous mappings have no name.
+    fn pathname(&self, mapping: &VmMappingInfo) -> String {
+        if let Some((dentry, _)) = &mapping.file {
+            return dentry.abs_path();
+        }
+
+        let is_within = |range: &Range<Vaddr>| {
+            range.start <= mapping.range.start && mapping.range.end <= range.end
+        };
+        if is_within(&self.0.vm().stack_range()) {
+            String::from("[stack]")
+        } else if is_within(&self.0.vm().heap().range()) {
+            String::from("[heap]")
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl FileOps for MapsFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        // A zombie process has no memory mapped.
+        if self.0.is_zombie() {
+            return Ok(Vec::new());
+        }
+
+        let mut output = String::new();
+        for mapping in self.0.root_vmar().mappings() {
+            let perm = |perm: VmPerms, ch: char| {
+                if mapping.perms.contains(perm) {
+                    ch
+                } else {
+                    '-'
+                }
+            };
+            let (offset, dev, ino) = match &mapping.file {
+                Some((dentry, offset)) => {
+                    let metadata = dentry.metadata();
+                    (*offset, DeviceId::from(metadata.dev), metadata.ino)
+                }
+                None => (0, DeviceId::new(0, 0), 0),
+            };
+
+            let line_start = output.len();
+            write!(
+                output,
+                "{:08x}-{:08x} {}{}{}{} {:08x} {:02x}:{:02x} {} ",
+                mapping.range.start,
+                mapping.range.end,
+                perm(VmPerms::READ, 'r'),
+                perm(VmPerms::WRITE, 'w'),
+                perm(VmPerms::EXEC, 'x'),
+                if mapping.is_shared { 's' } else { 'p' },
+                offset,
+                dev.major(),
+                dev.minor(),
+                ino,
+            )
+            .unwrap();
+
+            let pathname = self.pathname(&mapping);
+            if !pathname.is_empty() {
+                let line_len = output.len() - line_start;
+                for _ in line_len..PATHNAME_COLUMN {
+                    output.push(' ');
+                }
+                output.push_str(&pathname);
+            }
+            output.push('\n');
+        }
+        Ok(output.into_bytes())
+    }
+}
@@ -1,6 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use self::{cmdline::CmdlineFileOps, comm::CommFileOps, exe::ExeSymOps, fd::FdDirOps};
+use aster_util::slot_vec::SlotVec;
+
+use self::{
+    auxv::AuxvFileOps, cmdline::CmdlineFileOps, comm::CommFileOps, environ::EnvironFileOps,
+    exe::ExeSymOps, fd::FdDirOps, gid_map::GidMapFileOps, maps::MapsFileOps, ns::NsDirOps,
+    oom_score_adj::OomScoreAdjFileOps, statm::StatmFileOps, task::TaskDirOps,
+    uid_map::UidMapFileOps,
+};
 use super::template::{DirOps, ProcDir, ProcDirBuilder};
 use crate::{
     events::Observer,
@@ -9,13 +16,22 @@ use crate::{
         utils::{DirEntryVecExt, Inode},
     },
     prelude::*,
-    process::Process,
+    process::{credentials::UserNamespace, posix_thread::PosixThreadExt, Process},
 };
 
+mod auxv;
 mod cmdline;
 mod comm;
+mod environ;
 mod exe;
 mod fd;
+mod gid_map;
+mod maps;
+mod ns;
+mod oom_score_adj;
+mod statm;
+mod task;
+mod uid_map;
 
 /// Represents the inode at `/proc/[pid]`.
 pub struct PidDirOps(Arc<Process>);
@@ -46,14 +62,10 @@ impl Observer<FdEvents> for ProcDir<PidDirOps> {
 
 impl DirOps for PidDirOps {
     fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
-        let inode = match name {
-            "exe" => ExeSymOps::new_inode(self.0.clone(), this_ptr.clone()),
-            "comm" => CommFileOps::new_inode(self.0.clone(), this_ptr.clone()),
-            "fd" => FdDirOps::new_inode(self.0.clone(), this_ptr.clone()),
-            "cmdline" => CmdlineFileOps::new_inode(self.0.clone(), this_ptr.clone()),
-            _ => return_errno!(Errno::ENOENT),
-        };
-        Ok(inode)
+        if name == "task" {
+            return Ok(TaskDirOps::new_inode(self.0.clone(), this_ptr.clone()));
+        }
+        lookup_process_entry(&self.0, this_ptr, name)
     }
 
     fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
@@ -62,17 +74,73 @@ impl DirOps for PidDirOps {
             this.downcast_ref::<ProcDir<PidDirOps>>().unwrap().this()
         };
         let mut cached_children = this.cached_children().write();
-        cached_children.put_entry_if_not_found("exe", || {
-            ExeSymOps::new_inode(self.0.clone(), this_ptr.clone())
+        populate_process_entries(&self.0, this_ptr.clone(), &mut cached_children);
+        cached_children.put_entry_if_not_found("task", || {
+            TaskDirOps::new_inode(self.0.clone(), this_ptr.clone())
         });
-        cached_children.put_entry_if_not_found("comm", || {
-            CommFileOps::new_inode(self.0.clone(), this_ptr.clone())
-        });
-        cached_children.put_entry_if_not_found("fd", || {
-            FdDirOps::new_inode(self.0.clone(), this_ptr.clone())
-        });
-        cached_children.put_entry_if_not_found("cmdline", || {
-            CmdlineFileOps::new_inode(self.0.clone(), this_ptr.clone())
+    }
+}
+
+/// The names of the entries that describe the whole process.
+///
+/// These entries are present in both `/proc/[pid]` and `/proc/[pid]/task/[tid]`.
+const PROCESS_ENTRIES: &[&str] = &[
+    "exe",
+    "comm",
+    "fd",
+    "cmdline",
+    "environ",
+    "auxv",
+    "statm",
+    "maps",
+    "uid_map",
+    "gid_map",
+    "ns",
+    "oom_score_adj",
+];
+
+/// Creates the inode of the process entry called `name`.
+fn lookup_process_entry(
+    process: &Arc<Process>,
+    this_ptr: Weak<dyn Inode>,
+    name: &str,
+) -> Result<Arc<dyn Inode>> {
+    let inode = match name {
+        "exe" => ExeSymOps::new_inode(process.clone(), this_ptr),
+        "comm" => CommFileOps::new_inode(process.clone(), this_ptr),
+        "fd" => FdDirOps::new_inode(process.clone(), this_ptr),
+        "cmdline" => CmdlineFileOps::new_inode(process.clone(), this_ptr),
+        "environ" => EnvironFileOps::new_inode(process.clone(), this_ptr),
+        "auxv" => AuxvFileOps::new_inode(process.clone(), this_ptr),
+        "statm" => StatmFileOps::new_inode(process.clone(), this_ptr),
+        "maps" => MapsFileOps::new_inode(process.clone(), this_ptr),
+        "uid_map" => UidMapFileOps::new_inode(process.clone(), this_ptr),
+        "gid_map" => GidMapFileOps::new_inode(process.clone(), this_ptr),
+        "ns" => NsDirOps::new_inode(process.clone(), this_ptr),
+        "oom_score_adj" => OomScoreAdjFileOps::new_inode(process.clone(), this_ptr),
+        _ => return_errno!(Errno::ENOENT),
+    };
+    Ok(inode)
+}
+
+/// Puts all the process entries into `cached_children` if they are not found.
+fn populate_process_entries(
+    process: &Arc<Process>,
+    this_ptr: Weak<dyn Inode>,
+    cached_children: &mut SlotVec<(String, Arc<dyn Inode>)>,
+) {
+    for name in PROCESS_ENTRIES {
+        cached_children.put_entry_if_not_found(name, || {
+            lookup_process_entry(process, this_ptr.clone(), name).unwrap()
         });
     }
 }
+
+/// Gets the user namespace of a process, which is the one of its main thread.
+fn process_user_ns(process: &Process) -> Result<Arc<UserNamespace>> {
+    let main_thread = process
+        .main_thread()
+        .ok_or_else(|| Error::with_message(Errno::ESRCH, "the process has exited"))?;
+    let posix_thread = main_thread.as_posix_thread().unwrap();
+    Ok(posix_thread.credentials().user_ns())
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use super::process_user_ns;
+use crate::{
+    events::IoEvents,
+    fs::{
+        inode_handle::FileIo,
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder, ProcSymBuilder, SymOps},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+    process::{credentials::UserNamespace, signal::Poller},
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/ns`.
+pub struct NsDirOps(Arc<Process>);
+
+impl NsDirOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl DirOps for NsDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "user" => UserNsSymOps::new_inode(self.0.clone(), this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<NsDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children.put_entry_if_not_found("user", || {
+            UserNsSymOps::new_inode(self.0.clone(), this_ptr.clone())
+        });
+    }
+}
+
+/// Represents the inode at `/proc/[pid]/ns/user`.
+///
+/// The link reads like `user:[4026531837]`, where the number identifies the user
+/// namespace of the process. Opening the link gives a file that refers to the namespace,
+/// which can be passed to `setns`.
+struct UserNsSymOps(Arc<Process>);
+
+impl UserNsSymOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcSymBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl SymOps for UserNsSymOps {
+    fn read_link(&self) -> Result<String> {
+        let user_ns = process_user_ns(&self.0)?;
+        Ok(format!("user:[{}]", user_ns.ino()))
+    }
+
+    fn is_magic_link(&self) -> bool {
+        true
+    }
+
+    fn open(&self) -> Option<Result<Arc<dyn FileIo>>> {
+        let file = process_user_ns(&self.0).map(|user_ns| {
+            let file: Arc<dyn FileIo> = Arc::new(UserNsFile(user_ns));
+            file
+        });
+        Some(file)
+    }
+}
+
+/// The file opened from `/proc/[pid]/ns/user`, which holds a reference to the namespace.
+struct UserNsFile(Arc<UserNamespace>);
+
+impl FileIo for UserNsFile {
+    fn read(&self, _buf: &mut [u8]) -> Result<usize> {
+        return_errno_with_message!(Errno::EINVAL, "the namespace file cannot be read");
+    }
+
+    fn write(&self, _buf: &[u8]) -> Result<usize> {
+        return_errno_with_message!(Errno::EINVAL, "the namespace file cannot be written");
+    }
+
+    fn poll(&self, mask: IoEvents, _poller: Option<&Poller>) -> IoEvents {
+        let events = IoEvents::IN | IoEvents::OUT;
+        events & mask
+    }
+
+    fn user_ns(&self) -> Option<Arc<UserNamespace>> {
+        Some(self.0.clone())
+    }
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::sync::atomic::Ordering;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+    Process,
+};
+
+/// The minimum value of `oom_score_adj`, which disables the OOM killing of the process.
+const OOM_SCORE_ADJ_MIN: i32 = -1000;
+/// The maximum value of `oom_score_adj`.
+const OOM_SCORE_ADJ_MAX: i32 = 1000;
+
+/// Represents the inode at `/proc/[pid]/oom_score_adj`.
+pub struct OomScoreAdjFileOps(Arc<Process>);
+
+impl OomScoreAdjFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o644))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for OomScoreAdjFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let oom_score_adj = self.0.oom_score_adj().load(Ordering::Relaxed);
+        Ok(format!("{}\n", oom_score_adj).into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let oom_score_adj = core::str::from_utf8(buf)
+            .ok()
+            .and_then(|content| content.trim().parse::<i32>().ok())
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "the value is invalid"))?;
+        if !(OOM_SCORE_ADJ_MIN..=OOM_SCORE_ADJ_MAX).contains(&oom_score_adj) {
+            return_errno_with_message!(Errno::EINVAL, "the value is out of range");
+        }
+
+        // Disabling the OOM killing is privileged.
+        if oom_score_adj == OOM_SCORE_ADJ_MIN && !credentials().capable(CapSet::SYS_RESOURCE) {
+            return_errno_with_message!(Errno::EACCES, "CAP_SYS_RESOURCE is required");
+        }
+
+        self.0
+            .oom_score_adj()
+            .store(oom_score_adj, Ordering::Relaxed);
+        Ok(buf.len())
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    vm::vmar::VmarStat,
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/statm`.
+pub struct StatmFileOps(Arc<Process>);
+
+impl StatmFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for StatmFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let stat = if self.0.is_zombie() {
+            // A zombie process has no memory mapped.
+            VmarStat::default()
+        } else {
+            self.0.root_vmar().stat()
+        };
+
+        // The fields are size, resident, shared, text, lib, data and dt, counted in pages.
+        // The lib and dt fields are unused since Linux 2.6 and are always zero.
+        let output = format!(
+            "{} {} {} {} 0 {} 0\n",
+            stat.total, stat.resident, stat.shared, stat.text, stat.data
+        );
+        Ok(output.into_bytes())
+    }
+}
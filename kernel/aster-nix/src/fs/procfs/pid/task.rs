@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{lookup_process_entry, populate_process_entries};
+use crate::{
+    fs::{
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+    thread::{Thread, Tid},
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/task`.
+pub struct TaskDirOps(Arc<Process>);
+
+impl TaskDirOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self(process_ref))
+            .parent(parent)
+            // The task directories must be volatile, because the threads come and go.
+            .volatile()
+            .build()
+            .unwrap()
+    }
+
+    fn alive_threads(&self) -> Vec<Arc<Thread>> {
+        self.0
+            .threads()
+            .lock()
+            .iter()
+            .filter(|thread| !thread.status().is_exited())
+            .cloned()
+            .collect()
+    }
+}
+
+impl DirOps for TaskDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let tid = name.parse::<Tid>().map_err(|_| Error::new(Errno::ENOENT))?;
+        if !self
+            .alive_threads()
+            .iter()
+            .any(|thread| thread.tid() == tid)
+        {
+            return_errno!(Errno::ENOENT);
+        }
+        Ok(TidDirOps::new_inode(self.0.clone(), this_ptr.clone()))
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<TaskDirOps>>().unwrap().this()
+        };
+        let threads = self.alive_threads();
+        let mut cached_children = this.cached_children().write();
+
+        // Remove the entries of the exited threads.
+        let exited_names: Vec<String> = cached_children
+            .iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| {
+                !threads
+                    .iter()
+                    .any(|thread| thread.tid().to_string() == *name)
+            })
+            .collect();
+        for name in exited_names {
+            cached_children.remove_entry_by_name(&name);
+        }
+
+        for thread in threads {
+            cached_children.put_entry_if_not_found(&thread.tid().to_string(), || {
+                TidDirOps::new_inode(self.0.clone(), this_ptr.clone())
+            });
+        }
+    }
+}
+
+/// Represents the inode at `/proc/[pid]/task/[tid]`.
+//
+// TODO: The entries describe the whole process for now. Some of them, e.g., `comm`,
+// should describe the thread instead.
+struct TidDirOps(Arc<Process>);
+
+impl TidDirOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self(process_ref))
+            .parent(parent)
+            .volatile()
+            .build()
+            .unwrap()
+    }
+}
+
+impl DirOps for TidDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        lookup_process_entry(&self.0, this_ptr, name)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<TidDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        populate_process_entries(&self.0, this_ptr, &mut cached_children);
+    }
+}
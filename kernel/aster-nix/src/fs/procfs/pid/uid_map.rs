@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::process_user_ns;
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    prelude::*,
+    Process,
+};
+
+/// Represents the inode at `/proc/[pid]/uid_map`.
+pub struct UidMapFileOps(Arc<Process>);
+
+impl UidMapFileOps {
+    pub fn new_inode(process_ref: Arc<Process>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        // The inodes in procfs are always owned by root, so the file is writable by everyone
+        // and who can install the map is checked when it is written.
+        ProcFileBuilder::new(Self(process_ref))
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o666))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for UidMapFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let user_ns = process_user_ns(&self.0)?;
+        Ok(user_ns.uid_map_content().into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let content = core::str::from_utf8(buf)
+            .map_err(|_| Error::with_message(Errno::EINVAL, "the map is invalid"))?;
+        process_user_ns(&self.0)?.write_uid_map(content)?;
+        Ok(buf.len())
+    }
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+use core::{fmt::Write, time::Duration};
+
+use ostd::cpu::num_cpus;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    process::nr_forks,
+    sched::stats::{cpu_time, nr_context_switches, total_cpu_time, CpuTime},
+    time::realtime_offset,
+};
+
+/// Represents the inode at `/proc/stat`.
+pub struct ProcStatFileOps;
+
+impl ProcStatFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for ProcStatFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let mut output = format_cpu_time("cpu ", &total_cpu_time());
+        for cpu_id in 0..num_cpus() {
+            output.push_str(&format_cpu_time(
+                &format!("cpu{}", cpu_id),
+                &cpu_time(cpu_id),
+            ));
+        }
+
+        // The boot time is the real time when the monotonic clock starts.
+        let boot_time = realtime_offset().as_secs();
+        writeln!(output, "ctxt {}", nr_context_switches()).unwrap();
+        writeln!(output, "btime {}", boot_time).unwrap();
+        writeln!(output, "processes {}", nr_forks()).unwrap();
+        Ok(output.into_bytes())
+    }
+}
+
+/// The unit of the CPU time in `/proc/stat`, which is `USER_HZ` in Linux.
+const USER_HZ: u128 = 100;
+
+/// Formats a line of the CPU time.
+///
+/// The fields are user, nice, system, idle, iowait, irq, softirq, steal, guest and guest_nice.
+/// The fields that are not accounted are always zero.
+fn format_cpu_time(name: &str, cpu_time: &CpuTime) -> String {
+    let to_clock_ticks = |time: Duration| time.as_millis() * USER_HZ / 1000;
+    format!(
+        "{} {} 0 {} {} 0 0 0 0 0 0\n",
+        name,
+        to_clock_ticks(cpu_time.user),
+        to_clock_ticks(cpu_time.system),
+        to_clock_ticks(cpu_time.idle),
+    )
+}
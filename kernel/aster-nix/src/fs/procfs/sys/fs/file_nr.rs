@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        file_handle::nr_files,
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// The maximum number of open files in the system.
+///
+/// There is no system-wide limit, which Linux reports as `LONG_MAX`.
+const FILE_MAX: u64 = i64::MAX as u64;
+
+/// Represents the inode at `/proc/sys/fs/file-nr`.
+pub struct FileNrFileOps;
+
+impl FileNrFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for FileNrFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        // The number of free file handles is always zero because file handles
+        // are not allocated from a dedicated cache.
+        Ok(format!("{}\t0\t{}\n", nr_files(), FILE_MAX).into_bytes())
+    }
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use self::{file_nr::FileNrFileOps, nr_open::NrOpenFileOps, pipe_max_size::PipeMaxSizeFileOps};
+use crate::{
+    fs::{
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+};
+
+mod file_nr;
+mod nr_open;
+mod pipe_max_size;
+
+/// Represents the inode at `/proc/sys/fs`.
+pub struct FsDirOps;
+
+impl FsDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl DirOps for FsDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "file-nr" => FileNrFileOps::new_inode(this_ptr.clone()),
+            "nr_open" => NrOpenFileOps::new_inode(this_ptr.clone()),
+            "pipe-max-size" => PipeMaxSizeFileOps::new_inode(this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<FsDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children
+            .put_entry_if_not_found("file-nr", || FileNrFileOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("nr_open", || NrOpenFileOps::new_inode(this_ptr.clone()));
+        cached_children.put_entry_if_not_found("pipe-max-size", || {
+            PipeMaxSizeFileOps::new_inode(this_ptr.clone())
+        });
+    }
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        file_table::{nr_open, set_nr_open},
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/sys/fs/nr_open`.
+pub struct NrOpenFileOps;
+
+impl NrOpenFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self)
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o644))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for NrOpenFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        Ok(format!("{}\n", nr_open()).into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let nr_open = core::str::from_utf8(buf)
+            .ok()
+            .and_then(|text| text.trim().parse::<usize>().ok())
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "nr_open is invalid"))?;
+
+        set_nr_open(nr_open)?;
+        Ok(buf.len())
+    }
+}
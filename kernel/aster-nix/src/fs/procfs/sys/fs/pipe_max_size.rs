@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        pipe::{pipe_max_size, set_pipe_max_size},
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/sys/fs/pipe-max-size`.
+pub struct PipeMaxSizeFileOps;
+
+impl PipeMaxSizeFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self)
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o644))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for PipeMaxSizeFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        Ok(format!("{}\n", pipe_max_size()).into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let size = core::str::from_utf8(buf)
+            .ok()
+            .and_then(|text| text.trim().parse::<usize>().ok())
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "pipe-max-size is invalid"))?;
+
+        set_pipe_max_size(size)?;
+        Ok(buf.len())
+    }
+}
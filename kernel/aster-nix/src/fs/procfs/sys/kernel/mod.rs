@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use self::{
+    random::RandomDirOps, randomize_va_space::RandomizeVaSpaceFileOps, uts::UtsFieldFileOps,
+};
+use crate::{
+    fs::{
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+    syscall::{RELEASE, SYS_NAME, VERSION},
+};
+
+mod random;
+mod randomize_va_space;
+mod uts;
+
+/// Represents the inode at `/proc/sys/kernel`.
+pub struct KernelDirOps;
+
+impl KernelDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl DirOps for KernelDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "random" => RandomDirOps::new_inode(this_ptr.clone()),
+            "randomize_va_space" => RandomizeVaSpaceFileOps::new_inode(this_ptr.clone()),
+            "ostype" => UtsFieldFileOps::new_inode(&SYS_NAME, this_ptr.clone()),
+            "osrelease" => UtsFieldFileOps::new_inode(&RELEASE, this_ptr.clone()),
+            "version" => UtsFieldFileOps::new_inode(&VERSION, this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<KernelDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children
+            .put_entry_if_not_found("random", || RandomDirOps::new_inode(this_ptr.clone()));
+        cached_children.put_entry_if_not_found("randomize_va_space", || {
+            RandomizeVaSpaceFileOps::new_inode(this_ptr.clone())
+        });
+        cached_children.put_entry_if_not_found("ostype", || {
+            UtsFieldFileOps::new_inode(&SYS_NAME, this_ptr.clone())
+        });
+        cached_children.put_entry_if_not_found("osrelease", || {
+            UtsFieldFileOps::new_inode(&RELEASE, this_ptr.clone())
+        });
+        cached_children.put_entry_if_not_found("version", || {
+            UtsFieldFileOps::new_inode(&VERSION, this_ptr.clone())
+        });
+    }
+}
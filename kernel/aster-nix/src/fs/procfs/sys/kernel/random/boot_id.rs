@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use spin::Once;
+
+use super::{format_uuid, generate_uuid};
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// The UUID that identifies the current boot.
+static BOOT_ID: Once<[u8; 16]> = Once::new();
+
+/// Represents the inode at `/proc/sys/kernel/random/boot_id`.
+///
+/// The UUID is generated on the first read and stays the same until the next boot.
+pub struct BootIdFileOps;
+
+impl BootIdFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for BootIdFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let boot_id = BOOT_ID.try_call_once(generate_uuid)?;
+        Ok(format_uuid(boot_id).into_bytes())
+    }
+}
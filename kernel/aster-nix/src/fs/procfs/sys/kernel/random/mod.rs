@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use self::{boot_id::BootIdFileOps, uuid::UuidFileOps};
+use crate::{
+    fs::{
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+    util::random::getrandom,
+};
+
+mod boot_id;
+mod uuid;
+
+/// Represents the inode at `/proc/sys/kernel/random`.
+pub struct RandomDirOps;
+
+impl RandomDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl DirOps for RandomDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "boot_id" => BootIdFileOps::new_inode(this_ptr.clone()),
+            "uuid" => UuidFileOps::new_inode(this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<RandomDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children
+            .put_entry_if_not_found("boot_id", || BootIdFileOps::new_inode(this_ptr.clone()));
+        cached_children.put_entry_if_not_found("uuid", || UuidFileOps::new_inode(this_ptr.clone()));
+    }
+}
+
+/// Generates a random (version 4) UUID.
+fn generate_uuid() -> Result<[u8; 16]> {
+    let mut uuid = [0u8; 16];
+    getrandom(&mut uuid)?;
+
+    // Set the version to 4 and the variant to 1, as Linux does.
+    uuid[6] = (uuid[6] & 0x0f) | 0x40;
+    uuid[8] = (uuid[8] & 0x3f) | 0x80;
+    Ok(uuid)
+}
+
+/// Formats the UUID in the canonical form, e.g., `7b9d9b7e-4d47-4f8e-9a0c-2f1e5d6c7b8a`.
+fn format_uuid(uuid: &[u8; 16]) -> String {
+    let hex = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    };
+    format!(
+        "{}-{}-{}-{}-{}\n",
+        hex(&uuid[0..4]),
+        hex(&uuid[4..6]),
+        hex(&uuid[6..8]),
+        hex(&uuid[8..10]),
+        hex(&uuid[10..16])
+    )
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::{format_uuid, generate_uuid};
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/sys/kernel/random/uuid`.
+///
+/// Each read returns a newly generated UUID.
+pub struct UuidFileOps;
+
+impl UuidFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for UuidFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let uuid = generate_uuid()?;
+        Ok(format_uuid(&uuid).into_bytes())
+    }
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    prelude::*,
+    process::aslr::{randomize_va_space, set_randomize_va_space},
+};
+
+/// Represents the inode at `/proc/sys/kernel/randomize_va_space`.
+pub struct RandomizeVaSpaceFileOps;
+
+impl RandomizeVaSpaceFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self)
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o644))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for RandomizeVaSpaceFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        Ok(format!("{}\n", randomize_va_space()).into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let level = core::str::from_utf8(buf)
+            .ok()
+            .and_then(|text| text.trim().parse::<i32>().ok())
+            .ok_or_else(|| {
+                Error::with_message(Errno::EINVAL, "the randomization level is invalid")
+            })?;
+
+        set_randomize_va_space(level);
+        Ok(buf.len())
+    }
+}
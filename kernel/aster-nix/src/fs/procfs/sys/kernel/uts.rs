@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+};
+
+/// Represents the inodes at `/proc/sys/kernel/ostype`, `/proc/sys/kernel/osrelease`
+/// and `/proc/sys/kernel/version`.
+///
+/// Each of them shows a field that is reported by `uname`.
+pub struct UtsFieldFileOps(&'static CStr);
+
+impl UtsFieldFileOps {
+    pub fn new_inode(field: &'static CStr, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(field))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for UtsFieldFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let mut output = self.0.to_bytes().to_vec();
+        output.push(b'\n');
+        Ok(output)
+    }
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use self::{fs::FsDirOps, kernel::KernelDirOps, net::NetDirOps};
+use super::template::{DirOps, ProcDir, ProcDirBuilder};
+use crate::{
+    fs::utils::{DirEntryVecExt, Inode},
+    prelude::*,
+};
+
+mod fs;
+mod kernel;
+mod net;
+
+/// Represents the inode at `/proc/sys`.
+pub struct SysDirOps;
+
+impl SysDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl DirOps for SysDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "fs" => FsDirOps::new_inode(this_ptr.clone()),
+            "kernel" => KernelDirOps::new_inode(this_ptr.clone()),
+            "net" => NetDirOps::new_inode(this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<SysDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children.put_entry_if_not_found("fs", || FsDirOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("kernel", || KernelDirOps::new_inode(this_ptr.clone()));
+        cached_children.put_entry_if_not_found("net", || NetDirOps::new_inode(this_ptr.clone()));
+    }
+}
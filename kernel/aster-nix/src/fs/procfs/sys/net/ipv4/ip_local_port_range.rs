@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::{Inode, InodeMode},
+    },
+    net::iface::{ip_local_port_range, set_ip_local_port_range},
+    prelude::*,
+};
+
+/// Represents the inode at `/proc/sys/net/ipv4/ip_local_port_range`.
+pub struct IpLocalPortRangeFileOps;
+
+impl IpLocalPortRangeFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self)
+            .parent(parent)
+            .mode(InodeMode::from_bits_truncate(0o644))
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for IpLocalPortRangeFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let (start, end) = ip_local_port_range();
+        Ok(format!("{}\t{}\n", start, end).into_bytes())
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let invalid_range = || Error::with_message(Errno::EINVAL, "the port range is invalid");
+
+        let text = core::str::from_utf8(buf).map_err(|_| invalid_range())?;
+        let mut ports = text.split_whitespace().map(|port| port.parse::<u16>());
+        let (Some(Ok(start)), Some(Ok(end)), None) = (ports.next(), ports.next(), ports.next())
+        else {
+            return Err(invalid_range());
+        };
+
+        set_ip_local_port_range(start, end)?;
+        Ok(buf.len())
+    }
+}
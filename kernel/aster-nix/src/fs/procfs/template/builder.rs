@@ -8,7 +8,7 @@ use super::{
     sym::{ProcSym, SymOps},
 };
 use crate::{
-    fs::utils::{FileSystem, Inode},
+    fs::utils::{FileSystem, Inode, InodeMode},
     prelude::*,
 };
 
@@ -63,6 +63,7 @@ pub struct ProcFileBuilder<O: FileOps> {
     // Mandatory field
     file: O,
     // Optional fields
+    mode: InodeMode,
     optional_builder: Option<OptionalBuilder>,
 }
 
@@ -71,10 +72,16 @@ impl<O: FileOps> ProcFileBuilder<O> {
         let optional_builder: OptionalBuilder = Default::default();
         Self {
             file,
+            mode: InodeMode::from_bits_truncate(0o444),
             optional_builder: Some(optional_builder),
         }
     }
 
+    pub fn mode(mut self, mode: InodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn parent(self, parent: Weak<dyn Inode>) -> Self {
         self.optional_builder(|ob| ob.parent(parent))
     }
@@ -85,7 +92,7 @@ impl<O: FileOps> ProcFileBuilder<O> {
 
     pub fn build(mut self) -> Result<Arc<ProcFile<O>>> {
         let (fs, _, _, is_volatile) = self.optional_builder.take().unwrap().build()?;
-        Ok(ProcFile::new(self.file, fs, is_volatile))
+        Ok(ProcFile::new(self.file, fs, self.mode, is_volatile))
     }
 
     fn optional_builder<F>(mut self, f: F) -> Self
@@ -7,7 +7,7 @@ use core::time::Duration;
 use aster_util::slot_vec::SlotVec;
 use inherit_methods_macro::inherit_methods;
 
-use super::{Common, ProcFS};
+use super::{alloc_ino, Common};
 use crate::{
     fs::{
         device::Device,
@@ -34,11 +34,7 @@ impl<D: DirOps> ProcDir<D> {
         is_volatile: bool,
     ) -> Arc<Self> {
         let common = {
-            let ino = ino.unwrap_or_else(|| {
-                let arc_fs = fs.upgrade().unwrap();
-                let procfs = arc_fs.downcast_ref::<ProcFS>().unwrap();
-                procfs.alloc_id()
-            });
+            let ino = ino.unwrap_or_else(|| alloc_ino(&fs));
 
             let metadata =
                 Metadata::new_dir(ino, InodeMode::from_bits_truncate(0o555), super::BLOCK_SIZE);
@@ -4,7 +4,7 @@ use core::time::Duration;
 
 use inherit_methods_macro::inherit_methods;
 
-use super::{Common, ProcFS};
+use super::{alloc_ino, Common};
 use crate::{
     fs::utils::{FileSystem, Inode, InodeMode, InodeType, IoctlCmd, Metadata},
     prelude::*,
@@ -17,15 +17,9 @@ pub struct ProcFile<F: FileOps> {
 }
 
 impl<F: FileOps> ProcFile<F> {
-    pub fn new(file: F, fs: Weak<dyn FileSystem>, is_volatile: bool) -> Arc<Self> {
+    pub fn new(file: F, fs: Weak<dyn FileSystem>, mode: InodeMode, is_volatile: bool) -> Arc<Self> {
         let common = {
-            let arc_fs = fs.upgrade().unwrap();
-            let procfs = arc_fs.downcast_ref::<ProcFS>().unwrap();
-            let metadata = Metadata::new_file(
-                procfs.alloc_id(),
-                InodeMode::from_bits_truncate(0o444),
-                super::BLOCK_SIZE,
-            );
+            let metadata = Metadata::new_file(alloc_ino(&fs), mode, super::BLOCK_SIZE);
             Common::new(metadata, fs, is_volatile)
         };
         Arc::new(Self {
@@ -54,7 +48,12 @@ impl<F: FileOps + 'static> Inode for ProcFile<F> {
     fn set_ctime(&self, time: Duration);
     fn fs(&self) -> Arc<dyn FileSystem>;
 
-    fn resize(&self, _new_size: usize) -> Result<()> {
+    fn resize(&self, new_size: usize) -> Result<()> {
+        // Like Linux, truncating a file to zero takes no effect, so that the writable files can
+        // be opened with `O_TRUNC`.
+        if new_size == 0 {
+            return Ok(());
+        }
         Err(Error::new(Errno::EPERM))
     }
 
@@ -75,12 +74,12 @@ impl<F: FileOps + 'static> Inode for ProcFile<F> {
         self.read_at(offset, buf)
     }
 
-    fn write_at(&self, _offset: usize, _buf: &[u8]) -> Result<usize> {
-        Err(Error::new(Errno::EPERM))
+    fn write_at(&self, _offset: usize, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
     }
 
-    fn write_direct_at(&self, _offset: usize, _buf: &[u8]) -> Result<usize> {
-        Err(Error::new(Errno::EPERM))
+    fn write_direct_at(&self, offset: usize, buf: &[u8]) -> Result<usize> {
+        self.write_at(offset, buf)
     }
 
     fn read_link(&self) -> Result<String> {
@@ -102,4 +101,12 @@ impl<F: FileOps + 'static> Inode for ProcFile<F> {
 
 pub trait FileOps: Sync + Send {
     fn data(&self) -> Result<Vec<u8>>;
+
+    /// Writes the data to the file.
+    ///
+    /// The file is read-only by default. A writable file should override this method and be
+    /// built with a writable mode.
+    fn write(&self, _buf: &[u8]) -> Result<usize> {
+        Err(Error::new(Errno::EPERM))
+    }
 }
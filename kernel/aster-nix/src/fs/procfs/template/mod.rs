@@ -10,7 +10,10 @@ pub use self::{
 };
 use super::{ProcFS, BLOCK_SIZE};
 use crate::{
-    fs::utils::{FileSystem, InodeMode, InodeType, Metadata},
+    fs::{
+        sysfs::SysFS,
+        utils::{FileSystem, InodeMode, InodeType, Metadata},
+    },
     prelude::*,
     process::{Gid, Uid},
 };
@@ -20,6 +23,16 @@ mod dir;
 mod file;
 mod sym;
 
+/// Allocates an inode number from the fs that the inodes built from the templates
+/// belong to, which is either procfs or sysfs.
+fn alloc_ino(fs: &Weak<dyn FileSystem>) -> u64 {
+    let fs = fs.upgrade().unwrap();
+    if let Some(procfs) = fs.downcast_ref::<ProcFS>() {
+        return procfs.alloc_id();
+    }
+    fs.downcast_ref::<SysFS>().unwrap().alloc_id()
+}
+
 struct Common {
     metadata: RwLock<Metadata>,
     fs: Weak<dyn FileSystem>,
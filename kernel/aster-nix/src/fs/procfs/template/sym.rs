@@ -4,9 +4,14 @@ use core::time::Duration;
 
 use inherit_methods_macro::inherit_methods;
 
-use super::{Common, ProcFS};
+use super::{alloc_ino, Common};
 use crate::{
-    fs::utils::{FileSystem, Inode, InodeMode, InodeType, IoctlCmd, Metadata},
+    fs::{
+        inode_handle::FileIo,
+        utils::{
+            AccessMode, FileSystem, Inode, InodeMode, InodeType, IoctlCmd, Metadata, StatusFlags,
+        },
+    },
     prelude::*,
     process::{Gid, Uid},
 };
@@ -19,10 +24,8 @@ pub struct ProcSym<S: SymOps> {
 impl<S: SymOps> ProcSym<S> {
     pub fn new(sym: S, fs: Weak<dyn FileSystem>, is_volatile: bool) -> Arc<Self> {
         let common = {
-            let arc_fs = fs.upgrade().unwrap();
-            let procfs = arc_fs.downcast_ref::<ProcFS>().unwrap();
             let metadata = Metadata::new_symlink(
-                procfs.alloc_id(),
+                alloc_ino(&fs),
                 InodeMode::from_bits_truncate(0o777),
                 super::BLOCK_SIZE,
             );
@@ -83,6 +86,18 @@ impl<S: SymOps + 'static> Inode for ProcSym<S> {
         Err(Error::new(Errno::EPERM))
     }
 
+    fn is_magic_link(&self) -> bool {
+        self.inner.is_magic_link()
+    }
+
+    fn open(
+        &self,
+        _access_mode: AccessMode,
+        _status_flags: StatusFlags,
+    ) -> Option<Result<Arc<dyn FileIo>>> {
+        self.inner.open()
+    }
+
     fn ioctl(&self, _cmd: IoctlCmd, _arg: usize) -> Result<i32> {
         Err(Error::new(Errno::EPERM))
     }
@@ -94,4 +109,13 @@ impl<S: SymOps + 'static> Inode for ProcSym<S> {
 
 pub trait SymOps: Sync + Send {
     fn read_link(&self) -> Result<String>;
+
+    fn is_magic_link(&self) -> bool {
+        false
+    }
+
+    /// Opens the magic link itself.
+    fn open(&self) -> Option<Result<Arc<dyn FileIo>>> {
+        None
+    }
 }
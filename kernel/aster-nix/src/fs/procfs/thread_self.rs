@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use super::*;
+
+/// Represents the inode at `/proc/thread-self`.
+pub struct ThreadSelfSymOps;
+
+impl ThreadSelfSymOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcSymBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl SymOps for ThreadSelfSymOps {
+    fn read_link(&self) -> Result<String> {
+        Ok(format!(
+            "{}/task/{}",
+            current!().pid(),
+            current_thread!().tid()
+        ))
+    }
+}
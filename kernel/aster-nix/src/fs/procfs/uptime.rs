@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+use core::time::Duration;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    sched::stats::total_cpu_time,
+    time::clocks::MonotonicClock,
+};
+
+/// Represents the inode at `/proc/uptime`.
+pub struct UptimeFileOps;
+
+impl UptimeFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for UptimeFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let uptime = MonotonicClock::get().read_time();
+        // The idle time is summed across all the CPUs, so it can exceed the uptime.
+        let idle_time = total_cpu_time().idle;
+
+        let output = format!("{} {}\n", format_seconds(uptime), format_seconds(idle_time));
+        Ok(output.into_bytes())
+    }
+}
+
+/// Formats the duration as seconds with two decimal places.
+fn format_seconds(duration: Duration) -> String {
+    format!(
+        "{}.{:02}",
+        duration.as_secs(),
+        duration.subsec_millis() / 10
+    )
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::format;
+
+use crate::{
+    fs::{
+        procfs::template::{FileOps, ProcFileBuilder},
+        utils::Inode,
+    },
+    prelude::*,
+    syscall::{RELEASE, SYS_NAME, VERSION},
+};
+
+/// Represents the inode at `/proc/version`.
+///
+/// The content is consistent with the values reported by `uname`.
+pub struct VersionFileOps;
+
+impl VersionFileOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl FileOps for VersionFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let output = format!(
+            "{} version {} {}\n",
+            SYS_NAME.to_string_lossy(),
+            RELEASE.to_string_lossy(),
+            VERSION.to_string_lossy()
+        );
+        Ok(output.into_bytes())
+    }
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use core::{
-    sync::atomic::{AtomicU64, Ordering},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
     time::Duration,
 };
 
@@ -18,9 +18,11 @@ use crate::{
     events::IoEvents,
     fs::{
         device::Device,
+        inode_handle::FileIo,
+        named_pipe::NamedPipe,
         utils::{
-            CStr256, DirentVisitor, FileSystem, FsFlags, Inode, InodeMode, InodeType, IoctlCmd,
-            Metadata, PageCache, PageCacheBackend, SuperBlock,
+            AccessMode, CStr256, DirentVisitor, FileSystem, FsFlags, Inode, InodeMode, InodeType,
+            IoctlCmd, Metadata, PageCache, PageCacheBackend, StatusFlags, SuperBlock,
         },
     },
     prelude::*,
@@ -37,10 +39,43 @@ pub struct RamFS {
     root: Arc<RamInode>,
     /// An inode allocator
     inode_allocator: AtomicU64,
+    /// The maximum number of blocks that the file data can take, if limited
+    max_blocks: Option<usize>,
+    /// The number of blocks taken by the file data
+    used_blocks: AtomicUsize,
+    /// The flags of the fs
+    flags: FsFlags,
+    /// Whether the fs is devtmpfs
+    is_devtmpfs: bool,
 }
 
 impl RamFS {
     pub fn new() -> Arc<Self> {
+        Self::new_inner(None, false)
+    }
+
+    /// Creates a `RamFS` whose file data can take up to `max_size` bytes.
+    ///
+    /// This is how tmpfs is created. The writes beyond the limit fail with `ENOSPC`.
+    pub fn new_with_max_size(max_size: usize) -> Arc<Self> {
+        Self::new_inner(Some(max_size.div_ceil(BLOCK_SIZE)), false)
+    }
+
+    /// Creates a `RamFS` for devtmpfs.
+    ///
+    /// The kernel adds and deletes the device nodes without going through the dentries
+    /// of the mounted devtmpfs, so neither the negative dentries nor the dentries of the
+    /// device nodes can be cached.
+    pub fn new_devtmpfs() -> Arc<Self> {
+        Self::new_inner(None, true)
+    }
+
+    fn new_inner(max_blocks: Option<usize>, is_devtmpfs: bool) -> Arc<Self> {
+        let flags = if is_devtmpfs {
+            FsFlags::DENTRY_UNEVICTABLE
+        } else {
+            FsFlags::DENTRY_UNEVICTABLE | FsFlags::NEGATIVE_DENTRY
+        };
         Arc::new_cyclic(|weak_fs| Self {
             sb: SuperBlock::new(RAMFS_MAGIC, BLOCK_SIZE, NAME_MAX),
             root: Arc::new_cyclic(|weak_root| RamInode {
@@ -57,6 +92,10 @@ impl RamFS {
                 fs: weak_fs.clone(),
             }),
             inode_allocator: AtomicU64::new(ROOT_INO + 1),
+            max_blocks,
+            used_blocks: AtomicUsize::new(0),
+            flags,
+            is_devtmpfs,
         })
     }
 
@@ -67,6 +106,33 @@ impl RamFS {
     fn device_id(&self) -> u64 {
         0
     }
+
+    /// Accounts for the blocks of a file whose size changes from `old_size` to `new_size`.
+    ///
+    /// If the file grows beyond the size limit of the fs, `ENOSPC` is returned and
+    /// nothing is accounted.
+    fn charge_blocks(&self, old_size: usize, new_size: usize) -> Result<()> {
+        let old_blocks = old_size.div_ceil(BLOCK_SIZE);
+        let new_blocks = new_size.div_ceil(BLOCK_SIZE);
+        if new_blocks <= old_blocks {
+            self.used_blocks
+                .fetch_sub(old_blocks - new_blocks, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        let delta = new_blocks - old_blocks;
+        let Some(max_blocks) = self.max_blocks else {
+            self.used_blocks.fetch_add(delta, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.used_blocks
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used_blocks| {
+                let used_blocks = used_blocks + delta;
+                (used_blocks <= max_blocks).then_some(used_blocks)
+            })
+            .map_err(|_| Error::with_message(Errno::ENOSPC, "the fs is full"))?;
+        Ok(())
+    }
 }
 
 impl FileSystem for RamFS {
@@ -80,11 +146,18 @@ impl FileSystem for RamFS {
     }
 
     fn sb(&self) -> SuperBlock {
-        self.sb.clone()
+        let mut sb = self.sb.clone();
+        if let Some(max_blocks) = self.max_blocks {
+            let free_blocks = max_blocks.saturating_sub(self.used_blocks.load(Ordering::Relaxed));
+            sb.blocks = max_blocks;
+            sb.bfree = free_blocks;
+            sb.bavail = free_blocks;
+        }
+        sb
     }
 
     fn flags(&self) -> FsFlags {
-        FsFlags::DENTRY_UNEVICTABLE
+        self.flags
     }
 }
 
@@ -141,6 +214,13 @@ impl Node {
         }
     }
 
+    pub fn new_named_pipe(mode: InodeMode, uid: Uid, gid: Gid) -> Self {
+        Self {
+            inner: Inner::NamedPipe(NamedPipe::new()),
+            metadata: InodeMeta::new(mode, uid, gid),
+        }
+    }
+
     pub fn new_device(mode: InodeMode, uid: Uid, gid: Gid, device: Arc<dyn Device>) -> Self {
         Self {
             inner: Inner::Device(device),
@@ -226,6 +306,7 @@ enum Inner {
     SymLink(String),
     Device(Arc<dyn Device>),
     Socket,
+    NamedPipe(NamedPipe),
 }
 
 impl Inner {
@@ -270,6 +351,13 @@ impl Inner {
             _ => None,
         }
     }
+
+    fn as_named_pipe(&self) -> Option<&NamedPipe> {
+        match self {
+            Inner::NamedPipe(named_pipe) => Some(named_pipe),
+            _ => None,
+        }
+    }
 }
 
 struct DirEntry {
@@ -424,6 +512,16 @@ impl RamInode {
         })
     }
 
+    fn new_named_pipe(fs: &Arc<RamFS>, mode: InodeMode, uid: Uid, gid: Gid) -> Arc<Self> {
+        Arc::new_cyclic(|weak_self| RamInode {
+            node: RwMutex::new(Node::new_named_pipe(mode, uid, gid)),
+            ino: fs.alloc_id(),
+            typ: InodeType::NamedPipe,
+            this: weak_self.clone(),
+            fs: Arc::downgrade(fs),
+        })
+    }
+
     fn new_device(
         fs: &Arc<RamFS>,
         mode: InodeMode,
@@ -456,6 +554,19 @@ impl RamInode {
     }
 }
 
+impl Drop for RamInode {
+    fn drop(&mut self) {
+        if self.typ != InodeType::File {
+            return;
+        }
+        // The fs may have been dropped before its inodes.
+        if let Some(fs) = self.fs.upgrade() {
+            let file_size = self.node.read().metadata.size;
+            let _ = fs.charge_blocks(file_size, 0);
+        }
+    }
+}
+
 impl PageCacheBackend for RamInode {
     fn read_page(&self, _idx: usize, frame: &Frame) -> Result<BioWaiter> {
         // Initially, any block/page in a RamFs inode contains all zeros
@@ -482,6 +593,15 @@ impl Inode for RamInode {
             .map(|page_cache| page_cache.pages().dup())
     }
 
+    /// Do not cache the dentries of the device nodes in devtmpfs.
+    ///
+    /// A device node can be deleted by the kernel when the device is unregistered, which the
+    /// dentries of other mounts of devtmpfs do not know.
+    fn is_dentry_cacheable(&self) -> bool {
+        let is_device = matches!(self.typ, InodeType::CharDevice | InodeType::BlockDevice);
+        !is_device || !self.fs.upgrade().is_some_and(|fs| fs.is_devtmpfs)
+    }
+
     fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<usize> {
         let self_inode = self.node.read();
 
@@ -520,16 +640,27 @@ impl Inode for RamInode {
         };
         let file_size = self_inode.metadata.size;
         let new_size = offset + buf.len();
-        let should_expand_size = new_size > file_size;
-        if should_expand_size {
-            page_cache.pages().resize(new_size)?;
+        if new_size <= file_size {
+            page_cache.pages().write_bytes(offset, buf)?;
+            return Ok(buf.len());
+        }
+
+        // The blocks are charged before the file grows, and refunded if the write fails.
+        let fs = self.fs.upgrade().unwrap();
+        fs.charge_blocks(file_size, new_size)?;
+        if let Err(e) = page_cache.pages().resize(new_size) {
+            fs.charge_blocks(new_size, file_size)?;
+            return Err(e);
         }
-        page_cache.pages().write_bytes(offset, buf)?;
-        if should_expand_size {
-            // Turn the read guard into a write guard without releasing the lock.
-            let mut self_inode = self_inode.upgrade();
-            self_inode.resize(new_size);
+        if let Err(e) = page_cache.pages().write_bytes(offset, buf) {
+            page_cache.pages().resize(file_size)?;
+            fs.charge_blocks(new_size, file_size)?;
+            return Err(e.into());
         }
+
+        // Turn the read guard into a write guard without releasing the lock.
+        let mut self_inode = self_inode.upgrade();
+        self_inode.resize(new_size);
         Ok(buf.len())
     }
 
@@ -551,12 +682,17 @@ impl Inode for RamInode {
         if file_size == new_size {
             return Ok(());
         }
+        let fs = self.fs.upgrade().unwrap();
+        fs.charge_blocks(file_size, new_size)?;
+
+        let page_cache = self_inode.inner.as_file().unwrap();
+        if let Err(e) = page_cache.pages().resize(new_size) {
+            fs.charge_blocks(new_size, file_size)?;
+            return Err(e);
+        }
 
         let mut self_inode = self_inode.upgrade();
         self_inode.resize(new_size);
-        let self_inode = self_inode.downgrade();
-        let page_cache = self_inode.inner.as_file().unwrap();
-        page_cache.pages().resize(new_size)?;
 
         Ok(())
     }
@@ -659,6 +795,16 @@ impl Inode for RamInode {
         self.node.read().inner.as_device().cloned()
     }
 
+    fn open(
+        &self,
+        access_mode: AccessMode,
+        status_flags: StatusFlags,
+    ) -> Option<Result<Arc<dyn FileIo>>> {
+        let node = self.node.read();
+        let named_pipe = node.inner.as_named_pipe()?;
+        Some(named_pipe.open(access_mode, status_flags))
+    }
+
     fn create(&self, name: &str, type_: InodeType, mode: InodeMode) -> Result<Arc<dyn Inode>> {
         if name.len() > NAME_MAX {
             return_errno!(Errno::ENAMETOOLONG);
@@ -678,6 +824,9 @@ impl Inode for RamInode {
                 RamInode::new_symlink(&fs, mode, Uid::new_root(), Gid::new_root())
             }
             InodeType::Socket => RamInode::new_socket(&fs, mode, Uid::new_root(), Gid::new_root()),
+            InodeType::NamedPipe => {
+                RamInode::new_named_pipe(&fs, mode, Uid::new_root(), Gid::new_root())
+            }
             InodeType::Dir => {
                 RamInode::new_dir(&fs, mode, Uid::new_root(), Gid::new_root(), &self.this)
             }
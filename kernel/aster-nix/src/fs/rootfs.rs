@@ -6,10 +6,12 @@ use libflate::gzip::Decoder as GZipDecoder;
 use spin::Once;
 
 use super::{
+    device::devtmpfs,
     fs_resolver::{FsPath, FsResolver},
     path::MountNode,
     procfs::ProcFS,
     ramfs::RamFS,
+    sysfs::SysFS,
     utils::{FileSystem, InodeMode, InodeType},
 };
 use crate::prelude::*;
@@ -78,9 +80,12 @@ pub fn init(initramfs_buf: &[u8]) -> Result<()> {
     // Mount ProcFS
     let proc_dentry = fs.lookup(&FsPath::try_from("/proc")?)?;
     proc_dentry.mount(ProcFS::new())?;
-    // Mount DevFS
+    // Mount SysFS
+    let sys_dentry = fs.lookup(&FsPath::try_from("/sys")?)?;
+    sys_dentry.mount(SysFS::new())?;
+    // Mount devtmpfs
     let dev_dentry = fs.lookup(&FsPath::try_from("/dev")?)?;
-    dev_dentry.mount(RamFS::new())?;
+    dev_dentry.mount(devtmpfs())?;
 
     println!("[kernel] rootfs is ready");
 
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fs::{
+        procfs::template::{DirOps, FileOps, ProcDir, ProcDirBuilder, ProcFileBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    net::{iface::Iface, IFACES},
+    prelude::*,
+};
+
+/// Represents the inode at `/sys/class/net`.
+///
+/// TODO: List the interfaces of the network namespace of the current process once
+/// network namespaces are supported.
+pub struct NetDirOps;
+
+impl NetDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+impl DirOps for NetDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let Some(ifaces) = IFACES.get() else {
+            return_errno!(Errno::ENOENT);
+        };
+        let (index, iface) = ifaces
+            .iter()
+            .enumerate()
+            .find(|(_, iface)| iface.name() == name)
+            .ok_or_else(|| Error::new(Errno::ENOENT))?;
+        Ok(IfaceDirOps::new_inode(
+            iface_index(index),
+            iface.clone(),
+            this_ptr,
+        ))
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let Some(ifaces) = IFACES.get() else {
+            return;
+        };
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<NetDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        for (index, iface) in ifaces.iter().enumerate() {
+            cached_children.put_entry_if_not_found(iface.name(), || {
+                IfaceDirOps::new_inode(iface_index(index), iface.clone(), this_ptr.clone())
+            });
+        }
+    }
+}
+
+/// Returns the interface index of the interface at `index` in `IFACES`.
+///
+/// Like Linux, the interface indexes start from one.
+fn iface_index(index: usize) -> usize {
+    index + 1
+}
+
+/// Represents the inode at `/sys/class/net/[iface]`.
+struct IfaceDirOps {
+    ifindex: usize,
+    iface: Arc<dyn Iface>,
+}
+
+impl IfaceDirOps {
+    pub fn new_inode(
+        ifindex: usize,
+        iface: Arc<dyn Iface>,
+        parent: Weak<dyn Inode>,
+    ) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self { ifindex, iface })
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl DirOps for IfaceDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "ifindex" => IfindexFileOps::new_inode(self.ifindex, this_ptr.clone()),
+            "address" => AddressFileOps::new_inode(self.iface.clone(), this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<IfaceDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children.put_entry_if_not_found("ifindex", || {
+            IfindexFileOps::new_inode(self.ifindex, this_ptr.clone())
+        });
+        cached_children.put_entry_if_not_found("address", || {
+            AddressFileOps::new_inode(self.iface.clone(), this_ptr.clone())
+        });
+    }
+}
+
+/// Represents the inode at `/sys/class/net/[iface]/ifindex`.
+struct IfindexFileOps(usize);
+
+impl IfindexFileOps {
+    pub fn new_inode(ifindex: usize, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(ifindex))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for IfindexFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        Ok(format!("{}\n", self.0).into_bytes())
+    }
+}
+
+/// Represents the inode at `/sys/class/net/[iface]/address`.
+///
+/// It shows the MAC address of the interface, which is all zeros if there is none.
+struct AddressFileOps(Arc<dyn Iface>);
+
+impl AddressFileOps {
+    pub fn new_inode(iface: Arc<dyn Iface>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(iface))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for AddressFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        let bytes = self.0.mac_addr().map(|addr| addr.0).unwrap_or_default();
+        let address = bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(":");
+        Ok(format!("{}\n", address).into_bytes())
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fs::{
+        device::{registered_devices, Device, DeviceId},
+        procfs::template::{DirOps, FileOps, ProcDir, ProcDirBuilder, ProcFileBuilder},
+        utils::{DirEntryVecExt, Inode},
+    },
+    prelude::*,
+};
+
+/// Represents the inode at `/sys/devices`.
+///
+/// There is a directory for each registered device.
+pub struct DevicesDirOps;
+
+impl DevicesDirOps {
+    pub fn new_inode(parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self).parent(parent).build().unwrap()
+    }
+}
+
+/// Returns the name of the directory of a device.
+///
+/// Like Linux, the slashes in the device name are replaced with `!`.
+fn dir_name(device_name: &str) -> String {
+    device_name.replace('/', "!")
+}
+
+impl DirOps for DevicesDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let (_, device) = registered_devices()
+            .into_iter()
+            .find(|(device_name, _)| dir_name(device_name) == name)
+            .ok_or_else(|| Error::new(Errno::ENOENT))?;
+        Ok(DeviceDirOps::new_inode(device, this_ptr))
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<DevicesDirOps>>()
+                .unwrap()
+                .this()
+        };
+        let mut cached_children = this.cached_children().write();
+        for (device_name, device) in registered_devices() {
+            cached_children.put_entry_if_not_found(&dir_name(&device_name), || {
+                DeviceDirOps::new_inode(device.clone(), this_ptr.clone())
+            });
+        }
+    }
+}
+
+/// Represents the inode at `/sys/devices/[device]`.
+struct DeviceDirOps(Arc<dyn Device>);
+
+impl DeviceDirOps {
+    pub fn new_inode(device: Arc<dyn Device>, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self(device))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl DirOps for DeviceDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "dev" => DevFileOps::new_inode(self.0.id(), this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<DeviceDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children.put_entry_if_not_found("dev", || {
+            DevFileOps::new_inode(self.0.id(), this_ptr.clone())
+        });
+    }
+}
+
+/// Represents the inode at `/sys/devices/[device]/dev`.
+///
+/// It shows the device ID in the format of `major:minor`.
+struct DevFileOps(DeviceId);
+
+impl DevFileOps {
+    pub fn new_inode(id: DeviceId, parent: Weak<dyn Inode>) -> Arc<dyn Inode> {
+        ProcFileBuilder::new(Self(id))
+            .parent(parent)
+            .build()
+            .unwrap()
+    }
+}
+
+impl FileOps for DevFileOps {
+    fn data(&self) -> Result<Vec<u8>> {
+        Ok(format!("{}:{}\n", self.0.major(), self.0.minor()).into_bytes())
+    }
+}
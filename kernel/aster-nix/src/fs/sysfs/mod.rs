@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A minimal sysfs, which exposes the network interfaces and the registered devices.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use self::{class::ClassDirOps, devices::DevicesDirOps};
+use crate::{
+    fs::{
+        procfs::template::{DirOps, ProcDir, ProcDirBuilder},
+        utils::{DirEntryVecExt, FileSystem, FsFlags, Inode, SuperBlock, NAME_MAX},
+    },
+    prelude::*,
+};
+
+mod class;
+mod devices;
+
+/// Magic number.
+const SYSFS_MAGIC: u64 = 0x6265_6572;
+/// Root Inode ID.
+const SYSFS_ROOT_INO: u64 = 1;
+/// Block size.
+const BLOCK_SIZE: usize = 4096;
+
+pub struct SysFS {
+    sb: SuperBlock,
+    root: Arc<dyn Inode>,
+    inode_allocator: AtomicU64,
+}
+
+impl SysFS {
+    pub fn new() -> Arc<Self> {
+        Arc::new_cyclic(|weak_fs| Self {
+            sb: SuperBlock::new(SYSFS_MAGIC, BLOCK_SIZE, NAME_MAX),
+            root: RootDirOps::new_inode(weak_fs.clone()),
+            inode_allocator: AtomicU64::new(SYSFS_ROOT_INO + 1),
+        })
+    }
+
+    pub(in crate::fs) fn alloc_id(&self) -> u64 {
+        self.inode_allocator.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+impl FileSystem for SysFS {
+    fn sync(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn root_inode(&self) -> Arc<dyn Inode> {
+        self.root.clone()
+    }
+
+    fn sb(&self) -> SuperBlock {
+        self.sb.clone()
+    }
+
+    fn flags(&self) -> FsFlags {
+        FsFlags::empty()
+    }
+}
+
+/// Represents the inode at `/sys`.
+struct RootDirOps;
+
+impl RootDirOps {
+    pub fn new_inode(fs: Weak<SysFS>) -> Arc<dyn Inode> {
+        ProcDirBuilder::new(Self)
+            .fs(fs)
+            .ino(SYSFS_ROOT_INO)
+            .build()
+            .unwrap()
+    }
+}
+
+impl DirOps for RootDirOps {
+    fn lookup_child(&self, this_ptr: Weak<dyn Inode>, name: &str) -> Result<Arc<dyn Inode>> {
+        let inode = match name {
+            "class" => ClassDirOps::new_inode(this_ptr.clone()),
+            "devices" => DevicesDirOps::new_inode(this_ptr.clone()),
+            _ => return_errno!(Errno::ENOENT),
+        };
+        Ok(inode)
+    }
+
+    fn populate_children(&self, this_ptr: Weak<dyn Inode>) {
+        let this = {
+            let this = this_ptr.upgrade().unwrap();
+            this.downcast_ref::<ProcDir<RootDirOps>>().unwrap().this()
+        };
+        let mut cached_children = this.cached_children().write();
+        cached_children
+            .put_entry_if_not_found("class", || ClassDirOps::new_inode(this_ptr.clone()));
+        cached_children
+            .put_entry_if_not_found("devices", || DevicesDirOps::new_inode(this_ptr.clone()));
+    }
+}
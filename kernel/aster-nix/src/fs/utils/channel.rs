@@ -88,6 +88,18 @@ macro_rules! impl_common_methods_for_channel {
             self.this_end().pollee.poll(mask, poller)
         }
 
+        pub fn capacity(&self) -> usize {
+            self.0.common.capacity()
+        }
+
+        /// Sets the capacity of the channel.
+        ///
+        /// The items in the channel are kept. If there are more items than the new
+        /// capacity, this method fails with `EBUSY`.
+        pub fn set_capacity(&self, new_capacity: usize) -> Result<()> {
+            self.0.common.set_capacity(new_capacity)
+        }
+
         pub fn register_observer(
             &self,
             observer: Weak<dyn Observer<IoEvents>>,
@@ -122,7 +134,7 @@ impl<T> Producer<T> {
         // Update the event of pollee in a critical region so that pollee
         // always reflects the _true_ state of the underlying ring buffer
         // regardless of any race conditions.
-        self.0.common.lock_event();
+        let _event_guard = self.0.common.lock_event();
 
         let rb = this_end.rb();
         if rb.is_full() {
@@ -138,10 +150,27 @@ impl<T> Producer<T> {
 
 impl<T: Copy> Producer<T> {
     pub fn write(&self, buf: &[T]) -> Result<usize> {
+        self.write_with(buf, false)
+    }
+
+    /// Writes all the items in `buf` at once.
+    ///
+    /// Unlike [`write`], this method never writes part of the items. The items will not
+    /// be interleaved with the items written by other writers.
+    ///
+    /// [`write`]: Producer::write
+    pub fn write_atomic(&self, buf: &[T]) -> Result<usize> {
+        if buf.len() > self.capacity() {
+            return_errno_with_message!(Errno::EINVAL, "the buffer exceeds the capacity");
+        }
+        self.write_with(buf, true)
+    }
+
+    fn write_with(&self, buf: &[T], is_atomic: bool) -> Result<usize> {
         let is_nonblocking = self.is_nonblocking();
 
         // Fast path
-        let res = self.try_write(buf);
+        let res = self.try_write_with(buf, is_atomic);
         if should_io_return(&res, is_nonblocking) {
             return res;
         }
@@ -150,7 +179,7 @@ impl<T: Copy> Producer<T> {
         let mask = IoEvents::OUT;
         let poller = Poller::new();
         loop {
-            let res = self.try_write(buf);
+            let res = self.try_write_with(buf, is_atomic);
             if should_io_return(&res, is_nonblocking) {
                 return res;
             }
@@ -162,7 +191,25 @@ impl<T: Copy> Producer<T> {
         }
     }
 
-    fn try_write(&self, buf: &[T]) -> Result<usize> {
+    /// Writes the items in `buf` without blocking, regardless of the status flags.
+    pub fn try_write(&self, buf: &[T]) -> Result<usize> {
+        self.try_write_with(buf, false)
+    }
+
+    /// Writes all the items in `buf` at once without blocking, regardless of the status
+    /// flags.
+    ///
+    /// See [`write_atomic`] for details.
+    ///
+    /// [`write_atomic`]: Producer::write_atomic
+    pub fn try_write_atomic(&self, buf: &[T]) -> Result<usize> {
+        if buf.len() > self.capacity() {
+            return_errno_with_message!(Errno::EINVAL, "the buffer exceeds the capacity");
+        }
+        self.try_write_with(buf, true)
+    }
+
+    fn try_write_with(&self, buf: &[T], is_atomic: bool) -> Result<usize> {
         if self.is_shutdown() || self.is_peer_shutdown() {
             return_errno!(Errno::EPIPE);
         }
@@ -171,15 +218,28 @@ impl<T: Copy> Producer<T> {
             return Ok(0);
         }
 
-        let written_len = self.0.write(buf);
+        let written_len = if is_atomic {
+            self.0.write_atomic(buf)
+        } else {
+            self.0.write(buf)
+        };
 
         self.update_pollee();
 
         if written_len > 0 {
-            Ok(written_len)
-        } else {
-            return_errno_with_message!(Errno::EAGAIN, "try write later");
+            return Ok(written_len);
+        }
+
+        if is_atomic {
+            // The channel may not be full, but there is not enough room for the items.
+            // Clear the event so that the writer waits until the consumer makes room.
+            let _event_guard = self.0.common.lock_event();
+            if self.this_end().rb().free_len() < buf.len() {
+                self.this_end().pollee.del_events(IoEvents::OUT);
+            }
         }
+
+        return_errno_with_message!(Errno::EAGAIN, "try write later");
     }
 }
 
@@ -217,7 +277,11 @@ impl<T> Producer<T> {
         }
     }
 
-    fn try_push(&self, item: T) -> core::result::Result<(), (Error, T)> {
+    /// Pushes an item into the producer without blocking, regardless of the status flags.
+    ///
+    /// On failure, this method returns `Err` containing
+    /// the item fails to push.
+    pub fn try_push(&self, item: T) -> core::result::Result<(), (Error, T)> {
         if self.is_shutdown() || self.is_peer_shutdown() {
             let err = Error::with_message(Errno::EPIPE, "the pipe is shutdown");
             return Err((err, item));
@@ -237,7 +301,7 @@ impl<T> Drop for Producer<T> {
     fn drop(&mut self) {
         self.shutdown();
 
-        self.0.common.lock_event();
+        let _event_guard = self.0.common.lock_event();
 
         // When reading from a channel such as a pipe or a stream socket,
         // POLLHUP merely indicates that the peer closed its end of the channel.
@@ -261,7 +325,7 @@ impl<T> Consumer<T> {
         // Update the event of pollee in a critical region so that pollee
         // always reflects the _true_ state of the underlying ring buffer
         // regardless of any race conditions.
-        self.0.common.lock_event();
+        let _event_guard = self.0.common.lock_event();
 
         let rb = this_end.rb();
         if rb.is_empty() {
@@ -352,7 +416,8 @@ impl<T> Consumer<T> {
         }
     }
 
-    fn try_pop(&self) -> Result<T> {
+    /// Pops an item from the consumer without blocking, regardless of the status flags.
+    pub fn try_pop(&self) -> Result<T> {
         if self.is_shutdown() {
             return_errno_with_message!(Errno::EPIPE, "this end is shut down");
         }
@@ -377,7 +442,7 @@ impl<T> Drop for Consumer<T> {
     fn drop(&mut self) {
         self.shutdown();
 
-        self.0.common.lock_event();
+        let _event_guard = self.0.common.lock_event();
 
         // POLLERR is also set for a file descriptor referring to the write end of a pipe
         // when the read end has been closed.
@@ -408,6 +473,16 @@ impl<T: Copy, R: TRights> EndPoint<T, R> {
         let mut rb = self.common.producer.rb();
         rb.push_slice(buf)
     }
+
+    /// Writes all the items in `buf`, or nothing if there is not enough room.
+    #[require(R > Write)]
+    pub fn write_atomic(&self, buf: &[T]) -> usize {
+        let mut rb = self.common.producer.rb();
+        if rb.free_len() < buf.len() {
+            return 0;
+        }
+        rb.push_slice(buf)
+    }
 }
 
 impl<T, R: TRights> EndPoint<T, R> {
@@ -463,6 +538,41 @@ impl<T> Common<T> {
     pub fn capacity(&self) -> usize {
         self.producer.rb().capacity()
     }
+
+    pub fn set_capacity(&self, new_capacity: usize) -> Result<()> {
+        if new_capacity == 0 {
+            return_errno_with_message!(Errno::EINVAL, "capacity cannot be zero");
+        }
+
+        let _event_guard = self.lock_event();
+        let mut rb_producer = self.producer.rb();
+        let mut rb_consumer = self.consumer.rb();
+
+        if rb_consumer.len() > new_capacity {
+            return_errno_with_message!(Errno::EBUSY, "too many items in the channel");
+        }
+        if rb_producer.capacity() == new_capacity {
+            return Ok(());
+        }
+
+        let rb: HeapRb<T> = HeapRb::new(new_capacity);
+        let (mut new_producer, new_consumer) = rb.split();
+        while let Some(item) = rb_consumer.pop() {
+            // The new ring buffer is large enough to hold all the items.
+            let _ = new_producer.push(item);
+        }
+        *rb_producer = new_producer;
+        *rb_consumer = new_consumer;
+
+        // The pollee must reflect the new capacity.
+        if rb_producer.is_full() {
+            self.producer.pollee.del_events(IoEvents::OUT);
+        } else {
+            self.producer.pollee.add_events(IoEvents::OUT);
+        }
+
+        Ok(())
+    }
 }
 
 struct EndPointInner<T> {
@@ -40,6 +40,11 @@ bitflags! {
     pub struct FsFlags: u32 {
         /// Dentry cannot be evicted.
         const DENTRY_UNEVICTABLE = 1 << 1;
+        /// The names that do not exist can be cached as negative dentries.
+        ///
+        /// This requires that the names are only created via the dentry layer, so that
+        /// the negative dentries can be invalidated when the names are created.
+        const NEGATIVE_DENTRY = 1 << 2;
     }
 }
 
@@ -6,13 +6,17 @@ use core::time::Duration;
 
 use aster_rights::Full;
 use core2::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult, Write};
+use ostd::task::current_task;
 
-use super::{DirentVisitor, FileSystem, IoctlCmd};
+use super::{AccessMode, DirentVisitor, FileSystem, IoctlCmd, StatusFlags};
 use crate::{
     events::IoEvents,
-    fs::device::{Device, DeviceType},
+    fs::{
+        device::{Device, DeviceType},
+        inode_handle::FileIo,
+    },
     prelude::*,
-    process::{signal::Poller, Gid, Uid},
+    process::{credentials, credentials::capabilities::CapSet, signal::Poller, Gid, Uid},
     time::clocks::RealTimeCoarseClock,
     vm::vmo::Vmo,
 };
@@ -116,6 +120,22 @@ impl InodeMode {
     pub fn has_set_gid(&self) -> bool {
         self.contains(Self::S_ISGID)
     }
+
+    pub fn has_any_executable(&self) -> bool {
+        self.intersects(Self::S_IXUSR | Self::S_IXGRP | Self::S_IXOTH)
+    }
+}
+
+bitflags! {
+    /// The kinds of access that can be requested on an inode.
+    pub struct Permission: u16 {
+        /// Execute a file or search a directory.
+        const MAY_EXEC = 0x0001;
+        /// Write a file or add/remove entries in a directory.
+        const MAY_WRITE = 0x0002;
+        /// Read a file or list a directory.
+        const MAY_READ = 0x0004;
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -304,6 +324,18 @@ pub trait Inode: Any + Sync + Send {
         None
     }
 
+    /// Opens the inode for I/O, if the I/O is not done on the inode itself.
+    ///
+    /// For example, the I/O on a named pipe is done on the pipe behind it.
+    /// Returns `None` if the I/O is done on the inode directly.
+    fn open(
+        &self,
+        access_mode: AccessMode,
+        status_flags: StatusFlags,
+    ) -> Option<Result<Arc<dyn FileIo>>> {
+        None
+    }
+
     fn readdir_at(&self, offset: usize, visitor: &mut dyn DirentVisitor) -> Result<usize> {
         Err(Error::new(Errno::ENOTDIR))
     }
@@ -336,6 +368,15 @@ pub trait Inode: Any + Sync + Send {
         Err(Error::new(Errno::EISDIR))
     }
 
+    /// Returns whether the inode is a magic link, e.g., `/proc/[pid]/ns/user`.
+    ///
+    /// A magic link refers to a kernel object instead of a path. It is not followed
+    /// during the path lookup, so opening it opens the link itself, while its content
+    /// is still available through `read_link`.
+    fn is_magic_link(&self) -> bool {
+        false
+    }
+
     fn ioctl(&self, cmd: IoctlCmd, arg: usize) -> Result<i32> {
         Err(Error::new(Errno::EISDIR))
     }
@@ -406,6 +447,93 @@ impl dyn Inode {
         self.read_direct_at(0, &mut buf[..file_size])
     }
 
+    /// Checks whether the current thread is allowed to access the inode with `perm`.
+    ///
+    /// The permission bits of the owner are used if the file system user id of the current
+    /// thread is the owner of the inode. Otherwise, the permission bits of the group are used
+    /// if the file system group id or one of the supplementary group ids is the group of the
+    /// inode. Otherwise, the permission bits of the others are used.
+    ///
+    /// If the permission bits of the inode deny the access, the effective capabilities of
+    /// the current thread are consulted, following the rules of Linux:
+    /// - `CAP_DAC_READ_SEARCH` grants reading files, and reading and searching directories;
+    /// - `CAP_DAC_OVERRIDE` grants any access, except that executing a non-directory inode
+    ///   still requires at least one of its execute bits to be set.
+    ///
+    /// Kernel threads are never denied.
+    pub fn check_permission(&self, perm: Permission) -> Result<()> {
+        if current_task()
+            .and_then(|task| task.user_space().cloned())
+            .is_none()
+        {
+            return Ok(());
+        }
+
+        let credentials = credentials();
+        let capset = credentials.user_ns().capset_over(
+            credentials.effective_capset(),
+            self.owner()?,
+            self.group()?,
+        );
+        self.check_permission_as(
+            perm,
+            credentials.fsuid(),
+            credentials.fsgid(),
+            &credentials.groups(),
+            capset,
+        )
+    }
+
+    /// Checks whether the access to the inode with `perm` is allowed for the accessor
+    /// with the user id `uid`, the group id `gid`, the supplementary group ids `groups`
+    /// and the capabilities `capset`.
+    ///
+    /// This is the same as `check_permission`, except that the caller decides the identity
+    /// of the accessor, e.g., `access` uses the real user.
+    pub fn check_permission_as(
+        &self,
+        perm: Permission,
+        uid: Uid,
+        gid: Gid,
+        groups: &BTreeSet<Gid>,
+        capset: CapSet,
+    ) -> Result<()> {
+        let mode = self.mode()?;
+        // The permission bits of the owner, the group or the others, in the same order
+        // as those of `Permission`.
+        let class_bits = if uid == self.owner()? {
+            mode.bits() >> 6
+        } else if gid == self.group()? || groups.contains(&self.group()?) {
+            mode.bits() >> 3
+        } else {
+            mode.bits()
+        };
+        let granted = Permission::from_bits_truncate(class_bits & 0o7);
+        if granted.contains(perm) {
+            return Ok(());
+        }
+
+        if self.type_() == InodeType::Dir {
+            if !perm.contains(Permission::MAY_WRITE) && capset.contains(CapSet::DAC_READ_SEARCH) {
+                return Ok(());
+            }
+            if capset.contains(CapSet::DAC_OVERRIDE) {
+                return Ok(());
+            }
+        } else {
+            if (!perm.contains(Permission::MAY_EXEC) || mode.has_any_executable())
+                && capset.contains(CapSet::DAC_OVERRIDE)
+            {
+                return Ok(());
+            }
+            if perm == Permission::MAY_READ && capset.contains(CapSet::DAC_READ_SEARCH) {
+                return Ok(());
+            }
+        }
+
+        return_errno_with_message!(Errno::EACCES, "permission denied");
+    }
+
     pub fn writer(&self, from_offset: usize) -> InodeWriter {
         InodeWriter {
             inner: self,
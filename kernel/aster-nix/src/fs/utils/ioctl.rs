@@ -29,6 +29,8 @@ pub enum IoctlCmd {
     TIOCGPTN = 0x80045430,
     /// Lock/unlock Pty
     TIOCSPTLCK = 0x40045431,
+    /// Get the lock state of Pty
+    TIOCGPTLCK = 0x80045439,
     /// Safely open the slave
     TIOCGPTPEER = 0x40045441,
     /// Get tdx report using TDCALL
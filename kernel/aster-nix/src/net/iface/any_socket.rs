@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use smoltcp::wire::{IpProtocol, IpVersion};
+
 use super::{Iface, IpAddress, IpEndpoint};
 use crate::{events::Observer, prelude::*};
 
 pub type RawTcpSocket = smoltcp::socket::tcp::Socket<'static>;
 pub type RawUdpSocket = smoltcp::socket::udp::Socket<'static>;
+pub type RawIpSocket = smoltcp::socket::raw::Socket<'static>;
 
 pub struct AnyUnboundSocket {
     socket_family: AnyRawSocket,
@@ -15,11 +18,13 @@ pub struct AnyUnboundSocket {
 pub(super) enum AnyRawSocket {
     Tcp(RawTcpSocket),
     Udp(RawUdpSocket),
+    Raw(RawIpSocket),
 }
 
 pub(super) enum SocketFamily {
     Tcp,
     Udp,
+    Raw,
 }
 
 impl AnyUnboundSocket {
@@ -54,6 +59,31 @@ impl AnyUnboundSocket {
         }
     }
 
+    /// Creates a raw socket, which sends and receives the IPv4 packets of `protocol`.
+    pub fn new_raw(protocol: IpProtocol, observer: Weak<dyn Observer<()>>) -> Self {
+        let raw_ip_socket = {
+            let metadata = smoltcp::socket::raw::PacketMetadata::EMPTY;
+            let rx_buffer = smoltcp::socket::raw::PacketBuffer::new(
+                vec![metadata; RAW_METADATA_LEN],
+                vec![0u8; RAW_RECEIVE_PAYLOAD_LEN],
+            );
+            let tx_buffer = smoltcp::socket::raw::PacketBuffer::new(
+                vec![metadata; RAW_METADATA_LEN],
+                vec![0u8; RAW_SEND_PAYLOAD_LEN],
+            );
+            RawIpSocket::new(IpVersion::Ipv4, protocol, rx_buffer, tx_buffer)
+        };
+        AnyUnboundSocket {
+            socket_family: AnyRawSocket::Raw(raw_ip_socket),
+            observer,
+        }
+    }
+
+    /// Returns whether the socket is a raw socket, which is not bound to any port.
+    pub(super) fn is_raw(&self) -> bool {
+        matches!(self.socket_family, AnyRawSocket::Raw(_))
+    }
+
     pub(super) fn into_raw(self) -> (AnyRawSocket, Weak<dyn Observer<()>>) {
         (self.socket_family, self.observer)
     }
@@ -146,6 +176,8 @@ impl AnyBoundSocket {
         match self.socket_family {
             SocketFamily::Tcp => self.raw_with(|socket: &mut RawTcpSocket| socket.close()),
             SocketFamily::Udp => self.raw_with(|socket: &mut RawUdpSocket| socket.close()),
+            // Raw sockets have no connection or binding to close.
+            SocketFamily::Raw => (),
         }
     }
 }
@@ -155,7 +187,9 @@ impl Drop for AnyBoundSocket {
         self.close();
         self.iface.poll();
         self.iface.common().remove_socket(self.handle);
-        self.iface.common().release_port(self.port);
+        if !matches!(self.socket_family, SocketFamily::Raw) {
+            self.iface.common().release_port(self.port);
+        }
         self.iface.common().remove_bound_socket(self.weak_ref());
     }
 }
@@ -168,3 +202,8 @@ pub const SEND_BUF_LEN: usize = 65536;
 const UDP_METADATA_LEN: usize = 256;
 const UDP_SEND_PAYLOAD_LEN: usize = 65536;
 const UDP_RECEIVE_PAYLOAD_LEN: usize = 65536;
+
+// For raw sockets
+const RAW_METADATA_LEN: usize = 64;
+const RAW_SEND_PAYLOAD_LEN: usize = 65536;
+const RAW_RECEIVE_PAYLOAD_LEN: usize = 65536;
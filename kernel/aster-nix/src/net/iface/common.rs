@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use alloc::collections::btree_map::Entry;
-use core::sync::atomic::{AtomicU64, Ordering};
+use core::sync::atomic::{AtomicU16, AtomicU64, Ordering};
 
 use keyable_arc::KeyableWeak;
 use ostd::sync::WaitQueue;
@@ -23,6 +23,8 @@ pub struct IfaceCommon {
     interface: SpinLock<smoltcp::iface::Interface>,
     sockets: SpinLock<SocketSet<'static>>,
     used_ports: RwLock<BTreeMap<u16, usize>>,
+    /// The port where the search for the next ephemeral port starts.
+    next_ephemeral_port: AtomicU16,
     /// The time should do next poll. We stores the total milliseconds since system boots up.
     next_poll_at_ms: AtomicU64,
     bound_sockets: RwLock<BTreeSet<KeyableWeak<AnyBoundSocket>>>,
@@ -38,6 +40,7 @@ impl IfaceCommon {
             interface: SpinLock::new(interface),
             sockets: SpinLock::new(socket_set),
             used_ports: RwLock::new(used_ports),
+            next_ephemeral_port: AtomicU16::new(0),
             next_poll_at_ms: AtomicU64::new(0),
             bound_sockets: RwLock::new(BTreeSet::new()),
             polling_wait_queue: WaitQueue::new(),
@@ -68,16 +71,32 @@ impl IfaceCommon {
         &self.polling_wait_queue
     }
 
-    /// Alloc an unused port range from 49152 ~ 65535 (According to smoltcp docs)
+    /// Allocates an unused port in the range of ephemeral ports.
+    ///
+    /// The ports are tried in order, starting from the one after the last allocated port, so
+    /// that a released port will not be reused immediately.
     fn alloc_ephemeral_port(&self) -> Result<u16> {
+        let (start, end) = ip_local_port_range();
         let mut used_ports = self.used_ports.write();
-        for port in IP_LOCAL_PORT_START..=IP_LOCAL_PORT_END {
+
+        let first = {
+            let next = self.next_ephemeral_port.load(Ordering::Relaxed);
+            if (start..=end).contains(&next) {
+                next
+            } else {
+                start
+            }
+        };
+        for port in (first..=end).chain(start..first) {
             if let Entry::Vacant(e) = used_ports.entry(port) {
                 e.insert(0);
+                self.next_ephemeral_port
+                    .store(port.wrapping_add(1), Ordering::Relaxed);
                 return Ok(port);
             }
         }
-        return_errno_with_message!(Errno::EAGAIN, "no ephemeral port is available");
+
+        return_errno_with_message!(Errno::EADDRNOTAVAIL, "no ephemeral port is available");
     }
 
     fn bind_port(&self, port: u16, can_reuse: bool) -> Result<()> {
@@ -110,17 +129,23 @@ impl IfaceCommon {
         socket: Box<AnyUnboundSocket>,
         config: BindPortConfig,
     ) -> core::result::Result<Arc<AnyBoundSocket>, (Error, Box<AnyUnboundSocket>)> {
-        let port = if let Some(port) = config.port() {
-            port
+        // Raw sockets are not bound to any port, so the port is simply zero.
+        let port = if socket.is_raw() {
+            0
         } else {
-            match self.alloc_ephemeral_port() {
-                Ok(port) => port,
-                Err(err) => return Err((err, socket)),
+            let port = if let Some(port) = config.port() {
+                port
+            } else {
+                match self.alloc_ephemeral_port() {
+                    Ok(port) => port,
+                    Err(err) => return Err((err, socket)),
+                }
+            };
+            if let Some(err) = self.bind_port(port, config.can_reuse()).err() {
+                return Err((err, socket));
             }
+            port
         };
-        if let Some(err) = self.bind_port(port, config.can_reuse()).err() {
-            return Err((err, socket));
-        }
 
         let (handle, socket_family, observer) = match socket.into_raw() {
             (AnyRawSocket::Tcp(tcp_socket), observer) => (
@@ -133,6 +158,11 @@ impl IfaceCommon {
                 SocketFamily::Udp,
                 observer,
             ),
+            (AnyRawSocket::Raw(raw_socket), observer) => (
+                self.sockets.lock_irq_disabled().add(raw_socket),
+                SocketFamily::Raw,
+                observer,
+            ),
         };
         let bound_socket = AnyBoundSocket::new(iface, handle, port, socket_family, observer);
         self.insert_bound_socket(&bound_socket).unwrap();
@@ -200,5 +230,21 @@ impl IfaceCommon {
     }
 }
 
-const IP_LOCAL_PORT_START: u16 = 49152;
-const IP_LOCAL_PORT_END: u16 = 65535;
+/// The range of ephemeral ports, which can be changed at
+/// `/proc/sys/net/ipv4/ip_local_port_range`.
+static IP_LOCAL_PORT_RANGE: RwLock<(u16, u16)> = RwLock::new((32768, 60999));
+
+/// Returns the first and the last ports of the range of ephemeral ports.
+pub fn ip_local_port_range() -> (u16, u16) {
+    *IP_LOCAL_PORT_RANGE.read()
+}
+
+/// Sets the first and the last ports of the range of ephemeral ports.
+pub fn set_ip_local_port_range(start: u16, end: u16) -> Result<()> {
+    if start == 0 || start > end {
+        return_errno_with_message!(Errno::EINVAL, "the port range is invalid");
+    }
+
+    *IP_LOCAL_PORT_RANGE.write() = (start, end);
+    Ok(())
+}
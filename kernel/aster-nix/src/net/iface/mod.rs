@@ -14,8 +14,10 @@ mod util;
 mod virtio;
 
 pub use any_socket::{
-    AnyBoundSocket, AnyUnboundSocket, RawTcpSocket, RawUdpSocket, RECV_BUF_LEN, SEND_BUF_LEN,
+    AnyBoundSocket, AnyUnboundSocket, RawIpSocket, RawTcpSocket, RawUdpSocket, RECV_BUF_LEN,
+    SEND_BUF_LEN,
 };
+pub use common::{ip_local_port_range, set_ip_local_port_range};
 pub use loopback::IfaceLoopback;
 pub use smoltcp::wire::{EthernetAddress, IpAddress, IpEndpoint, Ipv4Address};
 pub use util::{spawn_background_poll_thread, BindPortConfig};
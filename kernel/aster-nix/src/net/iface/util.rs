@@ -28,9 +28,8 @@ impl BindPortConfig {
             } else {
                 Self::Specified(port)
             }
-        } else if can_reuse {
-            return_errno_with_message!(Errno::EINVAL, "invalid bind port config");
         } else {
+            // An ephemeral port is never in use, so there is no need to reuse it.
             Self::Ephemeral
         };
         Ok(config)
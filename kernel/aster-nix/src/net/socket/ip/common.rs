@@ -6,6 +6,7 @@ use crate::{
         IFACES,
     },
     prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
 };
 
 pub fn get_iface_to_bind(ip_addr: &IpAddress) -> Option<Arc<dyn Iface>> {
@@ -23,6 +24,31 @@ pub fn get_iface_to_bind(ip_addr: &IpAddress) -> Option<Arc<dyn Iface>> {
         .map(Clone::clone)
 }
 
+/// Gets the iface named `name`.
+///
+/// The name can be kept as `&'static str` to refer to the iface, since `IFACES` is never changed
+/// once it is initialized.
+pub fn get_iface_by_name(name: &str) -> Option<&'static Arc<dyn Iface>> {
+    let ifaces = IFACES.get().unwrap();
+    ifaces.iter().find(|iface| iface.name() == name)
+}
+
+/// Checks the device name set by `SO_BINDTODEVICE` and returns the name of the device to bind.
+///
+/// An empty name removes the binding, in which case `None` is returned.
+pub(super) fn check_bind_to_device(name: &str) -> Result<Option<&'static str>> {
+    if !credentials().capable(CapSet::NET_RAW) {
+        return_errno_with_message!(Errno::EPERM, "CAP_NET_RAW is required to bind to a device");
+    }
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    let iface = get_iface_by_name(name)
+        .ok_or_else(|| Error::with_message(Errno::ENODEV, "the device does not exist"))?;
+    Ok(Some(iface.name()))
+}
+
 /// Get a suitable iface to deal with sendto/connect request if the socket is not bound to an iface.
 /// If the remote address is the same as that of some iface, we will use the iface.
 /// Otherwise, we will use a default interface.
@@ -42,17 +68,27 @@ fn get_ephemeral_iface(remote_ip_addr: &IpAddress) -> Arc<dyn Iface> {
     ifaces[0].clone()
 }
 
+/// Binds the socket to `endpoint`.
+///
+/// If the socket is bound to the device named `bound_device` (i.e., via `SO_BINDTODEVICE`), the
+/// socket can only be bound to the address of that device. An unspecified address is also
+/// accepted in this case, meaning the address of the device.
 pub(super) fn bind_socket(
     unbound_socket: Box<AnyUnboundSocket>,
     endpoint: &IpEndpoint,
     can_reuse: bool,
+    bound_device: Option<&str>,
 ) -> core::result::Result<Arc<AnyBoundSocket>, (Error, Box<AnyUnboundSocket>)> {
-    let iface = match get_iface_to_bind(&endpoint.addr) {
-        Some(iface) => iface,
-        None => {
-            let err = Error::with_message(Errno::EADDRNOTAVAIL, "Request iface is not available");
-            return Err((err, unbound_socket));
-        }
+    let iface = match bound_device {
+        Some(name) => get_iface_by_name(name).cloned().filter(|iface| {
+            let IpAddress::Ipv4(ipv4_addr) = endpoint.addr;
+            ipv4_addr.is_unspecified() || iface.ipv4_addr() == Some(ipv4_addr)
+        }),
+        None => get_iface_to_bind(&endpoint.addr),
+    };
+    let Some(iface) = iface else {
+        let err = Error::with_message(Errno::EADDRNOTAVAIL, "Request iface is not available");
+        return Err((err, unbound_socket));
     };
     let bind_port_config = match BindPortConfig::new(endpoint.port, can_reuse) {
         Ok(config) => config,
@@ -61,8 +97,17 @@ pub(super) fn bind_socket(
     iface.bind_socket(unbound_socket, bind_port_config)
 }
 
-pub fn get_ephemeral_endpoint(remote_endpoint: &IpEndpoint) -> IpEndpoint {
-    let iface = get_ephemeral_iface(&remote_endpoint.addr);
+/// Gets the local endpoint to deal with sendto/connect request if the socket is not bound.
+///
+/// If the socket is bound to the device named `bound_device`, the address of that device is used.
+pub fn get_ephemeral_endpoint(
+    remote_endpoint: &IpEndpoint,
+    bound_device: Option<&str>,
+) -> IpEndpoint {
+    let iface = match bound_device.and_then(get_iface_by_name) {
+        Some(iface) => iface.clone(),
+        None => get_ephemeral_iface(&remote_endpoint.addr),
+    };
     let ip_addr = iface.ipv4_addr().unwrap();
     IpEndpoint::new(IpAddress::Ipv4(ip_addr), 0)
 }
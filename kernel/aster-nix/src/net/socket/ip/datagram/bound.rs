@@ -37,10 +37,19 @@ impl BoundDatagram {
         self.remote_endpoint = Some(*endpoint)
     }
 
+    /// Receives a datagram into `buf`.
+    ///
+    /// The excess bytes are discarded if the datagram is larger than `buf`. On success,
+    /// this method returns the length of the whole datagram, which may be larger than the
+    /// length of `buf`.
     pub fn try_recv(&self, buf: &mut [u8], _flags: SendRecvFlags) -> Result<(usize, IpEndpoint)> {
-        let result = self
-            .bound_socket
-            .raw_with(|socket: &mut RawUdpSocket| socket.recv_slice(buf));
+        let result = self.bound_socket.raw_with(|socket: &mut RawUdpSocket| {
+            socket.recv().map(|(datagram, endpoint)| {
+                let copied_len = datagram.len().min(buf.len());
+                buf[..copied_len].copy_from_slice(&datagram[..copied_len]);
+                (datagram.len(), endpoint)
+            })
+        });
         match result {
             Ok((recv_len, endpoint)) => Ok((recv_len, endpoint)),
             Err(RecvError::Exhausted) => {
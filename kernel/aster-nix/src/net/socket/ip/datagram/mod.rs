@@ -5,19 +5,31 @@ use core::sync::atomic::{AtomicBool, Ordering};
 use takeable::Takeable;
 
 use self::{bound::BoundDatagram, unbound::UnboundDatagram};
-use super::{common::get_ephemeral_endpoint, UNSPECIFIED_LOCAL_ENDPOINT};
+use super::{
+    common::{check_bind_to_device, get_ephemeral_endpoint},
+    UNSPECIFIED_LOCAL_ENDPOINT,
+};
 use crate::{
     events::{IoEvents, Observer},
-    fs::{file_handle::FileLike, utils::StatusFlags},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        utils::StatusFlags,
+    },
+    match_sock_option_mut, match_sock_option_ref,
     net::{
         iface::IpEndpoint,
         poll_ifaces,
         socket::{
+            options::{BindToDevice, Priority, SocketOption},
             util::{
                 copy_message_from_user, copy_message_to_user, create_message_buffer,
-                send_recv_flags::SendRecvFlags, socket_addr::SocketAddr, MessageHeader,
+                options::{check_priority, SocketOptionSet},
+                recv_return_len,
+                send_recv_flags::SendRecvFlags,
+                socket_addr::SocketAddr,
+                truncated_flags, MessageHeader,
             },
-            Socket,
+            IfName, Socket, SocketInfo,
         },
     },
     prelude::*,
@@ -30,8 +42,11 @@ mod unbound;
 
 pub struct DatagramSocket {
     inner: RwLock<Takeable<Inner>>,
+    options: RwLock<SocketOptionSet>,
     nonblocking: AtomicBool,
     pollee: Pollee,
+    info: SocketInfo,
+    _file_count: FileCount,
 }
 
 enum Inner {
@@ -40,7 +55,11 @@ enum Inner {
 }
 
 impl Inner {
-    fn bind(self, endpoint: &IpEndpoint) -> core::result::Result<BoundDatagram, (Error, Self)> {
+    fn bind(
+        self,
+        endpoint: &IpEndpoint,
+        bound_device: Option<&str>,
+    ) -> core::result::Result<BoundDatagram, (Error, Self)> {
         let unbound_datagram = match self {
             Inner::Unbound(unbound_datagram) => unbound_datagram,
             Inner::Bound(bound_datagram) => {
@@ -51,7 +70,7 @@ impl Inner {
             }
         };
 
-        let bound_datagram = match unbound_datagram.bind(endpoint) {
+        let bound_datagram = match unbound_datagram.bind(endpoint, bound_device) {
             Ok(bound_datagram) => bound_datagram,
             Err((err, unbound_datagram)) => return Err((err, Inner::Unbound(unbound_datagram))),
         };
@@ -61,26 +80,30 @@ impl Inner {
     fn bind_to_ephemeral_endpoint(
         self,
         remote_endpoint: &IpEndpoint,
+        bound_device: Option<&str>,
     ) -> core::result::Result<BoundDatagram, (Error, Self)> {
         if let Inner::Bound(bound_datagram) = self {
             return Ok(bound_datagram);
         }
 
-        let endpoint = get_ephemeral_endpoint(remote_endpoint);
-        self.bind(&endpoint)
+        let endpoint = get_ephemeral_endpoint(remote_endpoint, bound_device);
+        self.bind(&endpoint, bound_device)
     }
 }
 
 impl DatagramSocket {
-    pub fn new(nonblocking: bool) -> Arc<Self> {
+    pub fn new(info: SocketInfo, nonblocking: bool) -> Arc<Self> {
         Arc::new_cyclic(|me| {
             let unbound_datagram = UnboundDatagram::new(me.clone() as _);
             let pollee = Pollee::new(IoEvents::empty());
             unbound_datagram.init_pollee(&pollee);
             Self {
                 inner: RwLock::new(Takeable::new(Inner::Unbound(unbound_datagram))),
+                options: RwLock::new(SocketOptionSet::new_udp()),
                 nonblocking: AtomicBool::new(nonblocking),
                 pollee,
+                info,
+                _file_count: FileCount::new(),
             }
         })
     }
@@ -109,14 +132,16 @@ impl DatagramSocket {
         }
 
         // Slow path
+        let bound_device = self.options.read().bound_device();
         let mut inner = self.inner.write();
         inner.borrow_result(|owned_inner| {
-            let bound_datagram = match owned_inner.bind_to_ephemeral_endpoint(remote_endpoint) {
-                Ok(bound_datagram) => bound_datagram,
-                Err((err, err_inner)) => {
-                    return (err_inner, Err(err));
-                }
-            };
+            let bound_datagram =
+                match owned_inner.bind_to_ephemeral_endpoint(remote_endpoint, bound_device) {
+                    Ok(bound_datagram) => bound_datagram,
+                    Err((err, err_inner)) => {
+                        return (err_inner, Err(err));
+                    }
+                };
             bound_datagram.init_pollee(&self.pollee);
             (Inner::Bound(bound_datagram), Ok(()))
         })
@@ -205,7 +230,7 @@ impl FileLike for DatagramSocket {
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
         // TODO: set correct flags
         let flags = SendRecvFlags::empty();
-        self.recv(buf, flags).map(|(len, _)| len)
+        self.recv(buf, flags).map(|(len, _)| len.min(buf.len()))
     }
 
     fn write(&self, buf: &[u8]) -> Result<usize> {
@@ -257,6 +282,15 @@ impl FileLike for DatagramSocket {
         Ok(())
     }
 
+    fn register_exclusive_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_exclusive_observer(observer, mask);
+        Ok(())
+    }
+
     fn unregister_observer(
         &self,
         observer: &Weak<dyn Observer<IoEvents>>,
@@ -266,12 +300,17 @@ impl FileLike for DatagramSocket {
 }
 
 impl Socket for DatagramSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
     fn bind(&self, socket_addr: SocketAddr) -> Result<()> {
         let endpoint = socket_addr.try_into()?;
 
+        let bound_device = self.options.read().bound_device();
         let mut inner = self.inner.write();
         inner.borrow_result(|owned_inner| {
-            let bound_datagram = match owned_inner.bind(&endpoint) {
+            let bound_datagram = match owned_inner.bind(&endpoint, bound_device) {
                 Ok(bound_datagram) => bound_datagram,
                 Err((err, err_inner)) => {
                     return (err_inner, Err(err));
@@ -310,6 +349,40 @@ impl Socket for DatagramSocket {
             .ok_or_else(|| Error::with_message(Errno::ENOTCONN, "the socket is not connected"))
     }
 
+    fn get_option(&self, option: &mut dyn SocketOption) -> Result<()> {
+        match_sock_option_mut!(option, {
+            socket_bind_to_device: BindToDevice => {
+                let bound_device = self.options.read().bound_device().unwrap_or_default();
+                socket_bind_to_device.set(IfName::new(bound_device));
+            },
+            socket_priority: Priority => {
+                let priority = self.options.read().priority();
+                socket_priority.set(priority);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to get is unknown")
+        });
+
+        Ok(())
+    }
+
+    fn set_option(&self, option: &dyn SocketOption) -> Result<()> {
+        match_sock_option_ref!(option, {
+            socket_bind_to_device: BindToDevice => {
+                let name = socket_bind_to_device.get().unwrap();
+                let bound_device = check_bind_to_device(name.as_str())?;
+                self.options.write().set_bound_device(bound_device);
+            },
+            socket_priority: Priority => {
+                let priority = socket_priority.get().unwrap();
+                check_priority(*priority)?;
+                self.options.write().set_priority(*priority);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to be set is unknown")
+        });
+
+        Ok(())
+    }
+
     fn sendmsg(
         &self,
         io_vecs: &[IoVec],
@@ -322,6 +395,7 @@ impl Socket for DatagramSocket {
         let MessageHeader {
             addr,
             control_message,
+            ..
         } = message_header;
 
         let remote_endpoint = match addr {
@@ -350,23 +424,27 @@ impl Socket for DatagramSocket {
     }
 
     fn recvmsg(&self, io_vecs: &[IoVec], flags: SendRecvFlags) -> Result<(usize, MessageHeader)> {
-        // TODO: Deal with flags
-        debug_assert!(flags.is_all_supported());
+        // TODO: Deal with other flags
+        debug_assert!((flags - SendRecvFlags::MSG_TRUNC).is_all_supported());
 
         let mut buf = create_message_buffer(io_vecs);
 
         let (received_bytes, peer_addr) = self.recv(&mut buf, flags)?;
 
         let copied_bytes = {
-            let message = &buf[..received_bytes];
+            let message = &buf[..received_bytes.min(buf.len())];
             copy_message_to_user(io_vecs, message)
         };
 
         // TODO: Receive control message
 
-        let message_header = MessageHeader::new(Some(peer_addr), None);
+        let message_header = MessageHeader::new(Some(peer_addr), None)
+            .with_flags(truncated_flags(received_bytes, copied_bytes));
 
-        Ok((copied_bytes, message_header))
+        Ok((
+            recv_return_len(received_bytes, copied_bytes, flags),
+            message_header,
+        ))
     }
 }
 
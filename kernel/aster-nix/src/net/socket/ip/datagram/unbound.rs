@@ -24,8 +24,12 @@ impl UnboundDatagram {
         }
     }
 
-    pub fn bind(self, endpoint: &IpEndpoint) -> core::result::Result<BoundDatagram, (Error, Self)> {
-        let bound_socket = match bind_socket(self.unbound_socket, endpoint, false) {
+    pub fn bind(
+        self,
+        endpoint: &IpEndpoint,
+        bound_device: Option<&str>,
+    ) -> core::result::Result<BoundDatagram, (Error, Self)> {
+        let bound_socket = match bind_socket(self.unbound_socket, endpoint, false, bound_device) {
             Ok(bound_socket) => bound_socket,
             Err((err, unbound_socket)) => return Err((err, Self { unbound_socket })),
         };
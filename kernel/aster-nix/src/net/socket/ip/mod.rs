@@ -4,9 +4,12 @@ use crate::net::iface::{IpAddress, IpEndpoint, Ipv4Address};
 
 mod common;
 mod datagram;
+pub mod options;
+mod raw;
 pub mod stream;
 
 pub use datagram::DatagramSocket;
+pub use raw::RawSocket;
 pub use stream::StreamSocket;
 
 /// A local endpoint, which indicates that the local endpoint is unspecified.
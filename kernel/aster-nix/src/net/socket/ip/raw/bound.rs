@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use smoltcp::{
+    phy::ChecksumCapabilities,
+    socket::raw::{RecvError, SendError},
+    wire::{IpProtocol, Ipv4Packet, Ipv4Repr},
+};
+
+use crate::{
+    events::IoEvents,
+    net::iface::{AnyBoundSocket, Ipv4Address, RawIpSocket},
+    prelude::*,
+    process::signal::Pollee,
+};
+
+/// The default TTL of the IPv4 packets sent by raw sockets.
+const DEFAULT_TTL: u8 = 64;
+
+/// The length of the IPv4 header without any options.
+const IPV4_HEADER_LEN: usize = 20;
+
+pub struct BoundRaw {
+    bound_socket: Arc<AnyBoundSocket>,
+    protocol: IpProtocol,
+    remote_addr: Option<Ipv4Address>,
+}
+
+impl BoundRaw {
+    pub fn new(bound_socket: Arc<AnyBoundSocket>, protocol: IpProtocol) -> Self {
+        Self {
+            bound_socket,
+            protocol,
+            remote_addr: None,
+        }
+    }
+
+    pub fn local_addr(&self) -> Ipv4Address {
+        self.bound_socket.iface().ipv4_addr().unwrap()
+    }
+
+    pub fn remote_addr(&self) -> Option<Ipv4Address> {
+        self.remote_addr
+    }
+
+    pub fn set_remote_addr(&mut self, addr: Ipv4Address) {
+        self.remote_addr = Some(addr)
+    }
+
+    /// Receives a packet into `buf`, including the IP header.
+    ///
+    /// The excess bytes are discarded if the packet is larger than `buf`. On success,
+    /// this method returns the length of the whole packet, which may be larger than the
+    /// length of `buf`, and the source address of the packet.
+    pub fn try_recv(&self, buf: &mut [u8]) -> Result<(usize, Ipv4Address)> {
+        let result = self.bound_socket.raw_with(|socket: &mut RawIpSocket| {
+            socket.recv().map(|packet| {
+                let copied_len = packet.len().min(buf.len());
+                buf[..copied_len].copy_from_slice(&packet[..copied_len]);
+                // The packets are checked by the iface before they are delivered to the socket.
+                let src_addr = Ipv4Packet::new_unchecked(packet).src_addr();
+                (packet.len(), src_addr)
+            })
+        });
+        match result {
+            Ok((recv_len, src_addr)) => Ok((recv_len, src_addr)),
+            Err(RecvError::Exhausted) => {
+                return_errno_with_message!(Errno::EAGAIN, "the receive buffer is empty")
+            }
+        }
+    }
+
+    /// Sends `buf` to `remote`.
+    ///
+    /// If `hdr_incl` is true, `buf` is a whole IPv4 packet that is sent as is. Otherwise,
+    /// `buf` is the payload and the IPv4 header is built by the kernel.
+    pub fn try_send(&self, buf: &[u8], remote: &Ipv4Address, hdr_incl: bool) -> Result<usize> {
+        let packet = if hdr_incl {
+            if buf.len() < IPV4_HEADER_LEN {
+                return_errno_with_message!(Errno::EINVAL, "the IP header is incomplete");
+            }
+            buf.to_vec()
+        } else {
+            self.build_packet(buf, remote)
+        };
+
+        let result = self.bound_socket.raw_with(|socket: &mut RawIpSocket| {
+            if socket.payload_send_capacity() < packet.len() {
+                return None;
+            }
+            Some(socket.send_slice(&packet))
+        });
+        match result {
+            Some(Ok(())) => Ok(buf.len()),
+            Some(Err(SendError::BufferFull)) => {
+                return_errno_with_message!(Errno::EAGAIN, "the send buffer is full")
+            }
+            None => return_errno_with_message!(Errno::EMSGSIZE, "the message is too large"),
+        }
+    }
+
+    fn build_packet(&self, payload: &[u8], remote: &Ipv4Address) -> Vec<u8> {
+        let ipv4_repr = Ipv4Repr {
+            src_addr: self.local_addr(),
+            dst_addr: *remote,
+            next_header: self.protocol,
+            payload_len: payload.len(),
+            hop_limit: DEFAULT_TTL,
+        };
+
+        let mut packet = vec![0u8; ipv4_repr.buffer_len() + payload.len()];
+        let mut ipv4_packet = Ipv4Packet::new_unchecked(&mut packet);
+        ipv4_repr.emit(&mut ipv4_packet, &ChecksumCapabilities::default());
+        ipv4_packet.payload_mut().copy_from_slice(payload);
+        packet
+    }
+
+    pub(super) fn init_pollee(&self, pollee: &Pollee) {
+        pollee.reset_events();
+        self.update_io_events(pollee)
+    }
+
+    pub(super) fn update_io_events(&self, pollee: &Pollee) {
+        self.bound_socket.raw_with(|socket: &mut RawIpSocket| {
+            if socket.can_recv() {
+                pollee.add_events(IoEvents::IN);
+            } else {
+                pollee.del_events(IoEvents::IN);
+            }
+
+            if socket.can_send() {
+                pollee.add_events(IoEvents::OUT);
+            } else {
+                pollee.del_events(IoEvents::OUT);
+            }
+        });
+    }
+}
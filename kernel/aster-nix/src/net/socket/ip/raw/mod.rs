@@ -0,0 +1,476 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use smoltcp::wire::IpProtocol;
+use takeable::Takeable;
+
+use self::bound::BoundRaw;
+use super::{
+    common::{bind_socket, get_ephemeral_endpoint},
+    options::HdrIncl,
+    UNSPECIFIED_LOCAL_ENDPOINT,
+};
+use crate::{
+    events::{IoEvents, Observer},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        utils::StatusFlags,
+    },
+    match_sock_option_mut, match_sock_option_ref,
+    net::{
+        iface::{AnyUnboundSocket, IpAddress, IpEndpoint, Ipv4Address},
+        poll_ifaces,
+        socket::{
+            options::SocketOption,
+            util::{
+                copy_message_from_user, copy_message_to_user, create_message_buffer,
+                recv_return_len, send_recv_flags::SendRecvFlags, socket_addr::SocketAddr,
+                truncated_flags, MessageHeader,
+            },
+            Socket, SocketInfo,
+        },
+    },
+    prelude::*,
+    process::{
+        credentials,
+        credentials::capabilities::CapSet,
+        signal::{Pollee, Poller},
+    },
+    util::{net::Protocol, IoVec},
+};
+
+mod bound;
+
+/// A raw socket, which sends and receives the IPv4 packets of a specific protocol.
+///
+/// Like Linux, the received packets always include the IP header, while the IP header of the
+/// packets to send is built by the kernel unless `IP_HDRINCL` is set.
+///
+/// Currently, only ICMP raw sockets are supported.
+pub struct RawSocket {
+    inner: RwLock<Takeable<Inner>>,
+    info: SocketInfo,
+    _file_count: FileCount,
+    ip_protocol: IpProtocol,
+    /// Whether the packets to send include the IP header, i.e., `IP_HDRINCL`.
+    hdr_incl: AtomicBool,
+    nonblocking: AtomicBool,
+    pollee: Pollee,
+}
+
+enum Inner {
+    Unbound(Box<AnyUnboundSocket>),
+    Bound(BoundRaw),
+}
+
+impl Inner {
+    fn bind(
+        self,
+        addr: &Ipv4Address,
+        protocol: IpProtocol,
+    ) -> core::result::Result<BoundRaw, (Error, Self)> {
+        let unbound_socket = match self {
+            Inner::Unbound(unbound_socket) => unbound_socket,
+            Inner::Bound(bound_raw) => {
+                return Err((
+                    Error::with_message(Errno::EINVAL, "the socket is already bound to an address"),
+                    Inner::Bound(bound_raw),
+                ));
+            }
+        };
+
+        // Raw sockets have no ports.
+        let endpoint = IpEndpoint::new(IpAddress::Ipv4(*addr), 0);
+        let bound_socket = match bind_socket(unbound_socket, &endpoint, false, None) {
+            Ok(bound_socket) => bound_socket,
+            Err((err, unbound_socket)) => return Err((err, Inner::Unbound(unbound_socket))),
+        };
+        Ok(BoundRaw::new(bound_socket, protocol))
+    }
+
+    fn bind_to_ephemeral_endpoint(
+        self,
+        remote_addr: &Ipv4Address,
+        protocol: IpProtocol,
+    ) -> core::result::Result<BoundRaw, (Error, Self)> {
+        if let Inner::Bound(bound_raw) = self {
+            return Ok(bound_raw);
+        }
+
+        let remote_endpoint = IpEndpoint::new(IpAddress::Ipv4(*remote_addr), 0);
+        let IpAddress::Ipv4(local_addr) = get_ephemeral_endpoint(&remote_endpoint, None).addr;
+        self.bind(&local_addr, protocol)
+    }
+}
+
+impl RawSocket {
+    pub fn new(info: SocketInfo, nonblocking: bool) -> Result<Arc<Self>> {
+        if !credentials().capable(CapSet::NET_RAW) {
+            return_errno_with_message!(
+                Errno::EPERM,
+                "CAP_NET_RAW is required to create raw sockets"
+            );
+        }
+        let ip_protocol = match info.protocol() {
+            Protocol::IPPROTO_ICMP => IpProtocol::Icmp,
+            _ => return_errno_with_message!(
+                Errno::EPROTONOSUPPORT,
+                "only ICMP raw sockets are supported"
+            ),
+        };
+
+        Ok(Arc::new_cyclic(|me| {
+            let unbound_socket = Box::new(AnyUnboundSocket::new_raw(ip_protocol, me.clone() as _));
+            let pollee = Pollee::new(IoEvents::OUT);
+            Self {
+                inner: RwLock::new(Takeable::new(Inner::Unbound(unbound_socket))),
+                info,
+                _file_count: FileCount::new(),
+                ip_protocol,
+                hdr_incl: AtomicBool::new(false),
+                nonblocking: AtomicBool::new(nonblocking),
+                pollee,
+            }
+        }))
+    }
+
+    pub fn is_nonblocking(&self) -> bool {
+        self.nonblocking.load(Ordering::SeqCst)
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) {
+        self.nonblocking.store(nonblocking, Ordering::SeqCst);
+    }
+
+    fn remote_addr(&self) -> Option<Ipv4Address> {
+        let inner = self.inner.read();
+
+        match inner.as_ref() {
+            Inner::Bound(bound_raw) => bound_raw.remote_addr(),
+            Inner::Unbound(_) => None,
+        }
+    }
+
+    fn try_bind_ephemeral(&self, remote_addr: &Ipv4Address) -> Result<()> {
+        // Fast path
+        if let Inner::Bound(_) = self.inner.read().as_ref() {
+            return Ok(());
+        }
+
+        // Slow path
+        let mut inner = self.inner.write();
+        inner.borrow_result(|owned_inner| {
+            let bound_raw =
+                match owned_inner.bind_to_ephemeral_endpoint(remote_addr, self.ip_protocol) {
+                    Ok(bound_raw) => bound_raw,
+                    Err((err, err_inner)) => {
+                        return (err_inner, Err(err));
+                    }
+                };
+            bound_raw.init_pollee(&self.pollee);
+            (Inner::Bound(bound_raw), Ok(()))
+        })
+    }
+
+    fn try_recv(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        let inner = self.inner.read();
+
+        let Inner::Bound(bound_raw) = inner.as_ref() else {
+            return_errno_with_message!(Errno::EAGAIN, "the socket is not bound");
+        };
+
+        let received = bound_raw.try_recv(buf).map(|(recv_bytes, src_addr)| {
+            bound_raw.update_io_events(&self.pollee);
+            (recv_bytes, SocketAddr::IPv4(src_addr, 0))
+        });
+
+        drop(inner);
+        poll_ifaces();
+
+        received
+    }
+
+    fn recv(&self, buf: &mut [u8]) -> Result<(usize, SocketAddr)> {
+        // Like Linux, the socket can receive packets without being bound or sending any
+        // packet. Since the packets are only delivered to the socket once it is bound to an
+        // iface, an unbound socket is bound to the default iface here.
+        self.try_bind_ephemeral(&Ipv4Address::UNSPECIFIED)?;
+
+        if self.is_nonblocking() {
+            self.try_recv(buf)
+        } else {
+            self.wait_events(IoEvents::IN, || self.try_recv(buf))
+        }
+    }
+
+    fn try_send(&self, buf: &[u8], remote: &Ipv4Address) -> Result<usize> {
+        let inner = self.inner.read();
+
+        let Inner::Bound(bound_raw) = inner.as_ref() else {
+            return_errno_with_message!(Errno::EAGAIN, "the socket is not bound")
+        };
+
+        let hdr_incl = self.hdr_incl.load(Ordering::Relaxed);
+        let sent_bytes = bound_raw.try_send(buf, remote, hdr_incl).map(|sent_bytes| {
+            bound_raw.update_io_events(&self.pollee);
+            sent_bytes
+        });
+
+        drop(inner);
+        poll_ifaces();
+
+        sent_bytes
+    }
+
+    // TODO: Support timeout
+    fn wait_events<F, R>(&self, mask: IoEvents, mut cond: F) -> Result<R>
+    where
+        F: FnMut() -> Result<R>,
+    {
+        let poller = Poller::new();
+
+        loop {
+            match cond() {
+                Err(err) if err.error() == Errno::EAGAIN => (),
+                result => return result,
+            };
+
+            let events = self.poll(mask, Some(&poller));
+            if !events.is_empty() {
+                continue;
+            }
+
+            poller.wait()?;
+        }
+    }
+
+    fn update_io_events(&self) {
+        let inner = self.inner.read();
+        let Inner::Bound(bound_raw) = inner.as_ref() else {
+            return;
+        };
+        bound_raw.update_io_events(&self.pollee);
+    }
+}
+
+impl FileLike for RawSocket {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.recv(buf).map(|(len, _)| len.min(buf.len()))
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        let remote = self.remote_addr().ok_or_else(|| {
+            Error::with_message(
+                Errno::EDESTADDRREQ,
+                "the destination address is not specified",
+            )
+        })?;
+
+        // TODO: Block if send buffer is full
+        self.try_send(buf, &remote)
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        self.pollee.poll(mask, poller)
+    }
+
+    fn as_socket(self: Arc<Self>) -> Option<Arc<dyn Socket>> {
+        Some(self)
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        if self.is_nonblocking() {
+            StatusFlags::O_NONBLOCK
+        } else {
+            StatusFlags::empty()
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        if new_flags.contains(StatusFlags::O_NONBLOCK) {
+            self.set_nonblocking(true);
+        } else {
+            self.set_nonblocking(false);
+        }
+        Ok(())
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_observer(observer, mask);
+        Ok(())
+    }
+
+    fn register_exclusive_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_exclusive_observer(observer, mask);
+        Ok(())
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        self.pollee.unregister_observer(observer)
+    }
+}
+
+impl Socket for RawSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
+    fn bind(&self, socket_addr: SocketAddr) -> Result<()> {
+        let IpEndpoint {
+            addr: IpAddress::Ipv4(addr),
+            ..
+        } = socket_addr.try_into()?;
+
+        let mut inner = self.inner.write();
+        inner.borrow_result(|owned_inner| {
+            let bound_raw = match owned_inner.bind(&addr, self.ip_protocol) {
+                Ok(bound_raw) => bound_raw,
+                Err((err, err_inner)) => {
+                    return (err_inner, Err(err));
+                }
+            };
+            bound_raw.init_pollee(&self.pollee);
+            (Inner::Bound(bound_raw), Ok(()))
+        })
+    }
+
+    fn connect(&self, socket_addr: SocketAddr) -> Result<()> {
+        let IpEndpoint {
+            addr: IpAddress::Ipv4(addr),
+            ..
+        } = socket_addr.try_into()?;
+
+        self.try_bind_ephemeral(&addr)?;
+
+        let mut inner = self.inner.write();
+        let Inner::Bound(bound_raw) = inner.as_mut() else {
+            return_errno_with_message!(Errno::EINVAL, "the socket is not bound")
+        };
+        bound_raw.set_remote_addr(addr);
+
+        Ok(())
+    }
+
+    fn addr(&self) -> Result<SocketAddr> {
+        let inner = self.inner.read();
+        match inner.as_ref() {
+            Inner::Unbound(_) => Ok(UNSPECIFIED_LOCAL_ENDPOINT.into()),
+            Inner::Bound(bound_raw) => Ok(SocketAddr::IPv4(bound_raw.local_addr(), 0)),
+        }
+    }
+
+    fn peer_addr(&self) -> Result<SocketAddr> {
+        self.remote_addr()
+            .map(|addr| SocketAddr::IPv4(addr, 0))
+            .ok_or_else(|| Error::with_message(Errno::ENOTCONN, "the socket is not connected"))
+    }
+
+    fn get_option(&self, option: &mut dyn SocketOption) -> Result<()> {
+        match_sock_option_mut!(option, {
+            ip_hdr_incl: HdrIncl => {
+                let hdr_incl = self.hdr_incl.load(Ordering::Relaxed);
+                ip_hdr_incl.set(hdr_incl);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to get is unknown")
+        });
+
+        Ok(())
+    }
+
+    fn set_option(&self, option: &dyn SocketOption) -> Result<()> {
+        match_sock_option_ref!(option, {
+            ip_hdr_incl: HdrIncl => {
+                let hdr_incl = ip_hdr_incl.get().unwrap();
+                self.hdr_incl.store(*hdr_incl, Ordering::Relaxed);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to be set is unknown")
+        });
+
+        Ok(())
+    }
+
+    fn sendmsg(
+        &self,
+        io_vecs: &[IoVec],
+        message_header: MessageHeader,
+        flags: SendRecvFlags,
+    ) -> Result<usize> {
+        // TODO: Deal with flags
+        debug_assert!(flags.is_all_supported());
+
+        let MessageHeader {
+            addr,
+            control_message,
+            ..
+        } = message_header;
+
+        let remote_addr = match addr {
+            Some(remote_addr) => {
+                let IpEndpoint {
+                    addr: IpAddress::Ipv4(addr),
+                    ..
+                } = remote_addr.try_into()?;
+                self.try_bind_ephemeral(&addr)?;
+                addr
+            }
+            None => self.remote_addr().ok_or_else(|| {
+                Error::with_message(
+                    Errno::EDESTADDRREQ,
+                    "the destination address is not specified",
+                )
+            })?,
+        };
+
+        if control_message.is_some() {
+            // TODO: Support sending control message
+            warn!("sending control message is not supported");
+        }
+
+        let buf = copy_message_from_user(io_vecs);
+
+        // TODO: Block if the send buffer is full
+        self.try_send(&buf, &remote_addr)
+    }
+
+    fn recvmsg(&self, io_vecs: &[IoVec], flags: SendRecvFlags) -> Result<(usize, MessageHeader)> {
+        // TODO: Deal with other flags
+        debug_assert!((flags - SendRecvFlags::MSG_TRUNC).is_all_supported());
+
+        let mut buf = create_message_buffer(io_vecs);
+
+        let (received_bytes, peer_addr) = self.recv(&mut buf)?;
+
+        let copied_bytes = {
+            let message = &buf[..received_bytes.min(buf.len())];
+            copy_message_to_user(io_vecs, message)
+        };
+
+        // TODO: Receive control message
+
+        let message_header = MessageHeader::new(Some(peer_addr), None)
+            .with_flags(truncated_flags(received_bytes, copied_bytes));
+
+        Ok((
+            recv_return_len(received_bytes, copied_bytes, flags),
+            message_header,
+        ))
+    }
+}
+
+impl Observer<()> for RawSocket {
+    fn on_events(&self, _events: &()) {
+        self.update_io_events();
+    }
+}
@@ -121,6 +121,11 @@ impl ConnectedStream {
         });
     }
 
+    pub(super) fn set_nagle_enabled(&self, enabled: bool) {
+        self.bound_socket
+            .raw_with(|socket: &mut RawTcpSocket| socket.set_nagle_enabled(enabled));
+    }
+
     pub(super) fn set_observer(&self, observer: Weak<dyn Observer<()>>) {
         self.bound_socket.set_observer(observer)
     }
@@ -66,6 +66,11 @@ impl ConnectingStream {
         self.remote_endpoint
     }
 
+    pub(super) fn set_nagle_enabled(&self, enabled: bool) {
+        self.bound_socket
+            .raw_with(|socket: &mut RawTcpSocket| socket.set_nagle_enabled(enabled));
+    }
+
     pub(super) fn init_pollee(&self, pollee: &Pollee) {
         pollee.reset_events();
     }
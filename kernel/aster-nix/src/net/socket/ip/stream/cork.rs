@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use alloc::sync::Weak;
+use core::time::Duration;
+
+use super::{connected::ConnectedStream, StreamSocket};
+use crate::{
+    net::socket::util::{send_recv_flags::SendRecvFlags, shutdown_cmd::SockShutdownCmd},
+    prelude::*,
+    thread::work_queue::{submit_work_item, work_item::WorkItem, WorkPriority},
+    time::{clocks::MonotonicClock, timer::Timeout, Timer},
+};
+
+/// The maximum time that the data can be held back by `TCP_CORK`.
+const CORK_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// The data held back by `TCP_CORK`.
+///
+/// While a socket is corked, only full segments are sent. The rest of the data is held until
+/// the socket is uncorked, shut down or closed, or `CORK_TIMEOUT` elapses.
+pub(super) struct CorkBuffer {
+    inner: Mutex<Inner>,
+    /// The timer to flush the buffer when `CORK_TIMEOUT` elapses.
+    ///
+    /// The timer is armed if and only if the buffer is not empty.
+    timer: Arc<Timer>,
+}
+
+struct Inner {
+    buf: Vec<u8>,
+    /// The shutdown that is deferred until all the buffered data is sent.
+    pending_shutdown: Option<SockShutdownCmd>,
+}
+
+impl CorkBuffer {
+    pub(super) fn new(socket: Weak<StreamSocket>) -> Self {
+        // The timer callback runs in the interrupt context, so the flush is deferred.
+        let work_item = Arc::new(WorkItem::new(Box::new(move || {
+            if let Some(socket) = socket.upgrade() {
+                socket.flush_cork();
+            }
+        })));
+        let timer = MonotonicClock::timer_manager().create_timer(move || {
+            submit_work_item(work_item.clone(), WorkPriority::High);
+        });
+
+        Self {
+            inner: Mutex::new(Inner {
+                buf: Vec::new(),
+                pending_shutdown: None,
+            }),
+            timer,
+        }
+    }
+
+    /// Buffers the data in `buf` and sends the full segments.
+    pub(super) fn try_send(
+        &self,
+        connected_stream: &ConnectedStream,
+        buf: &[u8],
+        maxseg: usize,
+    ) -> Result<usize> {
+        let mut inner = self.inner.lock();
+        check_not_shut_down(&inner)?;
+
+        let cork_buf = &mut inner.buf;
+        let was_empty = cork_buf.is_empty();
+
+        let mut copied_len = 0;
+        loop {
+            if cork_buf.len() >= maxseg {
+                let full_len = cork_buf.len() / maxseg * maxseg;
+                match send_buffered(connected_stream, cork_buf, full_len) {
+                    Ok(()) => (),
+                    Err(err) if copied_len == 0 => return Err(err),
+                    Err(_) => break,
+                }
+                if cork_buf.len() >= maxseg {
+                    break;
+                }
+            }
+            if copied_len == buf.len() {
+                break;
+            }
+
+            let len = (maxseg - cork_buf.len()).min(buf.len() - copied_len);
+            cork_buf.extend_from_slice(&buf[copied_len..copied_len + len]);
+            copied_len += len;
+        }
+
+        if cork_buf.is_empty() {
+            self.timer.cancel();
+        } else if was_empty {
+            self.timer.set_timeout(Timeout::After(CORK_TIMEOUT));
+        }
+
+        if copied_len == 0 {
+            return_errno_with_message!(Errno::EAGAIN, "the send buffer is full");
+        }
+        Ok(copied_len)
+    }
+
+    /// Sends all the buffered data.
+    ///
+    /// If the data cannot be sent completely, `EAGAIN` is returned and the rest of the data will
+    /// be sent when the timer expires again. Once all the data is sent, the deferred shutdown,
+    /// if any, is performed.
+    pub(super) fn flush(&self, connected_stream: &ConnectedStream) -> Result<()> {
+        let mut inner = self.inner.lock();
+        self.flush_locked(&mut inner, connected_stream)
+    }
+
+    /// Sends all the buffered data before the data in `buf`, which is not buffered.
+    pub(super) fn flush_and_send(
+        &self,
+        connected_stream: &ConnectedStream,
+        buf: &[u8],
+        flags: SendRecvFlags,
+    ) -> Result<usize> {
+        let mut inner = self.inner.lock();
+        check_not_shut_down(&inner)?;
+        self.flush_locked(&mut inner, connected_stream)?;
+        connected_stream.try_send(buf, flags)
+    }
+
+    /// Shuts down the connection after all the buffered data is sent.
+    ///
+    /// If the data cannot be sent at once, the shutdown is deferred until the rest of the data
+    /// is sent, so the data is never discarded by the shutdown.
+    pub(super) fn shutdown(
+        &self,
+        connected_stream: &ConnectedStream,
+        cmd: SockShutdownCmd,
+    ) -> Result<()> {
+        let mut inner = self.inner.lock();
+        if inner.buf.is_empty() || !cmd.shut_write() {
+            return connected_stream.shutdown(cmd);
+        }
+
+        inner.pending_shutdown = Some(cmd);
+        match self.flush_locked(&mut inner, connected_stream) {
+            Err(err) if err.error() == Errno::EAGAIN => Ok(()),
+            result => result,
+        }
+    }
+
+    fn flush_locked(&self, inner: &mut Inner, connected_stream: &ConnectedStream) -> Result<()> {
+        if inner.buf.is_empty() {
+            return Ok(());
+        }
+
+        let len = inner.buf.len();
+        if let Err(err) = send_buffered(connected_stream, &mut inner.buf, len) {
+            // The data can never be sent if the connection is broken.
+            inner.buf.clear();
+            inner.pending_shutdown = None;
+            self.timer.cancel();
+            return Err(err);
+        }
+
+        if !inner.buf.is_empty() {
+            self.timer.set_timeout(Timeout::After(CORK_TIMEOUT));
+            return_errno_with_message!(Errno::EAGAIN, "the send buffer is full");
+        }
+
+        self.timer.cancel();
+        if let Some(cmd) = inner.pending_shutdown.take() {
+            connected_stream.shutdown(cmd)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that no data can be sent after the connection is shut down for writing.
+fn check_not_shut_down(inner: &Inner) -> Result<()> {
+    if inner.pending_shutdown.is_some() {
+        return_errno_with_message!(Errno::EPIPE, "the socket is shut down for writing");
+    }
+    Ok(())
+}
+
+/// Sends the first `len` bytes of `cork_buf` and removes the bytes that are sent.
+///
+/// It is not an error if the send buffer of the connection is full.
+fn send_buffered(
+    connected_stream: &ConnectedStream,
+    cork_buf: &mut Vec<u8>,
+    len: usize,
+) -> Result<()> {
+    match connected_stream.try_send(&cork_buf[..len], SendRecvFlags::empty()) {
+        Ok(sent_len) => {
+            cork_buf.drain(..sent_len);
+            Ok(())
+        }
+        Err(err) if err.error() == Errno::EAGAIN => Ok(()),
+        Err(err) => Err(err),
+    }
+}
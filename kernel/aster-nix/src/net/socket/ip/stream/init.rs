@@ -30,6 +30,8 @@ impl InitStream {
     pub fn bind(
         self,
         endpoint: &IpEndpoint,
+        can_reuse: bool,
+        bound_device: Option<&str>,
     ) -> core::result::Result<Arc<AnyBoundSocket>, (Error, Self)> {
         let unbound_socket = match self {
             InitStream::Unbound(unbound_socket) => unbound_socket,
@@ -40,7 +42,7 @@ impl InitStream {
                 ));
             }
         };
-        let bound_socket = match bind_socket(unbound_socket, endpoint, false) {
+        let bound_socket = match bind_socket(unbound_socket, endpoint, can_reuse, bound_device) {
             Ok(bound_socket) => bound_socket,
             Err((err, unbound_socket)) => return Err((err, InitStream::Unbound(unbound_socket))),
         };
@@ -50,18 +52,22 @@ impl InitStream {
     fn bind_to_ephemeral_endpoint(
         self,
         remote_endpoint: &IpEndpoint,
+        bound_device: Option<&str>,
     ) -> core::result::Result<Arc<AnyBoundSocket>, (Error, Self)> {
-        let endpoint = get_ephemeral_endpoint(remote_endpoint);
-        self.bind(&endpoint)
+        let endpoint = get_ephemeral_endpoint(remote_endpoint, bound_device);
+        self.bind(&endpoint, false, bound_device)
     }
 
     pub fn connect(
         self,
         remote_endpoint: &IpEndpoint,
+        bound_device: Option<&str>,
     ) -> core::result::Result<ConnectingStream, (Error, Self)> {
         let bound_socket = match self {
             InitStream::Bound(bound_socket) => bound_socket,
-            InitStream::Unbound(_) => self.bind_to_ephemeral_endpoint(remote_endpoint)?,
+            InitStream::Unbound(_) => {
+                self.bind_to_ephemeral_endpoint(remote_endpoint, bound_device)?
+            }
         };
 
         ConnectingStream::new(bound_socket, *remote_endpoint)
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use smoltcp::socket::tcp::ListenError;
 
 use super::connected::ConnectedStream;
@@ -16,6 +18,8 @@ pub struct ListenStream {
     bound_socket: Arc<AnyBoundSocket>,
     /// Backlog sockets listening at the local endpoint
     backlog_sockets: RwLock<Vec<BacklogSocket>>,
+    /// The number of active backlog sockets when the I/O events were last updated
+    nr_active: AtomicUsize,
 }
 
 impl ListenStream {
@@ -27,6 +31,7 @@ impl ListenStream {
             backlog,
             bound_socket,
             backlog_sockets: RwLock::new(Vec::new()),
+            nr_active: AtomicUsize::new(0),
         };
         if let Err(err) = listen_stream.fill_backlog_sockets() {
             return Err((err, listen_stream.bound_socket));
@@ -89,11 +94,18 @@ impl ListenStream {
         // The lock should be held to avoid data races
         let backlog_sockets = self.backlog_sockets.read();
 
-        let can_accept = backlog_sockets.iter().any(|socket| socket.is_active());
-        if can_accept {
-            pollee.add_events(IoEvents::IN);
-        } else {
+        let nr_active = backlog_sockets
+            .iter()
+            .filter(|socket| socket.is_active())
+            .count();
+        let old_nr_active = self.nr_active.swap(nr_active, Ordering::Relaxed);
+
+        // Adding the events wakes up an exclusive waiter, so it is done only when there are new
+        // connections instead of on every iface event.
+        if nr_active == 0 {
             pollee.del_events(IoEvents::IN);
+        } else if nr_active > old_nr_active {
+            pollee.add_events(IoEvents::IN);
         }
     }
 }
@@ -1,36 +1,48 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use core::sync::atomic::{AtomicBool, Ordering};
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
+use aster_time::read_monotonic_time;
 use connected::ConnectedStream;
 use connecting::ConnectingStream;
+use cork::CorkBuffer;
 use init::InitStream;
 use listen::ListenStream;
-use options::{Congestion, MaxSegment, NoDelay, WindowClamp};
+use options::{
+    Congestion, Cork, DeferAccept, KeepCount, KeepIdle, KeepInterval, MaxSegment, NoDelay,
+    WindowClamp,
+};
 use smoltcp::wire::IpEndpoint;
 use takeable::Takeable;
-use util::{TcpOptionSet, DEFAULT_MAXSEG};
+use util::{Retrans, TcpOptionSet, DEFAULT_MAXSEG};
 
-use super::UNSPECIFIED_LOCAL_ENDPOINT;
+use super::{common::check_bind_to_device, UNSPECIFIED_LOCAL_ENDPOINT};
 use crate::{
     events::{IoEvents, Observer},
-    fs::{file_handle::FileLike, utils::StatusFlags},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        utils::StatusFlags,
+    },
     match_sock_option_mut, match_sock_option_ref,
     net::{
         poll_ifaces,
         socket::{
             options::{
-                Error as SocketError, Linger, RecvBuf, ReuseAddr, ReusePort, SendBuf, SocketOption,
+                BindToDevice, Error as SocketError, Linger, Priority, RecvBuf, RecvTimeout,
+                ReuseAddr, ReusePort, SendBuf, SendTimeout, SocketOption,
             },
             util::{
                 copy_message_from_user, copy_message_to_user, create_message_buffer,
-                options::{SocketOptionSet, MIN_RECVBUF, MIN_SENDBUF},
+                options::{check_priority, SocketOptionSet, MIN_RECVBUF, MIN_SENDBUF},
                 send_recv_flags::SendRecvFlags,
                 shutdown_cmd::SockShutdownCmd,
                 socket_addr::SocketAddr,
                 MessageHeader,
             },
-            Socket,
+            IfName, Socket, SocketInfo,
         },
     },
     prelude::*,
@@ -40,6 +52,7 @@ use crate::{
 
 mod connected;
 mod connecting;
+mod cork;
 mod init;
 mod listen;
 pub mod options;
@@ -53,6 +66,9 @@ pub struct StreamSocket {
     state: RwLock<Takeable<State>>,
     is_nonblocking: AtomicBool,
     pollee: Pollee,
+    cork_buffer: CorkBuffer,
+    info: SocketInfo,
+    _file_count: FileCount,
 }
 
 enum State {
@@ -81,7 +97,7 @@ impl OptionSet {
 }
 
 impl StreamSocket {
-    pub fn new(nonblocking: bool) -> Arc<Self> {
+    pub fn new(info: SocketInfo, nonblocking: bool) -> Arc<Self> {
         Arc::new_cyclic(|me| {
             let init_stream = InitStream::new(me.clone() as _);
             let pollee = Pollee::new(IoEvents::empty());
@@ -91,20 +107,31 @@ impl StreamSocket {
                 state: RwLock::new(Takeable::new(State::Init(init_stream))),
                 is_nonblocking: AtomicBool::new(nonblocking),
                 pollee,
+                cork_buffer: CorkBuffer::new(me.clone()),
+                info,
+                _file_count: FileCount::new(),
             }
         })
     }
 
-    fn new_connected(connected_stream: ConnectedStream) -> Arc<Self> {
+    fn new_connected(
+        connected_stream: ConnectedStream,
+        options: OptionSet,
+        info: SocketInfo,
+    ) -> Arc<Self> {
         Arc::new_cyclic(move |me| {
             let pollee = Pollee::new(IoEvents::empty());
             connected_stream.set_observer(me.clone() as _);
+            connected_stream.set_nagle_enabled(!options.tcp.no_delay());
             connected_stream.init_pollee(&pollee);
             Self {
-                options: RwLock::new(OptionSet::new()),
+                options: RwLock::new(options),
                 state: RwLock::new(Takeable::new(State::Connected(connected_stream))),
                 is_nonblocking: AtomicBool::new(false),
                 pollee,
+                cork_buffer: CorkBuffer::new(me.clone()),
+                info,
+                _file_count: FileCount::new(),
             }
         })
     }
@@ -121,6 +148,10 @@ impl StreamSocket {
     // `Some(_)` if blocking is not necessary or not allowed.
     fn start_connect(&self, remote_endpoint: &IpEndpoint) -> Option<Result<()>> {
         let is_nonblocking = self.is_nonblocking();
+        let (no_delay, bound_device) = {
+            let options = self.options.read();
+            (options.tcp.no_delay(), options.socket.bound_device())
+        };
         let mut state = self.state.write();
 
         let result_or_block = state.borrow_result(|mut owned_state| {
@@ -153,12 +184,14 @@ impl StreamSocket {
                 }
             };
 
-            let connecting_stream = match init_stream.connect(remote_endpoint) {
+            let connecting_stream = match init_stream.connect(remote_endpoint, bound_device) {
                 Ok(connecting_stream) => connecting_stream,
                 Err((err, init_stream)) => {
                     return (State::Init(init_stream), Some(Err(err)));
                 }
             };
+            // Connecting resets the underlying socket, so the options must be applied afterwards.
+            connecting_stream.set_nagle_enabled(!no_delay);
             connecting_stream.init_pollee(&self.pollee);
 
             (
@@ -230,6 +263,12 @@ impl StreamSocket {
     }
 
     fn try_accept(&self) -> Result<(Arc<dyn FileLike>, SocketAddr)> {
+        // Like Linux, the accepted socket inherits the options of the listening socket.
+        let options = {
+            let mut options = self.options.read().clone();
+            options.socket.set_sock_errors(None);
+            options
+        };
         let state = self.state.read();
 
         let State::Listen(listen_stream) = state.as_ref() else {
@@ -240,7 +279,7 @@ impl StreamSocket {
             listen_stream.update_io_events(&self.pollee);
 
             let remote_endpoint = connected_stream.remote_endpoint();
-            let accepted_socket = Self::new_connected(connected_stream);
+            let accepted_socket = Self::new_connected(connected_stream, options, self.info);
             (accepted_socket as _, remote_endpoint.into())
         });
 
@@ -280,11 +319,18 @@ impl StreamSocket {
         if self.is_nonblocking() {
             self.try_recv(buf, flags)
         } else {
-            self.wait_events(IoEvents::IN, || self.try_recv(buf, flags))
+            self.wait_events(IoEvents::IN, self.recv_timeout(), || {
+                self.try_recv(buf, flags)
+            })
         }
     }
 
     fn try_send(&self, buf: &[u8], flags: SendRecvFlags) -> Result<usize> {
+        let (is_corked, maxseg) = {
+            let options = self.options.read();
+            (options.tcp.cork(), options.tcp.maxseg() as usize)
+        };
+
         let state = self.state.read();
 
         let connected_stream = match state.as_ref() {
@@ -300,10 +346,16 @@ impl StreamSocket {
             }
         };
 
-        let sent_bytes = connected_stream.try_send(buf, flags).map(|sent_bytes| {
-            connected_stream.update_io_events(&self.pollee);
-            sent_bytes
-        });
+        let sent_bytes = if is_corked {
+            self.cork_buffer.try_send(connected_stream, buf, maxseg)
+        } else {
+            // The data held back by the cork must be sent first.
+            self.cork_buffer
+                .flush_and_send(connected_stream, buf, flags)
+        };
+        // The events are updated even if no data is sent, so that a blocking sender waits
+        // until the send buffer has space instead of retrying at once.
+        connected_stream.update_io_events(&self.pollee);
 
         drop(state);
         poll_ifaces();
@@ -315,16 +367,30 @@ impl StreamSocket {
         if self.is_nonblocking() {
             self.try_send(buf, flags)
         } else {
-            self.wait_events(IoEvents::OUT, || self.try_send(buf, flags))
+            self.wait_events(IoEvents::OUT, self.send_timeout(), || {
+                self.try_send(buf, flags)
+            })
         }
     }
 
-    // TODO: Support timeout
-    fn wait_events<F, R>(&self, mask: IoEvents, mut cond: F) -> Result<R>
+    fn send_timeout(&self) -> Duration {
+        self.options.read().socket.send_timeout()
+    }
+
+    fn recv_timeout(&self) -> Duration {
+        self.options.read().socket.recv_timeout()
+    }
+
+    /// Waits until `cond` no longer fails with `EAGAIN`.
+    ///
+    /// A zero `timeout` means waiting forever. Otherwise, `EAGAIN` is returned once the timeout
+    /// expires.
+    fn wait_events<F, R>(&self, mask: IoEvents, timeout: Duration, mut cond: F) -> Result<R>
     where
         F: FnMut() -> Result<R>,
     {
         let poller = Poller::new();
+        let deadline = (!timeout.is_zero()).then(|| read_monotonic_time() + timeout);
 
         loop {
             match cond() {
@@ -337,7 +403,43 @@ impl StreamSocket {
                 continue;
             }
 
-            poller.wait()?;
+            let Some(deadline) = deadline else {
+                poller.wait()?;
+                continue;
+            };
+
+            let remaining = deadline.saturating_sub(read_monotonic_time());
+            if remaining.is_zero() {
+                return_errno_with_message!(Errno::EAGAIN, "the timeout expires");
+            }
+            match poller.wait_timeout(&remaining) {
+                Err(err) if err.error() == Errno::ETIME => (),
+                result => result?,
+            }
+        }
+    }
+
+    /// Sends the data held back by the cork.
+    fn flush_cork(&self) {
+        let state = self.state.read();
+        let State::Connected(connected_stream) = state.as_ref() else {
+            return;
+        };
+
+        // If not all the data can be sent, the rest will be sent when the timer expires again.
+        let _ = self.cork_buffer.flush(connected_stream);
+        connected_stream.update_io_events(&self.pollee);
+
+        drop(state);
+        poll_ifaces();
+    }
+
+    fn set_nagle_enabled(&self, enabled: bool) {
+        let state = self.state.read();
+        match state.as_ref() {
+            State::Connecting(connecting_stream) => connecting_stream.set_nagle_enabled(enabled),
+            State::Connected(connected_stream) => connected_stream.set_nagle_enabled(enabled),
+            State::Init(_) | State::Listen(_) => (),
         }
     }
 
@@ -376,6 +478,12 @@ impl FileLike for StreamSocket {
         self.pollee.poll(mask, poller)
     }
 
+    fn clean_for_close(&self) -> Result<()> {
+        // The data held back by the cork is not discarded when the socket is closed.
+        self.flush_cork();
+        Ok(())
+    }
+
     fn status_flags(&self) -> StatusFlags {
         if self.is_nonblocking() {
             StatusFlags::O_NONBLOCK
@@ -406,6 +514,15 @@ impl FileLike for StreamSocket {
         Ok(())
     }
 
+    fn register_exclusive_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_exclusive_observer(observer, mask);
+        Ok(())
+    }
+
     fn unregister_observer(
         &self,
         observer: &Weak<dyn Observer<IoEvents>>,
@@ -415,9 +532,19 @@ impl FileLike for StreamSocket {
 }
 
 impl Socket for StreamSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
     fn bind(&self, socket_addr: SocketAddr) -> Result<()> {
         let endpoint = socket_addr.try_into()?;
 
+        // FIXME: Linux does not allow reusing the port of a listening socket even if
+        // `SO_REUSEADDR` is set.
+        let (can_reuse, bound_device) = {
+            let options = self.options.read();
+            (options.socket.reuse_addr(), options.socket.bound_device())
+        };
         let mut state = self.state.write();
 
         state.borrow_result(|owned_state| {
@@ -431,7 +558,7 @@ impl Socket for StreamSocket {
                 );
             };
 
-            let bound_socket = match init_stream.bind(&endpoint) {
+            let bound_socket = match init_stream.bind(&endpoint, can_reuse, bound_device) {
                 Ok(bound_socket) => bound_socket,
                 Err((err, init_stream)) => {
                     return (State::Init(init_stream), Err(err));
@@ -449,7 +576,16 @@ impl Socket for StreamSocket {
             return result;
         }
 
-        self.wait_events(IoEvents::OUT, || self.check_connect())
+        // Like Linux, the connection is still in progress if it is not established before the
+        // timeout expires.
+        self.wait_events(IoEvents::OUT, self.send_timeout(), || self.check_connect())
+            .map_err(|err| {
+                if err.error() == Errno::EAGAIN {
+                    Error::with_message(Errno::EINPROGRESS, "the socket is connecting")
+                } else {
+                    err
+                }
+            })
     }
 
     fn listen(&self, backlog: usize) -> Result<()> {
@@ -488,14 +624,15 @@ impl Socket for StreamSocket {
         if self.is_nonblocking() {
             self.try_accept()
         } else {
-            self.wait_events(IoEvents::IN, || self.try_accept())
+            self.wait_events(IoEvents::IN, self.recv_timeout(), || self.try_accept())
         }
     }
 
     fn shutdown(&self, cmd: SockShutdownCmd) -> Result<()> {
         let state = self.state.read();
         match state.as_ref() {
-            State::Connected(connected_stream) => connected_stream.shutdown(cmd),
+            // The data held back by the cork is sent before the connection is closed.
+            State::Connected(connected_stream) => self.cork_buffer.shutdown(connected_stream, cmd),
             // TDOD: shutdown listening stream
             _ => return_errno_with_message!(Errno::EINVAL, "cannot shutdown"),
         }
@@ -610,6 +747,22 @@ impl Socket for StreamSocket {
                 let reuse_port = options.socket.reuse_port();
                 socket_reuse_port.set(reuse_port);
             },
+            socket_send_timeout: SendTimeout => {
+                let send_timeout = options.socket.send_timeout();
+                socket_send_timeout.set(send_timeout);
+            },
+            socket_recv_timeout: RecvTimeout => {
+                let recv_timeout = options.socket.recv_timeout();
+                socket_recv_timeout.set(recv_timeout);
+            },
+            socket_bind_to_device: BindToDevice => {
+                let bound_device = options.socket.bound_device().unwrap_or_default();
+                socket_bind_to_device.set(IfName::new(bound_device));
+            },
+            socket_priority: Priority => {
+                let priority = options.socket.priority();
+                socket_priority.set(priority);
+            },
             // TCP options:
             tcp_no_delay: NoDelay => {
                 let no_delay = options.tcp.no_delay();
@@ -634,6 +787,27 @@ impl Socket for StreamSocket {
                 let window_clamp = options.tcp.window_clamp();
                 tcp_window_clamp.set(window_clamp);
             },
+            tcp_defer_accept: DeferAccept => {
+                // The value read back is the timeout rounded to the retransmission periods.
+                let defer_accept = options.tcp.defer_accept().to_secs();
+                tcp_defer_accept.set(defer_accept);
+            },
+            tcp_cork: Cork => {
+                let cork = options.tcp.cork();
+                tcp_cork.set(cork);
+            },
+            tcp_keep_idle: KeepIdle => {
+                let keep_idle = options.tcp.keep_idle();
+                tcp_keep_idle.set(keep_idle);
+            },
+            tcp_keep_interval: KeepInterval => {
+                let keep_intvl = options.tcp.keep_intvl();
+                tcp_keep_interval.set(keep_intvl);
+            },
+            tcp_keep_count: KeepCount => {
+                let keep_cnt = options.tcp.keep_cnt();
+                tcp_keep_count.set(keep_cnt);
+            },
             _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to get is unknown")
         });
 
@@ -642,6 +816,7 @@ impl Socket for StreamSocket {
 
     fn set_option(&self, option: &dyn SocketOption) -> Result<()> {
         let mut options = self.options.write();
+        let mut is_uncorked = false;
 
         // FIXME: here we have only set the value of the option, without actually
         // making any real modifications.
@@ -675,10 +850,28 @@ impl Socket for StreamSocket {
                 let linger = socket_linger.get().unwrap();
                 options.socket.set_linger(*linger);
             },
+            socket_send_timeout: SendTimeout => {
+                let send_timeout = socket_send_timeout.get().unwrap();
+                options.socket.set_send_timeout(*send_timeout);
+            },
+            socket_recv_timeout: RecvTimeout => {
+                let recv_timeout = socket_recv_timeout.get().unwrap();
+                options.socket.set_recv_timeout(*recv_timeout);
+            },
+            socket_bind_to_device: BindToDevice => {
+                let name = socket_bind_to_device.get().unwrap();
+                options.socket.set_bound_device(check_bind_to_device(name.as_str())?);
+            },
+            socket_priority: Priority => {
+                let priority = socket_priority.get().unwrap();
+                check_priority(*priority)?;
+                options.socket.set_priority(*priority);
+            },
             // TCP options:
             tcp_no_delay: NoDelay => {
                 let no_delay = tcp_no_delay.get().unwrap();
                 options.tcp.set_no_delay(*no_delay);
+                self.set_nagle_enabled(!*no_delay);
             },
             tcp_congestion: Congestion => {
                 let congestion = tcp_congestion.get().unwrap();
@@ -703,9 +896,52 @@ impl Socket for StreamSocket {
                     options.tcp.set_window_clamp(*window_clamp);
                 }
             },
+            tcp_defer_accept: DeferAccept => {
+                // A negative timeout is treated as zero.
+                let secs = (*tcp_defer_accept.get().unwrap() as i32).max(0) as u32;
+                options.tcp.set_defer_accept(Retrans::from_secs(secs));
+            },
+            tcp_cork: Cork => {
+                let cork = tcp_cork.get().unwrap();
+                options.tcp.set_cork(*cork);
+                is_uncorked = !*cork;
+            },
+            tcp_keep_idle: KeepIdle => {
+                const MAX_KEEP_IDLE: u32 = 32767;
+
+                let keep_idle = tcp_keep_idle.get().unwrap();
+                if *keep_idle < 1 || *keep_idle > MAX_KEEP_IDLE {
+                    return_errno_with_message!(Errno::EINVAL, "the keepalive idle time is out of bounds");
+                }
+                options.tcp.set_keep_idle(*keep_idle);
+            },
+            tcp_keep_interval: KeepInterval => {
+                const MAX_KEEP_INTVL: u32 = 32767;
+
+                let keep_intvl = tcp_keep_interval.get().unwrap();
+                if *keep_intvl < 1 || *keep_intvl > MAX_KEEP_INTVL {
+                    return_errno_with_message!(Errno::EINVAL, "the keepalive interval is out of bounds");
+                }
+                options.tcp.set_keep_intvl(*keep_intvl);
+            },
+            tcp_keep_count: KeepCount => {
+                const MAX_KEEP_CNT: u32 = 127;
+
+                let keep_cnt = tcp_keep_count.get().unwrap();
+                if *keep_cnt < 1 || *keep_cnt > MAX_KEEP_CNT {
+                    return_errno_with_message!(Errno::EINVAL, "the keepalive probe count is out of bounds");
+                }
+                options.tcp.set_keep_cnt(*keep_cnt);
+            },
             _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to be set is unknown")
         });
 
+        // Polling the interfaces may invoke the observer, which will lock the options.
+        drop(options);
+        if is_uncorked {
+            self.flush_cork();
+        }
+
         Ok(())
     }
 }
@@ -5,7 +5,12 @@ use crate::impl_socket_options;
 
 impl_socket_options!(
     pub struct NoDelay(bool);
+    pub struct Cork(bool);
     pub struct Congestion(CongestionControl);
     pub struct MaxSegment(u32);
     pub struct WindowClamp(u32);
+    pub struct DeferAccept(u32);
+    pub struct KeepIdle(u32);
+    pub struct KeepInterval(u32);
+    pub struct KeepCount(u32);
 );
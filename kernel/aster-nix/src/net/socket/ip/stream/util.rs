@@ -7,21 +7,37 @@ use crate::prelude::*;
 #[set = "pub"]
 pub struct TcpOptionSet {
     no_delay: bool,
+    cork: bool,
     congestion: CongestionControl,
     maxseg: u32,
     window_clamp: u32,
+    defer_accept: Retrans,
+    /// The idle time in seconds before sending keepalive probes.
+    keep_idle: u32,
+    /// The interval in seconds between keepalive probes.
+    keep_intvl: u32,
+    /// The number of unanswered keepalive probes before dropping the connection.
+    keep_cnt: u32,
 }
 
 pub const DEFAULT_MAXSEG: u32 = 536;
 pub const DEFAULT_WINDOW_CLAMP: u32 = 0x8000_0000;
+pub const DEFAULT_KEEP_IDLE: u32 = 7200;
+pub const DEFAULT_KEEP_INTVL: u32 = 75;
+pub const DEFAULT_KEEP_CNT: u32 = 9;
 
 impl TcpOptionSet {
     pub fn new() -> Self {
         Self {
             no_delay: false,
+            cork: false,
             congestion: CongestionControl::Reno,
             maxseg: DEFAULT_MAXSEG,
             window_clamp: DEFAULT_WINDOW_CLAMP,
+            defer_accept: Retrans::ZERO,
+            keep_idle: DEFAULT_KEEP_IDLE,
+            keep_intvl: DEFAULT_KEEP_INTVL,
+            keep_cnt: DEFAULT_KEEP_CNT,
         }
     }
 }
@@ -32,6 +48,62 @@ impl Default for TcpOptionSet {
     }
 }
 
+/// The number of retransmissions, which is how a timeout like `TCP_DEFER_ACCEPT` is stored.
+///
+/// A timeout in seconds is converted to the number of retransmissions that it takes
+/// for an exponential backoff, which starts from one second and is capped at two minutes,
+/// to cover the timeout. So the timeout converted back is rounded up to the end of the
+/// last retransmission period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Retrans(u8);
+
+impl Retrans {
+    pub const ZERO: Self = Self(0);
+
+    /// The initial retransmission timeout in seconds.
+    const TIMEOUT_INIT_SECS: u32 = 1;
+    /// The maximum retransmission timeout in seconds.
+    const RTO_MAX_SECS: u32 = 120;
+
+    /// Converts a timeout in seconds to the number of retransmissions.
+    ///
+    /// The result saturates at `u8::MAX` retransmissions.
+    pub fn from_secs(secs: u32) -> Self {
+        if secs == 0 {
+            return Self::ZERO;
+        }
+
+        let mut timeout = Self::TIMEOUT_INIT_SECS;
+        let mut period = timeout;
+        let mut retrans = 1;
+        while secs > period && retrans < u8::MAX {
+            retrans += 1;
+            timeout = (timeout * 2).min(Self::RTO_MAX_SECS);
+            period += timeout;
+        }
+
+        Self(retrans)
+    }
+
+    /// Converts the number of retransmissions to a timeout in seconds.
+    ///
+    /// The result is at most 29887 seconds, which is the timeout of `u8::MAX` retransmissions.
+    pub fn to_secs(self) -> u32 {
+        if self.0 == 0 {
+            return 0;
+        }
+
+        let mut timeout = Self::TIMEOUT_INIT_SECS;
+        let mut period = timeout;
+        for _ in 1..self.0 {
+            timeout = (timeout * 2).min(Self::RTO_MAX_SECS);
+            period += timeout;
+        }
+
+        period
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum CongestionControl {
     Reno,
@@ -59,3 +131,36 @@ impl CongestionControl {
         }
     }
 }
+
+#[cfg(ktest)]
+mod test {
+    use ostd::prelude::*;
+
+    use super::*;
+
+    #[ktest]
+    fn retrans_round_trips() {
+        let mut last_secs = 0;
+        for retrans in 0..=u8::MAX {
+            let secs = Retrans(retrans).to_secs();
+            assert!(retrans == 0 || secs > last_secs);
+            assert_eq!(Retrans::from_secs(secs), Retrans(retrans));
+            last_secs = secs;
+        }
+        assert_eq!(last_secs, 29887);
+    }
+
+    #[ktest]
+    fn retrans_from_secs_is_monotonic_and_bounded() {
+        let max_secs = Retrans(u8::MAX).to_secs();
+
+        let mut last_secs = 0;
+        for secs in (0..=max_secs + 1).chain([u32::MAX - 1, u32::MAX]) {
+            let round_trip = Retrans::from_secs(secs).to_secs();
+            assert!(round_trip >= last_secs);
+            assert!(round_trip >= secs.min(max_secs));
+            assert!(round_trip <= max_secs);
+            last_secs = round_trip;
+        }
+    }
+}
@@ -2,8 +2,12 @@
 
 use self::options::SocketOption;
 pub use self::util::{
-    options::LingerOption, send_recv_flags::SendRecvFlags, shutdown_cmd::SockShutdownCmd,
-    socket_addr::SocketAddr, MessageHeader,
+    options::{IfName, LingerOption},
+    send_recv_flags::SendRecvFlags,
+    shutdown_cmd::SockShutdownCmd,
+    socket_addr::SocketAddr,
+    socket_info::SocketInfo,
+    MessageHeader,
 };
 use crate::{fs::file_handle::FileLike, prelude::*, util::IoVec};
 
@@ -15,6 +19,9 @@ pub mod vsock;
 
 /// Operations defined on a socket.
 pub trait Socket: FileLike + Send + Sync {
+    /// Get the address family and the protocol of the socket.
+    fn info(&self) -> SocketInfo;
+
     /// Assign the address specified by socket_addr to the socket
     fn bind(&self, _socket_addr: SocketAddr) -> Result<()> {
         return_errno_with_message!(Errno::EOPNOTSUPP, "bind() is not supported");
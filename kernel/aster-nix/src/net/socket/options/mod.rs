@@ -1,9 +1,15 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{impl_socket_options, prelude::*};
+use core::time::Duration;
+
+use crate::{
+    impl_socket_options,
+    prelude::*,
+    util::net::{CSocketAddrFamily, Protocol as CProtocol},
+};
 mod macros;
 
-use super::LingerOption;
+use super::{IfName, LingerOption};
 
 /// Socket options. This trait represents all options that can be set or got for a socket, including
 /// socket level options and options for specific socket type like tcp socket.
@@ -20,4 +26,10 @@ impl_socket_options!(
     pub struct Error(Option<crate::error::Error>);
     pub struct Linger(LingerOption);
     pub struct KeepAlive(bool);
+    pub struct SendTimeout(Duration);
+    pub struct RecvTimeout(Duration);
+    pub struct Domain(CSocketAddrFamily);
+    pub struct Protocol(CProtocol);
+    pub struct BindToDevice(IfName);
+    pub struct Priority(u32);
 );
@@ -1,6 +1,14 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{fs::path::Dentry, net::socket::util::socket_addr::SocketAddr, prelude::*};
+use crate::{
+    fs::{
+        fs_resolver::{split_path, FsPath},
+        path::Dentry,
+        utils::{InodeMode, InodeType, Permission},
+    },
+    net::socket::util::socket_addr::SocketAddr,
+    prelude::*,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum UnixSocketAddr {
@@ -53,3 +61,39 @@ impl From<UnixSocketAddrBound> for SocketAddr {
         SocketAddr::Unix(unix_socket_addr)
     }
 }
+
+/// Creates the socket file to bind a socket to the path.
+pub(super) fn create_socket_file(path: &str) -> Result<Arc<Dentry>> {
+    let (parent_pathname, file_name) = split_path(path);
+    let parent = {
+        let current = current!();
+        let fs = current.fs().read();
+        let parent_path = FsPath::try_from(parent_pathname)?;
+        fs.lookup(&parent_path)?
+    };
+    let dentry = parent.new_fs_child(
+        file_name,
+        InodeType::Socket,
+        InodeMode::S_IRUSR | InodeMode::S_IWUSR,
+    )?;
+    Ok(dentry)
+}
+
+/// Looks up the socket file at the path to connect or send to.
+pub(super) fn lookup_socket_file(path: &str) -> Result<Arc<Dentry>> {
+    let dentry = {
+        let current = current!();
+        let fs = current.fs().read();
+        let fs_path = FsPath::try_from(path)?;
+        fs.lookup(&fs_path)?
+    };
+
+    if dentry.type_() != InodeType::Socket {
+        return_errno_with_message!(Errno::ENOTSOCK, "not a socket file")
+    }
+
+    dentry
+        .inode()
+        .check_permission(Permission::MAY_READ | Permission::MAY_WRITE)?;
+    Ok(dentry)
+}
@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: MPL-2.0
+
+mod socket;
+
+pub use socket::UnixDatagramSocket;
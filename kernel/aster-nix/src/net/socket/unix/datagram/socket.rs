@@ -0,0 +1,426 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+
+use keyable_arc::KeyableWeak;
+
+use crate::{
+    events::{IoEvents, Observer},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        utils::{Channel, Consumer, Inode, Producer, StatusFlags},
+    },
+    match_sock_option_mut, match_sock_option_ref,
+    net::{
+        iface::RECV_BUF_LEN,
+        socket::{
+            options::{RecvBuf, SocketOption},
+            unix::{
+                addr::{create_socket_file, lookup_socket_file, UnixSocketAddrBound},
+                UnixSocketAddr,
+            },
+            util::{
+                copy_message_from_user, copy_message_to_user, options::MIN_RECVBUF,
+                recv_return_len, send_recv_flags::SendRecvFlags, socket_addr::SocketAddr,
+                truncated_flags, MessageHeader,
+            },
+            Socket, SocketInfo,
+        },
+    },
+    prelude::*,
+    process::signal::{Pollee, Poller},
+    util::IoVec,
+};
+
+/// A Unix datagram socket.
+///
+/// Each socket has a queue of the datagrams sent to it. A datagram is sent by pushing it
+/// into the queue of the destination socket, which is either specified by the address in
+/// `sendto`/`sendmsg` or is the peer socket set by `connect`.
+pub struct UnixDatagramSocket {
+    addr: Mutex<Option<UnixSocketAddrBound>>,
+    peer: Mutex<Option<Weak<UnixDatagramSocket>>>,
+    producer: Producer<Datagram>,
+    consumer: Consumer<Datagram>,
+    /// The limit of the total bytes of the queued datagrams, i.e., `SO_RCVBUF`.
+    recv_buf: AtomicU32,
+    /// The total bytes of the queued datagrams.
+    queued_bytes: AtomicUsize,
+    /// Notifies the senders waiting for room in the queue with `IoEvents::OUT` when some queued
+    /// bytes are released.
+    queue_pollee: Pollee,
+    is_nonblocking: AtomicBool,
+    info: SocketInfo,
+    _file_count: FileCount,
+    weak_self: Weak<UnixDatagramSocket>,
+}
+
+struct Datagram {
+    src_addr: Option<UnixSocketAddrBound>,
+    data: Box<[u8]>,
+}
+
+impl UnixDatagramSocket {
+    pub fn new(info: SocketInfo, is_nonblocking: bool) -> Result<Arc<Self>> {
+        let (producer, consumer) = Channel::with_capacity(DEFAULT_QUEUE_LEN)?.split();
+        Ok(Arc::new_cyclic(|weak_self| Self {
+            addr: Mutex::new(None),
+            peer: Mutex::new(None),
+            producer,
+            consumer,
+            recv_buf: AtomicU32::new(RECV_BUF_LEN as u32),
+            queued_bytes: AtomicUsize::new(0),
+            queue_pollee: Pollee::new(IoEvents::OUT),
+            is_nonblocking: AtomicBool::new(is_nonblocking),
+            info,
+            _file_count: FileCount::new(),
+            weak_self: weak_self.clone(),
+        }))
+    }
+
+    pub fn new_pair(info: SocketInfo, is_nonblocking: bool) -> Result<(Arc<Self>, Arc<Self>)> {
+        let socket_a = Self::new(info, is_nonblocking)?;
+        let socket_b = Self::new(info, is_nonblocking)?;
+        *socket_a.peer.lock() = Some(Arc::downgrade(&socket_b));
+        *socket_b.peer.lock() = Some(Arc::downgrade(&socket_a));
+        Ok((socket_a, socket_b))
+    }
+
+    fn is_nonblocking(&self) -> bool {
+        self.is_nonblocking.load(Ordering::Relaxed)
+    }
+
+    fn set_nonblocking(&self, is_nonblocking: bool) {
+        self.is_nonblocking.store(is_nonblocking, Ordering::Relaxed);
+    }
+
+    fn bound_addr(&self) -> Option<UnixSocketAddrBound> {
+        self.addr.lock().clone()
+    }
+
+    /// Returns the peer socket set by `connect`.
+    fn peer(&self) -> Result<Arc<UnixDatagramSocket>> {
+        let peer = self.peer.lock();
+        let Some(peer) = peer.as_ref() else {
+            return_errno_with_message!(Errno::ENOTCONN, "the socket is not connected");
+        };
+        peer.upgrade()
+            .ok_or_else(|| Error::with_message(Errno::ECONNREFUSED, "the peer socket is closed"))
+    }
+
+    fn send(
+        &self,
+        buf: &[u8],
+        remote: Option<UnixSocketAddrBound>,
+        flags: SendRecvFlags,
+    ) -> Result<usize> {
+        let remote = match remote {
+            Some(remote_addr) => lookup_bound_socket(&remote_addr)?,
+            None => self.peer()?,
+        };
+
+        let is_nonblocking = self.is_nonblocking() || flags.contains(SendRecvFlags::MSG_DONTWAIT);
+        remote.reserve_queued_bytes(buf.len(), is_nonblocking)?;
+
+        let datagram = Datagram {
+            src_addr: self.bound_addr(),
+            data: buf.into(),
+        };
+        let res = if is_nonblocking {
+            remote.producer.try_push(datagram)
+        } else {
+            remote.producer.push(datagram)
+        };
+        if let Err((err, _)) = res {
+            remote.release_queued_bytes(buf.len());
+            return Err(err);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn recv(&self, flags: SendRecvFlags) -> Result<Datagram> {
+        let datagram = if self.is_nonblocking() || flags.contains(SendRecvFlags::MSG_DONTWAIT) {
+            self.consumer.try_pop()
+        } else {
+            self.consumer.pop()
+        }?;
+
+        self.release_queued_bytes(datagram.data.len());
+        Ok(datagram)
+    }
+
+    /// Accounts `len` bytes to the receive queue before a datagram is pushed into it.
+    ///
+    /// A datagram is always accepted if the queue is empty. Otherwise, the datagram cannot
+    /// make the queue exceed the limit set by `SO_RCVBUF`, so the sender waits until the
+    /// receiver makes room, or gets `EAGAIN` in nonblocking mode.
+    fn reserve_queued_bytes(&self, len: usize, is_nonblocking: bool) -> Result<()> {
+        if self.try_reserve_queued_bytes(len) {
+            return Ok(());
+        }
+        if is_nonblocking {
+            return_errno_with_message!(Errno::EAGAIN, "the receive buffer of the peer is full");
+        }
+
+        let poller = Poller::new();
+        loop {
+            // The event is cleared before the poller is registered and the queue is checked
+            // again afterwards, so that the bytes released in between are not missed.
+            self.queue_pollee.del_events(IoEvents::OUT);
+            let events = self.queue_pollee.poll(IoEvents::OUT, Some(&poller));
+            if self.try_reserve_queued_bytes(len) {
+                return Ok(());
+            }
+            if events.is_empty() {
+                poller.wait()?;
+            }
+        }
+    }
+
+    fn try_reserve_queued_bytes(&self, len: usize) -> bool {
+        let recv_buf = self.recv_buf.load(Ordering::Relaxed) as usize;
+
+        self.queued_bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| {
+                if queued != 0 && queued + len > recv_buf {
+                    None
+                } else {
+                    Some(queued + len)
+                }
+            })
+            .is_ok()
+    }
+
+    fn release_queued_bytes(&self, len: usize) {
+        self.queued_bytes.fetch_sub(len, Ordering::Relaxed);
+        self.queue_pollee.add_events(IoEvents::OUT);
+    }
+}
+
+impl FileLike for UnixDatagramSocket {
+    fn as_socket(self: Arc<Self>) -> Option<Arc<dyn Socket>> {
+        Some(self)
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        // TODO: Set correct flags
+        let flags = SendRecvFlags::empty();
+        let datagram = self.recv(flags)?;
+
+        // The excess bytes of the datagram are discarded.
+        let len = datagram.data.len().min(buf.len());
+        buf[..len].copy_from_slice(&datagram.data[..len]);
+        Ok(len)
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize> {
+        // TODO: Set correct flags
+        let flags = SendRecvFlags::empty();
+        self.send(buf, None, flags)
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        let mut events = self.consumer.poll(mask, poller) & IoEvents::IN;
+
+        // A connected socket is writable only if the queue of the peer socket is not full.
+        events |= match self.peer() {
+            Ok(peer) => peer.producer.poll(mask, poller) & IoEvents::OUT,
+            Err(_) => IoEvents::OUT & mask,
+        };
+
+        events
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        if self.is_nonblocking() {
+            StatusFlags::O_NONBLOCK
+        } else {
+            StatusFlags::empty()
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        self.set_nonblocking(new_flags.contains(StatusFlags::O_NONBLOCK));
+        Ok(())
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.consumer.register_observer(observer, mask)
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        self.consumer.unregister_observer(observer)
+    }
+}
+
+impl Socket for UnixDatagramSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
+    fn bind(&self, socket_addr: SocketAddr) -> Result<()> {
+        let UnixSocketAddr::Path(path) = UnixSocketAddr::try_from(socket_addr)? else {
+            return_errno_with_message!(
+                Errno::EOPNOTSUPP,
+                "binding to abstract addresses is not supported"
+            );
+        };
+
+        let mut addr = self.addr.lock();
+        if addr.is_some() {
+            return_errno_with_message!(Errno::EINVAL, "the socket is already bound");
+        }
+
+        let dentry = create_socket_file(&path)?;
+        BOUND_SOCKETS
+            .write()
+            .insert(create_keyable_inode(dentry.inode()), self.weak_self.clone());
+
+        *addr = Some(UnixSocketAddrBound::Path(dentry));
+        Ok(())
+    }
+
+    fn connect(&self, socket_addr: SocketAddr) -> Result<()> {
+        let remote_addr = resolve_remote_addr(socket_addr)?;
+        let remote = lookup_bound_socket(&remote_addr)?;
+
+        *self.peer.lock() = Some(Arc::downgrade(&remote));
+        Ok(())
+    }
+
+    fn addr(&self) -> Result<SocketAddr> {
+        match self.bound_addr() {
+            Some(addr) => Ok(SocketAddr::from(addr)),
+            None => Ok(SocketAddr::Unix(UnixSocketAddr::Path(String::new()))),
+        }
+    }
+
+    fn peer_addr(&self) -> Result<SocketAddr> {
+        match self.peer()?.bound_addr() {
+            Some(addr) => Ok(SocketAddr::from(addr)),
+            None => Ok(SocketAddr::Unix(UnixSocketAddr::Path(String::new()))),
+        }
+    }
+
+    fn get_option(&self, option: &mut dyn SocketOption) -> Result<()> {
+        match_sock_option_mut!(option, {
+            socket_recv_buf: RecvBuf => {
+                let recv_buf = self.recv_buf.load(Ordering::Relaxed);
+                socket_recv_buf.set(recv_buf);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to get is unknown")
+        });
+
+        Ok(())
+    }
+
+    fn set_option(&self, option: &dyn SocketOption) -> Result<()> {
+        match_sock_option_ref!(option, {
+            socket_recv_buf: RecvBuf => {
+                let recv_buf = socket_recv_buf.get().unwrap();
+                self.recv_buf.store((*recv_buf).max(MIN_RECVBUF), Ordering::Relaxed);
+            },
+            _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the socket option to be set is unknown")
+        });
+
+        Ok(())
+    }
+
+    fn sendmsg(
+        &self,
+        io_vecs: &[IoVec],
+        message_header: MessageHeader,
+        flags: SendRecvFlags,
+    ) -> Result<usize> {
+        let MessageHeader {
+            addr,
+            control_message,
+            ..
+        } = message_header;
+
+        let remote_addr = addr.map(resolve_remote_addr).transpose()?;
+
+        if control_message.is_some() {
+            // TODO: Support sending control message
+            warn!("sending control message is not supported");
+        }
+
+        let buf = copy_message_from_user(io_vecs);
+
+        self.send(&buf, remote_addr, flags)
+    }
+
+    fn recvmsg(&self, io_vecs: &[IoVec], flags: SendRecvFlags) -> Result<(usize, MessageHeader)> {
+        let datagram = self.recv(flags)?;
+
+        // The excess bytes of the datagram are discarded.
+        let received_bytes = datagram.data.len();
+        let copied_bytes = copy_message_to_user(io_vecs, &datagram.data);
+
+        // TODO: Receive control message
+
+        let src_addr = datagram.src_addr.map(SocketAddr::from);
+        let message_header = MessageHeader::new(src_addr, None)
+            .with_flags(truncated_flags(received_bytes, copied_bytes));
+
+        Ok((
+            recv_return_len(received_bytes, copied_bytes, flags),
+            message_header,
+        ))
+    }
+}
+
+impl Drop for UnixDatagramSocket {
+    fn drop(&mut self) {
+        let Some(UnixSocketAddrBound::Path(dentry)) = self.addr.lock().take() else {
+            return;
+        };
+
+        BOUND_SOCKETS
+            .write()
+            .remove(&create_keyable_inode(dentry.inode()));
+    }
+}
+
+/// The maximum number of datagrams in the queue of a socket.
+const DEFAULT_QUEUE_LEN: usize = 128;
+
+/// The datagram sockets that are bound to socket files.
+static BOUND_SOCKETS: RwLock<BTreeMap<KeyableWeak<dyn Inode>, Weak<UnixDatagramSocket>>> =
+    RwLock::new(BTreeMap::new());
+
+fn resolve_remote_addr(socket_addr: SocketAddr) -> Result<UnixSocketAddrBound> {
+    match UnixSocketAddr::try_from(socket_addr)? {
+        UnixSocketAddr::Path(path) => Ok(UnixSocketAddrBound::Path(lookup_socket_file(&path)?)),
+        UnixSocketAddr::Abstract(name) => Ok(UnixSocketAddrBound::Abstract(name)),
+    }
+}
+
+fn lookup_bound_socket(addr: &UnixSocketAddrBound) -> Result<Arc<UnixDatagramSocket>> {
+    let UnixSocketAddrBound::Path(dentry) = addr else {
+        return_errno_with_message!(
+            Errno::ECONNREFUSED,
+            "no socket is bound to the abstract address"
+        );
+    };
+
+    BOUND_SOCKETS
+        .read()
+        .get(&create_keyable_inode(dentry.inode()))
+        .and_then(Weak::upgrade)
+        .ok_or_else(|| {
+            Error::with_message(Errno::ECONNREFUSED, "no socket is bound to the address")
+        })
+}
+
+fn create_keyable_inode(inode: &Arc<dyn Inode>) -> KeyableWeak<dyn Inode> {
+    KeyableWeak::from(Arc::downgrade(inode))
+}
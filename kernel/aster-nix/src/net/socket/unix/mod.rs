@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod addr;
+mod datagram;
 mod stream;
 
 pub use addr::UnixSocketAddr;
+pub use datagram::UnixDatagramSocket;
 pub use stream::UnixStreamSocket;
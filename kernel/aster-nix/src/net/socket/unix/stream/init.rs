@@ -5,12 +5,7 @@ use core::sync::atomic::{AtomicBool, Ordering};
 use super::{connected::Connected, endpoint::Endpoint, listener::push_incoming};
 use crate::{
     events::IoEvents,
-    fs::{
-        fs_resolver::{split_path, FsPath},
-        path::Dentry,
-        utils::{InodeMode, InodeType},
-    },
-    net::socket::unix::addr::{UnixSocketAddr, UnixSocketAddrBound},
+    net::socket::unix::addr::{create_socket_file, UnixSocketAddr, UnixSocketAddrBound},
     prelude::*,
     process::signal::{Pollee, Poller},
 };
@@ -83,19 +78,3 @@ impl Init {
         self.pollee.poll(mask, poller)
     }
 }
-
-fn create_socket_file(path: &str) -> Result<Arc<Dentry>> {
-    let (parent_pathname, file_name) = split_path(path);
-    let parent = {
-        let current = current!();
-        let fs = current.fs().read();
-        let parent_path = FsPath::try_from(parent_pathname)?;
-        fs.lookup(&parent_path)?
-    };
-    let dentry = parent.new_fs_child(
-        file_name,
-        InodeType::Socket,
-        InodeMode::S_IRUSR | InodeMode::S_IWUSR,
-    )?;
-    Ok(dentry)
-}
@@ -10,7 +10,7 @@ use crate::{
     fs::{file_handle::FileLike, path::Dentry, utils::Inode},
     net::socket::{
         unix::addr::{UnixSocketAddr, UnixSocketAddrBound},
-        SocketAddr,
+        SocketAddr, SocketInfo,
     },
     prelude::*,
     process::signal::{Pollee, Poller},
@@ -46,7 +46,7 @@ impl Listener {
         self.is_nonblocking.store(is_nonblocking, Ordering::Release);
     }
 
-    pub(super) fn accept(&self) -> Result<(Arc<dyn FileLike>, SocketAddr)> {
+    pub(super) fn accept(&self, info: SocketInfo) -> Result<(Arc<dyn FileLike>, SocketAddr)> {
         let addr = self.addr().clone();
         let is_nonblocking = self.is_nonblocking();
 
@@ -60,7 +60,7 @@ impl Listener {
             Some(addr) => SocketAddr::from(addr.clone()),
         };
 
-        let socket = Arc::new(UnixStreamSocket::new_connected(connected));
+        let socket = Arc::new(UnixStreamSocket::new_connected(connected, info));
 
         Ok((socket, peer_addr))
     }
@@ -9,33 +9,46 @@ use super::{
 use crate::{
     events::IoEvents,
     fs::{
-        file_handle::FileLike,
-        fs_resolver::FsPath,
-        path::Dentry,
-        utils::{InodeType, StatusFlags},
+        file_handle::{FileCount, FileLike},
+        utils::StatusFlags,
     },
     net::socket::{
-        unix::{addr::UnixSocketAddrBound, UnixSocketAddr},
+        unix::{
+            addr::{lookup_socket_file, UnixSocketAddrBound},
+            UnixSocketAddr,
+        },
         util::{
             copy_message_from_user, copy_message_to_user, create_message_buffer,
             send_recv_flags::SendRecvFlags, socket_addr::SocketAddr, MessageHeader,
         },
-        SockShutdownCmd, Socket,
+        SockShutdownCmd, Socket, SocketInfo,
     },
     prelude::*,
     process::signal::Poller,
     util::IoVec,
 };
 
-pub struct UnixStreamSocket(RwLock<State>);
+pub struct UnixStreamSocket {
+    state: RwLock<State>,
+    info: SocketInfo,
+    _file_count: FileCount,
+}
 
 impl UnixStreamSocket {
-    pub(super) fn new_init(init: Init) -> Self {
-        Self(RwLock::new(State::Init(Arc::new(init))))
+    pub(super) fn new_init(init: Init, info: SocketInfo) -> Self {
+        Self {
+            state: RwLock::new(State::Init(Arc::new(init))),
+            info,
+            _file_count: FileCount::new(),
+        }
     }
 
-    pub(super) fn new_connected(connected: Connected) -> Self {
-        Self(RwLock::new(State::Connected(Arc::new(connected))))
+    pub(super) fn new_connected(connected: Connected, info: SocketInfo) -> Self {
+        Self {
+            state: RwLock::new(State::Connected(Arc::new(connected))),
+            info,
+            _file_count: FileCount::new(),
+        }
     }
 }
 
@@ -46,26 +59,26 @@ enum State {
 }
 
 impl UnixStreamSocket {
-    pub fn new(nonblocking: bool) -> Self {
+    pub fn new(info: SocketInfo, nonblocking: bool) -> Self {
         let init = Init::new(nonblocking);
-        Self::new_init(init)
+        Self::new_init(init, info)
     }
 
-    pub fn new_pair(nonblocking: bool) -> Result<(Arc<Self>, Arc<Self>)> {
+    pub fn new_pair(info: SocketInfo, nonblocking: bool) -> Result<(Arc<Self>, Arc<Self>)> {
         let (end_a, end_b) = Endpoint::new_pair(nonblocking)?;
         let connected_a = {
             let connected = Connected::new(end_a);
-            Self::new_connected(connected)
+            Self::new_connected(connected, info)
         };
         let connected_b = {
             let connected = Connected::new(end_b);
-            Self::new_connected(connected)
+            Self::new_connected(connected, info)
         };
         Ok((Arc::new(connected_a), Arc::new(connected_b)))
     }
 
     fn bound_addr(&self) -> Option<UnixSocketAddrBound> {
-        let status = self.0.read();
+        let status = self.state.read();
         match &*status {
             State::Init(init) => init.addr(),
             State::Listen(listen) => Some(listen.addr().clone()),
@@ -85,7 +98,7 @@ impl UnixStreamSocket {
     }
 
     fn send(&self, buf: &[u8], _flags: SendRecvFlags) -> Result<usize> {
-        let connected = match &*self.0.read() {
+        let connected = match &*self.state.read() {
             State::Connected(connected) => connected.clone(),
             _ => return_errno_with_message!(Errno::ENOTCONN, "the socket is not connected"),
         };
@@ -94,7 +107,7 @@ impl UnixStreamSocket {
     }
 
     fn recv(&self, buf: &mut [u8], _flags: SendRecvFlags) -> Result<usize> {
-        let connected = match &*self.0.read() {
+        let connected = match &*self.state.read() {
             State::Connected(connected) => connected.clone(),
             _ => return_errno_with_message!(Errno::ENOTCONN, "the socket is not connected"),
         };
@@ -121,7 +134,7 @@ impl FileLike for UnixStreamSocket {
     }
 
     fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
-        let inner = self.0.read();
+        let inner = self.state.read();
         match &*inner {
             State::Init(init) => init.poll(mask, poller),
             State::Listen(listen) => listen.poll(mask, poller),
@@ -130,7 +143,7 @@ impl FileLike for UnixStreamSocket {
     }
 
     fn status_flags(&self) -> StatusFlags {
-        let inner = self.0.read();
+        let inner = self.state.read();
         let is_nonblocking = match &*inner {
             State::Init(init) => init.is_nonblocking(),
             State::Listen(listen) => listen.is_nonblocking(),
@@ -150,7 +163,7 @@ impl FileLike for UnixStreamSocket {
             supported_flags.contains(StatusFlags::O_NONBLOCK)
         };
 
-        let mut inner = self.0.write();
+        let mut inner = self.state.write();
         match &mut *inner {
             State::Init(init) => init.set_nonblocking(is_nonblocking),
             State::Listen(listen) => listen.set_nonblocking(is_nonblocking),
@@ -161,10 +174,14 @@ impl FileLike for UnixStreamSocket {
 }
 
 impl Socket for UnixStreamSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
     fn bind(&self, socket_addr: SocketAddr) -> Result<()> {
         let addr = UnixSocketAddr::try_from(socket_addr)?;
 
-        let init = match &*self.0.read() {
+        let init = match &*self.state.read() {
             State::Init(init) => init.clone(),
             _ => return_errno_with_message!(
                 Errno::EINVAL,
@@ -190,7 +207,7 @@ impl Socket for UnixStreamSocket {
             }
         };
 
-        let init = match &*self.0.read() {
+        let init = match &*self.state.read() {
             State::Init(init) => init.clone(),
             State::Listen(_) => return_errno_with_message!(Errno::EINVAL, "the socket is listened"),
             State::Connected(_) => {
@@ -200,12 +217,12 @@ impl Socket for UnixStreamSocket {
 
         let connected = init.connect(&remote_addr)?;
 
-        *self.0.write() = State::Connected(Arc::new(connected));
+        *self.state.write() = State::Connected(Arc::new(connected));
         Ok(())
     }
 
     fn listen(&self, backlog: usize) -> Result<()> {
-        let init = match &*self.0.read() {
+        let init = match &*self.state.read() {
             State::Init(init) => init.clone(),
             State::Listen(_) => {
                 return_errno_with_message!(Errno::EINVAL, "the socket is already listening")
@@ -221,21 +238,21 @@ impl Socket for UnixStreamSocket {
         ))?;
 
         let listener = Listener::new(addr.clone(), backlog, init.is_nonblocking())?;
-        *self.0.write() = State::Listen(Arc::new(listener));
+        *self.state.write() = State::Listen(Arc::new(listener));
         Ok(())
     }
 
     fn accept(&self) -> Result<(Arc<dyn FileLike>, SocketAddr)> {
-        let listen = match &*self.0.read() {
+        let listen = match &*self.state.read() {
             State::Listen(listen) => listen.clone(),
             _ => return_errno_with_message!(Errno::EINVAL, "the socket is not listening"),
         };
 
-        listen.accept()
+        listen.accept(self.info)
     }
 
     fn shutdown(&self, cmd: SockShutdownCmd) -> Result<()> {
-        let connected = match &*self.0.read() {
+        let connected = match &*self.state.read() {
             State::Connected(connected) => connected.clone(),
             _ => return_errno_with_message!(Errno::ENOTCONN, "the socked is not connected"),
         };
@@ -244,7 +261,7 @@ impl Socket for UnixStreamSocket {
     }
 
     fn addr(&self) -> Result<SocketAddr> {
-        let addr = match &*self.0.read() {
+        let addr = match &*self.state.read() {
             State::Init(init) => init.addr(),
             State::Listen(listen) => Some(listen.addr().clone()),
             State::Connected(connected) => connected.addr(),
@@ -258,7 +275,7 @@ impl Socket for UnixStreamSocket {
     }
 
     fn peer_addr(&self) -> Result<SocketAddr> {
-        let connected = match &*self.0.read() {
+        let connected = match &*self.state.read() {
             State::Connected(connected) => connected.clone(),
             _ => return_errno_with_message!(Errno::ENOTCONN, "the socket is not connected"),
         };
@@ -318,26 +335,8 @@ impl Drop for UnixStreamSocket {
             return;
         };
 
-        if let State::Listen(_) = &*self.0.read() {
+        if let State::Listen(_) = &*self.state.read() {
             unregister_backlog(&bound_addr);
         }
     }
 }
-
-fn lookup_socket_file(path: &str) -> Result<Arc<Dentry>> {
-    let dentry = {
-        let current = current!();
-        let fs = current.fs().read();
-        let fs_path = FsPath::try_from(path)?;
-        fs.lookup(&fs_path)?
-    };
-
-    if dentry.type_() != InodeType::Socket {
-        return_errno_with_message!(Errno::ENOTSOCK, "not a socket file")
-    }
-
-    if !dentry.mode()?.is_readable() || !dentry.mode()?.is_writable() {
-        return_errno_with_message!(Errno::EACCES, "the socket cannot be read or written")
-    }
-    Ok(dentry)
-}
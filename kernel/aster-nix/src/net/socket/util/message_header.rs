@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use super::socket_addr::SocketAddr;
+use super::{send_recv_flags::SendRecvFlags, socket_addr::SocketAddr};
 use crate::{prelude::*, util::IoVec};
 
 /// Message header used for sendmsg/recvmsg.
@@ -8,6 +8,7 @@ use crate::{prelude::*, util::IoVec};
 pub struct MessageHeader {
     pub(in crate::net) addr: Option<SocketAddr>,
     pub(in crate::net) control_message: Option<ControlMessage>,
+    pub(in crate::net) flags: SendRecvFlags,
 }
 
 impl MessageHeader {
@@ -16,13 +17,25 @@ impl MessageHeader {
         Self {
             addr,
             control_message,
+            flags: SendRecvFlags::empty(),
         }
     }
 
+    /// Sets the flags on the received message, e.g., `MSG_TRUNC`.
+    pub(in crate::net) fn with_flags(mut self, flags: SendRecvFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
     /// Returns the socket address.
     pub fn addr(&self) -> Option<&SocketAddr> {
         self.addr.as_ref()
     }
+
+    /// Returns the flags on the received message.
+    pub fn flags(&self) -> SendRecvFlags {
+        self.flags
+    }
 }
 
 /// Control message carried by MessageHeader.
@@ -93,3 +106,25 @@ pub fn copy_message_to_user(io_vecs: &[IoVec], message: &[u8]) -> usize {
 
     total_bytes
 }
+
+/// Returns the flags on a received datagram of `received_bytes` bytes,
+/// of which `copied_bytes` bytes are copied to user space.
+pub fn truncated_flags(received_bytes: usize, copied_bytes: usize) -> SendRecvFlags {
+    if copied_bytes < received_bytes {
+        SendRecvFlags::MSG_TRUNC
+    } else {
+        SendRecvFlags::empty()
+    }
+}
+
+/// Returns the return value of receiving a datagram.
+///
+/// Like Linux, if `MSG_TRUNC` is specified in `flags`, the length of the whole datagram
+/// is returned even if the datagram is truncated. Otherwise, the copied length is returned.
+pub fn recv_return_len(received_bytes: usize, copied_bytes: usize, flags: SendRecvFlags) -> usize {
+    if flags.contains(SendRecvFlags::MSG_TRUNC) {
+        received_bytes
+    } else {
+        copied_bytes
+    }
+}
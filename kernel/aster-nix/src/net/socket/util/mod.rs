@@ -5,8 +5,10 @@ pub mod options;
 pub mod send_recv_flags;
 pub mod shutdown_cmd;
 pub mod socket_addr;
+pub mod socket_info;
 
 pub use message_header::MessageHeader;
 pub(in crate::net) use message_header::{
-    copy_message_from_user, copy_message_to_user, create_message_buffer,
+    copy_message_from_user, copy_message_to_user, create_message_buffer, recv_return_len,
+    truncated_flags,
 };
@@ -5,6 +5,7 @@ use core::time::Duration;
 use crate::{
     net::iface::{RECV_BUF_LEN, SEND_BUF_LEN},
     prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
 };
 
 #[derive(Debug, Clone, CopyGetters, Setters)]
@@ -17,6 +18,16 @@ pub struct SocketOptionSet {
     send_buf: u32,
     recv_buf: u32,
     linger: LingerOption,
+    send_timeout: Duration,
+    recv_timeout: Duration,
+    /// The name of the device bound by `SO_BINDTODEVICE`.
+    bound_device: Option<&'static str>,
+    /// The priority set by `SO_PRIORITY`.
+    ///
+    /// The priority is only validated and reported back. It does not change the order in
+    /// which the packets are sent, since smoltcp dispatches the sockets of an interface in a
+    /// fixed order and the kernel has no transmit queue of its own to reorder.
+    priority: u32,
 }
 
 impl SocketOptionSet {
@@ -29,13 +40,37 @@ impl SocketOptionSet {
             send_buf: SEND_BUF_LEN as u32,
             recv_buf: RECV_BUF_LEN as u32,
             linger: LingerOption::default(),
+            send_timeout: Duration::ZERO,
+            recv_timeout: Duration::ZERO,
+            bound_device: None,
+            priority: 0,
         }
     }
+
+    /// Return the default socket level options for udp socket.
+    pub fn new_udp() -> Self {
+        // The buffers of udp sockets have the same sizes as those of tcp sockets.
+        Self::new_tcp()
+    }
 }
 
 pub const MIN_SENDBUF: u32 = 2304;
 pub const MIN_RECVBUF: u32 = 2304;
 
+/// The max priority that can be set without `CAP_NET_ADMIN`.
+const MAX_UNPRIVILEGED_PRIORITY: u32 = 6;
+
+/// Checks whether the current thread can set the socket priority to `priority`.
+pub fn check_priority(priority: u32) -> Result<()> {
+    if priority > MAX_UNPRIVILEGED_PRIORITY && !credentials().capable(CapSet::NET_ADMIN) {
+        return_errno_with_message!(
+            Errno::EPERM,
+            "CAP_NET_ADMIN is required to set a high priority"
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct LingerOption {
     is_on: bool,
@@ -55,3 +90,22 @@ impl LingerOption {
         self.timeout
     }
 }
+
+/// The name of a network device, as used by `SO_BINDTODEVICE`.
+///
+/// An empty name means no device.
+#[derive(Debug, Default, Clone)]
+pub struct IfName(String);
+
+impl IfName {
+    /// The maximum length of a device name, including the terminating null byte.
+    pub const IFNAMSIZ: usize = 16;
+
+    pub fn new(name: &str) -> Self {
+        Self(name.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::util::net::{CSocketAddrFamily, Protocol};
+
+/// The address family and the protocol of a socket.
+///
+/// They are decided when the socket is created and never change afterwards.
+#[derive(Debug, Clone, Copy)]
+pub struct SocketInfo {
+    domain: CSocketAddrFamily,
+    protocol: Protocol,
+}
+
+impl SocketInfo {
+    pub const fn new(domain: CSocketAddrFamily, protocol: Protocol) -> Self {
+        Self { domain, protocol }
+    }
+
+    pub fn domain(&self) -> CSocketAddrFamily {
+        self.domain
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+}
@@ -7,11 +7,14 @@ use atomic::Ordering;
 use super::{connected::Connected, connecting::Connecting, init::Init, listen::Listen};
 use crate::{
     events::IoEvents,
-    fs::{file_handle::FileLike, utils::StatusFlags},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        utils::StatusFlags,
+    },
     net::socket::{
         util::{copy_message_from_user, copy_message_to_user, create_message_buffer},
         vsock::{addr::VsockSocketAddr, VSOCK_GLOBAL},
-        MessageHeader, SendRecvFlags, SockShutdownCmd, Socket, SocketAddr,
+        MessageHeader, SendRecvFlags, SockShutdownCmd, Socket, SocketAddr, SocketInfo,
     },
     prelude::*,
     process::signal::Poller,
@@ -21,6 +24,8 @@ use crate::{
 pub struct VsockStreamSocket {
     status: RwLock<Status>,
     is_nonblocking: AtomicBool,
+    info: SocketInfo,
+    _file_count: FileCount,
 }
 
 pub enum Status {
@@ -30,18 +35,22 @@ pub enum Status {
 }
 
 impl VsockStreamSocket {
-    pub fn new(nonblocking: bool) -> Self {
+    pub fn new(info: SocketInfo, nonblocking: bool) -> Self {
         let init = Arc::new(Init::new());
         Self {
             status: RwLock::new(Status::Init(init)),
             is_nonblocking: AtomicBool::new(nonblocking),
+            info,
+            _file_count: FileCount::new(),
         }
     }
 
-    pub(super) fn new_from_connected(connected: Arc<Connected>) -> Self {
+    pub(super) fn new_from_connected(connected: Arc<Connected>, info: SocketInfo) -> Self {
         Self {
             status: RwLock::new(Status::Connected(connected)),
             is_nonblocking: AtomicBool::new(false),
+            info,
+            _file_count: FileCount::new(),
         }
     }
 
@@ -110,7 +119,7 @@ impl VsockStreamSocket {
             .response(&connected.get_info())
             .unwrap();
 
-        let socket = Arc::new(VsockStreamSocket::new_from_connected(connected));
+        let socket = Arc::new(VsockStreamSocket::new_from_connected(connected, self.info));
         Ok((socket, peer_addr.into()))
     }
 
@@ -197,6 +206,10 @@ impl FileLike for VsockStreamSocket {
 }
 
 impl Socket for VsockStreamSocket {
+    fn info(&self) -> SocketInfo {
+        self.info
+    }
+
     fn bind(&self, sockaddr: SocketAddr) -> Result<()> {
         let addr = VsockSocketAddr::try_from(sockaddr)?;
         let inner = self.status.read();
@@ -2,7 +2,7 @@
 
 #![allow(unused_variables)]
 
-use core::sync::atomic::Ordering;
+use core::sync::atomic::{AtomicU64, Ordering};
 
 use aster_rights::Full;
 use ostd::{
@@ -17,6 +17,7 @@ use super::{
     process_table,
     process_vm::ProcessVm,
     signal::sig_disposition::SigDispositions,
+    wait::reap_auto_reaped_children,
     Credentials, Process, ProcessBuilder,
 };
 use crate::{
@@ -82,6 +83,14 @@ impl CloneArgs {
         }
     }
 
+    /// Clone Args for syscall vfork.
+    pub const fn for_vfork() -> Self {
+        CloneArgs {
+            clone_flags: CloneFlags::CLONE_VM.union(CloneFlags::CLONE_VFORK),
+            ..Self::for_fork()
+        }
+    }
+
     pub const fn new(
         new_sp: u64,
         stack_size: usize,
@@ -120,34 +129,61 @@ impl CloneFlags {
             | CloneFlags::CLONE_SETTLS
             | CloneFlags::CLONE_PARENT_SETTID
             | CloneFlags::CLONE_CHILD_SETTID
-            | CloneFlags::CLONE_CHILD_CLEARTID;
+            | CloneFlags::CLONE_CHILD_CLEARTID
+            | CloneFlags::CLONE_VFORK
+            | CloneFlags::CLONE_NEWUSER;
         let unsupported_flags = *self - supported_flags;
         if !unsupported_flags.is_empty() {
             panic!("contains unsupported clone flags: {:?}", unsupported_flags);
         }
+        // A new user namespace cannot be shared with the threads or the file system
+        // information of the parent.
+        if self.contains(CloneFlags::CLONE_NEWUSER)
+            && self.intersects(CloneFlags::CLONE_THREAD | CloneFlags::CLONE_FS)
+        {
+            return_errno_with_message!(Errno::EINVAL, "invalid flags with CLONE_NEWUSER");
+        }
         Ok(())
     }
 }
 
+/// The number of threads and processes created by cloning since boot.
+static NR_FORKS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of threads and processes created by cloning since boot.
+pub fn nr_forks() -> u64 {
+    NR_FORKS.load(Ordering::Relaxed)
+}
+
 /// Clone a child thread or child process.
 ///
 /// FIXME: currently, the child process or thread will be scheduled to run at once,
 /// but this may not be the expected bahavior.
 pub fn clone_child(parent_context: &UserContext, clone_args: CloneArgs) -> Result<Tid> {
     clone_args.clone_flags.check_unsupported_flags()?;
-    if clone_args.clone_flags.contains(CloneFlags::CLONE_THREAD) {
+    let child_tid = if clone_args.clone_flags.contains(CloneFlags::CLONE_THREAD) {
         let child_thread = clone_child_thread(parent_context, clone_args)?;
         child_thread.run();
 
-        let child_tid = child_thread.tid();
-        Ok(child_tid)
+        child_thread.tid()
     } else {
+        let is_vfork = clone_args.clone_flags.contains(CloneFlags::CLONE_VFORK);
         let child_process = clone_child_process(parent_context, clone_args)?;
+        if is_vfork {
+            child_process.set_vfork_child();
+        }
         child_process.run();
 
-        let child_pid = child_process.pid();
-        Ok(child_pid)
-    }
+        // The parent is suspended until the vfork child execs or exits.
+        if is_vfork {
+            child_process.wait_for_vfork_child();
+        }
+
+        child_process.pid()
+    };
+
+    NR_FORKS.fetch_add(1, Ordering::Relaxed);
+    Ok(child_tid)
 }
 
 fn clone_child_thread(parent_context: &UserContext, clone_args: CloneArgs) -> Result<Arc<Thread>> {
@@ -198,7 +234,7 @@ fn clone_child_thread(parent_context: &UserContext, clone_args: CloneArgs) -> Re
     clone_parent_settid(child_tid, clone_args.parent_tidptr, clone_flags)?;
     clone_child_cleartid(child_posix_thread, clone_args.child_tidptr, clone_flags)?;
     clone_child_settid(
-        child_root_vmar,
+        &child_root_vmar,
         child_tid,
         clone_args.child_tidptr,
         clone_flags,
@@ -214,10 +250,14 @@ fn clone_child_process(
     let parent = Arc::downgrade(&current);
     let clone_flags = clone_args.clone_flags;
 
+    // Reap the exited children that are reaped automatically, so they do not pile up if the
+    // process never waits.
+    reap_auto_reaped_children(&current);
+
     // clone vm
     let child_process_vm = {
         let parent_process_vm = current.vm();
-        clone_vm(parent_process_vm, clone_flags)?
+        clone_vm(&parent_process_vm, clone_flags)?
     };
 
     // clone user space
@@ -243,10 +283,7 @@ fn clone_child_process(
     let child_fs = clone_fs(current.fs(), clone_flags);
 
     // clone umask
-    let child_umask = {
-        let parent_umask = current.umask().read().get();
-        Arc::new(RwLock::new(FileCreationMask::new(parent_umask)))
-    };
+    let child_umask = clone_umask(current.umask(), clone_flags);
 
     // clone sig dispositions
     let child_sig_dispositions = clone_sighand(current.sig_dispositions(), clone_flags);
@@ -265,6 +302,12 @@ fn clone_child_process(
     // inherit parent's nice value
     let child_nice = current.nice().load(Ordering::Relaxed);
 
+    // inherit parent's OOM score adjustment
+    let child_oom_score_adj = current.oom_score_adj().load(Ordering::Relaxed);
+
+    // inherit parent's personality
+    let child_personality = current.personality().load(Ordering::Relaxed);
+
     let child_tid = allocate_tid();
 
     let child = {
@@ -276,6 +319,11 @@ fn clone_child_process(
                 let credentials = credentials();
                 Credentials::new_from(&credentials)
             };
+            // The creator of a user namespace has all capabilities in it.
+            if clone_flags.contains(CloneFlags::CLONE_NEWUSER) {
+                let user_ns = credentials.user_ns().new_child(credentials.euid())?;
+                credentials.enter_user_ns(user_ns);
+            }
 
             PosixThreadBuilder::new(child_tid, child_user_space, credentials)
                 .thread_name(Some(child_thread_name))
@@ -292,7 +340,9 @@ fn clone_child_process(
             .fs(child_fs)
             .umask(child_umask)
             .sig_dispositions(child_sig_dispositions)
-            .nice(child_nice);
+            .nice(child_nice)
+            .oom_score_adj(child_oom_score_adj)
+            .personality(child_personality);
 
         process_builder.build()?
     };
@@ -305,7 +355,7 @@ fn clone_child_process(
 
     let child_root_vmar = child.root_vmar();
     clone_child_settid(
-        child_root_vmar,
+        &child_root_vmar,
         child_tid,
         clone_args.child_tidptr,
         clone_flags,
@@ -352,13 +402,16 @@ fn clone_parent_settid(
     Ok(())
 }
 
-/// Clone child process vm. If CLONE_VM is set, both threads share the same root vmar.
+/// Clone child process vm. If CLONE_VM is set, both processes share the same process vm.
 /// Otherwise, fork a new copy-on-write vmar.
-fn clone_vm(parent_process_vm: &ProcessVm, clone_flags: CloneFlags) -> Result<ProcessVm> {
+///
+/// A vfork child also shares the process vm if CLONE_VM is set. It gets a new process vm
+/// when it execs, so the memory of its parent is left intact.
+fn clone_vm(parent_process_vm: &Arc<ProcessVm>, clone_flags: CloneFlags) -> Result<Arc<ProcessVm>> {
     if clone_flags.contains(CloneFlags::CLONE_VM) {
         Ok(parent_process_vm.clone())
     } else {
-        ProcessVm::fork_from(parent_process_vm)
+        Ok(Arc::new(ProcessVm::fork_from(parent_process_vm)?))
     }
 }
 
@@ -373,8 +426,10 @@ fn clone_cpu_context(
     // The return value of child thread is zero
     child_context.set_syscall_ret(0);
 
-    if clone_flags.contains(CloneFlags::CLONE_VM) {
+    if clone_flags.contains(CloneFlags::CLONE_VM) && !clone_flags.contains(CloneFlags::CLONE_VFORK)
+    {
         // if parent and child shares the same address space, a new stack must be specified.
+        // A vfork child can use the stack of its parent since the parent is suspended.
         debug_assert!(new_sp != 0);
     }
     if new_sp != 0 {
@@ -405,6 +460,20 @@ fn clone_fs(
     }
 }
 
+/// Clone the umask. Like the other fs info, the umask is shared if CLONE_FS is set.
+/// Otherwise, the child gets an independent copy of the parent's umask.
+fn clone_umask(
+    parent_umask: &Arc<RwLock<FileCreationMask>>,
+    clone_flags: CloneFlags,
+) -> Arc<RwLock<FileCreationMask>> {
+    if clone_flags.contains(CloneFlags::CLONE_FS) {
+        parent_umask.clone()
+    } else {
+        let parent_umask = parent_umask.read().get();
+        Arc::new(RwLock::new(FileCreationMask::new(parent_umask)))
+    }
+}
+
 fn clone_files(
     parent_file_table: &Arc<Mutex<FileTable>>,
     clone_flags: CloneFlags,
@@ -58,6 +58,18 @@ impl CapSet {
         self.bits() as u32
     }
 
+    /// The capabilities related to the file system, which follow the file system user id.
+    pub const FS_SET: Self = Self::from_bits_truncate(
+        Self::CHOWN.bits()
+            | Self::DAC_OVERRIDE.bits()
+            | Self::DAC_READ_SEARCH.bits()
+            | Self::FOWNER.bits()
+            | Self::FSETID.bits()
+            | Self::LINUX_IMMUTABLE.bits()
+            | Self::MKNOD.bits()
+            | Self::MAC_OVERRIDE.bits(),
+    );
+
     /// Creates a new `CapSet` with the `SYS_ADMIN` capability set, typically for a root user.
     pub const fn new_root() -> Self {
         CapSet::SYS_ADMIN
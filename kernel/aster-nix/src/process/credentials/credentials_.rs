@@ -2,7 +2,7 @@
 
 use ostd::sync::{RwLockReadGuard, RwLockWriteGuard};
 
-use super::{group::AtomicGid, user::AtomicUid, Gid, Uid};
+use super::{group::AtomicGid, user::AtomicUid, user_namespace::UserNamespace, Gid, Uid};
 use crate::{
     prelude::*,
     process::credentials::capabilities::{AtomicCapSet, CapSet},
@@ -44,6 +44,9 @@ pub(super) struct Credentials_ {
 
     /// Capability that we can actually use
     effective_capset: AtomicCapSet,
+
+    /// The user namespace that maps the user and group IDs.
+    user_ns: RwLock<Arc<UserNamespace>>,
 }
 
 impl Credentials_ {
@@ -65,11 +68,17 @@ impl Credentials_ {
             inheritable_capset: AtomicCapSet::new(capset),
             permitted_capset: AtomicCapSet::new(capset),
             effective_capset: AtomicCapSet::new(capset),
+            user_ns: RwLock::new(UserNamespace::get_init_singleton().clone()),
         }
     }
 
     fn is_privileged(&self) -> bool {
-        self.euid.is_root()
+        self.is_root(self.euid())
+    }
+
+    /// Returns whether `uid` is the root user of the user namespace.
+    fn is_root(&self, uid: Uid) -> bool {
+        self.user_ns.read().is_root_uid(uid)
     }
 
     //  ******* Uid methods *******
@@ -91,6 +100,8 @@ impl Credentials_ {
     }
 
     pub(super) fn set_uid(&self, uid: Uid) {
+        let old_uids = self.uids();
+
         if self.is_privileged() {
             self.ruid.set(uid);
             self.euid.set(uid);
@@ -98,11 +109,17 @@ impl Credentials_ {
         } else {
             self.euid.set(uid);
         }
+        // Like Linux, `setuid` also sets the file system user id.
+        self.fsuid.set(self.euid());
+
+        self.update_capsets_for_uids(old_uids);
     }
 
     pub(super) fn set_reuid(&self, ruid: Option<Uid>, euid: Option<Uid>) -> Result<()> {
         self.check_uid_perm(ruid.as_ref(), euid.as_ref(), None, false)?;
 
+        let old_uids = self.uids();
+
         let should_set_suid = ruid.is_some() || euid.is_some_and(|euid| euid != self.ruid());
 
         self.set_resuid_unchecked(ruid, euid, None);
@@ -116,6 +133,8 @@ impl Credentials_ {
         // the same as `euid`, but `setreuid` does not mention the `fsuid` should be set.
         self.fsuid.set(self.euid());
 
+        self.update_capsets_for_uids(old_uids);
+
         Ok(())
     }
 
@@ -127,10 +146,14 @@ impl Credentials_ {
     ) -> Result<()> {
         self.check_uid_perm(ruid.as_ref(), euid.as_ref(), suid.as_ref(), true)?;
 
+        let old_uids = self.uids();
+
         self.set_resuid_unchecked(ruid, euid, suid);
 
         self.fsuid.set(self.euid());
 
+        self.update_capsets_for_uids(old_uids);
+
         Ok(())
     }
 
@@ -141,19 +164,20 @@ impl Credentials_ {
             return Ok(old_fsuid);
         };
 
-        if self.is_privileged() {
-            self.fsuid.set(fsuid);
-            return Ok(old_fsuid);
-        }
-
-        if fsuid != self.ruid() && fsuid != self.euid() && fsuid != self.suid() {
+        if !self.is_privileged()
+            && fsuid != self.ruid()
+            && fsuid != self.euid()
+            && fsuid != self.suid()
+        {
             return_errno_with_message!(
                 Errno::EPERM,
                 "fsuid can only be one of old ruid, old euid and old suid."
             )
         }
 
+        let old_uids = self.uids();
         self.fsuid.set(fsuid);
+        self.update_capsets_for_uids(old_uids);
 
         Ok(old_fsuid)
     }
@@ -215,6 +239,84 @@ impl Credentials_ {
         Ok(())
     }
 
+    /// Returns the real, effective, saved-set and file system user ids.
+    fn uids(&self) -> [Uid; 4] {
+        [self.ruid(), self.euid(), self.suid(), self.fsuid()]
+    }
+
+    /// Updates the capabilities after the user ids are changed from `old_uids`.
+    ///
+    /// Like Linux, the capabilities follow whether the user ids are the root user of the
+    /// user namespace:
+    /// - If none of the user ids is root any more, all capabilities are dropped;
+    /// - If the effective user id is no longer root, the effective capabilities are dropped,
+    ///   and they are restored from the permitted ones once the effective user id is root again;
+    /// - The same applies to the file system capabilities and the file system user id.
+    fn update_capsets_for_uids(&self, old_uids: [Uid; 4]) {
+        let [old_ruid, old_euid, old_suid, old_fsuid] = old_uids;
+
+        if (self.is_root(old_ruid) || self.is_root(old_euid) || self.is_root(old_suid))
+            && !self.is_root(self.ruid())
+            && !self.is_root(self.euid())
+            && !self.is_root(self.suid())
+        {
+            self.permitted_capset.set(CapSet::empty());
+            self.effective_capset.set(CapSet::empty());
+        }
+
+        if self.is_root(old_euid) && !self.is_root(self.euid()) {
+            self.effective_capset.set(CapSet::empty());
+        } else if !self.is_root(old_euid) && self.is_root(self.euid()) {
+            self.effective_capset.set(self.permitted_capset());
+        }
+
+        if self.is_root(old_fsuid) && !self.is_root(self.fsuid()) {
+            self.effective_capset
+                .set(self.effective_capset() - CapSet::FS_SET);
+        } else if !self.is_root(old_fsuid) && self.is_root(self.fsuid()) {
+            self.effective_capset
+                .set(self.effective_capset() | (self.permitted_capset() & CapSet::FS_SET));
+        }
+    }
+
+    /// Updates the capabilities when executing a new program.
+    ///
+    /// Like Linux, a program executed by the root user is granted all capabilities.
+    /// Since file capabilities are not supported, a program executed by other users
+    /// is granted no capability.
+    pub(super) fn update_capsets_for_exec(&self) {
+        let (permitted_capset, effective_capset) = self.capsets_for_exec(self.euid());
+        self.permitted_capset.set(permitted_capset);
+        self.effective_capset.set(effective_capset);
+    }
+
+    /// Returns whether executing a new program with the effective user id `euid` grants
+    /// capabilities beyond those of the real user.
+    ///
+    /// Like Linux, this is the case if the real user is not root, and the program either
+    /// runs with effective capabilities or gains permitted ones.
+    pub(super) fn exec_gains_capsets(&self, euid: Uid) -> bool {
+        let (permitted_capset, effective_capset) = self.capsets_for_exec(euid);
+        !self.is_root(self.ruid())
+            && (!effective_capset.is_empty() || !self.permitted_capset().contains(permitted_capset))
+    }
+
+    /// Returns the permitted and effective capabilities of a new program executed with
+    /// the effective user id `euid`.
+    fn capsets_for_exec(&self, euid: Uid) -> (CapSet, CapSet) {
+        let permitted_capset = if self.is_root(self.ruid()) || self.is_root(euid) {
+            CapSet::all()
+        } else {
+            CapSet::empty()
+        };
+        let effective_capset = if self.is_root(euid) {
+            permitted_capset
+        } else {
+            CapSet::empty()
+        };
+        (permitted_capset, effective_capset)
+    }
+
     fn set_resuid_unchecked(&self, ruid: Option<Uid>, euid: Option<Uid>, suid: Option<Uid>) {
         if let Some(ruid) = ruid {
             self.ruid.set(ruid);
@@ -418,6 +520,20 @@ impl Credentials_ {
     pub(super) fn set_effective_capset(&self, effective_capset: CapSet) {
         self.effective_capset.set(effective_capset);
     }
+
+    //  ******* User namespace methods *******
+
+    pub(super) fn user_ns(&self) -> Arc<UserNamespace> {
+        self.user_ns.read().clone()
+    }
+
+    /// Enters `user_ns`, in which all capabilities are granted as in Linux.
+    pub(super) fn enter_user_ns(&self, user_ns: Arc<UserNamespace>) {
+        *self.user_ns.write() = user_ns;
+        self.inheritable_capset.set(CapSet::empty());
+        self.permitted_capset.set(CapSet::all());
+        self.effective_capset.set(CapSet::all());
+    }
 }
 
 impl Clone for Credentials_ {
@@ -435,6 +551,7 @@ impl Clone for Credentials_ {
             inheritable_capset: self.inheritable_capset.clone(),
             permitted_capset: self.permitted_capset.clone(),
             effective_capset: self.effective_capset.clone(),
+            user_ns: RwLock::new(self.user_ns.read().clone()),
         }
     }
 }
@@ -6,11 +6,13 @@ mod credentials_;
 mod group;
 mod static_cap;
 mod user;
+mod user_namespace;
 
 use aster_rights::{FullOp, ReadOp, WriteOp};
 use credentials_::Credentials_;
 pub use group::Gid;
 pub use user::Uid;
+pub use user_namespace::UserNamespace;
 
 use super::posix_thread::PosixThreadExt;
 use crate::prelude::*;
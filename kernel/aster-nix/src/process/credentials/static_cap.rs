@@ -6,7 +6,10 @@ use aster_rights::{Dup, Read, TRights, Write};
 use aster_rights_proc::require;
 use ostd::sync::{RwLockReadGuard, RwLockWriteGuard};
 
-use super::{capabilities::CapSet, credentials_::Credentials_, Credentials, Gid, Uid};
+use super::{
+    capabilities::CapSet, credentials_::Credentials_, user_namespace::UserNamespace, Credentials,
+    Gid, Uid,
+};
 use crate::prelude::*;
 
 impl<R: TRights> Credentials<R> {
@@ -300,4 +303,52 @@ impl<R: TRights> Credentials<R> {
     pub fn set_effective_capset(&self, effective_capset: CapSet) {
         self.0.set_effective_capset(effective_capset);
     }
+
+    /// Updates the capabilities when executing a new program, after the user ids
+    /// are updated according to the program.
+    ///
+    /// This method requires the `Write` right.
+    #[require(R > Write)]
+    pub fn update_capsets_for_exec(&self) {
+        self.0.update_capsets_for_exec();
+    }
+
+    /// Returns whether executing a new program with the effective user id `euid` grants
+    /// capabilities beyond those of the real user.
+    ///
+    /// This method requires the `Read` right.
+    #[require(R > Read)]
+    pub fn exec_gains_capsets(&self, euid: Uid) -> bool {
+        self.0.exec_gains_capsets(euid)
+    }
+
+    // *********** User namespace methods **********
+
+    /// Gets the user namespace.
+    ///
+    /// This method requires the `Read` right.
+    #[require(R > Read)]
+    pub fn user_ns(&self) -> Arc<UserNamespace> {
+        self.0.user_ns()
+    }
+
+    /// Enters the user namespace, which grants all capabilities in it.
+    ///
+    /// This method requires the `Write` right.
+    #[require(R > Write)]
+    pub fn enter_user_ns(&self, user_ns: Arc<UserNamespace>) {
+        self.0.enter_user_ns(user_ns);
+    }
+
+    /// Returns whether the effective capabilities contain `cap` and it takes effect on
+    /// the whole system.
+    ///
+    /// The capabilities gained in a child user namespace only take effect on the objects
+    /// of that namespace, so they are not counted here.
+    ///
+    /// This method requires the `Read` right.
+    #[require(R > Read)]
+    pub fn capable(&self, cap: CapSet) -> bool {
+        self.0.user_ns().is_init() && self.0.effective_capset().contains(cap)
+    }
 }
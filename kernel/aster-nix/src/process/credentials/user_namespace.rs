@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! User namespaces.
+//!
+//! A user namespace maps the user and group IDs seen inside the namespace to the IDs
+//! used by the kernel, which are the IDs of the initial user namespace. The maps of a
+//! new namespace are empty until they are written once through `/proc/[pid]/uid_map`
+//! and `/proc/[pid]/gid_map`.
+
+use alloc::format;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use spin::Once;
+
+use super::{capabilities::CapSet, credentials, Gid, Uid};
+use crate::prelude::*;
+
+/// The maximum nesting depth of user namespaces.
+const MAX_USER_NS_LEVEL: usize = 32;
+
+/// The maximum number of extents in an ID map.
+const MAX_EXTENTS: usize = 340;
+
+/// The ID reported for the IDs that are not mapped in a user namespace.
+const OVERFLOW_ID: u32 = 65534;
+
+/// The inode number of the initial user namespace, which is the same as Linux.
+const INIT_USER_NS_INO: u64 = 0xEFFF_FFFD;
+
+/// The next inode number to allocate for a new namespace.
+static NEXT_NS_INO: AtomicU64 = AtomicU64::new(0xF000_0000);
+
+lazy_static! {
+    static ref INIT_USER_NS: Arc<UserNamespace> = Arc::new(UserNamespace {
+        parent: None,
+        level: 0,
+        ino: INIT_USER_NS_INO,
+        owner: Uid::new_root(),
+        uid_map: IdMap::new_identity(),
+        gid_map: IdMap::new_identity(),
+    });
+}
+
+/// A user namespace.
+pub struct UserNamespace {
+    parent: Option<Arc<UserNamespace>>,
+    level: usize,
+    /// The inode number that identifies the namespace in `/proc/[pid]/ns/user`.
+    ino: u64,
+    /// The effective user ID of the creator.
+    owner: Uid,
+    uid_map: IdMap,
+    gid_map: IdMap,
+}
+
+impl UserNamespace {
+    /// Gets the initial user namespace.
+    pub fn get_init_singleton() -> &'static Arc<UserNamespace> {
+        &INIT_USER_NS
+    }
+
+    /// Creates a child user namespace, whose creator has the effective user ID `owner`.
+    pub fn new_child(self: &Arc<Self>, owner: Uid) -> Result<Arc<Self>> {
+        if self.level + 1 > MAX_USER_NS_LEVEL {
+            return_errno_with_message!(Errno::EUSERS, "too many nested user namespaces");
+        }
+
+        Ok(Arc::new(Self {
+            parent: Some(self.clone()),
+            level: self.level + 1,
+            ino: NEXT_NS_INO.fetch_add(1, Ordering::Relaxed),
+            owner,
+            uid_map: IdMap::new_unset(),
+            gid_map: IdMap::new_unset(),
+        }))
+    }
+
+    /// Returns the inode number that identifies the namespace.
+    pub fn ino(&self) -> u64 {
+        self.ino
+    }
+
+    /// Returns whether this is the initial user namespace.
+    pub fn is_init(&self) -> bool {
+        self.parent.is_none()
+    }
+
+    /// Returns whether this namespace is `ancestor` or is nested in it.
+    pub fn is_same_or_descendant_of(&self, ancestor: &UserNamespace) -> bool {
+        let mut user_ns = self;
+        while user_ns.level > ancestor.level {
+            user_ns = user_ns.parent.as_ref().unwrap();
+        }
+        core::ptr::eq(user_ns, ancestor)
+    }
+
+    /// Maps a user ID in this namespace to the kernel user ID.
+    ///
+    /// This method fails with `EINVAL` if the ID is not mapped.
+    pub fn uid_to_kernel(&self, uid: Uid) -> Result<Uid> {
+        self.uid_map
+            .map_down(uid.as_u32())
+            .map(Uid::new)
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "the user ID is not mapped"))
+    }
+
+    /// Maps a kernel user ID to the user ID in this namespace.
+    ///
+    /// The overflow user ID is returned if the ID is not mapped.
+    pub fn uid_from_kernel(&self, uid: Uid) -> Uid {
+        Uid::new(self.uid_map.map_up(uid.as_u32()).unwrap_or(OVERFLOW_ID))
+    }
+
+    /// Maps a group ID in this namespace to the kernel group ID.
+    ///
+    /// This method fails with `EINVAL` if the ID is not mapped.
+    pub fn gid_to_kernel(&self, gid: Gid) -> Result<Gid> {
+        self.gid_map
+            .map_down(gid.as_u32())
+            .map(Gid::new)
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "the group ID is not mapped"))
+    }
+
+    /// Maps a kernel group ID to the group ID in this namespace.
+    ///
+    /// The overflow group ID is returned if the ID is not mapped.
+    pub fn gid_from_kernel(&self, gid: Gid) -> Gid {
+        Gid::new(self.gid_map.map_up(gid.as_u32()).unwrap_or(OVERFLOW_ID))
+    }
+
+    /// Returns whether the kernel user ID is the root user of this namespace.
+    pub fn is_root_uid(&self, uid: Uid) -> bool {
+        self.uid_map.map_up(uid.as_u32()) == Some(0)
+    }
+
+    /// Returns whether the kernel IDs `uid` and `gid` are both mapped in this namespace.
+    pub fn is_mapped(&self, uid: Uid, gid: Gid) -> bool {
+        self.uid_map.map_up(uid.as_u32()).is_some() && self.gid_map.map_up(gid.as_u32()).is_some()
+    }
+
+    /// Returns the capabilities in `capset` that take effect on an object owned by the
+    /// kernel IDs `uid` and `gid`.
+    ///
+    /// Like Linux, the capabilities in a user namespace only apply to the objects whose
+    /// owner and group are both mapped in the namespace.
+    pub fn capset_over(&self, capset: CapSet, uid: Uid, gid: Gid) -> CapSet {
+        if self.is_mapped(uid, gid) {
+            capset
+        } else {
+            CapSet::empty()
+        }
+    }
+
+    /// Returns the content of the `uid_map` file.
+    pub fn uid_map_content(&self) -> String {
+        self.map_content(IdType::Uid)
+    }
+
+    /// Returns the content of the `gid_map` file.
+    pub fn gid_map_content(&self) -> String {
+        self.map_content(IdType::Gid)
+    }
+
+    /// Installs the user ID map from the content written to the `uid_map` file.
+    pub fn write_uid_map(&self, content: &str) -> Result<()> {
+        self.write_map(IdType::Uid, content)
+    }
+
+    /// Installs the group ID map from the content written to the `gid_map` file.
+    pub fn write_gid_map(&self, content: &str) -> Result<()> {
+        self.write_map(IdType::Gid, content)
+    }
+
+    fn id_map(&self, id_type: IdType) -> &IdMap {
+        match id_type {
+            IdType::Uid => &self.uid_map,
+            IdType::Gid => &self.gid_map,
+        }
+    }
+
+    /// Returns the content of an ID map, in which the lower IDs are shown as the IDs
+    /// in the parent namespace.
+    fn map_content(&self, id_type: IdType) -> String {
+        let mut content = String::new();
+        for extent in self.id_map(id_type).extents() {
+            let lower_first = match self.parent.as_ref() {
+                Some(parent) => parent.id_map(id_type).map_up(extent.lower_first),
+                None => Some(extent.lower_first),
+            };
+            // The lower IDs are always mapped in the parent namespace when written.
+            let lower_first = lower_first.unwrap_or(OVERFLOW_ID);
+            content += &format!(
+                "{:>10} {:>10} {:>10}\n",
+                extent.first, lower_first, extent.count
+            );
+        }
+        content
+    }
+
+    fn write_map(&self, id_type: IdType, content: &str) -> Result<()> {
+        let Some(parent) = self.parent.as_ref() else {
+            return_errno_with_message!(Errno::EPERM, "the maps of the initial namespace are fixed");
+        };
+        let id_map = self.id_map(id_type);
+        if id_map.is_set() {
+            return_errno_with_message!(Errno::EPERM, "the map can only be written once");
+        }
+
+        let extents = parse_extents(content)?;
+        self.check_map_permission(id_type, &extents)?;
+
+        // The lower IDs are written as the IDs in the parent namespace. Each range must be
+        // mapped in the parent namespace as a whole.
+        let parent_map = parent.id_map(id_type);
+        let extents = extents
+            .into_iter()
+            .map(|extent| {
+                let lower_first = parent_map
+                    .map_range_down(extent.lower_first, extent.count)
+                    .ok_or_else(|| {
+                        Error::with_message(
+                            Errno::EPERM,
+                            "the range is not mapped in the parent namespace",
+                        )
+                    })?;
+                Ok(IdMapExtent {
+                    lower_first,
+                    ..extent
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        id_map.set(extents)
+    }
+
+    /// Checks whether the current thread can install `extents` as an ID map.
+    ///
+    /// A thread with `CAP_SETUID` (or `CAP_SETGID` for the group IDs) in the parent
+    /// namespace can install any map. Otherwise, only the creator of the namespace can map
+    /// a single ID to its own effective ID.
+    fn check_map_permission(&self, id_type: IdType, extents: &[IdMapExtent]) -> Result<()> {
+        let credentials = credentials();
+        let (required_cap, effective_id) = match id_type {
+            IdType::Uid => (CapSet::SETUID, credentials.euid().as_u32()),
+            IdType::Gid => (CapSet::SETGID, credentials.egid().as_u32()),
+        };
+        let parent = self.parent.as_ref().unwrap();
+        if credentials.effective_capset().contains(required_cap)
+            && parent.is_same_or_descendant_of(&credentials.user_ns())
+        {
+            return Ok(());
+        }
+
+        let is_own_id = |lower_first: u32| {
+            parent
+                .id_map(id_type)
+                .map_down(lower_first)
+                .is_some_and(|id| id == effective_id)
+        };
+        if let [extent] = extents
+            && extent.count == 1
+            && is_own_id(extent.lower_first)
+            && credentials.euid() == self.owner
+        {
+            return Ok(());
+        }
+
+        return_errno_with_message!(Errno::EPERM, "the map is not allowed to be written");
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum IdType {
+    Uid,
+    Gid,
+}
+
+/// A range of contiguous IDs in an ID map.
+#[derive(Debug, Clone, Copy)]
+struct IdMapExtent {
+    /// The first ID inside the namespace.
+    first: u32,
+    /// The first ID that `first` is mapped to.
+    lower_first: u32,
+    count: u32,
+}
+
+impl IdMapExtent {
+    fn map_down(&self, id: u32) -> Option<u32> {
+        let offset = id.checked_sub(self.first)?;
+        (offset < self.count).then(|| self.lower_first + offset)
+    }
+
+    fn map_up(&self, id: u32) -> Option<u32> {
+        let offset = id.checked_sub(self.lower_first)?;
+        (offset < self.count).then(|| self.first + offset)
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        let overlaps = |first: u32, other_first: u32| {
+            let last = first + (self.count - 1);
+            let other_last = other_first + (other.count - 1);
+            first <= other_last && other_first <= last
+        };
+        overlaps(self.first, other.first) || overlaps(self.lower_first, other.lower_first)
+    }
+}
+
+/// The map of the user IDs or the group IDs, which can only be set once.
+struct IdMap {
+    extents: Once<Vec<IdMapExtent>>,
+}
+
+impl IdMap {
+    fn new_unset() -> Self {
+        Self {
+            extents: Once::new(),
+        }
+    }
+
+    fn new_identity() -> Self {
+        let identity = IdMapExtent {
+            first: 0,
+            lower_first: 0,
+            count: u32::MAX,
+        };
+        Self {
+            extents: Once::initialized(vec![identity]),
+        }
+    }
+
+    fn is_set(&self) -> bool {
+        self.extents.is_completed()
+    }
+
+    fn extents(&self) -> &[IdMapExtent] {
+        self.extents.get().map_or(&[], |extents| extents.as_slice())
+    }
+
+    fn set(&self, extents: Vec<IdMapExtent>) -> Result<()> {
+        let mut is_installed = false;
+        self.extents.call_once(|| {
+            is_installed = true;
+            extents
+        });
+        if !is_installed {
+            return_errno_with_message!(Errno::EPERM, "the map can only be written once");
+        }
+        Ok(())
+    }
+
+    fn map_down(&self, id: u32) -> Option<u32> {
+        self.extents().iter().find_map(|extent| extent.map_down(id))
+    }
+
+    fn map_up(&self, id: u32) -> Option<u32> {
+        self.extents().iter().find_map(|extent| extent.map_up(id))
+    }
+
+    /// Maps a range of IDs, which must lie in a single extent.
+    fn map_range_down(&self, first: u32, count: u32) -> Option<u32> {
+        let last = first + (count - 1);
+        self.extents().iter().find_map(|extent| {
+            let lower_first = extent.map_down(first)?;
+            extent.map_down(last)?;
+            Some(lower_first)
+        })
+    }
+}
+
+/// Parses the lines of `first lower_first count` written to an ID map file.
+fn parse_extents(content: &str) -> Result<Vec<IdMapExtent>> {
+    let invalid_map = || Error::with_message(Errno::EINVAL, "the map is invalid");
+
+    let content = content.strip_suffix('\n').unwrap_or(content);
+    let mut extents: Vec<IdMapExtent> = Vec::new();
+    for line in content.split('\n') {
+        let mut ids = line.split_whitespace().map(|id| id.parse::<u32>());
+        let (Some(Ok(first)), Some(Ok(lower_first)), Some(Ok(count)), None) =
+            (ids.next(), ids.next(), ids.next(), ids.next())
+        else {
+            return Err(invalid_map());
+        };
+
+        // The ranges must be non-empty and must not wrap around.
+        if count == 0
+            || first.checked_add(count - 1).is_none()
+            || lower_first.checked_add(count - 1).is_none()
+        {
+            return Err(invalid_map());
+        }
+
+        let extent = IdMapExtent {
+            first,
+            lower_first,
+            count,
+        };
+        if extents.iter().any(|other| other.overlaps(&extent)) {
+            return_errno_with_message!(Errno::EINVAL, "the ranges of the map overlap");
+        }
+        if extents.len() == MAX_EXTENTS {
+            return_errno_with_message!(Errno::EINVAL, "the map has too many lines");
+        }
+        extents.push(extent);
+    }
+
+    Ok(extents)
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use super::{process_table, Pid, Process, TermStatus};
+use super::{process_table, Pid, Process, TermStatus, Terminal};
 use crate::{
     prelude::*,
     process::{
@@ -17,6 +17,9 @@ pub fn do_exit_group(term_status: TermStatus) {
     }
     current.set_zombie(term_status);
 
+    // Resume the parent if the process is a vfork child
+    current.reset_vfork_child();
+
     // Exit all threads
     let threads = current.threads().lock().clone();
     for thread in threads {
@@ -44,14 +47,31 @@ pub fn do_exit_group(term_status: TermStatus) {
         let _ = file.clean_for_close();
     }
 
-    // Move children to the init process
+    // Release the controlling terminal, which sends `SIGHUP` to the foreground process group
+    if current.is_session_leader()
+        && let Some(terminal) = current.session().unwrap().terminal()
+        && let Err(e) = terminal.release_current_session()
+    {
+        debug!("Ignore error when release terminal: {:?}", e);
+    }
+
+    // Move children to the nearest child subreaper or the init process
     if !is_init_process(&current) {
-        if let Some(init_process) = get_init_process() {
-            let mut init_children = init_process.children().lock();
+        if let Some(reaper) = find_child_reaper(&current) {
+            let mut has_zombie = false;
+            let mut reaper_children = reaper.children().lock();
             for (_, child_process) in current.children().lock().extract_if(|_, _| true) {
                 let mut parent = child_process.parent.lock();
-                init_children.insert(child_process.pid(), child_process.clone());
-                *parent = Arc::downgrade(&init_process);
+                reaper_children.insert(child_process.pid(), child_process.clone());
+                *parent = Arc::downgrade(&reaper);
+                has_zombie |= child_process.is_zombie();
+            }
+            drop(reaper_children);
+
+            // The new parent should be able to wait for the zombies.
+            if has_zombie {
+                reaper.enqueue_signal(KernelSignal::new(SIGCHLD));
+                reaper.children_pauser().resume_all();
             }
         }
     }
@@ -64,6 +84,21 @@ pub fn do_exit_group(term_status: TermStatus) {
     }
 }
 
+/// Finds the process that adopts the children of the exiting process.
+///
+/// Like Linux, this is the nearest ancestor that is a child subreaper and has not exited, or
+/// the init process if there is no such ancestor.
+fn find_child_reaper(process: &Process) -> Option<Arc<Process>> {
+    let mut ancestor = process.parent();
+    while let Some(process) = ancestor {
+        if process.is_child_subreaper() && !process.is_zombie() {
+            return Some(process);
+        }
+        ancestor = process.parent();
+    }
+    get_init_process()
+}
+
 const INIT_PROCESS_PID: Pid = 1;
 
 /// Gets the init process
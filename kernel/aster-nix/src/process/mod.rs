@@ -18,7 +18,7 @@ pub mod sync;
 mod term_status;
 mod wait;
 
-pub use clone::{clone_child, CloneArgs, CloneFlags};
+pub use clone::{clone_child, nr_forks, CloneArgs, CloneFlags};
 pub use credentials::{credentials, credentials_mut, Credentials, Gid, Uid};
 pub use exit::do_exit_group;
 pub use kill::{kill, kill_all, kill_group, tgkill};
@@ -27,9 +27,11 @@ pub use process::{
     Terminal,
 };
 pub use process_filter::ProcessFilter;
-pub use process_vm::{MAX_ARGV_NUMBER, MAX_ARG_LEN, MAX_ENVP_NUMBER, MAX_ENV_LEN};
-pub use program_loader::{check_executable_file, load_program_to_vm};
-pub use rlimit::ResourceType;
+pub use process_vm::{
+    aslr, ProcessVm, MAX_ARGS_SIZE, MAX_ARGV_NUMBER, MAX_ARG_LEN, MAX_ENVP_NUMBER, MAX_ENV_LEN,
+};
+pub use program_loader::{check_executable_file, load_program_to_vm, ProgramLoadOptions};
+pub use rlimit::{RLimit64, ResourceType};
 pub use term_status::TermStatus;
 pub use wait::{wait_child_exit, WaitOptions};
 
@@ -40,8 +40,9 @@ pub fn do_exit(thread: Arc<Thread>, term_status: TermStatus) -> Result<()> {
         thread_table::remove_thread(tid);
     }
 
-    if posix_thread.is_main_thread(tid) || posix_thread.is_last_thread() {
-        // exit current process.
+    // The process exits when all its threads exit. If the main thread exits first, the process
+    // is kept alive (and cannot be reaped by its parent) until the other threads exit.
+    if posix_thread.is_last_thread() {
         do_exit_group(term_status);
     }
 
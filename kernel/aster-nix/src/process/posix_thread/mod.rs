@@ -221,12 +221,6 @@ impl PosixThread {
         &self.robust_list
     }
 
-    fn is_main_thread(&self, tid: Tid) -> bool {
-        let process = self.process();
-        let pid = process.pid();
-        tid == pid
-    }
-
     fn is_last_thread(&self) -> bool {
         let process = self.process.upgrade().unwrap();
         let threads = process.threads().lock();
@@ -9,7 +9,11 @@ use super::{builder::PosixThreadBuilder, name::ThreadName, PosixThread};
 use crate::{
     fs::fs_resolver::{FsPath, FsResolver, AT_FDCWD},
     prelude::*,
-    process::{process_vm::ProcessVm, program_loader::load_program_to_vm, Credentials, Process},
+    process::{
+        process_vm::ProcessVm,
+        program_loader::{load_program_to_vm, ProgramLoadOptions},
+        Credentials, Process,
+    },
     thread::{Thread, Tid},
 };
 pub trait PosixThreadExt {
@@ -43,8 +47,14 @@ impl PosixThreadExt for Thread {
             let fs_path = FsPath::new(AT_FDCWD, executable_path)?;
             fs_resolver.lookup(&fs_path)?
         };
-        let (_, elf_load_info) =
-            load_program_to_vm(process_vm, elf_file, argv, envp, fs_resolver, 1)?;
+        let (_, elf_load_info) = load_program_to_vm(
+            process_vm,
+            elf_file,
+            argv,
+            envp,
+            fs_resolver,
+            ProgramLoadOptions::new(executable_path),
+        )?;
 
         let vm_space = process_vm.root_vmar().vm_space().clone();
         let mut cpu_ctx = UserContext::default();
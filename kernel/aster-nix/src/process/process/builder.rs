@@ -27,7 +27,7 @@ pub struct ProcessBuilder<'a> {
     main_thread_builder: Option<PosixThreadBuilder>,
     argv: Option<Vec<CString>>,
     envp: Option<Vec<CString>>,
-    process_vm: Option<ProcessVm>,
+    process_vm: Option<Arc<ProcessVm>>,
     file_table: Option<Arc<Mutex<FileTable>>>,
     fs: Option<Arc<RwMutex<FsResolver>>>,
     umask: Option<Arc<RwLock<FileCreationMask>>>,
@@ -35,6 +35,8 @@ pub struct ProcessBuilder<'a> {
     sig_dispositions: Option<Arc<Mutex<SigDispositions>>>,
     credentials: Option<Credentials>,
     nice: Option<Nice>,
+    oom_score_adj: Option<i32>,
+    personality: Option<u32>,
 }
 
 impl<'a> ProcessBuilder<'a> {
@@ -54,6 +56,8 @@ impl<'a> ProcessBuilder<'a> {
             sig_dispositions: None,
             credentials: None,
             nice: None,
+            oom_score_adj: None,
+            personality: None,
         }
     }
 
@@ -62,7 +66,7 @@ impl<'a> ProcessBuilder<'a> {
         self
     }
 
-    pub fn process_vm(&mut self, process_vm: ProcessVm) -> &mut Self {
+    pub fn process_vm(&mut self, process_vm: Arc<ProcessVm>) -> &mut Self {
         self.process_vm = Some(process_vm);
         self
     }
@@ -112,6 +116,16 @@ impl<'a> ProcessBuilder<'a> {
         self
     }
 
+    pub fn oom_score_adj(&mut self, oom_score_adj: i32) -> &mut Self {
+        self.oom_score_adj = Some(oom_score_adj);
+        self
+    }
+
+    pub fn personality(&mut self, personality: u32) -> &mut Self {
+        self.personality = Some(personality);
+        self
+    }
+
     fn check_build(&self) -> Result<()> {
         if self.main_thread_builder.is_some() {
             debug_assert!(self.parent.upgrade().is_some());
@@ -147,9 +161,13 @@ impl<'a> ProcessBuilder<'a> {
             sig_dispositions,
             credentials,
             nice,
+            oom_score_adj,
+            personality,
         } = self;
 
-        let process_vm = process_vm.or_else(|| Some(ProcessVm::alloc())).unwrap();
+        let process_vm = process_vm
+            .or_else(|| Some(Arc::new(ProcessVm::alloc())))
+            .unwrap();
 
         let file_table = file_table
             .or_else(|| Some(Arc::new(Mutex::new(FileTable::new_with_stdio()))))
@@ -173,6 +191,10 @@ impl<'a> ProcessBuilder<'a> {
 
         let nice = nice.or_else(|| Some(Nice::default())).unwrap();
 
+        let oom_score_adj = oom_score_adj.unwrap_or(0);
+
+        let personality = personality.unwrap_or(0);
+
         let process = {
             let threads = Vec::new();
             Process::new(
@@ -186,6 +208,8 @@ impl<'a> ProcessBuilder<'a> {
                 umask,
                 resource_limits,
                 nice,
+                oom_score_adj,
+                personality,
                 sig_dispositions,
             )
         };
@@ -197,7 +221,7 @@ impl<'a> ProcessBuilder<'a> {
             Thread::new_posix_thread_from_executable(
                 pid,
                 credentials.unwrap(),
-                process.vm(),
+                &process.vm(),
                 &process.fs().read(),
                 executable_path,
                 Arc::downgrade(&process),
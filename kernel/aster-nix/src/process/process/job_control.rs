@@ -91,6 +91,11 @@ impl JobControl {
             foreground.broadcast_signal(KernelSignal::new(SIGCONT));
         }
 
+        *self.foreground.lock() = Weak::new();
+        *self.session.lock() = Weak::new();
+
+        // Processes waiting to become the foreground can go ahead now.
+        self.pauser.resume_all();
         Ok(())
     }
 
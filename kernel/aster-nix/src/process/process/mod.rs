@@ -4,7 +4,7 @@ use self::timer_manager::PosixTimerManager;
 use super::{
     posix_thread::PosixThreadExt,
     process_table,
-    process_vm::{Heap, InitStackReader, ProcessVm},
+    process_vm::ProcessVm,
     rlimit::ResourceLimits,
     signal::{
         constants::SIGCHLD,
@@ -34,10 +34,13 @@ mod session;
 mod terminal;
 mod timer_manager;
 
+use core::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
+
 use aster_rights::Full;
 use atomic::Atomic;
 pub use builder::ProcessBuilder;
 pub use job_control::JobControl;
+use ostd::{sync::WaitQueue, task::current_task};
 pub use process_group::ProcessGroup;
 pub use session::Session;
 pub use terminal::Terminal;
@@ -60,11 +63,12 @@ pub struct Process {
     // Immutable Part
     pid: Pid,
 
-    process_vm: ProcessVm,
     /// Wait for child status changed
     children_pauser: Arc<Pauser>,
 
     // Mutable Part
+    /// The virtual memory, which is replaced when executing a new program.
+    process_vm: RwLock<Arc<ProcessVm>>,
     /// The executable path.
     executable_path: RwLock<String>,
     /// The threads
@@ -75,6 +79,8 @@ pub struct Process {
     pub(super) parent: Mutex<Weak<Process>>,
     /// Children processes
     children: Mutex<BTreeMap<Pid, Arc<Process>>>,
+    /// Whether the process adopts the orphaned descendants instead of the init process.
+    is_child_subreaper: AtomicBool,
     /// Process group
     pub(super) process_group: Mutex<Weak<ProcessGroup>>,
     /// File table
@@ -89,6 +95,10 @@ pub struct Process {
     /// According to POSIX.1, the nice value is a per-process attribute,
     /// the threads in a process should share a nice value.
     nice: Atomic<Nice>,
+    /// The adjustment of the OOM score, which is also shared by the threads in a process.
+    oom_score_adj: AtomicI32,
+    /// The execution domain of the process, which is also shared by the threads in a process.
+    personality: AtomicU32,
 
     // Signal
     /// Sig dispositions
@@ -101,6 +111,12 @@ pub struct Process {
 
     /// A manager that manages timer resources and utilities of the process.
     timer_manager: PosixTimerManager,
+
+    // Vfork
+    /// Whether the process is a vfork child, whose parent waits for it to exec or exit.
+    is_vfork_child: AtomicBool,
+    /// The wait queue of the parent of a vfork child.
+    vfork_wait_queue: WaitQueue,
 }
 
 impl Process {
@@ -110,7 +126,7 @@ impl Process {
         parent: Weak<Process>,
         threads: Vec<Arc<Thread>>,
         executable_path: String,
-        process_vm: ProcessVm,
+        process_vm: Arc<ProcessVm>,
 
         fs: Arc<RwMutex<FsResolver>>,
         file_table: Arc<Mutex<FileTable>>,
@@ -118,6 +134,8 @@ impl Process {
         umask: Arc<RwLock<FileCreationMask>>,
         resource_limits: ResourceLimits,
         nice: Nice,
+        oom_score_adj: i32,
+        personality: u32,
         sig_dispositions: Arc<Mutex<SigDispositions>>,
     ) -> Arc<Self> {
         let children_pauser = {
@@ -133,11 +151,12 @@ impl Process {
             pid,
             threads: Mutex::new(threads),
             executable_path: RwLock::new(executable_path),
-            process_vm,
+            process_vm: RwLock::new(process_vm),
             children_pauser,
             status: Mutex::new(ProcessStatus::Uninit),
             parent: Mutex::new(parent),
             children: Mutex::new(BTreeMap::new()),
+            is_child_subreaper: AtomicBool::new(false),
             process_group: Mutex::new(Weak::new()),
             file_table,
             fs,
@@ -146,8 +165,12 @@ impl Process {
             parent_death_signal: AtomicSigNum::new_empty(),
             resource_limits: Mutex::new(resource_limits),
             nice: Atomic::new(nice),
+            oom_score_adj: AtomicI32::new(oom_score_adj),
+            personality: AtomicU32::new(personality),
             timer_manager: PosixTimerManager::new(&prof_clock, process_ref),
             prof_clock,
+            is_vfork_child: AtomicBool::new(false),
+            vfork_wait_queue: WaitQueue::new(),
         })
     }
 
@@ -177,6 +200,8 @@ impl Process {
             let parent = Weak::new();
 
             let credentials = Credentials::new_root();
+            // The init process is executed by the root user, so it has all capabilities.
+            credentials.update_capsets_for_exec();
 
             let mut builder = ProcessBuilder::new(pid, executable_path, parent);
             builder.argv(argv).envp(envp).credentials(credentials);
@@ -254,6 +279,14 @@ impl Process {
         &self.nice
     }
 
+    pub fn oom_score_adj(&self) -> &AtomicI32 {
+        &self.oom_score_adj
+    }
+
+    pub fn personality(&self) -> &AtomicU32 {
+        &self.personality
+    }
+
     pub fn main_thread(&self) -> Option<Arc<Thread>> {
         self.threads
             .lock()
@@ -529,20 +562,32 @@ impl Process {
 
     // ************** Virtual Memory *************
 
-    pub fn vm(&self) -> &ProcessVm {
-        &self.process_vm
+    pub fn vm(&self) -> Arc<ProcessVm> {
+        self.process_vm.read().clone()
     }
 
-    pub fn root_vmar(&self) -> &Vmar<Full> {
-        self.process_vm.root_vmar()
+    pub fn root_vmar(&self) -> Vmar<Full> {
+        self.process_vm.read().root_vmar().dup().unwrap()
     }
 
-    pub fn heap(&self) -> &Heap {
-        self.process_vm.heap()
+    /// Replaces the virtual memory of the process with `process_vm`.
+    ///
+    /// The current thread switches to the new virtual memory at once. This method
+    /// should only be called by the process itself when executing a new program.
+    pub fn set_vm(&self, process_vm: ProcessVm) {
+        let mut old_process_vm = self.process_vm.write();
+        let vm_space = process_vm.root_vmar().vm_space().clone();
+        current_task()
+            .unwrap()
+            .user_space()
+            .unwrap()
+            .set_vm_space(vm_space);
+        *old_process_vm = Arc::new(process_vm);
     }
 
-    pub fn init_stack_reader(&self) -> InitStackReader {
-        self.process_vm.init_stack_reader()
+    /// Returns whether the virtual memory is shared with other processes.
+    pub(super) fn is_vm_shared(&self) -> bool {
+        Arc::strong_count(&*self.process_vm.read()) > 1
     }
 
     // ************** File system ****************
@@ -580,9 +625,10 @@ impl Process {
 
         // TODO: check that the signal is not user signal
 
-        // Enqueue signal to the first thread that does not block the signal
+        // Enqueue signal to the first thread that does not block the signal. The threads that
+        // have exited (e.g., the main thread after `pthread_exit`) cannot handle the signal.
         let threads = self.threads.lock();
-        for thread in threads.iter() {
+        for thread in threads.iter().filter(|thread| !thread.status().is_exited()) {
             let posix_thread = thread.as_posix_thread().unwrap();
             if !posix_thread.has_signal_blocked(&signal) {
                 posix_thread.enqueue_signal(Box::new(signal));
@@ -590,8 +636,12 @@ impl Process {
             }
         }
 
-        // If all threads block the signal, enqueue signal to the first thread
-        let thread = threads.iter().next().unwrap();
+        // If all threads block the signal, enqueue signal to the first thread that is alive
+        let thread = threads
+            .iter()
+            .find(|thread| !thread.status().is_exited())
+            .or_else(|| threads.iter().next())
+            .unwrap();
         let posix_thread = thread.as_posix_thread().unwrap();
         posix_thread.enqueue_signal(Box::new(signal));
     }
@@ -614,6 +664,51 @@ impl Process {
         self.parent_death_signal.as_sig_num()
     }
 
+    // ******************* Subreaper ********************
+
+    /// Sets whether the process is a child subreaper.
+    ///
+    /// When a process exits, its children are re-parented to the nearest ancestor that is a
+    /// child subreaper, instead of the init process.
+    pub fn set_child_subreaper(&self, is_child_subreaper: bool) {
+        self.is_child_subreaper
+            .store(is_child_subreaper, Ordering::Relaxed);
+    }
+
+    /// Returns whether the process is a child subreaper.
+    pub fn is_child_subreaper(&self) -> bool {
+        self.is_child_subreaper.load(Ordering::Relaxed)
+    }
+
+    // ******************* Vfork ********************
+
+    /// Marks the process as a vfork child.
+    pub(super) fn set_vfork_child(&self) {
+        self.is_vfork_child.store(true, Ordering::Release);
+    }
+
+    /// Returns whether the process is a vfork child whose parent is still waiting.
+    pub fn is_vfork_child(&self) -> bool {
+        self.is_vfork_child.load(Ordering::Acquire)
+    }
+
+    /// Resumes the parent of a vfork child.
+    ///
+    /// This method should be called when the vfork child execs or exits.
+    pub fn reset_vfork_child(&self) {
+        if self.is_vfork_child.swap(false, Ordering::AcqRel) {
+            self.vfork_wait_queue.wake_all();
+        }
+    }
+
+    /// Waits until the vfork child execs or exits.
+    ///
+    /// Like Linux, the wait cannot be interrupted by signals.
+    pub(super) fn wait_for_vfork_child(&self) {
+        self.vfork_wait_queue
+            .wait_until(|| (!self.is_vfork_child()).then_some(()));
+    }
+
     // ******************* Status ********************
 
     fn set_runnable(&self) {
@@ -676,6 +771,8 @@ mod test {
             Arc::new(RwLock::new(FileCreationMask::default())),
             ResourceLimits::default(),
             Nice::default(),
+            0,
+            0,
             Arc::new(Mutex::new(SigDispositions::default())),
         )
     }
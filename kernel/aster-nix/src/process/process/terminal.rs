@@ -57,6 +57,8 @@ pub trait Terminal: Send + Sync + FileIo {
     ///
     /// If self is not session leader, or the terminal is controlling terminal of other session,
     /// or the session already has controlling terminal, this method returns `EPERM`.
+    /// If the terminal is already the controlling terminal of the session, this method
+    /// does nothing.
     ///
     /// # Panics
     ///
@@ -66,6 +68,10 @@ pub trait Terminal: Send + Sync + FileIo {
             return_errno_with_message!(Errno::EPERM, "current process is not session leader");
         }
 
+        if self.is_controlling_terminal() {
+            return Ok(());
+        }
+
         let get_terminal = || {
             self.job_control().set_current_session()?;
             Ok(self.arc_self())
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Address space layout randomization (ASLR).
+//!
+//! When a program is loaded, the top of the stack, the base of the mmap region
+//! and the base of the heap are moved by random numbers of pages. The number of
+//! random bits for each region follows Linux on x86-64:
+//!
+//! | Region | Random bits (in pages) | Range    |
+//! |--------|------------------------|----------|
+//! | Stack  | 22                     | 16 GiB   |
+//! | Mmap   | 28                     | 1 TiB    |
+//! | Heap   | 13                     | 32 MiB   |
+//!
+//! The randomization is controlled system-wide by `/proc/sys/kernel/randomize_va_space`,
+//! and can be disabled for a process by the `ADDR_NO_RANDOMIZE` personality flag.
+
+use core::sync::atomic::{AtomicI32, Ordering};
+
+use crate::{prelude::*, util::random::getrandom};
+
+/// The number of random bits of the stack top, in pages.
+const STACK_RANDOM_BITS: u32 = 22;
+/// The number of random bits of the mmap base, in pages.
+const MMAP_RANDOM_BITS: u32 = 28;
+/// The number of random bits of the heap base, in pages.
+const HEAP_RANDOM_BITS: u32 = 13;
+
+/// The maximum offset of the heap base.
+pub(super) const MAX_HEAP_OFFSET: usize = ((1 << HEAP_RANDOM_BITS) - 1) * PAGE_SIZE;
+
+/// The personality flag that disables the randomization.
+pub const ADDR_NO_RANDOMIZE: u32 = 0x0040000;
+/// The personality flags that are cleared when executing a secure program.
+///
+/// They are `READ_IMPLIES_EXEC`, `ADDR_NO_RANDOMIZE`, `ADDR_COMPAT_LAYOUT` and `MMAP_PAGE_ZERO`.
+pub const PER_CLEAR_ON_SETID: u32 = 0x0400000 | ADDR_NO_RANDOMIZE | 0x0200000 | 0x0100000;
+
+/// The system-wide level of the randomization.
+///
+/// With level 0, no region is randomized. With level 1, the stack and the mmap region are
+/// randomized. With level 2, the heap is randomized as well. Like Linux, other values are
+/// accepted: negative values act as level 1, and values above 2 act as level 2.
+static RANDOMIZE_VA_SPACE: AtomicI32 = AtomicI32::new(2);
+
+/// Returns the system-wide level of the randomization.
+pub fn randomize_va_space() -> i32 {
+    RANDOMIZE_VA_SPACE.load(Ordering::Relaxed)
+}
+
+/// Sets the system-wide level of the randomization.
+pub fn set_randomize_va_space(level: i32) {
+    RANDOMIZE_VA_SPACE.store(level, Ordering::Relaxed);
+}
+
+/// The random offsets of the regions in the address space of a program.
+///
+/// All offsets are page-aligned.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomOffsets {
+    /// The offset of the stack top below the highest user address.
+    pub(super) stack: usize,
+    /// The offset of the mmap base above `USER_MMAP_BASE`.
+    pub(super) mmap: usize,
+    /// The offset of the heap base above `USER_HEAP_BASE`.
+    pub(super) heap: usize,
+}
+
+impl RandomOffsets {
+    /// Generates the random offsets for a process with `personality`.
+    ///
+    /// The offsets of the regions that are not randomized are zero.
+    pub fn new(personality: u32) -> Self {
+        let level = randomize_va_space();
+        if personality & ADDR_NO_RANDOMIZE != 0 || level == 0 {
+            return Self::default();
+        }
+
+        Self {
+            stack: random_pages(STACK_RANDOM_BITS),
+            mmap: random_pages(MMAP_RANDOM_BITS),
+            heap: if level > 1 {
+                random_pages(HEAP_RANDOM_BITS)
+            } else {
+                0
+            },
+        }
+    }
+}
+
+/// Returns a random number of pages below `2^nr_bits`, in bytes.
+fn random_pages(nr_bits: u32) -> usize {
+    let mut random = 0usize;
+    getrandom(random.as_bytes_mut()).unwrap();
+    (random & ((1 << nr_bits) - 1)) * PAGE_SIZE
+}
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use align_ext::AlignExt;
 use aster_rights::{Full, Rights};
@@ -21,8 +24,9 @@ pub const USER_HEAP_SIZE_LIMIT: usize = PAGE_SIZE * 1000; // 4MB
 
 #[derive(Debug)]
 pub struct Heap {
-    /// The lowest address of the heap
-    base: Vaddr,
+    /// The lowest address of the heap.
+    /// It is randomized each time the heap is mapped.
+    base: AtomicUsize,
     /// The heap size limit
     limit: usize,
     /// The current heap highest address
@@ -32,14 +36,23 @@ pub struct Heap {
 impl Heap {
     pub const fn new() -> Self {
         Heap {
-            base: USER_HEAP_BASE,
+            base: AtomicUsize::new(USER_HEAP_BASE),
             limit: USER_HEAP_SIZE_LIMIT,
             current_heap_end: AtomicUsize::new(USER_HEAP_BASE),
         }
     }
 
-    /// Inits and maps the heap Vmo
-    pub(super) fn alloc_and_map_vmo(&self, root_vmar: &Vmar<Full>) -> Result<()> {
+    /// Inits and maps the heap Vmo.
+    ///
+    /// The heap is placed `random_offset` bytes above `USER_HEAP_BASE`.
+    /// `random_offset` must be page-aligned.
+    pub(super) fn alloc_and_map_vmo(
+        &self,
+        root_vmar: &Vmar<Full>,
+        random_offset: usize,
+    ) -> Result<()> {
+        debug_assert!(random_offset % PAGE_SIZE == 0);
+        let base = USER_HEAP_BASE + random_offset;
         let heap_vmo = {
             let vmo_options = VmoOptions::<Rights>::new(0).flags(VmoFlags::RESIZABLE);
             vmo_options.alloc()?
@@ -49,31 +62,42 @@ impl Heap {
             root_vmar
                 .new_map(heap_vmo, perms)
                 .unwrap()
-                .offset(self.base)
+                .offset(base)
                 .size(self.limit)
         };
         vmar_map_options.build()?;
 
+        self.base.store(base, Ordering::Relaxed);
         self.set_uninitialized();
         Ok(())
     }
 
+    /// Returns the address range reserved for the heap.
+    pub fn range(&self) -> Range<Vaddr> {
+        let base = self.base();
+        base..base + self.limit
+    }
+
     pub fn brk(&self, new_heap_end: Option<Vaddr>) -> Result<Vaddr> {
         let current = current!();
         let root_vmar = current.root_vmar();
+        let base = self.base();
         match new_heap_end {
             None => Ok(self.current_heap_end.load(Ordering::Relaxed)),
             Some(new_heap_end) => {
-                if new_heap_end > self.base + self.limit {
+                if new_heap_end > base + self.limit {
                     return_errno_with_message!(Errno::ENOMEM, "heap size limit was met.");
                 }
                 let current_heap_end = self.current_heap_end.load(Ordering::Acquire);
-                if new_heap_end < current_heap_end {
-                    // FIXME: should we allow shrink current user heap?
+                if new_heap_end < base {
                     return Ok(current_heap_end);
                 }
-                let new_size = (new_heap_end - self.base).align_up(PAGE_SIZE);
-                let heap_mapping = root_vmar.get_vm_mapping(USER_HEAP_BASE)?;
+                if new_heap_end < current_heap_end {
+                    self.shrink_to(new_heap_end)?;
+                    return Ok(new_heap_end);
+                }
+                let new_size = (new_heap_end - base).align_up(PAGE_SIZE);
+                let heap_mapping = root_vmar.get_vm_mapping(base)?;
                 let heap_vmo = heap_mapping.vmo();
                 heap_vmo.resize(new_size)?;
                 self.current_heap_end.store(new_heap_end, Ordering::Release);
@@ -82,8 +106,37 @@ impl Heap {
         }
     }
 
+    /// Shrinks the heap so that it ends at `new_heap_end`.
+    ///
+    /// The pages above the new heap end are unmapped and their frames are released, so
+    /// that the memory is actually reclaimed rather than just hidden behind the break.
+    pub fn shrink_to(&self, new_heap_end: Vaddr) -> Result<()> {
+        let base = self.base();
+        let current_heap_end = self.current_heap_end.load(Ordering::Acquire);
+        if new_heap_end < base || new_heap_end > current_heap_end {
+            return_errno_with_message!(Errno::EINVAL, "the new heap end is out of the heap");
+        }
+
+        let new_size = (new_heap_end - base).align_up(PAGE_SIZE);
+        let old_size = (current_heap_end - base).align_up(PAGE_SIZE);
+        if new_size < old_size {
+            let current = current!();
+            let root_vmar = current.root_vmar();
+            let heap_mapping = root_vmar.get_vm_mapping(base)?;
+            heap_mapping.unmap(&(base + new_size..base + old_size), false)?;
+            heap_mapping.vmo().resize(new_size)?;
+        }
+
+        self.current_heap_end.store(new_heap_end, Ordering::Release);
+        Ok(())
+    }
+
     pub(super) fn set_uninitialized(&self) {
-        self.current_heap_end.store(self.base, Ordering::Relaxed);
+        self.current_heap_end.store(self.base(), Ordering::Relaxed);
+    }
+
+    fn base(&self) -> Vaddr {
+        self.base.load(Ordering::Relaxed)
     }
 }
 
@@ -91,7 +144,7 @@ impl Clone for Heap {
     fn clone(&self) -> Self {
         let current_heap_end = self.current_heap_end.load(Ordering::Relaxed);
         Self {
-            base: self.base,
+            base: AtomicUsize::new(self.base()),
             limit: self.limit,
             current_heap_end: AtomicUsize::new(current_heap_end),
         }
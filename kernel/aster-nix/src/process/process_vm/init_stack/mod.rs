@@ -15,6 +15,7 @@
 
 use core::{
     mem,
+    ops::Range,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
@@ -24,6 +25,7 @@ use ostd::mm::{VmIo, MAX_USERSPACE_VADDR};
 
 use self::aux_vec::{AuxKey, AuxVec};
 use crate::{
+    arch::ELF_PLATFORM,
     prelude::*,
     util::{random::getrandom, read_cstring_from_vmar},
     vm::{perms::VmPerms, vmar::Vmar, vmo::VmoOptions},
@@ -35,21 +37,39 @@ pub mod aux_vec;
 pub const INIT_STACK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
 
 /// The max number of arguments that can be used to creating a new process.
-pub const MAX_ARGV_NUMBER: usize = 128;
+pub const MAX_ARGV_NUMBER: usize = 0x7FFF_FFFF;
 /// The max number of environmental variables that can be used to creating a new process.
-pub const MAX_ENVP_NUMBER: usize = 128;
+pub const MAX_ENVP_NUMBER: usize = 0x7FFF_FFFF;
 /// The max length of each argument to create a new process.
-pub const MAX_ARG_LEN: usize = 2048;
+pub const MAX_ARG_LEN: usize = 32 * PAGE_SIZE;
 /// The max length of each environmental variable (the total length of key-value pair) to create a new process.
-pub const MAX_ENV_LEN: usize = 128;
+pub const MAX_ENV_LEN: usize = 32 * PAGE_SIZE;
+/// The max total size of the arguments and environmental variables to create a new process.
+///
+/// Like Linux, the size is a quarter of the stack size limit, and it counts both the strings
+/// (including the null terminators) and the pointers to them.
+pub const MAX_ARGS_SIZE: usize = INIT_STACK_SIZE / 4;
+
+/// An upper bound of the number of entries in the auxiliary vector, excluding `AT_NULL`.
+const MAX_NR_AUX_ENTRIES: usize = 32;
+/// The length of the random bytes referenced by `AT_RANDOM`.
+const AUX_RANDOM_LEN: usize = 16;
+/// An upper bound of the paddings inserted to align the stack.
+const MAX_ALIGNMENT_PADDING: usize = 32;
+/// The gap kept free below the stack, so that it does not grow into other mappings.
+///
+/// Like Linux, the gap is 256 pages.
+const STACK_GUARD_GAP: usize = 256 * PAGE_SIZE;
 
 /*
  * Illustration of the virtual memory space containing the processes' init stack:
  *
  *  (high address)
  *  +---------------------+ <------+ Highest address
- *  |                     |          Random stack paddings
+ *  |                     |          Random stack paddings (see `aslr`)
  *  +---------------------+ <------+ The base of stack (stack grows down)
+ *  | Executable path     | <------+ Referenced by AT_EXECFN
+ *  +---------------------+
  *  |                     |
  *  | Null-terminated     |
  *  | strings referenced  |
@@ -87,6 +107,11 @@ pub const MAX_ENV_LEN: usize = 128;
  *  +---------------------+
  *  |                     |
  *  +---------------------+ <------+ User stack default rlimit
+ *  |                     |          The stack grows down on page faults,
+ *  |                     |          until its size reaches RLIMIT_STACK
+ *  +---------------------+
+ *  |                     |          Guard gap
+ *  +---------------------+
  *  (low address)
  */
 
@@ -94,8 +119,9 @@ pub const MAX_ENV_LEN: usize = 128;
 #[derive(Debug, Clone)]
 pub struct InitStack {
     /// The initial highest address.
-    /// The stack grows down from this address
-    initial_top: Vaddr,
+    /// The stack grows down from this address.
+    /// It is randomized each time the stack is mapped.
+    initial_top: Arc<AtomicUsize>,
     /// The max allowed stack size
     max_size: usize,
     /// The current stack pointer.
@@ -103,44 +129,111 @@ pub struct InitStack {
     /// After initialized, `pos` points to the user stack pointer(rsp)
     /// of the process.
     pos: Arc<AtomicUsize>,
+    /// The lowest address of the stack, which is lowered as the stack grows.
+    bottom: Arc<Mutex<Vaddr>>,
 }
 
 impl InitStack {
     pub(super) fn new() -> Self {
-        let nr_pages_padding = {
-            let mut random_nr_pages_padding: u8 = 0;
-            getrandom(random_nr_pages_padding.as_bytes_mut()).unwrap();
-            random_nr_pages_padding as usize
-        };
-        let initial_top = MAX_USERSPACE_VADDR - PAGE_SIZE * nr_pages_padding;
+        let initial_top = MAX_USERSPACE_VADDR;
         let max_size = INIT_STACK_SIZE;
         Self {
-            initial_top,
+            initial_top: Arc::new(AtomicUsize::new(initial_top)),
             max_size,
             pos: Arc::new(AtomicUsize::new(initial_top)),
+            bottom: Arc::new(Mutex::new(initial_top - max_size)),
         }
     }
 
-    /// Init and map the vmo for init stack
-    pub(super) fn alloc_and_map_vmo(&self, root_vmar: &Vmar<Full>) -> Result<()> {
+    /// Creates the init stack of a forked process.
+    ///
+    /// The stack of the forked process grows independently,
+    /// and is placed independently when the forked process executes a new program.
+    pub(super) fn new_fork(&self) -> Self {
+        let bottom = *self.bottom.lock();
+        Self {
+            initial_top: Arc::new(AtomicUsize::new(self.initial_top())),
+            pos: Arc::new(AtomicUsize::new(self.pos())),
+            bottom: Arc::new(Mutex::new(bottom)),
+            ..self.clone()
+        }
+    }
+
+    /// Init and map the vmo for init stack.
+    ///
+    /// The top of the stack is placed `random_offset` bytes below the highest user address.
+    /// `random_offset` must be page-aligned.
+    pub(super) fn alloc_and_map_vmo(
+        &self,
+        root_vmar: &Vmar<Full>,
+        random_offset: usize,
+    ) -> Result<()> {
+        debug_assert!(random_offset % PAGE_SIZE == 0);
+        let initial_top = MAX_USERSPACE_VADDR - random_offset;
+
+        // The VMO spans all the addresses below the stack top, and each page of the stack
+        // is mapped at the same offset in the VMO. So the stack can grow down by extending
+        // the mapping within the VMO, whose pages are only committed when they are accessed.
         let vmo = {
-            let vmo_options = VmoOptions::<Rights>::new(self.max_size);
+            let vmo_options = VmoOptions::<Rights>::new(initial_top);
             vmo_options.alloc()?
         };
 
         let vmar_map_options = {
             let perms = VmPerms::READ | VmPerms::WRITE;
-            let map_addr = self.initial_top - self.max_size;
+            let map_addr = initial_top - self.max_size;
             debug_assert!(map_addr % PAGE_SIZE == 0);
-            root_vmar.new_map(vmo, perms)?.offset(map_addr)
+            root_vmar
+                .new_map(vmo, perms)?
+                .vmo_offset(map_addr)
+                .size(self.max_size)
+                .reserve_vmo_size(false)
+                .offset(map_addr)
         };
 
         vmar_map_options.build()?;
 
+        self.initial_top.store(initial_top, Ordering::Relaxed);
+        *self.bottom.lock() = initial_top - self.max_size;
         self.set_uninitialized();
         Ok(())
     }
 
+    /// Returns the address range where the stack is mapped,
+    /// including the pages that the stack has grown by.
+    pub(super) fn range(&self) -> Range<Vaddr> {
+        *self.bottom.lock()..self.initial_top()
+    }
+
+    /// Grows the stack down to cover `addr`.
+    ///
+    /// The size of the stack cannot exceed `size_limit`, and the stack cannot grow
+    /// within the guard gap of the mapping below it. Otherwise, `ENOMEM` is returned.
+    pub(super) fn grow_down(
+        &self,
+        root_vmar: &Vmar<Full>,
+        addr: Vaddr,
+        size_limit: usize,
+    ) -> Result<()> {
+        let mut bottom = self.bottom.lock();
+        if addr >= *bottom {
+            return_errno_with_message!(Errno::EFAULT, "the address is not below the stack");
+        }
+
+        let new_bottom = addr.align_down(PAGE_SIZE);
+        if self.initial_top() - new_bottom > size_limit {
+            return_errno_with_message!(Errno::ENOMEM, "the stack size exceeds the limit");
+        }
+        let guarded_range = new_bottom.saturating_sub(STACK_GUARD_GAP)..*bottom;
+        if !root_vmar.is_free_range(&guarded_range) {
+            return_errno_with_message!(Errno::ENOMEM, "the stack grows into another mapping");
+        }
+
+        root_vmar.grow_mapping_down(*bottom, new_bottom)?;
+        *bottom = new_bottom;
+        Ok(())
+    }
+
     /// Returns the user stack top(highest address), used to setup rsp.
     ///
     /// This method should only be called after the stack is initialized.
@@ -156,34 +249,42 @@ impl InitStack {
         vmar: &'a Vmar<Full>,
         argv: Vec<CString>,
         envp: Vec<CString>,
+        execfn: CString,
         auxvec: AuxVec,
     ) -> InitStackWriter<'a> {
         // The stack should be written only once.
         debug_assert!(!self.is_initialized());
         InitStackWriter {
-            pos: self.pos.clone(),
+            pos: AtomicUsize::new(self.pos()),
+            stack_pos: self.pos.clone(),
             vmar,
             argv,
             envp,
+            execfn,
             auxvec,
         }
     }
 
     pub(super) fn reader<'a>(&self, vmar: &'a Vmar<Full>) -> InitStackReader<'a> {
-        // The stack should only be read after initialized
-        debug_assert!(self.is_initialized());
+        // The stack may be read by other processes (e.g., via procfs) while it is being
+        // reinitialized by `execve`, so the state is checked when reading the contents.
         InitStackReader {
             base: self.pos(),
+            is_initialized: self.is_initialized(),
             vmar,
         }
     }
 
     fn is_initialized(&self) -> bool {
-        self.pos() != self.initial_top
+        self.pos() != self.initial_top()
     }
 
     fn set_uninitialized(&self) {
-        self.pos.store(self.initial_top, Ordering::Relaxed);
+        self.pos.store(self.initial_top(), Ordering::Relaxed);
+    }
+
+    fn initial_top(&self) -> Vaddr {
+        self.initial_top.load(Ordering::Relaxed)
     }
 
     fn pos(&self) -> Vaddr {
@@ -193,10 +294,15 @@ impl InitStack {
 
 /// A writer to initialize the content of an `InitStack`.
 pub struct InitStackWriter<'a> {
-    pos: Arc<AtomicUsize>,
+    /// The position being written.
+    pos: AtomicUsize,
+    /// The stack pointer of the `InitStack`, which is updated only after the whole
+    /// stack has been written. So the readers never see a partially written stack.
+    stack_pos: Arc<AtomicUsize>,
     vmar: &'a Vmar<Full>,
     argv: Vec<CString>,
     envp: Vec<CString>,
+    execfn: CString,
     auxvec: AuxVec,
 }
 
@@ -207,10 +313,16 @@ impl<'a> InitStackWriter<'a> {
 
         let argc = self.argv.len() as u64;
 
+        // Write the executable path at the base of the stack
+        let execfn_pointer = self.write_cstring(&self.execfn)?;
+        self.auxvec.set(AuxKey::AT_EXECFN, execfn_pointer)?;
         // Write envp string
         let envp_pointers = self.write_envp_strings()?;
         // Write argv string
         let argv_pointers = self.write_argv_strings()?;
+        // Write the platform string for auxvec
+        let platform_pointer = self.write_bytes(ELF_PLATFORM.to_bytes_with_nul())?;
+        self.auxvec.set(AuxKey::AT_PLATFORM, platform_pointer)?;
         // Generate random values for auxvec
         let random_value_pointer = {
             let random_value = generate_random_for_aux_vec();
@@ -229,6 +341,7 @@ impl<'a> InitStackWriter<'a> {
         // Ensure stack top is 16-bytes aligned
         debug_assert_eq!(self.pos() & !0xf, self.pos());
 
+        self.stack_pos.store(self.pos(), Ordering::Relaxed);
         Ok(())
     }
 
@@ -340,8 +453,38 @@ impl<'a> InitStackWriter<'a> {
     }
 }
 
-fn generate_random_for_aux_vec() -> [u8; 16] {
-    let mut rand_val = [0; 16];
+/// Checks whether `argv`, `envp`, the executable path and the auxiliary vector fit in the
+/// init stack.
+///
+/// The auxiliary vector is not built until the executable is loaded, so its size is
+/// estimated by its upper bound. This allows the check to be done before the old
+/// mappings of the process are cleared, so that `execve` can fail cleanly with `E2BIG`.
+pub fn check_init_stack_size(argv: &[CString], envp: &[CString], execfn: &str) -> Result<()> {
+    let strings_size: usize = argv
+        .iter()
+        .chain(envp.iter())
+        .map(|cstring| cstring.as_bytes_with_nul().len())
+        .sum::<usize>()
+        + (execfn.len() + 1)
+        + ELF_PLATFORM.to_bytes_with_nul().len();
+    let auxvec_size = (MAX_NR_AUX_ENTRIES + 1) * (mem::size_of::<u64>() * 2);
+    // The pointers include `argc` and the `NULL` pointers that end `argv` and `envp`.
+    let pointers_size = (argv.len() + envp.len() + 3) * mem::size_of::<u64>();
+
+    let total_size =
+        strings_size + AUX_RANDOM_LEN + auxvec_size + pointers_size + MAX_ALIGNMENT_PADDING;
+    if total_size > INIT_STACK_SIZE {
+        return_errno_with_message!(
+            Errno::E2BIG,
+            "the arguments and environment variables exceed the init stack"
+        );
+    }
+
+    Ok(())
+}
+
+fn generate_random_for_aux_vec() -> [u8; AUX_RANDOM_LEN] {
+    let mut rand_val = [0; AUX_RANDOM_LEN];
     getrandom(&mut rand_val).unwrap();
     rand_val
 }
@@ -349,12 +492,16 @@ fn generate_random_for_aux_vec() -> [u8; 16] {
 /// A reader to parse the content of an `InitStack`.
 pub struct InitStackReader<'a> {
     base: Vaddr,
+    is_initialized: bool,
     vmar: &'a Vmar<Full>,
 }
 
 impl<'a> InitStackReader<'a> {
     /// Read argc from the process init stack
     pub fn argc(&self) -> Result<u64> {
+        if !self.is_initialized {
+            return_errno_with_message!(Errno::EINVAL, "the init stack is not initialized");
+        }
         let stack_base = self.user_stack_top();
         Ok(self.vmar.read_val(stack_base)?)
     }
@@ -365,7 +512,9 @@ impl<'a> InitStackReader<'a> {
         // base = stack bottom + the size of argc
         let base = self.user_stack_top() + 8;
 
-        let mut argv = Vec::with_capacity(argc);
+        // The init stack is writable by the user, so `argc` cannot be trusted to
+        // preallocate the vector.
+        let mut argv = Vec::new();
         for i in 0..argc {
             let arg_ptr = {
                 let offset = base + i * 8;
@@ -406,6 +555,42 @@ impl<'a> InitStackReader<'a> {
         Ok(envp)
     }
 
+    /// Read the auxiliary vector from the process init stack.
+    ///
+    /// The entries are returned as `(key, value)` pairs in the order they appear on the
+    /// stack, including the `AT_NULL` entry that ends the vector.
+    pub fn auxvec(&self) -> Result<Vec<(u64, u64)>> {
+        let argc = self.argc()? as usize;
+        // base = stack bottom
+        // + the size of argc(8)
+        // + the size of arg pointer(8) * the number of arg(argc)
+        // + the size of null pointer(8)
+        let mut offset = self.user_stack_top() + 8 + 8 * argc + 8;
+
+        // Skip the envp pointers and the null pointer
+        for _ in 0..=MAX_ENVP_NUMBER {
+            let envp_ptr = self.vmar.read_val::<Vaddr>(offset)?;
+            offset += 8;
+            if envp_ptr == 0 {
+                break;
+            }
+        }
+
+        let mut auxvec = Vec::new();
+        for _ in 0..=MAX_NR_AUX_ENTRIES {
+            let key = self.vmar.read_val::<u64>(offset)?;
+            let value = self.vmar.read_val::<u64>(offset + 8)?;
+            offset += 16;
+
+            auxvec.push((key, value));
+            if key == AuxKey::AT_NULL as u64 {
+                break;
+            }
+        }
+
+        Ok(auxvec)
+    }
+
     pub const fn user_stack_top(&self) -> Vaddr {
         self.base
     }
@@ -9,18 +9,23 @@
 //! the basic info of process level vm segments,
 //! like init stack and heap.
 
+pub mod aslr;
 mod heap;
 mod init_stack;
 
+use core::ops::Range;
+
 use aster_rights::Full;
 pub use heap::Heap;
+use static_assertions::const_assert;
 
+use self::{aslr::RandomOffsets, heap::USER_HEAP_BASE};
 pub use self::{
     heap::USER_HEAP_SIZE_LIMIT,
     init_stack::{
         aux_vec::{AuxKey, AuxVec},
-        InitStack, InitStackReader, InitStackWriter, INIT_STACK_SIZE, MAX_ARGV_NUMBER, MAX_ARG_LEN,
-        MAX_ENVP_NUMBER, MAX_ENV_LEN,
+        check_init_stack_size, InitStack, InitStackReader, InitStackWriter, INIT_STACK_SIZE,
+        MAX_ARGS_SIZE, MAX_ARGV_NUMBER, MAX_ARG_LEN, MAX_ENVP_NUMBER, MAX_ENV_LEN,
     },
 };
 use crate::{prelude::*, vm::vmar::Vmar};
@@ -28,7 +33,8 @@ use crate::{prelude::*, vm::vmar::Vmar};
 /*
  * The user's virtual memory space layout looks like below.
  * TODO: The layout of the userheap does not match the current implementation,
- * And currently the initial program break is a fixed value.
+ * since the heap is placed above `USER_HEAP_BASE` rather than right after the program.
+ * The randomly padded pages are described in `aslr`.
  *
  *  (high address)
  *  +---------------------+ <------+ The top of Vmar, which is the highest address usable
@@ -43,12 +49,18 @@ use crate::{prelude::*, vm::vmar::Vmar};
  *  | MMAP Spaces         |
  *  |                     |
  *  | ...                 |
+ *  +---------------------+ <------+ The mmap base
+ *  |                     |          Randomly padded pages
+ *  +---------------------+ <------+ USER_MMAP_BASE
+ *  | ...                 |
  *  |         /\          |
  *  +---------||----------+ <------+ The current program break
  *  | User heap           |
  *  |                     |
  *  +---------------------+ <------+ The original program break
  *  |                     |          Randomly padded pages
+ *  +---------------------+ <------+ USER_HEAP_BASE
+ *  | ...                 |
  *  +---------------------+ <------+ The end of the program's last segment
  *  |                     |
  *  | Loaded segments     |
@@ -61,6 +73,12 @@ use crate::{prelude::*, vm::vmar::Vmar};
  *  (low address)
  */
 
+/// The lowest mmap base, above which the mappings without a specified address are placed.
+///
+/// It leaves room for the randomized heap above `USER_HEAP_BASE`.
+const USER_MMAP_BASE: Vaddr = 0x0000_0000_4000_0000;
+const_assert!(USER_HEAP_BASE + USER_HEAP_SIZE_LIMIT + aslr::MAX_HEAP_OFFSET <= USER_MMAP_BASE);
+
 // The process user space virtual memory
 pub struct ProcessVm {
     root_vmar: Vmar<Full>,
@@ -83,9 +101,10 @@ impl ProcessVm {
     pub fn alloc() -> Self {
         let root_vmar = Vmar::<Full>::new_root();
         let init_stack = InitStack::new();
-        init_stack.alloc_and_map_vmo(&root_vmar).unwrap();
+        init_stack.alloc_and_map_vmo(&root_vmar, 0).unwrap();
         let heap = Heap::new();
-        heap.alloc_and_map_vmo(&root_vmar).unwrap();
+        heap.alloc_and_map_vmo(&root_vmar, 0).unwrap();
+        root_vmar.set_mmap_base(USER_MMAP_BASE);
         Self {
             root_vmar,
             heap,
@@ -101,7 +120,7 @@ impl ProcessVm {
         Ok(Self {
             root_vmar,
             heap: other.heap.clone(),
-            init_stack: other.init_stack.clone(),
+            init_stack: other.init_stack.new_fork(),
         })
     }
 
@@ -119,19 +138,41 @@ impl ProcessVm {
         &self,
         argv: Vec<CString>,
         envp: Vec<CString>,
+        execfn: CString,
         aux_vec: AuxVec,
     ) -> InitStackWriter {
-        self.init_stack.writer(&self.root_vmar, argv, envp, aux_vec)
+        self.init_stack
+            .writer(&self.root_vmar, argv, envp, execfn, aux_vec)
     }
 
-    pub(super) fn heap(&self) -> &Heap {
+    /// Returns the address range where the stack is mapped,
+    /// including the pages that the stack has grown by.
+    pub fn stack_range(&self) -> Range<Vaddr> {
+        self.init_stack.range()
+    }
+
+    /// Grows the stack down to cover `addr`, as long as
+    /// the stack size does not exceed `size_limit`.
+    pub fn grow_stack(&self, addr: Vaddr, size_limit: usize) -> Result<()> {
+        self.init_stack.grow_down(&self.root_vmar, addr, size_limit)
+    }
+
+    pub fn heap(&self) -> &Heap {
         &self.heap
     }
 
     /// Clears existing mappings and then maps stack and heap vmo.
-    pub(super) fn clear_and_map(&self) {
+    ///
+    /// The stack, the heap and the mmap base are moved by `random_offsets`.
+    pub(super) fn clear_and_map(&self, random_offsets: RandomOffsets) {
         self.root_vmar.clear().unwrap();
-        self.init_stack.alloc_and_map_vmo(&self.root_vmar).unwrap();
-        self.heap.alloc_and_map_vmo(&self.root_vmar).unwrap();
+        self.init_stack
+            .alloc_and_map_vmo(&self.root_vmar, random_offsets.stack)
+            .unwrap();
+        self.heap
+            .alloc_and_map_vmo(&self.root_vmar, random_offsets.heap)
+            .unwrap();
+        self.root_vmar
+            .set_mmap_base(USER_MMAP_BASE + random_offsets.mmap);
     }
 }
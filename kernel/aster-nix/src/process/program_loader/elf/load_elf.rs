@@ -21,6 +21,8 @@ use crate::{
     process::{
         do_exit_group,
         process_vm::{AuxKey, AuxVec, ProcessVm},
+        program_loader::ProgramLoadOptions,
+        signal::constants::SIGKILL,
         TermStatus,
     },
     vdso::vdso_vmo,
@@ -42,6 +44,7 @@ pub fn load_elf_to_vm(
     fs_resolver: &FsResolver,
     argv: Vec<CString>,
     envp: Vec<CString>,
+    options: &ProgramLoadOptions,
 ) -> Result<ElfLoadInfo> {
     let parsed_elf = Elf::parse_elf(file_header)?;
 
@@ -57,8 +60,12 @@ pub fn load_elf_to_vm(
                     .set(AuxKey::AT_SYSINFO_EHDR, vdso_text_base as u64)
                     .unwrap();
             }
+            aux_vec
+                .set(AuxKey::AT_SECURE, options.is_secure as u64)
+                .unwrap();
 
-            let init_stack_writer = process_vm.init_stack_writer(argv, envp, aux_vec);
+            let execfn = CString::new(options.execfn)?;
+            let init_stack_writer = process_vm.init_stack_writer(argv, envp, execfn, aux_vec);
             init_stack_writer.write().unwrap();
 
             let user_stack_top = process_vm.init_stack_reader().user_stack_top();
@@ -77,8 +84,8 @@ pub fn load_elf_to_vm(
             // FIXME: `current` macro will be used in `do_exit_group`.
             // if the macro is used when creating the init process,
             // the macro will panic. This corner case should be handled later.
-            // FIXME: how to set the correct exit status?
-            do_exit_group(TermStatus::Exited(1));
+            // Like Linux, the process is killed by `SIGKILL`.
+            do_exit_group(TermStatus::Killed(SIGKILL));
 
             // The process will exit and the error code will be ignored.
             Err(err)
@@ -107,7 +114,11 @@ fn lookup_and_parse_ldso(
     Ok(Some((ldso_file, ldso_elf)))
 }
 
-fn load_ldso(root_vmar: &Vmar<Full>, ldso_file: &Dentry, ldso_elf: &Elf) -> Result<LdsoLoadInfo> {
+fn load_ldso(
+    root_vmar: &Vmar<Full>,
+    ldso_file: &Arc<Dentry>,
+    ldso_elf: &Elf,
+) -> Result<LdsoLoadInfo> {
     let map_addr = map_segment_vmos(ldso_elf, root_vmar, ldso_file)?;
     Ok(LdsoLoadInfo::new(
         ldso_elf.entry_point() + map_addr,
@@ -119,7 +130,7 @@ fn init_and_map_vmos(
     process_vm: &ProcessVm,
     ldso: Option<(Arc<Dentry>, Elf)>,
     parsed_elf: &Elf,
-    elf_file: &Dentry,
+    elf_file: &Arc<Dentry>,
 ) -> Result<(Vaddr, AuxVec)> {
     let root_vmar = process_vm.root_vmar();
 
@@ -198,7 +209,11 @@ impl ElfLoadInfo {
 }
 
 /// init vmo for each segment and then map segment to root vmar
-pub fn map_segment_vmos(elf: &Elf, root_vmar: &Vmar<Full>, elf_file: &Dentry) -> Result<Vaddr> {
+pub fn map_segment_vmos(
+    elf: &Elf,
+    root_vmar: &Vmar<Full>,
+    elf_file: &Arc<Dentry>,
+) -> Result<Vaddr> {
     // all segments of the shared object must be mapped to a continuous vm range
     // to ensure the relative offset of each segment not changed.
     let base_addr = if elf.is_shared_object() {
@@ -219,6 +234,7 @@ pub fn map_segment_vmos(elf: &Elf, root_vmar: &Vmar<Full>, elf_file: &Dentry) ->
                 anonymous_map_size,
                 root_vmar,
                 base_addr,
+                elf_file,
             )?;
         }
     }
@@ -257,6 +273,7 @@ fn map_segment_vmo(
     anonymous_map_size: usize,
     root_vmar: &Vmar<Full>,
     base_addr: Vaddr,
+    elf_file: &Arc<Dentry>,
 ) -> Result<()> {
     let perms = parse_segment_perm(program_header.flags);
     let offset = (program_header.virtual_addr as Vaddr).align_down(PAGE_SIZE);
@@ -267,7 +284,11 @@ fn map_segment_vmo(
         perms
     );
     let vmo_size = vmo.size();
-    let mut vm_map_options = root_vmar.new_map(vmo, perms)?.can_overwrite(true);
+    let file_offset = (program_header.offset as usize).align_down(PAGE_SIZE);
+    let mut vm_map_options = root_vmar
+        .new_map(vmo, perms)?
+        .can_overwrite(true)
+        .file(elf_file.clone(), file_offset);
     let offset = base_addr + offset;
     vm_map_options = vm_map_options.offset(offset);
     let map_addr = vm_map_options.build()?;
@@ -7,29 +7,80 @@ use self::{
     elf::{load_elf_to_vm, ElfLoadInfo},
     shebang::parse_shebang_line,
 };
-use super::process_vm::ProcessVm;
+use super::process_vm::{aslr::RandomOffsets, check_init_stack_size, ProcessVm};
 use crate::{
     fs::{
         fs_resolver::{FsPath, FsResolver, AT_FDCWD},
-        path::Dentry,
+        path::{Dentry, PerMountFlags},
+        utils::Permission,
     },
     prelude::*,
 };
 
+/// The maximum depth of nested shebang executables.
+///
+/// Like Linux, an interpreter of a shebang executable can be a shebang executable itself,
+/// but at most four shebang executables can be chained.
+const SHEBANG_RECURSION_LIMIT: usize = 4;
+
+/// Options for loading a program.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgramLoadOptions<'a> {
+    /// The path of the executable being loaded, as passed to execve or as named
+    /// in the shebang line of the script that is interpreted by the executable
+    filename: &'a str,
+    /// The path of the executable as passed to execve, which is reported by `AT_EXECFN`
+    execfn: &'a str,
+    is_secure: bool,
+    personality: u32,
+    /// The number of shebang executables that can still be chained. If the interpreter
+    /// of a shebang executable is also a shebang executable, the interpreter is loaded
+    /// recursively, and `ELOOP` is returned once the limit is reached.
+    recursion_limit: usize,
+}
+
+impl<'a> ProgramLoadOptions<'a> {
+    /// Creates the options to load the program that is executed as `filename`.
+    ///
+    /// The filename is the path of the executable as passed to execve, which is reported
+    /// by `AT_EXECFN` and passed to the interpreter of a shebang executable.
+    pub fn new(filename: &'a str) -> Self {
+        Self {
+            filename,
+            execfn: filename,
+            is_secure: false,
+            personality: 0,
+            recursion_limit: SHEBANG_RECURSION_LIMIT,
+        }
+    }
+
+    /// Sets whether the program runs in secure mode, which is reported
+    /// to the program by `AT_SECURE` in the auxiliary vector.
+    ///
+    /// The default value is `false`.
+    pub fn is_secure(mut self, is_secure: bool) -> Self {
+        self.is_secure = is_secure;
+        self
+    }
+
+    /// Sets the personality, which decides whether the address space layout is randomized.
+    ///
+    /// The default value is zero.
+    pub fn personality(mut self, personality: u32) -> Self {
+        self.personality = personality;
+        self
+    }
+}
+
 /// Load an executable to root vmar, including loading programe image, preparing heap and stack,
 /// initializing argv, envp and aux tables.
-/// About recursion_limit: recursion limit is used to limit th recursion depth of shebang executables.
-/// If the interpreter(the program behind #!) of shebang executable is also a shebang,
-/// then it will trigger recursion. We will try to setup root vmar for the interpreter.
-/// I guess for most cases, setting the recursion_limit as 1 should be enough.
-/// because the interpreter is usually an elf binary(e.g., /bin/bash)
 pub fn load_program_to_vm(
     process_vm: &ProcessVm,
     elf_file: Arc<Dentry>,
     argv: Vec<CString>,
     envp: Vec<CString>,
     fs_resolver: &FsResolver,
-    recursion_limit: usize,
+    options: ProgramLoadOptions,
 ) -> Result<(String, ElfLoadInfo)> {
     let abs_path = elf_file.abs_path();
     let inode = elf_file.inode();
@@ -40,30 +91,50 @@ pub fn load_program_to_vm(
         file_header_buffer
     };
     if let Some(mut new_argv) = parse_shebang_line(&*file_header)? {
-        if recursion_limit == 0 {
+        if options.recursion_limit == 0 {
             return_errno_with_message!(Errno::ELOOP, "the recursieve limit is reached");
         }
-        new_argv.extend_from_slice(&argv);
+        // The new arguments are the interpreter, its optional argument, the path of the
+        // script, and the original arguments except the first one. Like Linux, the path of
+        // the script is the one passed to execve for the outermost script, and the one in
+        // the shebang line of the outer script for a nested one.
+        new_argv.push(CString::new(options.filename)?);
+        new_argv.extend(argv.into_iter().skip(1));
+        let interpreter_path = new_argv[0].to_str()?.to_string();
         let interpreter = {
-            let filename = new_argv[0].to_str()?.to_string();
-            let fs_path = FsPath::new(AT_FDCWD, &filename)?;
+            let fs_path = FsPath::new(AT_FDCWD, &interpreter_path)?;
             fs_resolver.lookup(&fs_path)?
         };
         check_executable_file(&interpreter)?;
+        let interpreter_options = ProgramLoadOptions {
+            filename: &interpreter_path,
+            recursion_limit: options.recursion_limit - 1,
+            ..options
+        };
         return load_program_to_vm(
             process_vm,
             interpreter,
             new_argv,
             envp,
             fs_resolver,
-            recursion_limit - 1,
+            interpreter_options,
         );
     }
 
-    process_vm.clear_and_map();
+    // This is the last chance to fail without destroying the old program.
+    check_init_stack_size(&argv, &envp, options.execfn)?;
 
-    let elf_load_info =
-        load_elf_to_vm(process_vm, &*file_header, elf_file, fs_resolver, argv, envp)?;
+    process_vm.clear_and_map(RandomOffsets::new(options.personality));
+
+    let elf_load_info = load_elf_to_vm(
+        process_vm,
+        &*file_header,
+        elf_file,
+        fs_resolver,
+        argv,
+        envp,
+        &options,
+    )?;
 
     Ok((abs_path, elf_load_info))
 }
@@ -77,9 +148,11 @@ pub fn check_executable_file(dentry: &Arc<Dentry>) -> Result<()> {
         return_errno_with_message!(Errno::EACCES, "the dentry is not a regular file");
     }
 
-    if !dentry.mode()?.is_executable() {
-        return_errno_with_message!(Errno::EACCES, "the dentry is not executable");
+    if dentry.mount_node().flags().contains(PerMountFlags::NOEXEC) {
+        return_errno_with_message!(Errno::EACCES, "the file is on a noexec mount");
     }
 
+    dentry.inode().check_permission(Permission::MAY_EXEC)?;
+
     Ok(())
 }
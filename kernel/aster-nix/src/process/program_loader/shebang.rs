@@ -2,32 +2,66 @@
 
 use crate::prelude::*;
 
+/// The maximum length of a shebang line, including the leading `#!`.
+///
+/// Like Linux, the characters beyond the limit are ignored.
+const MAX_SHEBANG_LINE_LEN: usize = 127;
+
 /// Try to parse a buffer as a shebang line.
 ///
 /// If the buffer starts with `#!` and its header is a valid shebang sequence,
 /// then the function returns `Ok(Some(parts))`,
-/// where `parts` is a `Vec` that contains the path of and the arguments for the interpreter.
+/// where `parts` is a `Vec` that contains the path of the interpreter,
+/// followed by the optional argument for the interpreter.
 /// If the buffer starts with `#!` but some error occurs while parsing the file,
 /// then `Err(_)` is returned.
 /// If the buffer does not start with `#!`, then `Ok(None)` is returned.
+///
+/// Like Linux, everything after the interpreter path is passed as a single argument,
+/// with the leading and trailing blanks removed.
 pub fn parse_shebang_line(file_header_buffer: &[u8]) -> Result<Option<Vec<CString>>> {
-    if !file_header_buffer.starts_with(b"#!") || !file_header_buffer.contains(&b'\n') {
+    let line = &file_header_buffer[..file_header_buffer.len().min(MAX_SHEBANG_LINE_LEN)];
+    let Some(line) = line.strip_prefix(b"#!") else {
         // the file is not a shebang
         return Ok(None);
+    };
+    // The line ends at the newline character, or at the end of the file.
+    let line_len = line
+        .iter()
+        .position(|&c| c == b'\n' || c == b'\0')
+        .unwrap_or(line.len());
+    let line = trim_blanks(&line[..line_len]);
+
+    let (interpreter, arg) = match line.iter().position(is_blank) {
+        Some(interpreter_len) => (
+            &line[..interpreter_len],
+            trim_blanks(&line[interpreter_len..]),
+        ),
+        None => (line, &[] as &[u8]),
+    };
+    if interpreter.is_empty() {
+        return_errno_with_message!(Errno::ENOEXEC, "no interpreter is specified");
     }
-    let first_line_len = file_header_buffer.iter().position(|&c| c == b'\n').unwrap();
-    // skip #!
-    let shebang_header = &file_header_buffer[2..first_line_len];
-    let mut shebang_argv = Vec::new();
-    for arg in shebang_header.split(|&c| c == b' ') {
-        let arg = CString::new(arg)?;
-        shebang_argv.push(arg);
-    }
-    if shebang_argv.len() != 1 {
-        return_errno_with_message!(
-            Errno::EINVAL,
-            "One and only one intpreter program should be specified"
-        );
+
+    let mut shebang_argv = vec![CString::new(interpreter)?];
+    if !arg.is_empty() {
+        shebang_argv.push(CString::new(arg)?);
     }
     Ok(Some(shebang_argv))
 }
+
+fn is_blank(c: &u8) -> bool {
+    *c == b' ' || *c == b'\t'
+}
+
+fn trim_blanks(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|c| !is_blank(c))
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|c| !is_blank(c))
+        .map_or(start, |pos| pos + 1);
+    &bytes[start..end]
+}
@@ -3,7 +3,7 @@
 #![allow(non_camel_case_types)]
 
 use super::process_vm::{INIT_STACK_SIZE, USER_HEAP_SIZE_LIMIT};
-use crate::prelude::*;
+use crate::{fs::file_table::nr_open, prelude::*};
 
 pub struct ResourceLimits {
     rlimits: [RLimit64; RLIMIT_COUNT],
@@ -23,7 +23,7 @@ impl Default for ResourceLimits {
     fn default() -> Self {
         let stack_size = RLimit64::new(INIT_STACK_SIZE as u64);
         let heap_size = RLimit64::new(USER_HEAP_SIZE_LIMIT as u64);
-        let open_files = RLimit64::new(1024);
+        let open_files = RLimit64::new_with_max(1024, nr_open() as u64);
 
         let mut rlimits = Self {
             rlimits: [RLimit64::default(); RLIMIT_COUNT],
@@ -70,6 +70,10 @@ impl RLimit64 {
         Self { cur, max: u64::MAX }
     }
 
+    pub fn new_with_max(cur: u64, max: u64) -> Self {
+        Self { cur, max }
+    }
+
     pub fn get_cur(&self) -> u64 {
         self.cur
     }
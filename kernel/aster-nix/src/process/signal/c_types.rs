@@ -39,6 +39,16 @@ pub struct siginfo_t {
     siginfo_fields: siginfo_fields_t,
 }
 
+impl Debug for siginfo_t {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("siginfo_t")
+            .field("si_signo", &self.si_signo)
+            .field("si_errno", &self.si_errno)
+            .field("si_code", &self.si_code)
+            .finish_non_exhaustive()
+    }
+}
+
 impl siginfo_t {
     pub fn new(num: SigNum, code: i32) -> Self {
         siginfo_t {
@@ -58,6 +68,22 @@ impl siginfo_t {
         // let siginfo = *self;
         read_union_fields!(self.siginfo_fields.sigfault.addr)
     }
+
+    pub fn set_si_pid(&mut self, si_pid: Pid) {
+        self.siginfo_fields.common.first.piduid.pid = si_pid;
+    }
+
+    pub fn si_pid(&self) -> Pid {
+        read_union_fields!(self.siginfo_fields.common.first.piduid.pid)
+    }
+
+    pub fn set_si_uid(&mut self, si_uid: Uid) {
+        self.siginfo_fields.common.first.piduid.uid = si_uid;
+    }
+
+    pub fn si_uid(&self) -> Uid {
+        read_union_fields!(self.siginfo_fields.common.first.piduid.uid)
+    }
 }
 
 #[derive(Clone, Copy, Pod)]
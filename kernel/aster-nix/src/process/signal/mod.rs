@@ -24,7 +24,7 @@ pub use poll::{Pollee, Poller};
 use sig_action::{SigAction, SigActionFlags, SigDefaultAction};
 use sig_mask::SigMask;
 use sig_num::SigNum;
-pub use sig_stack::{SigStack, SigStackFlags};
+pub use sig_stack::{SigStack, SigStackFlags, SigStackStatus};
 
 use super::posix_thread::{PosixThread, PosixThreadExt};
 use crate::{
@@ -90,8 +90,13 @@ pub fn handle_pending_signal(
                         current.executable_path(),
                         sig_num.sig_name()
                     );
+                    let term_status = if matches!(sig_default_action, SigDefaultAction::Core) {
+                        TermStatus::Dumped(sig_num)
+                    } else {
+                        TermStatus::Killed(sig_num)
+                    };
                     // We should exit current here, since we cannot restore a valid status from trap now.
-                    do_exit_group(TermStatus::Killed(sig_num));
+                    do_exit_group(term_status);
                 }
                 SigDefaultAction::Ign => {}
                 SigDefaultAction::Stop => {
@@ -145,7 +150,7 @@ pub fn handle_user_signal(
     posix_thread.sig_mask().lock().block(mask.as_u64());
 
     // Set up signal stack.
-    let mut stack_pointer = if let Some(sp) = use_alternate_signal_stack(posix_thread) {
+    let mut stack_pointer = if let Some(sp) = use_alternate_signal_stack(posix_thread, flags) {
         sp as u64
     } else {
         // just use user stack
@@ -215,8 +220,9 @@ pub fn handle_user_signal(
 /// Use an alternate signal stack, which was installed by sigaltstack.
 /// It the stack is already active, we just increase the handler counter and return None, since
 /// the stack pointer can be read from context.
-/// It the stack is not used by any handler, we will return the new sp in alternate signal stack.
-fn use_alternate_signal_stack(posix_thread: &PosixThread) -> Option<usize> {
+/// It the stack is not used by any handler and the handler is registered with `SA_ONSTACK`,
+/// we will return the new sp in alternate signal stack.
+fn use_alternate_signal_stack(posix_thread: &PosixThread, flags: SigActionFlags) -> Option<usize> {
     let mut sig_stack = posix_thread.sig_stack().lock();
     let sig_stack = (*sig_stack).as_mut()?;
 
@@ -230,6 +236,10 @@ fn use_alternate_signal_stack(posix_thread: &PosixThread) -> Option<usize> {
         return None;
     }
 
+    if !flags.contains(SigActionFlags::SA_ONSTACK) {
+        return None;
+    }
+
     sig_stack.increase_handler_counter();
 
     // Make sp align at 16. FIXME: is this required?
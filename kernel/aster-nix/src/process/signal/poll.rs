@@ -87,6 +87,25 @@ impl Pollee {
         self.inner.subject.register_observer(observer, mask);
     }
 
+    /// Register an IoEvents observer that is notified exclusively.
+    ///
+    /// Among all the exclusive observers of the pollee, only one of them
+    /// gets notified each time new events specified by the `mask` argument
+    /// happen on the pollee. Other observers and pollers are notified as usual.
+    ///
+    /// Note that the observer will always get notified of the events in
+    /// `IoEvents::ALWAYS_POLL` regardless of the value of `mask`.
+    pub fn register_exclusive_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) {
+        let mask = mask | IoEvents::ALWAYS_POLL;
+        self.inner
+            .subject
+            .register_exclusive_observer(observer, mask);
+    }
+
     /// Unregister an IoEvents observer.
     ///
     /// If such an observer is found, then the registered observer will be
@@ -102,10 +121,11 @@ impl Pollee {
     /// Add some events to the pollee's state.
     ///
     /// This method wakes up all registered pollers that are interested in
-    /// the added events.
+    /// the added events, together with one of the exclusive observers.
     pub fn add_events(&self, events: IoEvents) {
         self.inner.events.fetch_or(events.bits(), Ordering::Release);
         self.inner.subject.notify_observers(&events);
+        self.inner.subject.notify_exclusive_observer(&events);
     }
 
     /// Remove some events from the pollee's state.
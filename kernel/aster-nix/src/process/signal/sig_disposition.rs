@@ -6,6 +6,11 @@ use super::{constants::*, sig_action::SigAction, sig_num::SigNum};
 pub struct SigDispositions {
     // SigNum -> SigAction
     map: [SigAction; COUNT_ALL_SIGS],
+    /// Whether `SA_NOCLDWAIT` is set for `SIGCHLD`.
+    ///
+    /// It is kept apart from the action, since it also takes effect with `SIG_DFL` and `SIG_IGN`,
+    /// which carry no flags.
+    is_nocldwait: bool,
 }
 
 impl Default for SigDispositions {
@@ -18,6 +23,7 @@ impl SigDispositions {
     pub fn new() -> Self {
         Self {
             map: [SigAction::default(); COUNT_ALL_SIGS],
+            is_nocldwait: false,
         }
     }
 
@@ -31,6 +37,14 @@ impl SigDispositions {
         self.map[idx] = sa;
     }
 
+    pub fn is_nocldwait(&self) -> bool {
+        self.is_nocldwait
+    }
+
+    pub fn set_nocldwait(&mut self, is_nocldwait: bool) {
+        self.is_nocldwait = is_nocldwait;
+    }
+
     pub fn set_default(&mut self, num: SigNum) {
         let idx = Self::num_to_idx(num);
         self.map[idx] = SigAction::Dfl;
@@ -40,12 +54,15 @@ impl SigDispositions {
     /// When execve, the handled signals are reset to the default; the dispositions of
     /// ignored signals are left unchanged.
     /// This function should be used when execve.
+    ///
+    /// Like Linux, the flags of all the signals, including `SA_NOCLDWAIT`, are cleared as well.
     pub fn inherit(&mut self) {
         for sigaction in &mut self.map {
             if let SigAction::User { .. } = sigaction {
                 *sigaction = SigAction::Dfl;
             }
         }
+        self.is_nocldwait = false;
     }
 
     fn num_to_idx(num: SigNum) -> usize {
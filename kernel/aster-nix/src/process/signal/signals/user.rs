@@ -6,7 +6,7 @@ use super::Signal;
 use crate::process::{
     signal::{
         c_types::siginfo_t,
-        constants::{SI_QUEUE, SI_TKILL, SI_USER},
+        constants::{SI_TKILL, SI_USER},
         sig_num::SigNum,
     },
     Pid, Uid,
@@ -24,7 +24,9 @@ pub struct UserSignal {
 pub enum UserSignalKind {
     Kill,
     Tkill,
-    Sigqueue,
+    /// The signal is sent by `rt_sigqueueinfo` or `rt_tgsigqueueinfo`, carrying the
+    /// information supplied by the sender.
+    Sigqueue(siginfo_t),
 }
 
 impl UserSignal {
@@ -55,14 +57,16 @@ impl Signal for UserSignal {
         let code = match self.kind {
             UserSignalKind::Kill => SI_USER,
             UserSignalKind::Tkill => SI_TKILL,
-            UserSignalKind::Sigqueue => SI_QUEUE,
+            UserSignalKind::Sigqueue(mut info) => {
+                // The information is delivered as is, except for the signal number.
+                info.si_signo = self.num.as_u8() as i32;
+                return info;
+            }
         };
 
-        siginfo_t::new(self.num, code)
-        // info.set_si_pid(self.pid);
-        // info.set_si_uid(self.uid);
-        // if let UserSignalKind::Sigqueue(val) = self.kind {
-        //     info.set_si_value(val);
-        // }
+        let mut info = siginfo_t::new(self.num, code);
+        info.set_si_pid(self.pid);
+        info.set_si_uid(self.uid);
+        info
     }
 }
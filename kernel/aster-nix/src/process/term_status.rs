@@ -2,10 +2,19 @@
 
 use super::signal::sig_num::SigNum;
 
+/// The bit that indicates a core dump in the wait status.
+const CORE_DUMP_FLAG: u32 = 0x80;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TermStatus {
+    /// Exited normally with the exit status.
     Exited(u8),
+    /// Killed by a signal.
     Killed(SigNum),
+    /// Killed by a signal whose default action is to dump core.
+    //
+    // TODO: The core file is not generated yet.
+    Dumped(SigNum),
 }
 
 impl TermStatus {
@@ -14,6 +23,7 @@ impl TermStatus {
         match self {
             TermStatus::Exited(status) => (*status as u32) << 8,
             TermStatus::Killed(signum) => signum.as_u8() as u32,
+            TermStatus::Dumped(signum) => signum.as_u8() as u32 | CORE_DUMP_FLAG,
         }
     }
 }
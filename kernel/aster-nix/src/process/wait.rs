@@ -3,7 +3,14 @@
 #![allow(dead_code)]
 
 use super::{process_filter::ProcessFilter, ExitCode, Pid, Process};
-use crate::{prelude::*, process::process_table, thread::thread_table};
+use crate::{
+    prelude::*,
+    process::{
+        process_table,
+        signal::{constants::SIGCHLD, sig_action::SigAction},
+    },
+    thread::thread_table,
+};
 
 // The definition of WaitOptions is from Occlum
 bitflags! {
@@ -30,6 +37,8 @@ pub fn wait_child_exit(
 ) -> Result<Option<Arc<Process>>> {
     let current = current!();
     let zombie_child = current.children_pauser().pause_until(|| {
+        reap_auto_reaped_children(&current);
+
         let unwaited_children = current
             .children()
             .lock()
@@ -57,8 +66,9 @@ pub fn wait_child_exit(
             if wait_options.contains(WaitOptions::WNOWAIT) {
                 // does not reap child, directly return
                 return Some(Ok(Some(zombie_child.clone())));
-            } else {
-                reap_zombie_child(&current, zombie_pid);
+            }
+            // The child may have been reaped by other threads of the process.
+            if reap_zombie_child(&current, zombie_pid).is_some() {
                 return Some(Ok(Some(zombie_child.clone())));
             }
         }
@@ -74,11 +84,46 @@ pub fn wait_child_exit(
     Ok(zombie_child)
 }
 
+/// Reaps the zombie children of the process if they are reaped automatically.
+///
+/// Like Linux, the children are reaped automatically if the process ignores `SIGCHLD` or sets
+/// `SA_NOCLDWAIT` for it. Then, the process cannot wait for its children, and `wait` blocks
+/// until all the children exit and fails with `ECHILD`.
+///
+/// Unlike Linux, an exiting child cannot reap itself, since it is still running. Instead, the
+/// zombie children are reaped by the parent when it waits for or creates a child.
+pub(super) fn reap_auto_reaped_children(process: &Process) {
+    if !is_auto_reaping(process) {
+        return;
+    }
+
+    let zombie_pids = process
+        .children()
+        .lock()
+        .values()
+        .filter(|child| child.is_zombie())
+        .map(|child| child.pid())
+        .collect::<Vec<_>>();
+    for pid in zombie_pids {
+        reap_zombie_child(process, pid);
+    }
+}
+
+fn is_auto_reaping(process: &Process) -> bool {
+    let sig_dispositions = process.sig_dispositions().lock();
+    sig_dispositions.get(SIGCHLD) == SigAction::Ign || sig_dispositions.is_nocldwait()
+}
+
 /// Free zombie child with pid, returns the exit code of child process.
-fn reap_zombie_child(process: &Process, pid: Pid) -> ExitCode {
-    let child_process = process.children().lock().remove(&pid).unwrap();
+///
+/// If the child has already been reaped, this function returns `None`.
+fn reap_zombie_child(process: &Process, pid: Pid) -> Option<ExitCode> {
+    let child_process = process.children().lock().remove(&pid)?;
     assert!(child_process.is_zombie());
-    child_process.root_vmar().destroy_all().unwrap();
+    // The memory cannot be destroyed if it is still used by other processes.
+    if !child_process.is_vm_shared() {
+        child_process.root_vmar().destroy_all().unwrap();
+    }
     for thread in &*child_process.threads().lock() {
         thread_table::remove_thread(thread.tid());
     }
@@ -110,5 +155,5 @@ fn reap_zombie_child(process: &Process, pid: Pid) -> ExitCode {
     }
 
     process_table_mut.remove(&child_process.pid());
-    child_process.exit_code().unwrap()
+    Some(child_process.exit_code().unwrap())
 }
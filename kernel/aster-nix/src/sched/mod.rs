@@ -2,7 +2,11 @@
 
 pub mod nice;
 mod priority_scheduler;
+pub mod stats;
 
-// There may be multiple scheduling policies in the system,
-// and subsequent schedulers can be placed under this module.
-pub use self::priority_scheduler::init;
+pub fn init() {
+    // There may be multiple scheduling policies in the system,
+    // and subsequent schedulers can be placed under this module.
+    priority_scheduler::init();
+    stats::init();
+}
@@ -3,6 +3,7 @@
 use intrusive_collections::LinkedList;
 use ostd::task::{set_scheduler, Scheduler, Task, TaskAdapter};
 
+use super::stats;
 use crate::prelude::*;
 
 pub fn init() {
@@ -43,11 +44,16 @@ impl Scheduler for PreemptScheduler {
     }
 
     fn dequeue(&self) -> Option<Arc<Task>> {
-        if !self.real_time_tasks.lock_irq_disabled().is_empty() {
+        let next_task = if !self.real_time_tasks.lock_irq_disabled().is_empty() {
             self.real_time_tasks.lock_irq_disabled().pop_front()
         } else {
             self.normal_tasks.lock_irq_disabled().pop_front()
+        };
+        // The current task is always switched to the dequeued task.
+        if next_task.is_some() {
+            stats::count_context_switch();
         }
+        next_task
     }
 
     fn should_preempt(&self, task: &Arc<Task>) -> bool {
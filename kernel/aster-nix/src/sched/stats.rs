@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The statistics of the scheduler, which are reported to user space by `/proc/stat`.
+
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use ostd::{
+    arch::{
+        timer::{self, TIMER_FREQ},
+        x86::trap::is_kernel_interrupted,
+    },
+    cpu::{num_cpus, this_cpu},
+};
+use spin::Once;
+
+use crate::{prelude::*, process::posix_thread::PosixThreadExt, thread::Thread};
+
+/// The CPU time statistics of each CPU, indexed by the CPU ID.
+static CPU_TIME_STATS: Once<Vec<CpuTimeStats>> = Once::new();
+
+/// The number of context switches since boot.
+static NR_CONTEXT_SWITCHES: AtomicU64 = AtomicU64::new(0);
+
+/// The number of timer ticks that a CPU spends in each mode.
+#[derive(Default)]
+struct CpuTimeStats {
+    user: AtomicU64,
+    system: AtomicU64,
+    idle: AtomicU64,
+}
+
+/// The time that one or more CPUs spend in each mode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CpuTime {
+    pub user: Duration,
+    pub system: Duration,
+    pub idle: Duration,
+}
+
+impl CpuTimeStats {
+    fn cpu_time(&self) -> CpuTime {
+        let ticks_to_duration = |ticks: &AtomicU64| {
+            Duration::from_millis(ticks.load(Ordering::Relaxed) * 1000 / TIMER_FREQ)
+        };
+
+        CpuTime {
+            user: ticks_to_duration(&self.user),
+            system: ticks_to_duration(&self.system),
+            idle: ticks_to_duration(&self.idle),
+        }
+    }
+}
+
+pub(super) fn init() {
+    CPU_TIME_STATS.call_once(|| (0..num_cpus()).map(|_| CpuTimeStats::default()).collect());
+    timer::register_callback(update_cpu_time_stats);
+}
+
+/// Accounts the current timer tick to the mode that the current CPU is in.
+///
+/// This function will be invoked at the system timer interrupt.
+fn update_cpu_time_stats() {
+    let stats = &CPU_TIME_STATS.get().unwrap()[this_cpu() as usize];
+
+    let ticks = if Thread::current().as_posix_thread().is_none() {
+        // There is no idle task yet. When no user task is runnable, the CPU keeps
+        // running kernel threads, e.g., the init thread that yields in a loop. So
+        // the time spent in kernel threads is regarded as idle.
+        &stats.idle
+    } else if is_kernel_interrupted() {
+        &stats.system
+    } else {
+        &stats.user
+    };
+    ticks.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records a context switch.
+pub(super) fn count_context_switch() {
+    NR_CONTEXT_SWITCHES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the number of context switches since boot.
+pub fn nr_context_switches() -> u64 {
+    NR_CONTEXT_SWITCHES.load(Ordering::Relaxed)
+}
+
+/// Returns the time that the CPU with `cpu_id` spends in each mode.
+pub fn cpu_time(cpu_id: u32) -> CpuTime {
+    CPU_TIME_STATS.get().unwrap()[cpu_id as usize].cpu_time()
+}
+
+/// Returns the time that all the CPUs spend in each mode.
+pub fn total_cpu_time() -> CpuTime {
+    (0..num_cpus())
+        .map(cpu_time)
+        .fold(CpuTime::default(), |total, cpu_time| CpuTime {
+            user: total.user + cpu_time.user,
+            system: total.system + cpu_time.system,
+            idle: total.idle + cpu_time.idle,
+        })
+}
@@ -5,9 +5,10 @@ use crate::{
     fs::{
         file_table::FileDesc,
         fs_resolver::{FsPath, AT_FDCWD},
-        utils::PATH_MAX,
+        utils::{Permission, PATH_MAX},
     },
     prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
     util::read_cstring_from_user,
 };
 
@@ -20,6 +21,20 @@ pub fn sys_faccessat(dirfd: FileDesc, path_ptr: Vaddr, mode: u16) -> Result<Sysc
     do_faccessat(dirfd, path_ptr, mode, 0)
 }
 
+pub fn sys_faccessat2(
+    dirfd: FileDesc,
+    path_ptr: Vaddr,
+    mode: u16,
+    flags: i32,
+) -> Result<SyscallReturn> {
+    debug!(
+        "faccessat2: dirfd = {}, path_ptr = {:#x}, mode = {:o}, flags = {}",
+        dirfd, path_ptr, mode, flags
+    );
+
+    do_faccessat(dirfd, path_ptr, mode, flags)
+}
+
 pub fn sys_access(path_ptr: Vaddr, mode: u16) -> Result<SyscallReturn> {
     debug!("access: path_ptr = {:#x}, mode = {:o}", path_ptr, mode);
 
@@ -64,6 +79,9 @@ pub fn do_faccessat(
     let current = current!();
     let dentry = {
         let path = path.to_string_lossy();
+        if path.is_empty() && !flags.contains(FaccessatFlags::AT_EMPTY_PATH) {
+            return_errno_with_message!(Errno::ENOENT, "path is empty");
+        }
         let fs_path = FsPath::new(dirfd, path.as_ref())?;
         let fs = current.fs().read();
         if flags.contains(FaccessatFlags::AT_SYMLINK_NOFOLLOW) {
@@ -77,19 +95,34 @@ pub fn do_faccessat(
         return Ok(SyscallReturn::Return(0));
     }
 
-    let inode_mode = dentry.mode()?;
-
-    // FIXME: The current implementation is dummy
-    if mode.contains(AccessMode::R_OK) && !inode_mode.is_readable() {
-        return_errno_with_message!(Errno::EACCES, "Read permission denied");
-    }
-
-    if mode.contains(AccessMode::W_OK) && !inode_mode.is_writable() {
-        return_errno_with_message!(Errno::EACCES, "Write permission denied");
-    }
+    // The bits of `AccessMode` share the same values as those of `Permission`.
+    let perm = Permission::from_bits_truncate(mode.bits());
 
-    if mode.contains(AccessMode::X_OK) && !inode_mode.is_executable() {
-        return_errno_with_message!(Errno::EACCES, "Execute permission denied");
+    // By default, the check is done as the real user, so that a set-user-ID program can
+    // check whether the user who invokes it can access the file. Like Linux, the real user
+    // has the permitted capabilities if it is root, and no capabilities otherwise.
+    let credentials = credentials();
+    if flags.contains(FaccessatFlags::AT_EACCESS) {
+        dentry.inode().check_permission(perm)?;
+    } else {
+        let user_ns = credentials.user_ns();
+        let capset = if user_ns.is_root_uid(credentials.ruid()) {
+            let inode = dentry.inode();
+            user_ns.capset_over(
+                credentials.permitted_capset(),
+                inode.owner()?,
+                inode.group()?,
+            )
+        } else {
+            CapSet::empty()
+        };
+        dentry.inode().check_permission_as(
+            perm,
+            credentials.ruid(),
+            credentials.rgid(),
+            &credentials.groups(),
+            capset,
+        )?;
     }
 
     Ok(SyscallReturn::Return(0))
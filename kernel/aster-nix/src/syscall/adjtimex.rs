@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use ostd::arch::timer::TIMER_FREQ;
+
+use super::SyscallReturn;
+use crate::{
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+    time::{clocks::RealTimeClock, timeval_t, Clock},
+    util::{read_val_from_user, write_val_to_user},
+};
+
+pub fn sys_adjtimex(timex_addr: Vaddr) -> Result<SyscallReturn> {
+    let mut timex = read_val_from_user::<timex_t>(timex_addr)?;
+    let modes = AdjtimexModes::from_bits_truncate(timex.modes);
+    debug!("modes = {:?}", modes);
+
+    // Only reading the state or the pending `adjtime` offset is unprivileged.
+    let is_read_only = modes.is_empty() || modes.contains(AdjtimexModes::OFFSET_SS_READ);
+    if !is_read_only && !credentials().capable(CapSet::SYS_TIME) {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_TIME is required");
+    }
+    if modes.contains(AdjtimexModes::SETOFFSET) {
+        return_errno_with_message!(Errno::EOPNOTSUPP, "stepping the clock is not supported");
+    }
+
+    let mut state = NTP_STATE.lock_irq_disabled();
+    if modes.contains(AdjtimexModes::ADJTIME) {
+        // The `adjtime` interface, which only deals with a single-shot offset.
+        let old_offset = state.adjtime_offset;
+        if !modes.contains(AdjtimexModes::OFFSET_SS_READ) {
+            state.adjtime_offset = timex.offset;
+        }
+        state.fill(&mut timex);
+        timex.offset = old_offset;
+    } else {
+        state.update(modes, &timex);
+        state.fill(&mut timex);
+    }
+    drop(state);
+
+    write_val_to_user(timex_addr, &timex)?;
+
+    // TODO: Discipline the clock with the stored state. Before that, the clock
+    // is always reported to be synchronized.
+    Ok(SyscallReturn::Return(TIME_OK as _))
+}
+
+/// The clock is synchronized and no leap second is pending.
+const TIME_OK: i32 = 0;
+
+bitflags::bitflags! {
+    struct AdjtimexModes: u32 {
+        const OFFSET    = 0x0001;
+        const FREQUENCY = 0x0002;
+        const MAXERROR  = 0x0004;
+        const ESTERROR  = 0x0008;
+        const STATUS    = 0x0010;
+        const TIMECONST = 0x0020;
+        const TAI       = 0x0080;
+        const SETOFFSET = 0x0100;
+        const MICRO     = 0x1000;
+        const NANO      = 0x2000;
+        const TICK      = 0x4000;
+        /// The mode used by `adjtime`.
+        const ADJTIME   = 0x8000;
+        const OFFSET_SINGLESHOT = Self::OFFSET.bits | Self::ADJTIME.bits;
+        const OFFSET_SS_READ    = Self::OFFSET_SINGLESHOT.bits | Self::NANO.bits;
+    }
+}
+
+/// The clock is not synchronized.
+const STA_UNSYNC: i32 = 0x0040;
+/// The offsets are in nanoseconds instead of microseconds.
+const STA_NANO: i32 = 0x2000;
+
+/// The maximum frequency tolerance of the clock, which is 500 PPM scaled by 2^16.
+const MAX_TOLERANCE: i64 = 500 << 16;
+/// The maximum error of an unsynchronized clock in microseconds.
+const NTP_PHASE_LIMIT: i64 = 16_000_000;
+
+/// The NTP state passed by `adjtimex`.
+///
+/// The values are only stored and reported back, they do not adjust any clock yet.
+struct NtpState {
+    offset: i64,
+    freq: i64,
+    maxerror: i64,
+    esterror: i64,
+    status: i32,
+    constant: i64,
+    tick: i64,
+    tai: i32,
+    adjtime_offset: i64,
+}
+
+static NTP_STATE: SpinLock<NtpState> = SpinLock::new(NtpState {
+    offset: 0,
+    freq: 0,
+    maxerror: NTP_PHASE_LIMIT,
+    esterror: NTP_PHASE_LIMIT,
+    status: STA_UNSYNC,
+    constant: 2,
+    tick: 1_000_000 / TIMER_FREQ as i64,
+    tai: 0,
+    adjtime_offset: 0,
+});
+
+impl NtpState {
+    fn update(&mut self, modes: AdjtimexModes, timex: &timex_t) {
+        if modes.contains(AdjtimexModes::STATUS) {
+            self.status = timex.status;
+        }
+        if modes.contains(AdjtimexModes::NANO) {
+            self.status |= STA_NANO;
+        }
+        if modes.contains(AdjtimexModes::MICRO) {
+            self.status &= !STA_NANO;
+        }
+        if modes.contains(AdjtimexModes::OFFSET) {
+            self.offset = timex.offset;
+        }
+        if modes.contains(AdjtimexModes::FREQUENCY) {
+            self.freq = timex.freq;
+        }
+        if modes.contains(AdjtimexModes::MAXERROR) {
+            self.maxerror = timex.maxerror;
+        }
+        if modes.contains(AdjtimexModes::ESTERROR) {
+            self.esterror = timex.esterror;
+        }
+        if modes.contains(AdjtimexModes::TIMECONST) {
+            self.constant = timex.constant;
+        }
+        if modes.contains(AdjtimexModes::TICK) {
+            self.tick = timex.tick;
+        }
+        if modes.contains(AdjtimexModes::TAI) {
+            self.tai = timex.constant as i32;
+        }
+    }
+
+    fn fill(&self, timex: &mut timex_t) {
+        timex.offset = self.offset;
+        timex.freq = self.freq;
+        timex.maxerror = self.maxerror;
+        timex.esterror = self.esterror;
+        timex.status = self.status;
+        timex.constant = self.constant;
+        timex.precision = 1;
+        timex.tolerance = MAX_TOLERANCE;
+        timex.tick = self.tick;
+        timex.tai = self.tai;
+
+        let now = RealTimeClock::get().read_time();
+        timex.time = timeval_t::from(now);
+        if self.status & STA_NANO != 0 {
+            // The field holds nanoseconds in this case.
+            timex.time.usec = now.subsec_nanos() as i64;
+        }
+    }
+}
+
+/// This struct is corresponding to the `timex` struct in Linux.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+struct timex_t {
+    modes: u32,
+    _pad0: u32,
+    offset: i64,
+    freq: i64,
+    maxerror: i64,
+    esterror: i64,
+    status: i32,
+    _pad1: u32,
+    constant: i64,
+    precision: i64,
+    tolerance: i64,
+    time: timeval_t,
+    tick: i64,
+    ppsfreq: i64,
+    jitter: i64,
+    shift: i32,
+    _pad2: u32,
+    stabil: i64,
+    jitcnt: i64,
+    calcnt: i64,
+    errcnt: i64,
+    stbcnt: i64,
+    tai: i32,
+    _reserved: [i32; 11],
+}
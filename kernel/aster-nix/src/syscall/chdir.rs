@@ -2,7 +2,12 @@
 
 use super::SyscallReturn;
 use crate::{
-    fs::{file_table::FileDesc, fs_resolver::FsPath, inode_handle::InodeHandle, utils::InodeType},
+    fs::{
+        file_table::FileDesc,
+        fs_resolver::FsPath,
+        inode_handle::InodeHandle,
+        utils::{InodeType, Permission},
+    },
     prelude::*,
     syscall::constants::MAX_FILENAME_LEN,
     util::read_cstring_from_user,
@@ -25,6 +30,7 @@ pub fn sys_chdir(path_ptr: Vaddr) -> Result<SyscallReturn> {
     if dentry.type_() != InodeType::Dir {
         return_errno_with_message!(Errno::ENOTDIR, "must be directory");
     }
+    dentry.inode().check_permission(Permission::MAY_EXEC)?;
     fs.set_cwd(dentry);
     Ok(SyscallReturn::Return(0))
 }
@@ -44,6 +50,7 @@ pub fn sys_fchdir(fd: FileDesc) -> Result<SyscallReturn> {
     if dentry.type_() != InodeType::Dir {
         return_errno_with_message!(Errno::ENOTDIR, "must be directory");
     }
+    dentry.inode().check_permission(Permission::MAY_EXEC)?;
     current.fs().write().set_cwd(dentry);
     Ok(SyscallReturn::Return(0))
 }
@@ -8,15 +8,16 @@ use crate::{
         utils::PATH_MAX,
     },
     prelude::*,
-    process::{Gid, Uid},
+    process::{credentials, Gid, Uid},
     util::read_cstring_from_user,
 };
 
 pub fn sys_fchown(fd: FileDesc, uid: i32, gid: i32) -> Result<SyscallReturn> {
     debug!("fd = {}, uid = {}, gid = {}", fd, uid, gid);
 
-    let uid = to_optional_id(uid, Uid::new)?;
-    let gid = to_optional_id(gid, Gid::new)?;
+    let user_ns = credentials().user_ns();
+    let uid = to_optional_id(uid, |uid| user_ns.uid_to_kernel(Uid::new(uid)))?;
+    let gid = to_optional_id(gid, |gid| user_ns.gid_to_kernel(Gid::new(gid)))?;
     if uid.is_none() && gid.is_none() {
         return Ok(SyscallReturn::Return(0));
     }
@@ -69,8 +70,9 @@ pub fn sys_fchownat(
         return self::sys_fchown(dirfd, uid, gid);
     }
 
-    let uid = to_optional_id(uid, Uid::new)?;
-    let gid = to_optional_id(gid, Gid::new)?;
+    let user_ns = credentials().user_ns();
+    let uid = to_optional_id(uid, |uid| user_ns.uid_to_kernel(Uid::new(uid)))?;
+    let gid = to_optional_id(gid, |gid| user_ns.gid_to_kernel(Gid::new(gid)))?;
     if uid.is_none() && gid.is_none() {
         return Ok(SyscallReturn::Return(0));
     }
@@ -95,9 +97,10 @@ pub fn sys_fchownat(
     Ok(SyscallReturn::Return(0))
 }
 
-fn to_optional_id<T>(id: i32, f: impl Fn(u32) -> T) -> Result<Option<T>> {
+/// Converts the ID to the kernel ID with `f`, or returns `None` if the ID is -1.
+fn to_optional_id<T>(id: i32, f: impl Fn(u32) -> Result<T>) -> Result<Option<T>> {
     let id = if id >= 0 {
-        Some(f(id as u32))
+        Some(f(id as u32)?)
     } else if id == -1 {
         // If the owner or group is specified as -1, then that ID is not changed.
         None
@@ -2,8 +2,12 @@
 
 use super::SyscallReturn;
 use crate::{
-    fs::{fs_resolver::FsPath, utils::InodeType},
+    fs::{
+        fs_resolver::FsPath,
+        utils::{InodeType, Permission},
+    },
     prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
     syscall::constants::MAX_FILENAME_LEN,
     util::read_cstring_from_user,
 };
@@ -25,6 +29,13 @@ pub fn sys_chroot(path_ptr: Vaddr) -> Result<SyscallReturn> {
     if dentry.type_() != InodeType::Dir {
         return_errno_with_message!(Errno::ENOTDIR, "must be directory");
     }
+    dentry.inode().check_permission(Permission::MAY_EXEC)?;
+    if !credentials()
+        .effective_capset()
+        .contains(CapSet::SYS_CHROOT)
+    {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_CHROOT is required");
+    }
     fs.set_root(dentry);
     Ok(SyscallReturn::Return(0))
 }
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::time::Duration;
+
+use ostd::arch::timer::TIMER_FREQ;
+
+use super::{
+    clock_gettime::{ClockId, DynamicClockIdInfo, DynamicClockType},
+    SyscallReturn,
+};
+use crate::{
+    prelude::*,
+    process::process_table,
+    thread::thread_table,
+    time::{clockid_t, timespec_t},
+    util::write_val_to_user,
+};
+
+pub fn sys_clock_getres(clockid: clockid_t, res_addr: Vaddr) -> Result<SyscallReturn> {
+    debug!("clockid = {:?}, res_addr = 0x{:x}", clockid, res_addr);
+
+    let resolution = clock_resolution(clockid)?;
+
+    // A null pointer is allowed, in which case only the clock ID is checked.
+    if res_addr != 0 {
+        let res = timespec_t::from(resolution);
+        write_val_to_user(res_addr, &res)?;
+    }
+
+    Ok(SyscallReturn::Return(0))
+}
+
+/// The resolution of the high-resolution clocks, which read the clocksource directly.
+const HIGH_RES_RESOLUTION: Duration = Duration::from_nanos(1);
+
+/// The resolution of the clocks that are updated in each system timer interruption,
+/// including the coarse clocks and the CPU-time clocks.
+const TICK_RESOLUTION: Duration = Duration::from_millis(1000 / TIMER_FREQ);
+
+/// Returns the resolution of a clock specified by the input clock ID.
+///
+/// If the clock ID does not support, this function will return `Err`.
+fn clock_resolution(clockid: clockid_t) -> Result<Duration> {
+    if clockid >= 0 {
+        let clock_id = ClockId::try_from(clockid)?;
+        let resolution = match clock_id {
+            ClockId::CLOCK_REALTIME
+            | ClockId::CLOCK_MONOTONIC
+            | ClockId::CLOCK_MONOTONIC_RAW
+            | ClockId::CLOCK_BOOTTIME => HIGH_RES_RESOLUTION,
+            ClockId::CLOCK_REALTIME_COARSE
+            | ClockId::CLOCK_MONOTONIC_COARSE
+            | ClockId::CLOCK_PROCESS_CPUTIME_ID
+            | ClockId::CLOCK_THREAD_CPUTIME_ID => TICK_RESOLUTION,
+        };
+        return Ok(resolution);
+    }
+
+    let clock_type = match DynamicClockIdInfo::try_from(clockid)? {
+        DynamicClockIdInfo::Pid(pid, clock_type) => {
+            process_table::get_process(pid)
+                .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid clock ID"))?;
+            clock_type
+        }
+        DynamicClockIdInfo::Tid(tid, clock_type) => {
+            thread_table::get_thread(tid)
+                .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid clock ID"))?;
+            clock_type
+        }
+        DynamicClockIdInfo::Fd(_) => {
+            return_errno_with_message!(Errno::EINVAL, "FD clocks are not supported")
+        }
+    };
+
+    match clock_type {
+        DynamicClockType::Profiling | DynamicClockType::Virtual => Ok(TICK_RESOLUTION),
+        // TODO: support scheduling clock.
+        _ => return_errno_with_message!(Errno::EINVAL, "the clock type is not supported"),
+    }
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::time::Duration;
+
+use super::{clock_gettime::ClockId, SyscallReturn};
+use crate::{
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+    time::{clockid_t, clocks::RealTimeClock, timespec_t},
+    util::read_val_from_user,
+};
+
+pub fn sys_clock_settime(clockid: clockid_t, timespec_addr: Vaddr) -> Result<SyscallReturn> {
+    let timespec = read_val_from_user::<timespec_t>(timespec_addr)?;
+    debug!("clockid = {:?}, timespec = {:?}", clockid, timespec);
+
+    // Only the real-time clock can be set.
+    if !matches!(ClockId::try_from(clockid), Ok(ClockId::CLOCK_REALTIME)) {
+        return_errno_with_message!(Errno::EINVAL, "the clock cannot be set");
+    }
+    if timespec.sec < 0 || !(0..NANOS_PER_SEC).contains(&timespec.nsec) {
+        return_errno_with_message!(Errno::EINVAL, "the time is invalid");
+    }
+
+    set_realtime(Duration::from(timespec))?;
+
+    Ok(SyscallReturn::Return(0))
+}
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// Sets the real time, which requires `CAP_SYS_TIME`.
+pub(super) fn set_realtime(time: Duration) -> Result<()> {
+    if !credentials().capable(CapSet::SYS_TIME) {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_TIME is required");
+    }
+
+    RealTimeClock::get().set_time(time)
+}
@@ -18,7 +18,7 @@ use super::SyscallReturn;
 use crate::{
     events::{IoEvents, Observer},
     fs::{
-        file_handle::FileLike,
+        file_handle::{FileCount, FileLike},
         file_table::{FdFlags, FileDesc},
         utils::{CreationFlags, InodeMode, InodeType, Metadata, StatusFlags},
     },
@@ -33,7 +33,7 @@ use crate::{
 pub fn sys_eventfd(init_val: u64) -> Result<SyscallReturn> {
     debug!("init_val = 0x{:x}", init_val);
 
-    let fd = do_sys_eventfd2(init_val, Flags::empty());
+    let fd = do_sys_eventfd2(init_val, Flags::empty())?;
 
     Ok(SyscallReturn::Return(fd as _))
 }
@@ -44,24 +44,21 @@ pub fn sys_eventfd2(init_val: u64, flags: u32) -> Result<SyscallReturn> {
         .ok_or_else(|| Error::with_message(Errno::EINVAL, "unknown flags"))?;
     debug!("init_val = 0x{:x}, flags = {:?}", init_val, flags);
 
-    let fd = do_sys_eventfd2(init_val, flags);
+    let fd = do_sys_eventfd2(init_val, flags)?;
 
     Ok(SyscallReturn::Return(fd as _))
 }
 
-fn do_sys_eventfd2(init_val: u64, flags: Flags) -> FileDesc {
+fn do_sys_eventfd2(init_val: u64, flags: Flags) -> Result<FileDesc> {
     let event_file = EventFile::new(init_val, flags);
-    let fd = {
-        let current = current!();
-        let mut file_table = current.file_table().lock();
-        let fd_flags = if flags.contains(Flags::EFD_CLOEXEC) {
-            FdFlags::CLOEXEC
-        } else {
-            FdFlags::empty()
-        };
-        file_table.insert(Arc::new(event_file), fd_flags)
+    let current = current!();
+    let mut file_table = current.file_table().lock();
+    let fd_flags = if flags.contains(Flags::EFD_CLOEXEC) {
+        FdFlags::CLOEXEC
+    } else {
+        FdFlags::empty()
     };
-    fd
+    file_table.insert(Arc::new(event_file), fd_flags)
 }
 
 bitflags! {
@@ -77,6 +74,7 @@ struct EventFile {
     pollee: Pollee,
     flags: Mutex<Flags>,
     write_pauser: Arc<Pauser>,
+    _file_count: FileCount,
 }
 
 impl EventFile {
@@ -91,6 +89,7 @@ impl EventFile {
             pollee,
             flags: Mutex::new(flags),
             write_pauser,
+            _file_count: FileCount::new(),
         }
     }
 
@@ -1,5 +1,8 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use alloc::format;
+use core::{mem, sync::atomic::Ordering};
+
 use aster_rights::WriteOp;
 use ostd::{cpu::UserContext, user::UserContextApi};
 
@@ -9,16 +12,17 @@ use crate::{
     fs::{
         file_table::FileDesc,
         fs_resolver::{FsPath, AT_FDCWD},
-        path::Dentry,
-        utils::InodeType,
+        path::{Dentry, PerMountFlags},
+        utils::{InodeMode, InodeType},
     },
     prelude::*,
     process::{
-        check_executable_file, credentials_mut, load_program_to_vm,
+        aslr, check_executable_file, credentials, credentials_mut, load_program_to_vm,
         posix_thread::{PosixThreadExt, ThreadName},
-        Credentials, Process, MAX_ARGV_NUMBER, MAX_ARG_LEN, MAX_ENVP_NUMBER, MAX_ENV_LEN,
+        Credentials, Process, ProcessVm, ProgramLoadOptions, MAX_ARGS_SIZE, MAX_ARGV_NUMBER,
+        MAX_ARG_LEN, MAX_ENVP_NUMBER, MAX_ENV_LEN,
     },
-    util::{read_cstring_from_user, read_val_from_user},
+    util::{read_path_from_user, read_val_from_user, try_read_cstring_from_user},
 };
 
 pub fn sys_execve(
@@ -27,12 +31,10 @@ pub fn sys_execve(
     envp_ptr_ptr: Vaddr,
     context: &mut UserContext,
 ) -> Result<SyscallReturn> {
-    let elf_file = {
-        let executable_path = read_filename(filename_ptr)?;
-        lookup_executable_file(AT_FDCWD, executable_path, OpenFlags::empty())?
-    };
+    let filename = read_filename(filename_ptr)?;
+    let elf_file = lookup_executable_file(AT_FDCWD, &filename, OpenFlags::empty())?;
 
-    do_execve(elf_file, argv_ptr_ptr, envp_ptr_ptr, context)?;
+    do_execve(elf_file, &filename, argv_ptr_ptr, envp_ptr_ptr, context)?;
     Ok(SyscallReturn::NoReturn)
 }
 
@@ -44,27 +46,31 @@ pub fn sys_execveat(
     flags: u32,
     context: &mut UserContext,
 ) -> Result<SyscallReturn> {
-    let elf_file = {
-        let flags = OpenFlags::from_bits_truncate(flags);
-        let filename = read_filename(filename_ptr)?;
-        lookup_executable_file(dfd, filename, flags)?
+    let flags = OpenFlags::from_bits_truncate(flags);
+    let filename = read_filename(filename_ptr)?;
+    let elf_file = lookup_executable_file(dfd, &filename, flags)?;
+
+    // Like Linux, the file that is specified by a file descriptor is named after the path of
+    // the file descriptor in `/dev/fd`.
+    let filename = if filename.starts_with('/') || dfd == AT_FDCWD {
+        filename
+    } else if filename.is_empty() {
+        format!("/dev/fd/{}", dfd)
+    } else {
+        format!("/dev/fd/{}/{}", dfd, filename)
     };
 
-    do_execve(elf_file, argv_ptr_ptr, envp_ptr_ptr, context)?;
+    do_execve(elf_file, &filename, argv_ptr_ptr, envp_ptr_ptr, context)?;
     Ok(SyscallReturn::NoReturn)
 }
 
-fn lookup_executable_file(
-    dfd: FileDesc,
-    filename: String,
-    flags: OpenFlags,
-) -> Result<Arc<Dentry>> {
+fn lookup_executable_file(dfd: FileDesc, filename: &str, flags: OpenFlags) -> Result<Arc<Dentry>> {
     let current = current!();
     let fs_resolver = current.fs().read();
     let dentry = if flags.contains(OpenFlags::AT_EMPTY_PATH) && filename.is_empty() {
         fs_resolver.lookup_from_fd(dfd)
     } else {
-        let fs_path = FsPath::new(dfd, &filename)?;
+        let fs_path = FsPath::new(dfd, filename)?;
         if flags.contains(OpenFlags::AT_SYMLINK_NOFOLLOW) {
             let dentry = fs_resolver.lookup_no_follow(&fs_path)?;
             if dentry.type_() == InodeType::SymLink {
@@ -81,13 +87,15 @@ fn lookup_executable_file(
 
 fn do_execve(
     elf_file: Arc<Dentry>,
+    filename: &str,
     argv_ptr_ptr: Vaddr,
     envp_ptr_ptr: Vaddr,
     context: &mut UserContext,
 ) -> Result<()> {
     let executable_path = elf_file.abs_path();
-    let argv = read_cstring_vec(argv_ptr_ptr, MAX_ARGV_NUMBER, MAX_ARG_LEN)?;
-    let envp = read_cstring_vec(envp_ptr_ptr, MAX_ENVP_NUMBER, MAX_ENV_LEN)?;
+    let mut args_size = 0;
+    let argv = read_cstring_vec(argv_ptr_ptr, MAX_ARGV_NUMBER, MAX_ARG_LEN, &mut args_size)?;
+    let envp = read_cstring_vec(envp_ptr_ptr, MAX_ENVP_NUMBER, MAX_ENV_LEN, &mut args_size)?;
     debug!(
         "filename: {:?}, argv = {:?}, envp = {:?}",
         executable_path, argv, envp
@@ -109,21 +117,53 @@ fn do_execve(
         file.clean_for_close()?;
     }
 
+    let is_secure = is_secure_exec(&elf_file)?;
+    // Like Linux, a secure program cannot inherit the personality flags
+    // that weaken its security, such as `ADDR_NO_RANDOMIZE`.
+    let personality = {
+        let personality = current.personality().load(Ordering::Relaxed);
+        if is_secure {
+            personality & !aslr::PER_CLEAR_ON_SETID
+        } else {
+            personality
+        }
+    };
+
     debug!("load program to root vmar");
     let (new_executable_path, elf_load_info) = {
         let fs_resolver = &*current.fs().read();
-        let process_vm = current.vm();
-        load_program_to_vm(process_vm, elf_file.clone(), argv, envp, fs_resolver, 1)?
+        // Like Linux, the new program runs in a new process vm, so the memory shared with
+        // other processes, such as the parent of a vfork child, is left intact.
+        let process_vm = ProcessVm::alloc();
+        let load_result = load_program_to_vm(
+            &process_vm,
+            elf_file.clone(),
+            argv,
+            envp,
+            fs_resolver,
+            ProgramLoadOptions::new(filename)
+                .is_secure(is_secure)
+                .personality(personality),
+        )?;
+        current.set_vm(process_vm);
+        load_result
     };
 
+    current.personality().store(personality, Ordering::Relaxed);
+
     // After the program has been successfully loaded, the virtual memory of the current process
     // is initialized. Hence, it is necessary to clear the previously recorded robust list.
     *posix_thread.robust_list().lock() = None;
+    // The alternate signal stack lies in the old virtual memory, so it is cleared as well.
+    *posix_thread.sig_stack().lock() = None;
+    // The parent of a vfork child can be resumed once the child no longer uses its memory.
+    current.reset_vfork_child();
     debug!("load elf in execve succeeds");
 
     let credentials = credentials_mut();
     set_uid_from_elf(&current, &credentials, &elf_file)?;
     set_gid_from_elf(&current, &credentials, &elf_file)?;
+    credentials.update_capsets_for_exec();
 
     // set executable path
     current.set_executable_path(new_executable_path);
@@ -151,14 +191,24 @@ bitflags::bitflags! {
 }
 
 fn read_filename(filename_ptr: Vaddr) -> Result<String> {
-    let filename = read_cstring_from_user(filename_ptr, MAX_FILENAME_LEN)?;
+    let filename = read_path_from_user(filename_ptr, MAX_FILENAME_LEN)?;
     Ok(filename.into_string().unwrap())
 }
 
+/// Reads a null-terminated vector of strings, such as `argv` and `envp`.
+///
+/// The size of the strings (including the null terminators) and the pointers to them is added
+/// to `total_size`, which is shared between `argv` and `envp`. If the total size exceeds
+/// `MAX_ARGS_SIZE`, `E2BIG` is returned without reading the rest of the strings.
+///
+/// Like Linux, a single string longer than `max_string_len` or a vector with more than
+/// `max_string_number` strings also results in `E2BIG`, while a fault when reading the
+/// vector or a string results in `EFAULT`.
 fn read_cstring_vec(
     array_ptr: Vaddr,
     max_string_number: usize,
     max_string_len: usize,
+    total_size: &mut usize,
 ) -> Result<Vec<CString>> {
     let mut res = Vec::new();
     let mut read_addr = array_ptr;
@@ -171,7 +221,17 @@ fn read_cstring_vec(
             find_null = true;
             break;
         }
-        let cstring = read_cstring_from_user(cstring_ptr, max_string_len)?;
+
+        *total_size += mem::size_of::<usize>();
+        if *total_size > MAX_ARGS_SIZE {
+            return_errno_with_message!(Errno::E2BIG, "the total size of the strings is too big");
+        }
+        // The string cannot be longer than the remaining size.
+        let max_len = max_string_len.min(MAX_ARGS_SIZE - *total_size);
+        let cstring = try_read_cstring_from_user(cstring_ptr, max_len)?.ok_or_else(|| {
+            Error::with_message(Errno::E2BIG, "the string in the vector is too long")
+        })?;
+        *total_size += cstring.as_bytes_with_nul().len();
         res.push(cstring);
     }
     if !find_null {
@@ -180,13 +240,58 @@ fn read_cstring_vec(
     Ok(res)
 }
 
+/// Returns the mode of the elf file.
+///
+/// The `set_uid` and `set_gid` bits are ignored if the elf file is on a nosuid mount,
+/// or if its owner or group is not mapped in the user namespace.
+fn elf_file_mode(elf_file: &Arc<Dentry>) -> Result<InodeMode> {
+    let mut mode = elf_file.mode()?;
+    let is_mapped = credentials()
+        .user_ns()
+        .is_mapped(elf_file.owner()?, elf_file.group()?);
+    if !is_mapped
+        || elf_file
+            .mount_node()
+            .flags()
+            .contains(PerMountFlags::NOSUID)
+    {
+        mode.remove(InodeMode::S_ISUID | InodeMode::S_ISGID);
+    }
+    Ok(mode)
+}
+
+/// Returns whether the program should run in secure mode.
+///
+/// Like Linux, this is the case if the effective IDs differ from the real IDs after the
+/// credentials are switched according to the `set_uid` and `set_gid` bits of the elf file,
+/// or if the program gains capabilities that the real user does not have.
+fn is_secure_exec(elf_file: &Arc<Dentry>) -> Result<bool> {
+    let credentials = credentials();
+    let mode = elf_file_mode(elf_file)?;
+
+    let new_euid = if mode.has_set_uid() {
+        elf_file.owner()?
+    } else {
+        credentials.euid()
+    };
+    let new_egid = if mode.has_set_gid() {
+        elf_file.group()?
+    } else {
+        credentials.egid()
+    };
+
+    Ok(new_euid != credentials.ruid()
+        || new_egid != credentials.rgid()
+        || credentials.exec_gains_capsets(new_euid))
+}
+
 /// Sets uid for credentials as the same of uid of elf file if elf file has `set_uid` bit.
 fn set_uid_from_elf(
     current: &Arc<Process>,
     credentials: &Credentials<WriteOp>,
     elf_file: &Arc<Dentry>,
 ) -> Result<()> {
-    if elf_file.mode()?.has_set_uid() {
+    if elf_file_mode(elf_file)?.has_set_uid() {
         let uid = elf_file.owner()?;
         credentials.set_euid(uid);
 
@@ -204,7 +309,7 @@ fn set_gid_from_elf(
     credentials: &Credentials<WriteOp>,
     elf_file: &Arc<Dentry>,
 ) -> Result<()> {
-    if elf_file.mode()?.has_set_gid() {
+    if elf_file_mode(elf_file)?.has_set_gid() {
         let gid = elf_file.group()?;
         credentials.set_egid(gid);
 
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{
+        fanotify::{FanotifyGroup, DEFAULT_MAX_EVENTS},
+        file_table::FdFlags,
+        utils::{AccessMode, CreationFlags, StatusFlags},
+    },
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+};
+
+pub fn sys_fanotify_init(flags: u32, event_f_flags: u32) -> Result<SyscallReturn> {
+    let flags = FanInitFlags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "unknown flags"))?;
+    debug!("flags = {:?}, event_f_flags = 0x{:x}", flags, event_f_flags);
+
+    if !credentials().capable(CapSet::SYS_ADMIN) {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_ADMIN is required");
+    }
+    if flags.intersects(FanInitFlags::CLASS_CONTENT | FanInitFlags::CLASS_PRE_CONTENT) {
+        return_errno_with_message!(Errno::EINVAL, "permission events are not supported");
+    }
+
+    let event_access_mode = AccessMode::from_u32(event_f_flags)?;
+    let event_status_flags = StatusFlags::from_bits_truncate(event_f_flags);
+    let event_fd_flags =
+        if CreationFlags::from_bits_truncate(event_f_flags).contains(CreationFlags::O_CLOEXEC) {
+            FdFlags::CLOEXEC
+        } else {
+            FdFlags::empty()
+        };
+
+    let max_events = if flags.contains(FanInitFlags::UNLIMITED_QUEUE) {
+        None
+    } else {
+        Some(DEFAULT_MAX_EVENTS)
+    };
+    let group = FanotifyGroup::new(
+        flags.contains(FanInitFlags::NONBLOCK),
+        max_events,
+        event_access_mode,
+        event_status_flags,
+        event_fd_flags,
+    );
+
+    let fd = {
+        let current = current!();
+        let mut file_table = current.file_table().lock();
+        let fd_flags = if flags.contains(FanInitFlags::CLOEXEC) {
+            FdFlags::CLOEXEC
+        } else {
+            FdFlags::empty()
+        };
+        file_table.insert(group, fd_flags)?
+    };
+
+    Ok(SyscallReturn::Return(fd as _))
+}
+
+bitflags! {
+    struct FanInitFlags: u32 {
+        const CLOEXEC           = 0x0000_0001;
+        const NONBLOCK          = 0x0000_0002;
+        const CLASS_CONTENT     = 0x0000_0004;
+        const CLASS_PRE_CONTENT = 0x0000_0008;
+        const UNLIMITED_QUEUE   = 0x0000_0010;
+        const UNLIMITED_MARKS   = 0x0000_0020;
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{
+        fanotify::{FanEvents, FanMarkTarget, FanotifyGroup},
+        file_table::FileDesc,
+        fs_resolver::{FsPath, AT_FDCWD},
+        utils::{InodeType, PATH_MAX},
+    },
+    prelude::*,
+    util::read_cstring_from_user,
+};
+
+pub fn sys_fanotify_mark(
+    fd: FileDesc,
+    flags: u32,
+    mask: u64,
+    dirfd: FileDesc,
+    path_ptr: Vaddr,
+) -> Result<SyscallReturn> {
+    let flags = FanMarkFlags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "unknown flags"))?;
+    let mask = FanEvents::from_bits(mask)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "unsupported events"))?;
+    debug!(
+        "fd = {}, flags = {:?}, mask = {:?}, dirfd = {}, path_ptr = 0x{:x}",
+        fd, flags, mask, dirfd, path_ptr
+    );
+
+    let action = flags & (FanMarkFlags::ADD | FanMarkFlags::REMOVE | FanMarkFlags::FLUSH);
+    if action.bits().count_ones() != 1 {
+        return_errno_with_message!(Errno::EINVAL, "exactly one action must be specified");
+    }
+    if mask.contains(FanEvents::Q_OVERFLOW) {
+        return_errno_with_message!(Errno::EINVAL, "the overflow event cannot be marked");
+    }
+    if !action.contains(FanMarkFlags::FLUSH) && mask.is_empty() {
+        return_errno_with_message!(Errno::EINVAL, "the mask is empty");
+    }
+
+    let current = current!();
+    let file = current.file_table().lock().get_file(fd)?.clone();
+    let group = file
+        .downcast_ref::<FanotifyGroup>()
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "the file is not a fanotify group"))?;
+
+    if action.contains(FanMarkFlags::FLUSH) {
+        group.flush_marks(flags.contains(FanMarkFlags::MOUNT));
+        return Ok(SyscallReturn::Return(0));
+    }
+
+    let dentry = {
+        let fs = current.fs().read();
+        if path_ptr == 0 {
+            if dirfd == AT_FDCWD {
+                fs.lookup(&FsPath::new(AT_FDCWD, ".")?)?
+            } else {
+                fs.lookup_from_fd(dirfd)?
+            }
+        } else {
+            let path = read_cstring_from_user(path_ptr, PATH_MAX)?;
+            let path = path.to_string_lossy();
+            let fs_path = FsPath::new(dirfd, path.as_ref())?;
+            if flags.contains(FanMarkFlags::DONT_FOLLOW) {
+                fs.lookup_no_follow(&fs_path)?
+            } else {
+                fs.lookup(&fs_path)?
+            }
+        }
+    };
+    if flags.contains(FanMarkFlags::ONLYDIR) && dentry.type_() != InodeType::Dir {
+        return_errno_with_message!(Errno::ENOTDIR, "the path is not a directory");
+    }
+
+    let target = if flags.contains(FanMarkFlags::MOUNT) {
+        FanMarkTarget::Mount(dentry.mount_node().clone())
+    } else {
+        FanMarkTarget::Inode(dentry.inode().clone())
+    };
+    if action.contains(FanMarkFlags::ADD) {
+        group.add_mark(target, mask);
+    } else {
+        group.remove_mark(&target, mask)?;
+    }
+
+    Ok(SyscallReturn::Return(0))
+}
+
+bitflags! {
+    struct FanMarkFlags: u32 {
+        const ADD         = 0x0000_0001;
+        const REMOVE      = 0x0000_0002;
+        const DONT_FOLLOW = 0x0000_0004;
+        const ONLYDIR     = 0x0000_0008;
+        const MOUNT       = 0x0000_0010;
+        const FLUSH       = 0x0000_0080;
+    }
+}
@@ -4,6 +4,8 @@ use super::SyscallReturn;
 use crate::{
     fs::{
         file_table::{FdFlags, FileDesc},
+        inode_handle::InodeHandle,
+        pipe::{PipeReader, PipeWriter},
         utils::StatusFlags,
     },
     prelude::*,
@@ -80,6 +82,41 @@ pub fn sys_fcntl(fd: FileDesc, cmd: i32, arg: u64) -> Result<SyscallReturn> {
             file.set_status_flags(new_status_flags)?;
             Ok(SyscallReturn::Return(0))
         }
+        FcntlCmd::F_SETPIPE_SZ => {
+            let current = current!();
+            let file = {
+                let file_table = current.file_table().lock();
+                file_table.get_file(fd)?.clone()
+            };
+            let size = arg as u32 as usize;
+            let new_size = if let Some(reader) = file.downcast_ref::<PipeReader>() {
+                reader.set_capacity(size)?
+            } else if let Some(writer) = file.downcast_ref::<PipeWriter>() {
+                writer.set_capacity(size)?
+            } else if let Some(inode_handle) = file.downcast_ref::<InodeHandle>() {
+                inode_handle.set_pipe_capacity(size)?
+            } else {
+                return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+            };
+            Ok(SyscallReturn::Return(new_size as _))
+        }
+        FcntlCmd::F_GETPIPE_SZ => {
+            let current = current!();
+            let file = {
+                let file_table = current.file_table().lock();
+                file_table.get_file(fd)?.clone()
+            };
+            let size = if let Some(reader) = file.downcast_ref::<PipeReader>() {
+                reader.capacity()
+            } else if let Some(writer) = file.downcast_ref::<PipeWriter>() {
+                writer.capacity()
+            } else if let Some(inode_handle) = file.downcast_ref::<InodeHandle>() {
+                inode_handle.pipe_capacity()?
+            } else {
+                return_errno_with_message!(Errno::EBADF, "the file is not a pipe");
+            };
+            Ok(SyscallReturn::Return(size as _))
+        }
     }
 }
 
@@ -93,4 +130,6 @@ enum FcntlCmd {
     F_GETFL = 3,
     F_SETFL = 4,
     F_DUPFD_CLOEXEC = 1030,
+    F_SETPIPE_SZ = 1031,
+    F_GETPIPE_SZ = 1032,
 }
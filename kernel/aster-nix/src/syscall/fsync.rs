@@ -2,22 +2,14 @@
 
 use super::SyscallReturn;
 use crate::{
-    fs::{file_table::FileDesc, inode_handle::InodeHandle},
+    fs::{file_table::FileDesc, inode_handle::InodeHandle, path::Dentry, utils::InodeType},
     prelude::*,
 };
 
 pub fn sys_fsync(fd: FileDesc) -> Result<SyscallReturn> {
     debug!("fd = {}", fd);
 
-    let dentry = {
-        let current = current!();
-        let file_table = current.file_table().lock();
-        let file = file_table.get_file(fd)?;
-        let inode_handle = file
-            .downcast_ref::<InodeHandle>()
-            .ok_or(Error::with_message(Errno::EINVAL, "not inode"))?;
-        inode_handle.dentry().clone()
-    };
+    let dentry = get_syncable_dentry(fd)?;
     dentry.sync_all()?;
     Ok(SyscallReturn::Return(0))
 }
@@ -25,15 +17,33 @@ pub fn sys_fsync(fd: FileDesc) -> Result<SyscallReturn> {
 pub fn sys_fdatasync(fd: FileDesc) -> Result<SyscallReturn> {
     debug!("fd = {}", fd);
 
-    let dentry = {
-        let current = current!();
-        let file_table = current.file_table().lock();
-        let file = file_table.get_file(fd)?;
-        let inode_handle = file
-            .downcast_ref::<InodeHandle>()
-            .ok_or(Error::with_message(Errno::EINVAL, "not inode"))?;
-        inode_handle.dentry().clone()
-    };
+    let dentry = get_syncable_dentry(fd)?;
     dentry.sync_data()?;
     Ok(SyscallReturn::Return(0))
 }
+
+/// Gets the dentry of the file that can be synchronized.
+///
+/// Only regular files, directories and block devices can be synchronized.
+/// Other files, e.g., pipes, sockets and character devices, have nothing
+/// to be synchronized, so `EINVAL` is returned.
+fn get_syncable_dentry(fd: FileDesc) -> Result<Arc<Dentry>> {
+    let current = current!();
+    let file_table = current.file_table().lock();
+    let file = file_table.get_file(fd)?;
+    let inode_handle = file
+        .downcast_ref::<InodeHandle>()
+        .ok_or(Error::with_message(Errno::EINVAL, "not inode"))?;
+    if inode_handle.is_path_only() {
+        return_errno_with_message!(Errno::EBADF, "file is opened as a path");
+    }
+
+    let dentry = inode_handle.dentry();
+    if !matches!(
+        dentry.type_(),
+        InodeType::File | InodeType::Dir | InodeType::BlockDevice
+    ) {
+        return_errno_with_message!(Errno::EINVAL, "the file does not support synchronization");
+    }
+    Ok(dentry.clone())
+}
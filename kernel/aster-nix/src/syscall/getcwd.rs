@@ -4,10 +4,28 @@ use super::SyscallReturn;
 use crate::{prelude::*, util::write_bytes_to_user};
 
 pub fn sys_getcwd(buf: Vaddr, len: usize) -> Result<SyscallReturn> {
-    // TODO: getcwd only return a fake result now
-    let fake_cwd = CString::new("/")?;
-    let bytes = fake_cwd.as_bytes_with_nul();
-    let write_len = len.min(bytes.len());
-    write_bytes_to_user(buf, &mut VmReader::from(&bytes[..write_len]))?;
-    Ok(SyscallReturn::Return(write_len as _))
+    debug!("buf = 0x{:x}, len = 0x{:x}", buf, len);
+
+    let current = current!();
+    let cwd = {
+        let fs = current.fs().read();
+        let cwd = fs.cwd();
+        if cwd.metadata().nlinks == 0 {
+            return_errno_with_message!(Errno::ENOENT, "the cwd has been unlinked");
+        }
+        // Like Linux, the cwd outside of the root directory (e.g., after `chroot`)
+        // is marked as unreachable.
+        match cwd.path_from(fs.root()) {
+            Some(path) => path,
+            None => String::from("(unreachable)") + &cwd.abs_path(),
+        }
+    };
+
+    let cwd = CString::new(cwd)?;
+    let bytes = cwd.as_bytes_with_nul();
+    if bytes.len() > len {
+        return_errno_with_message!(Errno::ERANGE, "the buffer is too small");
+    }
+    write_bytes_to_user(buf, &mut VmReader::from(bytes))?;
+    Ok(SyscallReturn::Return(bytes.len() as _))
 }
@@ -109,10 +109,15 @@ impl<'a, T: DirentSerializer> DirentVisitor for DirentBufferReader<'a, T> {
     }
 }
 
+/// The directory entry of `getdents`.
+///
+/// Unlike `Dirent64`, the type of the entry is stored in the last byte of the record,
+/// after the name and the padding.
 #[derive(Debug)]
 struct Dirent {
     inner: DirentInner,
     name: CString,
+    d_type: u8,
 }
 
 #[repr(packed)]
@@ -124,12 +129,15 @@ struct DirentInner {
 }
 
 impl DirentSerializer for Dirent {
-    fn new(ino: u64, offset: u64, _type_: InodeType, name: CString) -> Self {
+    fn new(ino: u64, offset: u64, type_: InodeType, name: CString) -> Self {
         let d_reclen = {
-            let len =
-                core::mem::size_of::<Dirent64Inner>() + name.as_c_str().to_bytes_with_nul().len();
+            // One more byte is needed for the type of the entry.
+            let len = core::mem::size_of::<DirentInner>()
+                + name.as_c_str().to_bytes_with_nul().len()
+                + core::mem::size_of::<u8>();
             align_up(len, 8) as u16
         };
+        let d_type = DirentType::from(type_) as u8;
         Self {
             inner: DirentInner {
                 d_ino: ino,
@@ -137,6 +145,7 @@ impl DirentSerializer for Dirent {
                 d_reclen,
             },
             name,
+            d_type,
         }
     }
 
@@ -164,6 +173,7 @@ impl DirentSerializer for Dirent {
             buf[offset..offset + item.len()].copy_from_slice(item);
             offset += item.len();
         }
+        buf[self.len() - 1] = self.d_type;
         Ok(())
     }
 }
@@ -6,7 +6,7 @@ use crate::{prelude::*, process::credentials};
 pub fn sys_getegid() -> Result<SyscallReturn> {
     let egid = {
         let credentials = credentials();
-        credentials.egid()
+        credentials.user_ns().gid_from_kernel(credentials.egid())
     };
 
     Ok(SyscallReturn::Return(egid.as_u32() as _))
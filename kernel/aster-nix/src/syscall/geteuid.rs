@@ -6,7 +6,7 @@ use crate::{prelude::*, process::credentials};
 pub fn sys_geteuid() -> Result<SyscallReturn> {
     let euid = {
         let credentials = credentials();
-        credentials.euid()
+        credentials.user_ns().uid_from_kernel(credentials.euid())
     };
 
     Ok(SyscallReturn::Return(euid.as_u32() as _))
@@ -6,7 +6,7 @@ use crate::{prelude::*, process::credentials};
 pub fn sys_getgid() -> Result<SyscallReturn> {
     let gid = {
         let credentials = credentials();
-        credentials.rgid()
+        credentials.user_ns().gid_from_kernel(credentials.rgid())
     };
 
     Ok(SyscallReturn::Return(gid.as_u32() as _))
@@ -24,9 +24,10 @@ pub fn sys_getgroups(size: i32, group_list_addr: Vaddr) -> Result<SyscallReturn>
         );
     }
 
+    let user_ns = credentials.user_ns();
     for (idx, gid) in groups.iter().enumerate() {
         let addr = group_list_addr + idx * core::mem::size_of_val(gid);
-        write_val_to_user(addr, gid)?;
+        write_val_to_user(addr, &user_ns.gid_from_kernel(*gid))?;
     }
 
     Ok(SyscallReturn::Return(groups.len() as _))
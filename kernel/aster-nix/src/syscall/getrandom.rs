@@ -4,13 +4,20 @@ use super::SyscallReturn;
 use crate::{device, prelude::*, util::write_bytes_to_user};
 
 pub fn sys_getrandom(buf: Vaddr, count: usize, flags: u32) -> Result<SyscallReturn> {
-    let flags = GetRandomFlags::from_bits_truncate(flags);
+    let flags = GetRandomFlags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid flags"))?;
     debug!(
         "buf = 0x{:x}, count = 0x{:x}, flags = {:?}",
         buf, count, flags
     );
-    // TODO: support nonblock flag.
-    // Currently our getrandom implementation relies on x86-specific `rdrand` instruction, so it will never block.
+    if flags.contains(GetRandomFlags::GRND_INSECURE | GetRandomFlags::GRND_RANDOM) {
+        return_errno_with_message!(
+            Errno::EINVAL,
+            "GRND_INSECURE cannot be used together with GRND_RANDOM"
+        );
+    }
+    // The kernel RNG is seeded during initialization, before any user program runs. So
+    // `GRND_NONBLOCK` never makes a difference because the call never blocks.
     let mut buffer = vec![0u8; count];
     let read_len = if flags.contains(GetRandomFlags::GRND_RANDOM) {
         device::Random::getrandom(&mut buffer)?
@@ -7,14 +7,15 @@ pub fn sys_getresgid(rgid_ptr: Vaddr, egid_ptr: Vaddr, sgid_ptr: Vaddr) -> Resul
     debug!("rgid_ptr = 0x{rgid_ptr:x}, egid_ptr = 0x{egid_ptr:x}, sgid_ptr = 0x{sgid_ptr:x}");
 
     let credentials = credentials();
+    let user_ns = credentials.user_ns();
 
-    let rgid = credentials.rgid();
+    let rgid = user_ns.gid_from_kernel(credentials.rgid());
     write_val_to_user(rgid_ptr, &rgid)?;
 
-    let egid = credentials.egid();
+    let egid = user_ns.gid_from_kernel(credentials.egid());
     write_val_to_user(egid_ptr, &egid)?;
 
-    let sgid = credentials.sgid();
+    let sgid = user_ns.gid_from_kernel(credentials.sgid());
     write_val_to_user(sgid_ptr, &sgid)?;
 
     Ok(SyscallReturn::Return(0))
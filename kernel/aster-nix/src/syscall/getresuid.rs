@@ -7,14 +7,15 @@ pub fn sys_getresuid(ruid_ptr: Vaddr, euid_ptr: Vaddr, suid_ptr: Vaddr) -> Resul
     debug!("ruid_ptr = 0x{ruid_ptr:x}, euid_ptr = 0x{euid_ptr:x}, suid_ptr = 0x{suid_ptr:x}");
 
     let credentials = credentials();
+    let user_ns = credentials.user_ns();
 
-    let ruid = credentials.ruid();
+    let ruid = user_ns.uid_from_kernel(credentials.ruid());
     write_val_to_user(ruid_ptr, &ruid)?;
 
-    let euid = credentials.euid();
+    let euid = user_ns.uid_from_kernel(credentials.euid());
     write_val_to_user(euid_ptr, &euid)?;
 
-    let suid = credentials.suid();
+    let suid = user_ns.uid_from_kernel(credentials.suid());
     write_val_to_user(suid_ptr, &suid)?;
 
     Ok(SyscallReturn::Return(0))
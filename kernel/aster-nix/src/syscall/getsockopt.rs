@@ -3,6 +3,8 @@
 use super::SyscallReturn;
 use crate::{
     fs::file_table::FileDesc,
+    match_sock_option_mut,
+    net::socket::options::{Domain, Protocol, SocketOption},
     prelude::*,
     util::{
         net::{get_socket_from_fd, new_raw_socket_option, CSocketOptionLevel},
@@ -30,12 +32,19 @@ pub fn sys_getsockopt(
 
     debug!("raw option: {:?}", raw_option);
 
-    socket.get_option(raw_option.as_sock_option_mut())?;
+    // The domain and the protocol are decided when the socket is created.
+    let info = socket.info();
+    let option = raw_option.as_sock_option_mut();
+    match_sock_option_mut!(option, {
+        socket_domain: Domain => socket_domain.set(info.domain()),
+        socket_protocol: Protocol => socket_protocol.set(info.protocol()),
+        _ => socket.get_option(option)?
+    });
 
     let write_len = {
         let current = current!();
         let vmar = current.root_vmar();
-        raw_option.write_to_user(vmar, optval, optlen)?
+        raw_option.write_to_user(&vmar, optval, optlen)?
     };
 
     write_val_to_user(optlen_addr, &(write_len as u32))?;
@@ -6,7 +6,7 @@ use crate::{prelude::*, process::credentials};
 pub fn sys_getuid() -> Result<SyscallReturn> {
     let uid = {
         let credentials = credentials();
-        credentials.ruid()
+        credentials.user_ns().uid_from_kernel(credentials.ruid())
     };
 
     Ok(SyscallReturn::Return(uid.as_u32() as _))
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use align_ext::AlignExt;
+use ostd::mm::MAX_USERSPACE_VADDR;
+
 use super::SyscallReturn;
-use crate::{prelude::*, util::read_bytes_from_user};
+use crate::prelude::*;
 
 pub fn sys_madvise(start: Vaddr, len: usize, behavior: i32) -> Result<SyscallReturn> {
     let behavior = MadviseBehavior::try_from(behavior)?;
@@ -9,31 +12,66 @@ pub fn sys_madvise(start: Vaddr, len: usize, behavior: i32) -> Result<SyscallRet
         "start = 0x{:x}, len = 0x{:x}, behavior = {:?}",
         start, len, behavior
     );
+
+    if start % PAGE_SIZE != 0 {
+        return_errno_with_message!(Errno::EINVAL, "the start address is not page-aligned");
+    }
+    let end = len
+        .checked_add(PAGE_SIZE - 1)
+        .map(|len| len.align_down(PAGE_SIZE))
+        .and_then(|len| start.checked_add(len))
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "the range overflows"))?;
+    if end > MAX_USERSPACE_VADDR {
+        return_errno_with_message!(Errno::ENOMEM, "the range is out of user space");
+    }
+    let advised_range = start..end;
+
+    let current = current!();
+    let root_vmar = current.root_vmar();
     match behavior {
+        // TODO: Adjust the readahead of the file-backed mappings according to the
+        // access pattern. For now, these hints only check that the range is mapped.
         MadviseBehavior::MADV_NORMAL
-        | MadviseBehavior::MADV_SEQUENTIAL
-        | MadviseBehavior::MADV_WILLNEED => {
-            // perform a read at first
-            let mut buffer = vec![0u8; len];
-            read_bytes_from_user(start, &mut VmWriter::from(buffer.as_mut_slice()))?;
+        | MadviseBehavior::MADV_RANDOM
+        | MadviseBehavior::MADV_SEQUENTIAL => {
+            root_vmar.check_mapped_range(&advised_range)?;
+        }
+        MadviseBehavior::MADV_WILLNEED
+        | MadviseBehavior::MADV_POPULATE_READ
+        | MadviseBehavior::MADV_POPULATE_WRITE => root_vmar.prefetch_pages(advised_range)?,
+        // Since pages are never locked, `MADV_DONTNEED_LOCKED` is the same as `MADV_DONTNEED`.
+        MadviseBehavior::MADV_DONTNEED | MadviseBehavior::MADV_DONTNEED_LOCKED => {
+            root_vmar.discard_pages(advised_range)?
+        }
+        // These advices are hints that the kernel is free to ignore. For example, the pages
+        // advised with `MADV_FREE` can be kept as is since there is no memory pressure to
+        // relieve, and there are no huge pages, page merging or core dumps to adjust.
+        // TODO: Support `MADV_DONTFORK` and `MADV_WIPEONFORK`, which change how the pages are
+        // inherited by a child process.
+        MadviseBehavior::MADV_FREE
+        | MadviseBehavior::MADV_DONTFORK
+        | MadviseBehavior::MADV_DOFORK
+        | MadviseBehavior::MADV_MERGEABLE
+        | MadviseBehavior::MADV_UNMERGEABLE
+        | MadviseBehavior::MADV_HUGEPAGE
+        | MadviseBehavior::MADV_NOHUGEPAGE
+        | MadviseBehavior::MADV_DONTDUMP
+        | MadviseBehavior::MADV_DODUMP
+        | MadviseBehavior::MADV_WIPEONFORK
+        | MadviseBehavior::MADV_KEEPONFORK
+        | MadviseBehavior::MADV_COLD
+        | MadviseBehavior::MADV_PAGEOUT => {
+            root_vmar.check_mapped_range(&advised_range)?;
+        }
+        MadviseBehavior::MADV_REMOVE
+        | MadviseBehavior::MADV_HWPOISON
+        | MadviseBehavior::MADV_SOFT_OFFLINE => {
+            return_errno_with_message!(Errno::EINVAL, "the advice is not supported");
         }
-        MadviseBehavior::MADV_DONTNEED => madv_dontneed(start, len)?,
-        _ => todo!(),
     }
     Ok(SyscallReturn::Return(0))
 }
 
-fn madv_dontneed(start: Vaddr, len: usize) -> Result<()> {
-    debug_assert!(start % PAGE_SIZE == 0);
-    debug_assert!(len % PAGE_SIZE == 0);
-    let current = current!();
-    let root_vmar = current.root_vmar();
-    let advised_range = start..start + len;
-    // `destroy()` interface may require adjustment and replacement afterwards.
-    let _ = root_vmar.destroy(advised_range);
-    Ok(())
-}
-
 #[repr(i32)]
 #[derive(Debug, Clone, Copy, TryFromInt)]
 #[allow(non_camel_case_types)]
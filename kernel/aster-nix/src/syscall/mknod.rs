@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{
+        device::{get_device, DeviceId, DeviceType},
+        file_table::FileDesc,
+        fs_resolver::{FsPath, AT_FDCWD},
+        utils::{InodeMode, InodeType, Permission},
+    },
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+    syscall::constants::MAX_FILENAME_LEN,
+    util::read_cstring_from_user,
+};
+
+pub fn sys_mknodat(
+    dirfd: FileDesc,
+    path_addr: Vaddr,
+    mode: u32,
+    dev: usize,
+) -> Result<SyscallReturn> {
+    let path = read_cstring_from_user(path_addr, MAX_FILENAME_LEN)?;
+    debug!(
+        "dirfd = {}, path = {:?}, mode = 0o{:o}, dev = 0x{:x}",
+        dirfd, path, mode, dev
+    );
+
+    // A zero file type means a regular file.
+    let inode_type = match mode & S_IFMT {
+        0 => InodeType::File,
+        type_bits => InodeType::try_from(type_bits)
+            .map_err(|_| Error::with_message(Errno::EINVAL, "invalid file type"))?,
+    };
+    let device_type = match inode_type {
+        InodeType::File | InodeType::NamedPipe => None,
+        InodeType::CharDevice => Some(DeviceType::CharDevice),
+        InodeType::BlockDevice => Some(DeviceType::BlockDevice),
+        InodeType::Dir => return_errno_with_message!(Errno::EPERM, "use mkdir instead"),
+        InodeType::SymLink | InodeType::Socket => {
+            return_errno_with_message!(Errno::EINVAL, "unsupported file type")
+        }
+    };
+    if device_type.is_some() && !credentials().capable(CapSet::MKNOD) {
+        return_errno_with_message!(Errno::EPERM, "creating device nodes requires CAP_MKNOD");
+    }
+
+    let current = current!();
+    let (dir_dentry, name) = {
+        let path = path.to_string_lossy();
+        if path.is_empty() {
+            return_errno_with_message!(Errno::ENOENT, "path is empty");
+        }
+        let fs_path = FsPath::new(dirfd, path.as_ref())?;
+        current.fs().read().lookup_dir_and_base_name(&fs_path)?
+    };
+    if name.ends_with('/') {
+        return_errno_with_message!(Errno::ENOENT, "path refers to a directory");
+    }
+    dir_dentry
+        .inode()
+        .check_permission(Permission::MAY_WRITE | Permission::MAY_EXEC)?;
+
+    let inode_mode = {
+        let mask_mode = (mode & 0o7777) as u16 & !current.umask().read().get();
+        InodeMode::from_bits_truncate(mask_mode)
+    };
+    match device_type {
+        Some(device_type) => {
+            let device = get_device(device_type, DeviceId::from(dev as u64));
+            let _ = dir_dentry.mknod(&name, inode_mode, device)?;
+        }
+        None => {
+            let _ = dir_dentry.new_fs_child(&name, inode_type, inode_mode)?;
+        }
+    }
+
+    Ok(SyscallReturn::Return(0))
+}
+
+pub fn sys_mknod(path_addr: Vaddr, mode: u32, dev: usize) -> Result<SyscallReturn> {
+    self::sys_mknodat(AT_FDCWD, path_addr, mode, dev)
+}
+
+/// The mask of the file type bits in the mode.
+const S_IFMT: u32 = 0o170000;
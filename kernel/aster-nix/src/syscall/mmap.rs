@@ -9,7 +9,10 @@ use aster_rights::Rights;
 
 use super::SyscallReturn;
 use crate::{
-    fs::file_table::FileDesc,
+    fs::{
+        file_table::FileDesc,
+        path::{Dentry, PerMountFlags},
+    },
     prelude::*,
     vm::{
         perms::VmPerms,
@@ -59,13 +62,14 @@ fn do_sys_mmap(
         return_errno_with_message!(Errno::EINVAL, "mmap only support page-aligned offset");
     }
 
-    let vmo = if option.flags.contains(MMapFlags::MAP_ANONYMOUS) {
+    let (vmo, file) = if option.flags.contains(MMapFlags::MAP_ANONYMOUS) {
         if offset != 0 {
             return_errno_with_message!(Errno::EINVAL, "offset must be zero for anonymous mapping");
         }
-        alloc_anonyous_vmo(len)?
+        (alloc_anonyous_vmo(len)?, None)
     } else {
-        alloc_filebacked_vmo(fd, len, offset, &option)?
+        let (vmo, dentry) = alloc_filebacked_vmo(fd, len, offset, vm_perms, &option)?;
+        (vmo, Some(dentry))
     };
 
     let current = current!();
@@ -84,6 +88,10 @@ fn do_sys_mmap(
             options = options.is_shared(true);
         }
 
+        if let Some(dentry) = file {
+            options = options.file(dentry, offset);
+        }
+
         options
     };
     let map_addr = vm_map_options.build()?;
@@ -101,30 +109,38 @@ fn alloc_filebacked_vmo(
     fd: FileDesc,
     len: usize,
     offset: usize,
+    vm_perms: VmPerms,
     option: &MMapOptions,
-) -> Result<Vmo> {
+) -> Result<(Vmo, Arc<Dentry>)> {
     let current = current!();
-    let page_cache_vmo = {
+    let (page_cache_vmo, dentry) = {
         let fs_resolver = current.fs().read();
         let dentry = fs_resolver.lookup_from_fd(fd)?;
+        if vm_perms.contains(VmPerms::EXEC)
+            && dentry.mount_node().flags().contains(PerMountFlags::NOEXEC)
+        {
+            return_errno_with_message!(Errno::EPERM, "the file is on a noexec mount");
+        }
         let inode = dentry.inode();
-        inode
+        let page_cache_vmo = inode
             .page_cache()
             .ok_or(Error::with_message(
                 Errno::EBADF,
                 "File does not have page cache",
             ))?
-            .to_dyn()
+            .to_dyn();
+        (page_cache_vmo, dentry)
     };
 
-    if option.typ() == MMapType::Private {
+    let vmo = if option.typ() == MMapType::Private {
         // map private
-        VmoChildOptions::new_cow(page_cache_vmo, offset..(offset + len)).alloc()
+        VmoChildOptions::new_cow(page_cache_vmo, offset..(offset + len)).alloc()?
     } else {
         // map shared
         // FIXME: map shared vmo can exceed parent range, but slice child cannot
-        VmoChildOptions::new_slice_rights(page_cache_vmo, offset..(offset + len)).alloc()
-    }
+        VmoChildOptions::new_slice_rights(page_cache_vmo, offset..(offset + len)).alloc()?
+    };
+    Ok((vmo, dentry))
 }
 
 fn check_option(option: &MMapOptions) -> Result<()> {
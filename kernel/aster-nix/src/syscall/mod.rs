@@ -4,11 +4,13 @@
 //! The each sub module contains functions that handle real syscall logic.
 pub use clock_gettime::ClockId;
 use ostd::cpu::UserContext;
+pub use uname::{RELEASE, SYS_NAME, VERSION};
 
 use crate::{cpu::LinuxAbi, prelude::*};
 
 mod accept;
 mod access;
+mod adjtimex;
 mod alarm;
 mod arch;
 mod arch_prctl;
@@ -20,7 +22,9 @@ mod chdir;
 mod chmod;
 mod chown;
 mod chroot;
+mod clock_getres;
 mod clock_gettime;
+mod clock_settime;
 mod clone;
 mod close;
 mod connect;
@@ -31,6 +35,8 @@ mod eventfd;
 mod execve;
 mod exit;
 mod exit_group;
+mod fanotify_init;
+mod fanotify_mark;
 mod fcntl;
 mod fork;
 mod fsync;
@@ -62,14 +68,18 @@ mod listen;
 mod lseek;
 mod madvise;
 mod mkdir;
+mod mknod;
 mod mmap;
 mod mount;
 mod mprotect;
+mod mremap;
 mod munmap;
 mod nanosleep;
 mod open;
 mod pause;
+mod personality;
 mod pipe;
+mod pivot_root;
 mod poll;
 mod prctl;
 mod pread64;
@@ -86,6 +96,7 @@ mod rmdir;
 mod rt_sigaction;
 mod rt_sigpending;
 mod rt_sigprocmask;
+mod rt_sigqueueinfo;
 mod rt_sigreturn;
 mod rt_sigsuspend;
 mod sched_getaffinity;
@@ -102,6 +113,7 @@ mod setfsuid;
 mod setgid;
 mod setgroups;
 mod setitimer;
+mod setns;
 mod setpgid;
 mod setregid;
 mod setresgid;
@@ -109,15 +121,18 @@ mod setresuid;
 mod setreuid;
 mod setsid;
 mod setsockopt;
+mod settimeofday;
 mod setuid;
 mod shutdown;
 mod sigaltstack;
+mod signalfd;
 mod socket;
 mod socketpair;
 mod stat;
 mod statfs;
 mod symlink;
 mod sync;
+mod sync_file_range;
 mod tgkill;
 mod time;
 mod timer_create;
@@ -128,6 +143,7 @@ mod umount;
 mod uname;
 mod unlink;
 mod utimens;
+mod vmsplice;
 mod wait4;
 mod waitid;
 mod write;
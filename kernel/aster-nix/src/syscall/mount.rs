@@ -1,23 +1,31 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use aster_block::BlockDevice;
+use ostd::mm::stat::mem_total;
+
 use super::SyscallReturn;
 use crate::{
     fs::{
+        device::devtmpfs,
         exfat::{ExfatFS, ExfatMountOptions},
         ext2::Ext2,
         fs_resolver::{FsPath, AT_FDCWD},
-        path::Dentry,
+        path::{Dentry, PerMountFlags},
+        procfs::ProcFS,
+        ramfs::RamFS,
+        sysfs::SysFS,
         utils::{FileSystem, InodeType},
     },
     prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
     syscall::constants::MAX_FILENAME_LEN,
     util::read_cstring_from_user,
 };
 
 /// The `data` argument is interpreted by the different filesystems.
 /// Typically it is a string of comma-separated options understood by
-/// this filesystem. The current implementation only considers the case
-/// where it is `NULL`. Because it should be interpreted by the specific filesystems.
+/// this filesystem. The current implementation only considers it
+/// for tmpfs, and ignores it for the other filesystems.
 pub fn sys_mount(
     devname_addr: Vaddr,
     dirname_addr: Vaddr,
@@ -33,6 +41,10 @@ pub fn sys_mount(
         devname, dirname, fstype_addr, mount_flags, data,
     );
 
+    if !credentials().capable(CapSet::SYS_ADMIN) {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_ADMIN is required");
+    }
+
     let current = current!();
     let dst_dentry = {
         let dirname = dirname.to_string_lossy();
@@ -62,7 +74,7 @@ pub fn sys_mount(
     } else if mount_flags.contains(MountFlags::MS_MOVE) {
         do_move_mount_old(devname, dst_dentry)?;
     } else {
-        do_new_mount(devname, fstype_addr, dst_dentry)?;
+        do_new_mount(devname, fstype_addr, mount_flags, data, dst_dentry)?;
     }
 
     Ok(SyscallReturn::Return(0))
@@ -128,7 +140,13 @@ fn do_move_mount_old(src_name: CString, dst_dentry: Arc<Dentry>) -> Result<()> {
 }
 
 /// Mount a new filesystem.
-fn do_new_mount(devname: CString, fs_type: Vaddr, target_dentry: Arc<Dentry>) -> Result<()> {
+fn do_new_mount(
+    devname: CString,
+    fs_type: Vaddr,
+    mount_flags: MountFlags,
+    data: Vaddr,
+    target_dentry: Arc<Dentry>,
+) -> Result<()> {
     if target_dentry.type_() != InodeType::Dir {
         return_errno_with_message!(Errno::ENOTDIR, "mountpoint must be directory");
     };
@@ -137,29 +155,90 @@ fn do_new_mount(devname: CString, fs_type: Vaddr, target_dentry: Arc<Dentry>) ->
     if fs_type.is_empty() {
         return_errno_with_message!(Errno::EINVAL, "fs_type is empty");
     }
-    let fs = get_fs(fs_type, devname)?;
-    target_dentry.mount(fs)?;
+    let data = if data == 0 {
+        None
+    } else {
+        Some(read_cstring_from_user(data, PAGE_SIZE)?)
+    };
+    let fs = get_fs(fs_type, devname, data)?;
+    target_dentry.mount_with_flags(fs, mount_flags.per_mount_flags())?;
     Ok(())
 }
 
-/// Get the filesystem by fs_type and devname.
-fn get_fs(fs_type: CString, devname: CString) -> Result<Arc<dyn FileSystem>> {
-    let devname = devname.to_str().unwrap();
-    let device = match aster_block::get_device(devname) {
-        Some(device) => device,
-        None => return_errno_with_message!(Errno::ENOENT, "Device does not exist"),
-    };
-    let fs_type = fs_type.to_str().unwrap();
+/// Get the filesystem by fs_type, devname and the options in data.
+///
+/// The filesystems that live in memory ignore the devname, while the others
+/// are opened on the block device named by it.
+fn get_fs(
+    fs_type: CString,
+    devname: CString,
+    data: Option<CString>,
+) -> Result<Arc<dyn FileSystem>> {
+    let fs_type = fs_type.to_str()?;
     match fs_type {
+        "ramfs" => Ok(RamFS::new()),
+        "tmpfs" => match parse_tmpfs_max_size(data.as_deref())? {
+            Some(max_size) => Ok(RamFS::new_with_max_size(max_size)),
+            None => Ok(RamFS::new()),
+        },
+        "proc" => Ok(ProcFS::new()),
+        "sysfs" => Ok(SysFS::new()),
+        "devtmpfs" => Ok(devtmpfs()),
         "ext2" => {
-            let ext2_fs = Ext2::open(device)?;
+            let ext2_fs = Ext2::open(get_block_device(devname)?)?;
             Ok(ext2_fs)
         }
         "exfat" => {
-            let exfat_fs = ExfatFS::open(device, ExfatMountOptions::default())?;
+            let exfat_fs = ExfatFS::open(get_block_device(devname)?, ExfatMountOptions::default())?;
             Ok(exfat_fs)
         }
-        _ => return_errno_with_message!(Errno::EINVAL, "Invalid fs type"),
+        _ => return_errno_with_message!(Errno::ENODEV, "the fs type is not supported"),
+    }
+}
+
+/// Parses the size limit of tmpfs from the `size=` option.
+///
+/// Like Linux, the size is in bytes with an optional `k`, `m` or `g` suffix, or in
+/// a percentage of the memory with the `%` suffix. It defaults to half of the memory,
+/// and `0` means no limit, in which case `None` is returned.
+fn parse_tmpfs_max_size(data: Option<&CStr>) -> Result<Option<usize>> {
+    let mut max_size = mem_total() / 2;
+
+    let options = match data {
+        Some(data) => data.to_str()?,
+        None => "",
+    };
+    for option in options.split(',') {
+        // Other options, such as `mode=`, are not supported yet.
+        if let Some(size) = option.strip_prefix("size=") {
+            max_size = parse_size(size)
+                .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid tmpfs size"))?;
+        }
+    }
+
+    Ok(if max_size == 0 { None } else { Some(max_size) })
+}
+
+fn parse_size(size: &str) -> Option<usize> {
+    if let Some(percent) = size.strip_suffix('%') {
+        let percent = percent.parse::<usize>().ok()?;
+        return (mem_total() / 100).checked_mul(percent);
+    }
+
+    let (number, shift) = match size.as_bytes().last()? {
+        b'k' | b'K' => (&size[..size.len() - 1], 10),
+        b'm' | b'M' => (&size[..size.len() - 1], 20),
+        b'g' | b'G' => (&size[..size.len() - 1], 30),
+        _ => (size, 0),
+    };
+    number.parse::<usize>().ok()?.checked_mul(1 << shift)
+}
+
+fn get_block_device(devname: CString) -> Result<Arc<dyn BlockDevice>> {
+    let devname = devname.to_str()?;
+    match aster_block::get_device(devname) {
+        Some(device) => Ok(device),
+        None => return_errno_with_message!(Errno::ENOENT, "Device does not exist"),
     }
 }
 
@@ -189,3 +268,20 @@ bitflags! {
         const MS_KERNMOUNT     =   1 << 22;      // This is a kern_mount call.
     }
 }
+
+impl MountFlags {
+    /// Extracts the flags that are stored on the new mount.
+    fn per_mount_flags(&self) -> PerMountFlags {
+        let mut flags = PerMountFlags::empty();
+        if self.contains(Self::MS_NOSUID) {
+            flags |= PerMountFlags::NOSUID;
+        }
+        if self.contains(Self::MS_NODEV) {
+            flags |= PerMountFlags::NODEV;
+        }
+        if self.contains(Self::MS_NOEXEC) {
+            flags |= PerMountFlags::NOEXEC;
+        }
+        flags
+    }
+}
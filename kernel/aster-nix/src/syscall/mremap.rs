@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use align_ext::AlignExt;
+use ostd::mm::MAX_USERSPACE_VADDR;
+
+use super::SyscallReturn;
+use crate::prelude::*;
+
+pub fn sys_mremap(
+    old_addr: Vaddr,
+    old_size: usize,
+    new_size: usize,
+    flags: i32,
+    new_addr: Vaddr,
+) -> Result<SyscallReturn> {
+    let flags = MremapFlags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid flags"))?;
+    debug!(
+        "old_addr = 0x{:x}, old_size = 0x{:x}, new_size = 0x{:x}, flags = {:?}, new_addr = 0x{:x}",
+        old_addr, old_size, new_size, flags, new_addr
+    );
+
+    let res = do_sys_mremap(old_addr, old_size, new_size, flags, new_addr)?;
+    Ok(SyscallReturn::Return(res as _))
+}
+
+fn do_sys_mremap(
+    old_addr: Vaddr,
+    old_size: usize,
+    new_size: usize,
+    flags: MremapFlags,
+    new_addr: Vaddr,
+) -> Result<Vaddr> {
+    if old_addr % PAGE_SIZE != 0 {
+        return_errno_with_message!(Errno::EINVAL, "the old address is not page-aligned");
+    }
+    if flags.contains(MremapFlags::MREMAP_FIXED) && !flags.contains(MremapFlags::MREMAP_MAYMOVE) {
+        return_errno_with_message!(Errno::EINVAL, "MREMAP_FIXED requires MREMAP_MAYMOVE");
+    }
+    if flags.contains(MremapFlags::MREMAP_DONTUNMAP) {
+        return_errno_with_message!(Errno::EINVAL, "MREMAP_DONTUNMAP is not supported");
+    }
+    if old_size == 0 {
+        // FIXME: Linux duplicates shared mappings if the old size is zero.
+        return_errno_with_message!(Errno::EINVAL, "the old size is zero");
+    }
+
+    let old_size = align_size(old_size)?;
+    let new_size = align_size(new_size)?;
+    if new_size == 0 {
+        return_errno_with_message!(Errno::EINVAL, "the new size is zero");
+    }
+    let old_range = old_addr..checked_end(old_addr, old_size)?;
+
+    let current = current!();
+    let root_vmar = current.root_vmar();
+
+    if flags.contains(MremapFlags::MREMAP_FIXED) {
+        if new_addr % PAGE_SIZE != 0 {
+            return_errno_with_message!(Errno::EINVAL, "the new address is not page-aligned");
+        }
+        let new_range = new_addr..checked_end(new_addr, new_size)?;
+        if new_range.start < old_range.end && old_range.start < new_range.end {
+            return_errno_with_message!(Errno::EINVAL, "the old and new ranges overlap");
+        }
+        return root_vmar.move_mapping(old_range, new_size, Some(new_addr));
+    }
+
+    match root_vmar.resize_mapping(old_range.clone(), new_size) {
+        Ok(()) => Ok(old_addr),
+        Err(err) if err.error() == Errno::ENOMEM && flags.contains(MremapFlags::MREMAP_MAYMOVE) => {
+            root_vmar.move_mapping(old_range, new_size, None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn align_size(size: usize) -> Result<usize> {
+    size.checked_add(PAGE_SIZE - 1)
+        .map(|size| size.align_down(PAGE_SIZE))
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "the size is too large"))
+}
+
+fn checked_end(addr: Vaddr, size: usize) -> Result<Vaddr> {
+    addr.checked_add(size)
+        .filter(|end| *end <= MAX_USERSPACE_VADDR)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "the range is out of user space"))
+}
+
+bitflags! {
+    struct MremapFlags: i32 {
+        const MREMAP_MAYMOVE = 1 << 0;
+        const MREMAP_FIXED = 1 << 1;
+        const MREMAP_DONTUNMAP = 1 << 2;
+    }
+}
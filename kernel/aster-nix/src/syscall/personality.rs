@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::sync::atomic::Ordering;
+
+use super::SyscallReturn;
+use crate::prelude::*;
+
+/// The value of `persona` that only queries the current personality.
+const PERSONALITY_QUERY: u32 = 0xffff_ffff;
+
+pub fn sys_personality(persona: u32) -> Result<SyscallReturn> {
+    debug!("persona = 0x{:x}", persona);
+    let current = current!();
+    let old_persona = if persona == PERSONALITY_QUERY {
+        current.personality().load(Ordering::Relaxed)
+    } else {
+        current.personality().swap(persona, Ordering::Relaxed)
+    };
+    Ok(SyscallReturn::Return(old_persona as _))
+}
@@ -4,8 +4,8 @@ use super::SyscallReturn;
 use crate::{
     fs::{
         file_table::{FdFlags, FileDesc},
-        pipe::{PipeReader, PipeWriter},
-        utils::{Channel, CreationFlags, StatusFlags},
+        pipe,
+        utils::{CreationFlags, StatusFlags},
     },
     prelude::*,
     util::{read_val_from_user, write_val_to_user},
@@ -15,14 +15,7 @@ pub fn sys_pipe2(fds: Vaddr, flags: u32) -> Result<SyscallReturn> {
     debug!("flags: {:?}", flags);
 
     let mut pipe_fds = read_val_from_user::<PipeFds>(fds)?;
-    let (reader, writer) = {
-        let (producer, consumer) = Channel::with_capacity_and_flags(
-            PIPE_BUF_SIZE,
-            StatusFlags::from_bits_truncate(flags),
-        )?
-        .split();
-        (PipeReader::new(consumer), PipeWriter::new(producer))
-    };
+    let (reader, writer) = pipe::new_pair_with_flags(StatusFlags::from_bits_truncate(flags))?;
     let pipe_reader = Arc::new(reader);
     let pipe_writer = Arc::new(writer);
     let fd_flags = if CreationFlags::from_bits_truncate(flags).contains(CreationFlags::O_CLOEXEC) {
@@ -33,8 +26,14 @@ pub fn sys_pipe2(fds: Vaddr, flags: u32) -> Result<SyscallReturn> {
 
     let current = current!();
     let mut file_table = current.file_table().lock();
-    pipe_fds.reader_fd = file_table.insert(pipe_reader, fd_flags);
-    pipe_fds.writer_fd = file_table.insert(pipe_writer, fd_flags);
+    pipe_fds.reader_fd = file_table.insert(pipe_reader, fd_flags)?;
+    pipe_fds.writer_fd = match file_table.insert(pipe_writer, fd_flags) {
+        Ok(fd) => fd,
+        Err(err) => {
+            file_table.close_file(pipe_fds.reader_fd);
+            return Err(err);
+        }
+    };
     debug!("pipe_fds: {:?}", pipe_fds);
     write_val_to_user(fds, &pipe_fds)?;
 
@@ -51,5 +50,3 @@ struct PipeFds {
     reader_fd: FileDesc,
     writer_fd: FileDesc,
 }
-
-const PIPE_BUF_SIZE: usize = 1024 * 1024;
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{
+        fs_resolver::{FsPath, AT_FDCWD},
+        path::Dentry,
+    },
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet},
+    syscall::constants::MAX_FILENAME_LEN,
+    util::read_cstring_from_user,
+};
+
+pub fn sys_pivot_root(new_root_ptr: Vaddr, put_old_ptr: Vaddr) -> Result<SyscallReturn> {
+    let new_root_name = read_cstring_from_user(new_root_ptr, MAX_FILENAME_LEN)?;
+    let put_old_name = read_cstring_from_user(put_old_ptr, MAX_FILENAME_LEN)?;
+    debug!(
+        "new_root = {:?}, put_old = {:?}",
+        new_root_name, put_old_name
+    );
+
+    if !credentials().capable(CapSet::SYS_ADMIN) {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_ADMIN is required");
+    }
+
+    let current = current!();
+    let (old_root, new_root, put_old) = {
+        let fs = current.fs().read();
+        let lookup = |name: CString| -> Result<Arc<Dentry>> {
+            let name = name.to_string_lossy();
+            if name.is_empty() {
+                return_errno_with_message!(Errno::ENOENT, "path is empty");
+            }
+            let fs_path = FsPath::new(AT_FDCWD, name.as_ref())?;
+            fs.lookup(&fs_path)
+        };
+        let new_root = lookup(new_root_name)?;
+        let put_old = lookup(put_old_name)?;
+        (fs.root().clone(), new_root, put_old)
+    };
+
+    new_root.pivot_root(&old_root, &put_old)?;
+
+    // Only the root and the working directory of the caller are changed, which are also
+    // seen by the processes sharing the file system information with it.
+    let mut fs = current.fs().write();
+    if fs.root().is_same(&old_root) {
+        fs.set_root(new_root.clone());
+    }
+    if fs.cwd().is_same(&old_root) {
+        fs.set_cwd(new_root);
+    }
+
+    Ok(SyscallReturn::Return(0))
+}
@@ -8,6 +8,7 @@ use crate::{
     fs::file_table::FileDesc,
     prelude::*,
     process::signal::Poller,
+    time::{clocks::MonotonicClock, Clock},
     util::{read_val_from_user, write_val_to_user},
 };
 
@@ -51,7 +52,10 @@ pub fn sys_poll(fds: Vaddr, nfds: u64, timeout: i32) -> Result<SyscallReturn> {
 }
 
 pub fn do_poll(poll_fds: &[PollFd], timeout: Option<Duration>) -> Result<usize> {
-    // The main loop of polling
+    let deadline = timeout.map(|timeout| MonotonicClock::get().read_time() + timeout);
+
+    // The main loop of polling. A single poller is shared by all the files, so
+    // the current thread sleeps until any of them gets interesting events.
     let poller = Poller::new();
     loop {
         let mut num_revents = 0;
@@ -85,15 +89,22 @@ pub fn do_poll(poll_fds: &[PollFd], timeout: Option<Duration>) -> Result<usize>
             return Ok(num_revents);
         }
 
-        // Return immediately if specifying a timeout of zero
-        if timeout.is_some() && timeout.as_ref().unwrap().is_zero() {
-            return Ok(0);
-        }
-
-        if let Some(timeout) = timeout.as_ref() {
-            poller.wait_timeout(timeout)?;
+        let wait_result = if let Some(deadline) = deadline {
+            // Return if the timeout is reached, including the case of a zero timeout
+            let now = MonotonicClock::get().read_time();
+            if now >= deadline {
+                return Ok(0);
+            }
+            poller.wait_timeout(&(deadline - now))
         } else {
-            poller.wait()?;
+            poller.wait()
+        };
+
+        match wait_result {
+            // The files are polled again before returning on timeout
+            Ok(()) => (),
+            Err(err) if err.error() == Errno::ETIME => (),
+            Err(err) => return Err(err),
         }
     }
 }
@@ -53,6 +53,15 @@ pub fn sys_prctl(option: i32, arg2: u64, arg3: u64, arg4: u64, arg5: u64) -> Res
                 thread_name.set_name(&new_thread_name)?;
             }
         }
+        PrctlCmd::PR_SET_CHILD_SUBREAPER(is_set) => {
+            let current = current!();
+            current.set_child_subreaper(is_set);
+        }
+        PrctlCmd::PR_GET_CHILD_SUBREAPER(write_to_addr) => {
+            let current = current!();
+            let write_val = current.is_child_subreaper() as i32;
+            write_val_to_user(write_to_addr, &write_val)?;
+        }
         _ => todo!(),
     }
     Ok(SyscallReturn::Return(0))
@@ -64,6 +73,8 @@ const PR_SET_NAME: i32 = 15;
 const PR_GET_NAME: i32 = 16;
 const PR_SET_TIMERSLACK: i32 = 29;
 const PR_GET_TIMERSLACK: i32 = 30;
+const PR_SET_CHILD_SUBREAPER: i32 = 36;
+const PR_GET_CHILD_SUBREAPER: i32 = 37;
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy)]
@@ -74,6 +85,8 @@ pub enum PrctlCmd {
     PR_GET_NAME(Vaddr),
     PR_SET_TIMERSLACK(u64),
     PR_GET_TIMERSLACK,
+    PR_SET_CHILD_SUBREAPER(bool),
+    PR_GET_CHILD_SUBREAPER(Vaddr),
 }
 
 impl PrctlCmd {
@@ -88,6 +101,8 @@ impl PrctlCmd {
             PR_GET_NAME => Ok(PrctlCmd::PR_GET_NAME(arg2 as _)),
             PR_GET_TIMERSLACK => todo!(),
             PR_SET_TIMERSLACK => todo!(),
+            PR_SET_CHILD_SUBREAPER => Ok(PrctlCmd::PR_SET_CHILD_SUBREAPER(arg2 != 0)),
+            PR_GET_CHILD_SUBREAPER => Ok(PrctlCmd::PR_GET_CHILD_SUBREAPER(arg2 as _)),
             _ => {
                 debug!("prctl cmd number: {}", option);
                 return_errno_with_message!(Errno::EINVAL, "unsupported prctl command");
@@ -2,8 +2,9 @@
 
 use super::SyscallReturn;
 use crate::{
+    fs::file_table::nr_open,
     prelude::*,
-    process::{Pid, ResourceType},
+    process::{Pid, RLimit64, ResourceType},
     util::{read_val_from_user, write_val_to_user},
 };
 
@@ -18,15 +19,32 @@ pub fn sys_prlimit64(
         "pid = {}, resource = {:?}, new_rlim_addr = 0x{:x}, old_rlim_addr = 0x{:x}",
         pid, resource, new_rlim_addr, old_rlim_addr
     );
-    let current = current!();
-    let mut resource_limits = current.resource_limits().lock();
+    let new_rlimit = if new_rlim_addr != 0 {
+        let new_rlimit: RLimit64 = read_val_from_user(new_rlim_addr)?;
+        // Even privileged processes cannot open more files than `nr_open`.
+        if matches!(resource, ResourceType::RLIMIT_NOFILE)
+            && new_rlimit.get_max() > nr_open() as u64
+        {
+            return_errno_with_message!(Errno::EPERM, "the limit exceeds nr_open");
+        }
+        Some(new_rlimit)
+    } else {
+        None
+    };
+
+    // The lock is not held while accessing the user space, since the page fault
+    // handler also reads the resource limits.
+    let old_rlimit = {
+        let current = current!();
+        let mut resource_limits = current.resource_limits().lock();
+        let old_rlimit = *resource_limits.get_rlimit(resource);
+        if let Some(new_rlimit) = new_rlimit {
+            *resource_limits.get_rlimit_mut(resource) = new_rlimit;
+        }
+        old_rlimit
+    };
     if old_rlim_addr != 0 {
-        let rlimit = resource_limits.get_rlimit(resource);
-        write_val_to_user(old_rlim_addr, rlimit)?;
-    }
-    if new_rlim_addr != 0 {
-        let new_rlimit = read_val_from_user(new_rlim_addr)?;
-        *resource_limits.get_rlimit_mut(resource) = new_rlimit;
+        write_val_to_user(old_rlim_addr, &old_rlimit)?;
     }
     Ok(SyscallReturn::Return(0))
 }
@@ -5,6 +5,7 @@ use crate::{
     fs::{
         file_table::FileDesc,
         fs_resolver::{FsPath, AT_FDCWD},
+        utils::InodeType,
     },
     prelude::*,
     syscall::constants::MAX_FILENAME_LEN,
@@ -23,15 +24,30 @@ pub fn sys_readlinkat(
         dirfd, path, usr_buf_addr, usr_buf_len
     );
 
+    if usr_buf_len == 0 {
+        return_errno_with_message!(Errno::EINVAL, "buffer size is zero");
+    }
+
     let current = current!();
+    let path = path.to_string_lossy();
+    // An empty path refers to `dirfd` itself, which is usually opened with
+    // `O_PATH | O_NOFOLLOW`.
+    if path.is_empty() && dirfd == AT_FDCWD {
+        return_errno_with_message!(Errno::ENOENT, "path is empty");
+    }
     let dentry = {
-        let path = path.to_string_lossy();
-        if path.is_empty() {
-            return_errno_with_message!(Errno::ENOENT, "path is empty");
-        }
         let fs_path = FsPath::new(dirfd, path.as_ref())?;
         current.fs().read().lookup_no_follow(&fs_path)?
     };
+    if dentry.type_() != InodeType::SymLink {
+        if path.is_empty() {
+            return_errno_with_message!(Errno::ENOENT, "dirfd is not a symlink");
+        }
+        return_errno_with_message!(Errno::EINVAL, "path is not a symlink");
+    }
+
+    // The link path is not terminated by a null byte, and is truncated if the
+    // buffer is too small.
     let linkpath = dentry.inode().read_link()?;
     let bytes = linkpath.as_bytes();
     let write_len = bytes.len().min(usr_buf_len);
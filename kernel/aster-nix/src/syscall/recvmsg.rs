@@ -7,12 +7,12 @@ use crate::{
     prelude::*,
     util::{
         net::{get_socket_from_fd, CUserMsgHdr},
-        read_val_from_user,
+        read_val_from_user, write_val_to_user,
     },
 };
 
 pub fn sys_recvmsg(sockfd: FileDesc, user_msghdr_ptr: Vaddr, flags: i32) -> Result<SyscallReturn> {
-    let c_user_msghdr: CUserMsgHdr = read_val_from_user(user_msghdr_ptr)?;
+    let mut c_user_msghdr: CUserMsgHdr = read_val_from_user(user_msghdr_ptr)?;
     let flags = SendRecvFlags::from_bits_truncate(flags);
 
     debug!(
@@ -34,5 +34,8 @@ pub fn sys_recvmsg(sockfd: FileDesc, user_msghdr_ptr: Vaddr, flags: i32) -> Resu
         warn!("receiving control message is not supported");
     }
 
+    c_user_msghdr.msg_flags = message_header.flags().bits() as u32;
+    write_val_to_user(user_msghdr_ptr, &c_user_msghdr)?;
+
     Ok(SyscallReturn::Return(total_bytes as _))
 }
@@ -3,7 +3,12 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::signal::{c_types::sigaction_t, sig_action::SigAction, sig_num::SigNum},
+    process::signal::{
+        c_types::sigaction_t,
+        constants::SIGCHLD,
+        sig_action::{SigAction, SigActionFlags},
+        sig_num::SigNum,
+    },
     util::{read_val_from_user, write_val_to_user},
 };
 
@@ -24,7 +29,10 @@ pub fn sys_rt_sigaction(
     let current = current!();
     let mut sig_dispositions = current.sig_dispositions().lock();
     let old_action = sig_dispositions.get(sig_num);
-    let old_action_c = old_action.as_c_type();
+    let mut old_action_c = old_action.as_c_type();
+    if sig_num == SIGCHLD && sig_dispositions.is_nocldwait() {
+        old_action_c.flags |= SigActionFlags::SA_NOCLDWAIT.bits();
+    }
     if old_sig_action_addr != 0 {
         write_val_to_user(old_sig_action_addr, &old_action_c)?;
     }
@@ -33,6 +41,11 @@ pub fn sys_rt_sigaction(
         let sig_action = SigAction::try_from(sig_action_c).unwrap();
         trace!("sig action = {:?}", sig_action);
         sig_dispositions.set(sig_num, sig_action);
+        // `SA_NOCLDWAIT` is recorded even if the action carries no flags.
+        if sig_num == SIGCHLD {
+            let flags = SigActionFlags::from_bits_truncate(sig_action_c.flags);
+            sig_dispositions.set_nocldwait(flags.contains(SigActionFlags::SA_NOCLDWAIT));
+        }
     }
 
     Ok(SyscallReturn::Return(0))
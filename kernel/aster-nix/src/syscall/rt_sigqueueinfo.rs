@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    prelude::*,
+    process::{
+        credentials, kill,
+        signal::{
+            c_types::siginfo_t,
+            constants::SI_TKILL,
+            sig_num::SigNum,
+            signals::user::{UserSignal, UserSignalKind},
+        },
+        tgkill, Pid,
+    },
+    thread::Tid,
+    util::read_val_from_user,
+};
+
+pub fn sys_rt_sigqueueinfo(pid: Pid, sig_num: u8, uinfo_addr: Vaddr) -> Result<SyscallReturn> {
+    debug!(
+        "pid = {}, sig_num = {}, uinfo_addr = 0x{:x}",
+        pid, sig_num, uinfo_addr
+    );
+
+    let signal = new_sigqueue_signal(pid, sig_num, uinfo_addr)?;
+    kill(pid, signal)?;
+    Ok(SyscallReturn::Return(0))
+}
+
+pub fn sys_rt_tgsigqueueinfo(
+    tgid: Pid,
+    tid: Tid,
+    sig_num: u8,
+    uinfo_addr: Vaddr,
+) -> Result<SyscallReturn> {
+    debug!(
+        "tgid = {}, tid = {}, sig_num = {}, uinfo_addr = 0x{:x}",
+        tgid, tid, sig_num, uinfo_addr
+    );
+
+    let signal = new_sigqueue_signal(tid, sig_num, uinfo_addr)?;
+    tgkill(tid, tgid, signal)?;
+    Ok(SyscallReturn::Return(0))
+}
+
+/// Creates the signal that carries the `siginfo_t` at `uinfo_addr`.
+///
+/// `target_id` is the pid or the tid of the target, which is used to check whether
+/// the signal is sent to the sender itself.
+fn new_sigqueue_signal(
+    target_id: u32,
+    sig_num: u8,
+    uinfo_addr: Vaddr,
+) -> Result<Option<UserSignal>> {
+    let sig_num = if sig_num == 0 {
+        None
+    } else {
+        Some(SigNum::try_from(sig_num)?)
+    };
+
+    let info = read_val_from_user::<siginfo_t>(uinfo_addr)?;
+    // According to man pages, the sender cannot impersonate the kernel or `kill` when
+    // it sends a signal to other processes, so the nonnegative codes and `SI_TKILL`
+    // are reserved.
+    if (info.si_code >= 0 || info.si_code == SI_TKILL) && target_id != current_thread!().tid() {
+        return_errno_with_message!(Errno::EPERM, "the si_code is reserved");
+    }
+
+    let signal = sig_num.map(|sig_num| {
+        let pid = current!().pid();
+        let uid = credentials().ruid();
+        UserSignal::new(sig_num, UserSignalKind::Sigqueue(info), pid, uid)
+    });
+    Ok(signal)
+}
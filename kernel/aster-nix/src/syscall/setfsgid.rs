@@ -3,22 +3,24 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Gid},
+    process::{credentials, credentials_mut, Gid},
 };
 
 pub fn sys_setfsgid(gid: i32) -> Result<SyscallReturn> {
     debug!("gid = {}", gid);
 
+    let user_ns = credentials().user_ns();
+
+    // Like Linux, an unmapped ID leaves the file system ID unchanged.
     let fsgid = if gid < 0 {
         None
     } else {
-        Some(Gid::new(gid as u32))
+        user_ns.gid_to_kernel(Gid::new(gid as u32)).ok()
     };
 
-    let old_fsgid = {
-        let credentials = credentials_mut();
-        credentials.set_fsgid(fsgid)?
-    };
+    let old_fsgid = credentials_mut().set_fsgid(fsgid)?;
 
-    Ok(SyscallReturn::Return(old_fsgid.as_u32() as _))
+    Ok(SyscallReturn::Return(
+        user_ns.gid_from_kernel(old_fsgid).as_u32() as _,
+    ))
 }
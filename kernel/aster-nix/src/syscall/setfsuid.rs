@@ -3,22 +3,24 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Uid},
+    process::{credentials, credentials_mut, Uid},
 };
 
 pub fn sys_setfsuid(uid: i32) -> Result<SyscallReturn> {
     debug!("uid = {}", uid);
 
+    let user_ns = credentials().user_ns();
+
+    // Like Linux, an unmapped ID leaves the file system ID unchanged.
     let fsuid = if uid < 0 {
         None
     } else {
-        Some(Uid::new(uid as u32))
+        user_ns.uid_to_kernel(Uid::new(uid as u32)).ok()
     };
 
-    let old_fsuid = {
-        let credentials = credentials_mut();
-        credentials.set_fsuid(fsuid)?
-    };
+    let old_fsuid = credentials_mut().set_fsuid(fsuid)?;
 
-    Ok(SyscallReturn::Return(old_fsuid.as_u32() as _))
+    Ok(SyscallReturn::Return(
+        user_ns.uid_from_kernel(old_fsuid).as_u32() as _,
+    ))
 }
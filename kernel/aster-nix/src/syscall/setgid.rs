@@ -3,7 +3,7 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Gid},
+    process::{credentials, credentials_mut, Gid},
 };
 
 pub fn sys_setgid(gid: i32) -> Result<SyscallReturn> {
@@ -13,7 +13,9 @@ pub fn sys_setgid(gid: i32) -> Result<SyscallReturn> {
         return_errno_with_message!(Errno::EINVAL, "gid cannot be negative");
     }
 
-    let gid = Gid::new(gid as u32);
+    let gid = credentials()
+        .user_ns()
+        .gid_to_kernel(Gid::new(gid as u32))?;
 
     let credentials = credentials_mut();
     credentials.set_gid(gid);
@@ -3,7 +3,7 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Gid},
+    process::{credentials, credentials_mut, Gid},
     util::read_val_from_user,
 };
 
@@ -16,11 +16,13 @@ pub fn sys_setgroups(size: usize, group_list_addr: Vaddr) -> Result<SyscallRetur
         return_errno_with_message!(Errno::EINVAL, "size cannot be greater than NGROUPS_MAX");
     }
 
+    let user_ns = credentials().user_ns();
+
     let mut new_groups = BTreeSet::new();
     for idx in 0..size {
         let addr = group_list_addr + idx * core::mem::size_of::<Gid>();
         let gid = read_val_from_user(addr)?;
-        new_groups.insert(gid);
+        new_groups.insert(user_ns.gid_to_kernel(gid)?);
     }
 
     let credentials = credentials_mut();
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{file_table::FileDesc, inode_handle::InodeHandle},
+    prelude::*,
+    process::{credentials, credentials::capabilities::CapSet, credentials_mut, CloneFlags},
+};
+
+pub fn sys_setns(fd: FileDesc, nstype: i32) -> Result<SyscallReturn> {
+    debug!("fd = {}, nstype = 0x{:x}", fd, nstype);
+
+    let file = {
+        let current = current!();
+        let file_table = current.file_table().lock();
+        file_table.get_file(fd)?.clone()
+    };
+    // TODO: Support other namespaces once they are implemented.
+    let user_ns = file
+        .downcast_ref::<InodeHandle>()
+        .and_then(|inode_handle| inode_handle.user_ns())
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "the file is not a namespace file"))?;
+    if nstype != 0 && nstype as u32 != CloneFlags::CLONE_NEWUSER.bits() {
+        return_errno_with_message!(Errno::EINVAL, "the namespace type does not match");
+    }
+
+    let current = current!();
+    let nr_alive_threads = current
+        .threads()
+        .lock()
+        .iter()
+        .filter(|thread| !thread.status().is_exited())
+        .count();
+    if nr_alive_threads > 1 {
+        return_errno_with_message!(
+            Errno::EINVAL,
+            "a multithreaded process cannot join a user namespace"
+        );
+    }
+
+    let credentials = credentials();
+    let current_user_ns = credentials.user_ns();
+    if Arc::ptr_eq(&current_user_ns, &user_ns) {
+        return_errno_with_message!(Errno::EINVAL, "the process is already in the namespace");
+    }
+    // `CAP_SYS_ADMIN` is required in the target namespace, which is only the case if the
+    // target namespace is nested in the current one.
+    if !credentials.effective_capset().contains(CapSet::SYS_ADMIN)
+        || !user_ns.is_same_or_descendant_of(&current_user_ns)
+    {
+        return_errno_with_message!(Errno::EPERM, "CAP_SYS_ADMIN is required");
+    }
+
+    credentials_mut().enter_user_ns(user_ns);
+    Ok(SyscallReturn::Return(0))
+}
@@ -3,22 +3,24 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Gid},
+    process::{credentials, credentials_mut, Gid},
 };
 
 pub fn sys_setregid(rgid: i32, egid: i32) -> Result<SyscallReturn> {
     debug!("rgid = {}, egid = {}", rgid, egid);
 
-    let rgid = if rgid > 0 {
-        Some(Gid::new(rgid as u32))
-    } else {
+    let user_ns = credentials().user_ns();
+
+    let rgid = if rgid == -1 {
         None
+    } else {
+        Some(user_ns.gid_to_kernel(Gid::new(rgid as u32))?)
     };
 
-    let egid = if egid > 0 {
-        Some(Gid::new(egid as u32))
-    } else {
+    let egid = if egid == -1 {
         None
+    } else {
+        Some(user_ns.gid_to_kernel(Gid::new(egid as u32))?)
     };
 
     let credentials = credentials_mut();
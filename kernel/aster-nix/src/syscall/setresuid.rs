@@ -3,26 +3,28 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Uid},
+    process::{credentials, credentials_mut, Uid},
 };
 
 pub fn sys_setresuid(ruid: i32, euid: i32, suid: i32) -> Result<SyscallReturn> {
-    let ruid = if ruid > 0 {
-        Some(Uid::new(ruid as u32))
-    } else {
+    let user_ns = credentials().user_ns();
+
+    let ruid = if ruid == -1 {
         None
+    } else {
+        Some(user_ns.uid_to_kernel(Uid::new(ruid as u32))?)
     };
 
-    let euid = if euid > 0 {
-        Some(Uid::new(euid as u32))
-    } else {
+    let euid = if euid == -1 {
         None
+    } else {
+        Some(user_ns.uid_to_kernel(Uid::new(euid as u32))?)
     };
 
-    let suid = if suid > 0 {
-        Some(Uid::new(suid as u32))
-    } else {
+    let suid = if suid == -1 {
         None
+    } else {
+        Some(user_ns.uid_to_kernel(Uid::new(suid as u32))?)
     };
 
     debug!("ruid = {:?}, euid = {:?}, suid = {:?}", ruid, euid, suid);
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use core::time::Duration;
+
+use super::{clock_settime::set_realtime, SyscallReturn};
+use crate::{prelude::*, time::timeval_t, util::read_val_from_user};
+
+// The use of the timezone structure is obsolete.
+// Glibc sets the timezone_addr argument to NULL, so just ignore it.
+pub fn sys_settimeofday(timeval_addr: Vaddr, /* timezone_addr: Vaddr */) -> Result<SyscallReturn> {
+    if timeval_addr == 0 {
+        return Ok(SyscallReturn::Return(0));
+    }
+
+    let timeval = read_val_from_user::<timeval_t>(timeval_addr)?;
+    debug!("timeval = {:?}", timeval);
+
+    if timeval.sec < 0 || !(0..USECS_PER_SEC).contains(&timeval.usec) {
+        return_errno_with_message!(Errno::EINVAL, "the time is invalid");
+    }
+
+    set_realtime(Duration::from(timeval))?;
+
+    Ok(SyscallReturn::Return(0))
+}
+
+const USECS_PER_SEC: i64 = 1_000_000;
@@ -3,7 +3,7 @@
 use super::SyscallReturn;
 use crate::{
     prelude::*,
-    process::{credentials_mut, Uid},
+    process::{credentials, credentials_mut, Uid},
 };
 
 pub fn sys_setuid(uid: i32) -> Result<SyscallReturn> {
@@ -13,7 +13,9 @@ pub fn sys_setuid(uid: i32) -> Result<SyscallReturn> {
         return_errno_with_message!(Errno::EINVAL, "uid cannot be negative");
     }
 
-    let uid = Uid::new(uid as u32);
+    let uid = credentials()
+        .user_ns()
+        .uid_to_kernel(Uid::new(uid as u32))?;
 
     let credentials = credentials_mut();
     credentials.set_uid(uid);
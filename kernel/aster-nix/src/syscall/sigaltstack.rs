@@ -1,13 +1,11 @@
 // SPDX-License-Identifier: MPL-2.0
 
-#![allow(dead_code)]
-
 use super::SyscallReturn;
 use crate::{
     prelude::*,
     process::{
         posix_thread::PosixThreadExt,
-        signal::{SigStack, SigStackFlags},
+        signal::{c_types::stack_t, SigStack, SigStackFlags, SigStackStatus},
     },
     util::{read_val_from_user, write_val_to_user},
 };
@@ -18,99 +16,80 @@ pub fn sys_sigaltstack(sig_stack_addr: Vaddr, old_sig_stack_addr: Vaddr) -> Resu
         sig_stack_addr, old_sig_stack_addr
     );
 
-    let old_stack = {
-        let current_thread = current_thread!();
-        let posix_thread = current_thread.as_posix_thread().unwrap();
-        let sig_stack = posix_thread.sig_stack().lock();
-        sig_stack.clone()
+    // Like Linux, the new stack is read before the old stack is written, so both of them
+    // can be the same buffer.
+    let new_stack = if sig_stack_addr != 0 {
+        let stack = read_val_from_user::<stack_t>(sig_stack_addr)?;
+        Some(parse_new_stack(stack)?)
+    } else {
+        None
     };
 
-    get_old_stack(old_sig_stack_addr, old_stack.as_ref())?;
-    set_new_stack(sig_stack_addr, old_stack.as_ref())?;
-
-    Ok(SyscallReturn::Return(0))
-}
+    let current_thread = current_thread!();
+    let posix_thread = current_thread.as_posix_thread().unwrap();
+    let mut sig_stack = posix_thread.sig_stack().lock();
+    let old_stack = stack_t::from(sig_stack.as_ref());
+
+    if let Some(new_stack) = new_stack {
+        if let Some(old_stack) = sig_stack.as_ref()
+            && old_stack.is_active()
+        {
+            return_errno_with_message!(Errno::EPERM, "the old stack is active now");
+        }
 
-fn get_old_stack(old_sig_stack_addr: Vaddr, old_stack: Option<&SigStack>) -> Result<()> {
-    if old_sig_stack_addr == 0 {
-        return Ok(());
+        debug!("new_stack = {:?}", new_stack);
+        *sig_stack = new_stack;
     }
+    drop(sig_stack);
 
-    let Some(old_stack) = old_stack else {
-        return Ok(());
-    };
-
-    debug!("old stack = {:?}", old_stack);
+    if old_sig_stack_addr != 0 {
+        debug!("old stack = {:?}", old_stack);
+        write_val_to_user(old_sig_stack_addr, &old_stack)?;
+    }
 
-    let stack = stack_t::from(old_stack.clone());
-    write_val_to_user(old_sig_stack_addr, &stack)
+    Ok(SyscallReturn::Return(0))
 }
 
-fn set_new_stack(sig_stack_addr: Vaddr, old_stack: Option<&SigStack>) -> Result<()> {
-    if sig_stack_addr == 0 {
-        return Ok(());
-    }
+/// Parses the new stack.
+///
+/// If the stack is disabled with `SS_DISABLE`, this function returns `None`.
+fn parse_new_stack(stack: stack_t) -> Result<Option<SigStack>> {
+    let flags = SigStackFlags::from_bits_truncate(stack.ss_flags as u32);
+    let mode = stack.ss_flags as u32 & !SigStackFlags::all().bits();
 
-    if let Some(old_stack) = old_stack
-        && old_stack.is_active()
-    {
-        return_errno_with_message!(Errno::EPERM, "the old stack is active now");
+    // Like Linux, `SS_ONSTACK` is accepted for compatibility, which means the same as zero.
+    if mode == SigStackStatus::SS_DISABLE as u32 {
+        return Ok(None);
+    }
+    if mode != SigStackStatus::SS_INACTIVE as u32 && mode != SigStackStatus::SS_ONSTACK as u32 {
+        return_errno_with_message!(Errno::EINVAL, "invalid flags");
     }
 
-    let new_stack = {
-        let stack = read_val_from_user::<stack_t>(sig_stack_addr)?;
-        SigStack::try_from(stack)?
-    };
-
-    debug!("new_stack = {:?}", new_stack);
-
-    let current_thread = current_thread!();
-    let posix_thread = current_thread.as_posix_thread().unwrap();
-    *posix_thread.sig_stack().lock() = Some(new_stack);
-
-    Ok(())
-}
+    if stack.ss_size < MINSIGSTKSZ {
+        return_errno_with_message!(Errno::ENOMEM, "stack size is less than MINSIGSTKSZ");
+    }
 
-#[allow(non_camel_case_types)]
-#[derive(Debug, Clone, Copy, Pod)]
-#[repr(C)]
-struct stack_t {
-    // Base address of stack
-    sp: Vaddr,
-    flags: i32,
-    // Number of bytes in stack
-    size: usize,
+    Ok(Some(SigStack::new(stack.ss_sp, flags, stack.ss_size)))
 }
 
-impl TryFrom<stack_t> for SigStack {
-    type Error = Error;
-
-    fn try_from(stack: stack_t) -> Result<Self> {
-        if stack.flags < 0 {
-            return_errno_with_message!(Errno::EINVAL, "negative flags");
-        }
-
-        let flags = SigStackFlags::from_bits(stack.flags as u32)
-            .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid flags"))?;
-
-        if stack.size < MINSTKSZ {
-            return_errno_with_message!(Errno::ENOMEM, "stack size is less than MINSTKSZ");
-        }
-
-        Ok(Self::new(stack.sp, flags, stack.size))
-    }
-}
+impl From<Option<&SigStack>> for stack_t {
+    fn from(stack: Option<&SigStack>) -> Self {
+        let Some(stack) = stack else {
+            return Self {
+                ss_sp: 0,
+                ss_flags: SigStackStatus::SS_DISABLE as i32,
+                ss_size: 0,
+            };
+        };
 
-impl From<SigStack> for stack_t {
-    fn from(stack: SigStack) -> Self {
         let flags = stack.flags().bits() as i32 | stack.status() as i32;
         Self {
-            sp: stack.base(),
-            flags,
-            size: stack.size(),
+            ss_sp: stack.base(),
+            ss_flags: flags,
+            ss_size: stack.size(),
         }
     }
 }
 
-const SIGSTKSZ: usize = 8192;
-const MINSTKSZ: usize = 2048;
+/// The minimum size of a signal stack.
+const MINSIGSTKSZ: usize = 2048;
@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! `signalfd()` creates a file descriptor (we name it as `SignalFile`)
+//! that can be used to accept signals targeted at the caller.
+//!
+//! Reading from `SignalFile` dequeues the pending signals that are in the mask
+//! of the file, so these signals will not be delivered to the signal handlers.
+//! The signals should usually be blocked, otherwise they may be delivered
+//! before they are read from the file.
+//!
+//! For more detailed information about this syscall,
+//! refer to the man 2 signalfd documentation.
+//!
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use ostd::mm::VmIo;
+
+use super::SyscallReturn;
+use crate::{
+    events::{IoEvents, Observer},
+    fs::{
+        file_handle::{FileCount, FileLike},
+        file_table::{FdFlags, FileDesc},
+        utils::{CreationFlags, InodeMode, InodeType, Metadata, StatusFlags},
+    },
+    prelude::*,
+    process::{
+        posix_thread::{PosixThread, PosixThreadExt},
+        signal::{
+            c_types::siginfo_t,
+            constants::{
+                SIGBUS, SIGFPE, SIGILL, SIGKILL, SIGSEGV, SIGSTOP, SI_QUEUE, SI_TKILL, SI_USER,
+            },
+            sig_mask::SigMask,
+            Pollee, Poller, SigEvents, SigEventsFilter,
+        },
+        Gid, Uid,
+    },
+    thread::Thread,
+    time::clocks::RealTimeClock,
+};
+
+pub fn sys_signalfd(fd: FileDesc, mask_addr: Vaddr, sizemask: usize) -> Result<SyscallReturn> {
+    debug!(
+        "fd = {}, mask_addr = 0x{:x}, sizemask = {}",
+        fd, mask_addr, sizemask
+    );
+
+    let fd = do_sys_signalfd4(fd, mask_addr, sizemask, Flags::empty())?;
+
+    Ok(SyscallReturn::Return(fd as _))
+}
+
+pub fn sys_signalfd4(
+    fd: FileDesc,
+    mask_addr: Vaddr,
+    sizemask: usize,
+    flags: u32,
+) -> Result<SyscallReturn> {
+    trace!("raw flags = {}", flags);
+    let flags = Flags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "unknown flags"))?;
+    debug!(
+        "fd = {}, mask_addr = 0x{:x}, sizemask = {}, flags = {:?}",
+        fd, mask_addr, sizemask, flags
+    );
+
+    let fd = do_sys_signalfd4(fd, mask_addr, sizemask, flags)?;
+
+    Ok(SyscallReturn::Return(fd as _))
+}
+
+fn do_sys_signalfd4(
+    fd: FileDesc,
+    mask_addr: Vaddr,
+    sizemask: usize,
+    flags: Flags,
+) -> Result<FileDesc> {
+    if sizemask != core::mem::size_of::<u64>() {
+        return_errno_with_message!(Errno::EINVAL, "the size of the mask is invalid");
+    }
+
+    let current = current!();
+    let mut mask = SigMask::from(current.root_vmar().read_val::<u64>(mask_addr)?);
+    // According to man pages, "it is not possible to receive SIGKILL or SIGSTOP signals
+    // via a signalfd file descriptor; these signals are silently ignored if specified in mask."
+    mask.remove_signal(SIGKILL);
+    mask.remove_signal(SIGSTOP);
+
+    let mut file_table = current.file_table().lock();
+
+    // Updates the mask of an existing file
+    if fd >= 0 {
+        let file = file_table.get_file(fd)?;
+        let Some(signal_file) = file.downcast_ref::<SignalFile>() else {
+            return_errno_with_message!(Errno::EINVAL, "the file is not a signalfd file");
+        };
+        signal_file.set_mask(mask);
+        return Ok(fd);
+    }
+
+    if fd != -1 {
+        return_errno_with_message!(Errno::EBADF, "the fd is invalid");
+    }
+
+    let signal_file = SignalFile::new(mask, flags);
+    let fd_flags = if flags.contains(Flags::SFD_CLOEXEC) {
+        FdFlags::CLOEXEC
+    } else {
+        FdFlags::empty()
+    };
+    file_table.insert(signal_file, fd_flags)
+}
+
+bitflags! {
+    struct Flags: u32 {
+        const SFD_CLOEXEC = CreationFlags::O_CLOEXEC.bits();
+        const SFD_NONBLOCK = StatusFlags::O_NONBLOCK.bits();
+    }
+}
+
+/// The file that accepts the signals in its mask.
+///
+/// The file observes the signal queues of the thread that creates it, so that it
+/// becomes readable once a signal in the mask is sent to that thread.
+//
+// FIXME: The signals are dequeued from the thread that reads the file, but only
+// the signals sent to the creating thread notify the pollers of the file.
+struct SignalFile {
+    mask: AtomicU64,
+    pollee: Pollee,
+    is_nonblocking: AtomicBool,
+    thread: Weak<Thread>,
+    weak_self: Weak<SignalFile>,
+    _file_count: FileCount,
+}
+
+impl SignalFile {
+    fn new(mask: SigMask, flags: Flags) -> Arc<Self> {
+        let current_thread = current_thread!();
+        let signal_file = Arc::new_cyclic(|weak_self| Self {
+            mask: AtomicU64::new(mask.as_u64()),
+            pollee: Pollee::new(IoEvents::empty()),
+            is_nonblocking: AtomicBool::new(flags.contains(Flags::SFD_NONBLOCK)),
+            thread: Arc::downgrade(&current_thread),
+            weak_self: weak_self.clone(),
+            _file_count: FileCount::new(),
+        });
+
+        let posix_thread = current_thread.as_posix_thread().unwrap();
+        let observer = signal_file.weak_self.clone() as Weak<dyn Observer<SigEvents>>;
+        posix_thread
+            .register_sigqueue_observer(observer, SigEventsFilter::new(SigMask::new_empty()));
+        signal_file.update_io_state(posix_thread);
+
+        signal_file
+    }
+
+    fn mask(&self) -> SigMask {
+        SigMask::from(self.mask.load(Ordering::Relaxed))
+    }
+
+    fn set_mask(&self, mask: SigMask) {
+        self.mask.store(mask.as_u64(), Ordering::Relaxed);
+
+        let current_thread = current_thread!();
+        self.update_io_state(current_thread.as_posix_thread().unwrap());
+    }
+
+    fn is_nonblocking(&self) -> bool {
+        self.is_nonblocking.load(Ordering::Relaxed)
+    }
+
+    fn update_io_state(&self, posix_thread: &PosixThread) {
+        let pending = posix_thread.sig_pending().as_u64() & self.mask().as_u64();
+        if pending != 0 {
+            self.pollee.add_events(IoEvents::IN);
+        } else {
+            self.pollee.del_events(IoEvents::IN);
+        }
+    }
+
+    /// Dequeues the signals in the mask and fills `buf` with their information.
+    ///
+    /// Returns `EAGAIN` if there are no such signals.
+    fn try_read(&self, buf: &mut [u8]) -> Result<usize> {
+        let current_thread = current_thread!();
+        let posix_thread = current_thread.as_posix_thread().unwrap();
+        let blocked = SigMask::from(!self.mask().as_u64());
+
+        let info_len = core::mem::size_of::<signalfd_siginfo>();
+        let mut read_len = 0;
+        while buf.len() - read_len >= info_len {
+            let Some(signal) = posix_thread.dequeue_signal(&blocked) else {
+                break;
+            };
+            let info = signalfd_siginfo::from(signal.to_info());
+            buf[read_len..read_len + info_len].copy_from_slice(info.as_bytes());
+            read_len += info_len;
+        }
+
+        self.update_io_state(posix_thread);
+
+        if read_len == 0 {
+            return_errno_with_message!(Errno::EAGAIN, "no signals are pending");
+        }
+        Ok(read_len)
+    }
+}
+
+impl Observer<SigEvents> for SignalFile {
+    fn on_events(&self, events: &SigEvents) {
+        if self.mask().contains(events.sig_num()) {
+            self.pollee.add_events(IoEvents::IN);
+        }
+    }
+}
+
+impl Drop for SignalFile {
+    fn drop(&mut self) {
+        let Some(thread) = self.thread.upgrade() else {
+            return;
+        };
+        let observer = self.weak_self.clone() as Weak<dyn Observer<SigEvents>>;
+        thread
+            .as_posix_thread()
+            .unwrap()
+            .unregiser_sigqueue_observer(&observer);
+    }
+}
+
+impl FileLike for SignalFile {
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        if buf.len() < core::mem::size_of::<signalfd_siginfo>() {
+            return_errno_with_message!(Errno::EINVAL, "buf len is less than signalfd_siginfo size");
+        }
+
+        loop {
+            match self.try_read(buf) {
+                Err(err) if err.error() == Errno::EAGAIN && !self.is_nonblocking() => (),
+                res => return res,
+            }
+
+            // Wait until a signal in the mask is pending
+            let poller = Poller::new();
+            if self.pollee.poll(IoEvents::IN, Some(&poller)).is_empty() {
+                poller.wait()?;
+            }
+        }
+    }
+
+    fn poll(&self, mask: IoEvents, poller: Option<&Poller>) -> IoEvents {
+        let current_thread = current_thread!();
+        self.update_io_state(current_thread.as_posix_thread().unwrap());
+
+        self.pollee.poll(mask, poller)
+    }
+
+    fn status_flags(&self) -> StatusFlags {
+        if self.is_nonblocking() {
+            StatusFlags::O_NONBLOCK
+        } else {
+            StatusFlags::empty()
+        }
+    }
+
+    fn set_status_flags(&self, new_flags: StatusFlags) -> Result<()> {
+        self.is_nonblocking.store(
+            new_flags.contains(StatusFlags::O_NONBLOCK),
+            Ordering::Relaxed,
+        );
+
+        // TODO: deal with other flags
+
+        Ok(())
+    }
+
+    fn register_observer(
+        &self,
+        observer: Weak<dyn Observer<IoEvents>>,
+        mask: IoEvents,
+    ) -> Result<()> {
+        self.pollee.register_observer(observer, mask);
+        Ok(())
+    }
+
+    fn unregister_observer(
+        &self,
+        observer: &Weak<dyn Observer<IoEvents>>,
+    ) -> Option<Weak<dyn Observer<IoEvents>>> {
+        self.pollee.unregister_observer(observer)
+    }
+
+    fn metadata(&self) -> Metadata {
+        let now = RealTimeClock::get().read_time();
+        Metadata {
+            dev: 0,
+            ino: 0,
+            size: 0,
+            blk_size: 0,
+            blocks: 0,
+            atime: now,
+            mtime: now,
+            ctime: now,
+            type_: InodeType::NamedPipe,
+            mode: InodeMode::from_bits_truncate(0o600),
+            nlinks: 1,
+            uid: Uid::new_root(),
+            gid: Gid::new_root(),
+            rdev: 0,
+        }
+    }
+}
+
+/// The signal information that is read from `SignalFile`.
+#[derive(Debug, Default, Clone, Copy, Pod)]
+#[repr(C)]
+struct signalfd_siginfo {
+    ssi_signo: u32,
+    ssi_errno: i32,
+    ssi_code: i32,
+    ssi_pid: u32,
+    ssi_uid: u32,
+    ssi_fd: i32,
+    ssi_tid: u32,
+    ssi_band: u32,
+    ssi_overrun: u32,
+    ssi_trapno: u32,
+    ssi_status: i32,
+    ssi_int: i32,
+    ssi_ptr: u64,
+    ssi_utime: u64,
+    ssi_stime: u64,
+    ssi_addr: u64,
+    ssi_addr_lsb: u16,
+    _pad2: u16,
+    ssi_syscall: i32,
+    ssi_call_addr: u64,
+    ssi_arch: u32,
+    _pad: [u8; 28],
+}
+
+impl From<siginfo_t> for signalfd_siginfo {
+    fn from(info: siginfo_t) -> Self {
+        let mut ssi = Self {
+            ssi_signo: info.si_signo as u32,
+            ssi_errno: info.si_errno,
+            ssi_code: info.si_code,
+            ..Default::default()
+        };
+
+        if matches!(info.si_code, SI_USER | SI_TKILL | SI_QUEUE) {
+            ssi.ssi_pid = info.si_pid();
+            ssi.ssi_uid = info.si_uid().as_u32();
+        }
+
+        let is_fault = [SIGSEGV, SIGBUS, SIGILL, SIGFPE]
+            .iter()
+            .any(|sig_num| sig_num.as_u8() as i32 == info.si_signo);
+        if is_fault && info.si_code > 0 {
+            ssi.ssi_addr = info.si_addr() as u64;
+        }
+
+        ssi
+    }
+}
@@ -4,9 +4,10 @@ use super::SyscallReturn;
 use crate::{
     fs::{file_handle::FileLike, file_table::FdFlags},
     net::socket::{
-        ip::{DatagramSocket, StreamSocket},
-        unix::UnixStreamSocket,
+        ip::{DatagramSocket, RawSocket, StreamSocket},
+        unix::{UnixDatagramSocket, UnixStreamSocket},
         vsock::VsockStreamSocket,
+        SocketInfo,
     },
     prelude::*,
     util::net::{CSocketAddrFamily, Protocol, SockFlags, SockType, SOCK_TYPE_MASK},
@@ -22,22 +23,35 @@ pub fn sys_socket(domain: i32, type_: i32, protocol: i32) -> Result<SyscallRetur
         domain, sock_type, sock_flags, protocol
     );
     let nonblocking = sock_flags.contains(SockFlags::SOCK_NONBLOCK);
+    // The default protocol of the socket type is used if no protocol is specified.
+    let protocol = match (domain, sock_type, protocol) {
+        (CSocketAddrFamily::AF_INET, SockType::SOCK_STREAM, Protocol::IPPROTO_IP) => {
+            Protocol::IPPROTO_TCP
+        }
+        (CSocketAddrFamily::AF_INET, SockType::SOCK_DGRAM, Protocol::IPPROTO_IP) => {
+            Protocol::IPPROTO_UDP
+        }
+        _ => protocol,
+    };
+    let info = SocketInfo::new(domain, protocol);
     let file_like = match (domain, sock_type, protocol) {
         (CSocketAddrFamily::AF_UNIX, SockType::SOCK_STREAM, _) => {
-            Arc::new(UnixStreamSocket::new(nonblocking)) as Arc<dyn FileLike>
-        }
-        (
-            CSocketAddrFamily::AF_INET,
-            SockType::SOCK_STREAM,
-            Protocol::IPPROTO_IP | Protocol::IPPROTO_TCP,
-        ) => StreamSocket::new(nonblocking) as Arc<dyn FileLike>,
-        (
-            CSocketAddrFamily::AF_INET,
-            SockType::SOCK_DGRAM,
-            Protocol::IPPROTO_IP | Protocol::IPPROTO_UDP,
-        ) => DatagramSocket::new(nonblocking) as Arc<dyn FileLike>,
+            Arc::new(UnixStreamSocket::new(info, nonblocking)) as Arc<dyn FileLike>
+        }
+        (CSocketAddrFamily::AF_UNIX, SockType::SOCK_DGRAM, _) => {
+            UnixDatagramSocket::new(info, nonblocking)? as Arc<dyn FileLike>
+        }
+        (CSocketAddrFamily::AF_INET, SockType::SOCK_STREAM, Protocol::IPPROTO_TCP) => {
+            StreamSocket::new(info, nonblocking) as Arc<dyn FileLike>
+        }
+        (CSocketAddrFamily::AF_INET, SockType::SOCK_DGRAM, Protocol::IPPROTO_UDP) => {
+            DatagramSocket::new(info, nonblocking) as Arc<dyn FileLike>
+        }
+        (CSocketAddrFamily::AF_INET, SockType::SOCK_RAW, Protocol::IPPROTO_ICMP) => {
+            RawSocket::new(info, nonblocking)? as Arc<dyn FileLike>
+        }
         (CSocketAddrFamily::AF_VSOCK, SockType::SOCK_STREAM, _) => {
-            Arc::new(VsockStreamSocket::new(nonblocking)) as Arc<dyn FileLike>
+            Arc::new(VsockStreamSocket::new(info, nonblocking)) as Arc<dyn FileLike>
         }
         _ => return_errno_with_message!(Errno::EAFNOSUPPORT, "unsupported domain"),
     };
@@ -49,7 +63,7 @@ pub fn sys_socket(domain: i32, type_: i32, protocol: i32) -> Result<SyscallRetur
         } else {
             FdFlags::empty()
         };
-        file_table.insert(file_like, fd_flags)
+        file_table.insert(file_like, fd_flags)?
     };
     Ok(SyscallReturn::Return(fd as _))
 }
@@ -2,8 +2,14 @@
 
 use super::SyscallReturn;
 use crate::{
-    fs::file_table::{FdFlags, FileDesc},
-    net::socket::unix::UnixStreamSocket,
+    fs::{
+        file_handle::FileLike,
+        file_table::{FdFlags, FileDesc},
+    },
+    net::socket::{
+        unix::{UnixDatagramSocket, UnixStreamSocket},
+        SocketInfo,
+    },
     prelude::*,
     util::{
         net::{CSocketAddrFamily, Protocol, SockFlags, SockType, SOCK_TYPE_MASK},
@@ -23,9 +29,15 @@ pub fn sys_socketpair(domain: i32, type_: i32, protocol: i32, sv: Vaddr) -> Resu
     );
     // TODO: deal with all sock_flags and protocol
     let nonblocking = sock_flags.contains(SockFlags::SOCK_NONBLOCK);
-    let (socket_a, socket_b) = match (domain, sock_type) {
+    let info = SocketInfo::new(domain, protocol);
+    let (socket_a, socket_b): (Arc<dyn FileLike>, Arc<dyn FileLike>) = match (domain, sock_type) {
         (CSocketAddrFamily::AF_UNIX, SockType::SOCK_STREAM) => {
-            UnixStreamSocket::new_pair(nonblocking)?
+            let (socket_a, socket_b) = UnixStreamSocket::new_pair(info, nonblocking)?;
+            (socket_a, socket_b)
+        }
+        (CSocketAddrFamily::AF_UNIX, SockType::SOCK_DGRAM) => {
+            let (socket_a, socket_b) = UnixDatagramSocket::new_pair(info, nonblocking)?;
+            (socket_a, socket_b)
         }
         _ => return_errno_with_message!(
             Errno::EAFNOSUPPORT,
@@ -41,8 +53,14 @@ pub fn sys_socketpair(domain: i32, type_: i32, protocol: i32, sv: Vaddr) -> Resu
         } else {
             FdFlags::empty()
         };
-        let fd_a = file_table.insert(socket_a, fd_flags);
-        let fd_b = file_table.insert(socket_b, fd_flags);
+        let fd_a = file_table.insert(socket_a, fd_flags)?;
+        let fd_b = match file_table.insert(socket_b, fd_flags) {
+            Ok(fd) => fd,
+            Err(err) => {
+                file_table.close_file(fd_a);
+                return Err(err);
+            }
+        };
         SocketFds(fd_a, fd_b)
     };
 
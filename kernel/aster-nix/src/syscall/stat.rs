@@ -10,6 +10,7 @@ use crate::{
         utils::Metadata,
     },
     prelude::*,
+    process::credentials,
     syscall::constants::MAX_FILENAME_LEN,
     time::timespec_t,
     util::{read_cstring_from_user, write_val_to_user},
@@ -120,14 +121,17 @@ pub struct Stat {
 }
 
 impl From<Metadata> for Stat {
+    /// Converts the metadata, in which the owner and the group are shown as the IDs in
+    /// the user namespace of the current thread.
     fn from(info: Metadata) -> Self {
+        let user_ns = credentials().user_ns();
         Self {
             st_dev: info.dev,
             st_ino: info.ino,
             st_nlink: info.nlinks,
             st_mode: info.type_ as u32 | info.mode.bits() as u32,
-            st_uid: info.uid.as_u32(),
-            st_gid: info.gid.as_u32(),
+            st_uid: user_ns.uid_from_kernel(info.uid).as_u32(),
+            st_gid: user_ns.gid_from_kernel(info.gid).as_u32(),
             __pad0: 0,
             st_rdev: info.rdev,
             st_size: info.size as isize,
@@ -1,9 +1,35 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use super::SyscallReturn;
-use crate::prelude::*;
+use crate::{
+    fs::{file_table::FileDesc, inode_handle::InodeHandle},
+    prelude::*,
+};
 
 pub fn sys_sync() -> Result<SyscallReturn> {
-    crate::fs::rootfs::root_mount().sync()?;
+    // The root mount may have been put under another mount by `pivot_root`.
+    let mut mount_node = crate::fs::rootfs::root_mount().clone();
+    while let Some(parent) = mount_node.parent().and_then(|parent| parent.upgrade()) {
+        mount_node = parent;
+    }
+    mount_node.sync()?;
+    Ok(SyscallReturn::Return(0))
+}
+
+pub fn sys_syncfs(fd: FileDesc) -> Result<SyscallReturn> {
+    debug!("fd = {}", fd);
+
+    let fs = {
+        let current = current!();
+        let file_table = current.file_table().lock();
+        let file = file_table.get_file(fd)?;
+        // The files that are not backed by inodes (e.g., pipes and sockets) belong to
+        // pseudo file systems, which have nothing to be synchronized.
+        let Some(inode_handle) = file.downcast_ref::<InodeHandle>() else {
+            return Ok(SyscallReturn::Return(0));
+        };
+        inode_handle.dentry().fs()
+    };
+    fs.sync()?;
     Ok(SyscallReturn::Return(0))
 }
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{file_table::FileDesc, inode_handle::InodeHandle, utils::InodeType},
+    prelude::*,
+};
+
+pub fn sys_sync_file_range(
+    fd: FileDesc,
+    offset: isize,
+    nbytes: isize,
+    flags: u32,
+) -> Result<SyscallReturn> {
+    debug!(
+        "fd = {}, offset = {}, nbytes = {}, flags = 0x{:x}",
+        fd, offset, nbytes, flags
+    );
+
+    let dentry = {
+        let current = current!();
+        let file_table = current.file_table().lock();
+        let file = file_table.get_file(fd)?;
+        let inode_handle = file.downcast_ref::<InodeHandle>();
+        if inode_handle.is_some_and(|inode_handle| inode_handle.is_path_only()) {
+            return_errno_with_message!(Errno::EBADF, "file is opened as a path");
+        }
+
+        let flags = SyncFileRangeFlags::from_bits(flags)
+            .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid flags"))?;
+        check_range(offset, nbytes)?;
+
+        let Some(inode_handle) = inode_handle else {
+            return_errno_with_message!(Errno::ESPIPE, "the file is not seekable");
+        };
+        let dentry = inode_handle.dentry();
+        if !matches!(dentry.type_(), InodeType::File | InodeType::Dir) {
+            return_errno_with_message!(
+                Errno::ESPIPE,
+                "the file is not a regular file or a directory"
+            );
+        }
+        if flags.is_empty() {
+            return Ok(SyscallReturn::Return(0));
+        }
+        dentry.clone()
+    };
+
+    // TODO: Only write back the dirty pages within the range. For now, all the
+    // data of the file is written back, which is a superset of what is required.
+    dentry.sync_data()?;
+    Ok(SyscallReturn::Return(0))
+}
+
+/// Checks the range of `nbytes` bytes starting from `offset`.
+///
+/// A zero `nbytes` means the range extends to the end of the file.
+fn check_range(offset: isize, nbytes: isize) -> Result<()> {
+    if offset < 0 {
+        return_errno_with_message!(Errno::EINVAL, "the offset is negative");
+    }
+    if nbytes < 0 {
+        return_errno_with_message!(Errno::EINVAL, "the number of bytes is negative");
+    }
+    if offset.checked_add(nbytes).is_none() {
+        return_errno_with_message!(Errno::EINVAL, "the range overflows");
+    }
+    Ok(())
+}
+
+bitflags! {
+    struct SyncFileRangeFlags: u32 {
+        /// Waits for the writeback of the pages that are being written back.
+        const SYNC_FILE_RANGE_WAIT_BEFORE = 1 << 0;
+        /// Starts the writeback of the dirty pages.
+        const SYNC_FILE_RANGE_WRITE = 1 << 1;
+        /// Waits for the writeback of the pages after it is started.
+        const SYNC_FILE_RANGE_WAIT_AFTER = 1 << 2;
+    }
+}
@@ -5,12 +5,13 @@ use crate::{prelude::*, util::write_val_to_user};
 
 // We don't use the real name and version of our os here. Instead, we pick up fake values witch is the same as the ones of linux.
 // The values are used to fool glibc since glibc will check the version and os name.
+// The values are also reported by procfs, e.g., `/proc/version`, to keep them consistent.
 lazy_static! {
     /// used to fool glibc
-    static ref SYS_NAME: CString = CString::new("Linux").unwrap();
+    pub static ref SYS_NAME: CString = CString::new("Linux").unwrap();
     static ref NODE_NAME: CString = CString::new("WHITLEY").unwrap();
-    static ref RELEASE: CString = CString::new("5.13.0").unwrap();
-    static ref VERSION: CString = CString::new("5.13.0").unwrap();
+    pub static ref RELEASE: CString = CString::new("5.13.0").unwrap();
+    pub static ref VERSION: CString = CString::new("5.13.0").unwrap();
     static ref MACHINE: CString = CString::new("x86_64").unwrap();
     static ref DOMAIN_NAME: CString = CString::new("").unwrap();
     static ref UTS_NAME: UtsName = {
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use super::SyscallReturn;
+use crate::{
+    fs::{file_handle::FileLike, file_table::FileDesc, pipe::PipeWriter},
+    prelude::*,
+    util::copy_iovs_from_user,
+};
+
+/// The maximum number of IO vectors.
+const UIO_MAXIOV: usize = 1024;
+
+pub fn sys_vmsplice(
+    fd: FileDesc,
+    io_vec_ptr: Vaddr,
+    io_vec_count: usize,
+    flags: u32,
+) -> Result<SyscallReturn> {
+    let flags = SpliceFlags::from_bits(flags)
+        .ok_or_else(|| Error::with_message(Errno::EINVAL, "invalid flags"))?;
+    debug!(
+        "fd = {}, io_vec_ptr = 0x{:x}, io_vec_count = {}, flags = {:?}",
+        fd, io_vec_ptr, io_vec_count, flags
+    );
+
+    if io_vec_count > UIO_MAXIOV {
+        return_errno_with_message!(Errno::EINVAL, "too many IO vectors");
+    }
+
+    let file = {
+        let current = current!();
+        let file_table = current.file_table().lock();
+        file_table.get_file(fd)?.clone()
+    };
+    let Some(pipe_writer) = file.downcast_ref::<PipeWriter>() else {
+        return_errno_with_message!(Errno::EBADF, "the file is not the write end of a pipe");
+    };
+
+    // TODO: Gift the user pages to the pipe with `SPLICE_F_GIFT` instead of copying them.
+    let io_vecs = copy_iovs_from_user(io_vec_ptr, io_vec_count)?;
+    let mut total_len = 0;
+    for io_vec in io_vecs.iter() {
+        if io_vec.is_empty() {
+            continue;
+        }
+
+        let buffer = {
+            let mut buffer = vec![0u8; io_vec.len()];
+            io_vec.read_exact_from_user(&mut buffer)?;
+            buffer
+        };
+
+        // Like Linux, wait only until some bytes are written to the pipe.
+        let res = if flags.contains(SpliceFlags::SPLICE_F_NONBLOCK) || total_len > 0 {
+            pipe_writer.try_write(&buffer)
+        } else {
+            pipe_writer.write(&buffer)
+        };
+        let written_len = match res {
+            Ok(written_len) => written_len,
+            Err(_) if total_len > 0 => break,
+            Err(err) => return Err(err),
+        };
+
+        total_len += written_len;
+        if written_len < buffer.len() {
+            break;
+        }
+    }
+
+    Ok(SyscallReturn::Return(total_len as _))
+}
+
+bitflags! {
+    struct SpliceFlags: u32 {
+        const SPLICE_F_MOVE = 1;
+        const SPLICE_F_NONBLOCK = 2;
+        const SPLICE_F_MORE = 4;
+        const SPLICE_F_GIFT = 8;
+    }
+}
@@ -5,7 +5,8 @@
 use ostd::{cpu::*, mm::VmSpace};
 
 use crate::{
-    prelude::*, process::signal::signals::fault::FaultSignal,
+    prelude::*,
+    process::{signal::signals::fault::FaultSignal, Process, ResourceType},
     vm::page_fault_handler::PageFaultHandler,
 };
 
@@ -55,7 +56,18 @@ pub(crate) fn handle_page_fault(
             vm_space as *const VmSpace
         );
 
-        if let Err(e) = root_vmar.handle_page_fault(page_fault_addr, not_present, write) {
+        let result = root_vmar
+            .handle_page_fault(page_fault_addr, not_present, write)
+            .or_else(|err| {
+                // The stack grows down on demand. So a fault below the stack
+                // is handled again after the stack grows to cover the address.
+                if not_present && try_grow_stack(&current, page_fault_addr) {
+                    root_vmar.handle_page_fault(page_fault_addr, not_present, write)
+                } else {
+                    Err(err)
+                }
+            });
+        if let Err(e) = result {
             error!(
                 "page fault handler failed: addr: 0x{:x}, err: {:?}",
                 page_fault_addr, e
@@ -69,6 +81,21 @@ pub(crate) fn handle_page_fault(
     }
 }
 
+/// Grows the stack of the process down to cover `addr`.
+///
+/// Returns whether the stack has grown. The stack size cannot exceed `RLIMIT_STACK`.
+fn try_grow_stack(process: &Process, addr: Vaddr) -> bool {
+    let stack_size_limit = process
+        .resource_limits()
+        .lock()
+        .get_rlimit(ResourceType::RLIMIT_STACK)
+        .get_cur();
+    process
+        .vm()
+        .grow_stack(addr, stack_size_limit as usize)
+        .is_ok()
+}
+
 /// generate a fault signal for current process.
 fn generate_fault_signal(trap_info: &CpuExceptionInfo) {
     let current = current!();
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A manually advanced tick source for deterministic tests of clocks and timers.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use ostd::sync::SpinLock;
+
+use crate::time::Clock;
+
+/// A clock whose time only changes when it is set or advanced explicitly.
+///
+/// It can be used to create a [`TimerManager`] directly, or be installed as the
+/// source of the monotonic clocks with [`set_monotonic_source`].
+///
+/// [`TimerManager`]: crate::time::timer::TimerManager
+pub struct ManualClock {
+    time: SpinLock<Duration>,
+}
+
+impl ManualClock {
+    /// Creates a new `ManualClock` starting at `start`.
+    pub fn new(start: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            time: SpinLock::new(start),
+        })
+    }
+
+    /// Advances the time of this clock by `interval`.
+    pub fn advance(&self, interval: Duration) {
+        *self.time.lock_irq_disabled() += interval;
+    }
+
+    /// Sets the time of this clock.
+    pub fn set_time(&self, time: Duration) {
+        *self.time.lock_irq_disabled() = time;
+    }
+}
+
+impl Clock for ManualClock {
+    fn read_time(&self) -> Duration {
+        *self.time.lock_irq_disabled()
+    }
+}
+
+static MONOTONIC_SOURCE: SpinLock<Option<Arc<ManualClock>>> = SpinLock::new(None);
+
+/// Replaces the source of the monotonic clocks with `clock`.
+///
+/// The monotonic clocks, and the real-time clocks based on them, will read the time
+/// of `clock` until the source is restored by passing `None`.
+pub fn set_monotonic_source(clock: Option<Arc<ManualClock>>) {
+    *MONOTONIC_SOURCE.lock_irq_disabled() = clock;
+}
+
+/// Reads the time of the installed manual monotonic source, if any.
+pub(super) fn read_manual_monotonic_source() -> Option<Duration> {
+    MONOTONIC_SOURCE
+        .lock_irq_disabled()
+        .as_ref()
+        .map(|clock| clock.read_time())
+}
@@ -1,9 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
 pub use cpu_clock::*;
+#[cfg(ktest)]
+pub use manual::*;
 pub use system_wide::*;
 
 mod cpu_clock;
+#[cfg(ktest)]
+mod manual;
 mod system_wide;
 
 pub(super) fn init() {
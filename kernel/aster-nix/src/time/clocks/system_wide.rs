@@ -8,8 +8,10 @@ use ostd::{arch::timer::Jiffies, cpu_local, sync::SpinLock, CpuLocal};
 use paste::paste;
 use spin::Once;
 
-use crate::time::{
-    self, system_time::START_TIME_AS_DURATION, timer::TimerManager, Clock, SystemTime,
+use crate::{
+    prelude::*,
+    time::{self, system_time, timer::TimerManager, Clock, SystemTime},
+    vdso,
 };
 
 /// The Clock that reads the jiffies, and turn the counter into `Duration`.
@@ -32,6 +34,16 @@ impl RealTimeClock {
     pub fn timer_manager() -> &'static Arc<TimerManager> {
         CLOCK_REALTIME_MANAGER.get().unwrap()
     }
+
+    /// Sets the time of this clock.
+    ///
+    /// Only the offset from [`MonotonicClock`] is changed, so the monotonic
+    /// clocks keep advancing as usual.
+    pub fn set_time(&self, time: Duration) -> Result<()> {
+        system_time::set_realtime(time)?;
+        vdso::update_vdso_realtime();
+        Ok(())
+    }
 }
 
 /// `MonotonicClock` represents a clock that measures time in a way that is
@@ -54,10 +66,7 @@ impl MonotonicClock {
 
 /// `RealTimeCoarseClock` is a coarse-grained version of a real-time clock.
 ///
-/// This clock will maintain a record to `RealTimeClock`. This record
-/// will be updated during each system timer interruption. Reading this clock
-/// will directly reads the value of the record instead of calculating the time
-/// based on the clocksource. Hence it is faster but less accurate.
+/// This clock is based on [`MonotonicCoarseClock`].
 ///
 /// Usually it will not be used to create a timer.
 pub struct RealTimeCoarseClock {
@@ -65,13 +74,6 @@ pub struct RealTimeCoarseClock {
 }
 
 impl RealTimeCoarseClock {
-    /// A reference to the current value of this clock.
-    fn current_ref() -> &'static Once<SpinLock<Duration>> {
-        static CURRENT: Once<SpinLock<Duration>> = Once::new();
-
-        &CURRENT
-    }
-
     /// Get the singleton of this clock.
     pub fn get() -> &'static Arc<RealTimeCoarseClock> {
         CLOCK_REALTIME_COARSE_INSTANCE.get().unwrap()
@@ -80,7 +82,10 @@ impl RealTimeCoarseClock {
 
 /// `MonotonicCoarseClock` is a coarse-grained version of the monotonic clock.
 ///
-/// This clock is based on [`RealTimeCoarseClock`].
+/// This clock will maintain a record to `MonotonicClock`. This record
+/// will be updated during each system timer interruption. Reading this clock
+/// will directly reads the value of the record instead of calculating the time
+/// based on the clocksource. Hence it is faster but less accurate.
 ///
 /// Usually it will not be used to create a timer.
 pub struct MonotonicCoarseClock {
@@ -88,6 +93,13 @@ pub struct MonotonicCoarseClock {
 }
 
 impl MonotonicCoarseClock {
+    /// A reference to the current value of this clock.
+    fn current_ref() -> &'static Once<SpinLock<Duration>> {
+        static CURRENT: Once<SpinLock<Duration>> = Once::new();
+
+        &CURRENT
+    }
+
     /// Get the singleton of this clock.
     pub fn get() -> &'static Arc<MonotonicCoarseClock> {
         CLOCK_MONOTONIC_COARSE_INSTANCE.get().unwrap()
@@ -147,34 +159,49 @@ impl Clock for RealTimeClock {
 
 impl Clock for MonotonicClock {
     fn read_time(&self) -> Duration {
-        read_monotonic_time()
+        read_monotonic_source()
     }
 }
 
 impl Clock for RealTimeCoarseClock {
     fn read_time(&self) -> Duration {
-        *Self::current_ref().get().unwrap().lock_irq_disabled()
+        MonotonicCoarseClock::get().read_time() + system_time::realtime_offset()
     }
 }
 
 impl Clock for MonotonicCoarseClock {
     fn read_time(&self) -> Duration {
-        RealTimeCoarseClock::get().read_time() - *START_TIME_AS_DURATION.get().unwrap()
+        *Self::current_ref().get().unwrap().lock_irq_disabled()
     }
 }
 
 impl Clock for MonotonicRawClock {
     fn read_time(&self) -> Duration {
-        read_monotonic_time()
+        read_monotonic_source()
     }
 }
 
 impl Clock for BootTimeClock {
     fn read_time(&self) -> Duration {
-        read_monotonic_time()
+        read_monotonic_source()
     }
 }
 
+/// Reads the time source of the monotonic clocks.
+///
+/// In ktests, the source can be replaced with a [`ManualClock`] to advance the time
+/// deterministically.
+///
+/// [`ManualClock`]: super::ManualClock
+pub(in crate::time) fn read_monotonic_source() -> Duration {
+    #[cfg(ktest)]
+    if let Some(time) = super::manual::read_manual_monotonic_source() {
+        return time;
+    }
+
+    read_monotonic_time()
+}
+
 /// Define the system-wide clocks.
 macro_rules! define_system_clocks {
     ($($clock_id:ident => $clock_type:ident,)*) => {
@@ -264,14 +291,14 @@ fn init_jiffies_clock_manager() {
 }
 
 fn update_coarse_clock() {
-    let real_time = RealTimeClock::get().read_time();
-    let current = RealTimeCoarseClock::current_ref().get().unwrap();
-    *current.lock_irq_disabled() = real_time;
+    let monotonic_time = MonotonicClock::get().read_time();
+    let current = MonotonicCoarseClock::current_ref().get().unwrap();
+    *current.lock_irq_disabled() = monotonic_time;
 }
 
 fn init_coarse_clock() {
-    let real_time = RealTimeClock::get().read_time();
-    RealTimeCoarseClock::current_ref().call_once(|| SpinLock::new(real_time));
+    let monotonic_time = MonotonicClock::get().read_time();
+    MonotonicCoarseClock::current_ref().call_once(|| SpinLock::new(monotonic_time));
     time::softirq::register_callback(update_coarse_clock);
 }
 
@@ -293,8 +320,11 @@ pub fn init_for_ktest() {
         let clock = RealTimeClock { _private: () };
         TimerManager::new(Arc::new(clock))
     });
+    CLOCK_REALTIME_INSTANCE.call_once(|| Arc::new(RealTimeClock { _private: () }));
+    CLOCK_MONOTONIC_INSTANCE.call_once(|| Arc::new(MonotonicClock { _private: () }));
     CLOCK_REALTIME_COARSE_INSTANCE.call_once(|| Arc::new(RealTimeCoarseClock { _private: () }));
-    RealTimeCoarseClock::current_ref().call_once(|| SpinLock::new(Duration::from_secs(0)));
+    CLOCK_MONOTONIC_COARSE_INSTANCE.call_once(|| Arc::new(MonotonicCoarseClock { _private: () }));
+    MonotonicCoarseClock::current_ref().call_once(|| SpinLock::new(Duration::from_secs(0)));
     JIFFIES_TIMER_MANAGER.call_once(|| {
         let clock = JiffiesClock { _private: () };
         TimerManager::new(Arc::new(clock))
@@ -61,11 +61,15 @@ impl Timer {
     }
 
     /// Cancel the current timer's set timeout callback.
+    ///
+    /// The timeout callback is removed from the [`TimerManager`], so the registered
+    /// callback function will not be invoked until the timer is set again.
     pub fn cancel(&self) {
-        let timer_callback = self.timer_callback.lock_irq_disabled();
+        let mut timer_callback = self.timer_callback.lock_irq_disabled();
         if let Some(timer_callback) = timer_callback.upgrade() {
-            timer_callback.cancel();
+            self.timer_manager.remove(&timer_callback);
         }
+        *timer_callback = Weak::default();
     }
 
     /// Set the timer with a timeout.
@@ -82,16 +86,31 @@ impl Timer {
             Timeout::When(timeout) => timeout,
         };
 
-        let timer_weak = Arc::downgrade(self);
-        let new_timer_callback = Arc::new(TimerCallback::new(
-            expired_time,
-            Box::new(move || interval_timer_callback(&timer_weak)),
-        ));
-
         let mut timer_callback = self.timer_callback.lock_irq_disabled();
+        self.arm(&mut timer_callback, expired_time);
+    }
+
+    /// Rearm the timer to expire when the clock's time is equal to `expired_time`.
+    ///
+    /// The previous timeout of this timer, if any, is cancelled.
+    pub fn set_expiration(self: &Arc<Self>, expired_time: Duration) {
+        self.set_timeout(Timeout::When(expired_time));
+    }
+
+    fn arm(self: &Arc<Self>, timer_callback: &mut Weak<TimerCallback>, expired_time: Duration) {
         if let Some(timer_callback) = timer_callback.upgrade() {
-            timer_callback.cancel();
+            self.timer_manager.remove(&timer_callback);
         }
+
+        let timer_weak = Arc::downgrade(self);
+        let new_timer_callback = Arc::new_cyclic(|callback_weak: &Weak<TimerCallback>| {
+            let callback_weak = callback_weak.clone();
+            TimerCallback::new(
+                expired_time,
+                Box::new(move || interval_timer_callback(&timer_weak, &callback_weak)),
+            )
+        });
+
         *timer_callback = Arc::downgrade(&new_timer_callback);
         self.timer_manager.insert(new_timer_callback);
     }
@@ -128,16 +147,25 @@ impl Timer {
     }
 }
 
-fn interval_timer_callback(timer: &Weak<Timer>) {
+fn interval_timer_callback(timer: &Weak<Timer>, fired_callback: &Weak<TimerCallback>) {
     let Some(timer) = timer.upgrade() else {
         return;
     };
 
     (timer.registered_callback)();
-    let interval = timer.interval.lock_irq_disabled();
-    if *interval != Duration::ZERO {
-        timer.set_timeout(Timeout::After(*interval));
+    let interval = timer.interval();
+    if interval == Duration::ZERO {
+        return;
+    }
+
+    // The timer should not be set again if it has been cancelled or set with
+    // another timeout during the callback.
+    let mut timer_callback = timer.timer_callback.lock_irq_disabled();
+    if !Weak::ptr_eq(&timer_callback, fired_callback) {
+        return;
     }
+    let expired_time = timer.timer_manager.clock.read_time() + interval;
+    timer.arm(&mut timer_callback, expired_time);
 }
 
 /// `TimerManager` is used to create timers and manage their expiries. It holds a clock and can
@@ -165,6 +193,14 @@ impl TimerManager {
             .push(timer_callback);
     }
 
+    /// Cancel a timer callback and remove it from this manager.
+    fn remove(&self, timer_callback: &Arc<TimerCallback>) {
+        timer_callback.cancel();
+        self.timer_callbacks
+            .lock_irq_disabled()
+            .retain(|callback| !Arc::ptr_eq(callback, timer_callback));
+    }
+
     /// Check the managed timers, and if any have timed out,
     /// call the corresponding callback functions.
     pub fn process_expired_timers(&self) {
@@ -190,7 +226,10 @@ impl TimerManager {
         };
 
         for callback in callbacks {
-            (callback.callback)();
+            // The callback may be cancelled after it is taken out of the list.
+            if !callback.is_cancelled() {
+                (callback.callback)();
+            }
         }
     }
 
@@ -254,3 +293,110 @@ impl Ord for TimerCallback {
         self.expired_time.cmp(&other.expired_time).reverse()
     }
 }
+
+#[cfg(ktest)]
+mod test {
+    use core::sync::atomic::AtomicUsize;
+
+    use ostd::prelude::*;
+
+    use super::*;
+    use crate::time::clocks::ManualClock;
+
+    fn new_counting_timer(clock: &Arc<ManualClock>) -> (Arc<Timer>, Arc<AtomicUsize>) {
+        let timer_manager = TimerManager::new(clock.clone());
+        let count = Arc::new(AtomicUsize::new(0));
+        let timer = {
+            let count = count.clone();
+            timer_manager.create_timer(move || {
+                count.fetch_add(1, Ordering::Relaxed);
+            })
+        };
+        (timer, count)
+    }
+
+    #[ktest]
+    fn timer_fires_at_exact_tick() {
+        let clock = ManualClock::new(Duration::ZERO);
+        let (timer, count) = new_counting_timer(&clock);
+
+        timer.set_timeout(Timeout::After(Duration::from_millis(10)));
+        clock.advance(Duration::from_millis(9));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert_eq!(timer.remain(), Duration::from_millis(1));
+
+        clock.advance(Duration::from_millis(1));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        clock.advance(Duration::from_secs(1));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[ktest]
+    fn interval_timer_rearms() {
+        let clock = ManualClock::new(Duration::from_secs(1));
+        let (timer, count) = new_counting_timer(&clock);
+
+        timer.set_interval(Duration::from_millis(5));
+        timer.set_timeout(Timeout::When(Duration::from_millis(1005)));
+        for expected in 1..=3 {
+            clock.advance(Duration::from_millis(5));
+            timer.timer_manager().process_expired_timers();
+            assert_eq!(count.load(Ordering::Relaxed), expected);
+        }
+    }
+
+    #[ktest]
+    fn cancelled_timer_never_fires() {
+        let clock = ManualClock::new(Duration::ZERO);
+        let (timer, count) = new_counting_timer(&clock);
+
+        timer.set_interval(Duration::from_millis(10));
+        timer.set_timeout(Timeout::After(Duration::from_millis(10)));
+        clock.advance(Duration::from_millis(5));
+        timer.cancel();
+        assert_eq!(timer.remain(), Duration::ZERO);
+
+        clock.advance(Duration::from_secs(1));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert!(timer.timer_manager().timer_callbacks.lock().is_empty());
+    }
+
+    #[ktest]
+    fn rearmed_timer_fires_once() {
+        let clock = ManualClock::new(Duration::ZERO);
+        let (timer, count) = new_counting_timer(&clock);
+
+        timer.set_expiration(Duration::from_millis(10));
+        timer.set_expiration(Duration::from_millis(20));
+        clock.set_time(Duration::from_millis(15));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+
+        clock.set_time(Duration::from_millis(20));
+        timer.timer_manager().process_expired_timers();
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[ktest]
+    fn monotonic_clock_reads_manual_source() {
+        use crate::time::clocks::{set_monotonic_source, MonotonicClock};
+
+        crate::time::clocks::init_for_ktest();
+        let clock = ManualClock::new(Duration::from_secs(100));
+        set_monotonic_source(Some(clock.clone()));
+
+        assert_eq!(MonotonicClock::get().read_time(), Duration::from_secs(100));
+        clock.advance(Duration::from_nanos(1));
+        assert_eq!(
+            MonotonicClock::get().read_time(),
+            Duration::from_secs(100) + Duration::from_nanos(1)
+        );
+
+        set_monotonic_source(None);
+    }
+}
@@ -5,7 +5,7 @@
 pub use core::{timer, Clock};
 
 use ::core::time::Duration;
-pub use system_time::{SystemTime, START_TIME};
+pub use system_time::{realtime_offset, SystemTime, START_TIME};
 pub use timer::{Timer, TimerManager};
 
 use crate::prelude::*;
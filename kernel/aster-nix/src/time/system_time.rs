@@ -2,10 +2,11 @@
 
 use core::time::Duration;
 
-use aster_time::{read_monotonic_time, read_start_time};
+use aster_time::read_start_time;
 use spin::Once;
 use time::{Date, Month, PrimitiveDateTime, Time};
 
+use super::clocks::read_monotonic_source;
 use crate::prelude::*;
 
 /// This struct corresponds to `SystemTime` in Rust std.
@@ -13,15 +14,41 @@ use crate::prelude::*;
 pub struct SystemTime(PrimitiveDateTime);
 
 pub static START_TIME: Once<SystemTime> = Once::new();
-pub(super) static START_TIME_AS_DURATION: Once<Duration> = Once::new();
+
+/// The offset of the real time from the monotonic time.
+///
+/// It is the start time when the system boots, and will be changed
+/// when the real time is set.
+static REALTIME_OFFSET: SpinLock<Duration> = SpinLock::new(Duration::ZERO);
 
 pub(super) fn init() {
     let start_time = convert_system_time(read_start_time()).unwrap();
-    START_TIME_AS_DURATION
-        .call_once(|| start_time.duration_since(&SystemTime::UNIX_EPOCH).unwrap());
+    *REALTIME_OFFSET.lock_irq_disabled() =
+        start_time.duration_since(&SystemTime::UNIX_EPOCH).unwrap();
     START_TIME.call_once(|| start_time);
 }
 
+/// Returns the offset of the real time from the monotonic time.
+pub fn realtime_offset() -> Duration {
+    *REALTIME_OFFSET.lock_irq_disabled()
+}
+
+/// Sets the real time, which is the duration since the Unix epoch.
+///
+/// The monotonic time is not affected. Hence the real time cannot be set
+/// to a time earlier than the time elapsed since the system booted.
+pub(super) fn set_realtime(realtime: Duration) -> Result<()> {
+    let mut offset = REALTIME_OFFSET.lock_irq_disabled();
+    let Some(new_offset) = realtime.checked_sub(read_monotonic_source()) else {
+        return_errno_with_message!(
+            Errno::EINVAL,
+            "the real time cannot be earlier than the boot time"
+        );
+    };
+    *offset = new_offset;
+    Ok(())
+}
+
 impl SystemTime {
     /// The unix epoch, which represents 1970-01-01 00:00:00
     pub const UNIX_EPOCH: SystemTime = SystemTime::unix_epoch();
@@ -41,10 +68,8 @@ impl SystemTime {
     /// Returns the current system time
     pub fn now() -> Self {
         // The get real time result should always be valid
-        START_TIME
-            .get()
-            .unwrap()
-            .checked_add(read_monotonic_time())
+        SystemTime::UNIX_EPOCH
+            .checked_add(realtime_offset() + read_monotonic_source())
             .unwrap()
     }
 
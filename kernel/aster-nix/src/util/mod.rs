@@ -34,7 +34,8 @@ pub fn read_bytes_from_user(src: Vaddr, dest: &mut VmWriter<'_>) -> Result<()> {
     ))?;
     let copy_len = dest.avail();
 
-    let mut user_reader = user_space.vm_space().reader(src, copy_len)?;
+    let vm_space = user_space.vm_space();
+    let mut user_reader = vm_space.reader(src, copy_len)?;
     user_reader.read_fallible(dest).map_err(|err| err.0)?;
     Ok(())
 }
@@ -51,9 +52,8 @@ pub fn read_val_from_user<T: Pod>(src: Vaddr) -> Result<T> {
         "the user space is missing",
     ))?;
 
-    let mut user_reader = user_space
-        .vm_space()
-        .reader(src, core::mem::size_of::<T>())?;
+    let vm_space = user_space.vm_space();
+    let mut user_reader = vm_space.reader(src, core::mem::size_of::<T>())?;
     Ok(user_reader.read_val()?)
 }
 
@@ -76,7 +76,8 @@ pub fn write_bytes_to_user(dest: Vaddr, src: &mut VmReader<'_, KernelSpace>) ->
     ))?;
     let copy_len = src.remain();
 
-    let mut user_writer = user_space.vm_space().writer(dest, copy_len)?;
+    let vm_space = user_space.vm_space();
+    let mut user_writer = vm_space.writer(dest, copy_len)?;
     user_writer.write_fallible(src).map_err(|err| err.0)?;
     Ok(())
 }
@@ -92,9 +93,8 @@ pub fn write_val_to_user<T: Pod>(dest: Vaddr, val: &T) -> Result<()> {
         "the user space is missing",
     ))?;
 
-    let mut user_writer = user_space
-        .vm_space()
-        .writer(dest, core::mem::size_of::<T>())?;
+    let vm_space = user_space.vm_space();
+    let mut user_writer = vm_space.writer(dest, core::mem::size_of::<T>())?;
     Ok(user_writer.write_val(val)?)
 }
 
@@ -107,14 +107,42 @@ pub fn write_val_to_user<T: Pod>(dest: Vaddr, val: &T) -> Result<()> {
 /// The original Linux implementation can be found at:
 /// <https://elixir.bootlin.com/linux/v6.0.9/source/lib/strncpy_from_user.c#L28>
 pub fn read_cstring_from_user(addr: Vaddr, max_len: usize) -> Result<CString> {
+    try_read_cstring_from_user(addr, max_len)?
+        .ok_or_else(|| Error::with_message(Errno::EFAULT, "Fails to read CString from user"))
+}
+
+/// Reads a path from the user space of the current process.
+///
+/// Unlike [`read_cstring_from_user`], a path longer than `max_len` results in `ENAMETOOLONG`.
+pub fn read_path_from_user(addr: Vaddr, max_len: usize) -> Result<CString> {
+    try_read_cstring_from_user(addr, max_len)?
+        .ok_or_else(|| Error::with_message(Errno::ENAMETOOLONG, "the path is too long"))
+}
+
+/// Reads a C string from the user space of the current process.
+///
+/// If no null terminator is found within `max_len` bytes, `None` is returned, so that the
+/// caller can decide the error.
+pub fn try_read_cstring_from_user(addr: Vaddr, max_len: usize) -> Result<Option<CString>> {
     let current = current!();
     let vmar = current.root_vmar();
-    read_cstring_from_vmar(vmar, addr, max_len)
+    try_read_cstring_from_vmar(&vmar, addr, max_len)
 }
 
 /// Read CString from `vmar`. If possible, use `read_cstring_from_user` instead.
 pub fn read_cstring_from_vmar(vmar: &Vmar<Full>, addr: Vaddr, max_len: usize) -> Result<CString> {
-    let mut buffer: Vec<u8> = Vec::with_capacity(max_len);
+    try_read_cstring_from_vmar(vmar, addr, max_len)?
+        .ok_or_else(|| Error::with_message(Errno::EFAULT, "Fails to read CString from user"))
+}
+
+fn try_read_cstring_from_vmar(
+    vmar: &Vmar<Full>,
+    addr: Vaddr,
+    max_len: usize,
+) -> Result<Option<CString>> {
+    // `max_len` can be much larger than the string (e.g., for the arguments of execve),
+    // so the buffer starts with at most one page and grows as the string is read.
+    let mut buffer: Vec<u8> = Vec::with_capacity(max_len.min(PAGE_SIZE));
     let mut cur_addr = addr;
 
     macro_rules! read_one_byte_at_a_time_while {
@@ -123,8 +151,10 @@ pub fn read_cstring_from_vmar(vmar: &Vmar<Full>, addr: Vaddr, max_len: usize) ->
                 let byte = vmar.read_val::<u8>(cur_addr)?;
                 buffer.push(byte);
                 if byte == 0 {
-                    return Ok(CString::from_vec_with_nul(buffer)
-                        .expect("We provided 0 but no 0 is found"));
+                    return Ok(Some(
+                        CString::from_vec_with_nul(buffer)
+                            .expect("We provided 0 but no 0 is found"),
+                    ));
                 }
                 cur_addr += mem::size_of::<u8>();
             }
@@ -146,8 +176,10 @@ pub fn read_cstring_from_vmar(vmar: &Vmar<Full>, addr: Vaddr, max_len: usize) ->
             for byte in word.to_ne_bytes() {
                 buffer.push(byte);
                 if byte == 0 {
-                    return Ok(CString::from_vec_with_nul(buffer)
-                        .expect("We provided 0 but no 0 is found"));
+                    return Ok(Some(
+                        CString::from_vec_with_nul(buffer)
+                            .expect("We provided 0 but no 0 is found"),
+                    ));
                 }
             }
             unreachable!("The branch should never be reached unless `has_zero` has bugs.")
@@ -162,7 +194,7 @@ pub fn read_cstring_from_vmar(vmar: &Vmar<Full>, addr: Vaddr, max_len: usize) ->
     read_one_byte_at_a_time_while!(buffer.len() < max_len);
 
     // Maximum length exceeded before finding the null terminator
-    return_errno_with_message!(Errno::EFAULT, "Fails to read CString from user");
+    Ok(None)
 }
 
 /// Determine whether the value contains a zero byte.
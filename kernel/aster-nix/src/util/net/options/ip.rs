@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use aster_rights::Full;
+
+use super::RawSocketOption;
+use crate::{
+    impl_raw_socket_option, net::socket::ip::options::HdrIncl, prelude::*,
+    util::net::options::SocketOption, vm::vmar::Vmar,
+};
+
+/// Sock options for IP level.
+///
+/// The raw definition is from https://elixir.bootlin.com/linux/v6.0.9/source/include/uapi/linux/in.h#L94
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, TryFromInt)]
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CIpOptionName {
+    TOS = 1,
+    TTL = 2,
+    HDRINCL = 3,
+    OPTIONS = 4,
+    RECVOPTS = 6,
+    RETOPTS = 7,
+    PKTINFO = 8,
+    MTU_DISCOVER = 10,
+    RECVERR = 11,
+    RECVTTL = 12,
+    RECVTOS = 13,
+    MTU = 14,
+}
+
+pub fn new_ip_option(name: i32) -> Result<Box<dyn RawSocketOption>> {
+    let name = CIpOptionName::try_from(name)?;
+    match name {
+        CIpOptionName::HDRINCL => Ok(Box::new(HdrIncl::new())),
+        _ => return_errno_with_message!(Errno::ENOPROTOOPT, "the IP option is not supported"),
+    }
+}
+
+impl_raw_socket_option!(HdrIncl);
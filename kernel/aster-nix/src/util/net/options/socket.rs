@@ -6,7 +6,8 @@ use super::RawSocketOption;
 use crate::{
     impl_raw_sock_option_get_only, impl_raw_socket_option,
     net::socket::options::{
-        Error, KeepAlive, Linger, RecvBuf, ReuseAddr, ReusePort, SendBuf, SocketOption,
+        BindToDevice, Domain, Error, KeepAlive, Linger, Priority, Protocol, RecvBuf, RecvTimeout,
+        ReuseAddr, ReusePort, SendBuf, SendTimeout, SocketOption,
     },
     prelude::*,
     vm::vmar::Vmar,
@@ -37,6 +38,11 @@ enum CSocketOptionName {
     LINGER = 13,
     BSDCOMPAT = 14,
     REUSEPORT = 15,
+    RCVTIMEO = 20,
+    SNDTIMEO = 21,
+    BINDTODEVICE = 25,
+    PROTOCOL = 38,
+    DOMAIN = 39,
     RCVTIMEO_NEW = 66,
     SNDTIMEO_NEW = 67,
 }
@@ -51,6 +57,16 @@ pub fn new_socket_option(name: i32) -> Result<Box<dyn RawSocketOption>> {
         CSocketOptionName::REUSEPORT => Ok(Box::new(ReusePort::new())),
         CSocketOptionName::LINGER => Ok(Box::new(Linger::new())),
         CSocketOptionName::KEEPALIVE => Ok(Box::new(KeepAlive::new())),
+        CSocketOptionName::RCVTIMEO | CSocketOptionName::RCVTIMEO_NEW => {
+            Ok(Box::new(RecvTimeout::new()))
+        }
+        CSocketOptionName::SNDTIMEO | CSocketOptionName::SNDTIMEO_NEW => {
+            Ok(Box::new(SendTimeout::new()))
+        }
+        CSocketOptionName::PROTOCOL => Ok(Box::new(Protocol::new())),
+        CSocketOptionName::DOMAIN => Ok(Box::new(Domain::new())),
+        CSocketOptionName::BINDTODEVICE => Ok(Box::new(BindToDevice::new())),
+        CSocketOptionName::PRIORITY => Ok(Box::new(Priority::new())),
         _ => todo!(),
     }
 }
@@ -62,3 +78,9 @@ impl_raw_sock_option_get_only!(Error);
 impl_raw_socket_option!(ReusePort);
 impl_raw_socket_option!(Linger);
 impl_raw_socket_option!(KeepAlive);
+impl_raw_socket_option!(SendTimeout);
+impl_raw_socket_option!(RecvTimeout);
+impl_raw_sock_option_get_only!(Protocol);
+impl_raw_sock_option_get_only!(Domain);
+impl_raw_socket_option!(BindToDevice);
+impl_raw_socket_option!(Priority);
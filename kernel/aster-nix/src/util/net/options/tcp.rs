@@ -5,7 +5,10 @@ use aster_rights::Full;
 use super::RawSocketOption;
 use crate::{
     impl_raw_socket_option,
-    net::socket::ip::stream::options::{Congestion, MaxSegment, NoDelay, WindowClamp},
+    net::socket::ip::stream::options::{
+        Congestion, Cork, DeferAccept, KeepCount, KeepIdle, KeepInterval, MaxSegment, NoDelay,
+        WindowClamp,
+    },
     prelude::*,
     util::net::options::SocketOption,
     vm::vmar::Vmar,
@@ -23,7 +26,9 @@ pub enum CTcpOptionName {
     MAXSEG = 2,        /* Limit MSS */
     CORK = 3,          /* Never send partially complete segments */
     KEEPIDLE = 4,      /* Start keeplives after this period */
-    KEEPALIVE = 5,     /* Interval between keepalives */
+    KEEPINTVL = 5,     /* Interval between keepalives */
+    KEEPCNT = 6,       /* Number of keepalives before death */
+    DEFER_ACCEPT = 9,  /* Wake up listener only when data arrive */
     WINDOW_CLAMP = 10, /* Bound advertised window */
     CONGESTION = 13,   /* Congestion control algorithm */
 }
@@ -32,14 +37,24 @@ pub fn new_tcp_option(name: i32) -> Result<Box<dyn RawSocketOption>> {
     let name = CTcpOptionName::try_from(name)?;
     match name {
         CTcpOptionName::NODELAY => Ok(Box::new(NoDelay::new())),
+        CTcpOptionName::CORK => Ok(Box::new(Cork::new())),
         CTcpOptionName::CONGESTION => Ok(Box::new(Congestion::new())),
         CTcpOptionName::MAXSEG => Ok(Box::new(MaxSegment::new())),
         CTcpOptionName::WINDOW_CLAMP => Ok(Box::new(WindowClamp::new())),
+        CTcpOptionName::DEFER_ACCEPT => Ok(Box::new(DeferAccept::new())),
+        CTcpOptionName::KEEPIDLE => Ok(Box::new(KeepIdle::new())),
+        CTcpOptionName::KEEPINTVL => Ok(Box::new(KeepInterval::new())),
+        CTcpOptionName::KEEPCNT => Ok(Box::new(KeepCount::new())),
         _ => todo!(),
     }
 }
 
 impl_raw_socket_option!(NoDelay);
+impl_raw_socket_option!(Cork);
 impl_raw_socket_option!(Congestion);
 impl_raw_socket_option!(MaxSegment);
 impl_raw_socket_option!(WindowClamp);
+impl_raw_socket_option!(DeferAccept);
+impl_raw_socket_option!(KeepIdle);
+impl_raw_socket_option!(KeepInterval);
+impl_raw_socket_option!(KeepCount);
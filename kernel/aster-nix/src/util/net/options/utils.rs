@@ -6,8 +6,10 @@ use aster_rights::Full;
 use ostd::mm::VmIo;
 
 use crate::{
-    net::socket::{ip::stream::CongestionControl, LingerOption},
+    net::socket::{ip::stream::CongestionControl, IfName, LingerOption},
     prelude::*,
+    time::timeval_t,
+    util::net::{CSocketAddrFamily, Protocol},
     vm::vmar::Vmar,
 };
 
@@ -115,6 +117,32 @@ impl WriteToUser for Option<Error> {
     }
 }
 
+impl WriteToUser for CSocketAddrFamily {
+    fn write_to_user(&self, vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<usize> {
+        let write_len = core::mem::size_of::<i32>();
+
+        if (max_len as usize) < write_len {
+            return_errno_with_message!(Errno::EINVAL, "max_len is too short");
+        }
+
+        vmar.write_val(addr, &(*self as i32))?;
+        Ok(write_len)
+    }
+}
+
+impl WriteToUser for Protocol {
+    fn write_to_user(&self, vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<usize> {
+        let write_len = core::mem::size_of::<i32>();
+
+        if (max_len as usize) < write_len {
+            return_errno_with_message!(Errno::EINVAL, "max_len is too short");
+        }
+
+        vmar.write_val(addr, &(*self as i32))?;
+        Ok(write_len)
+    }
+}
+
 impl ReadFromUser for LingerOption {
     fn read_from_user(vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<Self> {
         if (max_len as usize) < core::mem::size_of::<CLinger>() {
@@ -141,11 +169,55 @@ impl WriteToUser for LingerOption {
     }
 }
 
+impl ReadFromUser for Duration {
+    fn read_from_user(vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<Self> {
+        if (max_len as usize) < core::mem::size_of::<timeval_t>() {
+            return_errno_with_message!(Errno::EINVAL, "max_len is too short");
+        }
+
+        let timeval = vmar.read_val::<timeval_t>(addr)?;
+
+        if timeval.usec < 0 || timeval.usec >= 1_000_000 {
+            return_errno_with_message!(Errno::EDOM, "the microseconds are out of range");
+        }
+        // FIXME: Linux makes the socket operations nonblocking if the timeout is negative.
+        // Here we simply treat it as no timeout.
+        if timeval.sec < 0 {
+            return Ok(Duration::ZERO);
+        }
+
+        Ok(Duration::from(timeval))
+    }
+}
+
+impl WriteToUser for Duration {
+    fn write_to_user(&self, vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<usize> {
+        let write_len = core::mem::size_of::<timeval_t>();
+
+        if (max_len as usize) < write_len {
+            return_errno_with_message!(Errno::EINVAL, "max_len is too short");
+        }
+
+        let timeval = timeval_t::from(*self);
+        vmar.write_val(addr, &timeval)?;
+        Ok(write_len)
+    }
+}
+
 impl ReadFromUser for CongestionControl {
     fn read_from_user(vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<Self> {
-        let mut bytes = vec![0; max_len as usize];
+        // The maximum length of the name of a congestion control algorithm, including the null
+        // terminator.
+        const TCP_CA_NAME_MAX: u32 = 16;
+
+        let mut bytes = vec![0; max_len.min(TCP_CA_NAME_MAX - 1) as usize];
         vmar.read_bytes(addr, &mut bytes)?;
-        let name = String::from_utf8(bytes).unwrap();
+        // Like Linux, the name ends at the null terminator, if any.
+        if let Some(len) = bytes.iter().position(|&byte| byte == 0) {
+            bytes.truncate(len);
+        }
+        let name = String::from_utf8(bytes)
+            .map_err(|_| Error::with_message(Errno::EINVAL, "unsupported congestion name"))?;
         CongestionControl::new(&name)
     }
 }
@@ -165,6 +237,44 @@ impl WriteToUser for CongestionControl {
     }
 }
 
+/// Reads a device name.
+///
+/// Like Linux, the input longer than `IFNAMSIZ - 1` bytes is truncated and the input ends at the
+/// first null byte.
+impl ReadFromUser for IfName {
+    fn read_from_user(vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<Self> {
+        let mut bytes = vec![0; (max_len as usize).min(IfName::IFNAMSIZ - 1)];
+        vmar.read_bytes(addr, &mut bytes)?;
+        if let Some(len) = bytes.iter().position(|byte| *byte == 0) {
+            bytes.truncate(len);
+        }
+        let name = core::str::from_utf8(&bytes)
+            .map_err(|_| Error::with_message(Errno::EINVAL, "the device name is invalid"))?;
+        Ok(IfName::new(name))
+    }
+}
+
+/// Writes a device name with the terminating null byte.
+///
+/// Nothing is written if the name is empty.
+impl WriteToUser for IfName {
+    fn write_to_user(&self, vmar: &Vmar<Full>, addr: Vaddr, max_len: u32) -> Result<usize> {
+        let name = self.as_str();
+        if name.is_empty() {
+            return Ok(0);
+        }
+
+        let write_len = name.len() + 1;
+        if write_len > max_len as usize {
+            return_errno_with_message!(Errno::EINVAL, "max_len is too short");
+        }
+
+        vmar.write_bytes(addr, name.as_bytes())?;
+        vmar.write_val(addr + name.len(), &0u8)?;
+        Ok(write_len)
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod)]
 struct CLinger {
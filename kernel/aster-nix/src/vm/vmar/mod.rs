@@ -21,7 +21,9 @@ use self::{
     vm_mapping::VmMapping,
 };
 use super::page_fault_handler::PageFaultHandler;
-use crate::{prelude::*, thread::exception::handle_page_fault, vm::perms::VmPerms};
+use crate::{
+    fs::path::Dentry, prelude::*, thread::exception::handle_page_fault, vm::perms::VmPerms,
+};
 
 /// Virtual Memory Address Regions (VMARs) are a type of capability that manages
 /// user address spaces.
@@ -110,6 +112,9 @@ struct VmarInner {
     vm_mappings: BTreeMap<Vaddr, Arc<VmMapping>>,
     /// Free regions that can be used for creating child vmar or mapping vmos
     free_regions: BTreeMap<Vaddr, FreeRegion>,
+    /// The address from which free regions are searched first
+    /// for the child vmars and vmos without a specified offset
+    mmap_base: Vaddr,
 }
 
 impl VmarInner {
@@ -119,6 +124,7 @@ impl VmarInner {
             child_vmar_s: BTreeMap::new(),
             vm_mappings: BTreeMap::new(),
             free_regions: BTreeMap::new(),
+            mmap_base: 0,
         }
     }
 }
@@ -164,6 +170,7 @@ impl Vmar_ {
             child_vmar_s: BTreeMap::new(),
             vm_mappings: BTreeMap::new(),
             free_regions,
+            mmap_base: 0,
         };
         let vm_space = VmSpace::new();
         vm_space.register_page_fault_handler(handle_page_fault);
@@ -290,6 +297,28 @@ impl Vmar_ {
         Ok(())
     }
 
+    /// Adds the pages mapped in this VMAR and its children to the statistics.
+    fn collect_stat(&self, stat: &mut VmarStat) {
+        let inner = self.inner.lock();
+        for child_vmar in inner.child_vmar_s.values() {
+            child_vmar.collect_stat(stat);
+        }
+        for vm_mapping in inner.vm_mappings.values() {
+            vm_mapping.collect_stat(stat);
+        }
+    }
+
+    /// Adds the information of the mappings in this VMAR and its children to `infos`.
+    fn collect_mappings(&self, infos: &mut Vec<VmMappingInfo>) {
+        let inner = self.inner.lock();
+        for child_vmar in inner.child_vmar_s.values() {
+            child_vmar.collect_mappings(infos);
+        }
+        for vm_mapping in inner.vm_mappings.values() {
+            infos.push(vm_mapping.info());
+        }
+    }
+
     pub fn destroy_all(&self) -> Result<()> {
         let mut inner = self.inner.lock();
         inner.is_destroyed = true;
@@ -526,6 +555,7 @@ impl Vmar_ {
             child_vmar_s: BTreeMap::new(),
             vm_mappings: BTreeMap::new(),
             free_regions: child_regions,
+            mmap_base: 0,
         };
         let child_vmar_ = Vmar_::new(
             child_vmar_inner,
@@ -564,21 +594,55 @@ impl Vmar_ {
             }
         } else {
             // Else, we find a free region that can satisfy the length and align requirement.
+            // The free space above `mmap_base` is preferred. The space below it is used
+            // only if there is no room above it.
             // Here, we use a simple brute-force algorithm to find the first free range that can satisfy.
             // FIXME: A randomized algorithm may be more efficient.
-            for (region_base, free_region) in &inner.free_regions {
-                let region_start = free_region.start();
-                let region_end = free_region.end();
-                let child_vmar_real_start = region_start.align_up(align);
-                let child_vmar_real_end = child_vmar_real_start + child_size;
-                if region_start <= child_vmar_real_start && child_vmar_real_end <= region_end {
-                    return Ok((*region_base, child_vmar_real_start));
+            for lowest_start in [inner.mmap_base, 0] {
+                for (region_base, free_region) in &inner.free_regions {
+                    let region_start = free_region.start().max(lowest_start);
+                    let region_end = free_region.end();
+                    let child_vmar_real_start = region_start.align_up(align);
+                    let child_vmar_real_end = child_vmar_real_start + child_size;
+                    if region_start <= child_vmar_real_start && child_vmar_real_end <= region_end {
+                        return Ok((*region_base, child_vmar_real_start));
+                    }
                 }
             }
         }
         return_errno_with_message!(Errno::EACCES, "Cannot find free region for child")
     }
 
+    /// Returns whether the range is free, i.e., not occupied by any mapping or child VMAR.
+    fn is_free_range(&self, range: &Range<Vaddr>) -> bool {
+        self.find_free_region_for_child(Some(range.start), range.len(), PAGE_SIZE)
+            .is_ok()
+    }
+
+    /// Extends the mapping that starts at `map_addr` down to `new_start`.
+    fn grow_mapping_down(&self, map_addr: Vaddr, new_start: Vaddr) -> Result<()> {
+        let grown_range = new_start..map_addr;
+        let (free_region_base, _) = self
+            .find_free_region_for_child(Some(new_start), grown_range.len(), PAGE_SIZE)
+            .map_err(|_| {
+                Error::with_message(Errno::ENOMEM, "the pages below the mapping are not free")
+            })?;
+
+        let mut inner = self.inner.lock();
+        let Some(vm_mapping) = inner.vm_mappings.get(&map_addr).cloned() else {
+            return_errno_with_message!(Errno::EFAULT, "no mapping starts at the address");
+        };
+        vm_mapping.grow_down(new_start)?;
+        inner.vm_mappings.remove(&map_addr);
+        inner.vm_mappings.insert(new_start, vm_mapping);
+
+        let free_region = inner.free_regions.remove(&free_region_base).unwrap();
+        for region in free_region.allocate_range(grown_range) {
+            inner.free_regions.insert(region.start(), region);
+        }
+        Ok(())
+    }
+
     fn check_vmo_overwrite(&self, vmo_range: Range<usize>, can_overwrite: bool) -> Result<()> {
         let inner = self.inner.lock();
         if inner
@@ -720,6 +784,7 @@ impl Vmar_ {
         };
 
         let inner = self.inner.lock();
+        new_vmar_.inner.lock().mmap_base = inner.mmap_base;
         // Clone free regions.
         for (free_region_base, free_region) in &inner.free_regions {
             new_vmar_
@@ -763,6 +828,28 @@ impl Vmar_ {
 
         return_errno_with_message!(Errno::EFAULT, "No mapped vmo at this offset");
     }
+
+    /// Returns the mappings that intersect with the range, sorted by address.
+    ///
+    /// If any page in the range is not mapped, `ENOMEM` is returned.
+    fn get_vm_mappings_covering(&self, range: &Range<Vaddr>) -> Result<Vec<Arc<VmMapping>>> {
+        let inner = self.inner.lock();
+        let vm_mappings: Vec<_> = inner.vm_mappings.find(range).into_iter().cloned().collect();
+
+        let mut mapped_end = range.start;
+        for vm_mapping in vm_mappings.iter() {
+            let vm_mapping_range = vm_mapping.range();
+            if vm_mapping_range.start > mapped_end {
+                break;
+            }
+            mapped_end = vm_mapping_range.end;
+        }
+        if mapped_end < range.end {
+            return_errno_with_message!(Errno::ENOMEM, "the range is not fully mapped");
+        }
+
+        Ok(vm_mappings)
+    }
 }
 
 impl<R> Vmar<R> {
@@ -785,6 +872,213 @@ impl<R> Vmar<R> {
         self.check_rights(rights)?;
         self.0.get_vm_mapping(offset)
     }
+
+    /// Sets the address from which free regions are searched first
+    /// for the mappings without a specified address.
+    ///
+    /// The free space below `mmap_base` is still used if there is no room above it.
+    pub fn set_mmap_base(&self, mmap_base: Vaddr) {
+        self.0.inner.lock().mmap_base = mmap_base;
+    }
+
+    /// Returns whether the range is free, i.e., not occupied by any mapping or child VMAR.
+    pub fn is_free_range(&self, range: &Range<Vaddr>) -> bool {
+        self.0.is_free_range(range)
+    }
+
+    /// Extends the mapping that starts at `map_addr` down to `new_start`.
+    ///
+    /// The extended part maps the pages of the VMO right below the mapped ones, so the
+    /// mapping must map its VMO at an offset of at least `map_addr - new_start`. The
+    /// pages to map must be free, or `ENOMEM` is returned.
+    pub fn grow_mapping_down(&self, map_addr: Vaddr, new_start: Vaddr) -> Result<()> {
+        debug_assert!(new_start % PAGE_SIZE == 0);
+        debug_assert!(new_start < map_addr);
+        self.0.grow_mapping_down(map_addr, new_start)
+    }
+
+    /// Resizes the mapped range in place to `new_size` bytes.
+    ///
+    /// The range must be page-aligned and within a single mapping.
+    /// Shrinking unmaps the tail of the range. Growing maps the free pages
+    /// following the range with the same permissions and backing as the
+    /// mapping, or fails with `ENOMEM` if the pages are not free.
+    pub fn resize_mapping(&self, range: Range<Vaddr>, new_size: usize) -> Result<()> {
+        let vm_mapping = self.get_remapped_mapping(&range)?;
+        if new_size == range.len() {
+            return Ok(());
+        }
+        if new_size < range.len() {
+            return self.0.destroy(range.start + new_size..range.end);
+        }
+
+        let grown_range = range.end..range.start + new_size;
+        if !self.0.is_free_range(&grown_range) {
+            return_errno_with_message!(Errno::ENOMEM, "the pages after the range are not free");
+        }
+        let vmar = Vmar(self.0.clone(), self.rights());
+        vm_mapping
+            .grow_options(vmar, range.end, grown_range.len())?
+            .offset(grown_range.start)
+            .build()?;
+        Ok(())
+    }
+
+    /// Moves the mapped range to a new range of `new_size` bytes.
+    ///
+    /// The range must be page-aligned and within a single mapping.
+    /// If `new_addr` is given, the new range starts there and the mappings
+    /// overlapping the new range are unmapped. Otherwise, a free range is chosen.
+    ///
+    /// The pages keep their contents, permissions and backing VMO after being moved.
+    /// The old range is unmapped only after the new range is mapped, so it is left
+    /// intact on failure. On success, the start address of the new range is returned.
+    pub fn move_mapping(
+        &self,
+        range: Range<Vaddr>,
+        new_size: usize,
+        new_addr: Option<Vaddr>,
+    ) -> Result<Vaddr> {
+        let vm_mapping = self.get_remapped_mapping(&range)?;
+        let is_fixed = new_addr.is_some();
+        let new_addr = match new_addr {
+            Some(new_addr) => new_addr,
+            None => {
+                let (_, new_addr) = self
+                    .0
+                    .find_free_region_for_child(None, new_size, PAGE_SIZE)
+                    .map_err(|_| Error::with_message(Errno::ENOMEM, "no free range to move to"))?;
+                new_addr
+            }
+        };
+
+        // The new range is mapped without overwriting anything if it is chosen here, so
+        // that the mappings created meanwhile by other threads are left intact.
+        let can_overwrite = is_fixed;
+        let moved_size = min(range.len(), new_size);
+        let moved_range = range.start..range.start + moved_size;
+
+        // All the options are created before mapping anything, so that failing to
+        // allocate the VMOs leaves the old range intact.
+        let remap_options =
+            vm_mapping.remap_options(Vmar(self.0.clone(), self.rights()), &moved_range)?;
+        let grow_options = if new_size > moved_size {
+            let grow_options = vm_mapping.grow_options(
+                Vmar(self.0.clone(), self.rights()),
+                range.end,
+                new_size - moved_size,
+            )?;
+            Some(grow_options)
+        } else {
+            None
+        };
+
+        // The new range is mapped before the old range is unmapped. If the new range
+        // cannot be mapped completely, it is unmapped and the old range is kept.
+        remap_options
+            .offset(new_addr)
+            .can_overwrite(can_overwrite)
+            .build()?;
+        if let Some(grow_options) = grow_options {
+            let result = grow_options
+                .offset(new_addr + moved_size)
+                .can_overwrite(can_overwrite)
+                .build();
+            if let Err(err) = result {
+                self.0.destroy(new_addr..new_addr + moved_size)?;
+                return Err(err);
+            }
+        }
+
+        self.0.destroy(range)?;
+        Ok(new_addr)
+    }
+
+    /// Gets the mapping that the remapped range is within.
+    fn get_remapped_mapping(&self, range: &Range<Vaddr>) -> Result<Arc<VmMapping>> {
+        debug_assert!(range.start % PAGE_SIZE == 0);
+        debug_assert!(range.end % PAGE_SIZE == 0);
+        let vm_mapping = self.get_vm_mapping(range.start)?;
+        if range.end > vm_mapping.range().end {
+            return_errno_with_message!(Errno::EFAULT, "the range is not within a single mapping");
+        }
+        Ok(vm_mapping)
+    }
+
+    /// Checks that every page in the range is mapped, or returns `ENOMEM`.
+    pub fn check_mapped_range(&self, range: &Range<Vaddr>) -> Result<()> {
+        self.0.get_vm_mappings_covering(range)?;
+        Ok(())
+    }
+
+    /// Discards the pages in the range.
+    ///
+    /// The range must be page-aligned and fully mapped, or `ENOMEM` is returned.
+    /// The pages of private mappings are dropped, so they read back as zeros, or as
+    /// the file contents for file-backed mappings. The pages of shared mappings are
+    /// only unmapped, so their contents, including the dirty data, are kept.
+    pub fn discard_pages(&self, range: Range<Vaddr>) -> Result<()> {
+        for vm_mapping in self.0.get_vm_mappings_covering(&range)? {
+            let intersected_range = get_intersected_range(&vm_mapping.range(), &range);
+            vm_mapping.discard_pages(&intersected_range)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the pages of the file-backed mappings in the range in advance.
+    ///
+    /// The range must be page-aligned and fully mapped, or `ENOMEM` is returned.
+    pub fn prefetch_pages(&self, range: Range<Vaddr>) -> Result<()> {
+        for vm_mapping in self.0.get_vm_mappings_covering(&range)? {
+            let intersected_range = get_intersected_range(&vm_mapping.range(), &range);
+            vm_mapping.prefetch_pages(&intersected_range)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the statistics of the pages mapped in the VMAR.
+    pub fn stat(&self) -> VmarStat {
+        let mut stat = VmarStat::default();
+        self.0.collect_stat(&mut stat);
+        stat
+    }
+
+    /// Returns the information of the mappings in the VMAR, sorted by address.
+    pub fn mappings(&self) -> Vec<VmMappingInfo> {
+        let mut infos = Vec::new();
+        self.0.collect_mappings(&mut infos);
+        infos.sort_by_key(|info| info.range.start);
+        infos
+    }
+}
+
+/// The statistics of the pages mapped in a VMAR, counted in pages.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VmarStat {
+    /// The pages of all the mappings.
+    pub total: usize,
+    /// The pages that are backed by physical frames.
+    pub resident: usize,
+    /// The resident pages of the shared mappings.
+    pub shared: usize,
+    /// The pages of the executable mappings.
+    pub text: usize,
+    /// The pages of the private writable mappings, including the stack.
+    pub data: usize,
+}
+
+/// The information of a mapping in a VMAR.
+#[derive(Clone)]
+pub struct VmMappingInfo {
+    /// The address range of the mapping.
+    pub range: Range<Vaddr>,
+    /// The permissions of the mapping.
+    pub perms: VmPerms,
+    /// Whether the mapping is shared.
+    pub is_shared: bool,
+    /// The file that backs the mapping and the offset in the file
+    /// where the mapping starts, if the mapping is file-backed.
+    pub file: Option<(Arc<Dentry>, usize)>,
 }
 
 #[derive(Debug, Clone)]
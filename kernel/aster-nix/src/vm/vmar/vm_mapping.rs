@@ -7,13 +7,14 @@ use core::ops::Range;
 
 use ostd::mm::{Frame, FrameVec, PageFlags, VmIo, VmMapOptions, VmSpace};
 
-use super::{interval::Interval, is_intersected, Vmar, Vmar_};
+use super::{interval::Interval, is_intersected, VmMappingInfo, Vmar, VmarStat, Vmar_};
 use crate::{
+    fs::path::Dentry,
     prelude::*,
     vm::{
         perms::VmPerms,
         vmar::Rights,
-        vmo::{get_page_idx_range, Vmo, VmoChildOptions, VmoRightsOp},
+        vmo::{get_page_idx_range, Vmo, VmoChildOptions, VmoOptions, VmoRightsOp},
     },
 };
 
@@ -31,6 +32,16 @@ pub struct VmMapping {
     /// TODO: support file-backed shared mappings.
     /// only anonyous memory can be mapped shared now.
     is_shared: bool,
+    /// The file that the mapped vmo is created from, if any.
+    file: Option<MappedFile>,
+}
+
+/// The file that backs a `VmMapping`.
+#[derive(Clone)]
+struct MappedFile {
+    dentry: Arc<Dentry>,
+    /// The offset in the file where the mapped vmo starts, in bytes.
+    offset: usize,
 }
 
 impl VmMapping {
@@ -42,6 +53,7 @@ impl VmMapping {
             parent: self.parent.clone(),
             vmo,
             is_shared: self.is_shared,
+            file: self.file.clone(),
         })
     }
 }
@@ -81,10 +93,12 @@ impl VmMapping {
             offset,
             align,
             can_overwrite,
+            reserve_vmo_size,
             is_shared,
+            file,
         } = option;
         let Vmar(parent_vmar, _) = parent;
-        let vmo_size = vmo.size();
+        let vmo_size = if reserve_vmo_size { vmo.size() } else { 0 };
         let map_to_addr = parent_vmar.allocate_free_region_for_vmo(
             vmo_size,
             size,
@@ -112,6 +126,7 @@ impl VmMapping {
             parent: Arc::downgrade(&parent_vmar),
             vmo: vmo.to_dyn(),
             is_shared,
+            file,
         })
     }
 
@@ -309,6 +324,7 @@ impl VmMapping {
             parent: Arc::downgrade(new_parent),
             vmo: child_vmo,
             is_shared: self.is_shared,
+            file: self.file.clone(),
         })
     }
 
@@ -316,6 +332,165 @@ impl VmMapping {
         self.map_to_addr()..self.map_to_addr() + self.map_size()
     }
 
+    /// Adds the pages of the mapping to the statistics.
+    pub(super) fn collect_stat(&self, stat: &mut VmarStat) {
+        let inner = self.inner.lock();
+        let nr_pages = inner.map_size / PAGE_SIZE;
+        let nr_resident_pages = inner.mapped_pages.len();
+
+        stat.total += nr_pages;
+        stat.resident += nr_resident_pages;
+        if self.is_shared {
+            stat.shared += nr_resident_pages;
+        }
+        if inner.perms.contains(VmPerms::EXEC) {
+            stat.text += nr_pages;
+        }
+        if !self.is_shared && inner.perms.contains(VmPerms::WRITE) {
+            stat.data += nr_pages;
+        }
+    }
+
+    /// Creates the options to map the pages of `range` in the mapping again.
+    ///
+    /// The new mapping shares the VMO, the permissions and the sharing mode with
+    /// the mapping, so the pages keep their contents wherever they are mapped.
+    pub(super) fn remap_options<R>(
+        &self,
+        vmar: Vmar<R>,
+        range: &Range<Vaddr>,
+    ) -> Result<VmarMapOptions<R, Rights>> {
+        let inner = self.inner.lock();
+        debug_assert!(inner.map_to_addr <= range.start);
+        debug_assert!(range.end <= inner.map_to_addr + inner.map_size);
+        let vmo_offset = inner.vmo_offset + (range.start - inner.map_to_addr);
+
+        let mut options = VmarMapOptions::new(vmar, self.vmo.dup()?, inner.perms)
+            .vmo_offset(vmo_offset)
+            .size(range.len())
+            .reserve_vmo_size(false)
+            .is_shared(self.is_shared);
+        options.file = self.file.clone();
+        Ok(options)
+    }
+
+    /// Creates the options to map `size` bytes that follow `addr` in the mapping.
+    ///
+    /// The new mapping has the same permissions and sharing mode as the mapping.
+    /// If the mapping is file-backed, the new mapping maps the part of the file
+    /// that follows `addr`. Otherwise, it maps new anonymous memory.
+    pub(super) fn grow_options<R>(
+        &self,
+        vmar: Vmar<R>,
+        addr: Vaddr,
+        size: usize,
+    ) -> Result<VmarMapOptions<R, Rights>> {
+        let inner = self.inner.lock();
+        debug_assert!(inner.map_to_addr <= addr && addr <= inner.map_to_addr + inner.map_size);
+        let (vmo, file) = if let Some(file) = &self.file {
+            let file_offset = file.offset + inner.vmo_offset + (addr - inner.map_to_addr);
+            let page_cache_vmo = file
+                .dentry
+                .inode()
+                .page_cache()
+                .ok_or(Error::with_message(
+                    Errno::EBADF,
+                    "File does not have page cache",
+                ))?
+                .to_dyn();
+            let file_range = file_offset..file_offset + size;
+            let vmo = if self.is_shared {
+                VmoChildOptions::new_slice_rights(page_cache_vmo, file_range).alloc()?
+            } else {
+                VmoChildOptions::new_cow(page_cache_vmo, file_range).alloc()?
+            };
+            let file = MappedFile {
+                dentry: file.dentry.clone(),
+                offset: file_offset,
+            };
+            (vmo, Some(file))
+        } else {
+            (VmoOptions::<Rights>::new(size).alloc()?, None)
+        };
+
+        let mut options = VmarMapOptions::new(vmar, vmo, inner.perms).is_shared(self.is_shared);
+        options.file = file;
+        Ok(options)
+    }
+
+    /// Extends the mapping down to `new_start` to map the pages of the VMO
+    /// below the mapped ones.
+    ///
+    /// The caller must make sure that the pages to map are free.
+    pub(super) fn grow_down(&self, new_start: Vaddr) -> Result<()> {
+        let mut inner = self.inner.lock();
+        let grown_size = inner.map_to_addr - new_start;
+        if inner.vmo_offset < grown_size {
+            return_errno_with_message!(Errno::ENOMEM, "the VMO has no pages below the mapping");
+        }
+        inner.vmo_offset -= grown_size;
+        inner.map_to_addr = new_start;
+        inner.map_size += grown_size;
+        Ok(())
+    }
+
+    /// Discards the pages of `range` in the mapping.
+    ///
+    /// The pages of a private mapping are dropped from its VMO, so they are
+    /// zero-filled, or read from the file again, on the next access. The pages of a
+    /// shared mapping are only unmapped, so that no data is lost.
+    pub(super) fn discard_pages(&self, range: &Range<Vaddr>) -> Result<()> {
+        let parent = self.parent.upgrade().unwrap();
+        let vm_space = parent.vm_space();
+        let mut inner = self.inner.lock();
+        debug_assert!(inner.map_to_addr <= range.start && range.end <= inner.range().end);
+
+        if !self.is_shared {
+            let vmo_start = range.start - inner.map_to_addr + inner.vmo_offset;
+            let vmo_end = (range.end - inner.map_to_addr + inner.vmo_offset).min(self.vmo.size());
+            if vmo_start < vmo_end {
+                self.vmo.decommit(vmo_start..vmo_end)?;
+            }
+        }
+        inner.unmap(vm_space, range, false)
+    }
+
+    /// Reads the pages of `range` in the mapping from the file in advance.
+    ///
+    /// Nothing is done if the mapping is not file-backed.
+    pub(super) fn prefetch_pages(&self, range: &Range<Vaddr>) -> Result<()> {
+        if self.file.is_none() {
+            return Ok(());
+        }
+
+        let inner = self.inner.lock();
+        let vmo_start = range.start - inner.map_to_addr + inner.vmo_offset;
+        let vmo_end = (range.end - inner.map_to_addr + inner.vmo_offset).min(self.vmo.size());
+        drop(inner);
+        if vmo_start >= vmo_end {
+            return Ok(());
+        }
+        for page_idx in get_page_idx_range(&(vmo_start..vmo_end)) {
+            self.vmo.get_committed_frame(page_idx, false)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the information of the mapping.
+    pub(super) fn info(&self) -> VmMappingInfo {
+        let inner = self.inner.lock();
+        let file = self
+            .file
+            .as_ref()
+            .map(|file| (file.dentry.clone(), file.offset + inner.vmo_offset));
+        VmMappingInfo {
+            range: inner.map_to_addr..inner.map_to_addr + inner.map_size,
+            perms: inner.perms,
+            is_shared: self.is_shared,
+            file,
+        }
+    }
+
     /// Protect the current `VmMapping` to enforce new permissions within a specified range.
     ///
     /// Due to the property of `VmMapping`, this operation may require subdividing the current
@@ -626,8 +801,12 @@ pub struct VmarMapOptions<R1, R2> {
     offset: Option<usize>,
     align: usize,
     can_overwrite: bool,
+    // Whether the mapping reserves the size of the whole VMO in the VMAR
+    reserve_vmo_size: bool,
     // Whether the mapping is mapped with `MAP_SHARED`
     is_shared: bool,
+    // The file that the VMO is created from
+    file: Option<MappedFile>,
 }
 
 impl<R1, R2> VmarMapOptions<R1, R2> {
@@ -648,7 +827,9 @@ impl<R1, R2> VmarMapOptions<R1, R2> {
             offset: None,
             align: PAGE_SIZE,
             can_overwrite: false,
+            reserve_vmo_size: true,
             is_shared: false,
+            file: None,
         }
     }
 
@@ -718,6 +899,18 @@ impl<R1, R2> VmarMapOptions<R1, R2> {
         self
     }
 
+    /// Sets whether the mapping reserves the size of the whole VMO in the VMAR.
+    ///
+    /// By default, a mapping takes at least the size of its VMO in the VMAR, even if
+    /// it maps only part of the VMO. A mapping that maps part of a larger VMO on purpose,
+    /// e.g., a part of another mapping, should only take its own size.
+    ///
+    /// The default value is true.
+    pub fn reserve_vmo_size(mut self, reserve_vmo_size: bool) -> Self {
+        self.reserve_vmo_size = reserve_vmo_size;
+        self
+    }
+
     /// Sets whether the mapping can be shared with other process.
     ///
     /// The default value is false.
@@ -729,6 +922,18 @@ impl<R1, R2> VmarMapOptions<R1, R2> {
         self
     }
 
+    /// Sets the file that the VMO is created from.
+    ///
+    /// The `offset` is the offset in the file where the VMO starts.
+    /// The file is only used to describe the mapping, e.g., in
+    /// `/proc/[pid]/maps`, and does not affect how the mapping works.
+    ///
+    /// The default value is none, i.e., an anonymous mapping.
+    pub fn file(mut self, dentry: Arc<Dentry>, offset: usize) -> Self {
+        self.file = Some(MappedFile { dentry, offset });
+        self
+    }
+
     /// Creates the mapping.
     ///
     /// All options will be checked at this point.
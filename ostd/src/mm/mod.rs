@@ -18,6 +18,7 @@ pub(crate) mod page;
 pub(crate) mod page_prop;
 pub(crate) mod page_table;
 mod space;
+pub mod stat;
 
 use alloc::vec::Vec;
 use core::{fmt::Debug, ops::Range};
@@ -15,7 +15,48 @@ use spin::Once;
 use super::{cont_pages::ContPages, meta::PageMeta, Page};
 use crate::{boot::memory_region::MemoryRegionType, mm::PAGE_SIZE, sync::SpinLock};
 
-pub(in crate::mm) static PAGE_ALLOCATOR: Once<SpinLock<FrameAllocator>> = Once::new();
+/// A frame allocator that keeps track of the number of allocated frames.
+///
+/// The buddy allocator does not expose its statistics, so they are counted
+/// here for reporting the memory usage.
+pub(in crate::mm) struct CountingFrameAllocator {
+    allocator: FrameAllocator,
+    total: usize,
+    allocated: usize,
+}
+
+impl CountingFrameAllocator {
+    fn new(allocator: FrameAllocator, total: usize) -> Self {
+        Self {
+            allocator,
+            total,
+            allocated: 0,
+        }
+    }
+
+    pub(in crate::mm) fn alloc(&mut self, count: usize) -> Option<usize> {
+        let start = self.allocator.alloc(count)?;
+        self.allocated += count;
+        Some(start)
+    }
+
+    pub(in crate::mm) fn dealloc(&mut self, start_frame: usize, count: usize) {
+        self.allocator.dealloc(start_frame, count);
+        self.allocated -= count;
+    }
+
+    /// Returns the total number of frames managed by the allocator.
+    pub(in crate::mm) fn total_frames(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of frames that are not allocated.
+    pub(in crate::mm) fn free_frames(&self) -> usize {
+        self.total - self.allocated
+    }
+}
+
+pub(in crate::mm) static PAGE_ALLOCATOR: Once<SpinLock<CountingFrameAllocator>> = Once::new();
 
 /// Allocate a single page.
 pub(crate) fn alloc_single<M: PageMeta>() -> Option<Page<M>> {
@@ -64,6 +105,7 @@ pub(crate) fn alloc<M: PageMeta>(len: usize) -> Option<Vec<Page<M>>> {
 pub(crate) fn init() {
     let regions = crate::boot::memory_regions();
     let mut allocator = FrameAllocator::<32>::new();
+    let mut total = 0;
     for region in regions.iter() {
         if region.typ() == MemoryRegionType::Usable {
             // Make the memory region page-aligned, and skip if it is too small.
@@ -75,6 +117,7 @@ pub(crate) fn init() {
             }
             // Add global free pages to the frame allocator.
             allocator.add_frame(start, end);
+            total += end - start;
             info!(
                 "Found usable region, start:{:x}, end:{:x}",
                 region.base(),
@@ -82,5 +125,5 @@ pub(crate) fn init() {
             );
         }
     }
-    PAGE_ALLOCATOR.call_once(|| SpinLock::new(allocator));
+    PAGE_ALLOCATOR.call_once(|| SpinLock::new(CountingFrameAllocator::new(allocator, total)));
 }
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! APIs for memory statistics.
+
+use super::{page::allocator::PAGE_ALLOCATOR, PAGE_SIZE};
+
+/// Returns the total size of the memory that can be allocated in bytes.
+pub fn mem_total() -> usize {
+    PAGE_ALLOCATOR.get().unwrap().lock().total_frames() * PAGE_SIZE
+}
+
+/// Returns the size of the memory that is not allocated in bytes.
+pub fn mem_free() -> usize {
+    PAGE_ALLOCATOR.get().unwrap().lock().free_frames() * PAGE_SIZE
+}
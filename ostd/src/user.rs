@@ -6,7 +6,7 @@
 
 use trapframe::TrapFrame;
 
-use crate::{cpu::UserContext, mm::VmSpace, prelude::*, task::Task};
+use crate::{cpu::UserContext, mm::VmSpace, prelude::*, sync::RwLock, task::Task};
 
 /// A user space.
 ///
@@ -14,7 +14,7 @@ use crate::{cpu::UserContext, mm::VmSpace, prelude::*, task::Task};
 /// user mode.
 pub struct UserSpace {
     /// vm space
-    vm_space: Arc<VmSpace>,
+    vm_space: RwLock<Arc<VmSpace>>,
     /// cpu context before entering user space
     init_ctx: UserContext,
 }
@@ -25,12 +25,25 @@ impl UserSpace {
     /// Each instance maintains a VM address space and the CPU state to enable
     /// execution in the user space.
     pub fn new(vm_space: Arc<VmSpace>, init_ctx: UserContext) -> Self {
-        Self { vm_space, init_ctx }
+        Self {
+            vm_space: RwLock::new(vm_space),
+            init_ctx,
+        }
     }
 
     /// Returns the VM address space.
-    pub fn vm_space(&self) -> &VmSpace {
-        &self.vm_space
+    pub fn vm_space(&self) -> Arc<VmSpace> {
+        self.vm_space.read().clone()
+    }
+
+    /// Replaces the VM address space.
+    ///
+    /// This method should only be called by the task that the user space is bound to,
+    /// so the new VM address space takes effect at once.
+    pub fn set_vm_space(&self, vm_space: Arc<VmSpace>) {
+        let mut old_vm_space = self.vm_space.write();
+        vm_space.activate();
+        *old_vm_space = vm_space;
     }
 
     /// Returns the user mode that is bound to the current task and user space.
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <grp.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/dac_permission"
+#define TEST_FILE TEST_DIR "/file"
+#define TEST_UID 1000
+#define TEST_GID 1000
+#define TEST_SUPP_GID 2000
+
+static int try_open(const char *path, int flags)
+{
+	int fd;
+
+	fd = open(path, flags);
+	if (fd < 0)
+		return -1;
+	close(fd);
+	return 0;
+}
+
+static int prepare_file(mode_t mode, gid_t gid)
+{
+	CHECK(chown(TEST_FILE, 0, gid) == 0, "chown the test file");
+	CHECK(chmod(TEST_FILE, mode) == 0, "chmod the test file");
+	return 0;
+}
+
+static int drop_privileges(void)
+{
+	gid_t groups[] = { TEST_SUPP_GID };
+
+	CHECK(setgroups(1, groups) == 0, "setgroups");
+	CHECK(setgid(TEST_GID) == 0, "setgid");
+	CHECK(setuid(TEST_UID) == 0, "setuid");
+	CHECK_ERROR(setuid(0), EPERM);
+	return 0;
+}
+
+// Runs `test` in a child process without the root privileges.
+static int run_unprivileged(int (*test)(void))
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0) {
+		if (drop_privileges() < 0 || test() < 0)
+			_exit(1);
+		_exit(0);
+	}
+
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "run the unprivileged test");
+	return 0;
+}
+
+static int test_root_override(void)
+{
+	CHECK(prepare_file(0000, 0) == 0, "prepare the test file");
+	CHECK(try_open(TEST_FILE, O_RDWR) == 0, "open as root");
+	return 0;
+}
+
+static int test_other_denied(void)
+{
+	CHECK_ERROR(try_open(TEST_FILE, O_RDONLY), EACCES);
+	CHECK_ERROR(access(TEST_FILE, R_OK), EACCES);
+	return 0;
+}
+
+static int test_other_granted(void)
+{
+	CHECK(try_open(TEST_FILE, O_RDONLY) == 0, "open as others");
+	CHECK_ERROR(try_open(TEST_FILE, O_WRONLY), EACCES);
+	return 0;
+}
+
+static int test_group_granted(void)
+{
+	CHECK(try_open(TEST_FILE, O_RDONLY) == 0, "open as a group member");
+	CHECK_ERROR(try_open(TEST_FILE, O_WRONLY), EACCES);
+	return 0;
+}
+
+static int test_procfs_denied(void)
+{
+	CHECK(try_open("/proc/sys/fs/nr_open", O_RDONLY) == 0,
+	      "read nr_open");
+	CHECK_ERROR(try_open("/proc/sys/fs/nr_open", O_WRONLY), EACCES);
+	CHECK_ERROR(try_open("/proc/sys/kernel/randomize_va_space", O_WRONLY),
+		    EACCES);
+	CHECK_ERROR(try_open("/proc/1/oom_score_adj", O_WRONLY), EACCES);
+	return 0;
+}
+
+int main(void)
+{
+	int fd;
+
+	CHECK(mkdir(TEST_DIR, 0755) == 0 || errno == EEXIST,
+	      "create the test directory");
+	fd = open(TEST_FILE, O_CREAT | O_WRONLY | O_TRUNC, 0644);
+	CHECK(fd >= 0, "create the test file");
+	close(fd);
+
+	CHECK(test_root_override() == 0, "test the root override");
+
+	// The owner and the group do not match, and the other bits deny the access.
+	CHECK(prepare_file(0640, 0) == 0, "prepare the test file");
+	CHECK(run_unprivileged(test_other_denied) == 0,
+	      "test the denied access");
+
+	// The other bits grant the access even if the group bits do not.
+	CHECK(prepare_file(0604, 0) == 0, "prepare the test file");
+	CHECK(run_unprivileged(test_other_granted) == 0,
+	      "test the other bits");
+
+	// The group bits are used for the file system group id.
+	CHECK(prepare_file(0640, TEST_GID) == 0, "prepare the test file");
+	CHECK(run_unprivileged(test_group_granted) == 0,
+	      "test the group bits");
+
+	// The group bits are used for the supplementary groups.
+	CHECK(prepare_file(0640, TEST_SUPP_GID) == 0, "prepare the test file");
+	CHECK(run_unprivileged(test_group_granted) == 0,
+	      "test the supplementary groups");
+
+	CHECK(run_unprivileged(test_procfs_denied) == 0,
+	      "test the procfs files");
+
+	CHECK(unlink(TEST_FILE) == 0, "remove the test file");
+	CHECK(rmdir(TEST_DIR) == 0, "remove the test directory");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <fcntl.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <sys/stat.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#define NEW_ROOT "/tmp/chroot_test"
+#define MARKER_FILE NEW_ROOT "/marker"
+#define SUB_DIR NEW_ROOT "/dir"
+
+static int run_in_chroot(void)
+{
+	struct stat st;
+
+	if (chroot(NEW_ROOT) < 0) {
+		perror("chroot");
+		return 1;
+	}
+	if (chdir("/dir") < 0) {
+		perror("chdir");
+		return 1;
+	}
+
+	// `..` at the new root must stay at the new root
+	if (stat("../../../marker", &st) < 0) {
+		perror("stat relative path");
+		return 1;
+	}
+	if (stat("/../../marker", &st) < 0) {
+		perror("stat absolute path");
+		return 1;
+	}
+
+	// Nothing outside the new root should be reachable
+	if (stat("../../tmp", &st) == 0 || stat("/tmp/chroot_test", &st) == 0) {
+		fprintf(stderr, "escaped from the new root\n");
+		return 1;
+	}
+
+	return 0;
+}
+
+int main(void)
+{
+	mkdir(NEW_ROOT, 0755);
+	mkdir(SUB_DIR, 0755);
+	int fd = open(MARKER_FILE, O_CREAT | O_WRONLY, 0644);
+	if (fd < 0) {
+		perror("open");
+		exit(EXIT_FAILURE);
+	}
+	close(fd);
+
+	// Chroot in a child process to keep the root of the test process intact
+	pid_t pid = fork();
+	if (pid < 0) {
+		perror("fork");
+		exit(EXIT_FAILURE);
+	}
+	if (pid == 0) {
+		exit(run_in_chroot());
+	}
+
+	int status;
+	if (waitpid(pid, &status, 0) < 0 || !WIFEXITED(status) ||
+	    WEXITSTATUS(status) != 0) {
+		fprintf(stderr, "chroot test failed\n");
+		exit(EXIT_FAILURE);
+	}
+
+	unlink(MARKER_FILE);
+	rmdir(SUB_DIR);
+	rmdir(NEW_ROOT);
+
+	printf("chroot test passed\n");
+	return 0;
+}
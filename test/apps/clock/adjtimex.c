@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/time.h>
+#include <sys/timex.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NOBODY 65534
+#define TEST_FREQ (10 << 16)
+
+// Sets the frequency as an unprivileged user and returns the error number
+static int set_freq_as_nobody(void)
+{
+	struct timex tx = { .modes = ADJ_FREQUENCY, .freq = TEST_FREQ };
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		if (setgid(NOBODY) < 0 || setuid(NOBODY) < 0)
+			_exit(0);
+		_exit(adjtimex(&tx) < 0 ? errno : 0);
+	}
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -1;
+
+	return WEXITSTATUS(status);
+}
+
+int main(void)
+{
+	struct timex tx = { 0 };
+	struct timeval now;
+	long old_freq;
+
+	// Read the current state
+	CHECK(adjtimex(&tx) >= 0, "adjtimex");
+	CHECK(gettimeofday(&now, NULL) == 0, "gettimeofday");
+	CHECK(now.tv_sec - tx.time.tv_sec <= 1, "check the current time");
+	CHECK(tx.tick > 0 && tx.tolerance > 0, "check the clock state");
+	old_freq = tx.freq;
+
+	// Setting the state requires CAP_SYS_TIME
+	CHECK(set_freq_as_nobody() == EPERM, "adjtimex without CAP_SYS_TIME");
+
+	// The frequency is stored and reported back
+	tx.modes = ADJ_FREQUENCY;
+	tx.freq = TEST_FREQ;
+	CHECK(adjtimex(&tx) >= 0, "adjtimex(ADJ_FREQUENCY)");
+	tx.modes = 0;
+	CHECK(adjtimex(&tx) >= 0 && tx.freq == TEST_FREQ, "read frequency");
+
+	tx.modes = ADJ_FREQUENCY;
+	tx.freq = old_freq;
+	CHECK(adjtimex(&tx) >= 0, "restore frequency");
+
+	printf("Test passed\n");
+	return 0;
+}
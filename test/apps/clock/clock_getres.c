@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <time.h>
+
+#include "check.h"
+
+#define NSEC_PER_SEC 1000000000L
+
+static int valid_res(clockid_t clockid)
+{
+	struct timespec res = { .tv_sec = -1, .tv_nsec = -1 };
+
+	if (clock_getres(clockid, &res) < 0)
+		return 0;
+
+	return res.tv_sec == 0 && res.tv_nsec > 0 && res.tv_nsec < NSEC_PER_SEC;
+}
+
+int main(void)
+{
+	struct timespec res;
+
+	CHECK(valid_res(CLOCK_REALTIME), "clock_getres(CLOCK_REALTIME)");
+	CHECK(valid_res(CLOCK_MONOTONIC), "clock_getres(CLOCK_MONOTONIC)");
+	CHECK(valid_res(CLOCK_REALTIME_COARSE),
+	      "clock_getres(CLOCK_REALTIME_COARSE)");
+	CHECK(valid_res(CLOCK_PROCESS_CPUTIME_ID),
+	      "clock_getres(CLOCK_PROCESS_CPUTIME_ID)");
+	CHECK(valid_res(CLOCK_THREAD_CPUTIME_ID),
+	      "clock_getres(CLOCK_THREAD_CPUTIME_ID)");
+
+	// A null pointer only validates the clock ID
+	CHECK(clock_getres(CLOCK_MONOTONIC, NULL) == 0,
+	      "clock_getres with a null pointer");
+
+	CHECK(clock_getres(100, &res) < 0 && errno == EINVAL,
+	      "clock_getres with an invalid clock ID");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <time.h>
+
+#include "check.h"
+
+#define NSEC_PER_SEC 1000000000L
+#define SPIN_NSEC (NSEC_PER_SEC / 2)
+
+static long read_nsec(clockid_t clockid)
+{
+	struct timespec ts;
+
+	if (clock_gettime(clockid, &ts) < 0)
+		return -1;
+
+	return ts.tv_sec * NSEC_PER_SEC + ts.tv_nsec;
+}
+
+int main(void)
+{
+	long wall_start, thread_start, wall, thread, process;
+	struct timespec res;
+
+	CHECK(clock_getres(CLOCK_THREAD_CPUTIME_ID, &res) == 0,
+	      "clock_getres(CLOCK_THREAD_CPUTIME_ID)");
+
+	wall_start = read_nsec(CLOCK_MONOTONIC);
+	thread_start = read_nsec(CLOCK_THREAD_CPUTIME_ID);
+	CHECK(wall_start >= 0 && thread_start >= 0, "clock_gettime");
+
+	// Spin the CPU so that the thread clock advances with the wall clock
+	do {
+		wall = read_nsec(CLOCK_MONOTONIC) - wall_start;
+	} while (wall < SPIN_NSEC);
+
+	thread = read_nsec(CLOCK_THREAD_CPUTIME_ID) - thread_start;
+	CHECK(thread >= wall / 2, "thread CPU time advances");
+	CHECK(thread <= wall + 2 * res.tv_nsec, "thread CPU time is bounded");
+
+	// The process clock includes the CPU time of all its threads
+	process = read_nsec(CLOCK_PROCESS_CPUTIME_ID);
+	CHECK(process >= thread, "process CPU time");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/time.h>
+#include <sys/wait.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NOBODY 65534
+#define JUMP_SECS 3600
+
+// Sets the real time as an unprivileged user and returns the error number
+static int settime_as_nobody(const struct timespec *ts)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		if (setgid(NOBODY) < 0 || setuid(NOBODY) < 0)
+			_exit(0);
+		_exit(clock_settime(CLOCK_REALTIME, ts) < 0 ? errno : 0);
+	}
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -1;
+
+	return WEXITSTATUS(status);
+}
+
+int main(void)
+{
+	struct timespec real, mono, real_after, mono_after;
+	struct timeval tv;
+
+	CHECK(clock_gettime(CLOCK_REALTIME, &real) == 0, "read realtime");
+	CHECK(clock_gettime(CLOCK_MONOTONIC, &mono) == 0, "read monotonic");
+
+	// Only the real-time clock can be set, which requires CAP_SYS_TIME
+	CHECK(clock_settime(CLOCK_MONOTONIC, &mono) < 0 && errno == EINVAL,
+	      "set monotonic");
+	CHECK(settime_as_nobody(&real) == EPERM, "set realtime as nobody");
+
+	// Set the real time forward
+	real.tv_sec += JUMP_SECS;
+	CHECK(clock_settime(CLOCK_REALTIME, &real) == 0, "set realtime");
+
+	CHECK(clock_gettime(CLOCK_REALTIME, &real_after) == 0, "read realtime");
+	CHECK(clock_gettime(CLOCK_MONOTONIC, &mono_after) == 0,
+	      "read monotonic");
+	CHECK(real_after.tv_sec >= real.tv_sec &&
+		      real_after.tv_sec - real.tv_sec <= 1,
+	      "check realtime");
+	CHECK(mono_after.tv_sec >= mono.tv_sec &&
+		      mono_after.tv_sec - mono.tv_sec <= 1,
+	      "check monotonic");
+
+	// Set the real time back
+	CHECK(gettimeofday(&tv, NULL) == 0, "gettimeofday");
+	tv.tv_sec -= JUMP_SECS;
+	CHECK(settimeofday(&tv, NULL) == 0, "settimeofday");
+	CHECK(clock_gettime(CLOCK_REALTIME, &real_after) == 0, "read realtime");
+	CHECK(real_after.tv_sec < real.tv_sec, "check restored realtime");
+
+	tv.tv_usec = 1000000;
+	CHECK(settimeofday(&tv, NULL) < 0 && errno == EINVAL,
+	      "settimeofday with invalid time");
+
+	printf("Test passed\n");
+	return 0;
+}
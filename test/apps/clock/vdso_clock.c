@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/auxv.h>
+#include <sys/syscall.h>
+#include <sys/time.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NR_ITERATIONS 1000
+
+static long long ts_to_ns(const struct timespec *ts)
+{
+	return ts->tv_sec * 1000000000LL + ts->tv_nsec;
+}
+
+// Checks that the VDSO time of `clock` lies between two times read by the
+// system call
+static int check_clock(clockid_t clock)
+{
+	struct timespec before, vdso, after;
+
+	CHECK(syscall(SYS_clock_gettime, clock, &before) == 0,
+	      "clock_gettime via syscall");
+	CHECK(clock_gettime(clock, &vdso) == 0, "clock_gettime via VDSO");
+	CHECK(syscall(SYS_clock_gettime, clock, &after) == 0,
+	      "clock_gettime via syscall");
+	CHECK(ts_to_ns(&before) <= ts_to_ns(&vdso) &&
+		      ts_to_ns(&vdso) <= ts_to_ns(&after),
+	      "compare VDSO and syscall times");
+	return 0;
+}
+
+int main(void)
+{
+	struct timeval tv_vdso, tv_syscall;
+	int i;
+
+	errno = 0;
+	CHECK(getauxval(AT_SYSINFO_EHDR) != 0, "getauxval(AT_SYSINFO_EHDR)");
+
+	// The VDSO keeps agreeing with the system call across repeated calls
+	for (i = 0; i < NR_ITERATIONS; ++i) {
+		CHECK(check_clock(CLOCK_MONOTONIC) == 0, "check CLOCK_MONOTONIC");
+		CHECK(check_clock(CLOCK_REALTIME) == 0, "check CLOCK_REALTIME");
+	}
+
+	CHECK(gettimeofday(&tv_vdso, NULL) == 0, "gettimeofday via VDSO");
+	CHECK(syscall(SYS_gettimeofday, &tv_syscall, NULL) == 0,
+	      "gettimeofday via syscall");
+	CHECK(tv_syscall.tv_sec - tv_vdso.tv_sec <= 1, "compare realtime");
+
+	printf("Test passed\n");
+	return 0;
+}
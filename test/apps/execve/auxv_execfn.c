@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <libgen.h>
+#include <limits.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/auxv.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define CHILD_ARG "child"
+
+// This test should be run with the absolute path of the executable.
+int main(int argc, char *argv[])
+{
+	const char *execfn, *platform;
+	char dir[PATH_MAX], filename[PATH_MAX];
+
+	errno = 0;
+	execfn = (const char *)getauxval(AT_EXECFN);
+	CHECK(errno == 0 && execfn != NULL, "getauxval(AT_EXECFN)");
+
+	// AT_EXECFN is the filename as passed to execve, even if it is relative
+	if (argc == 3 && strcmp(argv[1], CHILD_ARG) == 0) {
+		CHECK(strcmp(execfn, argv[2]) == 0,
+		      "compare AT_EXECFN with the relative path");
+		printf("Test passed\n");
+		return 0;
+	}
+
+	CHECK(strcmp(execfn, argv[0]) == 0, "compare AT_EXECFN");
+
+	errno = 0;
+	platform = (const char *)getauxval(AT_PLATFORM);
+	CHECK(errno == 0 && platform != NULL, "getauxval(AT_PLATFORM)");
+	CHECK(strcmp(platform, "x86_64") == 0, "compare AT_PLATFORM");
+
+	// Run the executable again with a relative path
+	strncpy(dir, argv[0], sizeof(dir) - 1);
+	dir[sizeof(dir) - 1] = '\0';
+	snprintf(filename, sizeof(filename), "./%s", basename(argv[0]));
+	CHECK(chdir(dirname(dir)) == 0, "chdir");
+	execl(filename, argv[0], CHILD_ARG, filename, NULL);
+	CHECK(0, "execl");
+}
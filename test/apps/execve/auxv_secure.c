@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/auxv.h>
+#include <sys/stat.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NOBODY 65534
+
+// Runs the program as an unprivileged user and returns its `AT_SECURE`
+static int run_as_nobody(char *path)
+{
+	char *argv[] = { path, "child", NULL };
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		if (setgid(NOBODY) < 0 || setuid(NOBODY) < 0)
+			_exit(-1);
+		execv(path, argv);
+		_exit(-1);
+	}
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -1;
+
+	return WEXITSTATUS(status);
+}
+
+int main(int argc, char *argv[])
+{
+	unsigned char *random;
+	int i;
+
+	if (argc > 1)
+		return getauxval(AT_SECURE);
+
+	// AT_RANDOM points to 16 random bytes
+	errno = 0;
+	random = (unsigned char *)getauxval(AT_RANDOM);
+	CHECK(errno == 0 && random != NULL, "getauxval(AT_RANDOM)");
+	for (i = 0; i < 16; ++i)
+		if (random[i] != 0)
+			break;
+	CHECK(i < 16, "read AT_RANDOM bytes");
+
+	errno = 0;
+	CHECK(getauxval(AT_SECURE) == 0 && errno == 0, "getauxval(AT_SECURE)");
+
+	// The program runs in secure mode if the set-user-ID bit changes its
+	// effective user ID
+	CHECK(run_as_nobody(argv[0]) == 0, "run as nobody");
+	CHECK(chmod(argv[0], S_ISUID | 0755) == 0, "chmod");
+	i = run_as_nobody(argv[0]);
+	CHECK(chmod(argv[0], 0755) == 0, "chmod");
+	CHECK(i == 1, "run as nobody with the set-user-ID bit");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define EXECUTABLE "/test/execve/execve_bounds"
+
+// The maximum length of a single argument or environment variable, including
+// the null terminator.
+#define MAX_ARG_LEN (32 * 4096)
+
+// Executes the test program itself in a child process, and returns whether
+// `execve` succeeds.
+static int execve_succeeds(char **argv, char **envp)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		execve(EXECUTABLE, argv, envp);
+		_exit(1);
+	}
+
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -1;
+	return WEXITSTATUS(status) == 0;
+}
+
+// Returns whether `execve` fails with `errno`.
+static int execve_fails_with(char **argv, char **envp, int err)
+{
+	return execve(EXECUTABLE, argv, envp) == -1 && errno == err;
+}
+
+// Allocates a string of `len` bytes, including the null terminator.
+static char *alloc_string(int len)
+{
+	char *string;
+
+	string = malloc(len);
+	if (string == NULL)
+		return NULL;
+	memset(string, 'a', len - 1);
+	string[len - 1] = '\0';
+	return string;
+}
+
+int main(int argc, char *argv[])
+{
+	char *empty[] = { NULL };
+	char *longest, *too_long, *page, *unmapped;
+	char **vector;
+	long page_size;
+
+	// The program is executed by itself to check that `execve` succeeds.
+	if (argc > 1)
+		return 0;
+
+	longest = alloc_string(MAX_ARG_LEN);
+	too_long = alloc_string(MAX_ARG_LEN + 1);
+	CHECK(longest != NULL && too_long != NULL, "allocate strings");
+
+	// A single string can have at most `MAX_ARG_LEN` bytes.
+	CHECK(execve_succeeds((char *[]){ EXECUTABLE, longest, NULL }, empty) ==
+		      1,
+	      "execve with the longest argument");
+	CHECK(execve_fails_with((char *[]){ EXECUTABLE, too_long, NULL }, empty,
+				E2BIG),
+	      "execve with a too long argument");
+	CHECK(execve_succeeds((char *[]){ EXECUTABLE, "child", NULL },
+			      (char *[]){ longest, NULL }) == 1,
+	      "execve with the longest environment variable");
+	CHECK(execve_fails_with((char *[]){ EXECUTABLE, "child", NULL },
+				(char *[]){ too_long, NULL }, E2BIG),
+	      "execve with a too long environment variable");
+
+	// Map two pages and unmap the second one, so the memory faults right
+	// after the end of the first page.
+	page_size = sysconf(_SC_PAGESIZE);
+	page = mmap(NULL, 2 * page_size, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(page != MAP_FAILED, "mmap");
+	unmapped = page + page_size;
+	CHECK(munmap(unmapped, page_size) == 0, "munmap");
+
+	// The vector itself faults.
+	CHECK(execve_fails_with((char **)unmapped, empty, EFAULT),
+	      "execve with an unmapped argument vector");
+	CHECK(execve_fails_with(argv, (char **)unmapped, EFAULT),
+	      "execve with an unmapped environment vector");
+
+	// The vector faults in the middle, before the null pointer is found.
+	vector = (char **)unmapped - 2;
+	vector[0] = EXECUTABLE;
+	vector[1] = "child";
+	CHECK(execve_fails_with(vector, empty, EFAULT),
+	      "execve with an unterminated argument vector");
+
+	// A string in the vector faults.
+	CHECK(execve_fails_with((char *[]){ EXECUTABLE, unmapped, NULL }, empty,
+				EFAULT),
+	      "execve with an unmapped argument");
+
+	// A string faults before the null terminator is found.
+	memset(page, 'a', page_size);
+	CHECK(execve_fails_with((char *[]){ EXECUTABLE, page, NULL }, empty,
+				EFAULT),
+	      "execve with an unterminated argument");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define EXECUTABLE "/test/execve/execve_e2big"
+
+#define NR_ENVS 64
+#define ENV_LEN (64 * 1024)
+
+// Each vector of the medium-length strings takes about 1.2 MiB, which is less
+// than the exec limit of Linux (a quarter of the 8 MiB stack limit), but the
+// two vectors take more than the limit in total.
+#define NR_MEDIUM_STRINGS 300
+#define MEDIUM_STRING_LEN (4 * 1024)
+
+// Allocates a vector of `nr` strings of `len` bytes, including the null
+// terminators. The first string is `first` if it is not NULL.
+static char **alloc_strings(int nr, int len, char *first)
+{
+	char **strings;
+	int i;
+
+	strings = calloc(nr + 1, sizeof(char *));
+	if (strings == NULL)
+		return NULL;
+
+	for (i = 0; i < nr; ++i) {
+		if (i == 0 && first != NULL) {
+			strings[i] = first;
+			continue;
+		}
+		strings[i] = malloc(len);
+		if (strings[i] == NULL)
+			return NULL;
+		memset(strings[i], 'a', len - 1);
+		strings[i][0] = 'A' + i % 26;
+		strings[i][1] = '=';
+		strings[i][len - 1] = '\0';
+	}
+	strings[nr] = NULL;
+
+	return strings;
+}
+
+// Executes the test program itself in a child process, and returns whether
+// `execve` succeeds.
+static int execve_succeeds(char **argv, char **envp)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		execve(EXECUTABLE, argv, envp);
+		_exit(errno == E2BIG ? 1 : 2);
+	}
+
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -1;
+	return WEXITSTATUS(status) == 0;
+}
+
+int main(int argc, char *argv[])
+{
+	char *empty[] = { NULL };
+	char **envp, **medium_argv, **medium_envp;
+
+	// The program is executed by itself to check that `execve` succeeds.
+	if (argc > 1)
+		return 0;
+
+	// The environment variables take 4 MiB in total.
+	envp = alloc_strings(NR_ENVS, ENV_LEN, NULL);
+	CHECK(envp != NULL, "allocate environment variables");
+	CHECK(execve(EXECUTABLE, argv, envp) == -1 && errno == E2BIG,
+	      "execve with large environment variables");
+
+	medium_argv = alloc_strings(NR_MEDIUM_STRINGS, MEDIUM_STRING_LEN,
+				    EXECUTABLE);
+	medium_envp = alloc_strings(NR_MEDIUM_STRINGS, MEDIUM_STRING_LEN, NULL);
+	CHECK(medium_argv != NULL && medium_envp != NULL,
+	      "allocate medium-length strings");
+
+	CHECK(execve_succeeds(medium_argv, empty) == 1,
+	      "execve with medium-length arguments");
+	CHECK(execve_succeeds((char *[]){ EXECUTABLE, "child", NULL },
+			      medium_envp) == 1,
+	      "execve with medium-length environment variables");
+
+	// The limit applies to the arguments and environment variables together.
+	CHECK(execve(EXECUTABLE, medium_argv, medium_envp) == -1 &&
+		      errno == E2BIG,
+	      "execve with too many medium-length strings");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+// The test program itself serves as the interpreter of the scripts.
+#define INTERPRETER "/test/execve/shebang"
+#define SCRIPT_NO_ARG "/tmp/shebang_no_arg"
+#define SCRIPT_ARG "/tmp/shebang_arg"
+#define SCRIPT_CHAIN "/tmp/shebang_chain"
+#define MAX_CHAIN 6
+// The number of chained scripts that can be run
+#define NR_CHAINED 4
+
+static int write_script(const char *path, const char *content)
+{
+	int fd;
+
+	fd = open(path, O_CREAT | O_WRONLY | O_TRUNC, 0755);
+	CHECK(fd >= 0, "create script");
+	CHECK(write(fd, content, strlen(content)) == strlen(content),
+	      "write script");
+	CHECK(close(fd) == 0, "close script");
+	return 0;
+}
+
+// Writes a chain of scripts, in which each script is interpreted by the next
+// one and the last one is interpreted by the test program.
+static int write_script_chain(void)
+{
+	char path[64], content[64];
+	int i;
+
+	for (i = 0; i < MAX_CHAIN; ++i) {
+		snprintf(path, sizeof(path), "%s%d", SCRIPT_CHAIN, i);
+		if (i == MAX_CHAIN - 1)
+			snprintf(content, sizeof(content), "#!%s\n",
+				 INTERPRETER);
+		else
+			snprintf(content, sizeof(content), "#!%s%d\n",
+				 SCRIPT_CHAIN, i + 1);
+		CHECK(write_script(path, content) == 0, path);
+	}
+	return 0;
+}
+
+// Runs the script and returns the exit status, or the negated errno of
+// `execv` if it fails.
+static int run_script(char *path)
+{
+	char *argv[] = { "script", "last", NULL };
+	int status, err;
+	int pipe_fds[2];
+	pid_t pid;
+
+	if (pipe2(pipe_fds, O_CLOEXEC) < 0)
+		return -EIO;
+
+	pid = fork();
+	if (pid == 0) {
+		execv(path, argv);
+		err = errno;
+		write(pipe_fds[1], &err, sizeof(err));
+		_exit(-1);
+	}
+	close(pipe_fds[1]);
+	if (pid < 0 || waitpid(pid, &status, 0) != pid || !WIFEXITED(status))
+		return -EIO;
+	if (read(pipe_fds[0], &err, sizeof(err)) == sizeof(err))
+		status = -err;
+	else
+		status = WEXITSTATUS(status);
+	close(pipe_fds[0]);
+	return status;
+}
+
+// Checks the arguments of the chained scripts, which should be the paths of
+// the scripts from the innermost one to the outermost one.
+static int check_chain_args(char *argv[])
+{
+	char path[64];
+	int i;
+
+	for (i = 0; i < NR_CHAINED; ++i) {
+		snprintf(path, sizeof(path), "%s%d", SCRIPT_CHAIN,
+			 MAX_CHAIN - 1 - i);
+		if (strcmp(argv[i], path) != 0)
+			return 1;
+	}
+	return 0;
+}
+
+// Checks the arguments when the program runs as an interpreter, which should
+// be the interpreter, the optional argument, the paths of the scripts from the
+// innermost one, and the original arguments except the first one.
+static int check_interpreter_args(int argc, char *argv[])
+{
+	if (strcmp(argv[0], INTERPRETER) != 0)
+		return 1;
+
+	// The optional argument is passed as a whole, without the blanks around.
+	if (strcmp(argv[1], "-a  b") == 0)
+		return argc != 4 || strcmp(argv[2], SCRIPT_ARG) != 0 ||
+		       strcmp(argv[3], "last") != 0;
+
+	if (strcmp(argv[1], SCRIPT_NO_ARG) == 0)
+		return argc != 3 || strcmp(argv[2], "last") != 0;
+
+	return argc != NR_CHAINED + 2 || check_chain_args(&argv[1]) != 0 ||
+	       strcmp(argv[NR_CHAINED + 1], "last") != 0;
+}
+
+int main(int argc, char *argv[])
+{
+	char path[64];
+
+	if (argc > 1)
+		return check_interpreter_args(argc, argv);
+
+	CHECK(write_script(SCRIPT_NO_ARG, "#!" INTERPRETER "\necho\n") == 0,
+	      "write script without argument");
+	CHECK(write_script(SCRIPT_ARG, "#! \t" INTERPRETER " \t-a  b \t\n") ==
+		      0,
+	      "write script with argument");
+	CHECK(write_script_chain() == 0, "write script chain");
+
+	CHECK(run_script(SCRIPT_NO_ARG) == 0, "run script without argument");
+	CHECK(run_script(SCRIPT_ARG) == 0, "run script with argument");
+
+	// Four scripts can be chained, but six scripts are too many.
+	snprintf(path, sizeof(path), "%s%d", SCRIPT_CHAIN,
+		 MAX_CHAIN - NR_CHAINED);
+	CHECK(run_script(path) == 0, "run chained scripts");
+	snprintf(path, sizeof(path), "%s%d", SCRIPT_CHAIN, 0);
+	CHECK(run_script(path) == -ELOOP, "run too many chained scripts");
+
+	CHECK(unlink(SCRIPT_NO_ARG) == 0, "unlink script");
+	CHECK(unlink(SCRIPT_ARG) == 0, "unlink script");
+	for (int i = 0; i < MAX_CHAIN; ++i) {
+		snprintf(path, sizeof(path), "%s%d", SCRIPT_CHAIN, i);
+		CHECK(unlink(path) == 0, "unlink script");
+	}
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/syscall.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#ifndef SYS_faccessat2
+#define SYS_faccessat2 439
+#endif
+
+#define TEST_DIR "/tmp/faccessat2_test"
+#define TEST_FILE TEST_DIR "/file"
+#define DANGLING_LINK TEST_DIR "/dangling"
+
+static int faccessat2(int dirfd, const char *path, int mode, int flags)
+{
+	return syscall(SYS_faccessat2, dirfd, path, mode, flags);
+}
+
+static int test_modes(void)
+{
+	CHECK(faccessat2(AT_FDCWD, TEST_FILE, F_OK, 0) == 0, "F_OK");
+	CHECK_ERROR(faccessat2(AT_FDCWD, TEST_DIR "/none", F_OK, 0), ENOENT);
+
+	// Root can read and write any file, but executing requires an execute bit
+	CHECK(faccessat2(AT_FDCWD, TEST_FILE, R_OK | W_OK, 0) == 0,
+	      "R_OK | W_OK");
+	CHECK(faccessat2(AT_FDCWD, TEST_FILE, R_OK | W_OK, AT_EACCESS) == 0,
+	      "R_OK | W_OK with AT_EACCESS");
+	CHECK_ERROR(faccessat2(AT_FDCWD, TEST_FILE, X_OK, AT_EACCESS), EACCES);
+
+	CHECK_ERROR(faccessat2(AT_FDCWD, TEST_FILE, 0x8, 0), EINVAL);
+	CHECK_ERROR(faccessat2(AT_FDCWD, TEST_FILE, F_OK, 0x1), EINVAL);
+	return 0;
+}
+
+static int test_flags(void)
+{
+	int fd;
+
+	// The dangling symlink itself exists, but its target does not
+	CHECK(faccessat2(AT_FDCWD, DANGLING_LINK, F_OK, AT_SYMLINK_NOFOLLOW) == 0,
+	      "AT_SYMLINK_NOFOLLOW");
+	CHECK_ERROR(faccessat2(AT_FDCWD, DANGLING_LINK, F_OK, 0), ENOENT);
+
+	fd = open(TEST_FILE, O_RDONLY);
+	CHECK(fd >= 0, "open");
+	CHECK(faccessat2(fd, "", R_OK, AT_EMPTY_PATH) == 0, "AT_EMPTY_PATH");
+	CHECK_ERROR(faccessat2(fd, "", R_OK, 0), ENOENT);
+	close(fd);
+	return 0;
+}
+
+int main(void)
+{
+	int fd, ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	fd = open(TEST_FILE, O_WRONLY | O_CREAT, 0000);
+	if (fd < 0) {
+		perror("create file");
+		goto out;
+	}
+	close(fd);
+	if (symlink(TEST_DIR "/none", DANGLING_LINK) < 0) {
+		perror("symlink");
+		goto out;
+	}
+
+	if (test_modes() < 0 || test_flags() < 0)
+		goto out;
+
+	printf("faccessat2 test passed\n");
+	ret = 0;
+out:
+	unlink(DANGLING_LINK);
+	unlink(TEST_FILE);
+	rmdir(TEST_DIR);
+	return ret;
+}
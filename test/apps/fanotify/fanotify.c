@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/fanotify.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define FILE_NAME "/tmp/fanotify_test_file"
+
+static int test_invalid_args(int group_fd)
+{
+	CHECK_ERROR(fanotify_init(0x80000000, O_RDONLY), EINVAL);
+	CHECK_ERROR(fanotify_mark(group_fd, FAN_MARK_ADD | FAN_MARK_REMOVE,
+				  FAN_OPEN, AT_FDCWD, FILE_NAME),
+		    EINVAL);
+	CHECK_ERROR(fanotify_mark(STDIN_FILENO, FAN_MARK_ADD, FAN_OPEN,
+				  AT_FDCWD, FILE_NAME),
+		    EINVAL);
+	CHECK_ERROR(fanotify_mark(group_fd, FAN_MARK_REMOVE, FAN_OPEN,
+				  AT_FDCWD, FILE_NAME),
+		    ENOENT);
+	CHECK_ERROR(fanotify_mark(group_fd, FAN_MARK_ADD | FAN_MARK_ONLYDIR,
+				  FAN_OPEN, AT_FDCWD, FILE_NAME),
+		    ENOTDIR);
+	return 0;
+}
+
+static int test_events(int group_fd)
+{
+	char buf[4096];
+	struct fanotify_event_metadata *event;
+	struct stat file_stat, event_stat;
+	unsigned long long mask = 0;
+	ssize_t len;
+	int fd, nr_events = 0;
+
+	CHECK(stat(FILE_NAME, &file_stat) == 0, "stat");
+	CHECK(fanotify_mark(group_fd, FAN_MARK_ADD, FAN_OPEN | FAN_MODIFY,
+			    AT_FDCWD, FILE_NAME) == 0,
+	      "fanotify_mark");
+	CHECK_ERROR(read(group_fd, buf, sizeof(buf)), EAGAIN);
+
+	fd = open(FILE_NAME, O_WRONLY);
+	CHECK(fd >= 0, "open");
+	CHECK(write(fd, "hello", 5) == 5, "write");
+	CHECK(close(fd) == 0, "close");
+
+	// The buffer must be large enough to hold an event.
+	CHECK_ERROR(read(group_fd, buf, 4), EINVAL);
+
+	len = read(group_fd, buf, sizeof(buf));
+	CHECK(len > 0, "read events");
+	for (event = (struct fanotify_event_metadata *)buf;
+	     FAN_EVENT_OK(event, len); event = FAN_EVENT_NEXT(event, len)) {
+		CHECK(event->vers == FANOTIFY_METADATA_VERSION, "event version");
+		CHECK(event->pid == getpid(), "event pid");
+		CHECK(event->fd >= 0, "event fd");
+		CHECK(fstat(event->fd, &event_stat) == 0, "fstat event fd");
+		CHECK(event_stat.st_ino == file_stat.st_ino &&
+			      event_stat.st_dev == file_stat.st_dev,
+		      "event file");
+		CHECK(close(event->fd) == 0, "close event fd");
+		mask |= event->mask;
+		nr_events++;
+	}
+	CHECK(nr_events > 0, "event count");
+	CHECK((mask & FAN_OPEN) && (mask & FAN_MODIFY), "event mask");
+	CHECK_ERROR(read(group_fd, buf, sizeof(buf)), EAGAIN);
+
+	// No events are reported after the mark is removed.
+	CHECK(fanotify_mark(group_fd, FAN_MARK_REMOVE, FAN_OPEN | FAN_MODIFY,
+			    AT_FDCWD, FILE_NAME) == 0,
+	      "remove mark");
+	fd = open(FILE_NAME, O_RDONLY);
+	CHECK(fd >= 0, "open");
+	CHECK(close(fd) == 0, "close");
+	CHECK_ERROR(read(group_fd, buf, sizeof(buf)), EAGAIN);
+	return 0;
+}
+
+int main(void)
+{
+	int group_fd, fd;
+
+	fd = open(FILE_NAME, O_CREAT | O_WRONLY | O_TRUNC, 0644);
+	CHECK(fd >= 0, "create file");
+	CHECK(close(fd) == 0, "close");
+
+	group_fd = fanotify_init(FAN_CLASS_NOTIF | FAN_NONBLOCK | FAN_CLOEXEC,
+				 O_RDONLY);
+	CHECK(group_fd >= 0, "fanotify_init");
+
+	if (test_invalid_args(group_fd) < 0 || test_events(group_fd) < 0)
+		return -1;
+
+	CHECK(close(group_fd) == 0, "close group");
+	CHECK(unlink(FILE_NAME) == 0, "unlink");
+
+	printf("Test passed\n");
+	return 0;
+}
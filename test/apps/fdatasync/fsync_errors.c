@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/socket.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define FILE_PATH "/tmp/fsync_errors_file"
+#define FIFO_PATH "/tmp/fsync_errors_fifo"
+
+static int check_sync_ok(int fd, const char *msg)
+{
+	CHECK(fsync(fd) == 0, msg);
+	CHECK(fdatasync(fd) == 0, msg);
+	return 0;
+}
+
+static int check_sync_error(int fd, int err, const char *msg)
+{
+	CHECK(fsync(fd) < 0 && errno == err, msg);
+	CHECK(fdatasync(fd) < 0 && errno == err, msg);
+	return 0;
+}
+
+int main(void)
+{
+	int fd, fds[2];
+
+	// Regular files and directories can be synchronized.
+	fd = open(FILE_PATH, O_RDWR | O_CREAT | O_TRUNC, 0644);
+	CHECK(fd >= 0, "open the file");
+	CHECK(check_sync_ok(fd, "sync the file") == 0, "sync the file");
+	CHECK(close(fd) == 0, "close");
+
+	fd = open("/tmp", O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open the directory");
+	CHECK(check_sync_ok(fd, "sync the directory") == 0,
+	      "sync the directory");
+	CHECK(close(fd) == 0, "close");
+
+	// Pipes, sockets and character devices cannot be synchronized.
+	CHECK(pipe(fds) == 0, "pipe");
+	CHECK(check_sync_error(fds[0], EINVAL, "sync the pipe") == 0,
+	      "sync the pipe");
+	CHECK(close(fds[0]) == 0 && close(fds[1]) == 0, "close the pipe");
+
+	CHECK(socketpair(AF_UNIX, SOCK_STREAM, 0, fds) == 0, "socketpair");
+	CHECK(check_sync_error(fds[0], EINVAL, "sync the socket") == 0,
+	      "sync the socket");
+	CHECK(close(fds[0]) == 0 && close(fds[1]) == 0, "close the socket");
+
+	CHECK(mkfifo(FIFO_PATH, 0644) == 0, "mkfifo");
+	fd = open(FIFO_PATH, O_RDWR);
+	CHECK(fd >= 0, "open the fifo");
+	CHECK(check_sync_error(fd, EINVAL, "sync the fifo") == 0,
+	      "sync the fifo");
+	CHECK(close(fd) == 0, "close");
+	CHECK(unlink(FIFO_PATH) == 0, "unlink the fifo");
+
+	fd = open("/dev/null", O_RDWR);
+	CHECK(fd >= 0, "open /dev/null");
+	CHECK(check_sync_error(fd, EINVAL, "sync /dev/null") == 0,
+	      "sync /dev/null");
+	CHECK(close(fd) == 0, "close");
+
+	// Invalid file descriptors and `O_PATH` files are rejected.
+	fd = open(FILE_PATH, O_PATH);
+	CHECK(fd >= 0, "open the file with O_PATH");
+	CHECK(check_sync_error(fd, EBADF, "sync the O_PATH file") == 0,
+	      "sync the O_PATH file");
+	CHECK(close(fd) == 0, "close");
+	CHECK(check_sync_error(fd, EBADF, "sync the closed file") == 0,
+	      "sync the closed file");
+	CHECK(unlink(FILE_PATH) == 0, "unlink the file");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <limits.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/socket.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define FILE_NAME "test_sync_file_range.txt"
+#define ALL_FLAGS							\
+	(SYNC_FILE_RANGE_WAIT_BEFORE | SYNC_FILE_RANGE_WRITE |		\
+	 SYNC_FILE_RANGE_WAIT_AFTER)
+
+static int test_file(const char *dir)
+{
+	char path[256], buf[8192];
+	int fd;
+
+	snprintf(path, sizeof(path), "%s/%s", dir, FILE_NAME);
+	fd = open(path, O_RDWR | O_CREAT | O_TRUNC, 0644);
+	CHECK(fd >= 0, "open");
+	memset(buf, 'a', sizeof(buf));
+	CHECK(write(fd, buf, sizeof(buf)) == sizeof(buf), "write");
+
+	CHECK(sync_file_range(fd, 0, 4096, SYNC_FILE_RANGE_WRITE) == 0,
+	      "sync the first page");
+	CHECK(sync_file_range(fd, 4096, 0, ALL_FLAGS) == 0,
+	      "sync to the end of the file");
+	CHECK(sync_file_range(fd, 0, 0, 0) == 0, "sync with no flags");
+	CHECK(sync_file_range(fd, 1 << 20, 4096, ALL_FLAGS) == 0,
+	      "sync beyond the end of the file");
+
+	CHECK_ERROR(sync_file_range(fd, -1, 4096, 0), EINVAL);
+	CHECK_ERROR(sync_file_range(fd, 0, -1, 0), EINVAL);
+	CHECK_ERROR(sync_file_range(fd, LLONG_MAX, 1, 0), EINVAL);
+	CHECK_ERROR(sync_file_range(fd, 0, 4096, 0x8), EINVAL);
+
+	CHECK(close(fd) == 0, "close");
+	CHECK(unlink(path) == 0, "unlink");
+	return 0;
+}
+
+int main(int argc, char *argv[])
+{
+	int fd, fds[2];
+
+	// Each directory given in the arguments is on a file system to be tested.
+	for (int i = 1; i < argc; i++)
+		CHECK(test_file(argv[i]) == 0, argv[i]);
+
+	fd = open("/", O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open the directory");
+	CHECK(sync_file_range(fd, 0, 0, ALL_FLAGS) == 0, "sync the directory");
+	CHECK(close(fd) == 0, "close");
+
+	// Pipes, sockets and character devices are rejected.
+	CHECK(pipe(fds) == 0, "pipe");
+	CHECK_ERROR(sync_file_range(fds[0], 0, 0, 0), ESPIPE);
+	CHECK(close(fds[0]) == 0 && close(fds[1]) == 0, "close the pipe");
+
+	CHECK(socketpair(AF_UNIX, SOCK_STREAM, 0, fds) == 0, "socketpair");
+	CHECK_ERROR(sync_file_range(fds[0], 0, 0, 0), ESPIPE);
+	CHECK(close(fds[0]) == 0 && close(fds[1]) == 0, "close the socket");
+
+	fd = open("/dev/null", O_RDWR);
+	CHECK(fd >= 0, "open /dev/null");
+	CHECK_ERROR(sync_file_range(fd, 0, 0, 0), ESPIPE);
+	CHECK(close(fd) == 0, "close");
+
+	// Invalid file descriptors and `O_PATH` files are rejected.
+	fd = open("/", O_PATH);
+	CHECK(fd >= 0, "open with O_PATH");
+	CHECK_ERROR(sync_file_range(fd, 0, 0, 0), EBADF);
+	CHECK(close(fd) == 0, "close");
+	CHECK_ERROR(sync_file_range(fd, 0, 0, 0), EBADF);
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+int main(int argc, char *argv[])
+{
+	int fd, fds[2];
+
+	// Each directory given in the arguments is on a file system to be synced.
+	for (int i = 1; i < argc; i++) {
+		fd = open(argv[i], O_RDONLY | O_DIRECTORY);
+		CHECK(fd >= 0, "open the directory");
+		CHECK(syncfs(fd) == 0, "syncfs");
+		CHECK(close(fd) == 0, "close");
+	}
+
+	fd = open("/", O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open the root");
+	CHECK(syncfs(fd) == 0, "syncfs the root");
+	CHECK(close(fd) == 0, "close");
+
+	// The files on pseudo file systems can be synced, too.
+	CHECK(pipe(fds) == 0, "pipe");
+	CHECK(syncfs(fds[0]) == 0, "syncfs the pipe");
+	CHECK(close(fds[0]) == 0 && close(fds[1]) == 0, "close the pipe");
+
+	// The file descriptor must be valid.
+	CHECK(syncfs(fds[0]) < 0 && errno == EBADF, "syncfs the closed file");
+	CHECK(syncfs(-1) < 0 && errno == EBADF, "syncfs the invalid file");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/prctl.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define GRANDCHILD_EXIT_CODE 7
+
+int main(void)
+{
+	int pipe_fds[2];
+	pid_t child, grandchild, ppid;
+	int is_subreaper, status;
+
+	CHECK(prctl(PR_GET_CHILD_SUBREAPER, &is_subreaper) == 0 &&
+		      is_subreaper == 0,
+	      "get the default subreaper flag");
+	CHECK(prctl(PR_SET_CHILD_SUBREAPER, 1) == 0, "set the subreaper flag");
+	CHECK(prctl(PR_GET_CHILD_SUBREAPER, &is_subreaper) == 0 &&
+		      is_subreaper == 1,
+	      "get the subreaper flag");
+
+	CHECK(pipe(pipe_fds) == 0, "pipe");
+
+	child = fork();
+	if (child == 0) {
+		// The subreaper flag is not inherited
+		if (prctl(PR_GET_CHILD_SUBREAPER, &is_subreaper) < 0 ||
+		    is_subreaper != 0)
+			_exit(1);
+
+		ppid = getpid();
+		grandchild = fork();
+		if (grandchild == 0) {
+			// Wait until the child exits, and report the new parent
+			while (getppid() == ppid)
+				usleep(10 * 1000);
+			ppid = getppid();
+			if (write(pipe_fds[1], &ppid, sizeof(ppid)) !=
+			    sizeof(ppid))
+				_exit(1);
+			_exit(GRANDCHILD_EXIT_CODE);
+		}
+		if (write(pipe_fds[1], &grandchild, sizeof(grandchild)) !=
+		    sizeof(grandchild))
+			_exit(1);
+		_exit(0);
+	}
+	CHECK(child > 0, "fork");
+
+	CHECK(read(pipe_fds[0], &grandchild, sizeof(grandchild)) ==
+		      sizeof(grandchild),
+	      "read the pid of the grandchild");
+	CHECK(waitpid(child, &status, 0) == child && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "wait for the child");
+
+	// The orphaned grandchild is adopted by the subreaper, not the init
+	// process
+	CHECK(read(pipe_fds[0], &ppid, sizeof(ppid)) == sizeof(ppid) &&
+		      ppid == getpid(),
+	      "read the new parent of the grandchild");
+	CHECK(waitpid(grandchild, &status, 0) == grandchild &&
+		      WIFEXITED(status) &&
+		      WEXITSTATUS(status) == GRANDCHILD_EXIT_CODE,
+	      "wait for the grandchild");
+	CHECK(waitpid(-1, &status, WNOHANG) < 0 && errno == ECHILD,
+	      "wait without children");
+
+	printf("Test passed\n");
+	return 0;
+}
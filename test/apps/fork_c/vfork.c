@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define EXECUTABLE "/test/fork_c/vfork"
+
+#define SLEEP_MS 200
+
+static volatile int shared_value;
+
+static long elapsed_ms(const struct timespec *start)
+{
+	struct timespec now;
+
+	clock_gettime(CLOCK_MONOTONIC, &now);
+	return (now.tv_sec - start->tv_sec) * 1000 +
+	       (now.tv_nsec - start->tv_nsec) / 1000000;
+}
+
+int main(int argc, char *argv[])
+{
+	struct timespec start;
+	int status;
+	pid_t pid;
+
+	// The program is executed by the vfork child, which sleeps for a while.
+	if (argc > 1) {
+		usleep(SLEEP_MS * 1000);
+		return 0;
+	}
+
+	// The parent is suspended until the child exits.
+	clock_gettime(CLOCK_MONOTONIC, &start);
+	pid = vfork();
+	if (pid == 0) {
+		usleep(SLEEP_MS * 1000);
+		_exit(0);
+	}
+	CHECK(pid > 0, "vfork");
+	CHECK(elapsed_ms(&start) >= SLEEP_MS / 2,
+	      "suspend the parent until the child exits");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "wait for the child that exits");
+
+	// The child shares the memory with the parent.
+	shared_value = 1;
+	pid = vfork();
+	if (pid == 0) {
+		shared_value = 2;
+		_exit(0);
+	}
+	CHECK(pid > 0, "vfork");
+	CHECK(shared_value == 2, "see the write of the child");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "wait for the child that writes");
+
+	// The parent is resumed once the child execs, while the child is still
+	// running.
+	pid = vfork();
+	if (pid == 0) {
+		execl(EXECUTABLE, EXECUTABLE, "child", NULL);
+		_exit(1);
+	}
+	CHECK(pid > 0, "vfork");
+	CHECK(waitpid(pid, &status, WNOHANG) == 0,
+	      "resume the parent when the child execs");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "wait for the child that execs");
+	CHECK(shared_value == 2, "keep the memory after the child execs");
+
+	printf("Test passed\n");
+	return 0;
+}
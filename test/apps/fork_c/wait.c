@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static void sigchld_handler(int signum)
+{
+	(void)signum;
+}
+
+static pid_t wait_no_intr(int *status)
+{
+	pid_t pid;
+
+	do {
+		pid = waitpid(-1, status, 0);
+	} while (pid < 0 && errno == EINTR);
+	return pid;
+}
+
+int main(void)
+{
+	struct sigaction sa;
+	int status;
+	pid_t pid;
+
+	// The process has no children
+	CHECK(waitpid(-1, &status, 0) < 0 && errno == ECHILD,
+	      "wait without children");
+	CHECK(waitpid(-1, &status, WNOHANG) < 0 && errno == ECHILD,
+	      "wait without children with WNOHANG");
+
+	// The child is alive
+	pid = fork();
+	if (pid == 0) {
+		for (;;)
+			pause();
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(-1, &status, WNOHANG) == 0,
+	      "wait for any living child with WNOHANG");
+	CHECK(waitpid(pid, &status, WNOHANG) == 0,
+	      "wait for the living child with WNOHANG");
+	CHECK(waitpid(pid + 1000, &status, WNOHANG) < 0 && errno == ECHILD,
+	      "wait for a non-child process");
+
+	CHECK(kill(pid, SIGKILL) == 0, "kill");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFSIGNALED(status),
+	      "wait for the killed child");
+	CHECK(waitpid(-1, &status, WNOHANG) < 0 && errno == ECHILD,
+	      "wait for the reaped child");
+
+	// The children are reaped automatically if `SIGCHLD` is ignored
+	CHECK(signal(SIGCHLD, SIG_IGN) != SIG_ERR, "ignore SIGCHLD");
+	pid = fork();
+	if (pid == 0) {
+		usleep(100 * 1000);
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(-1, &status, 0) < 0 && errno == ECHILD,
+	      "wait for the child when SIGCHLD is ignored");
+
+	// The children are reaped automatically if `SA_NOCLDWAIT` is set
+	memset(&sa, 0, sizeof(sa));
+	sa.sa_handler = sigchld_handler;
+	sa.sa_flags = SA_NOCLDWAIT;
+	CHECK(sigaction(SIGCHLD, &sa, NULL) == 0, "set SA_NOCLDWAIT");
+	pid = fork();
+	if (pid == 0) {
+		usleep(100 * 1000);
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(wait_no_intr(&status) < 0 && errno == ECHILD,
+	      "wait for the child when SA_NOCLDWAIT is set");
+
+	// `SA_NOCLDWAIT` also takes effect with `SIG_DFL`
+	memset(&sa, 0, sizeof(sa));
+	sa.sa_handler = SIG_DFL;
+	sa.sa_flags = SA_NOCLDWAIT;
+	CHECK(sigaction(SIGCHLD, &sa, NULL) == 0,
+	      "set SA_NOCLDWAIT with SIG_DFL");
+	CHECK(sigaction(SIGCHLD, NULL, &sa) == 0 &&
+		      (sa.sa_flags & SA_NOCLDWAIT),
+	      "get SA_NOCLDWAIT with SIG_DFL");
+	pid = fork();
+	if (pid == 0) {
+		usleep(100 * 1000);
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(-1, &status, 0) < 0 && errno == ECHILD,
+	      "wait for the child when SA_NOCLDWAIT is set with SIG_DFL");
+
+	printf("Test passed\n");
+	return 0;
+}
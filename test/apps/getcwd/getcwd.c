@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <limits.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#define TEST_DIR "/tmp/getcwd_test"
+#define SRC_DIR TEST_DIR "/src"
+#define MNT_DIR TEST_DIR "/mnt"
+#define SUB_DIR MNT_DIR "/sub"
+#define GONE_DIR TEST_DIR "/gone"
+
+static int check_cwd(const char *expected)
+{
+	char buf[PATH_MAX];
+
+	if (getcwd(buf, sizeof(buf)) == NULL) {
+		perror("getcwd");
+		return -1;
+	}
+	if (strcmp(buf, expected) != 0) {
+		fprintf(stderr, "cwd is %s, expected %s\n", buf, expected);
+		return -1;
+	}
+	return 0;
+}
+
+static int test_across_mounts(void)
+{
+	int fd, ret = -1;
+
+	if (mount(SRC_DIR, MNT_DIR, NULL, MS_BIND, NULL) < 0) {
+		perror("mount");
+		return -1;
+	}
+	if (mkdir(SUB_DIR, 0755) < 0) {
+		perror("mkdir sub");
+		goto out_umount;
+	}
+
+	if (chdir(SUB_DIR) < 0) {
+		perror("chdir");
+		goto out_rmdir;
+	}
+	if (check_cwd(SUB_DIR) < 0)
+		goto out_chdir;
+
+	if (chdir("..") < 0 || check_cwd(MNT_DIR) < 0)
+		goto out_chdir;
+	if (chdir("..") < 0 || check_cwd(TEST_DIR) < 0)
+		goto out_chdir;
+
+	fd = open(SUB_DIR, O_RDONLY | O_DIRECTORY);
+	if (fd < 0) {
+		perror("open sub");
+		goto out_chdir;
+	}
+	if (fchdir(fd) < 0) {
+		perror("fchdir");
+		close(fd);
+		goto out_chdir;
+	}
+	close(fd);
+	if (check_cwd(SUB_DIR) < 0)
+		goto out_chdir;
+
+	ret = 0;
+out_chdir:
+	chdir("/");
+out_rmdir:
+	rmdir(SUB_DIR);
+out_umount:
+	umount(MNT_DIR);
+	return ret;
+}
+
+static int test_small_buffer(void)
+{
+	char buf[4];
+
+	if (chdir(TEST_DIR) < 0) {
+		perror("chdir");
+		return -1;
+	}
+	if (getcwd(buf, sizeof(buf)) != NULL || errno != ERANGE) {
+		fprintf(stderr, "getcwd with a small buffer should fail with ERANGE\n");
+		return -1;
+	}
+	return 0;
+}
+
+static int test_unlinked_cwd(void)
+{
+	char buf[PATH_MAX];
+
+	if (mkdir(GONE_DIR, 0755) < 0 || chdir(GONE_DIR) < 0) {
+		perror("mkdir or chdir");
+		return -1;
+	}
+	if (rmdir(GONE_DIR) < 0) {
+		perror("rmdir");
+		return -1;
+	}
+	if (getcwd(buf, sizeof(buf)) != NULL || errno != ENOENT) {
+		fprintf(stderr, "getcwd in an unlinked dir should fail with ENOENT\n");
+		return -1;
+	}
+	return chdir("/");
+}
+
+int main(void)
+{
+	int ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0 || mkdir(SRC_DIR, 0755) < 0 ||
+	    mkdir(MNT_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+
+	if (test_across_mounts() < 0)
+		goto out;
+	if (test_small_buffer() < 0)
+		goto out;
+	if (test_unlinked_cwd() < 0)
+		goto out;
+
+	printf("getcwd test passed\n");
+	ret = 0;
+out:
+	chdir("/");
+	rmdir(MNT_DIR);
+	rmdir(SRC_DIR);
+	rmdir(TEST_DIR);
+	return ret;
+}
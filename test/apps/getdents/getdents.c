@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <dirent.h>
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/syscall.h>
+#include <sys/sysmacros.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/getdents_test"
+
+struct linux_dirent64 {
+	unsigned long d_ino;
+	long d_off;
+	unsigned short d_reclen;
+	unsigned char d_type;
+	char d_name[];
+};
+
+struct linux_dirent {
+	unsigned long d_ino;
+	long d_off;
+	unsigned short d_reclen;
+	char d_name[];
+};
+
+struct expected_entry {
+	const char *name;
+	unsigned char type;
+	int found;
+};
+
+static struct expected_entry test_dir_entries[] = {
+	{ ".", DT_DIR, 0 },   { "..", DT_DIR, 0 },   { "file", DT_REG, 0 },
+	{ "dir", DT_DIR, 0 }, { "fifo", DT_FIFO, 0 }, { "null", DT_CHR, 0 },
+	{ "link", DT_LNK, 0 },
+};
+
+#define NR_ENTRIES (sizeof(test_dir_entries) / sizeof(test_dir_entries[0]))
+
+static char buf[4096];
+
+static struct expected_entry *find_entry(const char *name)
+{
+	for (size_t i = 0; i < NR_ENTRIES; i++) {
+		if (strcmp(test_dir_entries[i].name, name) == 0)
+			return &test_dir_entries[i];
+	}
+	return NULL;
+}
+
+static int setup_test_dir(void)
+{
+	int fd;
+
+	CHECK(mkdir(TEST_DIR, 0755) == 0, "mkdir");
+	fd = open(TEST_DIR "/file", O_CREAT | O_WRONLY, 0644);
+	CHECK(fd >= 0, "create file");
+	CHECK(close(fd) == 0, "close");
+	CHECK(mkdir(TEST_DIR "/dir", 0755) == 0, "mkdir dir");
+	CHECK(mknod(TEST_DIR "/fifo", S_IFIFO | 0644, 0) == 0, "mknod fifo");
+	CHECK(mknod(TEST_DIR "/null", S_IFCHR | 0666, makedev(1, 3)) == 0,
+	      "mknod null");
+	CHECK(symlink("file", TEST_DIR "/link") == 0, "symlink");
+	return 0;
+}
+
+static int cleanup_test_dir(void)
+{
+	CHECK(unlink(TEST_DIR "/link") == 0, "unlink link");
+	CHECK(unlink(TEST_DIR "/null") == 0, "unlink null");
+	CHECK(unlink(TEST_DIR "/fifo") == 0, "unlink fifo");
+	CHECK(rmdir(TEST_DIR "/dir") == 0, "rmdir dir");
+	CHECK(unlink(TEST_DIR "/file") == 0, "unlink file");
+	CHECK(rmdir(TEST_DIR) == 0, "rmdir");
+	return 0;
+}
+
+static int test_getdents64(void)
+{
+	struct linux_dirent64 *dirent;
+	struct expected_entry *entry;
+	int fd, nr_read = 0;
+	long len, pos;
+
+	for (size_t i = 0; i < NR_ENTRIES; i++)
+		test_dir_entries[i].found = 0;
+
+	fd = open(TEST_DIR, O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open");
+	len = syscall(SYS_getdents64, fd, buf, sizeof(buf));
+	CHECK(len > 0, "getdents64");
+
+	for (pos = 0; pos < len; pos += dirent->d_reclen, nr_read++) {
+		dirent = (struct linux_dirent64 *)(buf + pos);
+		CHECK(dirent->d_reclen % 8 == 0, "align the record");
+
+		// The `.` and `..` entries come first.
+		if (nr_read == 0)
+			CHECK(strcmp(dirent->d_name, ".") == 0, "read `.` first");
+		if (nr_read == 1)
+			CHECK(strcmp(dirent->d_name, "..") == 0,
+			      "read `..` second");
+
+		entry = find_entry(dirent->d_name);
+		CHECK(entry != NULL && !entry->found, "find the entry");
+		CHECK(dirent->d_type == entry->type, "check the entry type");
+		entry->found = 1;
+	}
+	CHECK(nr_read == NR_ENTRIES, "read all the entries");
+
+	// All entries have been read.
+	CHECK(syscall(SYS_getdents64, fd, buf, sizeof(buf)) == 0,
+	      "getdents64 at the end");
+	CHECK(close(fd) == 0, "close");
+	return 0;
+}
+
+static int test_getdents(void)
+{
+	struct linux_dirent *dirent;
+	struct expected_entry *entry;
+	int fd, nr_read = 0;
+	long len, pos;
+	unsigned char type;
+
+	for (size_t i = 0; i < NR_ENTRIES; i++)
+		test_dir_entries[i].found = 0;
+
+	fd = open(TEST_DIR, O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open");
+	len = syscall(SYS_getdents, fd, buf, sizeof(buf));
+	CHECK(len > 0, "getdents");
+
+	for (pos = 0; pos < len; pos += dirent->d_reclen, nr_read++) {
+		dirent = (struct linux_dirent *)(buf + pos);
+		// The type is stored in the last byte of the record.
+		type = *(buf + pos + dirent->d_reclen - 1);
+
+		entry = find_entry(dirent->d_name);
+		CHECK(entry != NULL && !entry->found, "find the legacy entry");
+		CHECK(type == entry->type, "check the legacy entry type");
+		entry->found = 1;
+	}
+	CHECK(nr_read == NR_ENTRIES, "read all the legacy entries");
+
+	CHECK(close(fd) == 0, "close");
+	return 0;
+}
+
+static int test_small_buffer(void)
+{
+	int fd;
+
+	fd = open(TEST_DIR, O_RDONLY | O_DIRECTORY);
+	CHECK(fd >= 0, "open");
+	CHECK(syscall(SYS_getdents64, fd, buf, 8) < 0 && errno == EINVAL,
+	      "getdents64 with a small buffer");
+	CHECK(close(fd) == 0, "close");
+	return 0;
+}
+
+static int test_proc_symlinks(void)
+{
+	int found_self = 0, found_thread_self = 0;
+	struct dirent *dirent;
+	DIR *dir;
+
+	dir = opendir("/proc");
+	CHECK(dir != NULL, "opendir /proc");
+	while ((dirent = readdir(dir)) != NULL) {
+		if (strcmp(dirent->d_name, "self") == 0) {
+			CHECK(dirent->d_type == DT_LNK, "check /proc/self type");
+			found_self = 1;
+		}
+		if (strcmp(dirent->d_name, "thread-self") == 0) {
+			CHECK(dirent->d_type == DT_LNK,
+			      "check /proc/thread-self type");
+			found_thread_self = 1;
+		}
+	}
+	CHECK(found_self && found_thread_self, "find the symlinks in /proc");
+	CHECK(closedir(dir) == 0, "closedir");
+	return 0;
+}
+
+int main(void)
+{
+	CHECK(setup_test_dir() == 0, "set up the test directory");
+	CHECK(test_getdents64() == 0, "test getdents64");
+	CHECK(test_getdents() == 0, "test getdents");
+	CHECK(test_small_buffer() == 0, "test the small buffer");
+	CHECK(cleanup_test_dir() == 0, "clean up the test directory");
+	CHECK(test_proc_symlinks() == 0, "test the symlinks in /proc");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,31 @@
+/* SPDX-License-Identifier: MPL-2.0 */
+
+/*
+ * Checks shared by the tests that are written as a sequence of test functions
+ * returning 0 on success and -1 on failure.
+ *
+ *  - CHECK() returns -1 from the calling function and reports the message and
+ * the current errno if the condition is false.
+ *
+ *  - CHECK_ERROR() expects the call to fail with the specified errno.
+ */
+
+#ifndef __CHECK_H
+#define __CHECK_H
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+
+#define CHECK(cond, msg)                                                  \
+	do {                                                              \
+		if (!(cond)) {                                            \
+			fprintf(stderr, "%s failed: %s\n", msg,           \
+				strerror(errno));                         \
+			return -1;                                        \
+		}                                                         \
+	} while (0)
+
+#define CHECK_ERROR(call, err) CHECK((call) < 0 && errno == (err), #call)
+
+#endif /* __CHECK_H */
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/sysmacros.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/mknod_test"
+#define TEST_FIFO TEST_DIR "/fifo"
+#define TEST_FILE TEST_DIR "/file"
+#define TEST_NULL TEST_DIR "/null"
+
+static int test_fifo(void)
+{
+	char buf[16];
+	struct stat st;
+	int reader, writer;
+
+	CHECK(mknod(TEST_FIFO, S_IFIFO | 0666, 0) == 0, "mknod fifo");
+	CHECK(stat(TEST_FIFO, &st) == 0, "stat fifo");
+	CHECK(S_ISFIFO(st.st_mode), "fifo type");
+	CHECK((st.st_mode & 0777) == 0644, "fifo mode with umask");
+	CHECK_ERROR(mknod(TEST_FIFO, S_IFIFO | 0666, 0), EEXIST);
+
+	// A non-blocking writer cannot be opened without any readers
+	CHECK_ERROR(open(TEST_FIFO, O_WRONLY | O_NONBLOCK), ENXIO);
+
+	reader = open(TEST_FIFO, O_RDONLY | O_NONBLOCK);
+	CHECK(reader >= 0, "open fifo for reading");
+	writer = open(TEST_FIFO, O_WRONLY);
+	CHECK(writer >= 0, "open fifo for writing");
+
+	CHECK_ERROR(read(reader, buf, sizeof(buf)), EAGAIN);
+	CHECK(write(writer, "hello", 5) == 5, "write fifo");
+	CHECK(read(reader, buf, sizeof(buf)) == 5, "read fifo");
+	CHECK(memcmp(buf, "hello", 5) == 0, "fifo content");
+
+	// The reader sees EOF after the writer is closed
+	close(writer);
+	CHECK(read(reader, buf, sizeof(buf)) == 0, "read fifo at EOF");
+	close(reader);
+
+	CHECK(unlink(TEST_FIFO) == 0, "unlink fifo");
+	return 0;
+}
+
+static int test_file(void)
+{
+	struct stat st;
+
+	CHECK(mknod(TEST_FILE, 0640, 0) == 0, "mknod file");
+	CHECK(stat(TEST_FILE, &st) == 0, "stat file");
+	CHECK(S_ISREG(st.st_mode) && st.st_size == 0, "file type");
+	CHECK((st.st_mode & 0777) == 0640, "file mode");
+	CHECK(unlink(TEST_FILE) == 0, "unlink file");
+
+	CHECK_ERROR(mknod(TEST_DIR "/dir", S_IFDIR | 0755, 0), EPERM);
+	CHECK_ERROR(mknod(TEST_DIR "/none/file", S_IFIFO | 0644, 0), ENOENT);
+	return 0;
+}
+
+static int test_device(void)
+{
+	struct stat st;
+	int fd;
+
+	CHECK(mknod(TEST_NULL, S_IFCHR | 0666, makedev(1, 3)) == 0,
+	      "mknod char device");
+	CHECK(stat(TEST_NULL, &st) == 0, "stat char device");
+	CHECK(S_ISCHR(st.st_mode), "char device type");
+	CHECK(major(st.st_rdev) == 1 && minor(st.st_rdev) == 3, "device ID");
+
+	// The node works like /dev/null
+	fd = open(TEST_NULL, O_RDWR);
+	CHECK(fd >= 0, "open char device");
+	CHECK(write(fd, "hello", 5) == 5, "write char device");
+	CHECK(read(fd, &st, sizeof(st)) == 0, "read char device");
+	close(fd);
+
+	CHECK(unlink(TEST_NULL) == 0, "unlink char device");
+	return 0;
+}
+
+int main(void)
+{
+	int ret = 1;
+
+	umask(022);
+	if (mkdir(TEST_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+
+	if (test_fifo() < 0 || test_file() < 0 || test_device() < 0)
+		goto out;
+
+	printf("mknod test passed\n");
+	ret = 0;
+out:
+	unlink(TEST_NULL);
+	unlink(TEST_FILE);
+	unlink(TEST_FIFO);
+	rmdir(TEST_DIR);
+	return ret;
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <sys/personality.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+#define RANDOMIZE_VA_SPACE_PATH "/proc/sys/kernel/randomize_va_space"
+
+struct layout {
+	uintptr_t stack;
+	uintptr_t mmap;
+	uintptr_t brk;
+};
+
+// Reports the layout of the address space to the parent process via stdout.
+static int report_layout(void)
+{
+	struct layout layout;
+	int local;
+	void *addr;
+
+	addr = mmap(NULL, PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	if (addr == MAP_FAILED)
+		return 1;
+
+	layout.stack = (uintptr_t)&local;
+	layout.mmap = (uintptr_t)addr;
+	layout.brk = (uintptr_t)sbrk(0);
+	if (write(STDOUT_FILENO, &layout, sizeof(layout)) != sizeof(layout))
+		return 1;
+	return 0;
+}
+
+static int run_child(int no_randomize, struct layout *layout)
+{
+	int fds[2], status;
+	pid_t pid;
+
+	CHECK(pipe(fds) == 0, "pipe");
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0) {
+		close(fds[0]);
+		if (dup2(fds[1], STDOUT_FILENO) < 0)
+			_exit(1);
+		if (no_randomize && personality(ADDR_NO_RANDOMIZE) < 0)
+			_exit(1);
+		execl("/proc/self/exe", "aslr", "child", NULL);
+		_exit(1);
+	}
+
+	close(fds[1]);
+	CHECK(read(fds[0], layout, sizeof(*layout)) == sizeof(*layout),
+	      "read the layout");
+	close(fds[0]);
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "report the layout");
+	return 0;
+}
+
+static int same_layout(const struct layout *a, const struct layout *b)
+{
+	return a->stack == b->stack && a->mmap == b->mmap && a->brk == b->brk;
+}
+
+static int write_randomize_va_space(const char *value)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(RANDOMIZE_VA_SPACE_PATH, O_WRONLY);
+	if (fd < 0)
+		return -1;
+	len = write(fd, value, strlen(value));
+	close(fd);
+	return len < 0 ? -1 : 0;
+}
+
+static int read_randomize_va_space(char *buf, size_t size)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(RANDOMIZE_VA_SPACE_PATH, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, size - 1);
+	close(fd);
+	if (len <= 0)
+		return -1;
+	buf[len] = '\0';
+	return 0;
+}
+
+static int test_personality(void)
+{
+	int old_persona;
+
+	old_persona = personality(0xffffffff);
+	CHECK(old_persona >= 0, "query the personality");
+	CHECK(personality(old_persona | ADDR_NO_RANDOMIZE) == old_persona,
+	      "set ADDR_NO_RANDOMIZE");
+	CHECK(personality(0xffffffff) == (old_persona | ADDR_NO_RANDOMIZE),
+	      "query the new personality");
+	CHECK(personality(old_persona) == (old_persona | ADDR_NO_RANDOMIZE),
+	      "restore the personality");
+	return 0;
+}
+
+static int test_randomized(void)
+{
+	struct layout first, second;
+
+	CHECK(run_child(0, &first) == 0, "run the first child");
+	CHECK(run_child(0, &second) == 0, "run the second child");
+	CHECK(first.stack % 16 == second.stack % 16, "check the stack alignment");
+	CHECK(first.stack != second.stack, "randomize the stack");
+	CHECK(first.mmap != second.mmap, "randomize the mmap base");
+	CHECK(first.mmap % PAGE_SIZE == 0 && second.mmap % PAGE_SIZE == 0,
+	      "check the mmap alignment");
+	CHECK(first.brk != second.brk, "randomize the brk");
+	return 0;
+}
+
+static int test_no_randomize(void)
+{
+	struct layout first, second;
+
+	CHECK(run_child(1, &first) == 0, "run the first child");
+	CHECK(run_child(1, &second) == 0, "run the second child");
+	CHECK(same_layout(&first, &second), "disable the randomization");
+	return 0;
+}
+
+static int test_randomize_va_space(void)
+{
+	struct layout level0[2], level1[2];
+	char old_value[16];
+
+	CHECK(read_randomize_va_space(old_value, sizeof(old_value)) == 0,
+	      "read randomize_va_space");
+	CHECK(strcmp(old_value, "2\n") == 0, "check the default level");
+	CHECK_ERROR(write_randomize_va_space("invalid"), EINVAL);
+
+	CHECK(write_randomize_va_space("0") == 0, "disable the randomization");
+	CHECK(run_child(0, &level0[0]) == 0,
+	      "run the first child with level 0");
+	CHECK(run_child(0, &level0[1]) == 0,
+	      "run the second child with level 0");
+	CHECK(write_randomize_va_space("1") == 0,
+	      "randomize the stack and the mmap base only");
+	CHECK(run_child(0, &level1[0]) == 0,
+	      "run the first child with level 1");
+	CHECK(run_child(0, &level1[1]) == 0,
+	      "run the second child with level 1");
+	CHECK(write_randomize_va_space(old_value) == 0,
+	      "restore randomize_va_space");
+
+	CHECK(same_layout(&level0[0], &level0[1]),
+	      "check the layouts with level 0");
+	CHECK(level1[0].stack != level1[1].stack,
+	      "randomize the stack with level 1");
+	CHECK(level1[0].mmap != level1[1].mmap,
+	      "randomize the mmap base with level 1");
+	// The heap base does not depend on where the executable is loaded,
+	// so it stays fixed when the heap is not randomized.
+	CHECK(level1[0].brk == level1[1].brk, "keep the brk with level 1");
+	return 0;
+}
+
+int main(int argc, char *argv[])
+{
+	if (argc > 1 && strcmp(argv[1], "child") == 0)
+		return report_layout();
+
+	CHECK(test_personality() == 0, "test the personality");
+	CHECK(test_randomized() == 0, "test the randomized layout");
+	CHECK(test_no_randomize() == 0, "test ADDR_NO_RANDOMIZE");
+	CHECK(test_randomize_va_space() == 0, "test randomize_va_space");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+#define HEAP_PAGES 16
+
+static char *do_brk(char *addr)
+{
+	return (char *)syscall(SYS_brk, addr);
+}
+
+// Touches the address in a child process and returns the signal that kills the child
+static int touch_in_child(volatile char *addr)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		*addr = 1;
+		_exit(0);
+	}
+	if (pid < 0 || waitpid(pid, &status, 0) != pid)
+		return -1;
+
+	return WIFSIGNALED(status) ? WTERMSIG(status) : 0;
+}
+
+int main(void)
+{
+	char *start, *end;
+	int i;
+
+	start = do_brk(NULL);
+	end = start + HEAP_PAGES * PAGE_SIZE;
+
+	// Grow the heap and fill it
+	CHECK(do_brk(end) == end, "grow heap");
+	memset(start, 0xff, end - start);
+
+	// Shrink the heap to a single page
+	CHECK(do_brk(start + PAGE_SIZE) == start + PAGE_SIZE, "shrink heap");
+	CHECK(do_brk(NULL) == start + PAGE_SIZE, "query heap end");
+	CHECK(touch_in_child(start + 8 * PAGE_SIZE) == SIGSEGV,
+	      "access above the heap end");
+
+	// The pages above the heap end are reclaimed, so they are zeroed once the
+	// heap grows again
+	CHECK(do_brk(end) == end, "grow heap again");
+	for (i = 2; i < HEAP_PAGES; ++i)
+		CHECK(start[i * PAGE_SIZE] == 0, "read regrown heap");
+
+	CHECK(do_brk(start) == start, "restore heap end");
+
+	printf("Test passed\n");
+	return 0;
+}
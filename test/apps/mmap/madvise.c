@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+#define FILE_PATH "/tmp/madvise_test_file"
+
+static int check_filled(char *addr, size_t size, char c)
+{
+	for (size_t i = 0; i < size; i++)
+		if (addr[i] != c)
+			return -1;
+	return 0;
+}
+
+static int test_errors(void)
+{
+	char *addr;
+
+	addr = mmap(NULL, 3 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	CHECK(munmap(addr + PAGE_SIZE, PAGE_SIZE) == 0, "munmap");
+
+	CHECK_ERROR(madvise(addr + 1, PAGE_SIZE, MADV_DONTNEED), EINVAL);
+	CHECK_ERROR(madvise(addr, 3 * PAGE_SIZE, MADV_DONTNEED), ENOMEM);
+	CHECK_ERROR(madvise(addr, 3 * PAGE_SIZE, MADV_WILLNEED), ENOMEM);
+	CHECK_ERROR(madvise(addr, 3 * PAGE_SIZE, MADV_NORMAL), ENOMEM);
+	CHECK_ERROR(madvise(addr + PAGE_SIZE, 1, MADV_SEQUENTIAL), ENOMEM);
+	CHECK(madvise(addr, 0, MADV_DONTNEED) == 0, "madvise an empty range");
+	CHECK_ERROR(madvise(addr, PAGE_SIZE, 999), EINVAL);
+	CHECK_ERROR(madvise(addr, 3 * PAGE_SIZE, MADV_DONTDUMP), ENOMEM);
+
+	CHECK(munmap(addr, 3 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_hints(void)
+{
+	char *addr;
+
+	addr = mmap(NULL, 2 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	memset(addr, 'a', 2 * PAGE_SIZE);
+
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_NORMAL) == 0, "MADV_NORMAL");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_RANDOM) == 0, "MADV_RANDOM");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_SEQUENTIAL) == 0,
+	      "MADV_SEQUENTIAL");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_WILLNEED) == 0,
+	      "MADV_WILLNEED");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_DONTFORK) == 0,
+	      "MADV_DONTFORK");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_DOFORK) == 0, "MADV_DOFORK");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_DONTDUMP) == 0,
+	      "MADV_DONTDUMP");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_DODUMP) == 0, "MADV_DODUMP");
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_COLD) == 0, "MADV_COLD");
+	CHECK(check_filled(addr, 2 * PAGE_SIZE, 'a') == 0,
+	      "check the pages after the hints");
+
+	// The pages advised with `MADV_FREE` may or may not be freed.
+	CHECK(madvise(addr, 2 * PAGE_SIZE, MADV_FREE) == 0, "MADV_FREE");
+
+	CHECK(munmap(addr, 2 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_private_anonymous(void)
+{
+	char *addr;
+	int status;
+	pid_t pid;
+
+	addr = mmap(NULL, 3 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	memset(addr, 'a', 3 * PAGE_SIZE);
+
+	// Only the pages in the range are dropped, and the mapping is kept.
+	CHECK(madvise(addr + PAGE_SIZE, PAGE_SIZE, MADV_DONTNEED) == 0,
+	      "MADV_DONTNEED");
+	CHECK(check_filled(addr, PAGE_SIZE, 'a') == 0, "check the first page");
+	CHECK(check_filled(addr + PAGE_SIZE, PAGE_SIZE, 0) == 0,
+	      "check the dropped page");
+	CHECK(check_filled(addr + 2 * PAGE_SIZE, PAGE_SIZE, 'a') == 0,
+	      "check the last page");
+	memset(addr + PAGE_SIZE, 'b', PAGE_SIZE);
+	CHECK(check_filled(addr + PAGE_SIZE, PAGE_SIZE, 'b') == 0,
+	      "write the dropped page");
+
+	// The pages shared with the parent process after fork read back as zero, too.
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0) {
+		if (madvise(addr, 3 * PAGE_SIZE, MADV_DONTNEED) < 0 ||
+		    check_filled(addr, 3 * PAGE_SIZE, 0) < 0)
+			_exit(1);
+		_exit(0);
+	}
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "MADV_DONTNEED in the child process");
+	CHECK(check_filled(addr, PAGE_SIZE, 'a') == 0,
+	      "check the pages of the parent process");
+
+	CHECK(munmap(addr, 3 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_shared_anonymous(void)
+{
+	char *addr;
+
+	addr = mmap(NULL, PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_SHARED | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	memset(addr, 'a', PAGE_SIZE);
+
+	CHECK(madvise(addr, PAGE_SIZE, MADV_DONTNEED) == 0, "MADV_DONTNEED");
+	CHECK(check_filled(addr, PAGE_SIZE, 'a') == 0,
+	      "check the shared page");
+
+	CHECK(munmap(addr, PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_file(void)
+{
+	char *private_addr, *shared_addr, buf[PAGE_SIZE];
+	int fd;
+
+	fd = open(FILE_PATH, O_RDWR | O_CREAT | O_TRUNC, 0644);
+	CHECK(fd >= 0, "open");
+	memset(buf, 'f', PAGE_SIZE);
+	CHECK(write(fd, buf, PAGE_SIZE) == PAGE_SIZE, "write");
+
+	// The private copy is dropped, and the file contents are read again.
+	private_addr = mmap(NULL, PAGE_SIZE, PROT_READ | PROT_WRITE,
+			    MAP_PRIVATE, fd, 0);
+	CHECK(private_addr != MAP_FAILED, "mmap the private mapping");
+	memset(private_addr, 'p', PAGE_SIZE);
+	CHECK(madvise(private_addr, PAGE_SIZE, MADV_DONTNEED) == 0,
+	      "MADV_DONTNEED on the private mapping");
+	CHECK(check_filled(private_addr, PAGE_SIZE, 'f') == 0,
+	      "check the private mapping");
+
+	// The dirty data of the shared mapping is not discarded.
+	shared_addr = mmap(NULL, PAGE_SIZE, PROT_READ | PROT_WRITE,
+			   MAP_SHARED, fd, 0);
+	CHECK(shared_addr != MAP_FAILED, "mmap the shared mapping");
+	CHECK(madvise(shared_addr, PAGE_SIZE, MADV_WILLNEED) == 0,
+	      "MADV_WILLNEED on the shared mapping");
+	memset(shared_addr, 's', PAGE_SIZE);
+	CHECK(madvise(shared_addr, PAGE_SIZE, MADV_DONTNEED) == 0,
+	      "MADV_DONTNEED on the shared mapping");
+	CHECK(check_filled(shared_addr, PAGE_SIZE, 's') == 0,
+	      "check the shared mapping");
+	CHECK(pread(fd, buf, PAGE_SIZE, 0) == PAGE_SIZE, "pread");
+	CHECK(check_filled(buf, PAGE_SIZE, 's') == 0, "check the file");
+
+	CHECK(munmap(private_addr, PAGE_SIZE) == 0, "munmap");
+	CHECK(munmap(shared_addr, PAGE_SIZE) == 0, "munmap");
+	CHECK(close(fd) == 0, "close");
+	CHECK(unlink(FILE_PATH) == 0, "unlink");
+	return 0;
+}
+
+int main(void)
+{
+	CHECK(test_errors() == 0, "test the errors");
+	CHECK(test_hints() == 0, "test the hints");
+	CHECK(test_private_anonymous() == 0,
+	      "test the private anonymous mapping");
+	CHECK(test_shared_anonymous() == 0, "test the shared anonymous mapping");
+	CHECK(test_file() == 0, "test the file mappings");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+#define FILE_PATH "/tmp/mremap_test_file"
+
+#define CHECK_MAP_ERROR(call, err) CHECK((call) == MAP_FAILED && errno == (err), #call)
+
+static void fill_pages(char *addr, int nr_pages)
+{
+	for (int i = 0; i < nr_pages; i++)
+		memset(addr + i * PAGE_SIZE, 'a' + i, PAGE_SIZE);
+}
+
+static int check_pages(char *addr, int nr_pages)
+{
+	for (int i = 0; i < nr_pages; i++)
+		for (int j = 0; j < PAGE_SIZE; j++)
+			if (addr[i * PAGE_SIZE + j] != 'a' + i)
+				return -1;
+	return 0;
+}
+
+static int check_zero(char *addr, size_t size)
+{
+	for (size_t i = 0; i < size; i++)
+		if (addr[i] != 0)
+			return -1;
+	return 0;
+}
+
+static int test_errors(void)
+{
+	char *addr;
+
+	addr = mmap(NULL, 2 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+
+	CHECK_MAP_ERROR(mremap(addr + 1, PAGE_SIZE, PAGE_SIZE, 0), EINVAL);
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, 0, 0), EINVAL);
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, PAGE_SIZE, MREMAP_FIXED,
+			       addr + 4 * PAGE_SIZE),
+			EINVAL);
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, PAGE_SIZE,
+			       MREMAP_MAYMOVE | MREMAP_FIXED, addr + 1),
+			EINVAL);
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, 2 * PAGE_SIZE,
+			       MREMAP_MAYMOVE | MREMAP_FIXED, addr),
+			EINVAL);
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, PAGE_SIZE, 0x80), EINVAL);
+
+	CHECK(munmap(addr, 2 * PAGE_SIZE) == 0, "munmap");
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, PAGE_SIZE, 0), EFAULT);
+	return 0;
+}
+
+static int test_shrink(void)
+{
+	char *addr, *new_addr;
+
+	addr = mmap(NULL, 4 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	fill_pages(addr, 4);
+
+	new_addr = mremap(addr, 4 * PAGE_SIZE, 2 * PAGE_SIZE, 0);
+	CHECK(new_addr == addr, "shrink in place");
+	CHECK(check_pages(addr, 2) == 0, "check the remaining pages");
+
+	// The tail is unmapped, so it cannot be remapped.
+	CHECK_MAP_ERROR(mremap(addr + 2 * PAGE_SIZE, PAGE_SIZE, PAGE_SIZE, 0),
+			EFAULT);
+	CHECK(munmap(addr, 2 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_grow_in_place(void)
+{
+	char *addr, *new_addr;
+
+	// Reserve the pages after the mapping so that they are free.
+	addr = mmap(NULL, 4 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	CHECK(munmap(addr + 2 * PAGE_SIZE, 2 * PAGE_SIZE) == 0, "munmap");
+	fill_pages(addr, 2);
+
+	new_addr = mremap(addr, 2 * PAGE_SIZE, 4 * PAGE_SIZE, 0);
+	CHECK(new_addr == addr, "grow in place");
+	CHECK(check_pages(addr, 2) == 0, "check the old pages");
+	CHECK(check_zero(addr + 2 * PAGE_SIZE, 2 * PAGE_SIZE) == 0,
+	      "check the new pages");
+	fill_pages(addr, 4);
+	CHECK(check_pages(addr, 4) == 0, "write the new pages");
+	CHECK(munmap(addr, 4 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_move(void)
+{
+	char *addr, *new_addr;
+
+	// The mapping cannot grow in place because the next page is occupied.
+	addr = mmap(NULL, 3 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	CHECK(mprotect(addr + 2 * PAGE_SIZE, PAGE_SIZE, PROT_READ) == 0,
+	      "mprotect");
+	fill_pages(addr, 2);
+
+	CHECK_MAP_ERROR(mremap(addr, 2 * PAGE_SIZE, 4 * PAGE_SIZE, 0), ENOMEM);
+
+	new_addr = mremap(addr, 2 * PAGE_SIZE, 4 * PAGE_SIZE, MREMAP_MAYMOVE);
+	CHECK(new_addr != MAP_FAILED && new_addr != addr, "move");
+	CHECK(check_pages(new_addr, 2) == 0, "check the moved pages");
+	CHECK(check_zero(new_addr + 2 * PAGE_SIZE, 2 * PAGE_SIZE) == 0,
+	      "check the new pages");
+	fill_pages(new_addr, 4);
+
+	// The old pages are unmapped.
+	CHECK_MAP_ERROR(mremap(addr, PAGE_SIZE, PAGE_SIZE, 0), EFAULT);
+	CHECK(munmap(addr + 2 * PAGE_SIZE, PAGE_SIZE) == 0, "munmap");
+	CHECK(munmap(new_addr, 4 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_move_fixed(void)
+{
+	char *addr, *dest, *new_addr;
+
+	addr = mmap(NULL, 2 * PAGE_SIZE, PROT_READ,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	CHECK(mprotect(addr, 2 * PAGE_SIZE, PROT_READ | PROT_WRITE) == 0,
+	      "mprotect");
+	fill_pages(addr, 2);
+	CHECK(mprotect(addr, 2 * PAGE_SIZE, PROT_READ) == 0, "mprotect");
+
+	// The destination is replaced.
+	dest = mmap(NULL, 3 * PAGE_SIZE, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(dest != MAP_FAILED, "mmap the destination");
+	memset(dest, 'z', 3 * PAGE_SIZE);
+
+	new_addr = mremap(addr, 2 * PAGE_SIZE, 2 * PAGE_SIZE,
+			  MREMAP_MAYMOVE | MREMAP_FIXED, dest);
+	CHECK(new_addr == dest, "move to the fixed address");
+	CHECK(check_pages(dest, 2) == 0, "check the moved pages");
+	CHECK(dest[2 * PAGE_SIZE] == 'z', "check the rest of the destination");
+
+	// The protection is kept.
+	CHECK(mprotect(dest, 2 * PAGE_SIZE, PROT_READ | PROT_WRITE) == 0,
+	      "mprotect the moved pages");
+	CHECK(munmap(dest, 3 * PAGE_SIZE) == 0, "munmap");
+	return 0;
+}
+
+static int test_shared_file(void)
+{
+	char *addr, *new_addr, buf[PAGE_SIZE];
+	int fd;
+
+	fd = open(FILE_PATH, O_RDWR | O_CREAT | O_TRUNC, 0644);
+	CHECK(fd >= 0, "open");
+	CHECK(ftruncate(fd, 4 * PAGE_SIZE) == 0, "ftruncate");
+
+	addr = mmap(NULL, 2 * PAGE_SIZE, PROT_READ | PROT_WRITE, MAP_SHARED,
+		    fd, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	fill_pages(addr, 2);
+
+	new_addr = mremap(addr, 2 * PAGE_SIZE, 4 * PAGE_SIZE, MREMAP_MAYMOVE);
+	CHECK(new_addr != MAP_FAILED, "mremap");
+	CHECK(check_pages(new_addr, 2) == 0, "check the moved pages");
+
+	// The new pages map the rest of the file.
+	memset(new_addr + 3 * PAGE_SIZE, 'd', PAGE_SIZE);
+	CHECK(pread(fd, buf, PAGE_SIZE, 3 * PAGE_SIZE) == PAGE_SIZE, "pread");
+	CHECK(buf[0] == 'd' && buf[PAGE_SIZE - 1] == 'd', "check the file");
+
+	CHECK(munmap(new_addr, 4 * PAGE_SIZE) == 0, "munmap");
+	CHECK(close(fd) == 0, "close");
+	CHECK(unlink(FILE_PATH) == 0, "unlink");
+	return 0;
+}
+
+int main(void)
+{
+	CHECK(test_errors() == 0, "test the errors");
+	CHECK(test_shrink() == 0, "test shrinking");
+	CHECK(test_grow_in_place() == 0, "test growing in place");
+	CHECK(test_move() == 0, "test moving");
+	CHECK(test_move_fixed() == 0, "test moving to a fixed address");
+	CHECK(test_shared_file() == 0, "test the shared file mapping");
+
+	printf("Test passed\n");
+	return 0;
+}
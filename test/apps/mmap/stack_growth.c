@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/resource.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define MB (1024 * 1024)
+#define FRAME_SIZE (64 * 1024)
+
+// Uses about `depth * FRAME_SIZE` bytes of the stack.
+static int recurse(int depth)
+{
+	volatile char frame[FRAME_SIZE];
+
+	memset((char *)frame, depth & 0xff, FRAME_SIZE);
+	if (depth > 0 && recurse(depth - 1) < 0)
+		return -1;
+
+	// Check that the frame is not corrupted by the deeper calls.
+	for (int i = 0; i < FRAME_SIZE; i += 1024)
+		if (frame[i] != (char)(depth & 0xff))
+			return -1;
+	return 0;
+}
+
+static int set_stack_limit(rlim_t limit)
+{
+	struct rlimit rlimit;
+
+	CHECK(getrlimit(RLIMIT_STACK, &rlimit) == 0, "getrlimit");
+	rlimit.rlim_cur = limit;
+	CHECK(setrlimit(RLIMIT_STACK, &rlimit) == 0, "setrlimit");
+	return 0;
+}
+
+int main(void)
+{
+	int status;
+	pid_t pid;
+
+	// A stack overflowing the limit is killed by `SIGSEGV`.
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0) {
+		if (set_stack_limit(8 * MB) < 0)
+			_exit(1);
+		recurse(16 * MB / FRAME_SIZE);
+		_exit(0);
+	}
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFSIGNALED(status) && WTERMSIG(status) == SIGSEGV,
+	      "overflow the stack");
+
+	// The stack grows on demand within the limit.
+	CHECK(set_stack_limit(32 * MB) == 0, "raise the stack limit");
+	CHECK(recurse(16 * MB / FRAME_SIZE) == 0, "grow the stack to 16 MiB");
+	CHECK(recurse(24 * MB / FRAME_SIZE) == 0, "grow the stack to 24 MiB");
+
+	// The grown stack is inherited by the child process.
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0)
+		_exit(recurse(24 * MB / FRAME_SIZE) < 0);
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "use the grown stack in the child process");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <sys/sysmacros.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define MNT_DIR "/tmp/devtmpfs_test"
+#define NODE_NAME "devtmpfs_test_node"
+
+static int check_char_device(const char *path, unsigned int major_id,
+			     unsigned int minor_id)
+{
+	struct stat st;
+
+	CHECK(stat(path, &st) == 0, path);
+	CHECK(S_ISCHR(st.st_mode), path);
+	CHECK(major(st.st_rdev) == major_id && minor(st.st_rdev) == minor_id,
+	      path);
+	return 0;
+}
+
+static int test_device_nodes(void)
+{
+	char buf[16];
+	int fd;
+
+	if (check_char_device(MNT_DIR "/null", 1, 3) < 0 ||
+	    check_char_device(MNT_DIR "/zero", 1, 5) < 0 ||
+	    check_char_device(MNT_DIR "/full", 1, 7) < 0 ||
+	    check_char_device(MNT_DIR "/random", 1, 8) < 0 ||
+	    check_char_device(MNT_DIR "/urandom", 1, 9) < 0 ||
+	    check_char_device(MNT_DIR "/tty", 5, 0) < 0)
+		return -1;
+
+	fd = open(MNT_DIR "/full", O_RDWR);
+	CHECK(fd >= 0, "open full");
+	CHECK(read(fd, buf, sizeof(buf)) == sizeof(buf) && buf[0] == 0,
+	      "read full");
+	CHECK_ERROR(write(fd, buf, sizeof(buf)), ENOSPC);
+	close(fd);
+	return 0;
+}
+
+static int test_shared_instance(void)
+{
+	struct stat st;
+
+	// All the mounts of devtmpfs share the same nodes
+	CHECK(mknod(MNT_DIR "/" NODE_NAME, S_IFCHR | 0666, makedev(1, 3)) == 0,
+	      "mknod");
+	CHECK(stat("/dev/" NODE_NAME, &st) == 0, "stat node in /dev");
+	CHECK(unlink("/dev/" NODE_NAME) == 0, "unlink node in /dev");
+	CHECK_ERROR(stat(MNT_DIR "/" NODE_NAME, &st), ENOENT);
+	return 0;
+}
+
+int main(void)
+{
+	int ret = 1;
+
+	if (mkdir(MNT_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	if (mount("devtmpfs", MNT_DIR, "devtmpfs", 0, NULL) < 0) {
+		perror("mount");
+		goto out;
+	}
+
+	if (test_device_nodes() < 0 || test_shared_instance() < 0) {
+		umount(MNT_DIR);
+		goto out;
+	}
+	umount(MNT_DIR);
+
+	printf("Test passed\n");
+	ret = 0;
+out:
+	rmdir(MNT_DIR);
+	return ret;
+}
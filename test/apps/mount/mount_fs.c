@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <linux/capability.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <sys/syscall.h>
+#include <sys/sysmacros.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define MNT_DIR "/tmp/mount_fs_test"
+#define MNT_FILE MNT_DIR "/file"
+#define MNT_SCRIPT MNT_DIR "/script"
+#define MNT_NULL MNT_DIR "/null"
+
+static int test_tmpfs(void)
+{
+	struct stat st;
+	int fd;
+
+	CHECK(mount("none", MNT_DIR, "tmpfs", 0, NULL) == 0, "mount tmpfs");
+	fd = open(MNT_FILE, O_CREAT | O_WRONLY, 0644);
+	CHECK(fd >= 0, "create file on tmpfs");
+	close(fd);
+	CHECK(stat(MNT_FILE, &st) == 0, "stat file on tmpfs");
+	CHECK(umount(MNT_DIR) == 0, "umount tmpfs");
+
+	// The file is gone with the filesystem
+	CHECK_ERROR(stat(MNT_FILE, &st), ENOENT);
+	return 0;
+}
+
+static int test_proc(void)
+{
+	struct stat st;
+
+	CHECK(mount("proc", MNT_DIR, "proc", 0, NULL) == 0, "mount proc");
+	CHECK(stat(MNT_DIR "/meminfo", &st) == 0, "stat meminfo");
+	CHECK(stat(MNT_DIR "/self", &st) == 0, "stat self");
+	CHECK(umount(MNT_DIR) == 0, "umount proc");
+	return 0;
+}
+
+static int test_unknown_type(void)
+{
+	CHECK_ERROR(mount("none", MNT_DIR, "nosuchfs", 0, NULL), ENODEV);
+	return 0;
+}
+
+static int test_noexec(void)
+{
+	char *argv[] = { MNT_SCRIPT, NULL };
+	int fd;
+
+	CHECK(mount("none", MNT_DIR, "ramfs", MS_NOEXEC, NULL) == 0,
+	      "mount noexec ramfs");
+	fd = open(MNT_SCRIPT, O_CREAT | O_WRONLY, 0755);
+	CHECK(fd >= 0, "create script");
+	CHECK(write(fd, "#!/bin/sh\n", 10) == 10, "write script");
+	close(fd);
+
+	CHECK_ERROR(execve(MNT_SCRIPT, argv, NULL), EACCES);
+	CHECK(umount(MNT_DIR) == 0, "umount noexec ramfs");
+	return 0;
+}
+
+static int test_nodev(void)
+{
+	CHECK(mount("none", MNT_DIR, "ramfs", MS_NODEV, NULL) == 0,
+	      "mount nodev ramfs");
+	CHECK(mknod(MNT_NULL, S_IFCHR | 0666, makedev(1, 3)) == 0,
+	      "mknod char device");
+
+	// The device node can be created, but not be opened
+	CHECK_ERROR(open(MNT_NULL, O_RDWR), EACCES);
+	CHECK(umount(MNT_DIR) == 0, "umount nodev ramfs");
+	return 0;
+}
+
+static int drop_caps_and_mount(void)
+{
+	struct __user_cap_header_struct header;
+	struct __user_cap_data_struct data[2];
+
+	memset(&header, 0, sizeof(header));
+	memset(&data, 0, sizeof(data));
+	header.version = _LINUX_CAPABILITY_VERSION_3;
+	CHECK(syscall(SYS_capset, &header, &data) == 0, "capset");
+
+	CHECK_ERROR(mount("none", MNT_DIR, "tmpfs", 0, NULL), EPERM);
+	return 0;
+}
+
+static int test_no_cap(void)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0)
+		_exit(drop_caps_and_mount() == 0 ? 0 : 1);
+
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "mount without CAP_SYS_ADMIN");
+	return 0;
+}
+
+int main(void)
+{
+	int ret = 1;
+
+	if (mkdir(MNT_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+
+	if (test_tmpfs() < 0 || test_proc() < 0 || test_unknown_type() < 0 ||
+	    test_noexec() < 0 || test_nodev() < 0 || test_no_cap() < 0) {
+		umount(MNT_DIR);
+		goto out;
+	}
+
+	printf("Test passed\n");
+	ret = 0;
+out:
+	rmdir(MNT_DIR);
+	return ret;
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <sys/vfs.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define MNT_DIR "/tmp/tmpfs_size_test"
+#define MNT_FILE MNT_DIR "/file"
+#define PAGE_SIZE 4096
+#define MAX_PAGES 16
+#define TMPFS_MAGIC 0x01021994
+
+static int check_free_blocks(unsigned long expected)
+{
+	struct statfs st;
+
+	CHECK(statfs(MNT_DIR, &st) == 0, "statfs");
+	CHECK(st.f_type == TMPFS_MAGIC, "tmpfs magic");
+	CHECK(st.f_bsize == PAGE_SIZE, "block size");
+	CHECK(st.f_blocks == MAX_PAGES, "total blocks");
+	CHECK(st.f_bfree == expected && st.f_bavail == expected,
+	      "free blocks");
+	return 0;
+}
+
+static int test_size_limit(void)
+{
+	char buf[PAGE_SIZE];
+	int fd, i;
+
+	memset(buf, 'a', sizeof(buf));
+	if (check_free_blocks(MAX_PAGES) < 0)
+		return -1;
+
+	fd = open(MNT_FILE, O_CREAT | O_WRONLY, 0644);
+	CHECK(fd >= 0, "create file");
+	for (i = 0; i < MAX_PAGES; i++)
+		CHECK(write(fd, buf, sizeof(buf)) == sizeof(buf), "write");
+	if (check_free_blocks(0) < 0)
+		return -1;
+
+	// The fs is full
+	CHECK_ERROR(write(fd, buf, sizeof(buf)), ENOSPC);
+	close(fd);
+
+	// The space is given back once the file is removed
+	CHECK(unlink(MNT_FILE) == 0, "unlink");
+	if (check_free_blocks(MAX_PAGES) < 0)
+		return -1;
+	return 0;
+}
+
+static int test_invalid_size(void)
+{
+	CHECK_ERROR(mount("none", MNT_DIR, "tmpfs", 0, "size=abc"), EINVAL);
+	return 0;
+}
+
+int main(void)
+{
+	int ret = 1;
+
+	if (mkdir(MNT_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+
+	if (test_invalid_size() < 0)
+		goto out;
+
+	if (mount("none", MNT_DIR, "tmpfs", 0, "mode=755,size=64k") < 0) {
+		perror("mount");
+		goto out;
+	}
+	if (test_size_limit() < 0) {
+		umount(MNT_DIR);
+		goto out;
+	}
+	umount(MNT_DIR);
+
+	printf("Test passed\n");
+	ret = 0;
+out:
+	rmdir(MNT_DIR);
+	return ret;
+}
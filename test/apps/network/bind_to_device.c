@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "test.h"
+
+static int sk_tcp;
+static int sk_udp;
+
+FN_SETUP(sockets)
+{
+	sk_tcp = CHECK(socket(AF_INET, SOCK_STREAM, 0));
+	sk_udp = CHECK(socket(AF_INET, SOCK_DGRAM, 0));
+}
+END_SETUP()
+
+FN_TEST(unbound)
+{
+	char name[16];
+	socklen_t len = sizeof(name);
+
+	TEST_RES(getsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, name, &len),
+		 len == 0);
+	len = sizeof(name);
+	TEST_RES(getsockopt(sk_udp, SOL_SOCKET, SO_BINDTODEVICE, name, &len),
+		 len == 0);
+}
+END_TEST()
+
+FN_TEST(bind_and_clear)
+{
+	char name[16];
+	socklen_t len = sizeof(name);
+
+	TEST_SUCC(setsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, "lo", 3));
+	TEST_RES(getsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, name, &len),
+		 len == 3 && strcmp(name, "lo") == 0);
+
+	len = 1;
+	TEST_ERRNO(getsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, name, &len),
+		   EINVAL);
+
+	TEST_SUCC(setsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, "", 1));
+	len = sizeof(name);
+	TEST_RES(getsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, name, &len),
+		 len == 0);
+}
+END_TEST()
+
+FN_TEST(invalid_device)
+{
+	TEST_ERRNO(setsockopt(sk_tcp, SOL_SOCKET, SO_BINDTODEVICE, "nodev0", 7),
+		   ENODEV);
+	TEST_ERRNO(setsockopt(sk_udp, SOL_SOCKET, SO_BINDTODEVICE, "nodev0", 7),
+		   ENODEV);
+}
+END_TEST()
+
+FN_TEST(udp_on_loopback)
+{
+	struct sockaddr_in addr;
+	socklen_t addrlen = sizeof(addr);
+	char buf[8];
+	int sk_recv;
+
+	sk_recv = TEST_SUCC(socket(AF_INET, SOCK_DGRAM, 0));
+	memset(&addr, 0, sizeof(addr));
+	addr.sin_family = AF_INET;
+	addr.sin_addr.s_addr = inet_addr("127.0.0.1");
+	TEST_SUCC(bind(sk_recv, (struct sockaddr *)&addr, sizeof(addr)));
+	TEST_SUCC(getsockname(sk_recv, (struct sockaddr *)&addr, &addrlen));
+
+	// The socket is bound to the address of the loopback device implicitly.
+	TEST_SUCC(setsockopt(sk_udp, SOL_SOCKET, SO_BINDTODEVICE, "lo", 3));
+	TEST_RES(sendto(sk_udp, "hello", 5, 0, (struct sockaddr *)&addr,
+			sizeof(addr)),
+		 _ret == 5);
+	TEST_RES(recv(sk_recv, buf, sizeof(buf), 0),
+		 _ret == 5 && memcmp(buf, "hello", 5) == 0);
+
+	TEST_SUCC(close(sk_recv));
+}
+END_TEST()
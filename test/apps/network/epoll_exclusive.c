@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/epoll.h>
+#include <sys/socket.h>
+#include <sys/wait.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define NR_WAITERS 4
+#define WAIT_MS 1000
+
+static struct sockaddr_in sk_addr;
+static int sk_listen;
+
+FN_SETUP(listen)
+{
+	sk_addr.sin_family = AF_INET;
+	sk_addr.sin_port = htons(0x123c);
+	CHECK(inet_aton("127.0.0.1", &sk_addr.sin_addr));
+
+	sk_listen = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(bind(sk_listen, (struct sockaddr *)&sk_addr, sizeof(sk_addr)));
+	CHECK(listen(sk_listen, NR_WAITERS));
+}
+END_SETUP()
+
+FN_TEST(invalid_exclusive)
+{
+	struct epoll_event ev = { .events = EPOLLIN | EPOLLEXCLUSIVE };
+	int epfd, epfd2;
+
+	epfd = TEST_SUCC(epoll_create1(0));
+	epfd2 = TEST_SUCC(epoll_create1(0));
+
+	// EPOLLEXCLUSIVE cannot be used with EPOLLONESHOT
+	ev.events |= EPOLLONESHOT;
+	TEST_ERRNO(epoll_ctl(epfd, EPOLL_CTL_ADD, sk_listen, &ev), EINVAL);
+	ev.events &= ~EPOLLONESHOT;
+
+	// EPOLLEXCLUSIVE cannot be used with epoll files
+	TEST_ERRNO(epoll_ctl(epfd, EPOLL_CTL_ADD, epfd2, &ev), EINVAL);
+
+	// EPOLLEXCLUSIVE cannot be used with EPOLL_CTL_MOD
+	TEST_SUCC(epoll_ctl(epfd, EPOLL_CTL_ADD, sk_listen, &ev));
+	TEST_ERRNO(epoll_ctl(epfd, EPOLL_CTL_MOD, sk_listen, &ev), EINVAL);
+	ev.events = EPOLLIN;
+	TEST_ERRNO(epoll_ctl(epfd, EPOLL_CTL_MOD, sk_listen, &ev), EINVAL);
+
+	TEST_SUCC(close(epfd));
+	TEST_SUCC(close(epfd2));
+}
+END_TEST()
+
+// Waits for the listening socket to be ready and exits with whether it is woken up
+static void wait_exclusive(int ready_fd)
+{
+	struct epoll_event ev = { .events = EPOLLIN | EPOLLEXCLUSIVE };
+	int epfd;
+
+	epfd = epoll_create1(0);
+	if (epfd < 0 || epoll_ctl(epfd, EPOLL_CTL_ADD, sk_listen, &ev) < 0)
+		_exit(EXIT_FAILURE);
+	if (write(ready_fd, "", 1) != 1)
+		_exit(EXIT_FAILURE);
+
+	_exit(epoll_wait(epfd, &ev, 1, WAIT_MS) == 1 ? 2 : 3);
+}
+
+FN_TEST(wake_one_exclusive)
+{
+	int pipe_fds[2];
+	int i, status, nr_woken = 0, nr_timeout = 0;
+	int sk_client, sk_accepted;
+	char buf[1];
+
+	TEST_SUCC(pipe(pipe_fds));
+	for (i = 0; i < NR_WAITERS; ++i) {
+		if (TEST_SUCC(fork()) == 0)
+			wait_exclusive(pipe_fds[1]);
+	}
+
+	// Wait for all the waiters to start waiting
+	for (i = 0; i < NR_WAITERS; ++i)
+		TEST_RES(read(pipe_fds[0], buf, 1), _ret == 1);
+	TEST_SUCC(usleep(100 * 1000));
+
+	// A single connection wakes up a single exclusive waiter
+	sk_client = TEST_SUCC(socket(PF_INET, SOCK_STREAM, 0));
+	TEST_SUCC(connect(sk_client, (struct sockaddr *)&sk_addr,
+			  sizeof(sk_addr)));
+
+	for (i = 0; i < NR_WAITERS; ++i) {
+		TEST_SUCC(wait(&status));
+		if (WIFEXITED(status) && WEXITSTATUS(status) == 2)
+			nr_woken++;
+		else if (WIFEXITED(status) && WEXITSTATUS(status) == 3)
+			nr_timeout++;
+	}
+	TEST_RES(nr_woken, _ret == 1);
+	TEST_RES(nr_timeout, _ret == NR_WAITERS - 1);
+
+	sk_accepted = TEST_SUCC(accept(sk_listen, NULL, NULL));
+
+	TEST_SUCC(close(sk_accepted));
+	TEST_SUCC(close(sk_client));
+	TEST_SUCC(close(pipe_fds[0]));
+	TEST_SUCC(close(pipe_fds[1]));
+}
+END_TEST()
+
+FN_TEST(wake_two_exclusive)
+{
+	int pipe_fds[2];
+	int i, status, nr_woken = 0, nr_timeout = 0;
+	int sk_clients[2], sk_accepted;
+	char buf[1];
+
+	TEST_SUCC(pipe(pipe_fds));
+	for (i = 0; i < NR_WAITERS; ++i) {
+		if (TEST_SUCC(fork()) == 0)
+			wait_exclusive(pipe_fds[1]);
+	}
+
+	// Wait for all the waiters to start waiting
+	for (i = 0; i < NR_WAITERS; ++i)
+		TEST_RES(read(pipe_fds[0], buf, 1), _ret == 1);
+	TEST_SUCC(usleep(100 * 1000));
+
+	// Each of the two connections in a row wakes up an exclusive waiter,
+	// even though the first one has not been accepted yet
+	for (i = 0; i < 2; ++i) {
+		sk_clients[i] = TEST_SUCC(socket(PF_INET, SOCK_STREAM, 0));
+		TEST_SUCC(connect(sk_clients[i], (struct sockaddr *)&sk_addr,
+				  sizeof(sk_addr)));
+	}
+
+	for (i = 0; i < NR_WAITERS; ++i) {
+		TEST_SUCC(wait(&status));
+		if (WIFEXITED(status) && WEXITSTATUS(status) == 2)
+			nr_woken++;
+		else if (WIFEXITED(status) && WEXITSTATUS(status) == 3)
+			nr_timeout++;
+	}
+	TEST_RES(nr_woken, _ret == 2);
+	TEST_RES(nr_timeout, _ret == NR_WAITERS - 2);
+
+	for (i = 0; i < 2; ++i) {
+		sk_accepted = TEST_SUCC(accept(sk_listen, NULL, NULL));
+		TEST_SUCC(close(sk_accepted));
+		TEST_SUCC(close(sk_clients[i]));
+	}
+	TEST_SUCC(close(pipe_fds[0]));
+	TEST_SUCC(close(pipe_fds[1]));
+}
+END_TEST()
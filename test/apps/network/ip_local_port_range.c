@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define RANGE_PATH "/proc/sys/net/ipv4/ip_local_port_range"
+#define RANGE_START 40000
+#define RANGE_END 40003
+#define RANGE_LEN (RANGE_END - RANGE_START + 1)
+
+static char old_range[64];
+static struct sockaddr_in sk_addr;
+static int sk_listen;
+static int sk_clients[RANGE_LEN];
+
+static ssize_t write_range(const char *range)
+{
+	int fd;
+	ssize_t ret;
+
+	fd = open(RANGE_PATH, O_WRONLY | O_TRUNC);
+	if (fd < 0)
+		return -1;
+	ret = write(fd, range, strlen(range));
+	close(fd);
+
+	return ret;
+}
+
+static int read_range(int *start, int *end)
+{
+	FILE *file;
+	int ret;
+
+	file = fopen(RANGE_PATH, "r");
+	if (file == NULL)
+		return -1;
+	ret = fscanf(file, "%d %d", start, end);
+	fclose(file);
+
+	return ret;
+}
+
+FN_SETUP(range)
+{
+	int fd;
+
+	fd = CHECK(open(RANGE_PATH, O_RDONLY));
+	CHECK(read(fd, old_range, sizeof(old_range) - 1));
+	CHECK(close(fd));
+}
+END_SETUP()
+
+FN_SETUP(listen)
+{
+	sk_addr.sin_family = AF_INET;
+	sk_addr.sin_port = htons(0x1238);
+	CHECK(inet_aton("127.0.0.1", &sk_addr.sin_addr));
+
+	sk_listen = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(bind(sk_listen, (struct sockaddr *)&sk_addr, sizeof(sk_addr)));
+	CHECK(listen(sk_listen, RANGE_LEN + 1));
+}
+END_SETUP()
+
+FN_TEST(set_range)
+{
+	int start, end;
+
+	TEST_RES(write_range("40000 40003\n"), _ret == 12);
+	TEST_RES(read_range(&start, &end),
+		 _ret == 2 && start == RANGE_START && end == RANGE_END);
+
+	TEST_ERRNO(write_range("40003 40000\n"), EINVAL);
+	TEST_RES(read_range(&start, &end),
+		 _ret == 2 && start == RANGE_START && end == RANGE_END);
+}
+END_TEST()
+
+FN_TEST(exhaust_range)
+{
+	int i, j, sk;
+	struct sockaddr_in addr;
+	socklen_t addrlen;
+	int ports[RANGE_LEN];
+
+	for (i = 0; i < RANGE_LEN; ++i) {
+		sk_clients[i] = TEST_SUCC(socket(PF_INET, SOCK_STREAM, 0));
+		TEST_SUCC(connect(sk_clients[i], (struct sockaddr *)&sk_addr,
+				  sizeof(sk_addr)));
+
+		addrlen = sizeof(addr);
+		TEST_SUCC(getsockname(sk_clients[i], (struct sockaddr *)&addr,
+				      &addrlen));
+		ports[i] = ntohs(addr.sin_port);
+		TEST_RES(ports[i], _ret >= RANGE_START && _ret <= RANGE_END);
+	}
+
+	for (i = 0; i < RANGE_LEN; ++i)
+		for (j = i + 1; j < RANGE_LEN; ++j)
+			TEST_RES(ports[i], _ret != ports[j]);
+
+	sk = TEST_SUCC(socket(PF_INET, SOCK_STREAM, 0));
+	TEST_ERRNO(connect(sk, (struct sockaddr *)&sk_addr, sizeof(sk_addr)),
+		   EADDRNOTAVAIL);
+	TEST_SUCC(close(sk));
+
+	for (i = 0; i < RANGE_LEN; ++i)
+		TEST_SUCC(close(sk_clients[i]));
+}
+END_TEST()
+
+FN_TEST(restore_range)
+{
+	TEST_RES(write_range(old_range), _ret == strlen(old_range));
+}
+END_TEST()
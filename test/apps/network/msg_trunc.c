@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define DATAGRAM_LEN 2000
+#define BUF_LEN 1000
+
+static int sk_udp_send;
+static int sk_udp_recv;
+static int sk_unix[2];
+
+static char datagram[DATAGRAM_LEN];
+
+FN_SETUP(udp)
+{
+	struct sockaddr_in addr = {
+		.sin_family = AF_INET,
+		.sin_port = htons(0x2345),
+	};
+
+	CHECK(inet_aton("127.0.0.1", &addr.sin_addr));
+
+	sk_udp_recv = CHECK(socket(AF_INET, SOCK_DGRAM, 0));
+	CHECK(bind(sk_udp_recv, (struct sockaddr *)&addr, sizeof(addr)));
+
+	sk_udp_send = CHECK(socket(AF_INET, SOCK_DGRAM, 0));
+	CHECK(connect(sk_udp_send, (struct sockaddr *)&addr, sizeof(addr)));
+}
+END_SETUP()
+
+FN_SETUP(unix)
+{
+	CHECK(socketpair(AF_UNIX, SOCK_DGRAM, 0, sk_unix));
+}
+END_SETUP()
+
+static int recvmsg_flags(int sk, char *buf, size_t len, int flags,
+			 int *msg_flags)
+{
+	struct iovec iov = { .iov_base = buf, .iov_len = len };
+	struct msghdr msg = { .msg_iov = &iov, .msg_iovlen = 1 };
+	int ret;
+
+	ret = recvmsg(sk, &msg, flags);
+	*msg_flags = msg.msg_flags;
+	return ret;
+}
+
+FN_TEST(msg_trunc)
+{
+	int sk_pairs[2][2] = { { sk_udp_send, sk_udp_recv },
+			       { sk_unix[0], sk_unix[1] } };
+	char buf[BUF_LEN];
+	int msg_flags, i;
+
+	memset(datagram, 'a', sizeof(datagram));
+
+	for (i = 0; i < 2; i++) {
+		int sk_send = sk_pairs[i][0], sk_recv = sk_pairs[i][1];
+
+		// The length of the whole datagram is returned with MSG_TRUNC
+		TEST_RES(send(sk_send, datagram, DATAGRAM_LEN, 0),
+			 _ret == DATAGRAM_LEN);
+		TEST_RES(recv(sk_recv, buf, BUF_LEN, MSG_TRUNC),
+			 _ret == DATAGRAM_LEN);
+
+		// MSG_TRUNC is set in msg_flags if the datagram is truncated
+		TEST_RES(send(sk_send, datagram, DATAGRAM_LEN, 0),
+			 _ret == DATAGRAM_LEN);
+		TEST_RES(recvmsg_flags(sk_recv, buf, BUF_LEN, 0, &msg_flags),
+			 _ret == BUF_LEN && (msg_flags & MSG_TRUNC));
+
+		TEST_RES(send(sk_send, datagram, BUF_LEN, 0), _ret == BUF_LEN);
+		TEST_RES(recvmsg_flags(sk_recv, buf, BUF_LEN, MSG_TRUNC,
+				       &msg_flags),
+			 _ret == BUF_LEN && !(msg_flags & MSG_TRUNC));
+	}
+}
+END_TEST()
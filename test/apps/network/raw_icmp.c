@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <linux/capability.h>
+#include <netinet/in.h>
+#include <netinet/ip.h>
+#include <netinet/ip_icmp.h>
+#include <arpa/inet.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define ECHO_ID 0x1234
+#define ECHO_SEQ 1
+
+static int sk_raw;
+static struct sockaddr_in lo_addr;
+
+FN_SETUP(socket)
+{
+	sk_raw = CHECK(socket(AF_INET, SOCK_RAW, IPPROTO_ICMP));
+
+	lo_addr.sin_family = AF_INET;
+	lo_addr.sin_addr.s_addr = inet_addr("127.0.0.1");
+}
+END_SETUP()
+
+static unsigned short checksum(void *data, int len)
+{
+	unsigned short *words = data;
+	unsigned int sum = 0;
+
+	for (; len > 1; len -= 2)
+		sum += *words++;
+	if (len == 1)
+		sum += *(unsigned char *)words;
+	sum = (sum >> 16) + (sum & 0xffff);
+	sum += sum >> 16;
+	return ~sum;
+}
+
+static int build_echo_request(char *buf)
+{
+	struct icmphdr *icmp = (struct icmphdr *)buf;
+	int len = sizeof(struct icmphdr) + 8;
+
+	memset(buf, 0, len);
+	icmp->type = ICMP_ECHO;
+	icmp->un.echo.id = htons(ECHO_ID);
+	icmp->un.echo.sequence = htons(ECHO_SEQ);
+	memcpy(buf + sizeof(struct icmphdr), "ping!!!", 8);
+	icmp->checksum = checksum(buf, len);
+	return len;
+}
+
+// Receives packets until the echo reply arrives, since the echo request sent
+// over the loopback device is also received by the raw socket.
+static int recv_echo_reply(void)
+{
+	char buf[128];
+	struct iphdr *ip = (struct iphdr *)buf;
+	struct icmphdr *icmp;
+	struct sockaddr_in addr;
+	socklen_t addrlen;
+	ssize_t len;
+
+	for (;;) {
+		addrlen = sizeof(addr);
+		len = recvfrom(sk_raw, buf, sizeof(buf), 0,
+			       (struct sockaddr *)&addr, &addrlen);
+		if (len < (ssize_t)(sizeof(struct iphdr) + sizeof(*icmp)))
+			return -1;
+
+		// The received packet includes the IP header.
+		if (ip->version != 4 || ip->protocol != IPPROTO_ICMP ||
+		    addr.sin_addr.s_addr != lo_addr.sin_addr.s_addr)
+			return -1;
+
+		icmp = (struct icmphdr *)(buf + ip->ihl * 4);
+		if (icmp->type != ICMP_ECHOREPLY)
+			continue;
+		if (ntohs(icmp->un.echo.id) != ECHO_ID ||
+		    ntohs(icmp->un.echo.sequence) != ECHO_SEQ)
+			return -1;
+		return 0;
+	}
+}
+
+FN_TEST(ping_loopback)
+{
+	char buf[64];
+	int len = build_echo_request(buf);
+
+	TEST_RES(sendto(sk_raw, buf, len, 0, (struct sockaddr *)&lo_addr,
+			sizeof(lo_addr)),
+		 _ret == len);
+	TEST_SUCC(recv_echo_reply());
+}
+END_TEST()
+
+FN_TEST(ping_loopback_hdrincl)
+{
+	char buf[64];
+	struct iphdr *ip = (struct iphdr *)buf;
+	int val = 1, len;
+	socklen_t optlen = sizeof(val);
+
+	TEST_RES(getsockopt(sk_raw, IPPROTO_IP, IP_HDRINCL, &val, &optlen),
+		 optlen == sizeof(val) && val == 0);
+	val = 1;
+	TEST_SUCC(setsockopt(sk_raw, IPPROTO_IP, IP_HDRINCL, &val,
+			     sizeof(val)));
+	TEST_RES(getsockopt(sk_raw, IPPROTO_IP, IP_HDRINCL, &val, &optlen),
+		 optlen == sizeof(val) && val == 1);
+
+	len = build_echo_request(buf + sizeof(struct iphdr)) +
+	      sizeof(struct iphdr);
+	memset(ip, 0, sizeof(*ip));
+	ip->version = 4;
+	ip->ihl = 5;
+	ip->tot_len = htons(len);
+	ip->ttl = 64;
+	ip->protocol = IPPROTO_ICMP;
+	ip->saddr = lo_addr.sin_addr.s_addr;
+	ip->daddr = lo_addr.sin_addr.s_addr;
+	ip->check = checksum(ip, sizeof(*ip));
+
+	TEST_RES(sendto(sk_raw, buf, len, 0, (struct sockaddr *)&lo_addr,
+			sizeof(lo_addr)),
+		 _ret == len);
+	TEST_SUCC(recv_echo_reply());
+
+	val = 0;
+	TEST_SUCC(setsockopt(sk_raw, IPPROTO_IP, IP_HDRINCL, &val,
+			     sizeof(val)));
+}
+END_TEST()
+
+// Drops all the capabilities of the current process.
+static int drop_caps(void)
+{
+	struct __user_cap_header_struct header = {
+		.version = _LINUX_CAPABILITY_VERSION_3,
+		.pid = 0,
+	};
+	struct __user_cap_data_struct data[2];
+
+	memset(data, 0, sizeof(data));
+	return syscall(SYS_capset, &header, data);
+}
+
+FN_TEST(unprivileged)
+{
+	int status;
+	pid_t pid;
+
+	// CAP_NET_RAW is required to create raw sockets.
+	pid = fork();
+	if (pid == 0) {
+		if (drop_caps() < 0)
+			_exit(2);
+		if (socket(AF_INET, SOCK_RAW, IPPROTO_ICMP) >= 0)
+			_exit(1);
+		_exit(errno == EPERM ? 0 : 1);
+	}
+	TEST_RES(waitpid(pid, &status, 0),
+		 _ret == pid && WIFEXITED(status) && WEXITSTATUS(status) == 0);
+}
+END_TEST()
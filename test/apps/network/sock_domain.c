@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <unistd.h>
+
+#include "test.h"
+
+static int sk_tcp;
+static int sk_udp;
+static int sk_unix_stream;
+static int sk_unix_dgram;
+
+FN_SETUP(sockets)
+{
+	sk_tcp = CHECK(socket(AF_INET, SOCK_STREAM, 0));
+	sk_udp = CHECK(socket(AF_INET, SOCK_DGRAM, 0));
+	sk_unix_stream = CHECK(socket(AF_UNIX, SOCK_STREAM, 0));
+	sk_unix_dgram = CHECK(socket(AF_UNIX, SOCK_DGRAM, 0));
+}
+END_SETUP()
+
+FN_TEST(domain)
+{
+	int val;
+	socklen_t len = sizeof(val);
+
+	TEST_RES(getsockopt(sk_tcp, SOL_SOCKET, SO_DOMAIN, &val, &len),
+		 len == sizeof(val) && val == AF_INET);
+	TEST_RES(getsockopt(sk_udp, SOL_SOCKET, SO_DOMAIN, &val, &len),
+		 len == sizeof(val) && val == AF_INET);
+	TEST_RES(getsockopt(sk_unix_stream, SOL_SOCKET, SO_DOMAIN, &val, &len),
+		 len == sizeof(val) && val == AF_UNIX);
+	TEST_RES(getsockopt(sk_unix_dgram, SOL_SOCKET, SO_DOMAIN, &val, &len),
+		 len == sizeof(val) && val == AF_UNIX);
+}
+END_TEST()
+
+FN_TEST(protocol)
+{
+	int val;
+	socklen_t len = sizeof(val);
+
+	TEST_RES(getsockopt(sk_tcp, SOL_SOCKET, SO_PROTOCOL, &val, &len),
+		 len == sizeof(val) && val == IPPROTO_TCP);
+	TEST_RES(getsockopt(sk_udp, SOL_SOCKET, SO_PROTOCOL, &val, &len),
+		 len == sizeof(val) && val == IPPROTO_UDP);
+	TEST_RES(getsockopt(sk_unix_stream, SOL_SOCKET, SO_PROTOCOL, &val,
+			    &len),
+		 len == sizeof(val) && val == 0);
+	TEST_RES(getsockopt(sk_unix_dgram, SOL_SOCKET, SO_PROTOCOL, &val, &len),
+		 len == sizeof(val) && val == 0);
+}
+END_TEST()
+
+FN_TEST(read_only)
+{
+	int val = AF_UNIX;
+
+	TEST_ERRNO(setsockopt(sk_tcp, SOL_SOCKET, SO_DOMAIN, &val, sizeof(val)),
+		   ENOPROTOOPT);
+	TEST_ERRNO(setsockopt(sk_tcp, SOL_SOCKET, SO_PROTOCOL, &val,
+			      sizeof(val)),
+		   ENOPROTOOPT);
+}
+END_TEST()
+
+FN_TEST(socketpair)
+{
+	int val, sv[2];
+	socklen_t len = sizeof(val);
+
+	TEST_SUCC(socketpair(AF_UNIX, SOCK_STREAM, 0, sv));
+	TEST_RES(getsockopt(sv[0], SOL_SOCKET, SO_DOMAIN, &val, &len),
+		 len == sizeof(val) && val == AF_UNIX);
+	TEST_RES(getsockopt(sv[1], SOL_SOCKET, SO_PROTOCOL, &val, &len),
+		 len == sizeof(val) && val == 0);
+	TEST_SUCC(close(sv[0]));
+	TEST_SUCC(close(sv[1]));
+}
+END_TEST()
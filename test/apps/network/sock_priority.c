@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <linux/capability.h>
+#include <netinet/in.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "test.h"
+
+// The priority only affects what `SO_PRIORITY` reports. The order in which the
+// packets are sent is not changed, so the throughput is not tested.
+
+static int sk_tcp;
+static int sk_udp;
+
+FN_SETUP(sockets)
+{
+	sk_tcp = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	sk_udp = CHECK(socket(PF_INET, SOCK_DGRAM, 0));
+}
+END_SETUP()
+
+static int get_priority(int sk)
+{
+	int priority;
+	socklen_t len = sizeof(priority);
+
+	if (getsockopt(sk, SOL_SOCKET, SO_PRIORITY, &priority, &len) < 0)
+		return -1;
+	if (len != sizeof(priority))
+		return -1;
+	return priority;
+}
+
+FN_TEST(default_priority)
+{
+	TEST_RES(get_priority(sk_tcp), _ret == 0);
+	TEST_RES(get_priority(sk_udp), _ret == 0);
+}
+END_TEST()
+
+FN_TEST(set_priority)
+{
+	int priority;
+
+	for (priority = 0; priority <= 6; ++priority) {
+		TEST_SUCC(setsockopt(sk_tcp, SOL_SOCKET, SO_PRIORITY, &priority,
+				     sizeof(priority)));
+		TEST_RES(get_priority(sk_tcp), _ret == priority);
+
+		TEST_SUCC(setsockopt(sk_udp, SOL_SOCKET, SO_PRIORITY, &priority,
+				     sizeof(priority)));
+		TEST_RES(get_priority(sk_udp), _ret == priority);
+	}
+
+	// CAP_NET_ADMIN is required to set a priority higher than 6.
+	priority = 7;
+	TEST_SUCC(setsockopt(sk_tcp, SOL_SOCKET, SO_PRIORITY, &priority,
+			     sizeof(priority)));
+	TEST_RES(get_priority(sk_tcp), _ret == 7);
+}
+END_TEST()
+
+// Drops all the capabilities of the current process.
+static int drop_caps(void)
+{
+	struct __user_cap_header_struct header = {
+		.version = _LINUX_CAPABILITY_VERSION_3,
+		.pid = 0,
+	};
+	struct __user_cap_data_struct data[2];
+
+	memset(data, 0, sizeof(data));
+	return syscall(SYS_capset, &header, data);
+}
+
+FN_TEST(unprivileged)
+{
+	int status;
+	pid_t pid;
+
+	pid = fork();
+	if (pid == 0) {
+		int priority = 7;
+
+		if (drop_caps() < 0)
+			_exit(2);
+		if (setsockopt(sk_udp, SOL_SOCKET, SO_PRIORITY, &priority,
+			       sizeof(priority)) == 0 ||
+		    errno != EPERM)
+			_exit(1);
+
+		priority = 6;
+		if (setsockopt(sk_udp, SOL_SOCKET, SO_PRIORITY, &priority,
+			       sizeof(priority)) < 0)
+			_exit(1);
+		_exit(get_priority(sk_udp) == 6 ? 0 : 1);
+	}
+	TEST_RES(waitpid(pid, &status, 0),
+		 _ret == pid && WIFEXITED(status) && WEXITSTATUS(status) == 0);
+}
+END_TEST()
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define TIMEOUT_MS 100
+
+static struct sockaddr_in sk_addr;
+static int sk_listen;
+static int sk_connected;
+static int sk_accepted;
+
+static const struct timeval timeout = {
+	.tv_sec = 0,
+	.tv_usec = TIMEOUT_MS * 1000,
+};
+
+static long elapsed_ms(const struct timespec *start)
+{
+	struct timespec now;
+
+	clock_gettime(CLOCK_MONOTONIC, &now);
+	return (now.tv_sec - start->tv_sec) * 1000 +
+	       (now.tv_nsec - start->tv_nsec) / 1000000;
+}
+
+FN_SETUP(connected)
+{
+	sk_addr.sin_family = AF_INET;
+	sk_addr.sin_port = htons(0x1236);
+	CHECK(inet_aton("127.0.0.1", &sk_addr.sin_addr));
+
+	sk_listen = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(bind(sk_listen, (struct sockaddr *)&sk_addr, sizeof(sk_addr)));
+	CHECK(listen(sk_listen, 2));
+
+	sk_connected = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(connect(sk_connected, (struct sockaddr *)&sk_addr,
+		      sizeof(sk_addr)));
+
+	sk_accepted = CHECK(accept(sk_listen, NULL, NULL));
+}
+END_SETUP()
+
+FN_TEST(get_set_timeout)
+{
+	struct timeval tv;
+	socklen_t tvlen = sizeof(tv);
+
+	TEST_RES(getsockopt(sk_connected, SOL_SOCKET, SO_RCVTIMEO, &tv, &tvlen),
+		 tvlen == sizeof(tv) && tv.tv_sec == 0 && tv.tv_usec == 0);
+
+	TEST_SUCC(setsockopt(sk_connected, SOL_SOCKET, SO_RCVTIMEO, &timeout,
+			     sizeof(timeout)));
+	TEST_RES(getsockopt(sk_connected, SOL_SOCKET, SO_RCVTIMEO, &tv, &tvlen),
+		 tvlen == sizeof(tv) && tv.tv_sec == timeout.tv_sec &&
+			 tv.tv_usec == timeout.tv_usec);
+
+	TEST_SUCC(setsockopt(sk_connected, SOL_SOCKET, SO_SNDTIMEO, &timeout,
+			     sizeof(timeout)));
+	TEST_RES(getsockopt(sk_connected, SOL_SOCKET, SO_SNDTIMEO, &tv, &tvlen),
+		 tvlen == sizeof(tv) && tv.tv_sec == timeout.tv_sec &&
+			 tv.tv_usec == timeout.tv_usec);
+
+	tv.tv_sec = 0;
+	tv.tv_usec = 1000000;
+	TEST_ERRNO(setsockopt(sk_connected, SOL_SOCKET, SO_SNDTIMEO, &tv,
+			      sizeof(tv)),
+		   EDOM);
+	TEST_ERRNO(setsockopt(sk_connected, SOL_SOCKET, SO_SNDTIMEO, &tv,
+			      sizeof(tv) - 1),
+		   EINVAL);
+}
+END_TEST()
+
+FN_TEST(recv_timeout)
+{
+	char buf[1];
+	struct timespec start;
+
+	TEST_SUCC(setsockopt(sk_connected, SOL_SOCKET, SO_RCVTIMEO, &timeout,
+			     sizeof(timeout)));
+
+	clock_gettime(CLOCK_MONOTONIC, &start);
+	TEST_ERRNO(recv(sk_connected, buf, sizeof(buf), 0), EAGAIN);
+	TEST_RES(elapsed_ms(&start), _ret >= TIMEOUT_MS / 2);
+
+	TEST_RES(send(sk_accepted, "a", 1, 0), _ret == 1);
+	TEST_RES(recv(sk_connected, buf, sizeof(buf), 0),
+		 _ret == 1 && buf[0] == 'a');
+}
+END_TEST()
+
+FN_TEST(accept_timeout)
+{
+	struct timespec start;
+
+	TEST_SUCC(setsockopt(sk_listen, SOL_SOCKET, SO_RCVTIMEO, &timeout,
+			     sizeof(timeout)));
+
+	clock_gettime(CLOCK_MONOTONIC, &start);
+	TEST_ERRNO(accept(sk_listen, NULL, NULL), EAGAIN);
+	TEST_RES(elapsed_ms(&start), _ret >= TIMEOUT_MS / 2);
+}
+END_TEST()
+
+FN_TEST(connect_timeout)
+{
+	int sk;
+	struct sockaddr_in addr = {
+		.sin_family = AF_INET,
+		.sin_port = htons(0x1237),
+	};
+
+	// In the QEMU user network, no host will answer the ARP requests for this address, so the
+	// connection can never be established.
+	CHECK(inet_aton("10.0.2.100", &addr.sin_addr));
+
+	sk = TEST_SUCC(socket(PF_INET, SOCK_STREAM, 0));
+	TEST_SUCC(setsockopt(sk, SOL_SOCKET, SO_SNDTIMEO, &timeout,
+			     sizeof(timeout)));
+
+	// Like Linux, the connection is still in progress after the timeout expires.
+	TEST_ERRNO(connect(sk, (struct sockaddr *)&addr, sizeof(addr)),
+		   EINPROGRESS);
+
+	TEST_SUCC(close(sk));
+}
+END_TEST()
@@ -2,6 +2,7 @@
 
 #include <stdio.h>
 #include <stdlib.h>
+#include <string.h>
 #include <sys/socket.h>
 #include <netinet/in.h>
 #include <netinet/tcp.h>
@@ -72,6 +73,89 @@ int main()
 		exit(EXIT_FAILURE);
 	}
 
+	// The deferred accept timeout is rounded to the retransmission periods
+	option = 10;
+	if (setsockopt(sockfd, IPPROTO_TCP, TCP_DEFER_ACCEPT, &option,
+		       sizeof(option)) < 0) {
+		perror("Setting TCP_DEFER_ACCEPT option failed");
+		exit(EXIT_FAILURE);
+	}
+
+	int defer_accept;
+	socklen_t defer_accept_len = sizeof(defer_accept);
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_DEFER_ACCEPT, &defer_accept,
+		       &defer_accept_len) < 0 ||
+	    defer_accept != 15) {
+		perror("Getting TCP_DEFER_ACCEPT option failed.");
+		exit(EXIT_FAILURE);
+	}
+
+	// The keepalive options have the same defaults as Linux
+	int keepintvl, keepcnt;
+	socklen_t keepintvl_len = sizeof(keepintvl);
+	socklen_t keepcnt_len = sizeof(keepcnt);
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_KEEPINTVL, &keepintvl,
+		       &keepintvl_len) < 0 ||
+	    keepintvl != 75) {
+		perror("Getting TCP_KEEPINTVL option failed.");
+		exit(EXIT_FAILURE);
+	}
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_KEEPCNT, &keepcnt,
+		       &keepcnt_len) < 0 ||
+	    keepcnt != 9) {
+		perror("Getting TCP_KEEPCNT option failed.");
+		exit(EXIT_FAILURE);
+	}
+
+	option = 10;
+	if (setsockopt(sockfd, IPPROTO_TCP, TCP_KEEPINTVL, &option,
+		       sizeof(option)) < 0) {
+		perror("Setting TCP_KEEPINTVL option failed");
+		exit(EXIT_FAILURE);
+	}
+	option = 3;
+	if (setsockopt(sockfd, IPPROTO_TCP, TCP_KEEPCNT, &option,
+		       sizeof(option)) < 0) {
+		perror("Setting TCP_KEEPCNT option failed");
+		exit(EXIT_FAILURE);
+	}
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_KEEPINTVL, &keepintvl,
+		       &keepintvl_len) < 0 ||
+	    keepintvl != 10) {
+		perror("Getting TCP_KEEPINTVL option failed.");
+		exit(EXIT_FAILURE);
+	}
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_KEEPCNT, &keepcnt,
+		       &keepcnt_len) < 0 ||
+	    keepcnt != 3) {
+		perror("Getting TCP_KEEPCNT option failed.");
+		exit(EXIT_FAILURE);
+	}
+
+	// The keepalive probe count is out of bounds
+	option = 0;
+	if (setsockopt(sockfd, IPPROTO_TCP, TCP_KEEPCNT, &option,
+		       sizeof(option)) == 0) {
+		fprintf(stderr, "Setting TCP_KEEPCNT to zero succeeded\n");
+		exit(EXIT_FAILURE);
+	}
+
+	// Switch to the CUBIC congestion control algorithm
+	if (setsockopt(sockfd, IPPROTO_TCP, TCP_CONGESTION, "cubic",
+		       sizeof("cubic")) < 0) {
+		perror("Setting TCP_CONGESTION option failed");
+		exit(EXIT_FAILURE);
+	}
+
+	char congestion[16] = { 0 };
+	socklen_t congestion_len = sizeof(congestion);
+	if (getsockopt(sockfd, IPPROTO_TCP, TCP_CONGESTION, congestion,
+		       &congestion_len) < 0 ||
+	    strcmp(congestion, "cubic") != 0) {
+		perror("Getting TCP_CONGESTION option failed.");
+		exit(EXIT_FAILURE);
+	}
+
 	// Close socket
 	close(sockfd);
 
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <netinet/tcp.h>
+#include <arpa/inet.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "test.h"
+
+// The data is sent after this timeout even if the socket is still corked.
+#define CORK_TIMEOUT_MS 200
+
+static struct sockaddr_in sk_addr;
+static int sk_listen;
+static int sk_connected;
+static int sk_accepted;
+
+static long elapsed_ms(const struct timespec *start)
+{
+	struct timespec now;
+
+	clock_gettime(CLOCK_MONOTONIC, &now);
+	return (now.tv_sec - start->tv_sec) * 1000 +
+	       (now.tv_nsec - start->tv_nsec) / 1000000;
+}
+
+FN_SETUP(connected)
+{
+	sk_addr.sin_family = AF_INET;
+	sk_addr.sin_port = htons(0x1238);
+	CHECK(inet_aton("127.0.0.1", &sk_addr.sin_addr));
+
+	sk_listen = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(bind(sk_listen, (struct sockaddr *)&sk_addr, sizeof(sk_addr)));
+	CHECK(listen(sk_listen, 2));
+
+	sk_connected = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(connect(sk_connected, (struct sockaddr *)&sk_addr,
+		      sizeof(sk_addr)));
+
+	sk_accepted = CHECK(accept(sk_listen, NULL, NULL));
+}
+END_SETUP()
+
+FN_TEST(get_set_cork)
+{
+	int cork;
+	socklen_t corklen = sizeof(cork);
+
+	TEST_RES(getsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			    &corklen),
+		 corklen == sizeof(cork) && cork == 0);
+
+	cork = 1;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+	TEST_RES(getsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			    &corklen),
+		 corklen == sizeof(cork) && cork == 1);
+
+	cork = 0;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+	TEST_RES(getsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			    &corklen),
+		 corklen == sizeof(cork) && cork == 0);
+}
+END_TEST()
+
+FN_TEST(coalesce_on_uncork)
+{
+	int cork;
+	char buf[16];
+
+	// The cork takes precedence over `TCP_NODELAY`.
+	cork = 1;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_NODELAY, &cork,
+			     sizeof(cork)));
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+
+	TEST_RES(send(sk_connected, "hello", 5, 0), _ret == 5);
+	TEST_RES(send(sk_connected, "world", 5, 0), _ret == 5);
+	TEST_ERRNO(recv(sk_accepted, buf, sizeof(buf), MSG_DONTWAIT), EAGAIN);
+
+	cork = 0;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+	TEST_RES(recv(sk_accepted, buf, sizeof(buf), 0),
+		 _ret == 10 && memcmp(buf, "helloworld", 10) == 0);
+
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_NODELAY, &cork,
+			     sizeof(cork)));
+}
+END_TEST()
+
+FN_TEST(flush_on_timeout)
+{
+	int cork;
+	char buf[16];
+	struct timespec start;
+
+	cork = 1;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+
+	clock_gettime(CLOCK_MONOTONIC, &start);
+	TEST_RES(send(sk_connected, "hello", 5, 0), _ret == 5);
+	TEST_RES(recv(sk_accepted, buf, sizeof(buf), 0),
+		 _ret == 5 && memcmp(buf, "hello", 5) == 0);
+	TEST_RES(elapsed_ms(&start), _ret >= CORK_TIMEOUT_MS / 2);
+
+	cork = 0;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+}
+END_TEST()
+
+FN_TEST(flush_on_shutdown)
+{
+	int cork;
+	char buf[16];
+
+	cork = 1;
+	TEST_SUCC(setsockopt(sk_connected, IPPROTO_TCP, TCP_CORK, &cork,
+			     sizeof(cork)));
+
+	// The corked data is sent before the end of the stream.
+	TEST_RES(send(sk_connected, "hello", 5, 0), _ret == 5);
+	TEST_SUCC(shutdown(sk_connected, SHUT_WR));
+	TEST_RES(recv(sk_accepted, buf, sizeof(buf), 0),
+		 _ret == 5 && memcmp(buf, "hello", 5) == 0);
+	TEST_RES(recv(sk_accepted, buf, sizeof(buf), 0), _ret == 0);
+
+	TEST_ERRNO(send(sk_connected, "world", 5, MSG_NOSIGNAL), EPIPE);
+}
+END_TEST()
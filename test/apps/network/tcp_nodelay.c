@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <sys/wait.h>
+#include <netinet/in.h>
+#include <netinet/tcp.h>
+#include <arpa/inet.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define NR_ROUNDS 100
+// A delayed ACK takes at least 10ms, so the rounds must finish in half of the time if no delayed
+// ACK is ever waited for.
+#define MAX_TOTAL_MS (NR_ROUNDS * 10 / 2)
+
+static struct sockaddr_in sk_addr;
+static int sk_listen;
+static int sk_client;
+static int sk_accepted;
+
+FN_SETUP(connected)
+{
+	int on = 1;
+
+	sk_addr.sin_family = AF_INET;
+	sk_addr.sin_port = htons(0x123b);
+	CHECK(inet_aton("127.0.0.1", &sk_addr.sin_addr));
+
+	sk_listen = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(setsockopt(sk_listen, IPPROTO_TCP, TCP_NODELAY, &on, sizeof(on)));
+	CHECK(bind(sk_listen, (struct sockaddr *)&sk_addr, sizeof(sk_addr)));
+	CHECK(listen(sk_listen, 1));
+
+	sk_client = CHECK(socket(PF_INET, SOCK_STREAM, 0));
+	CHECK(setsockopt(sk_client, IPPROTO_TCP, TCP_NODELAY, &on, sizeof(on)));
+	CHECK(connect(sk_client, (struct sockaddr *)&sk_addr,
+		      sizeof(sk_addr)));
+
+	sk_accepted = CHECK(accept(sk_listen, NULL, NULL));
+}
+END_SETUP()
+
+FN_TEST(inherit_nodelay)
+{
+	int val;
+	socklen_t len = sizeof(val);
+
+	TEST_RES(getsockopt(sk_accepted, IPPROTO_TCP, TCP_NODELAY, &val, &len),
+		 len == sizeof(val) && val != 0);
+}
+END_TEST()
+
+static long elapsed_ms(const struct timespec *start)
+{
+	struct timespec now;
+
+	clock_gettime(CLOCK_MONOTONIC, &now);
+	return (now.tv_sec - start->tv_sec) * 1000 +
+	       (now.tv_nsec - start->tv_nsec) / 1000000;
+}
+
+static int serve(int sk)
+{
+	int i;
+	char buf[2];
+
+	for (i = 0; i < NR_ROUNDS; ++i) {
+		if (recv(sk, buf, 2, MSG_WAITALL) != 2)
+			return -1;
+		if (send(sk, buf, 1, 0) != 1)
+			return -1;
+	}
+
+	return 0;
+}
+
+static int ping_pong(int sk)
+{
+	int i;
+	char buf[1];
+
+	// Two small writes are followed by a read. This pattern stalls if the second write waits
+	// for the ACK of the first one, which is delayed by the peer.
+	for (i = 0; i < NR_ROUNDS; ++i) {
+		if (send(sk, "a", 1, 0) != 1 || send(sk, "b", 1, 0) != 1)
+			return -1;
+		if (recv(sk, buf, 1, 0) != 1)
+			return -1;
+	}
+
+	return 0;
+}
+
+FN_TEST(ping_pong)
+{
+	pid_t pid;
+	int status;
+	struct timespec start;
+
+	pid = TEST_SUCC(fork());
+	if (pid == 0)
+		_exit(serve(sk_accepted) < 0 ? EXIT_FAILURE : EXIT_SUCCESS);
+
+	clock_gettime(CLOCK_MONOTONIC, &start);
+	TEST_SUCC(ping_pong(sk_client));
+	TEST_RES(elapsed_ms(&start), _ret < MAX_TOTAL_MS);
+
+	TEST_RES(wait(&status), _ret == pid && WIFEXITED(status) &&
+					WEXITSTATUS(status) == EXIT_SUCCESS);
+}
+END_TEST()
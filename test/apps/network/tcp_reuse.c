@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <sys/socket.h>
+#include <netinet/in.h>
+#include <arpa/inet.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define C_PORT htons(0x1239)
+#define S_PORT htons(0x123a)
+#define NR_CYCLES 32
+
+static struct sockaddr_in c_addr;
+static struct sockaddr_in s_addr;
+static int sk_listen;
+
+static int new_reuse_socket(void)
+{
+	int sk, on = 1;
+
+	sk = socket(PF_INET, SOCK_STREAM, 0);
+	if (sk < 0)
+		return -1;
+
+	if (setsockopt(sk, SOL_SOCKET, SO_REUSEADDR, &on, sizeof(on)) < 0) {
+		close(sk);
+		return -1;
+	}
+
+	return sk;
+}
+
+FN_SETUP(listen)
+{
+	c_addr.sin_family = AF_INET;
+	c_addr.sin_port = C_PORT;
+	CHECK(inet_aton("127.0.0.1", &c_addr.sin_addr));
+
+	s_addr.sin_family = AF_INET;
+	s_addr.sin_port = S_PORT;
+	CHECK(inet_aton("127.0.0.1", &s_addr.sin_addr));
+
+	sk_listen = CHECK(new_reuse_socket());
+	CHECK(bind(sk_listen, (struct sockaddr *)&s_addr, sizeof(s_addr)));
+	CHECK(listen(sk_listen, 2));
+}
+END_SETUP()
+
+FN_TEST(connect_close_cycles)
+{
+	int i, sk_client, sk_accepted;
+	char buf[1];
+
+	// The accepted sockets are closed first, so they should enter the TIME_WAIT state. The new
+	// connections from the same local address should still be established.
+	for (i = 0; i < NR_CYCLES; ++i) {
+		sk_client = TEST_SUCC(new_reuse_socket());
+		TEST_SUCC(bind(sk_client, (struct sockaddr *)&c_addr,
+			       sizeof(c_addr)));
+		TEST_SUCC(connect(sk_client, (struct sockaddr *)&s_addr,
+				  sizeof(s_addr)));
+
+		sk_accepted = TEST_SUCC(accept(sk_listen, NULL, NULL));
+		TEST_SUCC(close(sk_accepted));
+
+		TEST_RES(recv(sk_client, buf, sizeof(buf), 0), _ret == 0);
+		TEST_SUCC(close(sk_client));
+	}
+}
+END_TEST()
+
+FN_TEST(rebind_listen)
+{
+	TEST_SUCC(close(sk_listen));
+
+	sk_listen = TEST_SUCC(new_reuse_socket());
+	TEST_SUCC(bind(sk_listen, (struct sockaddr *)&s_addr, sizeof(s_addr)));
+	TEST_SUCC(listen(sk_listen, 2));
+	TEST_SUCC(close(sk_listen));
+}
+END_TEST()
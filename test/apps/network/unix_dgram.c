@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <fcntl.h>
+#include <stdlib.h>
+#include <sys/socket.h>
+#include <sys/un.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "test.h"
+
+#define SERVER_PATH "/tmp/unix_dgram_server.sock"
+#define CLIENT_PATH "/tmp/unix_dgram_client.sock"
+
+static int sk_pair[2];
+static int sk_server;
+static int sk_client;
+static int sk_unbound;
+
+static struct sockaddr_un server_addr = {
+	.sun_family = AF_UNIX,
+	.sun_path = SERVER_PATH,
+};
+static struct sockaddr_un client_addr = {
+	.sun_family = AF_UNIX,
+	.sun_path = CLIENT_PATH,
+};
+
+FN_SETUP(socketpair)
+{
+	CHECK(socketpair(AF_UNIX, SOCK_DGRAM | SOCK_NONBLOCK | SOCK_CLOEXEC, 0,
+			 sk_pair));
+}
+END_SETUP()
+
+FN_SETUP(bound)
+{
+	unlink(SERVER_PATH);
+	unlink(CLIENT_PATH);
+
+	sk_server = CHECK(socket(AF_UNIX, SOCK_DGRAM | SOCK_NONBLOCK, 0));
+	CHECK(bind(sk_server, (struct sockaddr *)&server_addr,
+		   sizeof(server_addr)));
+
+	sk_client = CHECK(socket(AF_UNIX, SOCK_DGRAM | SOCK_NONBLOCK, 0));
+	CHECK(bind(sk_client, (struct sockaddr *)&client_addr,
+		   sizeof(client_addr)));
+
+	sk_unbound = CHECK(socket(AF_UNIX, SOCK_DGRAM | SOCK_NONBLOCK, 0));
+}
+END_SETUP()
+
+FN_TEST(socketpair_flags)
+{
+	TEST_RES(fcntl(sk_pair[0], F_GETFL), (_ret & O_NONBLOCK) != 0);
+	TEST_RES(fcntl(sk_pair[1], F_GETFD), (_ret & FD_CLOEXEC) != 0);
+}
+END_TEST()
+
+FN_TEST(message_boundaries)
+{
+	char buf[16];
+
+	TEST_RES(write(sk_pair[0], "hello", 5), _ret == 5);
+	TEST_RES(write(sk_pair[0], "world!", 6), _ret == 6);
+
+	TEST_RES(read(sk_pair[1], buf, sizeof(buf)),
+		 _ret == 5 && memcmp(buf, "hello", 5) == 0);
+	TEST_RES(read(sk_pair[1], buf, sizeof(buf)),
+		 _ret == 6 && memcmp(buf, "world!", 6) == 0);
+	TEST_ERRNO(read(sk_pair[1], buf, sizeof(buf)), EAGAIN);
+
+	TEST_RES(write(sk_pair[1], "reply", 5), _ret == 5);
+	TEST_RES(read(sk_pair[0], buf, sizeof(buf)),
+		 _ret == 5 && memcmp(buf, "reply", 5) == 0);
+}
+END_TEST()
+
+FN_TEST(truncated_datagram)
+{
+	char buf[16];
+
+	TEST_RES(write(sk_pair[0], "truncated", 9), _ret == 9);
+	TEST_RES(read(sk_pair[1], buf, 5),
+		 _ret == 5 && memcmp(buf, "trunc", 5) == 0);
+
+	// The rest of the datagram is discarded
+	TEST_ERRNO(read(sk_pair[1], buf, sizeof(buf)), EAGAIN);
+}
+END_TEST()
+
+#define FLOOD_DGRAM_LEN 1024
+#define FLOOD_MAX_DGRAMS 1000
+
+static int flood(int sk)
+{
+	char buf[FLOOD_DGRAM_LEN] = { 0 };
+	int i;
+
+	for (i = 0; i < FLOOD_MAX_DGRAMS; ++i)
+		if (send(sk, buf, sizeof(buf), MSG_DONTWAIT) < 0)
+			break;
+
+	return i;
+}
+
+static int drain(int sk)
+{
+	char buf[FLOOD_DGRAM_LEN];
+	int i;
+
+	for (i = 0; i < FLOOD_MAX_DGRAMS; ++i)
+		if (recv(sk, buf, sizeof(buf), MSG_DONTWAIT) != sizeof(buf))
+			break;
+
+	return i;
+}
+
+FN_TEST(small_rcvbuf)
+{
+	int rcvbuf = 1;
+	socklen_t rcvbuf_len = sizeof(rcvbuf);
+	int sent;
+
+	TEST_SUCC(setsockopt(sk_pair[1], SOL_SOCKET, SO_RCVBUF, &rcvbuf,
+			     sizeof(rcvbuf)));
+	TEST_RES(getsockopt(sk_pair[1], SOL_SOCKET, SO_RCVBUF, &rcvbuf,
+			    &rcvbuf_len),
+		 rcvbuf_len == sizeof(rcvbuf) && rcvbuf > 1);
+
+	// The queue stops growing once it is full
+	sent = TEST(flood(sk_pair[0]), EAGAIN,
+		    _ret > 0 && _ret < FLOOD_MAX_DGRAMS);
+	TEST(drain(sk_pair[1]), EAGAIN, _ret == sent);
+
+	// There is room again after the queue is drained
+	TEST_RES(send(sk_pair[0], "room", 4, MSG_DONTWAIT), _ret == 4);
+	TEST_RES(recv(sk_pair[1], &rcvbuf, sizeof(rcvbuf), 0), _ret == 4);
+}
+END_TEST()
+
+FN_TEST(blocking_send)
+{
+	char buf[FLOOD_DGRAM_LEN] = { 0 };
+	int flags, sent, status;
+	pid_t pid;
+
+	// The queue is full, so the blocking sender waits until the receiver
+	// makes room
+	sent = TEST(flood(sk_pair[0]), EAGAIN, _ret > 0);
+
+	pid = TEST(fork(), 0, 1);
+	if (pid == 0) {
+		usleep(100 * 1000);
+		for (int i = 0; i < sent; ++i)
+			if (recv(sk_pair[1], buf, sizeof(buf), 0) != sizeof(buf))
+				_exit(EXIT_FAILURE);
+		_exit(EXIT_SUCCESS);
+	}
+
+	flags = TEST(fcntl(sk_pair[0], F_GETFL), 0, 1);
+	TEST_SUCC(fcntl(sk_pair[0], F_SETFL, flags & ~O_NONBLOCK));
+	TEST_RES(send(sk_pair[0], buf, sizeof(buf), 0), _ret == sizeof(buf));
+	TEST_SUCC(fcntl(sk_pair[0], F_SETFL, flags));
+
+	TEST_RES(waitpid(pid, &status, 0),
+		 _ret == pid && WIFEXITED(status) &&
+			 WEXITSTATUS(status) == EXIT_SUCCESS);
+	TEST(drain(sk_pair[1]), EAGAIN, _ret == 1);
+}
+END_TEST()
+
+FN_TEST(sendto_recvfrom)
+{
+	struct sockaddr_un addr;
+	socklen_t addrlen = sizeof(addr);
+	char buf[16];
+
+	TEST_RES(sendto(sk_client, "ping", 4, 0, (struct sockaddr *)&server_addr,
+			sizeof(server_addr)),
+		 _ret == 4);
+	TEST_RES(recvfrom(sk_server, buf, sizeof(buf), 0,
+			  (struct sockaddr *)&addr, &addrlen),
+		 _ret == 4 && memcmp(buf, "ping", 4) == 0 &&
+			 strcmp(addr.sun_path, CLIENT_PATH) == 0);
+
+	TEST_RES(sendto(sk_unbound, "pong", 4, 0,
+			(struct sockaddr *)&server_addr, sizeof(server_addr)),
+		 _ret == 4);
+	TEST_RES(recv(sk_server, buf, sizeof(buf), 0),
+		 _ret == 4 && memcmp(buf, "pong", 4) == 0);
+}
+END_TEST()
+
+FN_TEST(connect)
+{
+	char buf[16];
+
+	TEST_ERRNO(send(sk_unbound, "data", 4, 0), ENOTCONN);
+
+	TEST_SUCC(connect(sk_unbound, (struct sockaddr *)&server_addr,
+			  sizeof(server_addr)));
+	TEST_RES(send(sk_unbound, "data", 4, 0), _ret == 4);
+	TEST_RES(recv(sk_server, buf, sizeof(buf), 0),
+		 _ret == 4 && memcmp(buf, "data", 4) == 0);
+}
+END_TEST()
+
+FN_TEST(closed_server)
+{
+	TEST_SUCC(close(sk_server));
+	TEST_ERRNO(send(sk_unbound, "data", 4, 0), ECONNREFUSED);
+	TEST_ERRNO(sendto(sk_client, "data", 4, 0,
+			  (struct sockaddr *)&server_addr, sizeof(server_addr)),
+		   ECONNREFUSED);
+
+	TEST_SUCC(unlink(SERVER_PATH));
+	TEST_SUCC(unlink(CLIENT_PATH));
+}
+END_TEST()
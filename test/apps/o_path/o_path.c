@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/o_path_test"
+#define TEST_FILE TEST_DIR "/file"
+#define TEST_LINK TEST_DIR "/link"
+
+static int test_file(void)
+{
+	char buf[8];
+	struct stat st;
+	int fd, ret;
+
+	// The write-only file is not readable, but it can still be opened as a path
+	fd = open(TEST_FILE, O_PATH | O_RDWR);
+	CHECK(fd >= 0, "open with O_PATH");
+
+	errno = 0;
+	ret = read(fd, buf, sizeof(buf));
+	CHECK(ret < 0 && errno == EBADF, "read should fail with EBADF");
+	errno = 0;
+	ret = write(fd, "hello", 5);
+	CHECK(ret < 0 && errno == EBADF, "write should fail with EBADF");
+
+	// The metadata cannot be changed through the handle
+	CHECK_ERROR(fchmod(fd, 0600), EBADF);
+	CHECK_ERROR(fchown(fd, 0, 0), EBADF);
+	CHECK_ERROR(ftruncate(fd, 0), EBADF);
+
+	CHECK(fstat(fd, &st) == 0, "fstat");
+	CHECK(S_ISREG(st.st_mode) && st.st_size == 5, "fstat result");
+	CHECK((fcntl(fd, F_GETFL) & O_PATH) != 0, "F_GETFL");
+
+	close(fd);
+	return 0;
+}
+
+static int test_symlink(void)
+{
+	struct stat st;
+	int fd;
+
+	fd = open(TEST_LINK, O_PATH | O_NOFOLLOW);
+	CHECK(fd >= 0, "open symlink with O_PATH | O_NOFOLLOW");
+	CHECK(fstat(fd, &st) == 0 && S_ISLNK(st.st_mode), "fstat symlink");
+	close(fd);
+
+	fd = open(TEST_LINK, O_PATH);
+	CHECK(fd >= 0, "open symlink with O_PATH");
+	CHECK(fstat(fd, &st) == 0 && S_ISREG(st.st_mode), "fstat link target");
+	close(fd);
+	return 0;
+}
+
+static int test_dir(void)
+{
+	char buf[64];
+	struct stat st;
+	int fd;
+
+	errno = 0;
+	fd = open(TEST_FILE, O_PATH | O_DIRECTORY);
+	CHECK(fd < 0 && errno == ENOTDIR, "O_DIRECTORY should fail with ENOTDIR");
+
+	fd = open(TEST_DIR, O_PATH | O_DIRECTORY);
+	CHECK(fd >= 0, "open dir with O_PATH");
+
+	CHECK(fstatat(fd, "file", &st, 0) == 0, "fstatat");
+	CHECK(fchdir(fd) == 0, "fchdir");
+	CHECK(getcwd(buf, sizeof(buf)) != NULL, "getcwd");
+	CHECK(strcmp(buf, TEST_DIR) == 0, "cwd after fchdir");
+
+	close(fd);
+	return chdir("/");
+}
+
+int main(void)
+{
+	int fd, ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	fd = open(TEST_FILE, O_WRONLY | O_CREAT, 0200);
+	if (fd < 0 || write(fd, "hello", 5) != 5) {
+		perror("create file");
+		goto out;
+	}
+	close(fd);
+	if (symlink(TEST_FILE, TEST_LINK) < 0) {
+		perror("symlink");
+		goto out;
+	}
+
+	if (test_file() < 0 || test_symlink() < 0 || test_dir() < 0)
+		goto out;
+
+	printf("O_PATH test passed\n");
+	ret = 0;
+out:
+	unlink(TEST_LINK);
+	unlink(TEST_FILE);
+	rmdir(TEST_DIR);
+	return ret;
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/open_flags_test"
+#define TEST_FILE TEST_DIR "/file"
+#define SUB_DIR TEST_DIR "/dir"
+#define FILE_LINK TEST_DIR "/file_link"
+#define DIR_LINK TEST_DIR "/dir_link"
+
+static int expect_error(const char *path, int flags, int expected)
+{
+	int fd;
+
+	errno = 0;
+	fd = open(path, flags);
+	if (fd >= 0) {
+		fprintf(stderr, "open %s should fail\n", path);
+		close(fd);
+		return -1;
+	}
+	if (errno != expected) {
+		fprintf(stderr, "open %s: expected %s, got %s\n", path,
+			strerror(expected), strerror(errno));
+		return -1;
+	}
+	return 0;
+}
+
+static int expect_success(const char *path, int flags)
+{
+	int fd;
+
+	fd = open(path, flags, 0644);
+	CHECK(fd >= 0, path);
+	close(fd);
+	return 0;
+}
+
+static int test_nofollow(void)
+{
+	// The trailing symlink must not be followed
+	CHECK(expect_error(FILE_LINK, O_RDONLY | O_NOFOLLOW, ELOOP) == 0,
+	      "O_NOFOLLOW on a symlink to a file");
+	CHECK(expect_error(DIR_LINK, O_RDONLY | O_NOFOLLOW, ELOOP) == 0,
+	      "O_NOFOLLOW on a symlink to a directory");
+	CHECK(expect_error(FILE_LINK, O_RDONLY | O_CREAT | O_EXCL | O_NOFOLLOW,
+			   EEXIST) == 0,
+	      "O_CREAT | O_EXCL | O_NOFOLLOW on a symlink");
+
+	// Other files are opened normally
+	CHECK(expect_success(TEST_FILE, O_RDONLY | O_NOFOLLOW) == 0,
+	      "O_NOFOLLOW on a regular file");
+	CHECK(expect_success(SUB_DIR, O_RDONLY | O_NOFOLLOW) == 0,
+	      "O_NOFOLLOW on a directory");
+
+	// Symlinks in earlier components and with a trailing slash are followed
+	CHECK(expect_success(DIR_LINK "/file", O_RDONLY | O_NOFOLLOW) == 0,
+	      "O_NOFOLLOW with a symlink in the middle");
+	CHECK(expect_success(DIR_LINK "/", O_RDONLY | O_NOFOLLOW) == 0,
+	      "O_NOFOLLOW with a trailing slash");
+	return 0;
+}
+
+static int test_directory(void)
+{
+	CHECK(expect_error(TEST_FILE, O_RDONLY | O_DIRECTORY, ENOTDIR) == 0,
+	      "O_DIRECTORY on a regular file");
+	CHECK(expect_error(FILE_LINK, O_RDONLY | O_DIRECTORY, ENOTDIR) == 0,
+	      "O_DIRECTORY on a symlink to a file");
+
+	CHECK(expect_success(SUB_DIR, O_RDONLY | O_DIRECTORY) == 0,
+	      "O_DIRECTORY on a directory");
+	CHECK(expect_success(DIR_LINK, O_RDONLY | O_DIRECTORY) == 0,
+	      "O_DIRECTORY on a symlink to a directory");
+	return 0;
+}
+
+int main(void)
+{
+	int fd, ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0 || mkdir(SUB_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	fd = open(TEST_FILE, O_WRONLY | O_CREAT, 0644);
+	if (fd < 0) {
+		perror("create file");
+		goto out;
+	}
+	close(fd);
+	fd = open(SUB_DIR "/file", O_WRONLY | O_CREAT, 0644);
+	if (fd < 0) {
+		perror("create file in dir");
+		goto out;
+	}
+	close(fd);
+	if (symlink(TEST_FILE, FILE_LINK) < 0 || symlink(SUB_DIR, DIR_LINK) < 0) {
+		perror("symlink");
+		goto out;
+	}
+
+	if (test_nofollow() < 0 || test_directory() < 0)
+		goto out;
+
+	printf("open flags test passed\n");
+	ret = 0;
+out:
+	unlink(DIR_LINK);
+	unlink(FILE_LINK);
+	unlink(SUB_DIR "/file");
+	rmdir(SUB_DIR);
+	unlink(TEST_FILE);
+	rmdir(TEST_DIR);
+	return ret;
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <fcntl.h>
+#include <stdio.h>
+#include <sys/stat.h>
+#include <time.h>
+#include <unistd.h>
+
+#define TEST_DIR "/tmp/lookup_bench"
+#define DEEP_DIR TEST_DIR "/a/b/c/d/e/f/g/h"
+#define DEEP_FILE DEEP_DIR "/file"
+#define SIBLING DEEP_DIR "/sibling"
+#define NUM_OF_CALLS 100000
+
+static const char *dirs[] = {
+	TEST_DIR,
+	TEST_DIR "/a",
+	TEST_DIR "/a/b",
+	TEST_DIR "/a/b/c",
+	TEST_DIR "/a/b/c/d",
+	TEST_DIR "/a/b/c/d/e",
+	TEST_DIR "/a/b/c/d/e/f",
+	TEST_DIR "/a/b/c/d/e/f/g",
+	DEEP_DIR,
+};
+
+#define NR_DIRS (sizeof(dirs) / sizeof(dirs[0]))
+
+static long elapsed_ns(const struct timespec *start, const struct timespec *end)
+{
+	return (end->tv_sec - start->tv_sec) * 1000000000L +
+	       (end->tv_nsec - start->tv_nsec);
+}
+
+// Stats the deep file repeatedly. If `invalidate` is set, a name in the deepest
+// directory is removed before each lookup, so that no lookup can be served by
+// the lookup cache and each one walks the whole path.
+static long bench_stat(int invalidate)
+{
+	struct timespec start, end;
+	struct stat st;
+	long total_ns = 0;
+
+	for (int i = 0; i < NUM_OF_CALLS; i++) {
+		if (invalidate) {
+			close(open(SIBLING, O_CREAT | O_WRONLY, 0644));
+			unlink(SIBLING);
+		}
+
+		clock_gettime(CLOCK_MONOTONIC, &start);
+		if (stat(DEEP_FILE, &st) < 0) {
+			perror("stat");
+			return -1;
+		}
+		clock_gettime(CLOCK_MONOTONIC, &end);
+		total_ns += elapsed_ns(&start, &end);
+	}
+
+	return total_ns / NUM_OF_CALLS;
+}
+
+int main()
+{
+	long repeated_latency, invalidated_latency;
+	int fd;
+
+	for (size_t i = 0; i < NR_DIRS; i++)
+		mkdir(dirs[i], 0755);
+	fd = open(DEEP_FILE, O_CREAT | O_WRONLY, 0644);
+	if (fd < 0) {
+		perror("open");
+		return 1;
+	}
+	close(fd);
+
+	invalidated_latency = bench_stat(1);
+	repeated_latency = bench_stat(0);
+	if (invalidated_latency < 0 || repeated_latency < 0)
+		return 1;
+
+	printf("Stat the path %s %d times.\n", DEEP_FILE, NUM_OF_CALLS);
+	printf("Invalidated lookup average latency: %ld nanoseconds.\n",
+	       invalidated_latency);
+	printf("Repeated lookup average latency: %ld nanoseconds.\n",
+	       repeated_latency);
+
+	unlink(DEEP_FILE);
+	for (size_t i = NR_DIRS; i > 0; i--)
+		rmdir(dirs[i - 1]);
+	return 0;
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/path_lookup_test"
+#define DEEP_DIR TEST_DIR "/a/b/c"
+#define DEEP_FILE DEEP_DIR "/file"
+#define NR_REPEATS 1000
+
+static int create_file(const char *path)
+{
+	int fd;
+
+	fd = open(path, O_CREAT | O_WRONLY, 0644);
+	if (fd < 0)
+		return -1;
+	return close(fd);
+}
+
+static int test_repeated_lookups(void)
+{
+	struct stat st;
+	int fd;
+
+	for (int i = 0; i < NR_REPEATS; i++) {
+		fd = open(DEEP_FILE, O_RDONLY);
+		CHECK(fd >= 0, "open the deep path");
+		CHECK(close(fd) == 0, "close");
+	}
+
+	// Relative lookups from the same directory give the same results.
+	CHECK(chdir(TEST_DIR "/a") == 0, "chdir");
+	for (int i = 0; i < NR_REPEATS; i++)
+		CHECK(stat("b/c/file", &st) == 0 && S_ISREG(st.st_mode),
+		      "stat the relative path");
+	CHECK(chdir("/") == 0, "chdir back");
+	return 0;
+}
+
+static int test_unlink(void)
+{
+	struct stat old_st, new_st;
+
+	CHECK(stat(DEEP_FILE, &old_st) == 0, "stat before unlink");
+	CHECK(unlink(DEEP_FILE) == 0, "unlink");
+	CHECK_ERROR(stat(DEEP_FILE, &new_st), ENOENT);
+
+	// The new file is found instead of the unlinked one.
+	CHECK(create_file(DEEP_FILE) == 0, "create the file again");
+	CHECK(stat(DEEP_FILE, &new_st) == 0, "stat after recreation");
+	return 0;
+}
+
+static int test_rename(void)
+{
+	struct stat st;
+
+	CHECK(stat(DEEP_FILE, &st) == 0, "stat before rename");
+	CHECK(rename(TEST_DIR "/a/b", TEST_DIR "/a/d") == 0, "rename");
+	CHECK_ERROR(stat(DEEP_FILE, &st), ENOENT);
+	CHECK(stat(TEST_DIR "/a/d/c/file", &st) == 0, "stat the new path");
+
+	// A file moved over the old name replaces it.
+	CHECK(mkdir(TEST_DIR "/a/b", 0755) == 0, "mkdir the old name");
+	CHECK(create_file(TEST_DIR "/a/b/other") == 0, "create another file");
+	CHECK(stat(TEST_DIR "/a/b/other", &st) == 0, "stat another file");
+	CHECK(rename(TEST_DIR "/a/d/c/file", TEST_DIR "/a/b/other") == 0,
+	      "rename over another file");
+	CHECK_ERROR(stat(TEST_DIR "/a/d/c/file", &st), ENOENT);
+	CHECK(stat(TEST_DIR "/a/b/other", &st) == 0, "stat the replaced file");
+
+	// Restore the original layout.
+	CHECK(unlink(TEST_DIR "/a/b/other") == 0, "unlink another file");
+	CHECK(rmdir(TEST_DIR "/a/b") == 0, "rmdir the old name");
+	CHECK(rename(TEST_DIR "/a/d", TEST_DIR "/a/b") == 0, "rename back");
+	CHECK(create_file(DEEP_FILE) == 0, "create the file again");
+	CHECK(stat(DEEP_FILE, &st) == 0, "stat after renaming back");
+	return 0;
+}
+
+static int test_mount(void)
+{
+	struct stat st;
+
+	CHECK(stat(DEEP_FILE, &st) == 0, "stat before mount");
+	CHECK(mount("tmpfs", TEST_DIR "/a/b", "tmpfs", 0, NULL) == 0, "mount");
+	CHECK_ERROR(stat(DEEP_FILE, &st), ENOENT);
+
+	CHECK(umount(TEST_DIR "/a/b") == 0, "umount");
+	CHECK(stat(DEEP_FILE, &st) == 0, "stat after umount");
+	return 0;
+}
+
+static int test_moved_cwd(void)
+{
+	struct stat parent_st, st;
+
+	// The parent of the working directory changes after it is moved.
+	CHECK(chdir(DEEP_DIR) == 0, "chdir");
+	CHECK(stat(TEST_DIR "/a/b", &parent_st) == 0, "stat the old parent");
+	CHECK(stat("..", &st) == 0 && st.st_ino == parent_st.st_ino,
+	      "stat the old parent via ..");
+	CHECK(rename(DEEP_DIR, TEST_DIR "/c") == 0, "move the working directory");
+	CHECK(stat(TEST_DIR, &parent_st) == 0, "stat the new parent");
+	CHECK(stat("..", &st) == 0 && st.st_ino == parent_st.st_ino,
+	      "stat the new parent via ..");
+
+	// Restore the original layout.
+	CHECK(rename(TEST_DIR "/c", DEEP_DIR) == 0, "move back");
+	CHECK(chdir("/") == 0, "chdir back");
+	return 0;
+}
+
+static int test_negative_lookups(void)
+{
+	struct stat st;
+	int fd;
+
+	// Each name is looked up repeatedly before it is created.
+	for (int i = 0; i < NR_REPEATS; i++)
+		CHECK_ERROR(stat(DEEP_DIR "/created", &st), ENOENT);
+	fd = creat(DEEP_DIR "/created", 0644);
+	CHECK(fd >= 0, "creat");
+	CHECK(close(fd) == 0, "close");
+	fd = open(DEEP_DIR "/created", O_RDONLY);
+	CHECK(fd >= 0, "open the created file");
+	CHECK(close(fd) == 0, "close");
+
+	CHECK_ERROR(stat(DEEP_DIR "/dir", &st), ENOENT);
+	CHECK(mkdir(DEEP_DIR "/dir", 0755) == 0, "mkdir");
+	CHECK(stat(DEEP_DIR "/dir", &st) == 0 && S_ISDIR(st.st_mode),
+	      "stat the created directory");
+
+	CHECK_ERROR(stat(DEEP_DIR "/link", &st), ENOENT);
+	CHECK(link(DEEP_DIR "/created", DEEP_DIR "/link") == 0, "link");
+	CHECK(stat(DEEP_DIR "/link", &st) == 0, "stat the hard link");
+
+	CHECK_ERROR(lstat(DEEP_DIR "/symlink", &st), ENOENT);
+	CHECK(symlink("created", DEEP_DIR "/symlink") == 0, "symlink");
+	CHECK(lstat(DEEP_DIR "/symlink", &st) == 0 && S_ISLNK(st.st_mode),
+	      "lstat the symlink");
+
+	CHECK_ERROR(stat(DEEP_DIR "/fifo", &st), ENOENT);
+	CHECK(mknod(DEEP_DIR "/fifo", S_IFIFO | 0644, 0) == 0, "mknod");
+	CHECK(stat(DEEP_DIR "/fifo", &st) == 0 && S_ISFIFO(st.st_mode),
+	      "stat the fifo");
+
+	CHECK_ERROR(stat(DEEP_DIR "/renamed", &st), ENOENT);
+	CHECK(rename(DEEP_DIR "/created", DEEP_DIR "/renamed") == 0, "rename");
+	CHECK(stat(DEEP_DIR "/renamed", &st) == 0, "stat the renamed file");
+	CHECK_ERROR(stat(DEEP_DIR "/created", &st), ENOENT);
+
+	CHECK(unlink(DEEP_DIR "/renamed") == 0, "unlink the renamed file");
+	CHECK(unlink(DEEP_DIR "/link") == 0, "unlink the hard link");
+	CHECK(unlink(DEEP_DIR "/symlink") == 0, "unlink the symlink");
+	CHECK(unlink(DEEP_DIR "/fifo") == 0, "unlink the fifo");
+	CHECK(rmdir(DEEP_DIR "/dir") == 0, "rmdir");
+	return 0;
+}
+
+static int setup_test_dir(void)
+{
+	CHECK(mkdir(TEST_DIR, 0755) == 0, "mkdir");
+	CHECK(mkdir(TEST_DIR "/a", 0755) == 0, "mkdir a");
+	CHECK(mkdir(TEST_DIR "/a/b", 0755) == 0, "mkdir b");
+	CHECK(mkdir(DEEP_DIR, 0755) == 0, "mkdir c");
+	CHECK(create_file(DEEP_FILE) == 0, "create the file");
+	return 0;
+}
+
+static int cleanup_test_dir(void)
+{
+	CHECK(unlink(DEEP_FILE) == 0, "unlink the file");
+	CHECK(rmdir(DEEP_DIR) == 0, "rmdir c");
+	CHECK(rmdir(TEST_DIR "/a/b") == 0, "rmdir b");
+	CHECK(rmdir(TEST_DIR "/a") == 0, "rmdir a");
+	CHECK(rmdir(TEST_DIR) == 0, "rmdir");
+	return 0;
+}
+
+int main(void)
+{
+	CHECK(setup_test_dir() == 0, "set up the test directory");
+	CHECK(test_repeated_lookups() == 0, "test the repeated lookups");
+	CHECK(test_unlink() == 0, "test unlink");
+	CHECK(test_rename() == 0, "test rename");
+	CHECK(test_mount() == 0, "test mount");
+	CHECK(test_moved_cwd() == 0, "test the moved working directory");
+	CHECK(test_negative_lookups() == 0, "test the negative lookups");
+	CHECK(cleanup_test_dir() == 0, "clean up the test directory");
+
+	printf("Test passed\n");
+	return 0;
+}
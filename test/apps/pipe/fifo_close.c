@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define FIFO_PATH "/tmp/fifo_close_test"
+
+static volatile sig_atomic_t sigpipe_count = 0;
+
+static void sigpipe_handler(int signum)
+{
+	(void)signum;
+	sigpipe_count++;
+}
+
+static short poll_events(int fd, short events)
+{
+	struct pollfd pfd = { .fd = fd, .events = events };
+
+	if (poll(&pfd, 1, 0) < 0)
+		return -1;
+	return pfd.revents;
+}
+
+int main(void)
+{
+	int rfd, wfd;
+	char buf[4];
+
+	unlink(FIFO_PATH);
+	CHECK(mkfifo(FIFO_PATH, 0666) == 0, "mkfifo");
+
+	// The reader sees EOF after the writer is closed
+	CHECK((rfd = open(FIFO_PATH, O_RDONLY | O_NONBLOCK)) >= 0,
+	      "open the named pipe for reading");
+	CHECK((wfd = open(FIFO_PATH, O_WRONLY)) >= 0,
+	      "open the named pipe for writing");
+	CHECK(write(wfd, "a", 1) == 1, "write");
+	CHECK(close(wfd) == 0, "close the writer");
+
+	CHECK(poll_events(rfd, POLLIN) == (POLLIN | POLLHUP),
+	      "POLLHUP of the reader");
+	CHECK(fcntl(rfd, F_SETFL, 0) == 0, "make the reader blocking");
+	CHECK(read(rfd, buf, sizeof(buf)) == 1 && buf[0] == 'a',
+	      "read the remaining data");
+	CHECK(read(rfd, buf, sizeof(buf)) == 0, "read EOF");
+	CHECK(close(rfd) == 0, "close the reader");
+
+	// The writer gets SIGPIPE after the reader is closed
+	CHECK(signal(SIGPIPE, sigpipe_handler) != SIG_ERR, "set SIGPIPE handler");
+	CHECK((rfd = open(FIFO_PATH, O_RDONLY | O_NONBLOCK)) >= 0,
+	      "open the named pipe for reading");
+	CHECK((wfd = open(FIFO_PATH, O_WRONLY)) >= 0,
+	      "open the named pipe for writing");
+	CHECK(close(rfd) == 0, "close the reader");
+
+	CHECK(poll_events(wfd, POLLOUT) & POLLERR, "POLLERR of the writer");
+	CHECK(write(wfd, "a", 1) < 0 && errno == EPIPE, "write EPIPE");
+	CHECK(sigpipe_count == 1, "receive SIGPIPE");
+	CHECK(close(wfd) == 0, "close the writer");
+
+	CHECK(unlink(FIFO_PATH) == 0, "unlink the named pipe");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <limits.h>
+#include <poll.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NR_RECORDS 64
+
+// Writes records of `PIPE_BUF` bytes filled with `c` to the pipe
+static void write_records(int fd, char c, int nonblocking)
+{
+	char record[PIPE_BUF];
+	struct pollfd pfd = { .fd = fd, .events = POLLOUT };
+	ssize_t len;
+	int i;
+
+	memset(record, c, sizeof(record));
+	if (nonblocking)
+		fcntl(fd, F_SETFL, O_NONBLOCK);
+
+	for (i = 0; i < NR_RECORDS; i++) {
+		while ((len = write(fd, record, sizeof(record))) < 0 &&
+		       errno == EAGAIN)
+			poll(&pfd, 1, -1);
+		// A record is written entirely or not at all
+		if (len != sizeof(record))
+			_exit(1);
+	}
+	_exit(0);
+}
+
+int main(void)
+{
+	static char data[2 * NR_RECORDS * PIPE_BUF];
+	size_t total = 0, i;
+	ssize_t len;
+	int fds[2], status;
+	pid_t writers[2];
+
+	CHECK(pipe(fds) == 0, "pipe");
+
+	// One writer blocks while the other does not
+	for (i = 0; i < 2; i++) {
+		CHECK((writers[i] = fork()) >= 0, "fork");
+		if (writers[i] == 0) {
+			close(fds[0]);
+			write_records(fds[1], 'a' + i, i);
+		}
+	}
+	close(fds[1]);
+
+	// Read slowly in small chunks so that the writers contend for room
+	while ((len = read(fds[0], data + total, 1000)) > 0) {
+		total += len;
+		usleep(100);
+	}
+	CHECK(len == 0, "read until EOF");
+	CHECK(total == sizeof(data), "read all records");
+
+	for (i = 0; i < 2; i++) {
+		CHECK(waitpid(writers[i], &status, 0) == writers[i], "waitpid");
+		CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+		      "write records");
+	}
+
+	// No record is split by the bytes of the other writer
+	for (i = 0; i < total; i++)
+		CHECK(data[i] == data[i - i % PIPE_BUF], "check a record");
+
+	close(fds[0]);
+
+	printf("pipe atomic test passed\n");
+	return 0;
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int test_packets(int fds[2])
+{
+	char buf[64];
+
+	// Each write becomes a separate packet
+	CHECK(write(fds[1], "hello", 5) == 5, "write the first packet");
+	CHECK(write(fds[1], "world!", 6) == 6, "write the second packet");
+
+	// Each read returns at most one packet
+	CHECK(read(fds[0], buf, sizeof(buf)) == 5, "read the first packet");
+	CHECK(memcmp(buf, "hello", 5) == 0, "the first packet");
+	CHECK(read(fds[0], buf, sizeof(buf)) == 6, "read the second packet");
+	CHECK(memcmp(buf, "world!", 6) == 0, "the second packet");
+
+	// The excess bytes are discarded if the buffer is too small
+	CHECK(write(fds[1], "truncated", 9) == 9, "write a long packet");
+	CHECK(write(fds[1], "next", 4) == 4, "write the next packet");
+	CHECK(read(fds[0], buf, 5) == 5, "read part of the long packet");
+	CHECK(memcmp(buf, "trunc", 5) == 0, "part of the long packet");
+	CHECK(read(fds[0], buf, sizeof(buf)) == 4, "read the next packet");
+	CHECK(memcmp(buf, "next", 4) == 0, "the next packet");
+	return 0;
+}
+
+static int test_flags(int fds[2])
+{
+	char buf[8];
+
+	CHECK((fcntl(fds[0], F_GETFL) & O_NONBLOCK) != 0,
+	      "O_NONBLOCK in F_GETFL");
+	CHECK((fcntl(fds[0], F_GETFD) & FD_CLOEXEC) != 0,
+	      "FD_CLOEXEC in F_GETFD");
+
+	// The pipe is empty
+	CHECK(read(fds[0], buf, sizeof(buf)) < 0 && errno == EAGAIN,
+	      "read an empty pipe");
+
+	// Reading returns EOF after the write end is closed
+	close(fds[1]);
+	CHECK(read(fds[0], buf, sizeof(buf)) == 0, "read at EOF");
+	close(fds[0]);
+	return 0;
+}
+
+int main(void)
+{
+	int fds[2];
+
+	if (pipe2(fds, O_DIRECT | O_NONBLOCK | O_CLOEXEC) < 0) {
+		perror("pipe2");
+		return 1;
+	}
+
+	if (test_packets(fds) < 0 || test_flags(fds) < 0)
+		return 1;
+
+	printf("pipe packet test passed\n");
+	return 0;
+}
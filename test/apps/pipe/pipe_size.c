@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+
+#define FIFO_PATH "/tmp/pipe_size_fifo"
+#define PIPE_MAX_SIZE_PATH "/proc/sys/fs/pipe-max-size"
+
+static char buf[PAGE_SIZE];
+
+// Writes to the non-blocking pipe until it is full
+static long fill_pipe(int fd)
+{
+	long total = 0;
+	ssize_t len;
+
+	while ((len = write(fd, buf, sizeof(buf))) > 0)
+		total += len;
+	if (errno != EAGAIN)
+		return -1;
+	return total;
+}
+
+static int is_writable(int fd)
+{
+	struct pollfd pfd = { .fd = fd, .events = POLLOUT };
+
+	return poll(&pfd, 1, 0) == 1 && (pfd.revents & POLLOUT);
+}
+
+static long read_pipe_max_size(void)
+{
+	char value[32];
+	ssize_t len;
+	int fd;
+
+	fd = open(PIPE_MAX_SIZE_PATH, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, value, sizeof(value) - 1);
+	close(fd);
+	if (len <= 0)
+		return -1;
+	value[len] = '\0';
+	return atol(value);
+}
+
+static int write_pipe_max_size(const char *value)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(PIPE_MAX_SIZE_PATH, O_WRONLY);
+	if (fd < 0)
+		return -1;
+	len = write(fd, value, strlen(value));
+	close(fd);
+	return len < 0 ? -1 : 0;
+}
+
+// Resizes a pipe to `size` bytes without privileges and returns the error number
+static int resize_unprivileged(int size)
+{
+	int fds[2], status;
+	pid_t pid;
+
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0) {
+		if (setuid(1000) < 0 || pipe(fds) < 0)
+			_exit(255);
+		_exit(fcntl(fds[0], F_SETPIPE_SZ, size) < 0 ? errno : 0);
+	}
+
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) != 255,
+	      "run the unprivileged child");
+	return WEXITSTATUS(status);
+}
+
+static int test_pipe_max_size(void)
+{
+	char old_max_size[32];
+	long max_size;
+
+	max_size = read_pipe_max_size();
+	CHECK(max_size == 1024 * 1024, "read pipe-max-size");
+	snprintf(old_max_size, sizeof(old_max_size), "%ld", max_size);
+
+	CHECK(write_pipe_max_size("2147483649") < 0 && errno == EINVAL,
+	      "write a too large size");
+	CHECK(write_pipe_max_size("invalid") < 0 && errno == EINVAL,
+	      "write an invalid size");
+	CHECK(read_pipe_max_size() == max_size, "pipe-max-size is unchanged");
+
+	// The size is rounded up to a power of two that is at least a page
+	CHECK(write_pipe_max_size("1") == 0, "write a size below a page");
+	CHECK(read_pipe_max_size() == PAGE_SIZE, "read the page size");
+	CHECK(write_pipe_max_size("5000") == 0, "lower pipe-max-size");
+	CHECK(read_pipe_max_size() == 2 * PAGE_SIZE,
+	      "read the lowered pipe-max-size");
+
+	// Unprivileged users cannot exceed the maximum size
+	CHECK(resize_unprivileged(2 * PAGE_SIZE) == 0,
+	      "resize to the maximum size");
+	CHECK(resize_unprivileged(4 * PAGE_SIZE) == EPERM,
+	      "resize above the maximum size");
+
+	CHECK(write_pipe_max_size(old_max_size) == 0, "restore pipe-max-size");
+	return 0;
+}
+
+int main(void)
+{
+	int fds[2];
+
+	CHECK(test_pipe_max_size() == 0, "test pipe-max-size");
+
+	CHECK(pipe2(fds, O_NONBLOCK) == 0, "pipe2");
+
+	// The default capacity is 16 pages
+	CHECK(fcntl(fds[0], F_GETPIPE_SZ) == 16 * PAGE_SIZE,
+	      "get the default capacity");
+	CHECK(fill_pipe(fds[1]) == 16 * PAGE_SIZE, "fill the default pipe");
+	CHECK(!is_writable(fds[1]), "POLLOUT of a full pipe");
+
+	// Enlarging the pipe makes it writable again
+	CHECK(fcntl(fds[1], F_SETPIPE_SZ, 32 * PAGE_SIZE) == 32 * PAGE_SIZE,
+	      "enlarge the pipe");
+	CHECK(is_writable(fds[1]), "POLLOUT of an enlarged pipe");
+	CHECK(fill_pipe(fds[1]) == 16 * PAGE_SIZE, "fill the enlarged pipe");
+
+	// The pipe cannot be shrunk below the buffered bytes
+	CHECK(fcntl(fds[0], F_SETPIPE_SZ, PAGE_SIZE) < 0 && errno == EBUSY,
+	      "shrink a full pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+
+	CHECK(pipe2(fds, O_NONBLOCK) == 0, "pipe2");
+
+	// The capacity is at least a page
+	CHECK(fcntl(fds[0], F_SETPIPE_SZ, 1) == PAGE_SIZE, "shrink the pipe");
+	CHECK(fcntl(fds[1], F_GETPIPE_SZ) == PAGE_SIZE,
+	      "get the shrunk capacity");
+	CHECK(fill_pipe(fds[1]) == PAGE_SIZE, "fill the shrunk pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+
+	CHECK(pipe2(fds, O_NONBLOCK) == 0, "pipe2");
+
+	// The capacity is rounded up to a power of two
+	CHECK(fcntl(fds[1], F_SETPIPE_SZ, 3 * PAGE_SIZE) == 4 * PAGE_SIZE,
+	      "round up the capacity");
+	CHECK(fcntl(fds[0], F_GETPIPE_SZ) == 4 * PAGE_SIZE,
+	      "get the rounded capacity");
+	CHECK(fill_pipe(fds[1]) == 4 * PAGE_SIZE, "fill the rounded pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+
+	// Named pipes can be resized as well
+	unlink(FIFO_PATH);
+	CHECK(mkfifo(FIFO_PATH, 0666) == 0, "mkfifo");
+	CHECK((fds[0] = open(FIFO_PATH, O_RDONLY | O_NONBLOCK)) >= 0,
+	      "open the named pipe for reading");
+	CHECK((fds[1] = open(FIFO_PATH, O_WRONLY | O_NONBLOCK)) >= 0,
+	      "open the named pipe for writing");
+	CHECK(fcntl(fds[0], F_GETPIPE_SZ) == 16 * PAGE_SIZE,
+	      "get the default capacity of the named pipe");
+	CHECK(fcntl(fds[1], F_SETPIPE_SZ, 5 * PAGE_SIZE) == 8 * PAGE_SIZE,
+	      "resize the named pipe");
+	CHECK(fcntl(fds[0], F_GETPIPE_SZ) == 8 * PAGE_SIZE,
+	      "get the capacity of the resized named pipe");
+	CHECK(fill_pipe(fds[1]) == 8 * PAGE_SIZE, "fill the named pipe");
+	CHECK(fcntl(fds[0], F_SETPIPE_SZ, PAGE_SIZE) < 0 && errno == EBUSY,
+	      "shrink a full named pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+	CHECK(unlink(FIFO_PATH) == 0, "unlink the named pipe");
+
+	// Only pipes can be resized
+	CHECK((fds[0] = open("/", O_RDONLY)) >= 0, "open a non-pipe file");
+	CHECK(fcntl(fds[0], F_SETPIPE_SZ, PAGE_SIZE) < 0 && errno == EBADF,
+	      "resize a non-pipe file");
+	CHECK(fcntl(fds[0], F_GETPIPE_SZ) < 0 && errno == EBADF,
+	      "get the capacity of a non-pipe file");
+	close(fds[0]);
+
+	printf("pipe size test passed\n");
+	return 0;
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <poll.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/select.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NSEC_PER_MSEC 1000000L
+#define NSEC_PER_SEC 1000000000L
+#define WAIT_MSEC 500
+
+static long read_msec(clockid_t clockid)
+{
+	struct timespec ts;
+
+	if (clock_gettime(clockid, &ts) < 0)
+		return -1;
+
+	return (ts.tv_sec * NSEC_PER_SEC + ts.tv_nsec) / NSEC_PER_MSEC;
+}
+
+int main(void)
+{
+	long wall_start, cpu_start, wall, cpu;
+	struct timeval tv = { .tv_sec = 0, .tv_usec = WAIT_MSEC * 1000 };
+	struct pollfd pfd;
+	fd_set readfds;
+	int fds[2];
+
+	CHECK(pipe(fds) == 0, "pipe");
+
+	// Select on an idle pipe until the timeout expires
+	FD_ZERO(&readfds);
+	FD_SET(fds[0], &readfds);
+	wall_start = read_msec(CLOCK_MONOTONIC);
+	cpu_start = read_msec(CLOCK_PROCESS_CPUTIME_ID);
+	CHECK(select(fds[0] + 1, &readfds, NULL, NULL, &tv) == 0, "select");
+	wall = read_msec(CLOCK_MONOTONIC) - wall_start;
+	cpu = read_msec(CLOCK_PROCESS_CPUTIME_ID) - cpu_start;
+	CHECK(!FD_ISSET(fds[0], &readfds), "check readfds");
+
+	// The thread sleeps instead of spinning while waiting
+	CHECK(wall >= WAIT_MSEC, "wait until timeout");
+	CHECK(cpu < WAIT_MSEC / 10, "sleep while waiting");
+
+	// The same holds for poll
+	pfd.fd = fds[0];
+	pfd.events = POLLIN;
+	wall_start = read_msec(CLOCK_MONOTONIC);
+	cpu_start = read_msec(CLOCK_PROCESS_CPUTIME_ID);
+	CHECK(poll(&pfd, 1, WAIT_MSEC) == 0, "poll");
+	wall = read_msec(CLOCK_MONOTONIC) - wall_start;
+	cpu = read_msec(CLOCK_PROCESS_CPUTIME_ID) - cpu_start;
+	CHECK(wall >= WAIT_MSEC && cpu < WAIT_MSEC / 10, "poll while idle");
+
+	// The pipe becomes readable once written
+	CHECK(write(fds[1], "a", 1) == 1, "write");
+	FD_ZERO(&readfds);
+	FD_SET(fds[0], &readfds);
+	CHECK(select(fds[0] + 1, &readfds, NULL, NULL, NULL) == 1 &&
+		      FD_ISSET(fds[0], &readfds),
+	      "select on a readable pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/uio.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAGE_SIZE 4096
+
+static char page[PAGE_SIZE];
+
+int main(void)
+{
+	struct iovec iov[2];
+	char buf[16];
+	ssize_t len;
+	int fds[2], i;
+
+	CHECK(pipe(fds) == 0, "pipe");
+
+	// The bytes of all IO vectors are copied in order
+	iov[0].iov_base = "hello ";
+	iov[0].iov_len = 6;
+	iov[1].iov_base = "world";
+	iov[1].iov_len = 5;
+	CHECK(vmsplice(fds[1], iov, 2, 0) == 11, "vmsplice two IO vectors");
+	CHECK(read(fds[0], buf, sizeof(buf)) == 11, "read the bytes");
+	CHECK(memcmp(buf, "hello world", 11) == 0, "the bytes");
+
+	// Fill the pipe without blocking
+	iov[0].iov_base = page;
+	iov[0].iov_len = sizeof(page);
+	for (i = 0; (len = vmsplice(fds[1], iov, 1,
+				    SPLICE_F_NONBLOCK | SPLICE_F_GIFT)) > 0;
+	     i++)
+		;
+	CHECK(i > 0, "vmsplice pages");
+	CHECK(len < 0 && errno == EAGAIN, "vmsplice to a full pipe");
+
+	close(fds[0]);
+	close(fds[1]);
+
+	// Only pipes are accepted
+	CHECK((fds[0] = open("/", O_RDONLY)) >= 0, "open a non-pipe file");
+	CHECK(vmsplice(fds[0], iov, 1, 0) < 0 && errno == EBADF,
+	      "vmsplice to a non-pipe file");
+	close(fds[0]);
+	CHECK(vmsplice(fds[0], iov, 1, 0) < 0 && errno == EBADF,
+	      "vmsplice to a closed file descriptor");
+
+	printf("vmsplice test passed\n");
+	return 0;
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <sys/mount.h>
+#include <sys/stat.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#define NEW_ROOT "/tmp/pivot_root_test"
+#define PUT_OLD NEW_ROOT "/old"
+#define MARKER_FILE NEW_ROOT "/marker"
+
+static int to_parent[2], to_child[2];
+
+static int pivot_root(const char *new_root, const char *put_old)
+{
+	return syscall(SYS_pivot_root, new_root, put_old);
+}
+
+static int run_pivot_root(void)
+{
+	struct stat st;
+
+	// Make the new root a mount point
+	if (mount(NEW_ROOT, NEW_ROOT, NULL, MS_BIND, NULL) < 0) {
+		perror("mount");
+		return 1;
+	}
+
+	// The new root must not be the current root
+	if (pivot_root("/", PUT_OLD) == 0) {
+		fprintf(stderr, "pivot_root to the current root succeeded\n");
+		return 1;
+	}
+
+	if (pivot_root(NEW_ROOT, PUT_OLD) < 0) {
+		perror("pivot_root");
+		return 1;
+	}
+
+	// `/` now resolves to the new root, and the old root is under put_old
+	if (stat("/marker", &st) < 0) {
+		perror("stat new root");
+		return 1;
+	}
+	if (stat("/old" NEW_ROOT, &st) < 0) {
+		perror("stat old root");
+		return 1;
+	}
+
+	// Let the parent check its root while the root of this process is switched
+	char c = 0;
+	if (write(to_parent[1], &c, 1) != 1 || read(to_child[0], &c, 1) != 1) {
+		perror("sync with the parent");
+		return 1;
+	}
+
+	// Switch back, since the mount tree is shared with other processes
+	if (pivot_root("/old", "/old" NEW_ROOT) < 0) {
+		perror("pivot_root back");
+		return 1;
+	}
+	if (stat(MARKER_FILE, &st) < 0) {
+		perror("stat after switching back");
+		return 1;
+	}
+
+	if (umount(NEW_ROOT) < 0) {
+		perror("umount");
+		return 1;
+	}
+
+	return 0;
+}
+
+int main(void)
+{
+	mkdir(NEW_ROOT, 0755);
+	mkdir(PUT_OLD, 0755);
+	int fd = open(MARKER_FILE, O_CREAT | O_WRONLY, 0644);
+	if (fd < 0) {
+		perror("open");
+		exit(EXIT_FAILURE);
+	}
+	close(fd);
+
+	if (pipe(to_parent) < 0 || pipe(to_child) < 0) {
+		perror("pipe");
+		exit(EXIT_FAILURE);
+	}
+
+	pid_t pid = fork();
+	if (pid < 0) {
+		perror("fork");
+		exit(EXIT_FAILURE);
+	}
+	if (pid == 0) {
+		exit(run_pivot_root());
+	}
+
+	// The root of this process is not changed by the child
+	close(to_parent[1]);
+	char c;
+	struct stat st;
+	if (read(to_parent[0], &c, 1) == 1) {
+		if (stat("/marker", &st) == 0 || errno != ENOENT ||
+		    stat(MARKER_FILE, &st) < 0) {
+			fprintf(stderr, "the root of the parent is changed\n");
+			exit(EXIT_FAILURE);
+		}
+		if (write(to_child[1], &c, 1) != 1) {
+			perror("resume the child");
+			exit(EXIT_FAILURE);
+		}
+	}
+
+	int status;
+	if (waitpid(pid, &status, 0) < 0 || !WIFEXITED(status) ||
+	    WEXITSTATUS(status) != 0) {
+		fprintf(stderr, "pivot_root test failed\n");
+		exit(EXIT_FAILURE);
+	}
+
+	unlink(MARKER_FILE);
+	rmdir(PUT_OLD);
+	rmdir(NEW_ROOT);
+
+	printf("pivot_root test passed\n");
+	return 0;
+}
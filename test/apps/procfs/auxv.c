@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/auxv.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define MAX_ENTRIES 64
+
+static unsigned long auxv[MAX_ENTRIES][2];
+static unsigned long auxv_partial[MAX_ENTRIES][2];
+
+// Reads `/proc/self/auxv` with reads of `chunk` bytes and returns the number of bytes
+static long read_auxv(void *buf, size_t len, size_t chunk)
+{
+	long total = 0;
+	ssize_t ret;
+	int fd;
+
+	fd = open("/proc/self/auxv", O_RDONLY);
+	if (fd < 0)
+		return -1;
+
+	while ((ret = read(fd, (char *)buf + total,
+			   len - total < chunk ? len - total : chunk)) > 0)
+		total += ret;
+
+	close(fd);
+	return ret < 0 ? -1 : total;
+}
+
+static int has_entry(int nr_entries, unsigned long key)
+{
+	int i;
+
+	for (i = 0; i < nr_entries; ++i)
+		if (auxv[i][0] == key)
+			return auxv[i][1] == getauxval(key);
+
+	return 0;
+}
+
+int main(void)
+{
+	long len;
+	int nr_entries;
+
+	len = read_auxv(auxv, sizeof(auxv), sizeof(auxv));
+	CHECK(len > 0 && len % sizeof(auxv[0]) == 0, "read /proc/self/auxv");
+	nr_entries = len / sizeof(auxv[0]);
+
+	// The vector is terminated by AT_NULL
+	CHECK(auxv[nr_entries - 1][0] == AT_NULL, "check AT_NULL");
+
+	CHECK(has_entry(nr_entries, AT_PAGESZ), "check AT_PAGESZ");
+	CHECK(has_entry(nr_entries, AT_ENTRY), "check AT_ENTRY");
+	CHECK(has_entry(nr_entries, AT_RANDOM), "check AT_RANDOM");
+	CHECK(has_entry(nr_entries, AT_EXECFN), "check AT_EXECFN");
+	CHECK(has_entry(nr_entries, AT_PLATFORM), "check AT_PLATFORM");
+
+	// Partial reads return the same content
+	CHECK(read_auxv(auxv_partial, sizeof(auxv_partial), 5) == len,
+	      "read /proc/self/auxv partially");
+	CHECK(memcmp(auxv, auxv_partial, len) == 0, "compare partial reads");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static char buf[4096];
+
+static ssize_t read_cmdline(const char *path)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(path, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, sizeof(buf));
+	close(fd);
+	return len;
+}
+
+int main(int argc, char *argv[])
+{
+	char path[64], *pos;
+	ssize_t len;
+	pid_t pid;
+
+	// Run again with known arguments.
+	if (argc == 1) {
+		execl("/proc/self/exe", argv[0], "first", "", "third", NULL);
+		CHECK(0, "execl");
+	}
+	CHECK(argc == 4, "check the arguments");
+
+	// The arguments are separated and terminated by NUL.
+	len = read_cmdline("/proc/self/cmdline");
+	CHECK(len > 0 && buf[len - 1] == '\0', "read /proc/self/cmdline");
+	pos = buf;
+	for (int i = 0; i < argc; i++) {
+		CHECK(pos < buf + len && strcmp(pos, argv[i]) == 0,
+		      "compare the argument");
+		pos += strlen(pos) + 1;
+	}
+	CHECK(pos == buf + len, "read all the arguments");
+
+	// A zombie process has an empty command line.
+	pid = fork();
+	if (pid == 0)
+		_exit(0);
+	CHECK(pid > 0, "fork");
+	CHECK(waitid(P_PID, pid, NULL, WEXITED | WNOWAIT) == 0,
+	      "wait for the child to exit");
+	snprintf(path, sizeof(path), "/proc/%d/cmdline", pid);
+	CHECK(read_cmdline(path) == 0, "read the command line of a zombie");
+	CHECK(waitpid(pid, NULL, 0) == pid, "reap the child");
+
+	printf("Test passed\n");
+	return 0;
+}
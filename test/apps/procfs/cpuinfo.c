@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+#include <errno.h>
+#include <sched.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "check.h"
+
+// Returns the value after "key\t: " if the line has the key
+static const char *field_value(const char *line, const char *key)
+{
+	size_t len = strlen(key);
+
+	if (strncmp(line, key, len) != 0 || line[len] != '\t')
+		return NULL;
+	line = strstr(line, ": ");
+	return line == NULL ? NULL : line + 2;
+}
+
+int main(void)
+{
+	char line[4096];
+	const char *value;
+	int nr_processors = 0, nr_vendors = 0, nr_models = 0, nr_flags = 0,
+	    nr_mhz = 0;
+	double mhz;
+	cpu_set_t cpu_set;
+	FILE *file;
+
+	file = fopen("/proc/cpuinfo", "r");
+	CHECK(file != NULL, "fopen");
+	while (fgets(line, sizeof(line), file) != NULL) {
+		if ((value = field_value(line, "processor")) != NULL) {
+			CHECK(atoi(value) == nr_processors, "processor");
+			nr_processors++;
+		} else if ((value = field_value(line, "vendor_id")) != NULL) {
+			CHECK(strlen(value) > 1, "vendor_id");
+			nr_vendors++;
+		} else if ((value = field_value(line, "model name")) != NULL) {
+			nr_models++;
+		} else if ((value = field_value(line, "cpu MHz")) != NULL) {
+			CHECK(sscanf(value, "%lf", &mhz) == 1 && mhz > 0,
+			      "cpu MHz");
+			nr_mhz++;
+		} else if ((value = field_value(line, "flags")) != NULL) {
+			// All x86-64 CPUs have these features
+			CHECK(strstr(value, "fpu ") == value, "flags");
+			CHECK(strstr(value, " sse2 ") != NULL, "flags");
+			CHECK(strstr(value, " lm") != NULL, "flags");
+			nr_flags++;
+		}
+	}
+	fclose(file);
+
+	// There is one block for each CPU that the scheduler can use
+	CHECK(sched_getaffinity(0, sizeof(cpu_set), &cpu_set) == 0,
+	      "sched_getaffinity");
+	CHECK(nr_processors == CPU_COUNT(&cpu_set), "number of processors");
+	CHECK(nr_vendors == nr_processors && nr_models == nr_processors &&
+		      nr_mhz == nr_processors && nr_flags == nr_processors,
+	      "number of fields");
+
+	printf("Test passed\n");
+	return 0;
+}
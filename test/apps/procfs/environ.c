@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static char buf[4096];
+
+static ssize_t read_environ(const char *path)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(path, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, sizeof(buf));
+	close(fd);
+	return len;
+}
+
+int main(int argc, char *argv[])
+{
+	char *const test_envp[] = { "FIRST=1", "EMPTY=", "LAST=last", NULL };
+	char path[64], *pos;
+	int status, i;
+	ssize_t len;
+	pid_t pid;
+
+	// Run again with a known environment.
+	if (argc == 1) {
+		execle("/proc/self/exe", argv[0], "child", NULL, test_envp);
+		CHECK(0, "execle");
+	}
+
+	// The variables are separated and terminated by NUL.
+	len = read_environ("/proc/self/environ");
+	CHECK(len > 0 && buf[len - 1] == '\0', "read /proc/self/environ");
+	pos = buf;
+	for (i = 0; test_envp[i] != NULL; i++) {
+		CHECK(pos < buf + len && strcmp(pos, test_envp[i]) == 0,
+		      "compare the variable");
+		pos += strlen(pos) + 1;
+	}
+	CHECK(pos == buf + len, "read all the variables");
+
+	// Other users cannot read the environment.
+	pid = fork();
+	if (pid == 0) {
+		pause();
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	snprintf(path, sizeof(path), "/proc/%d/environ", pid);
+	CHECK(read_environ(path) > 0, "read the environment of the child");
+
+	if (fork() == 0) {
+		if (setuid(65534) < 0)
+			_exit(1);
+		if (read_environ(path) >= 0 || errno != EACCES)
+			_exit(2);
+		_exit(0);
+	}
+	CHECK(wait(&status) > 0 && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "read the environment as another user");
+
+	CHECK(kill(pid, SIGKILL) == 0, "kill");
+	CHECK(waitpid(pid, NULL, 0) == pid, "reap the child");
+
+	printf("Test passed\n");
+	return 0;
+}
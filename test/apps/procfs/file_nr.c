@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NR_FILES 16
+
+static int read_file_nr(unsigned long *nr_allocated, unsigned long *nr_free,
+			unsigned long *max)
+{
+	char buf[128];
+	ssize_t len;
+	int fd;
+
+	fd = open("/proc/sys/fs/file-nr", O_RDONLY);
+	CHECK(fd >= 0, "open file-nr");
+	len = read(fd, buf, sizeof(buf) - 1);
+	CHECK(len > 0, "read file-nr");
+	CHECK(close(fd) == 0, "close file-nr");
+	buf[len] = '\0';
+
+	CHECK(sscanf(buf, "%lu\t%lu\t%lu\n", nr_allocated, nr_free, max) == 3,
+	      "parse file-nr");
+	return 0;
+}
+
+int main(void)
+{
+	unsigned long before, opened, duplicated, closed, nr_free, max;
+	int fds[NR_FILES], dup_fds[NR_FILES];
+
+	CHECK(read_file_nr(&before, &nr_free, &max) == 0, "read file-nr");
+	CHECK(before > 0, "check the allocated files");
+	CHECK(nr_free == 0, "check the free files");
+	CHECK(max >= before, "check the maximum files");
+
+	for (int i = 0; i < NR_FILES; i++) {
+		fds[i] = open("/dev/null", O_RDONLY);
+		CHECK(fds[i] >= 0, "open");
+	}
+	CHECK(read_file_nr(&opened, &nr_free, &max) == 0, "read file-nr");
+	CHECK(opened >= before + NR_FILES, "check the opened files");
+
+	// Duplicated file descriptors refer to the same open files.
+	for (int i = 0; i < NR_FILES; i++) {
+		dup_fds[i] = dup(fds[i]);
+		CHECK(dup_fds[i] >= 0, "dup");
+	}
+	CHECK(read_file_nr(&duplicated, &nr_free, &max) == 0, "read file-nr");
+	CHECK(duplicated < opened + NR_FILES, "check the duplicated files");
+
+	for (int i = 0; i < NR_FILES; i++) {
+		CHECK(close(fds[i]) == 0, "close");
+		CHECK(close(dup_fds[i]) == 0, "close");
+	}
+	CHECK(read_file_nr(&closed, &nr_free, &max) == 0, "read file-nr");
+	CHECK(closed + NR_FILES <= duplicated, "check the closed files");
+
+	printf("Test passed\n");
+	return 0;
+}
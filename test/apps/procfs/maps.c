@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define FILE_PATH "/tmp/maps_test_file"
+#define PAGE_SIZE 4096
+
+struct mapping {
+	unsigned long start, end, offset, inode;
+	char perms[5];
+	char pathname[256];
+};
+
+// Finds the mapping that contains `addr` in `/proc/self/maps`.
+static int find_mapping(void *addr, struct mapping *mapping)
+{
+	char line[512];
+	FILE *file;
+	int found = -1;
+
+	file = fopen("/proc/self/maps", "r");
+	if (file == NULL)
+		return -1;
+	while (fgets(line, sizeof(line), file) != NULL) {
+		mapping->pathname[0] = '\0';
+		if (sscanf(line, "%lx-%lx %4s %lx %*x:%*x %lu %255s",
+			   &mapping->start, &mapping->end, mapping->perms,
+			   &mapping->offset, &mapping->inode,
+			   mapping->pathname) < 5)
+			break;
+		if (mapping->start <= (unsigned long)addr &&
+		    (unsigned long)addr < mapping->end) {
+			found = 0;
+			break;
+		}
+	}
+	fclose(file);
+	return found;
+}
+
+int main(void)
+{
+	char exe_path[256], *heap;
+	struct mapping mapping;
+	int stack_var, fd;
+	ssize_t len;
+	void *addr;
+
+	// The code of the executable is mapped from its file.
+	len = readlink("/proc/self/exe", exe_path, sizeof(exe_path) - 1);
+	CHECK(len > 0, "readlink");
+	exe_path[len] = '\0';
+	CHECK(find_mapping((void *)main, &mapping) == 0, "find the code");
+	CHECK(strcmp(mapping.perms, "r-xp") == 0, "check the code perms");
+	CHECK(strcmp(mapping.pathname, exe_path) == 0, "check the code path");
+	CHECK(mapping.inode != 0, "check the code inode");
+
+	// The stack and the heap are labeled.
+	CHECK(find_mapping(&stack_var, &mapping) == 0, "find the stack");
+	CHECK(strcmp(mapping.perms, "rw-p") == 0, "check the stack perms");
+	CHECK(strcmp(mapping.pathname, "[stack]") == 0, "check the stack name");
+
+	heap = sbrk(PAGE_SIZE);
+	CHECK(heap != (void *)-1, "sbrk");
+	heap[0] = 1;
+	CHECK(find_mapping(heap, &mapping) == 0, "find the heap");
+	CHECK(strcmp(mapping.perms, "rw-p") == 0, "check the heap perms");
+	CHECK(strcmp(mapping.pathname, "[heap]") == 0, "check the heap name");
+
+	// Anonymous mappings have no name.
+	addr = mmap(NULL, PAGE_SIZE, PROT_READ, MAP_PRIVATE | MAP_ANONYMOUS,
+		    -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap anonymous");
+	CHECK(find_mapping(addr, &mapping) == 0, "find the anonymous mapping");
+	CHECK(mapping.start == (unsigned long)addr &&
+		      mapping.end == (unsigned long)addr + PAGE_SIZE,
+	      "check the anonymous range");
+	CHECK(strcmp(mapping.perms, "r--p") == 0, "check the anonymous perms");
+	CHECK(mapping.offset == 0 && mapping.inode == 0 &&
+		      mapping.pathname[0] == '\0',
+	      "check the anonymous name");
+	CHECK(munmap(addr, PAGE_SIZE) == 0, "munmap anonymous");
+
+	// Shared file mappings show the file and the offset in it.
+	fd = open(FILE_PATH, O_RDWR | O_CREAT | O_TRUNC, 0644);
+	CHECK(fd >= 0, "open");
+	CHECK(ftruncate(fd, 2 * PAGE_SIZE) == 0, "ftruncate");
+	addr = mmap(NULL, PAGE_SIZE, PROT_READ | PROT_WRITE, MAP_SHARED, fd,
+		    PAGE_SIZE);
+	CHECK(addr != MAP_FAILED, "mmap the file");
+	CHECK(find_mapping(addr, &mapping) == 0, "find the file mapping");
+	CHECK(strcmp(mapping.perms, "rw-s") == 0, "check the file perms");
+	CHECK(mapping.offset == PAGE_SIZE, "check the file offset");
+	CHECK(strcmp(mapping.pathname, FILE_PATH) == 0, "check the file path");
+	CHECK(munmap(addr, PAGE_SIZE) == 0, "munmap the file");
+	CHECK(close(fd) == 0, "close");
+	CHECK(unlink(FILE_PATH) == 0, "unlink");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "check.h"
+
+// Reads the value of a field in "Key:   value kB" format
+static long read_field(const char *key)
+{
+	char line[128], name[64];
+	unsigned long value;
+	long result = -1;
+	FILE *file = fopen("/proc/meminfo", "r");
+
+	if (file == NULL)
+		return -1;
+	while (fgets(line, sizeof(line), file) != NULL) {
+		char *colon = strchr(line, ':');
+
+		if (colon == NULL || colon - line >= (long)sizeof(name))
+			continue;
+		memcpy(name, line, colon - line);
+		name[colon - line] = '\0';
+		if (strcmp(name, key) != 0)
+			continue;
+		if (sscanf(colon + 1, " %lu kB\n", &value) == 1 &&
+		    strcmp(line + strlen(line) - 4, " kB\n") == 0)
+			result = value;
+		break;
+	}
+	fclose(file);
+	return result;
+}
+
+int main(void)
+{
+	long total, free, available;
+
+	total = read_field("MemTotal");
+	free = read_field("MemFree");
+	available = read_field("MemAvailable");
+	CHECK(total > 0, "MemTotal");
+	CHECK(free > 0 && free <= total, "MemFree");
+	CHECK(available > 0 && available <= total, "MemAvailable");
+	CHECK(read_field("Buffers") >= 0, "Buffers");
+	CHECK(read_field("Cached") >= 0, "Cached");
+	CHECK(read_field("SwapTotal") >= 0, "SwapTotal");
+	CHECK(read_field("SwapFree") >= 0, "SwapFree");
+	CHECK(read_field("SwapFree") <= read_field("SwapTotal"), "SwapFree");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/resource.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NR_OPEN_PATH "/proc/sys/fs/nr_open"
+#define TEST_NR_OPEN 128
+
+static long read_nr_open(void)
+{
+	char buf[32];
+	ssize_t len;
+	int fd;
+
+	fd = open(NR_OPEN_PATH, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, sizeof(buf) - 1);
+	close(fd);
+	if (len <= 0)
+		return -1;
+	buf[len] = '\0';
+	return atol(buf);
+}
+
+static int write_nr_open(const char *value)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(NR_OPEN_PATH, O_WRONLY);
+	if (fd < 0)
+		return -1;
+	len = write(fd, value, strlen(value));
+	close(fd);
+	return len < 0 ? -1 : 0;
+}
+
+int main(void)
+{
+	struct rlimit limit;
+	char old_nr_open[32];
+	int fd, fds[2], last_fd;
+	long nr_open;
+
+	nr_open = read_nr_open();
+	CHECK(nr_open > 0, "read nr_open");
+	snprintf(old_nr_open, sizeof(old_nr_open), "%ld", nr_open);
+
+	// The limit cannot be raised above nr_open, even by root.
+	limit.rlim_cur = 16;
+	limit.rlim_max = nr_open + 1;
+	CHECK_ERROR(setrlimit(RLIMIT_NOFILE, &limit), EPERM);
+
+	CHECK_ERROR(write_nr_open("1"), EINVAL);
+	CHECK_ERROR(write_nr_open("invalid"), EINVAL);
+	CHECK(read_nr_open() == nr_open, "nr_open is unchanged");
+
+	// No file descriptor can be allocated at or above nr_open,
+	// even if the limit was raised before nr_open is lowered.
+	limit.rlim_cur = 2 * TEST_NR_OPEN;
+	limit.rlim_max = 2 * TEST_NR_OPEN;
+	CHECK(setrlimit(RLIMIT_NOFILE, &limit) == 0, "setrlimit");
+	CHECK(write_nr_open("128") == 0, "lower nr_open");
+	CHECK(read_nr_open() == TEST_NR_OPEN, "read the lowered nr_open");
+	CHECK_ERROR(setrlimit(RLIMIT_NOFILE, &limit), EPERM);
+
+	last_fd = -1;
+	while ((fd = open("/dev/null", O_RDONLY)) >= 0)
+		last_fd = fd;
+	CHECK(errno == EMFILE, "open until EMFILE");
+	CHECK(last_fd == TEST_NR_OPEN - 1, "check the last fd");
+	CHECK_ERROR(dup(0), EMFILE);
+	CHECK_ERROR(fcntl(0, F_DUPFD, 0), EMFILE);
+
+	// Both ends of a pipe need a file descriptor.
+	CHECK(close(last_fd) == 0, "close the last fd");
+	CHECK_ERROR(pipe(fds), EMFILE);
+	fd = open("/dev/null", O_RDONLY);
+	CHECK(fd == last_fd, "reuse the last fd");
+
+	for (fd = 3; fd <= last_fd; fd++)
+		CHECK(close(fd) == 0, "close");
+	CHECK(write_nr_open(old_nr_open) == 0, "restore nr_open");
+
+	printf("Test passed\n");
+	return 0;
+}
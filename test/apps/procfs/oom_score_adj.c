@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <pthread.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define OOM_SCORE_ADJ_PATH "/proc/self/oom_score_adj"
+
+static int read_value(const char *path, int *value)
+{
+	char buf[16];
+	ssize_t len;
+	int fd;
+
+	fd = open(path, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, sizeof(buf) - 1);
+	close(fd);
+	if (len <= 0)
+		return -1;
+	buf[len] = '\0';
+	*value = atoi(buf);
+	return 0;
+}
+
+static int write_value(int fd, const char *value)
+{
+	if (write(fd, value, strlen(value)) != (ssize_t)strlen(value))
+		return -1;
+	return 0;
+}
+
+static void *thread_main(void *arg)
+{
+	int *value = arg;
+
+	if (read_value("/proc/thread-self/oom_score_adj", value) < 0)
+		*value = -1;
+	return NULL;
+}
+
+int main(void)
+{
+	int fd, value, status;
+	pthread_t thread;
+	pid_t pid;
+
+	CHECK(read_value(OOM_SCORE_ADJ_PATH, &value) == 0 && value == 0,
+	      "read the initial value");
+
+	fd = open(OOM_SCORE_ADJ_PATH, O_WRONLY);
+	CHECK(fd >= 0, "open");
+	CHECK(write_value(fd, "500\n") == 0, "write");
+	CHECK(read_value(OOM_SCORE_ADJ_PATH, &value) == 0 && value == 500,
+	      "read the written value");
+
+	// The value is shared by the threads in the process.
+	CHECK(pthread_create(&thread, NULL, thread_main, &value) == 0,
+	      "pthread_create");
+	CHECK(pthread_join(thread, NULL) == 0 && value == 500,
+	      "read the value from another thread");
+
+	// Invalid values are rejected.
+	CHECK(write_value(fd, "1001") < 0 && errno == EINVAL,
+	      "write a value out of range");
+	CHECK(write_value(fd, "-1001") < 0 && errno == EINVAL,
+	      "write a negative value out of range");
+	CHECK(write_value(fd, "abc") < 0 && errno == EINVAL,
+	      "write a value that is not a number");
+	CHECK(read_value(OOM_SCORE_ADJ_PATH, &value) == 0 && value == 500,
+	      "keep the value after invalid writes");
+
+	// The value is inherited by the child.
+	pid = fork();
+	if (pid == 0) {
+		if (read_value(OOM_SCORE_ADJ_PATH, &value) < 0 || value != 500)
+			_exit(1);
+		// Disabling the OOM killing requires the capability.
+		if (setuid(65534) < 0)
+			_exit(2);
+		if (write_value(fd, "-1000") == 0 || errno != EACCES)
+			_exit(3);
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "check the child");
+
+	CHECK(write_value(fd, "-1000") == 0, "write the minimum value");
+	CHECK(read_value(OOM_SCORE_ADJ_PATH, &value) == 0 && value == -1000,
+	      "read the minimum value");
+	CHECK(write_value(fd, "0") == 0, "restore the value");
+	CHECK(close(fd) == 0, "close");
+
+	printf("Test passed\n");
+	return 0;
+}
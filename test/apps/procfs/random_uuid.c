@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+// The canonical form of a UUID (36 characters) followed by a newline
+#define UUID_LEN 37
+
+static int read_uuid(const char *path, char *buf)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(path, O_RDONLY);
+	if (fd < 0)
+		return -1;
+	len = read(fd, buf, UUID_LEN + 1);
+	close(fd);
+	if (len != UUID_LEN)
+		return -1;
+
+	buf[UUID_LEN] = '\0';
+	return 0;
+}
+
+static int is_valid_uuid(const char *uuid)
+{
+	int i;
+
+	for (i = 0; i < UUID_LEN - 1; ++i) {
+		if (i == 8 || i == 13 || i == 18 || i == 23) {
+			if (uuid[i] != '-')
+				return 0;
+		} else if (!strchr("0123456789abcdef", uuid[i])) {
+			return 0;
+		}
+	}
+
+	// The version is 4 and the variant is 1
+	return uuid[14] == '4' && strchr("89ab", uuid[19]) &&
+	       uuid[UUID_LEN - 1] == '\n';
+}
+
+int main(void)
+{
+	char uuid1[UUID_LEN + 1], uuid2[UUID_LEN + 1];
+
+	// Each read of uuid returns a new UUID
+	CHECK(read_uuid("/proc/sys/kernel/random/uuid", uuid1) == 0, "read uuid");
+	CHECK(read_uuid("/proc/sys/kernel/random/uuid", uuid2) == 0, "read uuid");
+	CHECK(is_valid_uuid(uuid1) && is_valid_uuid(uuid2), "check uuid");
+	CHECK(strcmp(uuid1, uuid2) != 0, "compare uuid");
+
+	// The boot_id stays the same
+	CHECK(read_uuid("/proc/sys/kernel/random/boot_id", uuid1) == 0,
+	      "read boot_id");
+	CHECK(read_uuid("/proc/sys/kernel/random/boot_id", uuid2) == 0,
+	      "read boot_id");
+	CHECK(is_valid_uuid(uuid1), "check boot_id");
+	CHECK(strcmp(uuid1, uuid2) == 0, "compare boot_id");
+
+	printf("Test passed\n");
+	return 0;
+}
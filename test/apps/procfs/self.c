@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <dirent.h>
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/syscall.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int check_link(const char *path, const char *expected)
+{
+	char buf[64];
+	ssize_t len;
+
+	len = readlink(path, buf, sizeof(buf) - 1);
+	if (len < 0)
+		return -1;
+	buf[len] = '\0';
+	return strcmp(buf, expected) == 0 ? 0 : -1;
+}
+
+static int has_entry(const char *dir_path, const char *name)
+{
+	struct dirent *entry;
+	int found = 0;
+	DIR *dir;
+
+	dir = opendir(dir_path);
+	if (dir == NULL)
+		return 0;
+	while ((entry = readdir(dir)) != NULL) {
+		if (strcmp(entry->d_name, name) == 0)
+			found = 1;
+	}
+	closedir(dir);
+	return found;
+}
+
+int main(void)
+{
+	char expected[64], path[64], tid_str[16];
+	pid_t pid = getpid();
+	pid_t tid = syscall(SYS_gettid);
+	int fd;
+
+	// `/proc/self` and `/proc/thread-self` point to the caller.
+	snprintf(expected, sizeof(expected), "%d", pid);
+	CHECK(check_link("/proc/self", expected) == 0, "readlink /proc/self");
+	snprintf(expected, sizeof(expected), "%d/task/%d", pid, tid);
+	CHECK(check_link("/proc/thread-self", expected) == 0,
+	      "readlink /proc/thread-self");
+	CHECK(has_entry("/proc", "self") && has_entry("/proc", "thread-self"),
+	      "list /proc");
+
+	// The thread directories are listed in the task directory.
+	snprintf(tid_str, sizeof(tid_str), "%d", tid);
+	CHECK(has_entry("/proc/self/task", tid_str), "list /proc/self/task");
+	CHECK(has_entry("/proc/self", "task"), "list /proc/self");
+
+	// The files are accessible through the symlinks.
+	fd = open("/proc/thread-self/comm", O_RDONLY);
+	CHECK(fd >= 0, "open /proc/thread-self/comm");
+	CHECK(close(fd) == 0, "close");
+	snprintf(path, sizeof(path), "/proc/%d/task/%d/exe", pid, tid);
+	CHECK(access(path, F_OK) == 0, "access /proc/[pid]/task/[tid]/exe");
+
+	// Threads that do not exist are not found.
+	snprintf(path, sizeof(path), "/proc/%d/task/%d", pid, tid + 100000);
+	CHECK(access(path, F_OK) < 0 && errno == ENOENT,
+	      "access a thread that does not exist");
+
+	printf("Test passed\n");
+	return 0;
+}
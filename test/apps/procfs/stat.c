@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+struct proc_stat {
+	unsigned long long cpu[10];
+	int nr_cpus;
+	unsigned long long ctxt;
+	unsigned long long btime;
+	unsigned long long processes;
+};
+
+static int read_proc_stat(struct proc_stat *stat)
+{
+	char line[1024];
+	unsigned long long *c = stat->cpu;
+	FILE *file = fopen("/proc/stat", "r");
+
+	if (file == NULL)
+		return -1;
+	memset(stat, 0, sizeof(*stat));
+	while (fgets(line, sizeof(line), file) != NULL) {
+		if (strncmp(line, "cpu  ", 5) == 0) {
+			if (sscanf(line + 5,
+				   "%llu %llu %llu %llu %llu %llu %llu %llu %llu %llu\n",
+				   &c[0], &c[1], &c[2], &c[3], &c[4], &c[5],
+				   &c[6], &c[7], &c[8], &c[9]) != 10)
+				goto err;
+		} else if (strncmp(line, "cpu", 3) == 0) {
+			int id;
+
+			if (sscanf(line, "cpu%d ", &id) != 1 ||
+			    id != stat->nr_cpus)
+				goto err;
+			stat->nr_cpus++;
+		} else {
+			sscanf(line, "ctxt %llu\n", &stat->ctxt);
+			sscanf(line, "btime %llu\n", &stat->btime);
+			sscanf(line, "processes %llu\n", &stat->processes);
+		}
+	}
+	fclose(file);
+	return 0;
+err:
+	fclose(file);
+	return -1;
+}
+
+int main(void)
+{
+	struct proc_stat before, after;
+	struct timespec now, boot;
+	pid_t pid;
+
+	CHECK(read_proc_stat(&before) == 0, "read /proc/stat");
+	CHECK(before.nr_cpus > 0, "cpuN lines");
+	CHECK(before.ctxt > 0 && before.processes > 0, "ctxt and processes");
+
+	// The boot time is consistent with the real-time and monotonic clocks
+	CHECK(clock_gettime(CLOCK_REALTIME, &now) == 0 &&
+		      clock_gettime(CLOCK_BOOTTIME, &boot) == 0,
+	      "clock_gettime");
+	CHECK(before.btime <= now.tv_sec &&
+		      now.tv_sec - boot.tv_sec - before.btime <= 1,
+	      "btime");
+
+	// Forking a child and waiting for it switches contexts
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0)
+		_exit(0);
+	CHECK(waitpid(pid, NULL, 0) == pid, "waitpid");
+
+	CHECK(read_proc_stat(&after) == 0, "read /proc/stat");
+	CHECK(after.processes > before.processes, "processes");
+	CHECK(after.ctxt > before.ctxt, "ctxt");
+	CHECK(after.btime == before.btime, "btime");
+	for (int i = 0; i < 10; i++)
+		CHECK(after.cpu[i] >= before.cpu[i], "cpu time");
+
+	printf("Test passed\n");
+	return 0;
+}
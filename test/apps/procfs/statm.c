@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mman.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NR_MAPPED_PAGES 64
+#define NR_TOUCHED_PAGES 16
+
+struct statm {
+	unsigned long size, resident, shared, text, lib, data, dt;
+};
+
+static int read_statm(struct statm *statm)
+{
+	int ret;
+	FILE *file = fopen("/proc/self/statm", "r");
+
+	if (file == NULL)
+		return -1;
+	ret = fscanf(file, "%lu %lu %lu %lu %lu %lu %lu\n", &statm->size,
+		     &statm->resident, &statm->shared, &statm->text,
+		     &statm->lib, &statm->data, &statm->dt);
+	fclose(file);
+	return ret == 7 ? 0 : -1;
+}
+
+int main(void)
+{
+	struct statm before, mapped, touched, unmapped;
+	long page_size = sysconf(_SC_PAGESIZE);
+	char *addr;
+
+	CHECK(read_statm(&before) == 0, "read statm");
+	CHECK(before.size > 0 && before.resident > 0, "size and resident");
+	CHECK(before.resident <= before.size, "resident");
+	CHECK(before.text > 0 && before.text <= before.size, "text");
+	CHECK(before.data > 0 && before.data <= before.size, "data");
+	CHECK(before.lib == 0 && before.dt == 0, "lib and dt");
+
+	addr = mmap(NULL, NR_MAPPED_PAGES * page_size, PROT_READ | PROT_WRITE,
+		    MAP_PRIVATE | MAP_ANONYMOUS, -1, 0);
+	CHECK(addr != MAP_FAILED, "mmap");
+	CHECK(read_statm(&mapped) == 0, "read statm");
+	CHECK(mapped.size == before.size + NR_MAPPED_PAGES, "mapped size");
+	CHECK(mapped.data == before.data + NR_MAPPED_PAGES, "mapped data");
+
+	// Touching the pages makes them resident
+	for (int i = 0; i < NR_TOUCHED_PAGES; i++)
+		addr[i * page_size] = 1;
+	CHECK(read_statm(&touched) == 0, "read statm");
+	CHECK(touched.resident >= mapped.resident + NR_TOUCHED_PAGES,
+	      "touched resident");
+
+	CHECK(munmap(addr, NR_MAPPED_PAGES * page_size) == 0, "munmap");
+	CHECK(read_statm(&unmapped) == 0, "read statm");
+	CHECK(unmapped.size == before.size, "unmapped size");
+
+	printf("Test passed\n");
+	return 0;
+}
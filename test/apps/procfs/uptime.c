@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int has_two_decimals(const char *str)
+{
+	const char *dot = strchr(str, '.');
+
+	return dot != NULL && strlen(dot) == 3;
+}
+
+static int read_uptime(double *uptime, double *idle)
+{
+	char buf[128], uptime_str[64], idle_str[64];
+	ssize_t len;
+	FILE *file = fopen("/proc/uptime", "r");
+
+	if (file == NULL)
+		return -1;
+	len = fread(buf, 1, sizeof(buf) - 1, file);
+	fclose(file);
+	buf[len] = '\0';
+
+	// The format is "<uptime> <idle>\n" with two decimal places
+	if (sscanf(buf, "%63s %63s", uptime_str, idle_str) != 2 ||
+	    strlen(buf) != strlen(uptime_str) + strlen(idle_str) + 2 ||
+	    buf[len - 1] != '\n')
+		return -1;
+	if (!has_two_decimals(uptime_str) || !has_two_decimals(idle_str))
+		return -1;
+	return sscanf(buf, "%lf %lf", uptime, idle) == 2 ? 0 : -1;
+}
+
+int main(void)
+{
+	double uptime, idle, later_uptime, later_idle;
+	struct timespec mono;
+
+	CHECK(read_uptime(&uptime, &idle) == 0, "read /proc/uptime");
+	CHECK(clock_gettime(CLOCK_MONOTONIC, &mono) == 0, "clock_gettime");
+	CHECK(uptime > 0 && idle >= 0, "values");
+	CHECK(mono.tv_sec + mono.tv_nsec / 1e9 - uptime < 1, "uptime");
+
+	usleep(200 * 1000);
+	CHECK(read_uptime(&later_uptime, &later_idle) == 0, "read /proc/uptime");
+	CHECK(later_uptime - uptime >= 0.19, "uptime increases");
+	CHECK(later_idle >= idle, "idle time");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/utsname.h>
+
+#include "check.h"
+
+// Reads the first line of a file without the trailing newline
+static int read_line(const char *path, char *buf, int len)
+{
+	FILE *file = fopen(path, "r");
+	char *newline;
+
+	if (file == NULL)
+		return -1;
+	if (fgets(buf, len, file) == NULL) {
+		fclose(file);
+		return -1;
+	}
+	fclose(file);
+
+	newline = strchr(buf, '\n');
+	if (newline == NULL)
+		return -1;
+	*newline = '\0';
+	return 0;
+}
+
+int main(void)
+{
+	struct utsname uts;
+	char buf[256], expected[256];
+
+	CHECK(uname(&uts) == 0, "uname");
+
+	// The files are consistent with uname
+	CHECK(read_line("/proc/sys/kernel/ostype", buf, sizeof(buf)) == 0 &&
+		      strcmp(buf, uts.sysname) == 0,
+	      "ostype");
+	CHECK(read_line("/proc/sys/kernel/osrelease", buf, sizeof(buf)) == 0 &&
+		      strcmp(buf, uts.release) == 0,
+	      "osrelease");
+	CHECK(read_line("/proc/sys/kernel/version", buf, sizeof(buf)) == 0 &&
+		      strcmp(buf, uts.version) == 0,
+	      "version");
+
+	snprintf(expected, sizeof(expected), "%s version %s ", uts.sysname,
+		 uts.release);
+	CHECK(read_line("/proc/version", buf, sizeof(buf)) == 0 &&
+		      strncmp(buf, expected, strlen(expected)) == 0,
+	      "/proc/version");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <pthread.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define EXIT_CODE 5
+
+static int pipe_fds[2];
+
+static void *thread_fn(void *arg)
+{
+	char byte;
+
+	(void)arg;
+
+	// Wait until the parent has checked that the process is still alive.
+	if (read(pipe_fds[0], &byte, 1) != 1)
+		exit(1);
+	exit(EXIT_CODE);
+}
+
+int main(void)
+{
+	pthread_t thread;
+	int status;
+	pid_t pid;
+
+	CHECK(pipe(pipe_fds) == 0, "pipe");
+
+	pid = fork();
+	if (pid == 0) {
+		if (pthread_create(&thread, NULL, thread_fn, NULL) != 0)
+			exit(1);
+		// Only the main thread exits, while the other thread keeps running.
+		pthread_exit(NULL);
+	}
+	CHECK(pid > 0, "fork");
+
+	// The process is alive after the main thread exits.
+	usleep(100 * 1000);
+	CHECK(waitpid(pid, &status, WNOHANG) == 0,
+	      "wait for the process whose main thread exits");
+
+	// The process can still be signaled.
+	CHECK(kill(pid, 0) == 0, "kill with signal 0");
+
+	// The process exits when the remaining thread exits the group.
+	CHECK(write(pipe_fds[1], "x", 1) == 1, "write");
+	CHECK(waitpid(pid, &status, 0) == pid, "wait for the process");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == EXIT_CODE,
+	      "exit status of the process");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _XOPEN_SOURCE 600
+#include <errno.h>
+#include <fcntl.h>
+#include <signal.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/ioctl.h>
+#include <sys/wait.h>
+#include <termios.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static volatile sig_atomic_t got_sigint = 0;
+static volatile sig_atomic_t got_sighup = 0;
+
+static void handle_signal(int signum)
+{
+	if (signum == SIGINT)
+		got_sigint = 1;
+	else if (signum == SIGHUP)
+		got_sighup = 1;
+}
+
+static int wait_flag(volatile sig_atomic_t *flag)
+{
+	for (int i = 0; i < 100 && !*flag; i++)
+		usleep(10 * 1000);
+	return *flag;
+}
+
+static int master;
+
+// Acquires the slave as the controlling terminal, receives SIGINT from VINTR,
+// and relinquishes the terminal.
+static int session_leader(const char *slave_name)
+{
+	int slave, pgrp;
+	char intr;
+	struct termios term;
+
+	CHECK(setsid() == getpid(), "setsid");
+	slave = open(slave_name, O_RDWR | O_NOCTTY);
+	CHECK(slave >= 0, "open slave");
+
+	// The terminal is not the controlling terminal yet
+	CHECK(ioctl(slave, TIOCGPGRP, &pgrp) < 0 && errno == ENOTTY,
+	      "TIOCGPGRP before TIOCSCTTY");
+
+	CHECK(ioctl(slave, TIOCSCTTY, 0) == 0, "TIOCSCTTY");
+	// Acquiring the same terminal again is a no-op
+	CHECK(ioctl(slave, TIOCSCTTY, 0) == 0, "TIOCSCTTY again");
+	CHECK(ioctl(slave, TIOCGPGRP, &pgrp) == 0 && pgrp == getpgrp(),
+	      "TIOCGPGRP");
+	CHECK(ioctl(slave, TIOCSPGRP, &pgrp) == 0, "TIOCSPGRP");
+
+	// VINTR sends SIGINT to the foreground process group
+	CHECK(tcgetattr(slave, &term) == 0, "tcgetattr");
+	intr = term.c_cc[VINTR];
+	CHECK(write(master, &intr, 1) == 1, "write VINTR");
+	CHECK(wait_flag(&got_sigint), "wait SIGINT");
+
+	// Relinquishing the terminal sends SIGHUP to the foreground process group
+	CHECK(ioctl(slave, TIOCNOTTY) == 0, "TIOCNOTTY");
+	CHECK(wait_flag(&got_sighup), "wait SIGHUP");
+	CHECK(ioctl(slave, TIOCGPGRP, &pgrp) < 0 && errno == ENOTTY,
+	      "TIOCGPGRP after TIOCNOTTY");
+
+	close(slave);
+	return 0;
+}
+
+// Acquires the terminal released by another session.
+static int new_session_leader(const char *slave_name)
+{
+	int slave;
+
+	CHECK(setsid() == getpid(), "setsid");
+	slave = open(slave_name, O_RDWR | O_NOCTTY);
+	CHECK(slave >= 0, "open slave");
+	CHECK(ioctl(slave, TIOCSCTTY, 0) == 0, "TIOCSCTTY in a new session");
+	CHECK(ioctl(slave, TIOCNOTTY) == 0, "TIOCNOTTY in a new session");
+
+	close(slave);
+	return 0;
+}
+
+static int run_child(int (*fn)(const char *), const char *slave_name)
+{
+	int status;
+	pid_t pid = fork();
+
+	if (pid == 0)
+		exit(fn(slave_name) == 0 ? EXIT_SUCCESS : EXIT_FAILURE);
+	if (pid < 0 || waitpid(pid, &status, 0) != pid)
+		return -1;
+	return WIFEXITED(status) && WEXITSTATUS(status) == EXIT_SUCCESS ? 0 : -1;
+}
+
+int main(void)
+{
+	int slave;
+	char *slave_name;
+	struct sigaction sa;
+
+	memset(&sa, 0, sizeof(sa));
+	sa.sa_handler = handle_signal;
+	CHECK(sigaction(SIGINT, &sa, NULL) == 0, "sigaction SIGINT");
+	CHECK(sigaction(SIGHUP, &sa, NULL) == 0, "sigaction SIGHUP");
+
+	master = posix_openpt(O_RDWR | O_NOCTTY);
+	CHECK(master >= 0, "posix_openpt");
+	CHECK(grantpt(master) == 0 && unlockpt(master) == 0,
+	      "unlockpt");
+	slave_name = ptsname(master);
+	CHECK(slave_name != NULL, "ptsname");
+
+	// Only a session leader can acquire a controlling terminal
+	if (getsid(0) != getpid()) {
+		slave = open(slave_name, O_RDWR | O_NOCTTY);
+		CHECK(slave >= 0, "open slave");
+		CHECK(ioctl(slave, TIOCSCTTY, 0) < 0 && errno == EPERM,
+		      "TIOCSCTTY by a non-leader");
+		close(slave);
+	}
+
+	CHECK(run_child(session_leader, slave_name) == 0,
+	      "session leader");
+	CHECK(run_child(new_session_leader, slave_name) == 0,
+	      "new session leader");
+
+	close(master);
+
+	printf("Test passed\n");
+	return 0;
+}
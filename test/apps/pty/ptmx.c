@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/ioctl.h>
+#include <termios.h>
+#include <unistd.h>
+
+#include "check.h"
+
+int main(void)
+{
+	int master, slave, index, lock;
+	char slave_name[32], buf[16];
+	struct termios term;
+
+	master = open("/dev/ptmx", O_RDWR | O_NOCTTY);
+	CHECK(master >= 0, "open /dev/ptmx");
+	CHECK(ioctl(master, TIOCGPTN, &index) == 0, "ioctl(TIOCGPTN)");
+	snprintf(slave_name, sizeof(slave_name), "/dev/pts/%d", index);
+
+	// A new slave is locked until it is unlocked by the master
+	CHECK(ioctl(master, TIOCGPTLCK, &lock) == 0 && lock != 0,
+	      "ioctl(TIOCGPTLCK)");
+	CHECK(open(slave_name, O_RDWR | O_NOCTTY) < 0 && errno == EIO,
+	      "open locked slave");
+
+	lock = 0;
+	CHECK(ioctl(master, TIOCSPTLCK, &lock) == 0, "ioctl(TIOCSPTLCK)");
+	CHECK(ioctl(master, TIOCGPTLCK, &lock) == 0 && lock == 0,
+	      "ioctl(TIOCGPTLCK)");
+	slave = open(slave_name, O_RDWR | O_NOCTTY);
+	CHECK(slave >= 0, "open slave");
+
+	// Switch to the raw mode without echo
+	CHECK(tcgetattr(slave, &term) == 0, "tcgetattr");
+	cfmakeraw(&term);
+	CHECK(tcsetattr(slave, TCSANOW, &term) == 0, "tcsetattr");
+
+	// The pair behaves like a bidirectional pipe
+	CHECK(write(master, "ping", 4) == 4, "write master");
+	CHECK(read(slave, buf, sizeof(buf)) == 4 && memcmp(buf, "ping", 4) == 0,
+	      "read slave");
+	CHECK(write(slave, "pong", 4) == 4, "write slave");
+	CHECK(read(master, buf, sizeof(buf)) == 4 && memcmp(buf, "pong", 4) == 0,
+	      "read master");
+
+	close(slave);
+	close(master);
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <signal.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/ioctl.h>
+#include <sys/wait.h>
+#include <termios.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define NEW_ROWS 40
+#define NEW_COLS 120
+
+static volatile sig_atomic_t got_sigwinch;
+
+static void handle_sigwinch(int sig)
+{
+	got_sigwinch = 1;
+}
+
+// Makes the slave the controlling terminal and waits for SIGWINCH
+static int run_child(const char *slave_name, int ready_fd)
+{
+	struct winsize ws;
+	int slave, i;
+
+	CHECK(signal(SIGWINCH, handle_sigwinch) != SIG_ERR, "signal");
+	CHECK(setsid() >= 0, "setsid");
+	slave = open(slave_name, O_RDWR);
+	CHECK(slave >= 0, "open slave");
+	CHECK(ioctl(slave, TIOCSCTTY, 0) == 0, "ioctl(TIOCSCTTY)");
+	CHECK(tcsetpgrp(slave, getpgrp()) == 0, "tcsetpgrp");
+	CHECK(write(ready_fd, "", 1) == 1, "notify parent");
+
+	for (i = 0; i < 100 && !got_sigwinch; ++i)
+		usleep(10 * 1000);
+	CHECK(got_sigwinch, "wait for SIGWINCH");
+
+	// The new window size is visible from the slave
+	CHECK(ioctl(slave, TIOCGWINSZ, &ws) == 0, "ioctl(TIOCGWINSZ)");
+	CHECK(ws.ws_row == NEW_ROWS && ws.ws_col == NEW_COLS,
+	      "check window size");
+
+	return 0;
+}
+
+int main(void)
+{
+	struct winsize ws = { .ws_row = NEW_ROWS, .ws_col = NEW_COLS };
+	int master, pipe_fds[2], status;
+	char *slave_name, buf;
+	pid_t pid;
+
+	master = posix_openpt(O_RDWR | O_NOCTTY);
+	CHECK(master >= 0, "posix_openpt");
+	CHECK(grantpt(master) == 0 && unlockpt(master) == 0, "unlock pty");
+	slave_name = ptsname(master);
+	CHECK(slave_name != NULL, "ptsname");
+	CHECK(pipe(pipe_fds) == 0, "pipe");
+
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0)
+		_exit(run_child(slave_name, pipe_fds[1]) == 0 ? EXIT_SUCCESS :
+								EXIT_FAILURE);
+
+	// Resizing the window from the master side notifies the foreground
+	// process group of the slave
+	CHECK(read(pipe_fds[0], &buf, 1) == 1, "wait for child");
+	CHECK(ioctl(master, TIOCSWINSZ, &ws) == 0, "ioctl(TIOCSWINSZ)");
+
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0, "check child");
+
+	printf("Test passed\n");
+	return 0;
+}
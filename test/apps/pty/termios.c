@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _XOPEN_SOURCE 600
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <termios.h>
+#include <unistd.h>
+
+#include "check.h"
+
+// Reads until a new line, which may be echoed as "\r\n" or "\n"
+static int read_line(int fd, char *buf, int len)
+{
+	int total = 0;
+
+	while (total < len) {
+		int n = read(fd, buf + total, len - total);
+		if (n <= 0)
+			return -1;
+		total += n;
+		if (buf[total - 1] == '\n')
+			return total;
+	}
+	return -1;
+}
+
+int main(void)
+{
+	int master, slave, n;
+	char buf[32];
+	struct termios term;
+	struct pollfd pfd;
+
+	master = posix_openpt(O_RDWR | O_NOCTTY);
+	CHECK(master >= 0, "posix_openpt");
+	CHECK(grantpt(master) == 0 && unlockpt(master) == 0, "unlockpt");
+	slave = open(ptsname(master), O_RDWR | O_NOCTTY);
+	CHECK(slave >= 0, "open slave");
+
+	// A new terminal is in canonical mode with echo enabled
+	CHECK(tcgetattr(slave, &term) == 0, "tcgetattr");
+	CHECK((term.c_lflag & ICANON) && (term.c_lflag & ECHO),
+	      "default lflag");
+
+	// Reads return whole lines, with VERASE erasing the previous character
+	buf[0] = 'a';
+	buf[1] = 'b';
+	buf[2] = term.c_cc[VERASE];
+	buf[3] = 'c';
+	buf[4] = '\n';
+	CHECK(write(master, buf, 5) == 5, "write line");
+	n = read(slave, buf, sizeof(buf));
+	CHECK(n == 3 && memcmp(buf, "ac\n", 3) == 0, "read line");
+	CHECK(read_line(master, buf, sizeof(buf)) >= 7 &&
+		      memcmp(buf, "ab\b \bc", 6) == 0,
+	      "read echo");
+
+	// VEOF on an empty line makes the read return zero
+	buf[0] = term.c_cc[VEOF];
+	CHECK(write(master, buf, 1) == 1, "write VEOF");
+	CHECK(read(slave, buf, sizeof(buf)) == 0, "read VEOF");
+
+	// Switch to the raw mode without echo
+	term.c_lflag &= ~(ICANON | ECHO);
+	term.c_cc[VMIN] = 1;
+	term.c_cc[VTIME] = 0;
+	CHECK(tcsetattr(slave, TCSAFLUSH, &term) == 0, "tcsetattr");
+	CHECK(tcgetattr(slave, &term) == 0, "tcgetattr");
+	CHECK(!(term.c_lflag & ICANON) && !(term.c_lflag & ECHO),
+	      "raw lflag");
+
+	// Reads return single keypresses without waiting for a new line
+	CHECK(write(master, "x", 1) == 1, "write key");
+	CHECK(read(slave, buf, sizeof(buf)) == 1 && buf[0] == 'x', "read key");
+	CHECK(write(master, "y", 1) == 1, "write key");
+	CHECK(read(slave, buf, sizeof(buf)) == 1 && buf[0] == 'y', "read key");
+
+	// Nothing is echoed back
+	pfd.fd = master;
+	pfd.events = POLLIN;
+	CHECK(poll(&pfd, 1, 100) == 0, "poll echo");
+
+	close(slave);
+	close(master);
+
+	printf("Test passed\n");
+	return 0;
+}
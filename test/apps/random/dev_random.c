@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <sys/sysmacros.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define BUF_LEN 64
+
+static int test_device(const char *path, unsigned int minor)
+{
+	unsigned char buf1[BUF_LEN], buf2[BUF_LEN];
+	struct pollfd pfd;
+	struct stat st;
+	int fd;
+
+	CHECK(stat(path, &st) == 0, "stat");
+	CHECK(S_ISCHR(st.st_mode) && major(st.st_rdev) == 1 &&
+		      minor(st.st_rdev) == minor,
+	      "check device ID");
+
+	fd = open(path, O_RDWR | O_NONBLOCK);
+	CHECK(fd >= 0, "open");
+
+	// Reads never block and return fresh random bytes
+	CHECK(read(fd, buf1, sizeof(buf1)) == sizeof(buf1), "read");
+	CHECK(read(fd, buf2, sizeof(buf2)) == sizeof(buf2), "read");
+	CHECK(memcmp(buf1, buf2, BUF_LEN) != 0, "compare random bytes");
+
+	// Writes are accepted
+	CHECK(write(fd, buf1, sizeof(buf1)) == sizeof(buf1), "write");
+
+	// The devices are always readable since the RNG has been seeded
+	pfd.fd = fd;
+	pfd.events = POLLIN;
+	CHECK(poll(&pfd, 1, 0) == 1 && pfd.revents == POLLIN, "poll");
+
+	CHECK(close(fd) == 0, "close");
+	return 0;
+}
+
+int main(void)
+{
+	CHECK(test_device("/dev/random", 8) == 0, "test /dev/random");
+	CHECK(test_device("/dev/urandom", 9) == 0, "test /dev/urandom");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/random.h>
+
+#include "check.h"
+
+#define BUF_LEN 64
+
+static int is_zero(const unsigned char *buf, size_t len)
+{
+	size_t i;
+
+	for (i = 0; i < len; ++i)
+		if (buf[i] != 0)
+			return 0;
+	return 1;
+}
+
+int main(void)
+{
+	unsigned char buf1[BUF_LEN] = { 0 }, buf2[BUF_LEN] = { 0 };
+
+	CHECK(getrandom(buf1, sizeof(buf1), 0) == sizeof(buf1), "getrandom");
+	CHECK(getrandom(buf2, sizeof(buf2), GRND_NONBLOCK) == sizeof(buf2),
+	      "getrandom(GRND_NONBLOCK)");
+	CHECK(!is_zero(buf1, BUF_LEN) && memcmp(buf1, buf2, BUF_LEN) != 0,
+	      "compare random bytes");
+
+	CHECK(getrandom(buf1, sizeof(buf1), GRND_RANDOM | GRND_NONBLOCK) > 0,
+	      "getrandom(GRND_RANDOM)");
+	CHECK(getrandom(buf1, sizeof(buf1), GRND_INSECURE) == sizeof(buf1),
+	      "getrandom(GRND_INSECURE)");
+	CHECK(getrandom(buf1, 0, 0) == 0, "getrandom with zero length");
+
+	// Unknown flags and conflicting flags are rejected
+	CHECK(getrandom(buf1, sizeof(buf1), 0x100) == -1 && errno == EINVAL,
+	      "getrandom with unknown flags");
+	CHECK(getrandom(buf1, sizeof(buf1), GRND_INSECURE | GRND_RANDOM) == -1 &&
+		      errno == EINVAL,
+	      "getrandom with conflicting flags");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/readlink_test"
+#define TEST_FILE TEST_DIR "/file"
+#define TEST_LINK TEST_DIR "/link"
+#define LINK_LOOP TEST_DIR "/loop"
+
+static int test_symlink(void)
+{
+	char buf[64];
+	ssize_t len;
+	int dirfd, fd;
+
+	// The target is returned without a null byte
+	memset(buf, 'x', sizeof(buf));
+	len = readlink(TEST_LINK, buf, sizeof(buf));
+	CHECK(len == strlen(TEST_FILE), "readlink length");
+	CHECK(memcmp(buf, TEST_FILE, len) == 0 && buf[len] == 'x',
+	      "readlink content");
+
+	// The target is truncated if the buffer is too small
+	len = readlink(TEST_LINK, buf, 4);
+	CHECK(len == 4 && memcmp(buf, TEST_FILE, 4) == 0, "readlink truncated");
+
+	// The final component is never followed, even for a symlink loop
+	len = readlink(LINK_LOOP, buf, sizeof(buf));
+	CHECK(len == strlen(LINK_LOOP) && memcmp(buf, LINK_LOOP, len) == 0,
+	      "readlink a symlink loop");
+
+	dirfd = open(TEST_DIR, O_RDONLY | O_DIRECTORY);
+	CHECK(dirfd >= 0, "open dir");
+	len = readlinkat(dirfd, "link", buf, sizeof(buf));
+	CHECK(len == strlen(TEST_FILE), "readlinkat");
+	close(dirfd);
+
+	// An empty path refers to the symlink opened with O_PATH
+	fd = open(TEST_LINK, O_PATH | O_NOFOLLOW);
+	CHECK(fd >= 0, "open symlink with O_PATH");
+	len = readlinkat(fd, "", buf, sizeof(buf));
+	CHECK(len == strlen(TEST_FILE), "readlinkat with an empty path");
+	close(fd);
+	return 0;
+}
+
+static int test_errors(void)
+{
+	char buf[64];
+	int fd;
+
+	CHECK_ERROR(readlink(TEST_FILE, buf, sizeof(buf)), EINVAL);
+	CHECK_ERROR(readlink(TEST_DIR, buf, sizeof(buf)), EINVAL);
+	CHECK_ERROR(readlink(TEST_DIR "/none", buf, sizeof(buf)), ENOENT);
+	CHECK_ERROR(readlink(TEST_LINK, buf, 0), EINVAL);
+	CHECK_ERROR(readlink("", buf, sizeof(buf)), ENOENT);
+
+	fd = open(TEST_FILE, O_RDONLY);
+	CHECK(fd >= 0, "open file");
+	CHECK_ERROR(readlinkat(fd, "", buf, sizeof(buf)), ENOENT);
+	close(fd);
+	return 0;
+}
+
+static int test_proc(void)
+{
+	char buf[64], path[64];
+	ssize_t len;
+	int fd;
+
+	fd = open(TEST_FILE, O_RDONLY);
+	CHECK(fd >= 0, "open file");
+	snprintf(path, sizeof(path), "/proc/self/fd/%d", fd);
+	len = readlink(path, buf, sizeof(buf));
+	CHECK(len == strlen(TEST_FILE) && memcmp(buf, TEST_FILE, len) == 0,
+	      "readlink /proc/self/fd/N");
+	close(fd);
+
+	len = readlink("/proc/self/exe", buf, sizeof(buf));
+	CHECK(len > 0 && buf[0] == '/', "readlink /proc/self/exe");
+	return 0;
+}
+
+int main(void)
+{
+	int fd, ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	fd = open(TEST_FILE, O_WRONLY | O_CREAT, 0644);
+	if (fd < 0) {
+		perror("create file");
+		goto out;
+	}
+	close(fd);
+	if (symlink(TEST_FILE, TEST_LINK) < 0 ||
+	    symlink(LINK_LOOP, LINK_LOOP) < 0) {
+		perror("symlink");
+		goto out;
+	}
+
+	if (test_symlink() < 0 || test_errors() < 0 || test_proc() < 0)
+		goto out;
+
+	printf("readlink test passed\n");
+	ret = 0;
+out:
+	unlink(LINK_LOOP);
+	unlink(TEST_LINK);
+	unlink(TEST_FILE);
+	rmdir(TEST_DIR);
+	return ret;
+}
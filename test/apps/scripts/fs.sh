@@ -51,6 +51,9 @@ test_fdatasync() {
     rm -f /ext2/test_fdatasync.txt
     fdatasync/fdatasync /exfat
     rm -f /exfat/test_fdatasync.txt
+    fdatasync/fsync_errors
+    fdatasync/syncfs / /ext2 /exfat
+    fdatasync/sync_file_range / /ext2 /exfat
 }
 
 echo "Start ext2 fs test......"
@@ -59,4 +62,66 @@ echo "All ext2 fs test passed."
 
 echo "Start fdatasync test......"
 test_fdatasync
-echo "All fdatasync test passed."
\ No newline at end of file
+echo "All fdatasync test passed."
+
+echo "Start umask test......"
+umask/umask
+echo "All umask test passed."
+
+echo "Start chroot test......"
+chroot/chroot
+echo "All chroot test passed."
+
+echo "Start pivot_root test......"
+pivot_root/pivot_root
+echo "All pivot_root test passed."
+
+echo "Start getcwd test......"
+getcwd/getcwd
+echo "All getcwd test passed."
+
+echo "Start O_PATH test......"
+o_path/o_path
+echo "All O_PATH test passed."
+
+echo "Start open flags test......"
+open_flags/open_flags
+echo "All open flags test passed."
+
+echo "Start path lookup test......"
+path_lookup/path_lookup
+echo "All path lookup test passed."
+
+echo "Start symlink loop test......"
+symlink_loop/symlink_loop
+echo "All symlink loop test passed."
+
+echo "Start faccessat2 test......"
+faccessat2/faccessat2
+echo "All faccessat2 test passed."
+
+echo "Start readlink test......"
+readlink/readlink
+echo "All readlink test passed."
+
+echo "Start mknod test......"
+mknod/mknod
+echo "All mknod test passed."
+
+echo "Start getdents test......"
+getdents/getdents
+echo "All getdents test passed."
+
+echo "Start mount test......"
+mount/devtmpfs
+mount/mount_fs
+mount/tmpfs_size
+echo "All mount test passed."
+
+echo "Start sysfs test......"
+sysfs/sysfs
+echo "All sysfs test passed."
+
+echo "Start fanotify test......"
+fanotify/fanotify
+echo "All fanotify test passed."
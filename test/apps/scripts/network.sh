@@ -16,6 +16,7 @@ echo "Start network test......"
 ./unix_server &
 ./unix_client
 ./socketpair
+./unix_dgram
 ./sockoption
 ./listen_backlog
 ./send_buf_full
@@ -23,5 +24,16 @@ echo "Start network test......"
 ./http_client
 ./tcp_err
 ./udp_err
+./msg_trunc
+./sock_timeout
+./sock_domain
+./ip_local_port_range
+./tcp_reuse
+./tcp_nodelay
+./epoll_exclusive
+./bind_to_device
+./raw_icmp
+./sock_priority
+./tcp_cork
 
 echo "All network test passed"
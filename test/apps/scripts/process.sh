@@ -10,21 +10,74 @@ cd ${SCRIPT_DIR}/..
 echo "Start process test......"
 # These test programs are sorted by name.
 tests="
+capability/dac_permission
+clock/adjtimex
+clock/clock_getres
+clock/cputime_clock
+clock/settime
+clock/vdso_clock
 clone3/clone_process
+execve/auxv_execfn
+execve/auxv_secure
 execve/execve
 eventfd2/eventfd2
+execve/execve_bounds
+execve/execve_e2big
+execve/shebang
 fork/fork
 fork_c/fork
+fork_c/subreaper
+fork_c/vfork
+fork_c/wait
 getpid/getpid
 hello_pie/hello
 hello_world/hello_world
 itimer/setitimer
 itimer/timer_create
+mmap/aslr
+mmap/brk_shrink
+mmap/madvise
 mmap/mmap_and_fork
+mmap/mremap
+mmap/stack_growth
+pipe/fifo_close
+pipe/pipe_atomic
+pipe/pipe_packet
+pipe/pipe_size
+pipe/select_idle
+pipe/vmsplice
+procfs/auxv
+procfs/cmdline
+procfs/cpuinfo
+procfs/environ
+procfs/file_nr
+procfs/maps
+procfs/meminfo
+procfs/nr_open
+procfs/oom_score_adj
+procfs/random_uuid
+procfs/self
+procfs/stat
+procfs/statm
+procfs/uptime
+procfs/version
+pthread/main_thread_exit
 pthread/pthread_test
+pty/ctty
 pty/open_pty
+pty/ptmx
+pty/sigwinch
+pty/termios
+random/dev_random
+random/getrandom
+signal_c/exit_status
 signal_c/parent_death_signal
+signal_c/sigaltstack
 signal_c/signal_test
+signal_c/signalfd
+signal_c/sigqueueinfo
+user_ns/id_map
+user_ns/setns
 "
 
 for testcase in ${tests}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static pid_t spawn_paused_child(void)
+{
+	pid_t pid = fork();
+
+	if (pid == 0) {
+		for (;;)
+			pause();
+	}
+	return pid;
+}
+
+int main(void)
+{
+	int status;
+	pid_t pid;
+
+	// The child exits normally.
+	pid = fork();
+	if (pid == 0)
+		_exit(3);
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && !WIFSIGNALED(status) &&
+		      WEXITSTATUS(status) == 3,
+	      "exit normally");
+
+	// The child is killed by a signal that terminates the process.
+	pid = spawn_paused_child();
+	CHECK(pid > 0, "fork");
+	CHECK(kill(pid, SIGKILL) == 0, "kill");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFSIGNALED(status) && !WIFEXITED(status) &&
+		      WTERMSIG(status) == SIGKILL && !WCOREDUMP(status),
+	      "kill with SIGKILL");
+
+	// The child is killed by a signal that dumps core. Whether `WCOREDUMP`
+	// is set depends on `RLIMIT_CORE` in Linux, so it is not checked.
+	pid = spawn_paused_child();
+	CHECK(pid > 0, "fork");
+	CHECK(kill(pid, SIGSEGV) == 0, "kill");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFSIGNALED(status) && !WIFEXITED(status) &&
+		      WTERMSIG(status) == SIGSEGV,
+	      "kill with SIGSEGV");
+
+	// The child raises a fault.
+	pid = fork();
+	if (pid == 0) {
+		*(volatile int *)NULL = 0;
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFSIGNALED(status) && WTERMSIG(status) == SIGSEGV,
+	      "fault with SIGSEGV");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <signal.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define STACK_SIZE (4 * 4096)
+
+static char alt_stack[STACK_SIZE];
+
+static volatile int handler_on_stack;
+static volatile int handler_ss_flags;
+static volatile int handler_set_errno;
+
+static int is_on_alt_stack(void *addr)
+{
+	return (uintptr_t)addr >= (uintptr_t)alt_stack &&
+	       (uintptr_t)addr < (uintptr_t)alt_stack + STACK_SIZE;
+}
+
+static void handler(int signum)
+{
+	int local;
+	stack_t ss;
+
+	(void)signum;
+	handler_on_stack = is_on_alt_stack(&local);
+
+	sigaltstack(NULL, &ss);
+	handler_ss_flags = ss.ss_flags;
+
+	// The stack cannot be changed while it is in use.
+	if (handler_on_stack) {
+		ss.ss_flags = SS_DISABLE;
+		handler_set_errno = sigaltstack(&ss, NULL) < 0 ? errno : 0;
+	}
+}
+
+static int set_handler(int flags)
+{
+	struct sigaction sa;
+
+	memset(&sa, 0, sizeof(sa));
+	sa.sa_handler = handler;
+	sa.sa_flags = flags;
+	return sigaction(SIGUSR1, &sa, NULL);
+}
+
+int main(void)
+{
+	stack_t ss, old_ss;
+
+	// No stack is installed at first
+	CHECK(sigaltstack(NULL, &old_ss) == 0 && old_ss.ss_flags == SS_DISABLE,
+	      "get the initial stack");
+
+	// The stack is validated
+	ss.ss_sp = alt_stack;
+	ss.ss_size = MINSIGSTKSZ - 1;
+	ss.ss_flags = 0;
+	CHECK(sigaltstack(&ss, NULL) < 0 && errno == ENOMEM,
+	      "set a too small stack");
+	ss.ss_size = STACK_SIZE;
+	ss.ss_flags = 0x10;
+	CHECK(sigaltstack(&ss, NULL) < 0 && errno == EINVAL,
+	      "set a stack with invalid flags");
+
+	// The stack is installed
+	ss.ss_flags = 0;
+	CHECK(sigaltstack(&ss, &old_ss) == 0 && old_ss.ss_flags == SS_DISABLE,
+	      "set the stack");
+	CHECK(sigaltstack(NULL, &old_ss) == 0 && old_ss.ss_sp == alt_stack &&
+		      old_ss.ss_size == STACK_SIZE && old_ss.ss_flags == 0,
+	      "get the stack");
+
+	// The handler with `SA_ONSTACK` runs on the stack
+	CHECK(set_handler(SA_ONSTACK) == 0, "sigaction");
+	CHECK(raise(SIGUSR1) == 0, "raise");
+	CHECK(handler_on_stack, "run the handler on the stack");
+	CHECK(handler_ss_flags == SS_ONSTACK, "get the stack in the handler");
+	CHECK(handler_set_errno == EPERM, "set the stack in the handler");
+
+	// The handler without `SA_ONSTACK` runs on the normal stack
+	CHECK(set_handler(0) == 0, "sigaction");
+	CHECK(raise(SIGUSR1) == 0, "raise");
+	CHECK(!handler_on_stack, "run the handler on the normal stack");
+	CHECK(handler_ss_flags == 0, "get the stack in the handler");
+
+	// The stack is disabled
+	ss.ss_flags = SS_DISABLE;
+	CHECK(sigaltstack(&ss, NULL) == 0, "disable the stack");
+	CHECK(sigaltstack(NULL, &old_ss) == 0 && old_ss.ss_flags == SS_DISABLE,
+	      "get the disabled stack");
+	CHECK(set_handler(SA_ONSTACK) == 0, "sigaction");
+	CHECK(raise(SIGUSR1) == 0, "raise");
+	CHECK(!handler_on_stack, "run the handler without the stack");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <poll.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/signalfd.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int is_readable(int fd)
+{
+	struct pollfd pfd = { .fd = fd, .events = POLLIN };
+
+	return poll(&pfd, 1, 0) == 1 && (pfd.revents & POLLIN);
+}
+
+int main(void)
+{
+	struct signalfd_siginfo info;
+	sigset_t mask, pending;
+	char small_buf[16];
+	int fd;
+
+	// The signals read from the file should be blocked.
+	sigemptyset(&mask);
+	sigaddset(&mask, SIGUSR1);
+	CHECK(sigprocmask(SIG_BLOCK, &mask, NULL) == 0, "sigprocmask");
+
+	fd = signalfd(-1, &mask, SFD_NONBLOCK | SFD_CLOEXEC);
+	CHECK(fd >= 0, "signalfd");
+	CHECK(fcntl(fd, F_GETFD) == FD_CLOEXEC, "set FD_CLOEXEC");
+	CHECK((fcntl(fd, F_GETFL) & O_NONBLOCK) != 0, "set O_NONBLOCK");
+
+	// No signals are pending.
+	CHECK(read(fd, &info, sizeof(info)) < 0 && errno == EAGAIN,
+	      "read without pending signals");
+	CHECK(!is_readable(fd), "poll without pending signals");
+
+	// The pending signal is read from the file and consumed.
+	CHECK(kill(getpid(), SIGUSR1) == 0, "kill");
+	CHECK(is_readable(fd), "poll with a pending signal");
+	CHECK(read(fd, small_buf, sizeof(small_buf)) < 0 && errno == EINVAL,
+	      "read with a small buffer");
+	CHECK(read(fd, &info, sizeof(info)) == sizeof(info), "read the signal");
+	CHECK(info.ssi_signo == SIGUSR1 && info.ssi_code == SI_USER &&
+		      info.ssi_pid == (uint32_t)getpid() &&
+		      info.ssi_uid == getuid(),
+	      "check the signal information");
+	CHECK(read(fd, &info, sizeof(info)) < 0 && errno == EAGAIN,
+	      "read the signal again");
+	CHECK(!is_readable(fd), "poll after reading the signal");
+	CHECK(sigpending(&pending) == 0 && !sigismember(&pending, SIGUSR1),
+	      "consume the signal");
+
+	// The signals that are not in the mask are left pending.
+	sigaddset(&mask, SIGUSR2);
+	CHECK(sigprocmask(SIG_BLOCK, &mask, NULL) == 0, "sigprocmask");
+	CHECK(kill(getpid(), SIGUSR2) == 0, "kill");
+	CHECK(!is_readable(fd), "poll with a signal not in the mask");
+
+	// The mask of the existing file can be updated.
+	CHECK(signalfd(fd, &mask, 0) == fd, "update the mask");
+	CHECK(is_readable(fd), "poll with a signal in the new mask");
+	CHECK(read(fd, &info, sizeof(info)) == sizeof(info) &&
+		      info.ssi_signo == SIGUSR2,
+	      "read the signal in the new mask");
+
+	// Only signalfd files can be updated.
+	CHECK(signalfd(STDIN_FILENO, &mask, 0) < 0 && errno == EINVAL,
+	      "update a file that is not a signalfd file");
+
+	CHECK(close(fd) == 0, "close");
+
+	printf("Test passed\n");
+	return 0;
+}
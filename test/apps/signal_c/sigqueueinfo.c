@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define PAYLOAD 0x1234
+
+static volatile int received_signo;
+static volatile int received_code;
+static volatile int received_value;
+static volatile pid_t received_pid;
+
+static void handler(int signo, siginfo_t *info, void *context)
+{
+	received_signo = info->si_signo;
+	received_code = info->si_code;
+	received_value = info->si_value.sival_int;
+	received_pid = info->si_pid;
+}
+
+static void reset_received(void)
+{
+	received_signo = 0;
+	received_code = 0;
+	received_value = 0;
+	received_pid = 0;
+}
+
+static void init_info(siginfo_t *info, int code)
+{
+	memset(info, 0, sizeof(*info));
+	// The signal number is overwritten by the kernel.
+	info->si_signo = SIGUSR2;
+	info->si_code = code;
+	info->si_pid = getpid();
+	info->si_uid = getuid();
+	info->si_value.sival_int = PAYLOAD;
+}
+
+int main(void)
+{
+	struct sigaction sa;
+	siginfo_t info;
+	int status;
+	pid_t pid;
+
+	memset(&sa, 0, sizeof(sa));
+	sa.sa_sigaction = handler;
+	sa.sa_flags = SA_SIGINFO;
+	CHECK(sigaction(SIGRTMIN, &sa, NULL) == 0, "sigaction");
+
+	// The payload is delivered to the handler.
+	reset_received();
+	init_info(&info, SI_QUEUE);
+	CHECK(syscall(SYS_rt_sigqueueinfo, getpid(), SIGRTMIN, &info) == 0,
+	      "rt_sigqueueinfo");
+	CHECK(received_signo == SIGRTMIN && received_code == SI_QUEUE &&
+		      received_value == PAYLOAD && received_pid == getpid(),
+	      "receive the payload");
+
+	// The signal can be sent to a specific thread.
+	reset_received();
+	init_info(&info, SI_QUEUE);
+	CHECK(syscall(SYS_rt_tgsigqueueinfo, getpid(), syscall(SYS_gettid),
+		      SIGRTMIN, &info) == 0,
+	      "rt_tgsigqueueinfo");
+	CHECK(received_signo == SIGRTMIN && received_value == PAYLOAD,
+	      "receive the payload from rt_tgsigqueueinfo");
+
+	// The reserved codes can be used only when sending to the sender itself.
+	reset_received();
+	init_info(&info, SI_USER);
+	CHECK(syscall(SYS_rt_sigqueueinfo, getpid(), SIGRTMIN, &info) == 0,
+	      "rt_sigqueueinfo with SI_USER to itself");
+	CHECK(received_signo == SIGRTMIN && received_code == SI_USER,
+	      "receive the signal with SI_USER");
+
+	pid = fork();
+	if (pid == 0) {
+		pause();
+		_exit(0);
+	}
+	CHECK(pid > 0, "fork");
+	init_info(&info, SI_USER);
+	CHECK(syscall(SYS_rt_sigqueueinfo, pid, SIGKILL, &info) < 0 &&
+		      errno == EPERM,
+	      "rt_sigqueueinfo with SI_USER to others");
+	init_info(&info, SI_TKILL);
+	CHECK(syscall(SYS_rt_sigqueueinfo, pid, SIGKILL, &info) < 0 &&
+		      errno == EPERM,
+	      "rt_sigqueueinfo with SI_TKILL to others");
+
+	// The signal number zero only checks the permission.
+	init_info(&info, SI_QUEUE);
+	CHECK(syscall(SYS_rt_sigqueueinfo, pid, 0, &info) == 0,
+	      "rt_sigqueueinfo with signal zero");
+	CHECK(syscall(SYS_rt_sigqueueinfo, pid, SIGKILL, &info) == 0,
+	      "rt_sigqueueinfo to others");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFSIGNALED(status) &&
+		      WTERMSIG(status) == SIGKILL,
+	      "kill the child");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/stat.h>
+#include <unistd.h>
+
+#include "check.h"
+
+#define TEST_DIR "/tmp/symlink_loop_test"
+#define LINK_A TEST_DIR "/a"
+#define LINK_B TEST_DIR "/b"
+#define TARGET TEST_DIR "/target"
+#define CHAIN_LEN 50
+
+static int test_loop(void)
+{
+	struct stat st;
+	int fd;
+
+	CHECK(symlink(LINK_B, LINK_A) == 0, "symlink a -> b");
+	CHECK(symlink(LINK_A, LINK_B) == 0, "symlink b -> a");
+
+	errno = 0;
+	fd = open(LINK_A, O_RDONLY);
+	CHECK(fd < 0 && errno == ELOOP, "open a symlink loop");
+	errno = 0;
+	fd = open(LINK_A, O_RDWR | O_CREAT, 0644);
+	CHECK(fd < 0 && errno == ELOOP, "create through a symlink loop");
+	errno = 0;
+	CHECK(stat(LINK_A, &st) < 0 && errno == ELOOP, "stat a symlink loop");
+	errno = 0;
+	CHECK(stat(LINK_A "/file", &st) < 0 && errno == ELOOP,
+	      "stat through a symlink loop");
+
+	// The symlink itself is still accessible
+	CHECK(lstat(LINK_A, &st) == 0 && S_ISLNK(st.st_mode), "lstat");
+
+	unlink(LINK_A);
+	unlink(LINK_B);
+	return 0;
+}
+
+static int test_chain(void)
+{
+	char path[64], target[64];
+	struct stat st;
+	int i, ret = 0;
+
+	// link_i -> link_{i-1} -> ... -> link_0 -> target
+	for (i = 0; i < CHAIN_LEN; i++) {
+		if (i == 0)
+			snprintf(target, sizeof(target), TARGET);
+		else
+			snprintf(target, sizeof(target), TEST_DIR "/link_%d",
+				 i - 1);
+		snprintf(path, sizeof(path), TEST_DIR "/link_%d", i);
+		if (symlink(target, path) < 0) {
+			perror("symlink");
+			ret = -1;
+			goto out;
+		}
+	}
+
+	// Following 30 symlinks is within the limit
+	if (stat(TEST_DIR "/link_29", &st) < 0) {
+		perror("stat link_29");
+		ret = -1;
+		goto out;
+	}
+
+	// But following 50 symlinks is not
+	errno = 0;
+	if (stat(TEST_DIR "/link_49", &st) == 0 || errno != ELOOP) {
+		fprintf(stderr, "stat link_49 should fail with ELOOP\n");
+		ret = -1;
+	}
+
+out:
+	for (i = 0; i < CHAIN_LEN; i++) {
+		snprintf(path, sizeof(path), TEST_DIR "/link_%d", i);
+		unlink(path);
+	}
+	return ret;
+}
+
+int main(void)
+{
+	int fd, ret = 1;
+
+	if (mkdir(TEST_DIR, 0755) < 0) {
+		perror("mkdir");
+		return 1;
+	}
+	fd = open(TARGET, O_WRONLY | O_CREAT, 0644);
+	if (fd < 0) {
+		perror("create target");
+		goto out;
+	}
+	close(fd);
+
+	if (test_loop() < 0 || test_chain() < 0)
+		goto out;
+
+	printf("symlink loop test passed\n");
+	ret = 0;
+out:
+	unlink(TARGET);
+	rmdir(TEST_DIR);
+	return ret;
+}
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <dirent.h>
+#include <errno.h>
+#include <fcntl.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int read_file(const char *path, char *buf, size_t size)
+{
+	ssize_t len;
+	int fd;
+
+	fd = open(path, O_RDONLY);
+	CHECK(fd >= 0, path);
+	len = read(fd, buf, size - 1);
+	close(fd);
+	CHECK(len > 0, path);
+	buf[len] = '\0';
+	return 0;
+}
+
+static int test_net_class(void)
+{
+	char buf[64];
+	struct dirent *entry;
+	int found_lo = 0;
+	DIR *dir;
+
+	dir = opendir("/sys/class/net");
+	CHECK(dir != NULL, "opendir /sys/class/net");
+	while ((entry = readdir(dir)) != NULL) {
+		if (strcmp(entry->d_name, "lo") == 0)
+			found_lo = 1;
+	}
+	closedir(dir);
+	CHECK(found_lo, "find lo");
+
+	if (read_file("/sys/class/net/lo/ifindex", buf, sizeof(buf)) < 0)
+		return -1;
+	CHECK(atoi(buf) > 0, "lo ifindex");
+
+	// The loopback interface has no MAC address
+	if (read_file("/sys/class/net/lo/address", buf, sizeof(buf)) < 0)
+		return -1;
+	CHECK(strcmp(buf, "00:00:00:00:00:00\n") == 0, "lo address");
+	return 0;
+}
+
+static int test_devices(void)
+{
+	char path[512], buf[64];
+	unsigned int major_id, minor_id;
+	struct dirent *entry;
+	int nr_devices = 0;
+	DIR *dir;
+
+	if (read_file("/sys/devices/null/dev", buf, sizeof(buf)) < 0)
+		return -1;
+	CHECK(strcmp(buf, "1:3\n") == 0, "null dev");
+	if (read_file("/sys/devices/zero/dev", buf, sizeof(buf)) < 0)
+		return -1;
+	CHECK(strcmp(buf, "1:5\n") == 0, "zero dev");
+
+	// Each device has a `dev` attribute
+	dir = opendir("/sys/devices");
+	CHECK(dir != NULL, "opendir /sys/devices");
+	while ((entry = readdir(dir)) != NULL) {
+		if (entry->d_name[0] == '.')
+			continue;
+		snprintf(path, sizeof(path), "/sys/devices/%s/dev",
+			 entry->d_name);
+		if (read_file(path, buf, sizeof(buf)) < 0) {
+			closedir(dir);
+			return -1;
+		}
+		CHECK(sscanf(buf, "%u:%u", &major_id, &minor_id) == 2, path);
+		nr_devices++;
+	}
+	closedir(dir);
+	CHECK(nr_devices >= 2, "list devices");
+	return 0;
+}
+
+int main(void)
+{
+	if (test_net_class() < 0 || test_devices() < 0)
+		return 1;
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#include <fcntl.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <sys/stat.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#define TEST_FILE "/tmp/test_umask.txt"
+
+int main(void)
+{
+	struct stat st;
+
+	umask(022);
+	if (umask(022) != 022) {
+		fprintf(stderr, "umask does not return the previous mask\n");
+		exit(EXIT_FAILURE);
+	}
+
+	int fd = open(TEST_FILE, O_CREAT | O_WRONLY | O_TRUNC, 0666);
+	if (fd < 0) {
+		perror("open");
+		exit(EXIT_FAILURE);
+	}
+	close(fd);
+
+	if (stat(TEST_FILE, &st) < 0) {
+		perror("stat");
+		exit(EXIT_FAILURE);
+	}
+	unlink(TEST_FILE);
+	if ((st.st_mode & 0777) != 0644) {
+		fprintf(stderr, "unexpected file mode: %o\n", st.st_mode & 0777);
+		exit(EXIT_FAILURE);
+	}
+
+	// Only the permission bits are kept
+	umask(07777);
+	if (umask(022) != 0777) {
+		fprintf(stderr, "umask is not masked with 0777\n");
+		exit(EXIT_FAILURE);
+	}
+
+	// The child inherits the umask, but its changes are invisible to the parent
+	pid_t pid = fork();
+	if (pid < 0) {
+		perror("fork");
+		exit(EXIT_FAILURE);
+	}
+	if (pid == 0) {
+		if (umask(077) != 022) {
+			exit(EXIT_FAILURE);
+		}
+		exit(EXIT_SUCCESS);
+	}
+
+	int status;
+	if (waitpid(pid, &status, 0) < 0 || !WIFEXITED(status) ||
+	    WEXITSTATUS(status) != 0) {
+		fprintf(stderr, "the child does not inherit the umask\n");
+		exit(EXIT_FAILURE);
+	}
+	if (umask(022) != 022) {
+		fprintf(stderr, "the umask of the parent is changed\n");
+		exit(EXIT_FAILURE);
+	}
+
+	printf("umask test passed\n");
+	return 0;
+}
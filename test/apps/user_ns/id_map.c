@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <sched.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/mount.h>
+#include <sys/prctl.h>
+#include <sys/stat.h>
+#include <sys/syscall.h>
+#include <sys/wait.h>
+#include <unistd.h>
+#include <linux/capability.h>
+
+#include "check.h"
+
+#define OVERFLOW_ID 65534
+#define TEST_UID 1000
+#define SECRET_FILE "/tmp/user_ns_secret"
+
+static int sync_pipe[2];
+static char child_stack[64 * 1024];
+
+static int child_main(void *arg)
+{
+	char c;
+
+	(void)arg;
+
+	// No IDs are mapped before the maps are written.
+	CHECK(getuid() == OVERFLOW_ID && getgid() == OVERFLOW_ID,
+	      "unmapped IDs");
+
+	CHECK(read(sync_pipe[0], &c, 1) == 1, "wait for the maps");
+
+	CHECK(getuid() == 0 && geteuid() == 0, "mapped uid");
+	CHECK(getgid() == 0 && getegid() == 0, "mapped gid");
+	return 0;
+}
+
+static int write_map(pid_t pid, const char *name, const char *content);
+
+static int has_cap(int cap)
+{
+	struct __user_cap_header_struct hdr = {
+		.version = _LINUX_CAPABILITY_VERSION_3,
+	};
+	struct __user_cap_data_struct data[2];
+
+	if (syscall(SYS_capget, &hdr, data) < 0)
+		return 0;
+	return (data[cap / 32].effective & (1U << (cap % 32))) != 0;
+}
+
+// Runs in a namespace whose root user is mapped to `TEST_UID`.
+static int mapped_child_main(void *arg)
+{
+	struct stat st;
+	char c;
+
+	(void)arg;
+
+	CHECK(read(sync_pipe[0], &c, 1) == 1, "wait for the maps");
+	CHECK(getuid() == 0 && geteuid() == 0, "mapped uid");
+
+	// The IDs are translated through the namespace.
+	CHECK(setuid(0) == 0, "setuid to the mapped root");
+	CHECK(setresuid(-1, 0, -1) == 0, "setresuid to the mapped root");
+	CHECK_ERROR(setuid(1), EINVAL);
+	CHECK_ERROR(setreuid(-1, TEST_UID), EINVAL);
+	CHECK(stat("/", &st) == 0, "stat");
+	CHECK(st.st_uid == OVERFLOW_ID, "unmapped owner");
+
+	// The capabilities only take effect inside the namespace.
+	CHECK(has_cap(CAP_SYS_ADMIN) && has_cap(CAP_DAC_OVERRIDE),
+	      "full capabilities");
+	CHECK_ERROR(mount("none", "/tmp", "tmpfs", 0, NULL), EPERM);
+	CHECK_ERROR(open(SECRET_FILE, O_RDONLY), EACCES);
+	return 0;
+}
+
+// Creates a namespace as an unprivileged user, which maps the namespace root to itself.
+static int test_unprivileged_creator(void)
+{
+	char buf[32];
+	pid_t pid;
+	int status;
+
+	CHECK(setuid(TEST_UID) == 0, "setuid");
+	// Linux hides the `/proc` files of the process after `setuid` unless it is dumpable.
+	prctl(PR_SET_DUMPABLE, 1);
+	pid = clone(mapped_child_main, child_stack + sizeof(child_stack),
+		    CLONE_NEWUSER | SIGCHLD, NULL);
+	CHECK(pid > 0, "clone");
+
+	// Only the own ID can be mapped.
+	CHECK_ERROR(write_map(pid, "uid_map", "0 0 1\n"), EPERM);
+	snprintf(buf, sizeof(buf), "0 %d 1\n", TEST_UID);
+	CHECK(write_map(pid, "uid_map", buf) == 0, "write uid_map");
+
+	CHECK(write(sync_pipe[1], "x", 1) == 1, "wake up the child");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0, "child status");
+	return 0;
+}
+
+static int write_map(pid_t pid, const char *name, const char *content)
+{
+	char path[64];
+	ssize_t len;
+	int fd;
+
+	snprintf(path, sizeof(path), "/proc/%d/%s", pid, name);
+	fd = open(path, O_WRONLY);
+	if (fd < 0)
+		return -1;
+	len = write(fd, content, strlen(content));
+	close(fd);
+	return len < 0 ? -1 : 0;
+}
+
+static int read_map(pid_t pid, const char *name, char *buf, size_t size)
+{
+	char path[64];
+	ssize_t len;
+	int fd;
+
+	snprintf(path, sizeof(path), "/proc/%d/%s", pid, name);
+	fd = open(path, O_RDONLY);
+	CHECK(fd >= 0, path);
+	len = read(fd, buf, size - 1);
+	CHECK(len >= 0, path);
+	buf[len] = '\0';
+	close(fd);
+	return 0;
+}
+
+int main(void)
+{
+	char buf[256];
+	pid_t pid;
+	int fd, status;
+
+	// The maps of the initial namespace cannot be changed.
+	CHECK(read_map(getpid(), "uid_map", buf, sizeof(buf)) == 0, "read map");
+	CHECK(strcmp(buf, "         0          0 4294967295\n") == 0,
+	      "initial uid_map");
+	CHECK_ERROR(write_map(getpid(), "uid_map", "0 0 1\n"), EPERM);
+
+	CHECK(pipe(sync_pipe) == 0, "pipe");
+	pid = clone(child_main, child_stack + sizeof(child_stack),
+		    CLONE_NEWUSER | SIGCHLD, NULL);
+	CHECK(pid > 0, "clone");
+
+	CHECK(read_map(pid, "uid_map", buf, sizeof(buf)) == 0, "read map");
+	CHECK(buf[0] == '\0', "empty uid_map");
+
+	// The ranges must not overlap.
+	CHECK_ERROR(write_map(pid, "uid_map", "0 0 10\n5 100 1\n"), EINVAL);
+	CHECK_ERROR(write_map(pid, "uid_map", "0 0 10\n100 5 1\n"), EINVAL);
+	CHECK_ERROR(write_map(pid, "uid_map", "0 0 0\n"), EINVAL);
+	CHECK_ERROR(write_map(pid, "uid_map", "0 1 4294967295\n"), EINVAL);
+
+	CHECK(write_map(pid, "uid_map", "0 0 1\n") == 0, "write uid_map");
+	CHECK(write_map(pid, "gid_map", "0 0 1\n") == 0, "write gid_map");
+
+	// A map can only be written once.
+	CHECK_ERROR(write_map(pid, "uid_map", "1 1 1\n"), EPERM);
+
+	CHECK(read_map(pid, "uid_map", buf, sizeof(buf)) == 0, "read map");
+	CHECK(strcmp(buf, "         0          0          1\n") == 0,
+	      "written uid_map");
+
+	CHECK(write(sync_pipe[1], "x", 1) == 1, "wake up the child");
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0, "child status");
+
+	fd = open(SECRET_FILE, O_WRONLY | O_CREAT | O_TRUNC, 0600);
+	CHECK(fd >= 0, "create the secret file");
+	close(fd);
+	pid = fork();
+	CHECK(pid >= 0, "fork");
+	if (pid == 0)
+		_exit(test_unprivileged_creator() == 0 ? 0 : 1);
+	CHECK(waitpid(pid, &status, 0) == pid, "waitpid");
+	unlink(SECRET_FILE);
+	CHECK(WIFEXITED(status) && WEXITSTATUS(status) == 0,
+	      "unprivileged creator");
+
+	printf("Test passed\n");
+	return 0;
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MPL-2.0
+
+#define _GNU_SOURCE
+
+#include <errno.h>
+#include <fcntl.h>
+#include <sched.h>
+#include <signal.h>
+#include <stdio.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#include "check.h"
+
+static int sync_pipe[2];
+static char child_stack[64 * 1024];
+
+static int child_main(void *arg)
+{
+	char c;
+
+	(void)arg;
+
+	// Keep the namespace alive until the parent joins it.
+	return read(sync_pipe[0], &c, 1) == 1 ? 0 : -1;
+}
+
+static int read_ns_link(const char *path, char *buf, size_t size)
+{
+	unsigned long ino;
+	ssize_t len;
+
+	len = readlink(path, buf, size - 1);
+	if (len < 0)
+		return -1;
+	buf[len] = '\0';
+	return sscanf(buf, "user:[%lu]", &ino) == 1 ? 0 : -1;
+}
+
+int main(void)
+{
+	char self_link[64], child_link[64], path[64];
+	int child_fd, self_fd, status;
+	pid_t pid;
+
+	CHECK(read_ns_link("/proc/self/ns/user", self_link,
+			   sizeof(self_link)) == 0,
+	      "readlink /proc/self/ns/user");
+
+	CHECK(pipe(sync_pipe) == 0, "pipe");
+	pid = clone(child_main, child_stack + sizeof(child_stack),
+		    CLONE_NEWUSER | SIGCHLD, NULL);
+	CHECK(pid > 0, "clone");
+
+	// The child is in a different namespace.
+	snprintf(path, sizeof(path), "/proc/%d/ns/user", pid);
+	CHECK(read_ns_link(path, child_link, sizeof(child_link)) == 0,
+	      "readlink /proc/[pid]/ns/user");
+	CHECK(strcmp(self_link, child_link) != 0, "different namespaces");
+
+	// The namespace files can be opened but not read.
+	child_fd = open(path, O_RDONLY);
+	CHECK(child_fd >= 0, "open the namespace of the child");
+	self_fd = open("/proc/self/ns/user", O_RDONLY);
+	CHECK(self_fd >= 0, "open the namespace of the process");
+	CHECK_ERROR(read(child_fd, path, sizeof(path)), EINVAL);
+
+	// The namespace type must match, and the file must be a namespace file.
+	CHECK_ERROR(setns(child_fd, CLONE_NEWNET), EINVAL);
+	CHECK_ERROR(setns(sync_pipe[0], 0), EINVAL);
+	CHECK_ERROR(setns(self_fd, CLONE_NEWUSER), EINVAL);
+
+	// Join the namespace of the child.
+	CHECK(setns(child_fd, CLONE_NEWUSER) == 0, "setns");
+	CHECK(read_ns_link("/proc/self/ns/user", self_link,
+			   sizeof(self_link)) == 0,
+	      "readlink /proc/self/ns/user after setns");
+	CHECK(strcmp(self_link, child_link) == 0, "join the namespace");
+
+	CHECK(close(child_fd) == 0 && close(self_fd) == 0, "close");
+	CHECK(write(sync_pipe[1], "x", 1) == 1, "resume the child");
+	CHECK(waitpid(pid, &status, 0) == pid && WIFEXITED(status) &&
+		      WEXITSTATUS(status) == 0,
+	      "wait for the child");
+
+	printf("Test passed\n");
+	return 0;
+}